@@ -0,0 +1,78 @@
+//! Criterion benchmarks for the hot paths: evaluation, move ordering,
+//! capture execution and fixed-depth search on the `gomoku-cli bench` positions.
+//!
+//! ```text
+//! cargo bench --bench engine
+//! cargo bench --bench engine -- search   # one group
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use gomoku::bench::BENCH_POSITIONS;
+use gomoku::eval::evaluate;
+use gomoku::rules::{execute_captures_fast, undo_captures, RuleSet};
+use gomoku::search::Searcher;
+use gomoku::testsuite::parse_position;
+use gomoku::{Board, Pos, Stone};
+
+fn positions() -> Vec<(Board, Stone)> {
+    BENCH_POSITIONS.iter().map(|text| parse_position(text).unwrap()).collect()
+}
+
+fn bench_evaluate(c: &mut Criterion) {
+    let positions = positions();
+    c.bench_function("evaluate", |b| {
+        b.iter(|| {
+            positions.iter().map(|(board, color)| evaluate(RuleSet::NINUKI, black_box(board), *color)).sum::<i32>()
+        })
+    });
+}
+
+fn bench_move_ordering(c: &mut Criterion) {
+    let positions = positions();
+    let searcher = Searcher::with_threads(1, 1);
+    c.bench_function("ordered_moves", |b| {
+        b.iter(|| {
+            positions
+                .iter()
+                .map(|(board, color)| searcher.ordered_moves(black_box(board), *color).len())
+                .sum::<usize>()
+        })
+    });
+}
+
+fn bench_captures(c: &mut Criterion) {
+    // Black at K10 captures in three directions: W pairs at L10-M10, K11-K12, L11-M12
+    let mut board = Board::new();
+    for pos in [Pos::new(9, 10), Pos::new(9, 11), Pos::new(10, 9), Pos::new(11, 9), Pos::new(10, 10), Pos::new(11, 11)] {
+        board.place_stone(pos, Stone::White);
+    }
+    for pos in [Pos::new(9, 12), Pos::new(12, 9), Pos::new(12, 12)] {
+        board.place_stone(pos, Stone::Black);
+    }
+    let mov = Pos::new(9, 9);
+
+    c.bench_function("execute_captures_fast", |b| {
+        b.iter(|| {
+            board.place_stone(mov, Stone::Black);
+            let info = execute_captures_fast(RuleSet::NINUKI, &mut board, black_box(mov), Stone::Black);
+            assert_eq!(info.pairs, 3);
+            undo_captures(&mut board, Stone::Black, &info);
+            board.remove_stone(mov);
+        })
+    });
+}
+
+fn bench_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("search");
+    group.sample_size(10);
+    for (i, (board, color)) in positions().into_iter().enumerate() {
+        group.bench_with_input(BenchmarkId::new("depth_4", i + 1), &board, |b, board| {
+            b.iter(|| Searcher::with_threads(16, 1).search(board, color, 4).nodes)
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_evaluate, bench_move_ordering, bench_captures, bench_search);
+criterion_main!(benches);
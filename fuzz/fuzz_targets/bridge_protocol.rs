@@ -0,0 +1,21 @@
+//! Server messages of the online play bridge
+//!
+//! Each line that parses as a `ServerMessage` is handed to one session in
+//! order: bad games and moves must be refused with an error, not a panic.
+//! The engine searches one ply so inputs that start games stay fast.
+
+#![no_main]
+
+use gomoku::bridge::{BridgeConfig, BridgeSession, ServerMessage};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else { return };
+    let config = BridgeConfig { tt_size_mb: 1, max_depth: 1, time_limit_ms: 1, lag_margin_ms: 0, ..BridgeConfig::default() };
+    let mut session = BridgeSession::new(config);
+    for line in text.lines() {
+        if let Ok(message) = serde_json::from_str::<ServerMessage>(line) {
+            let _ = session.handle(message);
+        }
+    }
+});
@@ -0,0 +1,20 @@
+//! The rule conformance line protocol, both sides
+//!
+//! Query lines go through `serve` with the crate's own rules, which must
+//! answer every line without panicking, even for positions no game
+//! reaches; answer lines go through `parse_outcome`.
+
+#![no_main]
+
+use gomoku::conformance::protocol::parse_outcome;
+use gomoku::conformance::{serve, ReferenceRules};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else { return };
+    let mut answers = Vec::new();
+    serve(&mut ReferenceRules, text.as_bytes(), &mut answers).expect("writing to a Vec");
+    for line in text.lines() {
+        let _ = parse_outcome(line);
+    }
+});
@@ -0,0 +1,21 @@
+//! Position strings and puzzle suites of the test suite format
+//!
+//! `parse_position` and `parse_suite` must not panic, and a parsed
+//! position must read back the same after `format_position`.
+
+#![no_main]
+
+use gomoku::testsuite::{format_position, parse_position, parse_suite};
+use gomoku::Stone;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else { return };
+    let _ = parse_suite(text);
+    let Ok((board, to_move)) = parse_position(text) else { return };
+    let (again, again_to_move) = parse_position(&format_position(&board, to_move)).expect("formatted position parses");
+    assert_eq!((again.bitboards(), again_to_move), (board.bitboards(), to_move));
+    for color in [Stone::Black, Stone::White] {
+        assert_eq!(again.captures(color), board.captures(color));
+    }
+});
@@ -0,0 +1,18 @@
+//! `GameRecord::parse` on arbitrary text: SGF and move lists
+//!
+//! Parsing must not panic, a parsed record must survive being written out
+//! in either format and read back, and replaying it must not panic.
+
+#![no_main]
+
+use gomoku::record::GameRecord;
+use gomoku::rules::RuleSet;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else { return };
+    let Ok(record) = GameRecord::parse(text) else { return };
+    assert_eq!(GameRecord::parse(&record.to_sgf()).as_ref(), Ok(&record));
+    assert_eq!(GameRecord::parse(&record.to_move_list()).as_ref(), Ok(&record));
+    let _ = record.boards(RuleSet::NINUKI);
+});
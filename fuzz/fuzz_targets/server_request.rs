@@ -0,0 +1,30 @@
+//! HTTP requests to the engine server
+//!
+//! The input is read as a stream of requests and served one after another
+//! by a single server, so a game created early can be played, loaded and
+//! resigned later on. Bad request lines, headers and bodies must get an
+//! error response, not a panic. Searches are capped at one ply unless a
+//! request asks for more, which `POST /games` may.
+
+#![no_main]
+
+use gomoku::server::{serve_request, Server, ServerConfig};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let config = ServerConfig {
+        tt_size_mb: 1,
+        max_depth: 1,
+        time_limit_ms: 1,
+        threads: 1,
+        pool_size: 1,
+        ..ServerConfig::default()
+    };
+    let server = Server::new(config);
+    let mut input = data;
+    while !input.is_empty() {
+        if serve_request(&server, &mut input, std::io::sink()).is_err() {
+            break;
+        }
+    }
+});
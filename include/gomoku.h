@@ -0,0 +1,134 @@
+#ifndef GOMOKU_H
+#define GOMOKU_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// Number of cells expected by `gomoku_engine_set_position` (19 × 19).
+//
+// Written as a literal so the generated header is self-contained.
+#define GOMOKU_BOARD_CELLS 361
+
+// Cell / color value: empty intersection
+#define GOMOKU_EMPTY 0
+
+// Cell / color value: Black
+#define GOMOKU_BLACK 1
+
+// Cell / color value: White
+#define GOMOKU_WHITE 2
+
+// Success
+#define GOMOKU_OK 0
+
+// Search finished but there is no legal move
+#define GOMOKU_NO_MOVE 1
+
+// A required pointer argument was null
+#define GOMOKU_ERR_NULL -1
+
+// An argument was out of range (cell value, color, array length)
+#define GOMOKU_ERR_INVALID_ARG -2
+
+// The engine panicked; the handle should be freed
+#define GOMOKU_ERR_INTERNAL -3
+
+// Opaque engine handle: an AI engine plus the position it searches.
+typedef struct GomokuEngine GomokuEngine;
+
+// Best move reported by `gomoku_engine_best_move`.
+typedef struct GomokuMove {
+  // Row, 0-based from the top
+  uint8_t row;
+  // Column, 0-based from the left
+  uint8_t col;
+  // Alpha-beta depth reached (0 for book/VCF/defense moves)
+  int8_t depth;
+  // Evaluation from the mover's perspective
+  int32_t score;
+  // Nodes searched
+  uint64_t nodes;
+  // Thinking time in milliseconds
+  uint64_t time_ms;
+  // The engine would rather resign than play this move
+  // (see `gomoku_engine_set_resign_threshold`)
+  bool resign;
+} GomokuMove;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Create an engine. Free it with `gomoku_engine_free`.
+//
+// Uses a 64 MB transposition table. Returns null on failure.
+struct GomokuEngine *gomoku_engine_new(int8_t max_depth, uint64_t time_limit_ms);
+
+// Destroy an engine created by `gomoku_engine_new`. Null is ignored.
+//
+// # Safety
+//
+// `engine` must be null or a pointer returned by `gomoku_engine_new` that
+// has not been freed yet.
+void gomoku_engine_free(struct GomokuEngine *engine);
+
+// Replace the engine's position.
+//
+// `cells` holds `GOMOKU_BOARD_CELLS` values in row-major order, each
+// `GOMOKU_EMPTY`, `GOMOKU_BLACK` or `GOMOKU_WHITE`. Capture counts are in
+// pairs (0-5). On error the previous position is kept.
+//
+// # Safety
+//
+// `engine` must be a live handle and `cells` must point to `len` readable bytes.
+int32_t gomoku_engine_set_position(struct GomokuEngine *engine,
+                                   const uint8_t *cells,
+                                   size_t len,
+                                   uint8_t black_captures,
+                                   uint8_t white_captures);
+
+// Search the best move for `color` in the current position.
+//
+// Writes the result to `out` and returns `GOMOKU_OK`, or returns
+// `GOMOKU_NO_MOVE` (leaving `out` untouched) if there is no legal move.
+//
+// # Safety
+//
+// `engine` must be a live handle and `out` must point to a writable `GomokuMove`.
+int32_t gomoku_engine_best_move(struct GomokuEngine *engine, uint8_t color, struct GomokuMove *out);
+
+// Ask whether `color` accepts a draw offer in the current position.
+//
+// Returns 1 to accept, 0 to decline, or a negative error code.
+//
+// # Safety
+//
+// `engine` must be null or a live handle.
+int32_t gomoku_engine_should_accept_draw(const struct GomokuEngine *engine, uint8_t color);
+
+// Set the auto-resign threshold: `GomokuMove.resign` is set once the search
+// score drops to `threshold` or below. Pass `enabled = false` to never resign
+// (the default).
+//
+// # Safety
+//
+// `engine` must be null or a live handle.
+void gomoku_engine_set_resign_threshold(struct GomokuEngine *engine,
+                                        bool enabled,
+                                        int32_t threshold);
+
+// Clear the transposition table and history (call between games).
+//
+// # Safety
+//
+// `engine` must be null or a live handle.
+void gomoku_engine_clear_cache(struct GomokuEngine *engine);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* GOMOKU_H */
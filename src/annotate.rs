@@ -0,0 +1,267 @@
+//! Move-quality annotation of played games
+//!
+//! [`annotate_game`] searches every position of a game with a fixed node
+//! budget and compares each played move with the engine's best move. The
+//! score delta is measured in win probability (see
+//! [`AIEngine::win_probability`]), so a move that only shortens a
+//! long win or loss is not flagged, and is classified as an inaccuracy,
+//! mistake or blunder by [`MoveQuality::from_loss`]. `gomoku-cli annotate
+//! <file>` prints the annotation of a saved game.
+//!
+//! ```
+//! use gomoku::annotate::{annotate_game_with_budget, MoveQuality};
+//! use gomoku::rules::RuleSet;
+//! use gomoku::{Pos, Stone};
+//!
+//! let moves = [Pos::new(9, 9), Pos::new(9, 10), Pos::new(10, 10)];
+//! let annotation = annotate_game_with_budget(RuleSet::NINUKI, &moves, 2_000).unwrap();
+//! assert_eq!(annotation.moves[2].color, Stone::Black);
+//! println!("{}", annotation); // move list with ?! / ? / ?? marks, then totals
+//! # let _ = MoveQuality::Blunder;
+//! ```
+
+use std::fmt;
+
+use crate::board::{Pos, Stone};
+use crate::engine::{pos_to_notation, AIEngine};
+use crate::eval::PatternScore;
+use crate::record::GameRecord;
+use crate::rules::{check_winner, RuleSet};
+use crate::search::{Searcher, MAX_SEARCH_DEPTH};
+
+/// Nodes searched per position by [`annotate_game`]
+pub const DEFAULT_ANNOTATE_NODES: u64 = 50_000;
+
+/// Win probability lost by an inaccuracy, mistake and blunder (at least)
+pub const INACCURACY_LOSS: f64 = 0.07;
+pub const MISTAKE_LOSS: f64 = 0.15;
+pub const BLUNDER_LOSS: f64 = 0.30;
+
+/// TT size for annotation searches
+const ANNOTATE_TT_MB: usize = 16;
+
+/// How a played move compares with the engine's best move
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MoveQuality {
+    /// The engine's own choice
+    Best,
+    /// Loses less than [`INACCURACY_LOSS`]
+    Good,
+    Inaccuracy,
+    Mistake,
+    Blunder,
+}
+
+impl MoveQuality {
+    /// Classify a move that is not the engine's choice by the win
+    /// probability it gives away.
+    #[must_use]
+    pub fn from_loss(loss: f64) -> Self {
+        if loss >= BLUNDER_LOSS {
+            Self::Blunder
+        } else if loss >= MISTAKE_LOSS {
+            Self::Mistake
+        } else if loss >= INACCURACY_LOSS {
+            Self::Inaccuracy
+        } else {
+            Self::Good
+        }
+    }
+
+    /// Display name
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Best => "Best",
+            Self::Good => "Good",
+            Self::Inaccuracy => "Inaccuracy",
+            Self::Mistake => "Mistake",
+            Self::Blunder => "Blunder",
+        }
+    }
+
+    /// Annotation symbol (`?!`, `?`, `??`; empty for good moves)
+    #[must_use]
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Best | Self::Good => "",
+            Self::Inaccuracy => "?!",
+            Self::Mistake => "?",
+            Self::Blunder => "??",
+        }
+    }
+}
+
+/// One annotated move
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveAnnotation {
+    /// The move played
+    pub mov: Pos,
+    pub color: Stone,
+    /// The engine's choice in the position before the move
+    pub best_move: Option<Pos>,
+    /// Score of the engine's choice for the mover
+    pub best_score: i32,
+    /// Score of the position after the played move, for the mover
+    pub played_score: i32,
+    pub quality: MoveQuality,
+}
+
+impl MoveAnnotation {
+    /// Win probability the played move gives away (0 for the best move).
+    #[must_use]
+    pub fn loss(&self) -> f64 {
+        if self.best_move == Some(self.mov) {
+            return 0.0;
+        }
+        (AIEngine::win_probability(self.best_score) - AIEngine::win_probability(self.played_score)).max(0.0)
+    }
+}
+
+/// Results of [`annotate_game`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameAnnotation {
+    pub moves: Vec<MoveAnnotation>,
+}
+
+impl GameAnnotation {
+    /// Number of `color`'s moves of the given quality.
+    #[must_use]
+    pub fn count(&self, color: Stone, quality: MoveQuality) -> usize {
+        self.moves.iter().filter(|m| m.color == color && m.quality == quality).count()
+    }
+}
+
+impl fmt::Display for GameAnnotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, m) in self.moves.iter().enumerate() {
+            let side = if m.color == Stone::Black { 'B' } else { 'W' };
+            write!(f, "{:>3}. {} {}", i + 1, side, pos_to_notation(m.mov))?;
+            if m.quality > MoveQuality::Good {
+                let best = m.best_move.map_or_else(|| "none".to_string(), pos_to_notation);
+                let pad = 4 - pos_to_notation(m.mov).len();
+                write!(f, "{:pad$} {:<2} {} (-{:.0}%, best {})", "", m.quality.symbol(), m.quality.name(), m.loss() * 100.0, best)?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f, "===========================")?;
+        for (color, name) in [(Stone::Black, "Black"), (Stone::White, "White")] {
+            writeln!(
+                f,
+                "{}: {} inaccuracies, {} mistakes, {} blunders",
+                name,
+                self.count(color, MoveQuality::Inaccuracy),
+                self.count(color, MoveQuality::Mistake),
+                self.count(color, MoveQuality::Blunder)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Annotate `moves` (Black first), played by `rules`, with
+/// [`DEFAULT_ANNOTATE_NODES`] per position.
+///
+/// # Errors
+///
+/// Returns an error naming the first illegal move.
+pub fn annotate_game(rules: RuleSet, moves: &[Pos]) -> Result<GameAnnotation, String> {
+    annotate_game_with_budget(rules, moves, DEFAULT_ANNOTATE_NODES)
+}
+
+/// Annotate `moves`, played by `rules`, searching `nodes` per position.
+/// Single-threaded and node-limited, so the result is reproducible.
+///
+/// # Errors
+///
+/// Returns an error naming the first illegal move.
+pub fn annotate_game_with_budget(rules: RuleSet, moves: &[Pos], nodes: u64) -> Result<GameAnnotation, String> {
+    let mut searcher = Searcher::with_threads(ANNOTATE_TT_MB, 1);
+    searcher.set_rules(rules);
+    let boards = GameRecord::new(moves.to_vec()).boards(rules)?;
+
+    // Best move and score for the side to move in every position
+    let searched: Vec<(Option<Pos>, i32)> = boards
+        .iter()
+        .enumerate()
+        .map(|(i, board)| {
+            let to_move = GameRecord::color_of(i);
+            match check_winner(rules, board) {
+                Some(winner) if winner == to_move => (None, PatternScore::FIVE),
+                Some(_) => (None, -PatternScore::FIVE),
+                None => {
+                    let result = searcher.search_nodes(board, to_move, MAX_SEARCH_DEPTH, nodes);
+                    (result.best_move, result.score)
+                }
+            }
+        })
+        .collect();
+
+    let moves = moves
+        .iter()
+        .enumerate()
+        .map(|(i, &mov)| {
+            let (best_move, best_score) = searched[i];
+            let mut annotation = MoveAnnotation {
+                mov,
+                color: GameRecord::color_of(i),
+                best_move,
+                best_score,
+                // The next position is scored for the opponent
+                played_score: -searched[i + 1].1,
+                quality: MoveQuality::Best,
+            };
+            if best_move != Some(mov) {
+                annotation.quality = MoveQuality::from_loss(annotation.loss());
+            }
+            annotation
+        })
+        .collect();
+    Ok(GameAnnotation { moves })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quality_thresholds() {
+        assert_eq!(MoveQuality::from_loss(0.0), MoveQuality::Good);
+        assert_eq!(MoveQuality::from_loss(INACCURACY_LOSS), MoveQuality::Inaccuracy);
+        assert_eq!(MoveQuality::from_loss(MISTAKE_LOSS), MoveQuality::Mistake);
+        assert_eq!(MoveQuality::from_loss(0.9), MoveQuality::Blunder);
+        assert!(MoveQuality::Blunder > MoveQuality::Mistake);
+    }
+
+    #[test]
+    fn test_missing_the_win_is_a_blunder() {
+        // Black has a closed four on row 10 (F-J, White on E10) that White
+        // fails to block, then plays elsewhere instead of K10
+        let moves: Vec<Pos> = [
+            (9, 5), (9, 4), (9, 6), (0, 2), (9, 7), (0, 4), (9, 8), (18, 18),
+            (3, 15),
+        ]
+        .iter()
+        .map(|&(r, c)| Pos::new(r, c))
+        .collect();
+        let annotation = annotate_game_with_budget(RuleSet::NINUKI, &moves, 5_000).unwrap();
+        let last = annotation.moves.last().unwrap();
+        assert_eq!(last.color, Stone::Black);
+        assert_eq!(last.best_move, Some(Pos::new(9, 9)));
+        assert_eq!(last.quality, MoveQuality::Blunder);
+        assert_eq!(annotation.count(Stone::Black, MoveQuality::Blunder), 1);
+        assert!(annotation.to_string().contains("??"));
+
+        // Taking the win is the best move
+        let winning_line: Vec<Pos> = moves[..8].iter().copied().chain([Pos::new(9, 9)]).collect();
+        let won = annotate_game_with_budget(RuleSet::NINUKI, &winning_line, 5_000).unwrap();
+        let winning = won.moves.last().unwrap();
+        assert_eq!(winning.played_score, PatternScore::FIVE);
+        assert_eq!(winning.quality, MoveQuality::Best);
+    }
+
+    #[test]
+    fn test_illegal_game_is_rejected() {
+        assert!(annotate_game_with_budget(RuleSet::NINUKI, &[Pos::new(9, 9), Pos::new(9, 9)], 100).is_err());
+    }
+}
@@ -0,0 +1,433 @@
+//! Games database: an append-only archive of finished games
+//!
+//! Every game is one line of a text file, tab-separated: Black's and
+//! White's names, the result (`1-0`, `0-1`, `1/2-1/2` or `*`), the time
+//! control and the moves in board notation. Lines starting with `#` are
+//! comments. [`GameArchive::open`] reads the file and indexes every
+//! position reached in it by its Zobrist hash, so [`GameArchive::find`]
+//! lists the games that went through a position (transpositions included)
+//! and [`GameArchive::openings`] gathers a player's results by opening:
+//! the "openings I keep losing" report. The file doesn't name the rules;
+//! archived games are Ninuki-renju games and are replayed by
+//! [`RuleSet::NINUKI`].
+//!
+//! The GUI appends its finished games when archiving is on;
+//! `gomoku-cli archive` adds games from other sources (a match runner, the
+//! bridge) and prints the reports.
+//!
+//! ```
+//! use gomoku::archive::{ArchivedGame, GameArchive, GameScore};
+//! use gomoku::record::GameRecord;
+//!
+//! let mut archive = GameArchive::new();
+//! let moves = GameRecord::parse("K10 L11 K11 L10").unwrap().moves;
+//! let game = ArchivedGame::new("me", "engine", GameScore::WhiteWins, "5+3", moves);
+//! archive.append(game).unwrap();
+//!
+//! let report = archive.openings("me", 2, 1);
+//! assert_eq!(report.openings[0].losses, 1);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::board::{Board, Pos, Stone};
+use crate::engine::pos_to_notation;
+use crate::record::GameRecord;
+use crate::rules::RuleSet;
+use crate::search::ZobristTable;
+
+/// Result of an archived game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameScore {
+    BlackWins,
+    WhiteWins,
+    Draw,
+    /// Abandoned or adjourned
+    Unfinished,
+}
+
+impl GameScore {
+    /// PGN-style result: `1-0`, `0-1`, `1/2-1/2` or `*`
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GameScore::BlackWins => "1-0",
+            GameScore::WhiteWins => "0-1",
+            GameScore::Draw => "1/2-1/2",
+            GameScore::Unfinished => "*",
+        }
+    }
+
+    /// Parse [`GameScore::as_str`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for any other text.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        match text {
+            "1-0" => Ok(GameScore::BlackWins),
+            "0-1" => Ok(GameScore::WhiteWins),
+            "1/2-1/2" => Ok(GameScore::Draw),
+            "*" => Ok(GameScore::Unfinished),
+            _ => Err(format!("bad result '{}' (1-0, 0-1, 1/2-1/2 or *)", text)),
+        }
+    }
+
+    /// The result for the side that won, `Stone::Empty` for a draw
+    #[must_use]
+    pub fn from_winner(winner: Stone) -> Self {
+        match winner {
+            Stone::Black => GameScore::BlackWins,
+            Stone::White => GameScore::WhiteWins,
+            Stone::Empty => GameScore::Draw,
+        }
+    }
+
+    /// Points `color` scored: 1 for a win, half for a draw, `None` when
+    /// the game didn't finish
+    #[must_use]
+    pub fn points(self, color: Stone) -> Option<f64> {
+        match self {
+            GameScore::Unfinished => None,
+            GameScore::Draw => Some(0.5),
+            GameScore::BlackWins => Some(if color == Stone::Black { 1.0 } else { 0.0 }),
+            GameScore::WhiteWins => Some(if color == Stone::White { 1.0 } else { 0.0 }),
+        }
+    }
+}
+
+/// One game of the archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedGame {
+    pub black: String,
+    pub white: String,
+    pub result: GameScore,
+    /// Free text, e.g. [`TimeControl::label`](crate::clock::TimeControl::label);
+    /// `-` when there was none
+    pub time_control: String,
+    pub moves: Vec<Pos>,
+}
+
+/// Tabs and line breaks would split the line; names keep the rest
+fn field(text: &str) -> String {
+    let text = text.trim().replace(['\t', '\n', '\r'], " ");
+    if text.is_empty() { "-".to_string() } else { text }
+}
+
+impl ArchivedGame {
+    #[must_use]
+    pub fn new(black: &str, white: &str, result: GameScore, time_control: &str, moves: Vec<Pos>) -> Self {
+        Self { black: field(black), white: field(white), result, time_control: field(time_control), moves }
+    }
+
+    /// The game's line in the archive file (without the line break)
+    #[must_use]
+    pub fn to_line(&self) -> String {
+        let moves = GameRecord::new(self.moves.clone()).to_move_list();
+        format!("{}\t{}\t{}\t{}\t{}", self.black, self.white, self.result.as_str(), self.time_control, moves.trim())
+    }
+
+    /// Parse [`ArchivedGame::to_line`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when a field is missing or malformed.
+    pub fn parse_line(line: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [black, white, result, time_control, moves] = fields[..] else {
+            return Err(format!("expected 5 tab-separated fields, found {}", fields.len()));
+        };
+        let moves = GameRecord::from_move_list(moves)?.moves;
+        Ok(Self::new(black, white, GameScore::parse(result)?, time_control, moves))
+    }
+
+    /// The color `player` had in this game, if they played in it
+    #[must_use]
+    pub fn color_of(&self, player: &str) -> Option<Stone> {
+        if self.black == player {
+            Some(Stone::Black)
+        } else if self.white == player {
+            Some(Stone::White)
+        } else {
+            None
+        }
+    }
+}
+
+/// A game that reached a queried position, and after how many moves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PositionHit {
+    /// Index into [`GameArchive::games`]
+    pub game: usize,
+    pub ply: usize,
+}
+
+/// A player's results from one opening position
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpeningStats {
+    /// The moves of the first game that reached it
+    pub moves: Vec<Pos>,
+    pub games: usize,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl OpeningStats {
+    /// The player's score, 0.0 (lost every game) to 1.0
+    #[must_use]
+    pub fn score(&self) -> f64 {
+        (self.wins as f64 + self.draws as f64 * 0.5) / self.games.max(1) as f64
+    }
+}
+
+/// The openings a player scores below half in, worst first
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpeningReport {
+    pub player: String,
+    /// Moves that make an opening
+    pub plies: usize,
+    pub openings: Vec<OpeningStats>,
+}
+
+impl OpeningReport {
+    /// The report as a Markdown table
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# Openings {} keeps losing\n\n", self.player);
+        if self.openings.is_empty() {
+            let _ = writeln!(out, "No opening of {} moves scores below 50%.", self.plies);
+            return out;
+        }
+        out.push_str("| Opening | Games | Won | Drawn | Lost | Score |\n|---|---|---|---|---|---|\n");
+        for opening in &self.openings {
+            let moves: Vec<String> = opening.moves.iter().map(|&pos| pos_to_notation(pos)).collect();
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} | {:.0}% |",
+                moves.join(" "),
+                opening.games,
+                opening.wins,
+                opening.draws,
+                opening.losses,
+                opening.score() * 100.0
+            );
+        }
+        out
+    }
+}
+
+/// The games of an archive file, indexed by the positions they reached
+pub struct GameArchive {
+    /// File new games are appended to; `None` keeps them in memory
+    path: Option<PathBuf>,
+    games: Vec<ArchivedGame>,
+    /// Position hash -> every (game, ply) that reached it
+    index: HashMap<u64, Vec<PositionHit>>,
+    zobrist: ZobristTable,
+}
+
+impl Default for GameArchive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameArchive {
+    /// An empty archive kept in memory
+    #[must_use]
+    pub fn new() -> Self {
+        Self { path: None, games: Vec::new(), index: HashMap::new(), zobrist: ZobristTable::new() }
+    }
+
+    /// Open the archive at `path`, reading and indexing the games already
+    /// in it; a missing file is an empty archive, created on the first
+    /// [`append`](Self::append).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the file can't be read or a line isn't a
+    /// valid game, naming the line.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        let mut archive = Self { path: Some(path.to_path_buf()), ..Self::new() };
+        for (number, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            ArchivedGame::parse_line(line)
+                .and_then(|game| archive.insert(game))
+                .map_err(|e| format!("{}:{}: {}", path.display(), number + 1, e))?;
+        }
+        Ok(archive)
+    }
+
+    /// File new games are appended to, `None` for an archive in memory
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Every game, oldest first
+    #[must_use]
+    pub fn games(&self) -> &[ArchivedGame] {
+        &self.games
+    }
+
+    /// Add `game`, writing it to the archive file if there is one. Returns
+    /// its index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the moves break the rules or the file can't
+    /// be written; the game isn't added then.
+    pub fn append(&mut self, game: ArchivedGame) -> Result<usize, String> {
+        let line = game.to_line();
+        let boards = GameRecord::new(game.moves.clone()).boards(RuleSet::NINUKI)?;
+        if let Some(path) = &self.path {
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut file| writeln!(file, "{}", line))
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+        }
+        Ok(self.index_game(game, &boards))
+    }
+
+    fn insert(&mut self, game: ArchivedGame) -> Result<usize, String> {
+        let boards = GameRecord::new(game.moves.clone()).boards(RuleSet::NINUKI)?;
+        Ok(self.index_game(game, &boards))
+    }
+
+    fn index_game(&mut self, game: ArchivedGame, boards: &[Board]) -> usize {
+        let id = self.games.len();
+        for (ply, board) in boards.iter().enumerate() {
+            let hash = self.zobrist.hash(board, GameRecord::color_of(ply));
+            self.index.entry(hash).or_default().push(PositionHit { game: id, ply });
+        }
+        self.games.push(game);
+        id
+    }
+
+    /// The games that reached `board` with `to_move` to play, in the order
+    /// they were added
+    #[must_use]
+    pub fn find(&self, board: &Board, to_move: Stone) -> Vec<PositionHit> {
+        let hash = self.zobrist.hash(board, to_move);
+        self.index.get(&hash).cloned().unwrap_or_default()
+    }
+
+    /// `player`'s finished games grouped by the position after `plies`
+    /// moves (so transpositions count together); the openings with at
+    /// least `min_games` games and a score below half, worst first.
+    #[must_use]
+    pub fn openings(&self, player: &str, plies: usize, min_games: usize) -> OpeningReport {
+        let mut by_position: HashMap<u64, OpeningStats> = HashMap::new();
+        let mut order = Vec::new();
+        for game in &self.games {
+            let Some(color) = game.color_of(player) else { continue };
+            let Some(points) = game.result.points(color) else { continue };
+            if game.moves.len() < plies {
+                continue;
+            }
+            let opening = &game.moves[..plies];
+            let Ok(boards) = GameRecord::new(opening.to_vec()).boards(RuleSet::NINUKI) else { continue };
+            let hash = self.zobrist.hash(&boards[plies], GameRecord::color_of(plies));
+            let stats = by_position.entry(hash).or_insert_with(|| {
+                order.push(hash);
+                OpeningStats { moves: opening.to_vec(), games: 0, wins: 0, draws: 0, losses: 0 }
+            });
+            stats.games += 1;
+            match points {
+                p if p > 0.75 => stats.wins += 1,
+                p if p > 0.25 => stats.draws += 1,
+                _ => stats.losses += 1,
+            }
+        }
+        let mut openings: Vec<OpeningStats> = order
+            .into_iter()
+            .filter_map(|hash| by_position.remove(&hash))
+            .filter(|stats| stats.games >= min_games.max(1) && stats.score() < 0.5)
+            .collect();
+        openings.sort_by(|a, b| a.score().total_cmp(&b.score()).then(b.games.cmp(&a.games)));
+        OpeningReport { player: player.to_string(), plies, openings }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(black: &str, white: &str, result: GameScore, moves: &str) -> ArchivedGame {
+        ArchivedGame::new(black, white, result, "-", GameRecord::parse(moves).unwrap().moves)
+    }
+
+    #[test]
+    fn test_lines_round_trip_through_the_file() {
+        let path = std::env::temp_dir().join(format!("gomoku-archive-{}.txt", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut archive = GameArchive::open(&path).unwrap();
+        let first = ArchivedGame::new("Ann\tLee", "engine", GameScore::Draw, "10+5", vec![Pos::new(9, 9)]);
+        assert_eq!(first.black, "Ann Lee");
+        archive.append(first.clone()).unwrap();
+        archive.append(game("engine", "Ann Lee", GameScore::Unfinished, "K10 L11")).unwrap();
+        assert!(archive.append(game("x", "y", GameScore::Draw, "K10 K10")).is_err(), "Illegal games are refused");
+
+        let reopened = GameArchive::open(&path).unwrap();
+        assert_eq!(reopened.games().len(), 2);
+        assert_eq!(reopened.games()[0], first);
+        assert_eq!(reopened.games()[1].result, GameScore::Unfinished);
+        fs::remove_file(&path).unwrap();
+
+        assert!(ArchivedGame::parse_line("a\tb\t2-0\t-\tK10").is_err());
+    }
+
+    #[test]
+    fn test_find_includes_transpositions() {
+        let mut archive = GameArchive::new();
+        archive.append(game("a", "b", GameScore::BlackWins, "K10 L11 K11 L10")).unwrap();
+        archive.append(game("a", "b", GameScore::BlackWins, "K11 L10 K10 L11 M12")).unwrap();
+        archive.append(game("a", "b", GameScore::BlackWins, "K10 L11 M12")).unwrap();
+
+        let boards = GameRecord::parse("K10 L11 K11 L10").unwrap().boards(RuleSet::NINUKI).unwrap();
+        let hits = archive.find(&boards[4], Stone::Black);
+        assert_eq!(hits, vec![PositionHit { game: 0, ply: 4 }, PositionHit { game: 1, ply: 4 }]);
+        assert_eq!(archive.find(&boards[2], Stone::Black).len(), 2);
+        assert!(archive.find(&boards[2], Stone::White).is_empty(), "The side to move is part of the position");
+    }
+
+    #[test]
+    fn test_openings_report_lists_the_losing_ones() {
+        let mut archive = GameArchive::new();
+        for (black, white, result, moves) in [
+            ("me", "bot", GameScore::WhiteWins, "K10 L11 K11"),
+            ("me", "bot", GameScore::WhiteWins, "K10 L11 M12"),
+            ("me", "bot", GameScore::BlackWins, "K10 L11 K12"),
+            ("bot", "me", GameScore::BlackWins, "K10 K11 L10"),
+            ("bot", "me", GameScore::Draw, "K10 K11 J10"),
+            ("me", "bot", GameScore::BlackWins, "K10 J9 K11"),
+            ("me", "bot", GameScore::Unfinished, "K10 J9 K12"),
+            ("x", "y", GameScore::BlackWins, "K10 K11"),
+        ] {
+            archive.append(game(black, white, result, moves)).unwrap();
+        }
+
+        let report = archive.openings("me", 2, 2);
+        assert_eq!(report.openings.len(), 2);
+        assert_eq!(report.openings[0].moves, GameRecord::parse("K10 K11").unwrap().moves);
+        assert_eq!((report.openings[0].games, report.openings[0].draws, report.openings[0].losses), (2, 1, 1));
+        assert!((report.openings[1].score() - 1.0 / 3.0).abs() < 1e-9);
+        assert!(report.to_markdown().contains("| K10 L11 | 3 | 1 | 0 | 2 | 33% |"));
+        assert!(archive.openings("me", 2, 4).to_markdown().contains("No opening"));
+    }
+}
@@ -0,0 +1,142 @@
+//! Fixed-depth benchmark for regression tracking
+//!
+//! [`run_bench`] searches [`BENCH_POSITIONS`] to a fixed depth, one thread
+//! and a fresh [`Searcher`] per position, so the total node count is a
+//! deterministic signature of the search: any change to move ordering,
+//! pruning or evaluation changes it, and a refactor that should not change
+//! behavior must keep it. `gomoku-cli bench [depth]` prints the report.
+//!
+//! ```
+//! use gomoku::bench::run_bench;
+//!
+//! let report = run_bench(2);
+//! assert_eq!(report.nodes(), run_bench(2).nodes());
+//! ```
+
+use std::fmt;
+
+use crate::board::Pos;
+use crate::engine::pos_to_notation;
+use crate::search::Searcher;
+use crate::testsuite::parse_position;
+use crate::time::Instant;
+
+/// Depth used by `gomoku-cli bench` without an argument
+pub const DEFAULT_BENCH_DEPTH: i8 = 6;
+
+/// Opening to early middlegame positions, in the
+/// [`testsuite`](crate::testsuite) position format
+pub const BENCH_POSITIONS: &[&str] = &[
+    "19/19/19/19/19/19/19/19/19/9B9/19/19/19/19/19/19/19/19/19 W 0 0",
+    "19/19/19/19/19/19/19/19/8W1W8/9B9/10B8/19/19/19/19/19/19/19/19 B 0 0",
+    "19/19/19/19/19/19/19/10W8/9W1B7/9BB8/8W2B7/19/19/19/19/19/19/19/19 W 0 0",
+    "19/19/19/19/19/19/19/7W3W7/8BWB8/9BW8/8B1B8/11W7/19/19/19/19/19/19/19 B 0 0",
+    "19/19/19/19/19/19/19/7B1W9/8BW9/7BBBW8/8W10/7W11/19/19/19/19/19/19/19 W 0 0",
+    "19/19/19/19/19/19/12W6/8BW9/9BW8/9BBW7/9BBW7/9W9/19/19/19/19/19/19/19 W 0 1",
+];
+
+/// TT size for each bench search; fixed so results do not depend on config
+const BENCH_TT_MB: usize = 4;
+
+/// One searched position
+#[derive(Debug, Clone)]
+pub struct BenchEntry {
+    pub best_move: Option<Pos>,
+    pub score: i32,
+    pub nodes: u64,
+    pub time_ms: u64,
+}
+
+/// Results of [`run_bench`]
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub depth: i8,
+    pub entries: Vec<BenchEntry>,
+}
+
+impl BenchReport {
+    /// Total nodes over all positions: the bench signature.
+    #[must_use]
+    pub fn nodes(&self) -> u64 {
+        self.entries.iter().map(|e| e.nodes).sum()
+    }
+
+    /// Total search time in milliseconds.
+    #[must_use]
+    pub fn time_ms(&self) -> u64 {
+        self.entries.iter().map(|e| e.time_ms).sum()
+    }
+
+    /// Nodes per second over the whole run.
+    #[must_use]
+    pub fn nps(&self) -> u64 {
+        (self.nodes() * 1000).checked_div(self.time_ms()).unwrap_or(0)
+    }
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, entry) in self.entries.iter().enumerate() {
+            writeln!(
+                f,
+                "Position {}/{}: best {} score {} nodes {} time {}ms",
+                i + 1,
+                self.entries.len(),
+                entry.best_move.map_or_else(|| "none".to_string(), pos_to_notation),
+                entry.score,
+                entry.nodes,
+                entry.time_ms
+            )?;
+        }
+        writeln!(f, "===========================")?;
+        writeln!(f, "Total time (ms) : {}", self.time_ms())?;
+        writeln!(f, "Nodes searched  : {}", self.nodes())?;
+        writeln!(f, "Nodes/second    : {}", self.nps())
+    }
+}
+
+/// Search every bench position to `depth`.
+#[must_use]
+pub fn run_bench(depth: i8) -> BenchReport {
+    let entries = BENCH_POSITIONS
+        .iter()
+        .map(|text| {
+            let (board, color) = parse_position(text).expect("bench position parses");
+            let mut searcher = Searcher::with_threads(BENCH_TT_MB, 1);
+            let start = Instant::now();
+            let result = searcher.search(&board, color, depth);
+            BenchEntry {
+                best_move: result.best_move,
+                score: result.score,
+                nodes: result.nodes,
+                time_ms: start.elapsed().as_millis() as u64,
+            }
+        })
+        .collect();
+    BenchReport { depth, entries }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bench_positions_parse() {
+        for text in BENCH_POSITIONS {
+            let (board, _) = parse_position(text).unwrap();
+            assert!(board.stone_count() > 0);
+        }
+    }
+
+    #[test]
+    fn test_bench_is_deterministic() {
+        let first = run_bench(2);
+        let second = run_bench(2);
+        assert_eq!(first.entries.len(), BENCH_POSITIONS.len());
+        assert_eq!(first.nodes(), second.nodes());
+        for (a, b) in first.entries.iter().zip(&second.entries) {
+            assert_eq!((a.best_move, a.score), (b.best_move, b.score));
+        }
+        assert!(first.to_string().contains(&format!("Nodes searched  : {}", first.nodes())));
+    }
+}
@@ -0,0 +1,78 @@
+//! Online play bridge
+//!
+//! ```text
+//! gomoku-bridge [--addr host:port] [--name NAME] [--token TOKEN] [--games N] [--reconnects N]
+//!               [--lag-ms N] [--tt-mb N] [--depth N] [--time-ms N] [--rules ninuki|renju]
+//!               [--notation SPEC]
+//! ```
+//!
+//! Connects to a server speaking the bridge protocol and plays `--games`
+//! games (default: until stopped), reconnecting up to `--reconnects` times
+//! in a row when the connection drops. `--lag-ms` is kept back from the
+//! clock for network lag; `--time-ms` is the time per move of games
+//! without a clock. `--notation` is the coordinate convention of the moves
+//! in the log and in errors, such as `bottom-left` (see `gomoku::notation`).
+//!
+//! See `gomoku::bridge` for the protocol.
+
+use std::process::ExitCode;
+
+use gomoku::bridge::{run, BridgeConfig};
+use gomoku::logging::{set_sink, LogLevel, StderrSink};
+use gomoku::rules::RuleSet;
+
+const USAGE: &str = "usage: gomoku-bridge [--addr <host:port>] [--name <name>] [--token <token>] [--games <N>] [--reconnects <N>] [--lag-ms <N>] [--tt-mb <N>] [--depth <N>] [--time-ms <N>] [--rules ninuki|renju] [--notation <spec>]";
+
+fn parse_args(args: &[String]) -> Result<BridgeConfig, String> {
+    let mut config = BridgeConfig::default();
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        let value = iter.next().ok_or(format!("missing value for {}", flag))?;
+        let bad = |_| format!("bad value '{}' for {}", value, flag);
+        match flag.as_str() {
+            "--addr" => config.addr = value.clone(),
+            "--name" => config.name = value.clone(),
+            "--token" => config.token = Some(value.clone()),
+            "--games" => config.games = value.parse().map_err(bad)?,
+            "--reconnects" => config.reconnect_attempts = value.parse().map_err(bad)?,
+            "--lag-ms" => config.lag_margin_ms = value.parse().map_err(bad)?,
+            "--tt-mb" => config.tt_size_mb = value.parse().map_err(bad)?,
+            "--depth" => config.max_depth = value.parse().map_err(bad)?,
+            "--time-ms" => config.time_limit_ms = value.parse().map_err(bad)?,
+            "--rules" => {
+                config.rules = match value.as_str() {
+                    "ninuki" => RuleSet::NINUKI,
+                    "renju" => RuleSet::RENJU,
+                    _ => return Err(format!("bad value '{}' for {}", value, flag)),
+                }
+            }
+            "--notation" => config.notation = value.parse()?,
+            _ => return Err(format!("unknown option '{}'", flag)),
+        }
+    }
+    Ok(config)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let config = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("gomoku-bridge: {}\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    set_sink(LogLevel::Info, StderrSink);
+
+    match run(config) {
+        Ok(games) => {
+            eprintln!("gomoku-bridge: {} games played", games);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("gomoku-bridge: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! Command-line tools around the engine; no window needed
+//!
+//! ```text
+//! gomoku-cli [--notation <spec>] <command> [args...]
+//!
+//! gomoku-cli bench [depth]
+//! gomoku-cli annotate <file> [nodes]
+//! gomoku-cli suggest [moves...]
+//! gomoku-cli tune [iterations] [file]
+//! gomoku-cli play [black | white] [--ascii] [ms]
+//! gomoku-cli archive <archive> add <file> <black> <white> <result> [time control]
+//! gomoku-cli archive <archive> find [moves...]
+//! gomoku-cli archive <archive> openings <player> [plies]
+//! gomoku-cli export <file> <out.gif | dir>
+//! ```
+//!
+//! `bench` runs the fixed-depth benchmark and prints its node-count
+//! signature (see `gomoku::bench`). `annotate` prints the move-quality
+//! annotation of a saved game (SGF or move list, see `gomoku::annotate`).
+//! `suggest` prints the engine's move, with the reason for it, after the
+//! given moves. `tune` tunes the search parameters by self-play and writes
+//! them to the file (default `tuned.txt`, see `gomoku::tune`). `play` plays
+//! a game against the engine in the terminal (see `gomoku::play`).
+//! `archive` appends a game to a games archive, lists the games that
+//! reached the position after some moves, or prints the openings a player
+//! keeps losing (see `gomoku::archive`). With the `render` feature,
+//! `export` draws a saved game as an animated GIF, or as one PNG per move
+//! into a directory (see `gomoku::render`).
+//!
+//! `--notation` reads and writes moves in another coordinate convention,
+//! such as `bottom-left` or `top-left+i` (see `gomoku::notation`); move
+//! lists in files and on the command line, the board drawn by `play` and
+//! the moves printed all use it. The default is the engine's own.
+//!
+//! Games and positions are played by Ninuki-renju rules. Only the `std`
+//! feature is needed; the GUI is the `gomoku` binary.
+
+use std::process::ExitCode;
+
+use gomoku::annotate::{annotate_game_with_budget, DEFAULT_ANNOTATE_NODES};
+use gomoku::archive::{ArchivedGame, GameArchive, GameScore};
+use gomoku::bench::{run_bench, DEFAULT_BENCH_DEPTH};
+use gomoku::notation::{set_notation, Notation};
+use gomoku::play::{play, PlayConfig};
+use gomoku::record::GameRecord;
+#[cfg(feature = "render")]
+use gomoku::render::{export_game, RenderStyle};
+use gomoku::rules::{check_winner, RuleSet};
+use gomoku::search::SearchParams;
+use gomoku::tune::{tune, TuneConfig};
+use gomoku::{AIEngine, Stone};
+
+const USAGE: &str =
+    "usage: gomoku-cli [--notation <spec>] <bench | annotate | suggest | tune | play | archive | export> [args...]";
+
+const ARCHIVE_USAGE: &str = "usage: gomoku-cli archive <archive> add <file> <black> <white> <result> [time control]
+       gomoku-cli archive <archive> find [moves...]
+       gomoku-cli archive <archive> openings <player> [plies]";
+
+fn read_record(path: &str, notation: Notation) -> Result<GameRecord, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    GameRecord::parse_with_notation(&text, notation)
+}
+
+/// `gomoku-cli bench`
+fn bench(args: &[String]) -> Result<String, String> {
+    let depth = match args {
+        [] => DEFAULT_BENCH_DEPTH,
+        [depth] => depth.parse().map_err(|_| "usage: gomoku-cli bench [depth]")?,
+        _ => return Err("usage: gomoku-cli bench [depth]".to_string()),
+    };
+    Ok(run_bench(depth).to_string())
+}
+
+/// `gomoku-cli annotate`
+fn annotate(args: &[String], notation: Notation) -> Result<String, String> {
+    const ANNOTATE_USAGE: &str = "usage: gomoku-cli annotate <file> [nodes]";
+    let (path, nodes) = match args {
+        [path] => (path, DEFAULT_ANNOTATE_NODES),
+        [path, nodes] => (path, nodes.parse().map_err(|_| ANNOTATE_USAGE)?),
+        _ => return Err(ANNOTATE_USAGE.to_string()),
+    };
+    let record = read_record(path, notation)?;
+    Ok(annotate_game_with_budget(RuleSet::NINUKI, &record.moves, nodes)?.to_string())
+}
+
+/// `gomoku-cli suggest`
+fn suggest(args: &[String], notation: Notation) -> Result<String, String> {
+    let record = GameRecord::from_move_list_with_notation(&args.join(" "), notation)?;
+    let boards = record.boards_with_notation(RuleSet::NINUKI, notation)?;
+    let board = &boards[boards.len() - 1];
+    if let Some(winner) = check_winner(RuleSet::NINUKI, board) {
+        return Err(format!("game is over: {:?} has won", winner));
+    }
+    let color = GameRecord::color_of(record.moves.len());
+    let hint = AIEngine::new().get_hint(board, color).ok_or("no legal move")?;
+    Ok(format!("{:?}: {}\n", color, hint))
+}
+
+/// `gomoku-cli tune`; reports each iteration as it goes
+fn tune_params(args: &[String]) -> Result<String, String> {
+    const TUNE_USAGE: &str = "usage: gomoku-cli tune [iterations] [file]";
+    if args.len() > 2 {
+        return Err(TUNE_USAGE.to_string());
+    }
+    let iterations = match args.first() {
+        None => TuneConfig::default().iterations,
+        Some(n) => n.parse().map_err(|_| TUNE_USAGE)?,
+    };
+    let path = args.get(1).map_or("tuned.txt", String::as_str);
+    let config = TuneConfig { iterations, ..TuneConfig::default() };
+    tune(SearchParams::default(), &config, |k, params, score| {
+        println!("iteration {}/{}: shifted-up side scored {:.2}", k, iterations, score);
+        if let Err(e) = std::fs::write(path, params.to_string()) {
+            eprintln!("gomoku-cli: {}: {}", path, e);
+            std::process::exit(1);
+        }
+    });
+    Ok(format!("tuned parameters written to {}\n", path))
+}
+
+/// `gomoku-cli play`; the game itself goes to the terminal
+fn play_game(args: &[String], notation: Notation) -> Result<String, String> {
+    let mut config = PlayConfig { notation, ..PlayConfig::default() };
+    for arg in args {
+        match arg.as_str() {
+            "black" => config.human = Stone::Black,
+            "white" => config.human = Stone::White,
+            "--ascii" => config.unicode = false,
+            ms => {
+                config.time_limit_ms = ms.parse().map_err(|_| "usage: gomoku-cli play [black | white] [--ascii] [ms]")?
+            }
+        }
+    }
+    play(std::io::stdin().lock(), std::io::stdout(), &config).map_err(|e| e.to_string())?;
+    Ok(String::new())
+}
+
+/// `gomoku-cli archive`
+fn archive(args: &[String], notation: Notation) -> Result<String, String> {
+    let path = args.first().ok_or(ARCHIVE_USAGE)?;
+    let mut archive = GameArchive::open(path)?;
+    match (args.get(1).map(String::as_str), &args[2.min(args.len())..]) {
+        (Some("add"), [file, black, white, result, rest @ ..]) if rest.len() <= 1 => {
+            let moves = read_record(file, notation)?.moves;
+            let time_control = rest.first().map_or("-", String::as_str);
+            let game = ArchivedGame::new(black, white, GameScore::parse(result)?, time_control, moves);
+            let index = archive.append(game)?;
+            Ok(format!("added game {} to {}\n", index + 1, path))
+        }
+        (Some("find"), moves) => {
+            let record = GameRecord::from_move_list_with_notation(&moves.join(" "), notation)?;
+            let boards = record.boards_with_notation(RuleSet::NINUKI, notation)?;
+            let hits = archive.find(&boards[boards.len() - 1], GameRecord::color_of(record.moves.len()));
+            let mut out = format!("{} games reached the position\n", hits.len());
+            for hit in hits {
+                let game = &archive.games()[hit.game];
+                out.push_str(&format!(
+                    "game {}: {} - {} {} (after move {})\n",
+                    hit.game + 1,
+                    game.black,
+                    game.white,
+                    game.result.as_str(),
+                    hit.ply
+                ));
+            }
+            Ok(out)
+        }
+        (Some("openings"), [player, rest @ ..]) if rest.len() <= 1 => {
+            let plies = rest.first().map_or(Ok(4), |n| n.parse()).map_err(|_| ARCHIVE_USAGE)?;
+            Ok(archive.openings(player, plies, 2).to_markdown())
+        }
+        _ => Err(ARCHIVE_USAGE.to_string()),
+    }
+}
+
+/// `gomoku-cli export`
+#[cfg(feature = "render")]
+fn export(args: &[String], notation: Notation) -> Result<String, String> {
+    let [game, out] = args else {
+        return Err("usage: gomoku-cli export <file> <out.gif | dir>".to_string());
+    };
+    let record = read_record(game, notation)?;
+    let images = export_game(RuleSet::NINUKI, &record.moves, std::path::Path::new(out), &RenderStyle::default())?;
+    Ok(format!("wrote {} moves to {}\n", images, out))
+}
+
+#[cfg(not(feature = "render"))]
+fn export(_: &[String], _: Notation) -> Result<String, String> {
+    Err("export needs the `render` feature".to_string())
+}
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let mut notation = Notation::DEFAULT;
+    if args.first().is_some_and(|arg| arg == "--notation") {
+        match args.get(1).ok_or_else(|| "missing value for --notation".to_string()).and_then(|spec| spec.parse()) {
+            Ok(chosen) => notation = chosen,
+            Err(e) => {
+                eprintln!("gomoku-cli: {}\n{}", e, USAGE);
+                return ExitCode::from(2);
+            }
+        }
+        args.drain(..2);
+    }
+    // Annotations and hints print through their Display impls
+    set_notation(notation);
+    let Some((command, rest)) = args.split_first() else {
+        eprintln!("{}", USAGE);
+        return ExitCode::from(2);
+    };
+    let output = match command.as_str() {
+        "bench" => bench(rest),
+        "annotate" => annotate(rest, notation),
+        "suggest" => suggest(rest, notation),
+        "tune" => tune_params(rest),
+        "play" => play_game(rest, notation),
+        "archive" => archive(rest, notation),
+        "export" => export(rest, notation),
+        _ => Err(format!("unknown command '{}'\n{}", command, USAGE)),
+    };
+    match output {
+        Ok(out) => {
+            print!("{}", out);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("gomoku-cli: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
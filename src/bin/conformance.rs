@@ -0,0 +1,54 @@
+//! Ninuki-renju rule conformance runner
+//!
+//! ```text
+//! gomoku-conformance                          # check the built-in rules
+//! gomoku-conformance --engine <cmd> [args..]  # check an external engine
+//! gomoku-conformance --serve                  # answer protocol queries on stdin
+//! ```
+//!
+//! See `gomoku::conformance::protocol` for the line protocol an external
+//! engine must speak. Exits with status 1 if any case fails.
+
+use std::io;
+use std::process::ExitCode;
+
+use gomoku::conformance::{run_suite, serve, ProcessAdapter, ReferenceRules, RulesAdapter};
+
+const USAGE: &str = "usage: gomoku-conformance [--engine <program> [args...] | --serve]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let mut adapter: Box<dyn RulesAdapter> = match args.first().map(String::as_str) {
+        None => Box::new(ReferenceRules),
+        Some("--serve") => {
+            let stdin = io::stdin().lock();
+            return match serve(&mut ReferenceRules, stdin, io::stdout().lock()) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(e) => {
+                    eprintln!("gomoku-conformance: {}", e);
+                    ExitCode::FAILURE
+                }
+            };
+        }
+        Some("--engine") if args.len() >= 2 => match ProcessAdapter::spawn(&args[1], &args[2..]) {
+            Ok(adapter) => Box::new(adapter),
+            Err(e) => {
+                eprintln!("gomoku-conformance: cannot start '{}': {}", args[1], e);
+                return ExitCode::FAILURE;
+            }
+        },
+        Some(_) => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let report = run_suite(adapter.as_mut());
+    print!("{}", report);
+    if report.is_pass() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
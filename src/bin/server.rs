@@ -0,0 +1,93 @@
+//! HTTP/JSON engine server
+//!
+//! ```text
+//! gomoku-server [--addr 127.0.0.1:8080] [--tt-mb N] [--depth N] [--time-ms N] [--max-games N]
+//!               [--threads N] [--pool N] [--max-connections N] [--rules ninuki|renju]
+//!               [--notation SPEC]
+//! ```
+//!
+//! `--rules renju` forbids Black's double-fours and overlines as well as its
+//! double-threes, and lets White play anything.
+//!
+//! `--threads` sets the search threads of each game (default: the CPU
+//! count, at most 8); a game can ask for its own with `threads` in
+//! `POST /games`.
+//!
+//! `--pool` sets how many engines are kept allocated between games
+//! (default 4); more games than that build engines as they start.
+//!
+//! `--max-connections` limits the connections served at once (default
+//! 256); the rest are answered 503.
+//!
+//! `--notation` is the coordinate convention of moves sent as text, such
+//! as `bottom-left` (see `gomoku::notation`); cells are always 0-based.
+//!
+//! See `gomoku::server` for the API.
+
+use std::net::TcpListener;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use gomoku::rules::RuleSet;
+use gomoku::server::{run, Server, ServerConfig};
+
+const USAGE: &str =
+    "usage: gomoku-server [--addr <host:port>] [--tt-mb <N>] [--depth <N>] [--time-ms <N>] [--max-games <N>] [--threads <N>] [--pool <N>] [--max-connections <N>] [--rules ninuki|renju] [--notation <spec>]";
+
+fn parse_args(args: &[String]) -> Result<(String, ServerConfig), String> {
+    let mut addr = "127.0.0.1:8080".to_string();
+    let mut config = ServerConfig::default();
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        let value = iter.next().ok_or(format!("missing value for {}", flag))?;
+        let bad = |_| format!("bad value '{}' for {}", value, flag);
+        match flag.as_str() {
+            "--addr" => addr = value.clone(),
+            "--tt-mb" => config.tt_size_mb = value.parse().map_err(bad)?,
+            "--depth" => config.max_depth = value.parse().map_err(bad)?,
+            "--time-ms" => config.time_limit_ms = value.parse().map_err(bad)?,
+            "--max-games" => config.max_games = value.parse().map_err(bad)?,
+            "--threads" => config.threads = value.parse().map_err(bad)?,
+            "--pool" => config.pool_size = value.parse().map_err(bad)?,
+            "--max-connections" => config.max_connections = value.parse().map_err(bad)?,
+            "--rules" => {
+                config.rules = match value.as_str() {
+                    "ninuki" => RuleSet::NINUKI,
+                    "renju" => RuleSet::RENJU,
+                    _ => return Err(format!("bad value '{}' for {}", value, flag)),
+                }
+            }
+            "--notation" => config.notation = value.parse()?,
+            _ => return Err(format!("unknown option '{}'", flag)),
+        }
+    }
+    Ok((addr, config))
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (addr, config) = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("gomoku-server: {}\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let listener = match TcpListener::bind(&addr) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("gomoku-server: cannot bind {}: {}", addr, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    eprintln!("gomoku-server listening on {}", addr);
+
+    match run(Arc::new(Server::new(config)), listener) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("gomoku-server: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Puzzle/tactics suite runner
+//!
+//! ```text
+//! gomoku-testsuite [--time-ms N] [--depth N] [--params FILE] [--paranoid] <file.epd>...
+//! ```
+//!
+//! See `gomoku::testsuite` for the file format. `--params` tunes the search
+//! with a `gomoku::search::SearchParams` file. `--paranoid` checks the board
+//! after every move the search makes and undoes, and panics on the first
+//! inconsistency. Exits with status 1 if any puzzle fails.
+
+use std::process::ExitCode;
+
+use gomoku::search::SearchParams;
+use gomoku::testsuite::{parse_suite, run_puzzles, SuiteConfig};
+
+const USAGE: &str =
+    "usage: gomoku-testsuite [--time-ms <N>] [--depth <N>] [--params <file>] [--paranoid] <file.epd>...";
+
+fn parse_args(args: &[String]) -> Result<(SuiteConfig, Vec<String>), String> {
+    let mut config = SuiteConfig::default();
+    let mut files = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if !arg.starts_with("--") {
+            files.push(arg.clone());
+            continue;
+        }
+        if arg == "--paranoid" {
+            config.paranoid = true;
+            continue;
+        }
+        let value = iter.next().ok_or(format!("missing value for {}", arg))?;
+        let bad = |_| format!("bad value '{}' for {}", value, arg);
+        match arg.as_str() {
+            "--time-ms" => config.time_limit_ms = value.parse().map_err(bad)?,
+            "--depth" => config.max_depth = value.parse().map_err(bad)?,
+            "--params" => config.params = SearchParams::load(value)?,
+            _ => return Err(format!("unknown option '{}'", arg)),
+        }
+    }
+    if files.is_empty() {
+        return Err("no suite files given".to_string());
+    }
+    Ok((config, files))
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (config, files) = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("gomoku-testsuite: {}\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut all_passed = true;
+    for file in &files {
+        let puzzles = match std::fs::read_to_string(file).map_err(|e| e.to_string()).and_then(|t| parse_suite(&t)) {
+            Ok(puzzles) => puzzles,
+            Err(e) => {
+                eprintln!("gomoku-testsuite: {}: {}", file, e);
+                return ExitCode::FAILURE;
+            }
+        };
+        println!("== {}", file);
+        let report = run_puzzles(&puzzles, &config);
+        print!("{}", report);
+        all_passed &= report.is_pass();
+    }
+
+    if all_passed {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,118 @@
+//! Search tree recorder and viewer
+//!
+//! ```text
+//! gomoku-trace record [--depth N] [--budget N] [--out FILE] <position>
+//! gomoku-trace view [--max-ply N] <trace.json>
+//! ```
+//!
+//! `record` searches a position (the first four fields of a
+//! `gomoku::testsuite` puzzle line, quoted) and writes the trace as JSON to
+//! `--out` or stdout. `view` prints a recorded trace as an indented tree
+//! followed by a count of node outcomes.
+
+use std::process::ExitCode;
+
+use gomoku::pos_to_notation;
+use gomoku::search::trace::SearchTrace;
+use gomoku::search::Searcher;
+use gomoku::testsuite::parse_position;
+
+const USAGE: &str = "usage: gomoku-trace record [--depth <N>] [--budget <N>] [--out <FILE>] <position>
+       gomoku-trace view [--max-ply <N>] <trace.json>";
+
+/// `--name value` pairs, in order
+type Options<'a> = Vec<(&'a str, &'a str)>;
+
+/// Split `--name value` options from the single positional argument.
+fn parse_options<'a>(args: &'a [String], known: &[&str]) -> Result<(Options<'a>, &'a str), String> {
+    let mut options = Vec::new();
+    let mut positional = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if !arg.starts_with("--") {
+            if positional.replace(arg.as_str()).is_some() {
+                return Err(format!("unexpected argument '{}'", arg));
+            }
+            continue;
+        }
+        if !known.contains(&arg.as_str()) {
+            return Err(format!("unknown option '{}'", arg));
+        }
+        let value = iter.next().ok_or(format!("missing value for {}", arg))?;
+        options.push((arg.as_str(), value.as_str()));
+    }
+    Ok((options, positional.ok_or("missing argument")?))
+}
+
+fn parse_value<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("bad value '{}' for {}", value, name))
+}
+
+fn record(args: &[String]) -> Result<(), String> {
+    let (options, position) = parse_options(args, &["--depth", "--budget", "--out"])?;
+    let mut depth = 6i8;
+    let mut budget = 100_000usize;
+    let mut out = None;
+    for (name, value) in options {
+        match name {
+            "--depth" => depth = parse_value(name, value)?,
+            "--budget" => budget = parse_value(name, value)?,
+            _ => out = Some(value),
+        }
+    }
+
+    let (board, color) = parse_position(position)?;
+    let mut searcher = Searcher::with_threads(16, 1);
+    let (result, trace) = searcher.search_traced(&board, color, depth, budget);
+    eprintln!(
+        "best {} score {} depth {} nodes {} (recorded {}{})",
+        result.best_move.map_or_else(|| "none".to_string(), pos_to_notation),
+        result.score,
+        result.depth,
+        result.nodes,
+        trace.nodes.len(),
+        if trace.truncated { ", truncated" } else { "" }
+    );
+
+    let json = trace.to_json();
+    match out {
+        Some(path) => std::fs::write(path, json).map_err(|e| format!("{}: {}", path, e)),
+        None => {
+            println!("{}", json);
+            Ok(())
+        }
+    }
+}
+
+fn view(args: &[String]) -> Result<(), String> {
+    let (options, path) = parse_options(args, &["--max-ply"])?;
+    let mut max_ply = None;
+    for (name, value) in options {
+        max_ply = Some(parse_value(name, value)?);
+    }
+
+    let json = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let trace = SearchTrace::from_json(&json).map_err(|e| format!("{}: {}", path, e))?;
+    print!("{}", trace.render(max_ply));
+    println!("-- {} nodes", trace.nodes.len());
+    for (outcome, count) in trace.outcome_counts() {
+        println!("{:>18} {}", outcome.as_str(), count);
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let run = match args.first().map(String::as_str) {
+        Some("record") => record(&args[1..]),
+        Some("view") => view(&args[1..]),
+        _ => Err("expected 'record' or 'view'".to_string()),
+    };
+    match run {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("gomoku-trace: {}\n{}", e, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
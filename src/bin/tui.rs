@@ -0,0 +1,54 @@
+//! Full-screen terminal front-end
+//!
+//! ```text
+//! gomoku-tui [black | white] [--ascii] [--notation <spec>] [ms]
+//! ```
+//!
+//! Plays a game against the engine: you take `black` (default) or
+//! `white`, and the engine gets `ms` milliseconds per move (default 500).
+//! `--ascii` draws stones as `X`/`O` for terminals without Unicode.
+//! `--notation` labels the board and moves in another coordinate
+//! convention, such as `bottom-left` (see `gomoku::notation`).
+//!
+//! See `gomoku::tui` for the keys.
+
+use std::process::ExitCode;
+
+use gomoku::play::PlayConfig;
+use gomoku::tui::run;
+use gomoku::Stone;
+
+const USAGE: &str = "usage: gomoku-tui [black | white] [--ascii] [--notation <spec>] [ms]";
+
+fn parse_args(args: &[String]) -> Result<PlayConfig, String> {
+    let mut config = PlayConfig::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "black" => config.human = Stone::Black,
+            "white" => config.human = Stone::White,
+            "--ascii" => config.unicode = false,
+            "--notation" => config.notation = iter.next().ok_or("missing value for --notation")?.parse()?,
+            ms => config.time_limit_ms = ms.parse().map_err(|_| format!("unknown argument '{}'", ms))?,
+        }
+    }
+    Ok(config)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let config = match parse_args(&args) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match run(&config) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
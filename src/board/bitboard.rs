@@ -1,10 +1,17 @@
 //! Bitboard implementation for fast pattern matching
+//!
+//! Population count, shifts and neighbor dilation dispatch to SIMD kernels
+//! (see [`super::simd`]) when the CPU supports them.
 
-use super::{TOTAL_CELLS, Pos};
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not};
+
+use super::simd::{self, Words, BOARD_MASK};
+use super::{Pos, BOARD_SIZE, TOTAL_CELLS};
 
 /// Bitboard representation for fast pattern matching
 /// Uses 6 x u64 to represent 361 cells (6 * 64 = 384 >= 361)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bitboard {
     bits: [u64; 6],
 }
@@ -15,6 +22,12 @@ impl Bitboard {
         Self { bits: [0; 6] }
     }
 
+    /// Copy with the bit at cell index `idx` set (usable in const tables)
+    pub(crate) const fn with_index(mut self, idx: usize) -> Self {
+        self.bits[idx / 64] |= 1u64 << (idx % 64);
+        self
+    }
+
     /// Set a bit at position
     #[inline]
     pub fn set(&mut self, pos: Pos) {
@@ -42,10 +55,82 @@ impl Bitboard {
         (self.bits[word] >> bit) & 1 == 1
     }
 
+    /// Bitboard with every board cell set
+    pub const fn full() -> Self {
+        Self { bits: BOARD_MASK }
+    }
+
     /// Count total set bits (popcount)
     #[inline]
     pub fn count(&self) -> u32 {
-        self.bits.iter().map(|b| b.count_ones()).sum()
+        simd::popcount(&self.bits)
+    }
+
+    /// Move every stone by `(dr, dc)`; stones leaving the board are dropped.
+    #[inline]
+    pub fn shift(&self, dr: i32, dc: i32) -> Self {
+        let size = BOARD_SIZE as i32;
+        if dr.abs() >= size || dc.abs() >= size {
+            return Self::new();
+        }
+        // Drop the columns that would wrap into the neighboring row
+        let keep = if dc >= 0 {
+            simd::cols_below((size - dc) as usize)
+        } else {
+            simd::col_range((-dc) as usize, BOARD_SIZE)
+        };
+        let src = *self & Self { bits: keep };
+        let mut bits = simd::shift(&src.bits, dr * size + dc);
+        Self::mask(&mut bits);
+        Self { bits }
+    }
+
+    /// Set cells plus their 8 neighbors (Chebyshev distance 1)
+    #[inline]
+    pub fn dilate(&self) -> Self {
+        Self { bits: simd::dilate(&self.bits) }
+    }
+
+    /// Cells set in `self` but not in `other`
+    #[inline]
+    pub fn and_not(&self, other: &Self) -> Self {
+        Self { bits: core::array::from_fn(|i| self.bits[i] & !other.bits[i]) }
+    }
+
+    /// Extract up to 32 cells along a line as a mask: bit `i` is the cell
+    /// `i` steps from `start` in direction `(dr, dc)`. Stops at the board edge.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn line(&self, start: Pos, dr: i32, dc: i32) -> u32 {
+        if (dr, dc) == (0, 1) {
+            // Row segment: contiguous bits, at most two words
+            let idx = start.to_index();
+            let len = BOARD_SIZE - usize::from(start.col);
+            let (word, bit) = (idx / 64, idx % 64);
+            let mut raw = self.bits[word] >> bit;
+            if bit != 0 && word + 1 < self.bits.len() {
+                raw |= self.bits[word + 1] << (64 - bit);
+            }
+            return (raw & ((1u64 << len) - 1)) as u32;
+        }
+        let mut mask = 0;
+        let (mut r, mut c) = (i32::from(start.row), i32::from(start.col));
+        let mut i = 0;
+        while Pos::is_valid(r, c) && i < u32::BITS {
+            if self.get(Pos::new(r as u8, c as u8)) {
+                mask |= 1 << i;
+            }
+            r += dr;
+            c += dc;
+            i += 1;
+        }
+        mask
+    }
+
+    #[inline]
+    fn mask(bits: &mut Words) {
+        for (word, mask) in bits.iter_mut().zip(BOARD_MASK) {
+            *word &= mask;
+        }
     }
 
     /// Check if empty
@@ -64,6 +149,48 @@ impl Bitboard {
     }
 }
 
+impl BitAnd for Bitboard {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self {
+        Self { bits: core::array::from_fn(|i| self.bits[i] & rhs.bits[i]) }
+    }
+}
+
+impl BitOr for Bitboard {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        Self { bits: core::array::from_fn(|i| self.bits[i] | rhs.bits[i]) }
+    }
+}
+
+impl BitAndAssign for Bitboard {
+    #[inline]
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = *self & rhs;
+    }
+}
+
+impl BitOrAssign for Bitboard {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
+/// Complement within the 361 board cells
+impl Not for Bitboard {
+    type Output = Self;
+
+    #[inline]
+    fn not(self) -> Self {
+        Self::full().and_not(&self)
+    }
+}
+
 /// Iterator over set bits in a Bitboard
 pub struct BitboardIter {
     bits: [u64; 6],
@@ -1,21 +1,85 @@
 //! Board structure with capture tracking
 
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
 use super::bitboard::Bitboard;
-use super::{Pos, Stone, BOARD_SIZE};
+use super::{Pos, Stone, Symmetry, BOARD_SIZE, TOTAL_CELLS};
+use crate::error::GomokuError;
+use crate::eval::incremental::EvalAccumulator;
+use crate::notation::{notation, Notation};
+use crate::rules::RuleSet;
 
 /// Game board with capture tracking
-#[derive(Debug, Clone)]
+///
+/// `Display` draws the grid with coordinates in the current notation and
+/// the captured pairs below it (`{:#}` draws Unicode stones); `Debug` is
+/// one line listing the stones and captures.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "BoardData"))]
 pub struct Board {
     /// Black stones bitboard
-    pub black: Bitboard,
+    black: Bitboard,
     /// White stones bitboard
-    pub white: Bitboard,
-    /// Number of pairs captured by each side (0-5, 5 = win)
-    pub black_captures: u8,
-    pub white_captures: u8,
+    white: Bitboard,
+    /// Number of pairs captured by each side (0-5, 5 = win); the move
+    /// reaching the win may take a few more
+    black_captures: u8,
+    white_captures: u8,
     /// Move history for undo (reserved for future use)
     #[allow(dead_code)]
+    #[cfg_attr(feature = "serde", serde(skip))]
     history: Vec<MoveRecord>,
+    /// Incremental evaluation terms, patched by `place_stone`/`remove_stone`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    eval: Option<Box<EvalAccumulator>>,
+    /// Cells within distance 2 of any stone (stones dilated twice),
+    /// patched by `place_stone`/`remove_stone`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    candidates: Bitboard,
+}
+
+/// Deserialized form of [`Board`]; derived state is rebuilt from the stones.
+///
+/// The board doesn't know its rules, so captures are checked against the
+/// largest capture win any [`RuleSet`] allows; incremental evaluation
+/// starts off, as on a new board.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct BoardData {
+    black: Bitboard,
+    white: Bitboard,
+    black_captures: u8,
+    white_captures: u8,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<BoardData> for Board {
+    type Error = String;
+
+    fn try_from(data: BoardData) -> Result<Self, String> {
+        let mut board = Self {
+            black: data.black,
+            white: data.white,
+            black_captures: data.black_captures,
+            white_captures: data.white_captures,
+            ..Self::new()
+        };
+        board.refresh_candidates();
+        let loosest = RuleSet::NINUKI.with_captures(2, crate::rules::MAX_CAPTURE_WIN_PAIRS)?;
+        board.check_invariants(loosest)?;
+        Ok(board)
+    }
+}
+
+/// How a point changed between two positions, for [`Board::diff`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Change {
+    Added,
+    Removed,
 }
 
 /// Record of a move for undo functionality (reserved for future use)
@@ -35,6 +99,8 @@ impl Board {
             black_captures: 0,
             white_captures: 0,
             history: Vec::with_capacity(361),
+            eval: None,
+            candidates: Bitboard::new(),
         }
     }
 
@@ -68,15 +134,101 @@ impl Board {
         match stone {
             Stone::Black => self.black.set(pos),
             Stone::White => self.white.set(pos),
-            Stone::Empty => {}
+            Stone::Empty => return,
+        }
+        self.candidates |= NEIGHBORHOODS[pos.to_index()];
+        if let Some(eval) = self.eval.as_deref_mut() {
+            eval.update(pos, stone, true);
         }
     }
 
+    /// [`Board::place_stone`] for untrusted input: the point must be on the
+    /// board and empty and `stone` a color.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`GomokuError`] for the first check that fails; the board
+    /// is unchanged then.
+    pub fn try_place_stone(&mut self, pos: Pos, stone: Stone) -> Result<(), GomokuError> {
+        let pos = Pos::try_new(pos.row, pos.col)?;
+        if stone == Stone::Empty {
+            return Err(GomokuError::NoColor);
+        }
+        if !self.is_empty(pos) {
+            return Err(GomokuError::Occupied(pos));
+        }
+        self.place_stone(pos, stone);
+        Ok(())
+    }
+
     /// Remove a stone
     #[inline]
     pub fn remove_stone(&mut self, pos: Pos) {
+        if let Some(eval) = self.eval.as_deref_mut() {
+            if self.black.get(pos) {
+                eval.update(pos, Stone::Black, false);
+            } else if self.white.get(pos) {
+                eval.update(pos, Stone::White, false);
+            }
+        }
         self.black.clear(pos);
         self.white.clear(pos);
+        // Dilation can't be undone per stone (neighbors may share cells)
+        self.refresh_candidates();
+    }
+
+    /// Empty or occupied cells within distance 2 of any stone: the area
+    /// move generation draws from. Includes the stones themselves.
+    #[inline]
+    pub fn candidates(&self) -> &Bitboard {
+        &self.candidates
+    }
+
+    /// Empty cells within distance 2 of any stone.
+    #[inline]
+    pub fn candidate_moves(&self) -> Bitboard {
+        self.candidates.and_not(&(self.black | self.white))
+    }
+
+    /// Recompute [`candidates`](Self::candidates) from the stones.
+    /// `place_stone`/`remove_stone` keep it current, so this is only a
+    /// repair after the stone bitboards were written directly.
+    #[inline]
+    pub fn refresh_candidates(&mut self) {
+        self.candidates = (self.black | self.white).dilate().dilate();
+    }
+
+    /// Track evaluation terms under `rules` across `place_stone`/`remove_stone`
+    /// so [`evaluate`](crate::eval::evaluate) by the same rules only
+    /// re-tallies the lines that changed instead of rescanning the board.
+    /// Worth it for boards that are searched.
+    pub fn enable_incremental_eval(&mut self, rules: RuleSet) {
+        self.eval = Some(Box::new(EvalAccumulator::from_board(self, rules)));
+    }
+
+    /// Store the tallies of lines changed since the last refresh, so later
+    /// evaluations don't redo them. Cheap no-op when nothing changed.
+    #[inline]
+    pub fn refresh_eval(&mut self) {
+        if let Some(eval) = self.eval.as_deref_mut() {
+            eval.refresh();
+        }
+    }
+
+    /// Stop tracking incremental evaluation terms.
+    pub fn disable_incremental_eval(&mut self) {
+        self.eval = None;
+    }
+
+    /// Whether incremental evaluation is enabled.
+    #[inline]
+    pub fn has_incremental_eval(&self) -> bool {
+        self.eval.is_some()
+    }
+
+    #[inline]
+    pub(crate) fn eval_accumulator(&self) -> Option<&EvalAccumulator> {
+        self.eval.as_deref()
     }
 
     /// Get bitboard for a color (returns None for Empty)
@@ -89,9 +241,63 @@ impl Board {
         }
     }
 
-    /// Get mutable bitboard for a color (returns None for Empty)
+    /// Both colors' stones in one bitboard
     #[inline]
-    pub fn stones_mut(&mut self, stone: Stone) -> Option<&mut Bitboard> {
+    pub fn occupancy(&self) -> Bitboard {
+        self.black | self.white
+    }
+
+    /// Black's and White's stones, e.g. to key a position by
+    #[inline]
+    pub fn bitboards(&self) -> (Bitboard, Bitboard) {
+        (self.black, self.white)
+    }
+
+    /// Every stone with its color, row by row
+    pub fn occupied(&self) -> impl Iterator<Item = (Pos, Stone)> + '_ {
+        self.occupancy().iter_ones().map(move |pos| (pos, self.get(pos)))
+    }
+
+    /// The stones of `color`, row by row (none for `Stone::Empty`)
+    pub fn stones_of(&self, color: Stone) -> impl Iterator<Item = Pos> {
+        self.stones(color).map(Bitboard::iter_ones).into_iter().flatten()
+    }
+
+    /// Empty cells within `radius` (king steps) of any stone, row by row
+    pub fn empty_near(&self, radius: u8) -> impl Iterator<Item = Pos> {
+        let occupied = self.occupancy();
+        let near = (0..radius).fold(occupied, |area, _| area.dilate());
+        near.and_not(&occupied).iter_ones()
+    }
+
+    /// Every full line of the board in direction `(dr, dc)`, one of
+    /// `(0, 1)`, `(1, 0)`, `(1, 1)` and `(1, -1)`: the cells in order with
+    /// their contents. Diagonals shorter than five cells are included.
+    pub fn lines(&self, (dr, dc): (i32, i32)) -> impl Iterator<Item = Vec<(Pos, Stone)>> + '_ {
+        debug_assert!(matches!((dr, dc), (0, 1) | (1, 0) | (1, 1) | (1, -1)), "not a line direction");
+        (0..TOTAL_CELLS)
+            .map(Pos::from_index)
+            .filter(move |start| !Pos::is_valid(i32::from(start.row) - dr, i32::from(start.col) - dc))
+            .map(move |start| {
+                let mut line = Vec::with_capacity(BOARD_SIZE);
+                let (mut row, mut col) = (i32::from(start.row), i32::from(start.col));
+                while Pos::is_valid(row, col) {
+                    #[allow(clippy::cast_sign_loss)]
+                    let pos = Pos::new(row as u8, col as u8);
+                    line.push((pos, self.get(pos)));
+                    row += dr;
+                    col += dc;
+                }
+                line
+            })
+    }
+
+    /// Get mutable bitboard for a color (returns None for Empty). Writes
+    /// bypass the candidate area and incremental evaluation; only the
+    /// invariant tests use it to corrupt a board on purpose.
+    #[cfg(test)]
+    #[inline]
+    pub(crate) fn stones_mut(&mut self, stone: Stone) -> Option<&mut Bitboard> {
         match stone {
             Stone::Black => Some(&mut self.black),
             Stone::White => Some(&mut self.white),
@@ -99,7 +305,8 @@ impl Board {
         }
     }
 
-    /// Get capture count for a color
+    /// Get capture count for a color, in pairs of
+    /// [`pair_size`](crate::rules::RuleSet::pair_size) stones
     #[inline]
     pub fn captures(&self, stone: Stone) -> u8 {
         match stone {
@@ -109,9 +316,40 @@ impl Board {
         }
     }
 
-    /// Add captures for a color (saturating, max 255)
+    /// Pairs `stone` still needs to capture to win under `rules`; 0 once it
+    /// has won by capture
     #[inline]
-    pub fn add_captures(&mut self, stone: Stone, count: u8) {
+    pub fn captures_to_win(&self, rules: RuleSet, stone: Stone) -> u8 {
+        rules.capture_win_pairs().saturating_sub(self.captures(stone))
+    }
+
+    /// Set the pairs `stone` has captured, for setting up a position. A
+    /// position still in play has fewer than the capture win of `rules`;
+    /// the count may reach it, not pass it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GomokuError::NoColor`] for `Stone::Empty` and
+    /// [`GomokuError::TooManyCaptures`] above the capture win; the board is
+    /// unchanged then.
+    pub fn set_captures(&mut self, rules: RuleSet, stone: Stone, pairs: u8) -> Result<(), GomokuError> {
+        let max = rules.capture_win_pairs();
+        if pairs > max {
+            return Err(GomokuError::TooManyCaptures { pairs, max });
+        }
+        match stone {
+            Stone::Black => self.black_captures = pairs,
+            Stone::White => self.white_captures = pairs,
+            Stone::Empty => return Err(GomokuError::NoColor),
+        }
+        Ok(())
+    }
+
+    /// Add captures for a color (saturating, max 255). Capture execution
+    /// uses this, so it doesn't stop at the capture win; outside the crate
+    /// counts only change through the rules or [`Board::set_captures`].
+    #[inline]
+    pub(crate) fn add_captures(&mut self, stone: Stone, count: u8) {
         match stone {
             Stone::Black => self.black_captures = self.black_captures.saturating_add(count),
             Stone::White => self.white_captures = self.white_captures.saturating_add(count),
@@ -121,7 +359,7 @@ impl Board {
 
     /// Subtract captures for a color (saturating, min 0) - used for unmake
     #[inline]
-    pub fn sub_captures(&mut self, stone: Stone, count: u8) {
+    pub(crate) fn sub_captures(&mut self, stone: Stone, count: u8) {
         match stone {
             Stone::Black => self.black_captures = self.black_captures.saturating_sub(count),
             Stone::White => self.white_captures = self.white_captures.saturating_sub(count),
@@ -140,8 +378,193 @@ impl Board {
     pub fn is_board_empty(&self) -> bool {
         self.black.is_empty() && self.white.is_empty()
     }
+
+    /// Check the state that moves and undos must keep consistent: the
+    /// stone bitboards are disjoint and on the board, capture counters are
+    /// in range for `rules`, and the candidate area and incremental evaluation (if
+    /// enabled) match a rebuild from the stones. Slow; meant for debugging
+    /// make/unmake code (see [`Searcher::set_paranoid`]).
+    ///
+    /// [`Searcher::set_paranoid`]: crate::search::Searcher::set_paranoid
+    ///
+    /// # Errors
+    ///
+    /// Returns a message naming the first broken invariant.
+    pub fn check_invariants(&self, rules: RuleSet) -> Result<(), String> {
+        let overlap = self.black & self.white;
+        if !overlap.is_empty() {
+            return Err(format!("{} points hold both colors", overlap.count()));
+        }
+        let off_board = (self.black | self.white).and_not(&Bitboard::full());
+        if !off_board.is_empty() {
+            return Err(format!("{} stones are off the board", off_board.count()));
+        }
+        // The move reaching the capture win takes at most eight pairs
+        let max_pairs = rules.capture_win_pairs().saturating_add(7);
+        for color in [Stone::Black, Stone::White] {
+            if self.captures(color) > max_pairs {
+                return Err(format!("{:?} has captured {} pairs (at most {})", color, self.captures(color), max_pairs));
+            }
+        }
+        if self.candidates != (self.black | self.white).dilate().dilate() {
+            return Err("candidate area doesn't match the stones".to_string());
+        }
+        if let Some(eval) = self.eval.as_deref() {
+            let fresh = EvalAccumulator::from_board(self, eval.rules());
+            for color in [Stone::Black, Stone::White] {
+                for phase in 0..3 {
+                    if eval.color_score(color, phase) != fresh.color_score(color, phase) {
+                        return Err(format!("incremental evaluation of {:?} doesn't match the stones", color));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Stones added and removed going from this position to `other`, in
+    /// point order. A point whose stone changed color is listed twice, the
+    /// removal first. Capture counts aren't compared.
+    #[must_use]
+    pub fn diff(&self, other: &Board) -> Vec<(Pos, Stone, Change)> {
+        let changed = self.black.and_not(&other.black)
+            | other.black.and_not(&self.black)
+            | self.white.and_not(&other.white)
+            | other.white.and_not(&self.white);
+        let mut changes = Vec::with_capacity(changed.count() as usize);
+        for pos in changed.iter_ones() {
+            let (before, after) = (self.get(pos), other.get(pos));
+            if before != Stone::Empty {
+                changes.push((pos, before, Change::Removed));
+            }
+            if after != Stone::Empty {
+                changes.push((pos, after, Change::Added));
+            }
+        }
+        changes
+    }
+
+    /// This position moved by `sym`, capture counts kept (and incremental
+    /// evaluation, if enabled here)
+    #[must_use]
+    pub fn transform(&self, sym: Symmetry) -> Board {
+        let mut out = Board::new();
+        for (bb, color) in [(&self.black, Stone::Black), (&self.white, Stone::White)] {
+            for pos in bb.iter_ones() {
+                out.place_stone(pos.transform(sym), color);
+            }
+        }
+        out.black_captures = self.black_captures;
+        out.white_captures = self.white_captures;
+        if let Some(eval) = self.eval_accumulator() {
+            out.enable_incremental_eval(eval.rules());
+        }
+        out
+    }
+
+    /// This position turned a quarter clockwise
+    #[must_use]
+    pub fn rotate90(&self) -> Board {
+        self.transform(Symmetry::Rotate90)
+    }
+
+    /// This position with left and right swapped
+    #[must_use]
+    pub fn mirror(&self) -> Board {
+        self.transform(Symmetry::FlipCols)
+    }
+
+    /// Draw the position top row first with coordinates on all sides and
+    /// the captured pairs on the last line (no newline after it), with
+    /// `last_move` in brackets, coordinates in `notation`
+    pub(crate) fn write_grid(
+        &self,
+        out: &mut impl fmt::Write,
+        last_move: Option<Pos>,
+        unicode: bool,
+        notation: Notation,
+    ) -> fmt::Result {
+        let letters = |out: &mut dyn fmt::Write| {
+            out.write_str("   ")?;
+            (0..BOARD_SIZE as u8).try_for_each(|col| write!(out, " {}", notation.column_letter(col)))?;
+            out.write_char('\n')
+        };
+        letters(out)?;
+        for row in 0..BOARD_SIZE as u8 {
+            let number = notation.row_number(row);
+            write!(out, "{:>2} ", number)?;
+            for col in 0..BOARD_SIZE as u8 {
+                let pos = Pos::new(row, col);
+                let before = if last_move == Some(pos) {
+                    '['
+                } else if col > 0 && last_move == Some(Pos::new(row, col - 1)) {
+                    ']'
+                } else {
+                    ' '
+                };
+                out.write_char(before)?;
+                out.write_char(match (self.get(pos), unicode) {
+                    (Stone::Black, true) => '●',
+                    (Stone::White, true) => '○',
+                    (Stone::Empty, true) => '·',
+                    (Stone::Black, false) => 'X',
+                    (Stone::White, false) => 'O',
+                    (Stone::Empty, false) => '.',
+                })?;
+            }
+            let after = if last_move == Some(Pos::new(row, BOARD_SIZE as u8 - 1)) { ']' } else { ' ' };
+            writeln!(out, "{}{}", after, number)?;
+        }
+        letters(out)?;
+        write!(out, "Captured pairs: Black {}, White {}", self.black_captures, self.white_captures)
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_grid(f, None, f.alternate(), notation())
+    }
 }
 
+/// `Board { black: [K10 L11], white: [J9], captures: 0/1 }`, on one line
+/// even with `{:#?}`
+impl fmt::Debug for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let notation = notation();
+        f.write_str("Board {")?;
+        for (name, stones) in [("black", &self.black), ("white", &self.white)] {
+            write!(f, " {}: [", name)?;
+            for (i, pos) in stones.iter_ones().enumerate() {
+                write!(f, "{}{}", if i == 0 { "" } else { " " }, notation.format(pos))?;
+            }
+            f.write_str("],")?;
+        }
+        write!(f, " captures: {}/{} }}", self.black_captures, self.white_captures)
+    }
+}
+
+/// 5x5 square around every cell, clipped to the board.
+static NEIGHBORHOODS: [Bitboard; TOTAL_CELLS] = {
+    let mut zones = [Bitboard::new(); TOTAL_CELLS];
+    let mut idx = 0;
+    while idx < TOTAL_CELLS {
+        let (row, col) = ((idx / BOARD_SIZE) as i32, (idx % BOARD_SIZE) as i32);
+        let mut r = row - 2;
+        while r <= row + 2 {
+            let mut c = col - 2;
+            while c <= col + 2 {
+                if Pos::is_valid(r, c) {
+                    zones[idx] = zones[idx].with_index(r as usize * BOARD_SIZE + c as usize);
+                }
+                c += 1;
+            }
+            r += 1;
+        }
+        idx += 1;
+    }
+    zones
+};
+
 impl Default for Board {
     fn default() -> Self {
         Self::new()
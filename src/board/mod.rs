@@ -1,14 +1,22 @@
 //! Board representation for Gomoku
 
 pub mod bitboard;
+#[allow(clippy::module_inception)]
 pub mod board;
+mod simd;
+pub mod symmetry;
 
 #[cfg(test)]
 mod tests;
 
 // Re-exports
 pub use bitboard::Bitboard;
-pub use board::Board;
+pub use board::{Board, Change};
+pub use symmetry::Symmetry;
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::error::GomokuError;
 
 /// Board size (19x19)
 pub const BOARD_SIZE: usize = 19;
@@ -16,6 +24,7 @@ pub const TOTAL_CELLS: usize = BOARD_SIZE * BOARD_SIZE; // 361
 
 /// Stone colors
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Stone {
     Empty,
     Black,
@@ -36,6 +45,7 @@ impl Stone {
 
 /// Position on the board
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pos {
     pub row: u8,
     pub col: u8,
@@ -48,6 +58,20 @@ impl Pos {
         Self { row, col }
     }
 
+    /// [`Pos::new`] for untrusted input.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GomokuError::OutOfBounds`] for a row or column off the board.
+    #[inline]
+    pub fn try_new(row: u8, col: u8) -> Result<Self, GomokuError> {
+        if Self::is_valid(i32::from(row), i32::from(col)) {
+            Ok(Self { row, col })
+        } else {
+            Err(GomokuError::OutOfBounds { row, col })
+        }
+    }
+
     #[inline]
     pub fn to_index(self) -> usize {
         self.row as usize * BOARD_SIZE + self.col as usize
@@ -62,19 +86,46 @@ impl Pos {
     }
 
     #[inline]
-    pub fn is_valid(row: i32, col: i32) -> bool {
+    pub const fn is_valid(row: i32, col: i32) -> bool {
         row >= 0 && row < BOARD_SIZE as i32 && col >= 0 && col < BOARD_SIZE as i32
     }
+
+    /// Where this point goes under `sym`
+    #[inline]
+    #[must_use]
+    pub fn transform(self, sym: Symmetry) -> Self {
+        sym.apply(self)
+    }
+
+    /// Parse board notation such as `K10`, the inverse of
+    /// [`crate::pos_to_notation`], in the configured [`crate::notation`]
+    /// (by default a column letter `A`-`T` without `I`, either case, then
+    /// the row `1`-`19`).
+    ///
+    /// # Errors
+    ///
+    /// Returns a message saying what is wrong with `s`.
+    ///
+    /// ```
+    /// use gomoku::Pos;
+    ///
+    /// assert_eq!(Pos::from_notation("K10"), Ok(Pos::new(9, 9)));
+    /// assert_eq!(Pos::from_notation("j1"), Ok(Pos::new(0, 8)));
+    /// assert!(Pos::from_notation("I5").unwrap_err().contains("no column I"));
+    /// ```
+    pub fn from_notation(s: &str) -> Result<Self, String> {
+        crate::notation::notation().parse(s)
+    }
 }
 
 impl PartialOrd for Pos {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
 impl Ord for Pos {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.to_index().cmp(&other.to_index())
     }
 }
@@ -0,0 +1,289 @@
+//! SIMD kernels for [`Bitboard`](super::Bitboard) operations
+//!
+//! A bitboard is six `u64` words (384 bits, 361 used). On x86_64 the AVX2
+//! and POPCNT versions are selected at runtime with
+//! `is_x86_feature_detected!`; other targets and older CPUs use the
+//! portable scalar versions. Both paths must give identical results.
+//!
+//! Shifts move bits across word boundaries, so every kernel works on the
+//! whole board at once: AVX2 holds words 0-3 in one register and words 4-5
+//! (padded with zeros) in another.
+
+use super::{BOARD_SIZE, TOTAL_CELLS};
+
+pub(super) type Words = [u64; 6];
+
+/// Bits 0..361: every board cell.
+pub(super) const BOARD_MASK: Words = cols_below(BOARD_SIZE);
+
+/// Cells in column 0.
+const FIRST_COL: Words = col_range(0, 1);
+
+/// Cells in column 18.
+const LAST_COL: Words = col_range(BOARD_SIZE - 1, BOARD_SIZE);
+
+/// Cells whose column is in `from..to`.
+pub(super) const fn col_range(from: usize, to: usize) -> Words {
+    let mut words = [0; 6];
+    let mut idx = 0;
+    while idx < TOTAL_CELLS {
+        let col = idx % BOARD_SIZE;
+        if col >= from && col < to {
+            words[idx / 64] |= 1 << (idx % 64);
+        }
+        idx += 1;
+    }
+    words
+}
+
+/// Cells whose column is below `to`.
+pub(super) const fn cols_below(to: usize) -> Words {
+    col_range(0, to)
+}
+
+/// Whether the CPU has `feature`: detected at run time with `std`,
+/// otherwise whatever the build targets
+#[cfg(target_arch = "x86_64")]
+macro_rules! cpu_has {
+    ($feature:tt) => {{
+        #[cfg(feature = "std")]
+        let has = std::is_x86_feature_detected!($feature);
+        #[cfg(not(feature = "std"))]
+        let has = cfg!(target_feature = $feature);
+        has
+    }};
+}
+
+#[inline]
+fn map2(a: &Words, b: &Words, f: impl Fn(u64, u64) -> u64) -> Words {
+    core::array::from_fn(|i| f(a[i], b[i]))
+}
+
+/// Number of set bits.
+#[inline]
+pub(super) fn popcount(words: &Words) -> u32 {
+    #[cfg(target_arch = "x86_64")]
+    if cpu_has!("popcnt") {
+        // SAFETY: the CPU supports POPCNT (checked above).
+        return unsafe { x86::popcount(words) };
+    }
+    scalar::popcount(words)
+}
+
+/// Shift every bit `n` places toward higher indices (lower for negative
+/// `n`). Bits shifted past either end are dropped; no board masking.
+#[inline]
+pub(super) fn shift(words: &Words, n: i32) -> Words {
+    #[cfg(target_arch = "x86_64")]
+    if n.unsigned_abs() < 64 && cpu_has!("avx2") {
+        // SAFETY: the CPU supports AVX2 (checked above).
+        return unsafe { x86::shift(words, n) };
+    }
+    scalar::shift(words, n)
+}
+
+/// Set bits plus their 8 neighbors, clipped to the board.
+#[inline]
+pub(super) fn dilate(words: &Words) -> Words {
+    #[cfg(target_arch = "x86_64")]
+    if cpu_has!("avx2") {
+        // SAFETY: the CPU supports AVX2 (checked above).
+        return unsafe { x86::dilate(words) };
+    }
+    scalar::dilate(words)
+}
+
+pub(super) mod scalar {
+    use super::{map2, Words, BOARD_MASK, BOARD_SIZE, FIRST_COL, LAST_COL};
+
+    pub(in crate::board) fn popcount(words: &Words) -> u32 {
+        words.iter().map(|w| w.count_ones()).sum()
+    }
+
+    pub(in crate::board) fn shift(words: &Words, n: i32) -> Words {
+        let word_shift = n.div_euclid(64);
+        let bit_shift = n.rem_euclid(64) as u32;
+        let word = |i: i32| -> u64 {
+            let src = i - word_shift;
+            if (0..6).contains(&src) { words[src as usize] } else { 0 }
+        };
+        core::array::from_fn(|i| {
+            let i = i as i32;
+            let lo = if bit_shift == 0 { 0 } else { word(i - 1) >> (64 - bit_shift) };
+            (word(i) << bit_shift) | lo
+        })
+    }
+
+    pub(in crate::board) fn dilate(words: &Words) -> Words {
+        let not_last = map2(words, &LAST_COL, |w, m| w & !m);
+        let not_first = map2(words, &FIRST_COL, |w, m| w & !m);
+        let row = map2(words, &shift(&not_last, 1), |a, b| a | b);
+        let row = map2(&row, &shift(&not_first, -1), |a, b| a | b);
+        let size = BOARD_SIZE as i32;
+        let all = map2(&row, &shift(&row, size), |a, b| a | b);
+        let all = map2(&all, &shift(&row, -size), |a, b| a | b);
+        map2(&all, &BOARD_MASK, |a, m| a & m)
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use core::arch::x86_64::{
+        __m256i, _mm256_and_si256, _mm256_andnot_si256, _mm256_blend_epi32, _mm256_loadu_si256,
+        _mm256_or_si256, _mm256_permute4x64_epi64, _mm256_setzero_si256, _mm256_sll_epi64,
+        _mm256_srl_epi64, _mm256_storeu_si256, _mm_cvtsi32_si128,
+    };
+
+    use super::{Words, BOARD_MASK, BOARD_SIZE, FIRST_COL, LAST_COL};
+
+    /// Words 0-3 and words 4-5 (upper two lanes zero).
+    #[derive(Clone, Copy)]
+    struct Board256(__m256i, __m256i);
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn load(words: &Words) -> Board256 {
+        let mut padded = [0u64; 8];
+        padded[..6].copy_from_slice(words);
+        let ptr = padded.as_ptr().cast::<__m256i>();
+        Board256(_mm256_loadu_si256(ptr), _mm256_loadu_si256(ptr.add(1)))
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn store(b: Board256) -> Words {
+        let mut padded = [0u64; 8];
+        let ptr = padded.as_mut_ptr().cast::<__m256i>();
+        _mm256_storeu_si256(ptr, b.0);
+        _mm256_storeu_si256(ptr.add(1), b.1);
+        [padded[0], padded[1], padded[2], padded[3], padded[4], padded[5]]
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn or(a: Board256, b: Board256) -> Board256 {
+        Board256(_mm256_or_si256(a.0, b.0), _mm256_or_si256(a.1, b.1))
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn and(a: Board256, b: Board256) -> Board256 {
+        Board256(_mm256_and_si256(a.0, b.0), _mm256_and_si256(a.1, b.1))
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn and_not(a: Board256, mask: Board256) -> Board256 {
+        Board256(_mm256_andnot_si256(mask.0, a.0), _mm256_andnot_si256(mask.1, a.1))
+    }
+
+    /// Each lane replaced by the word below it: `[0, w0, w1, w2]`, `[w3, w4, w5, w6]`.
+    #[target_feature(enable = "avx2")]
+    unsafe fn words_below(b: Board256) -> Board256 {
+        let lo = _mm256_permute4x64_epi64::<0b10_01_00_11>(b.0);
+        let hi = _mm256_permute4x64_epi64::<0b10_01_00_11>(b.1);
+        Board256(
+            _mm256_blend_epi32::<0b0000_0011>(lo, _mm256_setzero_si256()),
+            _mm256_blend_epi32::<0b0000_0011>(hi, lo),
+        )
+    }
+
+    /// Each lane replaced by the word above it: `[w1, w2, w3, w4]`, `[w5, w6, w7, 0]`.
+    #[target_feature(enable = "avx2")]
+    unsafe fn words_above(b: Board256) -> Board256 {
+        let lo = _mm256_permute4x64_epi64::<0b00_11_10_01>(b.0);
+        let hi = _mm256_permute4x64_epi64::<0b00_11_10_01>(b.1);
+        Board256(
+            _mm256_blend_epi32::<0b1100_0000>(lo, hi),
+            _mm256_blend_epi32::<0b1100_0000>(hi, _mm256_setzero_si256()),
+        )
+    }
+
+    /// Shift by `n` bits, `|n| < 64`.
+    #[target_feature(enable = "avx2")]
+    unsafe fn shift256(b: Board256, n: i32) -> Board256 {
+        let bits = n.unsigned_abs() as i32;
+        if bits == 0 {
+            return b;
+        }
+        let count = _mm_cvtsi32_si128(bits);
+        let carry = _mm_cvtsi32_si128(64 - bits);
+        if n > 0 {
+            let below = words_below(b);
+            Board256(
+                _mm256_or_si256(_mm256_sll_epi64(b.0, count), _mm256_srl_epi64(below.0, carry)),
+                _mm256_or_si256(_mm256_sll_epi64(b.1, count), _mm256_srl_epi64(below.1, carry)),
+            )
+        } else {
+            let above = words_above(b);
+            Board256(
+                _mm256_or_si256(_mm256_srl_epi64(b.0, count), _mm256_sll_epi64(above.0, carry)),
+                _mm256_or_si256(_mm256_srl_epi64(b.1, count), _mm256_sll_epi64(above.1, carry)),
+            )
+        }
+    }
+
+    #[target_feature(enable = "popcnt")]
+    pub(super) unsafe fn popcount(words: &Words) -> u32 {
+        words.iter().map(|w| w.count_ones()).sum()
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn shift(words: &Words, n: i32) -> Words {
+        // Left shifts spill past word 5 into the zero padding, which `store` drops
+        store(shift256(load(words), n))
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn dilate(words: &Words) -> Words {
+        let b = load(words);
+        let row = or(
+            b,
+            or(
+                shift256(and_not(b, load(&LAST_COL)), 1),
+                shift256(and_not(b, load(&FIRST_COL)), -1),
+            ),
+        );
+        let size = BOARD_SIZE as i32;
+        let all = or(row, or(shift256(row, size), shift256(row, -size)));
+        store(and(all, load(&BOARD_MASK)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_words(seed: &mut u64) -> Words {
+        core::array::from_fn(|_| {
+            *seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            *seed
+        })
+    }
+
+    #[test]
+    fn test_masks() {
+        assert_eq!(scalar::popcount(&BOARD_MASK), TOTAL_CELLS as u32);
+        assert_eq!(scalar::popcount(&FIRST_COL), BOARD_SIZE as u32);
+        assert_eq!(scalar::popcount(&LAST_COL), BOARD_SIZE as u32);
+    }
+
+    #[test]
+    fn test_dispatch_matches_scalar() {
+        let mut seed = 0x0DDB_1A5E_5BAD_5EED;
+        for _ in 0..500 {
+            let words = map2(&random_words(&mut seed), &BOARD_MASK, |w, m| w & m);
+            assert_eq!(popcount(&words), scalar::popcount(&words));
+            assert_eq!(dilate(&words), scalar::dilate(&words));
+            for n in [-63, -20, -19, -18, -1, 0, 1, 18, 19, 20, 63, 64, -64, 200, -200] {
+                assert_eq!(shift(&words, n), scalar::shift(&words, n), "shift {}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_scalar_shift_crosses_words() {
+        let mut words = [0; 6];
+        words[0] = 1 << 63;
+        let up = scalar::shift(&words, 1);
+        assert_eq!(up, [0, 1, 0, 0, 0, 0]);
+        assert_eq!(scalar::shift(&up, -1), words);
+        assert_eq!(scalar::shift(&words, 64 * 5), [0, 0, 0, 0, 0, 1 << 63]);
+        assert_eq!(scalar::shift(&words, 64 * 5 + 1), [0; 6]);
+    }
+}
@@ -0,0 +1,160 @@
+//! Board symmetries
+//!
+//! The square board has eight symmetries (four rotations, each with or
+//! without a reflection), and the rules and evaluation treat symmetric
+//! positions alike. [`Symmetry::canonical`] picks one representative of the
+//! eight, so equivalent early positions share opening-book answers and
+//! transposition-table entries; a move found on the canonical board maps
+//! back with [`Symmetry::inverse`].
+//!
+//! ```
+//! use gomoku::board::{Board, Pos, Stone, Symmetry};
+//!
+//! // A black stone diagonally off the center, in two of its eight places
+//! let mut a = Board::new();
+//! a.place_stone(Pos::new(8, 8), Stone::Black);
+//! let mut b = Board::new();
+//! b.place_stone(Pos::new(10, 8), Stone::Black);
+//!
+//! let (canon_a, sym_a) = Symmetry::canonical(&a);
+//! let (canon_b, sym_b) = Symmetry::canonical(&b);
+//! assert_eq!(canon_a.bitboards(), canon_b.bitboards());
+//!
+//! // A point of the canonical board maps back to each original
+//! let stone = canon_a.stones_of(Stone::Black).next().unwrap();
+//! assert_eq!(sym_a.inverse().apply(stone), Pos::new(8, 8));
+//! assert_eq!(sym_b.inverse().apply(stone), Pos::new(10, 8));
+//! ```
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use super::{Bitboard, Board, Pos, Stone, BOARD_SIZE};
+
+/// One of the eight symmetries of the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    Identity,
+    /// Quarter turn clockwise
+    Rotate90,
+    Rotate180,
+    /// Quarter turn counter-clockwise
+    Rotate270,
+    /// Top and bottom swapped
+    FlipRows,
+    /// Left and right swapped
+    FlipCols,
+    /// Mirrored in the main diagonal (row and column swapped)
+    Transpose,
+    /// Mirrored in the anti-diagonal
+    AntiTranspose,
+}
+
+impl Symmetry {
+    pub const ALL: [Self; 8] = [
+        Self::Identity,
+        Self::Rotate90,
+        Self::Rotate180,
+        Self::Rotate270,
+        Self::FlipRows,
+        Self::FlipCols,
+        Self::Transpose,
+        Self::AntiTranspose,
+    ];
+
+    /// Where `pos` goes
+    #[inline]
+    #[must_use]
+    pub fn apply(self, pos: Pos) -> Pos {
+        let last = BOARD_SIZE as u8 - 1;
+        let (r, c) = (pos.row, pos.col);
+        let (row, col) = match self {
+            Self::Identity => (r, c),
+            Self::Rotate90 => (c, last - r),
+            Self::Rotate180 => (last - r, last - c),
+            Self::Rotate270 => (last - c, r),
+            Self::FlipRows => (last - r, c),
+            Self::FlipCols => (r, last - c),
+            Self::Transpose => (c, r),
+            Self::AntiTranspose => (last - c, last - r),
+        };
+        Pos::new(row, col)
+    }
+
+    /// The symmetry that undoes this one
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        match self {
+            Self::Rotate90 => Self::Rotate270,
+            Self::Rotate270 => Self::Rotate90,
+            other => other,
+        }
+    }
+
+    /// Every point of `bb` moved by this symmetry
+    #[must_use]
+    pub fn apply_bitboard(self, bb: &Bitboard) -> Bitboard {
+        let mut out = Bitboard::new();
+        for pos in bb.iter_ones() {
+            out.set(self.apply(pos));
+        }
+        out
+    }
+
+    /// The representative of `board`'s eight images (the one whose Black,
+    /// then White, stones come first in index order) and the symmetry that
+    /// maps `board` onto it. Positions related by a symmetry get the same
+    /// representative.
+    #[must_use]
+    pub fn canonical(board: &Board) -> (Board, Self) {
+        let key = |sym: Self| -> [Vec<usize>; 2] {
+            [Stone::Black, Stone::White].map(|color| {
+                let mut cells: Vec<usize> = board.stones_of(color).map(|pos| sym.apply(pos).to_index()).collect();
+                cells.sort_unstable();
+                cells
+            })
+        };
+        let best = Self::ALL.into_iter().min_by_key(|&sym| key(sym)).unwrap_or(Self::Identity);
+        (board.transform(best), best)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Stone;
+    use crate::rules::RuleSet;
+
+    #[test]
+    fn test_symmetries_are_a_group() {
+        let pos = Pos::new(2, 15);
+        let mut images: Vec<usize> = Symmetry::ALL.iter().map(|sym| sym.apply(pos).to_index()).collect();
+        images.sort_unstable();
+        images.dedup();
+        assert_eq!(images.len(), 8);
+        for sym in Symmetry::ALL {
+            assert_eq!(sym.inverse().apply(sym.apply(pos)), pos, "{:?}", sym);
+        }
+        assert_eq!(Symmetry::Rotate90.apply(Symmetry::Rotate90.apply(pos)), Symmetry::Rotate180.apply(pos));
+        assert_eq!(Symmetry::Rotate90.apply(Pos::new(0, 0)), Pos::new(0, 18));
+        let center = Pos::new(9, 9);
+        assert!(Symmetry::ALL.iter().all(|sym| sym.apply(center) == center));
+    }
+
+    #[test]
+    fn test_canonical_board_is_shared_by_images() {
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.place_stone(Pos::new(8, 10), Stone::White);
+        board.place_stone(Pos::new(7, 12), Stone::Black);
+        board.set_captures(RuleSet::NINUKI, Stone::White, 1).unwrap();
+
+        let (canon, sym) = Symmetry::canonical(&board);
+        assert_eq!(board.transform(sym).bitboards(), canon.bitboards());
+        for image in Symmetry::ALL.map(|s| board.transform(s)) {
+            let (other, other_sym) = Symmetry::canonical(&image);
+            assert_eq!((other.bitboards(), other.captures(Stone::White)), (canon.bitboards(), 1));
+            // The symmetry maps the image's stones onto the same representative
+            assert_eq!(other_sym.apply_bitboard(&image.bitboards().1), canon.bitboards().1);
+        }
+    }
+}
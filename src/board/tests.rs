@@ -1,6 +1,7 @@
 use super::*;
 use super::bitboard::Bitboard;
 use super::board::Board;
+use crate::rules::RuleSet;
 
 #[test]
 fn test_stone_opponent() {
@@ -148,6 +149,81 @@ fn test_bitboard_word_boundaries() {
     assert_eq!(bb.count(), 4);
 }
 
+#[test]
+fn test_bitboard_shift_drops_off_board() {
+    let mut bb = Bitboard::new();
+    bb.set(Pos::new(0, 18));
+    bb.set(Pos::new(9, 9));
+    bb.set(Pos::new(18, 0));
+
+    // Right: (0,18) would wrap into row 1
+    let right = bb.shift(0, 1);
+    assert_eq!(right.iter_ones().collect::<Vec<_>>(), vec![Pos::new(9, 10), Pos::new(18, 1)]);
+
+    // Down-left: (18,0) falls off the bottom, (0,18) moves to (1,17)
+    let down_left = bb.shift(1, -1);
+    assert_eq!(down_left.iter_ones().collect::<Vec<_>>(), vec![Pos::new(1, 17), Pos::new(10, 8)]);
+
+    assert!(bb.shift(19, 0).is_empty());
+}
+
+#[test]
+fn test_bitboard_dilate() {
+    let mut bb = Bitboard::new();
+    bb.set(Pos::new(9, 9));
+    assert_eq!(bb.dilate().count(), 9);
+    assert_eq!(bb.dilate().dilate().count(), 25);
+
+    // Corners and edges are clipped, no wrap between rows
+    let mut corner = Bitboard::new();
+    corner.set(Pos::new(0, 18));
+    let dilated = corner.dilate();
+    assert_eq!(dilated.count(), 4);
+    assert!(dilated.get(Pos::new(1, 17)));
+    assert!(!dilated.get(Pos::new(1, 0)));
+
+    let mut edge = Bitboard::new();
+    edge.set(Pos::new(18, 0));
+    assert_eq!(edge.dilate().count(), 4);
+    assert!(!edge.dilate().get(Pos::new(17, 18)));
+}
+
+#[test]
+fn test_bitboard_set_ops() {
+    let mut a = Bitboard::new();
+    let mut b = Bitboard::new();
+    a.set(Pos::new(1, 1));
+    a.set(Pos::new(2, 2));
+    b.set(Pos::new(2, 2));
+    b.set(Pos::new(3, 3));
+
+    assert_eq!((a | b).count(), 3);
+    assert_eq!((a & b).count(), 1);
+    assert_eq!(a.and_not(&b).iter_ones().collect::<Vec<_>>(), vec![Pos::new(1, 1)]);
+    assert_eq!((!a).count(), TOTAL_CELLS as u32 - 2);
+    assert_eq!(Bitboard::full().count(), TOTAL_CELLS as u32);
+    assert!((!Bitboard::full()).is_empty());
+}
+
+#[test]
+fn test_bitboard_line() {
+    let mut bb = Bitboard::new();
+    bb.set(Pos::new(3, 5));
+    bb.set(Pos::new(3, 18));
+    bb.set(Pos::new(5, 7));
+    bb.set(Pos::new(18, 4));
+
+    // Row 3 from col 5 (crosses a word boundary at index 64)
+    assert_eq!(bb.line(Pos::new(3, 5), 0, 1), 1 | (1 << 13));
+    assert_eq!(bb.line(Pos::new(3, 0), 0, 1), (1 << 5) | (1 << 18));
+    // Diagonal through (3,5) and (5,7)
+    assert_eq!(bb.line(Pos::new(0, 2), 1, 1), (1 << 3) | (1 << 5));
+    // Last row lives in the final word
+    assert_eq!(bb.line(Pos::new(18, 0), 0, 1), 1 << 4);
+    // Anti-diagonal from the top edge
+    assert_eq!(bb.line(Pos::new(0, 8), 1, -1), 1 << 3);
+}
+
 // Board tests
 
 #[test]
@@ -178,6 +254,45 @@ fn test_board_captures() {
     board.add_captures(Stone::Black, 2);
     assert_eq!(board.captures(Stone::Black), 2);
     assert_eq!(board.captures(Stone::White), 0);
+    assert_eq!(board.captures_to_win(RuleSet::NINUKI, Stone::Black), 3);
+
+    board.add_captures(Stone::Black, 4);
+    assert_eq!(board.captures_to_win(RuleSet::NINUKI, Stone::Black), 0);
+}
+
+#[test]
+fn test_board_display_and_debug() {
+    let mut board = Board::new();
+    board.place_stone(Pos::new(9, 9), Stone::Black);
+    board.place_stone(Pos::new(0, 18), Stone::White);
+    board.set_captures(RuleSet::NINUKI, Stone::White, 2).unwrap();
+
+    let text = board.to_string();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), BOARD_SIZE + 3);
+    assert!(lines[10].contains(" . X . "), "{:?}", lines[10]);
+    assert!(lines[1].contains(" O "), "{:?}", lines[1]);
+    assert_eq!(lines[BOARD_SIZE + 2], "Captured pairs: Black 0, White 2");
+    assert!(format!("{:#}", board).contains(" · ● · "));
+
+    let n = crate::notation::notation();
+    let debug = format!("Board {{ black: [{}], white: [{}], captures: 0/2 }}", n.format(Pos::new(9, 9)), n.format(Pos::new(0, 18)));
+    assert_eq!(format!("{:?}", board), debug);
+    assert_eq!(format!("{:#?}", board), debug);
+}
+
+#[test]
+fn test_set_captures_validates() {
+    let mut board = Board::new();
+    assert_eq!(board.set_captures(RuleSet::NINUKI, Stone::White, 5), Ok(()));
+    assert_eq!(board.captures(Stone::White), 5);
+    assert_eq!(
+        board.set_captures(RuleSet::NINUKI, Stone::White, 6),
+        Err(GomokuError::TooManyCaptures { pairs: 6, max: 5 })
+    );
+    assert_eq!(board.set_captures(RuleSet::NINUKI, Stone::Empty, 1), Err(GomokuError::NoColor));
+    assert_eq!(board.captures(Stone::White), 5, "a rejected count leaves the board unchanged");
+    assert_eq!(board.check_invariants(RuleSet::NINUKI), Ok(()));
 }
 
 #[test]
@@ -190,3 +305,208 @@ fn test_board_stone_count() {
     assert_eq!(board.stone_count(), 3);
     assert!(!board.is_board_empty());
 }
+
+#[test]
+fn test_board_candidates_follow_place_remove() {
+    let mut board = Board::new();
+    assert!(board.candidates().is_empty());
+
+    board.place_stone(Pos::new(9, 9), Stone::Black);
+    assert_eq!(board.candidates().count(), 25);
+    assert_eq!(board.candidate_moves().count(), 24);
+
+    board.place_stone(Pos::new(0, 0), Stone::White);
+    board.place_stone(Pos::new(9, 11), Stone::White);
+    assert_eq!(board.candidates().count(), 25 + 9 + 10);
+    assert!(board.candidate_moves().get(Pos::new(2, 2)));
+    assert!(!board.candidate_moves().get(Pos::new(3, 3)));
+
+    // Removing a stone keeps cells still covered by its neighbors
+    board.remove_stone(Pos::new(9, 11));
+    assert_eq!(board.candidates().count(), 25 + 9);
+    assert!(board.candidates().get(Pos::new(9, 11)));
+    assert!(!board.candidates().get(Pos::new(9, 12)));
+
+    board.remove_stone(Pos::new(9, 9));
+    board.remove_stone(Pos::new(0, 0));
+    assert!(board.candidates().is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_board_serde_round_trip() {
+    let mut board = Board::new();
+    board.place_stone(Pos::new(9, 9), Stone::Black);
+    board.place_stone(Pos::new(18, 18), Stone::White);
+    board.add_captures(Stone::White, 3);
+
+    let json = serde_json::to_string(&board).unwrap();
+    let restored: Board = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restored.bitboards(), board.bitboards());
+    assert_eq!(restored.captures(Stone::White), 3);
+    assert_eq!(restored.get(Pos::new(18, 18)), Stone::White);
+    assert_eq!(restored.candidates(), board.candidates());
+    assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_board_deserialize_rejects_bad_input() {
+    let board = |black: &str, white: &str, captures: u8| {
+        let json = format!(
+            r#"{{"black":{{"bits":{}}},"white":{{"bits":{}}},"black_captures":{},"white_captures":0}}"#,
+            black, white, captures
+        );
+        serde_json::from_str::<Board>(&json)
+    };
+    let empty = "[0,0,0,0,0,0]";
+    let one = "[1,0,0,0,0,0]";
+
+    let restored = board(one, empty, 2).unwrap();
+    assert_eq!(restored.stone_count(), 1);
+    assert_eq!(restored.candidates().count(), 9);
+
+    let off_board = format!("[0,0,0,0,0,{}]", u64::MAX);
+    assert!(board(&off_board, empty, 0).unwrap_err().to_string().contains("off the board"));
+    assert!(board(one, one, 0).unwrap_err().to_string().contains("both colors"));
+    assert!(board(empty, empty, 200).unwrap_err().to_string().contains("captured 200 pairs"));
+}
+
+#[test]
+fn test_pos_from_notation_errors() {
+    assert_eq!(Pos::from_notation(" t19 "), Ok(Pos::new(18, 18)));
+    assert_eq!(Pos::from_notation("H1"), Ok(Pos::new(0, 7)));
+    assert!(Pos::from_notation("").unwrap_err().contains("empty"));
+    assert!(Pos::from_notation("I10").unwrap_err().contains("no column I"));
+    assert!(Pos::from_notation("U10").unwrap_err().contains("off the board"));
+    assert!(Pos::from_notation("10K").unwrap_err().contains("column letter"));
+    assert!(Pos::from_notation("K").unwrap_err().contains("row number"));
+    assert!(Pos::from_notation("K0").unwrap_err().contains("row 0"));
+    assert!(Pos::from_notation("A20").unwrap_err().contains("row 20"));
+}
+
+#[test]
+fn test_fallible_pos_and_placement() {
+    use crate::error::GomokuError;
+
+    assert_eq!(Pos::try_new(18, 18), Ok(Pos::new(18, 18)));
+    assert_eq!(Pos::try_new(3, 19), Err(GomokuError::OutOfBounds { row: 3, col: 19 }));
+
+    let mut board = Board::new();
+    let pos = Pos::new(9, 9);
+    assert_eq!(board.try_place_stone(pos, Stone::Empty), Err(GomokuError::NoColor));
+    assert_eq!(board.try_place_stone(pos, Stone::Black), Ok(()));
+    assert_eq!(board.try_place_stone(pos, Stone::White), Err(GomokuError::Occupied(pos)));
+    assert_eq!(board.get(pos), Stone::Black);
+    // A Pos built directly can still be off the board
+    let off = Pos { row: 25, col: 0 };
+    assert_eq!(board.try_place_stone(off, Stone::White), Err(GomokuError::OutOfBounds { row: 25, col: 0 }));
+    assert_eq!(board.stone_count(), 1);
+}
+
+#[test]
+fn test_board_rotate_and_mirror() {
+    let mut board = Board::new();
+    board.place_stone(Pos::new(0, 0), Stone::Black);
+    board.place_stone(Pos::new(3, 7), Stone::White);
+    board.set_captures(RuleSet::NINUKI, Stone::Black, 2).unwrap();
+
+    let rotated = board.rotate90();
+    assert_eq!(rotated.get(Pos::new(0, 18)), Stone::Black);
+    assert_eq!(rotated.get(Pos::new(7, 15)), Stone::White);
+    assert_eq!(rotated.captures(Stone::Black), 2);
+    let full_turn = rotated.rotate90().rotate90().rotate90();
+    assert_eq!(full_turn.bitboards(), board.bitboards());
+
+    let mirrored = board.mirror();
+    assert_eq!(mirrored.get(Pos::new(3, 11)), Stone::White);
+    assert_eq!(mirrored.mirror().bitboards(), board.bitboards());
+    assert_eq!(Pos::new(3, 7).transform(Symmetry::FlipCols), Pos::new(3, 11));
+    // Moved stones keep their candidate neighborhoods
+    assert!(mirrored.candidates().get(Pos::new(5, 11)));
+}
+
+#[test]
+fn test_board_diff() {
+    let mut before = Board::new();
+    before.place_stone(Pos::new(9, 9), Stone::Black);
+    before.place_stone(Pos::new(9, 10), Stone::White);
+    before.place_stone(Pos::new(0, 0), Stone::Black);
+    assert!(before.diff(&before.clone()).is_empty());
+
+    let mut after = before.clone();
+    after.remove_stone(Pos::new(0, 0));
+    after.place_stone(Pos::new(10, 10), Stone::Black);
+    after.remove_stone(Pos::new(9, 10));
+    after.place_stone(Pos::new(9, 10), Stone::Black);
+    after.set_captures(RuleSet::NINUKI, Stone::White, 1).unwrap();
+    assert_eq!(
+        before.diff(&after),
+        vec![
+            (Pos::new(0, 0), Stone::Black, Change::Removed),
+            (Pos::new(9, 10), Stone::White, Change::Removed),
+            (Pos::new(9, 10), Stone::Black, Change::Added),
+            (Pos::new(10, 10), Stone::Black, Change::Added),
+        ]
+    );
+}
+
+#[test]
+fn test_board_check_invariants() {
+    let mut board = Board::new();
+    board.place_stone(Pos::new(9, 9), Stone::Black);
+    board.place_stone(Pos::new(9, 10), Stone::White);
+    board.enable_incremental_eval(RuleSet::NINUKI);
+    assert_eq!(board.check_invariants(RuleSet::NINUKI), Ok(()));
+
+    let mut both = board.clone();
+    both.stones_mut(Stone::White).unwrap().set(Pos::new(9, 9));
+    assert!(both.check_invariants(RuleSet::NINUKI).unwrap_err().contains("both colors"));
+
+    // Bitboard edits skip the candidate area and the incremental evaluation
+    let mut stale = board.clone();
+    stale.stones_mut(Stone::Black).unwrap().set(Pos::new(8, 8));
+    assert!(stale.check_invariants(RuleSet::NINUKI).unwrap_err().contains("candidate area"));
+    stale.refresh_candidates();
+    assert!(stale.check_invariants(RuleSet::NINUKI).unwrap_err().contains("incremental evaluation"));
+    stale.enable_incremental_eval(RuleSet::NINUKI);
+    assert_eq!(stale.check_invariants(RuleSet::NINUKI), Ok(()));
+
+    let mut captures = board.clone();
+    captures.add_captures(Stone::White, 200);
+    assert!(captures.check_invariants(RuleSet::NINUKI).unwrap_err().contains("White has captured 200 pairs"));
+}
+
+#[test]
+fn test_board_iterators() {
+    let mut board = Board::new();
+    board.place_stone(Pos::new(9, 10), Stone::White);
+    board.place_stone(Pos::new(9, 9), Stone::Black);
+    board.place_stone(Pos::new(0, 0), Stone::Black);
+
+    let occupied: Vec<(Pos, Stone)> = board.occupied().collect();
+    assert_eq!(
+        occupied,
+        vec![(Pos::new(0, 0), Stone::Black), (Pos::new(9, 9), Stone::Black), (Pos::new(9, 10), Stone::White)]
+    );
+    assert_eq!(board.stones_of(Stone::White).collect::<Vec<_>>(), vec![Pos::new(9, 10)]);
+    assert_eq!(board.stones_of(Stone::Empty).count(), 0);
+
+    // Corner stone: 3 neighbors; the center pair: a 3x4 block minus itself
+    assert_eq!(board.empty_near(1).count(), 3 + 10);
+    assert!(board.empty_near(1).all(|pos| board.is_empty(pos)));
+    assert_eq!(board.empty_near(0).count(), 0);
+    assert_eq!(board.empty_near(2).collect::<Vec<_>>(), board.candidate_moves().iter_ones().collect::<Vec<_>>());
+
+    assert_eq!(board.lines((0, 1)).count(), BOARD_SIZE);
+    assert_eq!(board.lines((1, 1)).count(), 2 * BOARD_SIZE - 1);
+    for dir in [(0, 1), (1, 0), (1, 1), (1, -1)] {
+        assert_eq!(board.lines(dir).map(|line| line.len()).sum::<usize>(), TOTAL_CELLS);
+    }
+    let row = board.lines((0, 1)).nth(9).unwrap();
+    assert_eq!(row[9..11], [(Pos::new(9, 9), Stone::Black), (Pos::new(9, 10), Stone::White)]);
+    let anti = board.lines((1, -1)).find(|line| line[0].0 == Pos::new(0, 18)).unwrap();
+    assert_eq!(anti.len(), BOARD_SIZE);
+    assert_eq!(anti[9], (Pos::new(9, 9), Stone::Black));
+}
@@ -0,0 +1,621 @@
+//! Online play bridge (feature `bridge`)
+//!
+//! Connects the engine to a game server and plays unattended: it logs in,
+//! asks for games, answers the opponent's moves under the server's clock,
+//! and reconnects (resuming the game in progress) when the connection
+//! drops. Servers speak many dialects, so the bridge talks one small
+//! line-delimited JSON protocol over TCP; an adapter in front of a
+//! particular site (playok/kurnik, a renju server, a tournament manager)
+//! only has to translate these messages.
+//!
+//! Server to bridge ([`ServerMessage`]):
+//!
+//! ```text
+//! {"type":"game","game_id":"g1","color":"Black","moves":[],"black_ms":300000,"white_ms":300000,"increment_ms":2000}
+//! {"type":"move","game_id":"g1","move":{"row":9,"col":10}}
+//! {"type":"clock","game_id":"g1","black_ms":297500,"white_ms":299000}
+//! {"type":"game_over","game_id":"g1","result":"Black wins"}
+//! {"type":"ping"}
+//! {"type":"error","message":"..."}
+//! ```
+//!
+//! Bridge to server ([`ClientMessage`]):
+//!
+//! ```text
+//! {"type":"hello","name":"gomoku","token":"..."}
+//! {"type":"seek"}
+//! {"type":"resume","game_id":"g1"}
+//! {"type":"move","game_id":"g1","move":{"row":9,"col":9}}
+//! {"type":"pong"}
+//! ```
+//!
+//! `game` starts a game and also resynchronizes one after `resume`: it
+//! carries every move so far, and the bridge rebuilds the position from
+//! them. `move` is only needed for the opponent's moves; echoes of the
+//! bridge's own are ignored. Clocks are optional; without them the engine
+//! uses its fixed time per move.
+//!
+//! The protocol logic lives in [`BridgeSession`], which turns messages into
+//! replies without touching the network. The transport is a [`GameHost`]:
+//! [`TcpHost`] speaks the protocol above over TCP, [`LocalHost`] passes the
+//! messages through channels in-process, and an integration with another
+//! server only implements the trait. [`play`] drives a session on any host;
+//! [`run`] on a [`TcpHost`].
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::board::{Pos, Stone};
+use crate::engine::AIEngine;
+use crate::game::Game;
+use crate::logging::{log_info, log_warn};
+use crate::notation::Notation;
+use crate::rules::RuleSet;
+
+/// Where to connect and how to play
+#[derive(Debug, Clone)]
+pub struct BridgeConfig {
+    /// Server address (`host:port`)
+    pub addr: String,
+    /// Account name and optional login token sent in `hello`
+    pub name: String,
+    pub token: Option<String>,
+    /// Games to play before stopping (0: keep seeking games)
+    pub games: usize,
+    /// Reconnection attempts in a row before giving up, and the wait
+    /// before the first one (doubled after each failure, up to a minute)
+    pub reconnect_attempts: u32,
+    pub reconnect_delay_ms: u64,
+    /// Time kept back from the clock for network lag (ms)
+    pub lag_margin_ms: u64,
+    /// Engine settings; `time_limit_ms` is the time per move when the
+    /// server sends no clock
+    pub tt_size_mb: usize,
+    pub max_depth: i8,
+    pub time_limit_ms: u64,
+    /// Rules the server's games are played by
+    pub rules: RuleSet,
+    /// Coordinates moves are logged and reported in; the protocol itself
+    /// sends cells
+    pub notation: Notation,
+}
+
+impl Default for BridgeConfig {
+    fn default() -> Self {
+        Self {
+            addr: "127.0.0.1:6000".to_string(),
+            name: "gomoku".to_string(),
+            token: None,
+            games: 0,
+            reconnect_attempts: 10,
+            reconnect_delay_ms: 1000,
+            lag_margin_ms: 300,
+            tt_size_mb: 64,
+            max_depth: 20,
+            time_limit_ms: 1000,
+            rules: RuleSet::NINUKI,
+            notation: Notation::DEFAULT,
+        }
+    }
+}
+
+/// A message from the server
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// A game starts, or its state after `resume`
+    Game {
+        game_id: String,
+        /// The bridge's color
+        color: Stone,
+        #[serde(default)]
+        moves: Vec<Pos>,
+        #[serde(default)]
+        black_ms: Option<u64>,
+        #[serde(default)]
+        white_ms: Option<u64>,
+        #[serde(default)]
+        increment_ms: u64,
+    },
+    /// A move played in a game (the opponent's; echoes are ignored)
+    Move {
+        game_id: String,
+        #[serde(rename = "move")]
+        pos: Pos,
+    },
+    /// Time left on both clocks
+    Clock { game_id: String, black_ms: u64, white_ms: u64 },
+    GameOver {
+        game_id: String,
+        #[serde(default)]
+        result: String,
+    },
+    Ping,
+    Error { message: String },
+}
+
+/// A message to the server
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Hello {
+        name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        token: Option<String>,
+    },
+    /// Ask for a new game
+    Seek,
+    /// Continue a game after reconnecting
+    Resume { game_id: String },
+    Move {
+        game_id: String,
+        #[serde(rename = "move")]
+        pos: Pos,
+    },
+    Pong,
+}
+
+/// The game being played
+struct ActiveGame {
+    id: String,
+    color: Stone,
+    game: Game,
+    /// Time left for Black and White, when the server keeps a clock
+    clock_ms: Option<(u64, u64)>,
+    increment_ms: u64,
+}
+
+/// The bridge's protocol state: the game in progress and the games played
+pub struct BridgeSession {
+    config: BridgeConfig,
+    engine: AIEngine,
+    current: Option<ActiveGame>,
+    finished: usize,
+}
+
+impl BridgeSession {
+    #[must_use]
+    pub fn new(config: BridgeConfig) -> Self {
+        let engine = AIEngine::with_config(config.tt_size_mb, config.max_depth, config.time_limit_ms);
+        Self { config, engine, current: None, finished: 0 }
+    }
+
+    /// Games finished so far
+    #[must_use]
+    pub fn games_finished(&self) -> usize {
+        self.finished
+    }
+
+    /// Whether the configured number of games has been played
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.config.games > 0 && self.finished >= self.config.games
+    }
+
+    /// Messages to send on (re)connecting: `hello`, then `resume` for the
+    /// game in progress or `seek` for a new one
+    #[must_use]
+    pub fn on_connect(&self) -> Vec<ClientMessage> {
+        let hello = ClientMessage::Hello { name: self.config.name.clone(), token: self.config.token.clone() };
+        let next = match &self.current {
+            Some(active) => ClientMessage::Resume { game_id: active.id.clone() },
+            None => ClientMessage::Seek,
+        };
+        vec![hello, next]
+    }
+
+    /// Handle one message and return the replies; the engine's move is
+    /// searched before this returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the configured rules are invalid or the
+    /// server's moves can't be played on the game's board; the game is
+    /// dropped and the next connection seeks a new one.
+    pub fn handle(&mut self, message: ServerMessage) -> Result<Vec<ClientMessage>, String> {
+        match message {
+            ServerMessage::Game { game_id, color, moves, black_ms, white_ms, increment_ms } => {
+                if color == Stone::Empty {
+                    return Err(format!("game {}: no color to play", game_id));
+                }
+                self.engine.set_rules(self.config.rules);
+                let mut game = Game::with_rules(self.config.rules).with_notation(self.config.notation);
+                for &pos in &moves {
+                    if let Err(e) = game.play(pos) {
+                        self.current = None;
+                        return Err(format!("game {}: move {}: {}", game_id, move_name(pos, self.config.notation), e));
+                    }
+                }
+                let clock_ms = black_ms.zip(white_ms);
+                log_info!("Game {}: playing {:?} from move {}", game_id, color, moves.len() + 1);
+                self.current = Some(ActiveGame { id: game_id, color, game, clock_ms, increment_ms });
+                Ok(self.engine_move().into_iter().collect())
+            }
+            ServerMessage::Move { game_id, pos } => {
+                let Some(active) = self.current.as_mut().filter(|active| active.id == game_id) else {
+                    log_warn!("Move for unknown game {}", game_id);
+                    return Ok(Vec::new());
+                };
+                let echo = active.game.history().last().is_some_and(|record| record.pos == pos);
+                if echo || active.game.to_move() == active.color {
+                    // Our own move coming back, or a move out of turn
+                    return Ok(Vec::new());
+                }
+                if let Err(e) = active.game.play(pos) {
+                    self.current = None;
+                    return Err(format!("game {}: move {}: {}", game_id, move_name(pos, self.config.notation), e));
+                }
+                Ok(self.engine_move().into_iter().collect())
+            }
+            ServerMessage::Clock { game_id, black_ms, white_ms } => {
+                if let Some(active) = self.current.as_mut().filter(|active| active.id == game_id) {
+                    active.clock_ms = Some((black_ms, white_ms));
+                }
+                Ok(Vec::new())
+            }
+            ServerMessage::GameOver { game_id, result } => {
+                if self.current.as_ref().is_some_and(|active| active.id == game_id) {
+                    log_info!("Game {} over: {}", game_id, result);
+                    self.current = None;
+                    self.finished += 1;
+                }
+                Ok(if self.is_done() { Vec::new() } else { vec![ClientMessage::Seek] })
+            }
+            ServerMessage::Ping => Ok(vec![ClientMessage::Pong]),
+            ServerMessage::Error { message } => {
+                log_warn!("Server error: {}", message);
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    /// Search and play the engine's move if it is its turn
+    fn engine_move(&mut self) -> Option<ClientMessage> {
+        let active = self.current.as_mut()?;
+        if active.game.is_over() || active.game.to_move() != active.color {
+            return None;
+        }
+        let start = Instant::now();
+        let board = active.game.board();
+        let result = match active.clock_ms {
+            Some((black_ms, white_ms)) => {
+                let left = if active.color == Stone::Black { black_ms } else { white_ms };
+                let remaining = Duration::from_millis(left.saturating_sub(self.config.lag_margin_ms));
+                let increment = Duration::from_millis(active.increment_ms);
+                self.engine.get_move_with_clock(board, active.color, remaining, increment)
+            }
+            None => self.engine.get_move_with_stats(board, active.color),
+        };
+        let pos = result.best_move?;
+        active.game.play(pos).ok()?;
+
+        // Until the server's next clock update
+        if let Some((black_ms, white_ms)) = active.clock_ms.as_mut() {
+            let used = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
+            let left = if active.color == Stone::Black { black_ms } else { white_ms };
+            *left = left.saturating_sub(used) + active.increment_ms;
+        }
+        let name = self.config.notation.format(pos);
+        log_info!("Game {}: {} (depth {}, score {})", active.id, name, result.depth, result.score);
+        Some(ClientMessage::Move { game_id: active.id.clone(), pos })
+    }
+}
+
+/// A move from the server in notation, or as coordinates when it is off the
+/// board (notation has no letter for it)
+fn move_name(pos: Pos, notation: Notation) -> String {
+    if Pos::is_valid(i32::from(pos.row), i32::from(pos.col)) {
+        notation.format(pos)
+    } else {
+        format!("({}, {})", pos.row, pos.col)
+    }
+}
+
+/// Where games come from: the transport under a [`BridgeSession`]. A new
+/// server integration implements this and reuses the session's protocol,
+/// clock and reconnection handling.
+pub trait GameHost {
+    /// Open (or reopen, after a lost connection) the connection
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the host can't be reached; [`play`] retries.
+    fn connect(&mut self) -> io::Result<()>;
+
+    /// Wait for the next message: a game start, a move, a clock update,
+    /// the end of a game... `Ok(None)` when the connection is closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the connection fails.
+    fn receive(&mut self) -> io::Result<Option<ServerMessage>>;
+
+    /// Send a message: a move, a seek...
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the connection fails.
+    fn send(&mut self, message: &ClientMessage) -> io::Result<()>;
+}
+
+/// The network host: the JSON line protocol over TCP
+pub struct TcpHost {
+    addr: String,
+    connection: Option<(BufReader<TcpStream>, TcpStream)>,
+}
+
+impl TcpHost {
+    #[must_use]
+    pub fn new(addr: &str) -> Self {
+        Self { addr: addr.to_string(), connection: None }
+    }
+}
+
+impl GameHost for TcpHost {
+    fn connect(&mut self) -> io::Result<()> {
+        self.connection = None;
+        let stream = TcpStream::connect(&self.addr)?;
+        log_info!("Connected to {}", self.addr);
+        self.connection = Some((BufReader::new(stream.try_clone()?), stream));
+        Ok(())
+    }
+
+    fn receive(&mut self) -> io::Result<Option<ServerMessage>> {
+        let Some((reader, _)) = self.connection.as_mut() else { return Ok(None) };
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                log_warn!("{} closed the connection", self.addr);
+                self.connection = None;
+                return Ok(None);
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(message) => return Ok(Some(message)),
+                Err(e) => log_warn!("Ignoring bad message '{}': {}", line.trim(), e),
+            }
+        }
+    }
+
+    fn send(&mut self, message: &ClientMessage) -> io::Result<()> {
+        let Some((_, writer)) = self.connection.as_mut() else {
+            return Err(io::Error::new(io::ErrorKind::NotConnected, "not connected"));
+        };
+        let line = serde_json::to_string(message).map_err(io::Error::other)?;
+        writeln!(writer, "{}", line)?;
+        writer.flush()
+    }
+}
+
+/// An in-process host: messages go through channels to a [`LocalServer`]
+/// on another thread, e.g. a GUI or a test harness hosting the bot
+pub struct LocalHost {
+    incoming: Receiver<ServerMessage>,
+    outgoing: Sender<ClientMessage>,
+}
+
+/// The other end of a [`LocalHost`]
+pub struct LocalServer {
+    pub incoming: Receiver<ClientMessage>,
+    pub outgoing: Sender<ServerMessage>,
+}
+
+impl LocalHost {
+    /// A host and the server end that feeds it; the connection closes when
+    /// the server end is dropped
+    #[must_use]
+    pub fn pair() -> (LocalHost, LocalServer) {
+        let (to_host, incoming) = mpsc::channel();
+        let (outgoing, from_host) = mpsc::channel();
+        (LocalHost { incoming, outgoing }, LocalServer { incoming: from_host, outgoing: to_host })
+    }
+}
+
+impl GameHost for LocalHost {
+    fn connect(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn receive(&mut self) -> io::Result<Option<ServerMessage>> {
+        Ok(self.incoming.recv().ok())
+    }
+
+    fn send(&mut self, message: &ClientMessage) -> io::Result<()> {
+        self.outgoing
+            .send(message.clone())
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "local server is gone"))
+    }
+}
+
+/// Serve one connection until the session is done (`Ok(true)`) or the
+/// host hangs up (`Ok(false)`)
+fn serve(session: &mut BridgeSession, host: &mut impl GameHost) -> io::Result<bool> {
+    for message in session.on_connect() {
+        host.send(&message)?;
+    }
+    while let Some(message) = host.receive()? {
+        let replies = session.handle(message).unwrap_or_else(|e| {
+            log_warn!("{}", e);
+            vec![ClientMessage::Seek]
+        });
+        for reply in &replies {
+            host.send(reply)?;
+        }
+        if session.is_done() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Play on `host` until `config.games` games are over, reconnecting
+/// whenever the connection is lost. Returns the games played.
+///
+/// # Errors
+///
+/// Returns the last connection error once `config.reconnect_attempts`
+/// attempts in a row have failed.
+pub fn play(host: &mut impl GameHost, config: BridgeConfig) -> io::Result<usize> {
+    let (attempts, first_delay) = (config.reconnect_attempts, config.reconnect_delay_ms);
+    let mut session = BridgeSession::new(config);
+    let mut failures = 0;
+    loop {
+        let outcome = host.connect().and_then(|()| {
+            failures = 0;
+            serve(&mut session, host)
+        });
+        match outcome {
+            Ok(true) => return Ok(session.games_finished()),
+            Ok(false) => {}
+            Err(e) => {
+                log_warn!("Connection failed: {}", e);
+                failures += 1;
+                if failures > attempts {
+                    return Err(e);
+                }
+            }
+        }
+        let delay = first_delay.saturating_mul(1 << failures.saturating_sub(1).min(6)).min(60_000);
+        thread::sleep(Duration::from_millis(delay));
+    }
+}
+
+/// [`play`] on a [`TcpHost`] connected to `config.addr`
+///
+/// # Errors
+///
+/// See [`play`].
+pub fn run(config: BridgeConfig) -> io::Result<usize> {
+    let mut host = TcpHost::new(&config.addr);
+    play(&mut host, config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    fn quick_config() -> BridgeConfig {
+        BridgeConfig {
+            tt_size_mb: 1,
+            max_depth: 2,
+            time_limit_ms: 50,
+            reconnect_delay_ms: 10,
+            ..BridgeConfig::default()
+        }
+    }
+
+    fn parse(line: &str) -> ServerMessage {
+        serde_json::from_str(line).unwrap()
+    }
+
+    #[test]
+    fn test_session_plays_its_color_and_ignores_echoes() {
+        let mut session = BridgeSession::new(BridgeConfig { games: 1, ..quick_config() });
+        assert_eq!(session.on_connect()[1], ClientMessage::Seek);
+
+        let replies = session.handle(parse(r#"{"type":"game","game_id":"g1","color":"Black"}"#)).unwrap();
+        let [ClientMessage::Move { game_id, pos }] = replies.as_slice() else { panic!("{:?}", replies) };
+        assert_eq!(game_id, "g1");
+
+        // The server echoes our move, then the opponent answers
+        let echo = ServerMessage::Move { game_id: "g1".to_string(), pos: *pos };
+        assert!(session.handle(echo).unwrap().is_empty());
+        let reply = session.handle(parse(r#"{"type":"move","game_id":"g1","move":{"row":0,"col":0}}"#)).unwrap();
+        assert!(matches!(reply.as_slice(), [ClientMessage::Move { .. }]));
+
+        assert_eq!(session.handle(ServerMessage::Ping).unwrap(), vec![ClientMessage::Pong]);
+        assert_eq!(session.on_connect()[1], ClientMessage::Resume { game_id: "g1".to_string() });
+
+        let over = session.handle(parse(r#"{"type":"game_over","game_id":"g1","result":"resigned"}"#)).unwrap();
+        assert!(over.is_empty() && session.is_done());
+    }
+
+    #[test]
+    fn test_session_rejects_an_impossible_game() {
+        let mut session = BridgeSession::new(quick_config());
+        let twice = r#"{"type":"game","game_id":"g2","color":"White","moves":[{"row":9,"col":9},{"row":9,"col":9}]}"#;
+        assert!(session.handle(parse(twice)).is_err());
+        let off_board = r#"{"type":"game","game_id":"g3","color":"White","moves":[{"row":200,"col":9}]}"#;
+        assert!(session.handle(parse(off_board)).unwrap_err().contains("(200, 9) is off the board"));
+        assert_eq!(session.on_connect()[1], ClientMessage::Seek);
+    }
+
+    #[test]
+    fn test_play_on_a_local_host() {
+        let (mut host, server) = LocalHost::pair();
+        let harness = thread::spawn(move || {
+            assert!(matches!(server.incoming.recv().unwrap(), ClientMessage::Hello { .. }));
+            assert_eq!(server.incoming.recv().unwrap(), ClientMessage::Seek);
+            let game_id = "local".to_string();
+            let start = ServerMessage::Game {
+                game_id: game_id.clone(),
+                color: Stone::White,
+                moves: vec![Pos::new(9, 9)],
+                black_ms: Some(10_000),
+                white_ms: Some(10_000),
+                increment_ms: 0,
+            };
+            server.outgoing.send(start).unwrap();
+            let reply = server.incoming.recv().unwrap();
+            let clock = ServerMessage::Clock { game_id: game_id.clone(), black_ms: 9_000, white_ms: 9_500 };
+            server.outgoing.send(clock).unwrap();
+            server.outgoing.send(ServerMessage::GameOver { game_id, result: "aborted".to_string() }).unwrap();
+            reply
+        });
+
+        assert_eq!(play(&mut host, BridgeConfig { games: 1, ..quick_config() }).unwrap(), 1);
+        let ClientMessage::Move { game_id, pos } = harness.join().unwrap() else { panic!("no move") };
+        assert_eq!(game_id, "local");
+        assert_ne!(pos, Pos::new(9, 9));
+    }
+
+    #[test]
+    fn test_run_resumes_after_a_dropped_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let config = BridgeConfig { addr: listener.local_addr().unwrap().to_string(), games: 1, ..quick_config() };
+
+        let server = thread::spawn(move || {
+            let read_line = |reader: &mut BufReader<TcpStream>| {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                line
+            };
+            // First connection: start a game as White, take Black's reply, drop
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            assert!(read_line(&mut reader).contains(r#""type":"hello""#));
+            assert!(read_line(&mut reader).contains(r#""type":"seek""#));
+            let start = r#"{"type":"game","game_id":"g7","color":"White","moves":[{"row":9,"col":9}],"black_ms":60000,"white_ms":60000}"#;
+            writeln!(&stream, "{}", start).unwrap();
+            let first: serde_json::Value = serde_json::from_str(&read_line(&mut reader)).unwrap();
+            drop((stream, reader));
+
+            // Second connection: the bridge resumes; resync and finish
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            read_line(&mut reader);
+            assert!(read_line(&mut reader).contains(r#"{"type":"resume","game_id":"g7"}"#));
+            let resync = format!(
+                r#"{{"type":"game","game_id":"g7","color":"White","moves":[{{"row":9,"col":9}},{},{{"row":0,"col":0}}]}}"#,
+                first["move"]
+            );
+            writeln!(&stream, "{}", resync).unwrap();
+            let second = read_line(&mut reader);
+            writeln!(&stream, r#"{{"type":"game_over","game_id":"g7","result":"Black resigned"}}"#).unwrap();
+            second
+        });
+
+        assert_eq!(run(config).unwrap(), 1);
+        let second = server.join().unwrap();
+        assert!(second.contains(r#""type":"move","game_id":"g7""#), "{}", second);
+    }
+}
@@ -0,0 +1,430 @@
+//! Game clocks and per-move time allocation
+//!
+//! [`GameClock`] keeps both players' time under a [`TimeControl`] (Fischer
+//! increment or Japanese byo-yomi). [`TimeManager`] turns the time a player
+//! has left into a budget for the next move, spending more in sharp
+//! middlegame positions and less in the opening.
+//!
+//! # Example
+//!
+//! ```
+//! use std::time::Duration;
+//! use gomoku::clock::{GameClock, TimeControl, TimeManager};
+//! use gomoku::rules::RuleSet;
+//! use gomoku::{Board, Stone};
+//!
+//! let control = TimeControl::Fischer {
+//!     initial: Duration::from_secs(300),
+//!     increment: Duration::from_secs(3),
+//! };
+//! let mut clock = GameClock::new(control);
+//! clock.start(Stone::Black);
+//!
+//! let budget = clock.budget(&TimeManager::default(), RuleSet::NINUKI, &Board::new(), Stone::Black);
+//! assert!(budget > Duration::ZERO && budget < Duration::from_secs(300));
+//!
+//! assert!(clock.press(Stone::Black)); // Black moved in time
+//! ```
+
+use std::time::Duration;
+
+use crate::board::{Board, Stone};
+use crate::engine::{classify_position, GamePhase, PositionClass};
+use crate::rules::RuleSet;
+use crate::time::Instant;
+
+/// How much time each player gets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeControl {
+    /// Main time plus `increment` added after every move
+    Fischer { initial: Duration, increment: Duration },
+    /// Main time, then `periods` periods of `period` each. A move finished
+    /// within a period does not use it up; running over one moves on to the
+    /// next, and running out of periods loses.
+    ByoYomi { main: Duration, period: Duration, periods: u32 },
+}
+
+impl TimeControl {
+    /// Short label, e.g. `5+3` or `5m 5x30s`
+    pub fn label(&self) -> String {
+        match *self {
+            Self::Fischer { initial, increment } => {
+                format!("{}+{}", initial.as_secs() / 60, increment.as_secs())
+            }
+            Self::ByoYomi { main, period, periods } => {
+                format!("{}m {}x{}s", main.as_secs() / 60, periods, period.as_secs())
+            }
+        }
+    }
+}
+
+/// Both players' clocks
+#[derive(Debug, Clone)]
+pub struct GameClock {
+    control: TimeControl,
+    /// Main time left [Black, White]
+    main: [Duration; 2],
+    /// Byo-yomi periods left [Black, White]
+    periods: [u32; 2],
+    /// Side whose clock is running, and since when
+    running: Option<(Stone, Instant)>,
+    flagged: Option<Stone>,
+}
+
+/// Clock state after spending some time
+struct Spent {
+    main: Duration,
+    periods: u32,
+    /// Time used in the current byo-yomi period
+    period_used: Duration,
+    flagged: bool,
+}
+
+impl GameClock {
+    /// Full time for both players, no clock running.
+    pub fn new(control: TimeControl) -> Self {
+        let (main, periods) = match control {
+            TimeControl::Fischer { initial, .. } => (initial, 0),
+            TimeControl::ByoYomi { main, periods, .. } => (main, periods),
+        };
+        Self {
+            control,
+            main: [main; 2],
+            periods: [periods; 2],
+            running: None,
+            flagged: None,
+        }
+    }
+
+    /// The time control in use
+    pub fn control(&self) -> TimeControl {
+        self.control
+    }
+
+    /// Start `color`'s clock (pausing whichever clock was running).
+    pub fn start(&mut self, color: Stone) {
+        self.pause();
+        if self.flagged.is_none() {
+            self.running = Some((color, Instant::now()));
+        }
+    }
+
+    /// Stop the running clock without ending the move (no increment).
+    pub fn pause(&mut self) {
+        if let Some((color, since)) = self.running.take() {
+            self.charge(color, since.elapsed());
+        }
+    }
+
+    /// `color` finished a move: charge the time used and add the increment.
+    ///
+    /// Returns `false` if `color` ran out of time.
+    pub fn press(&mut self, color: Stone) -> bool {
+        let elapsed = match self.running.take() {
+            Some((running, since)) if running == color => since.elapsed(),
+            other => {
+                self.running = other;
+                Duration::ZERO
+            }
+        };
+        self.press_after(color, elapsed)
+    }
+
+    fn press_after(&mut self, color: Stone, elapsed: Duration) -> bool {
+        self.charge(color, elapsed);
+        if self.flagged == Some(color) {
+            return false;
+        }
+        if let TimeControl::Fischer { increment, .. } = self.control {
+            self.main[idx(color)] += increment;
+        }
+        true
+    }
+
+    /// Time `color` has left: main time, or what is left of the current
+    /// byo-yomi period once main time is used up.
+    pub fn remaining(&self, color: Stone) -> Duration {
+        let spent = self.spent(color, self.running_elapsed(color));
+        match self.control {
+            TimeControl::ByoYomi { period, .. } if spent.main.is_zero() && spent.periods > 0 => {
+                period.saturating_sub(spent.period_used)
+            }
+            _ => spent.main,
+        }
+    }
+
+    /// Byo-yomi periods `color` has left (0 under Fischer).
+    pub fn periods_left(&self, color: Stone) -> u32 {
+        self.spent(color, self.running_elapsed(color)).periods
+    }
+
+    /// Whether `color` has used up main time and is in byo-yomi.
+    pub fn in_byo_yomi(&self, color: Stone) -> bool {
+        matches!(self.control, TimeControl::ByoYomi { .. })
+            && self.spent(color, self.running_elapsed(color)).main.is_zero()
+    }
+
+    /// The side that has run out of time, if any (checked live).
+    pub fn flagged(&self) -> Option<Stone> {
+        self.flagged.or_else(|| {
+            let (color, since) = self.running?;
+            self.spent(color, since.elapsed()).flagged.then_some(color)
+        })
+    }
+
+    /// Whether a clock is running
+    pub fn is_running(&self) -> bool {
+        self.running.is_some()
+    }
+
+    /// Time `color` can use on this move and the time guaranteed for each
+    /// later move, as `(remaining, increment)` for [`TimeManager::allocate`].
+    pub fn allowance(&self, color: Stone) -> (Duration, Duration) {
+        match self.control {
+            TimeControl::Fischer { increment, .. } => (self.remaining(color), increment),
+            TimeControl::ByoYomi { period, .. } => {
+                let in_period = if self.periods_left(color) > 0 { period } else { Duration::ZERO };
+                let remaining = if self.in_byo_yomi(color) {
+                    self.remaining(color)
+                } else {
+                    self.remaining(color) + in_period
+                };
+                (remaining, in_period)
+            }
+        }
+    }
+
+    /// Budget for `color`'s next move by `rules`.
+    pub fn budget(&self, manager: &TimeManager, rules: RuleSet, board: &Board, color: Stone) -> Duration {
+        let (remaining, increment) = self.allowance(color);
+        manager.allocate(rules, remaining, increment, board, color)
+    }
+
+    fn running_elapsed(&self, color: Stone) -> Duration {
+        match self.running {
+            Some((running, since)) if running == color => since.elapsed(),
+            _ => Duration::ZERO,
+        }
+    }
+
+    fn charge(&mut self, color: Stone, elapsed: Duration) {
+        let spent = self.spent(color, elapsed);
+        self.main[idx(color)] = spent.main;
+        self.periods[idx(color)] = spent.periods;
+        if spent.flagged {
+            self.flagged = Some(color);
+            self.running = None;
+        }
+    }
+
+    fn spent(&self, color: Stone, elapsed: Duration) -> Spent {
+        let main = self.main[idx(color)];
+        let periods = self.periods[idx(color)];
+        if elapsed <= main {
+            return Spent { main: main - elapsed, periods, period_used: Duration::ZERO, flagged: false };
+        }
+
+        let mut over = elapsed - main;
+        let mut periods = periods;
+        if let TimeControl::ByoYomi { period, .. } = self.control {
+            while periods > 0 && over >= period {
+                over -= period;
+                periods -= 1;
+            }
+        }
+        Spent { main: Duration::ZERO, periods, period_used: over, flagged: periods == 0 }
+    }
+}
+
+fn idx(color: Stone) -> usize {
+    if color == Stone::White { 1 } else { 0 }
+}
+
+/// Per-move time allocation from the time left on the clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeManager {
+    /// Moves per side a game is assumed to last
+    pub expected_moves: u32,
+    /// Fewest moves the remaining time is ever split over
+    pub min_moves_to_go: u32,
+    /// Held back from every move for GUI/thread overhead
+    pub safety_margin: Duration,
+    /// Smallest budget handed out (time permitting)
+    pub min_budget: Duration,
+}
+
+impl Default for TimeManager {
+    fn default() -> Self {
+        Self {
+            expected_moves: 40,
+            min_moves_to_go: 10,
+            safety_margin: Duration::from_millis(50),
+            min_budget: Duration::from_millis(50),
+        }
+    }
+}
+
+impl TimeManager {
+    /// Budget for `color`'s next move by `rules`, with `remaining` on the
+    /// clock and `increment` guaranteed for every later move.
+    ///
+    /// The base share is `remaining / moves_to_go` plus most of the
+    /// increment. It is scaled by [`classify_position`]: down in the opening
+    /// and when `color` has an open four, up when the position is tactical
+    /// (threats on the board) or a capture win is near. It never exceeds a quarter of the remaining time beyond the increment.
+    pub fn allocate(
+        &self,
+        rules: RuleSet,
+        remaining: Duration,
+        increment: Duration,
+        board: &Board,
+        color: Stone,
+    ) -> Duration {
+        let usable = remaining.saturating_sub(self.safety_margin);
+        if usable.is_zero() {
+            return Duration::ZERO;
+        }
+
+        let captured = u32::from(board.captures(Stone::Black)) + u32::from(board.captures(Stone::White));
+        let played = (board.stone_count() + u32::from(rules.pair_size()) * captured) / 2;
+        let moves_to_go = self.expected_moves.saturating_sub(played).max(self.min_moves_to_go).max(1);
+        let base = remaining / moves_to_go + increment * 3 / 4;
+
+        let class = classify_position(rules, board);
+        let pct = Self::phase_percent(board, class.phase) * Self::complexity_percent(&class, color) / 100;
+        let budget = base * pct / 100;
+
+        let soft_cap = remaining / 4 + increment * 3 / 4;
+        budget.min(soft_cap).max(self.min_budget).min(usable)
+    }
+
+    /// Opening positions are simple; spend less on them, least on the
+    /// first few moves
+    fn phase_percent(board: &Board, phase: GamePhase) -> u32 {
+        match phase {
+            GamePhase::Opening if board.stone_count() <= 4 => 40,
+            GamePhase::Opening => 80,
+            GamePhase::Midgame | GamePhase::Endgame => 100,
+        }
+    }
+
+    /// Spend more when either side has threats or a capture win is close,
+    /// little when `color` has an open four to complete
+    fn complexity_percent(class: &PositionClass, color: Stone) -> u32 {
+        if class.threats(color).open_fours > 0 {
+            return 50;
+        }
+        let mut pct = 100;
+        if class.black.is_tactical() || class.white.is_tactical() {
+            pct += 50;
+        }
+        if class.capture_win_within(2) {
+            pct += 25;
+        }
+        pct
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Pos;
+
+    const FISCHER: TimeControl = TimeControl::Fischer {
+        initial: Duration::from_secs(60),
+        increment: Duration::from_secs(2),
+    };
+    const BYO_YOMI: TimeControl = TimeControl::ByoYomi {
+        main: Duration::from_secs(10),
+        period: Duration::from_secs(5),
+        periods: 2,
+    };
+
+    #[test]
+    fn test_fischer_increment_and_flag() {
+        let mut clock = GameClock::new(FISCHER);
+        assert!(clock.press_after(Stone::Black, Duration::from_secs(10)));
+        assert_eq!(clock.remaining(Stone::Black), Duration::from_secs(52));
+        assert_eq!(clock.remaining(Stone::White), Duration::from_secs(60));
+
+        assert!(!clock.press_after(Stone::White, Duration::from_secs(61)));
+        assert_eq!(clock.flagged(), Some(Stone::White));
+        assert_eq!(clock.remaining(Stone::White), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_byo_yomi_periods() {
+        let mut clock = GameClock::new(BYO_YOMI);
+        // Main time runs out, move finished inside the first period
+        assert!(clock.press_after(Stone::Black, Duration::from_secs(13)));
+        assert!(clock.in_byo_yomi(Stone::Black));
+        assert_eq!(clock.periods_left(Stone::Black), 2);
+        assert_eq!(clock.remaining(Stone::Black), Duration::from_secs(5));
+
+        // Over one period: lose it, keep the last
+        assert!(clock.press_after(Stone::Black, Duration::from_secs(7)));
+        assert_eq!(clock.periods_left(Stone::Black), 1);
+
+        // Over the last period: flag
+        assert!(!clock.press_after(Stone::Black, Duration::from_secs(5)));
+        assert_eq!(clock.flagged(), Some(Stone::Black));
+    }
+
+    #[test]
+    fn test_allocation_scales_with_phase_and_time() {
+        let manager = TimeManager::default();
+        let opening = Board::new();
+        let mut middlegame = Board::new();
+        for i in 0..6 {
+            middlegame.place_stone(Pos::new(9, 4 + 2 * i), Stone::Black);
+            middlegame.place_stone(Pos::new(10, 4 + 2 * i), Stone::White);
+        }
+
+        let minute = Duration::from_secs(60);
+        let early = manager.allocate(RuleSet::NINUKI, minute, Duration::ZERO, &opening, Stone::Black);
+        let mid = manager.allocate(RuleSet::NINUKI, minute, Duration::ZERO, &middlegame, Stone::Black);
+        assert!(early < mid, "opening {:?} should get less than middlegame {:?}", early, mid);
+        assert!(mid <= minute / 4);
+
+        let short =
+            manager.allocate(RuleSet::NINUKI, Duration::from_secs(5), Duration::ZERO, &middlegame, Stone::Black);
+        assert!(short < mid);
+        // Never more than what is on the clock
+        let last =
+            manager.allocate(RuleSet::NINUKI, Duration::from_millis(80), Duration::ZERO, &middlegame, Stone::Black);
+        assert!(last <= Duration::from_millis(30));
+    }
+
+    #[test]
+    fn test_allocation_follows_position_class() {
+        let manager = TimeManager::default();
+        let minute = Duration::from_secs(60);
+        let mut board = Board::new();
+        for i in 0..6 {
+            board.place_stone(Pos::new(3, 2 * i), Stone::Black);
+            board.place_stone(Pos::new(15, 2 * i), Stone::White);
+        }
+        let quiet = manager.allocate(RuleSet::NINUKI, minute, Duration::ZERO, &board, Stone::White);
+
+        // Black open four: White has to think, Black just completes it
+        for col in 5..9 {
+            board.place_stone(Pos::new(9, col), Stone::Black);
+        }
+        let defending = manager.allocate(RuleSet::NINUKI, minute, Duration::ZERO, &board, Stone::White);
+        let winning = manager.allocate(RuleSet::NINUKI, minute, Duration::ZERO, &board, Stone::Black);
+        assert!(defending > quiet, "{:?} vs {:?}", defending, quiet);
+        assert!(winning < quiet, "{:?} vs {:?}", winning, quiet);
+    }
+
+    #[test]
+    fn test_byo_yomi_allowance() {
+        let mut clock = GameClock::new(BYO_YOMI);
+        assert_eq!(clock.allowance(Stone::Black), (Duration::from_secs(15), Duration::from_secs(5)));
+        clock.press_after(Stone::Black, Duration::from_secs(10));
+        assert_eq!(clock.allowance(Stone::Black), (Duration::from_secs(5), Duration::from_secs(5)));
+
+        let budget = clock.budget(&TimeManager::default(), RuleSet::NINUKI, &Board::new(), Stone::Black);
+        assert!(budget < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,656 @@
+//! The rule case table
+//!
+//! Hand-written cases cover specific situations; generated families sweep
+//! the same pattern over every edge cell and direction. Coordinates are
+//! `(row, col)`, 0-based from the top-left corner.
+
+use super::{Category, Outcome, RuleCase};
+use crate::board::{Board, Pos, Stone, BOARD_SIZE};
+use crate::rules::RuleSet;
+
+/// All eight directions with their compass names (row grows southward)
+const DIRECTIONS: [(i32, i32, &str); 8] = [
+    (-1, 0, "n"),
+    (-1, 1, "ne"),
+    (0, 1, "e"),
+    (1, 1, "se"),
+    (1, 0, "s"),
+    (1, -1, "sw"),
+    (0, -1, "w"),
+    (-1, -1, "nw"),
+];
+
+const CENTER: (u8, u8) = (9, 9);
+
+/// Every case in the suite
+pub fn all_cases() -> Vec<RuleCase> {
+    let mut cases = Vec::new();
+    cases.extend(legality_cases());
+    cases.extend(capture_cases());
+    cases.extend(no_capture_cases());
+    cases.extend(edge_capture_cases());
+    cases.extend(edge_pinned_pair_cases());
+    cases.extend(five_cases());
+    cases.extend(edge_five_cases());
+    cases.extend(capture_win_cases());
+    cases.extend(double_three_cases());
+    cases.extend(breakable_five_cases());
+    cases
+}
+
+/// Case under construction: a position, then `play(..)` and an expectation
+struct CaseBuilder {
+    name: String,
+    category: Category,
+    board: Board,
+    to_move: Stone,
+    mov: Pos,
+}
+
+fn case(name: impl Into<String>, category: Category) -> CaseBuilder {
+    CaseBuilder {
+        name: name.into(),
+        category,
+        board: Board::new(),
+        to_move: Stone::Black,
+        mov: Pos::new(CENTER.0, CENTER.1),
+    }
+}
+
+impl CaseBuilder {
+    fn black(self, cells: &[(u8, u8)]) -> Self {
+        self.stones(cells, Stone::Black)
+    }
+
+    fn white(self, cells: &[(u8, u8)]) -> Self {
+        self.stones(cells, Stone::White)
+    }
+
+    fn stones(mut self, cells: &[(u8, u8)], color: Stone) -> Self {
+        for &(r, c) in cells {
+            self.board.place_stone(Pos::new(r, c), color);
+        }
+        self
+    }
+
+    fn captures(mut self, black: u8, white: u8) -> Self {
+        self.board.set_captures(RuleSet::NINUKI, Stone::Black, black).unwrap();
+        self.board.set_captures(RuleSet::NINUKI, Stone::White, white).unwrap();
+        self
+    }
+
+    fn play(mut self, color: Stone, (r, c): (u8, u8)) -> Self {
+        self.to_move = color;
+        self.mov = Pos::new(r, c);
+        self
+    }
+
+    fn forbidden(self) -> RuleCase {
+        self.finish(Outcome::illegal())
+    }
+
+    fn expect(self, captured: &[(u8, u8)], winner: Option<Stone>) -> RuleCase {
+        let captured = captured.iter().map(|&(r, c)| Pos::new(r, c)).collect();
+        self.finish(Outcome::legal(captured, winner))
+    }
+
+    fn finish(self, expected: Outcome) -> RuleCase {
+        RuleCase {
+            name: self.name,
+            category: self.category,
+            board: self.board,
+            to_move: self.to_move,
+            mov: self.mov,
+            expected,
+        }
+    }
+}
+
+/// Cell `k` steps from `(r, c)` along `(dr, dc)`, if on the board
+fn step((r, c): (u8, u8), (dr, dc): (i32, i32), k: i32) -> Option<(u8, u8)> {
+    let (nr, nc) = (i32::from(r) + dr * k, i32::from(c) + dc * k);
+    Pos::is_valid(nr, nc).then_some((nr as u8, nc as u8))
+}
+
+fn is_border((r, c): (u8, u8)) -> bool {
+    let last = (BOARD_SIZE - 1) as u8;
+    r == 0 || c == 0 || r == last || c == last
+}
+
+fn border_cells() -> impl Iterator<Item = (u8, u8)> {
+    let n = BOARD_SIZE as u8;
+    (0..n).flat_map(move |r| (0..n).map(move |c| (r, c))).filter(|&cell| is_border(cell))
+}
+
+fn legality_cases() -> Vec<RuleCase> {
+    vec![
+        case("occupied-by-own-stone", Category::Legality)
+            .black(&[(9, 9)])
+            .play(Stone::Black, (9, 9))
+            .forbidden(),
+        case("occupied-by-opponent-stone", Category::Legality)
+            .white(&[(9, 9)])
+            .play(Stone::Black, (9, 9))
+            .forbidden(),
+        case("first-move-in-corner", Category::Legality)
+            .play(Stone::Black, (0, 0))
+            .expect(&[], None),
+        case("first-move-in-opposite-corner", Category::Legality)
+            .play(Stone::White, (18, 18))
+            .expect(&[], None),
+        case("surrounded-point-is-not-suicide", Category::Legality)
+            .white(&[(8, 9), (10, 9), (9, 8), (9, 10)])
+            .play(Stone::Black, (9, 9))
+            .expect(&[], None),
+        case("surrounded-corner-is-not-suicide", Category::Legality)
+            .black(&[(0, 1), (1, 0), (1, 1)])
+            .play(Stone::White, (0, 0))
+            .expect(&[], None),
+    ]
+}
+
+fn capture_cases() -> Vec<RuleCase> {
+    let mut cases = Vec::new();
+
+    for (color, name) in [(Stone::Black, "black"), (Stone::White, "white")] {
+        for (dr, dc, dir) in DIRECTIONS {
+            let d = (dr, dc);
+            let pair = [step(CENTER, d, 1).unwrap(), step(CENTER, d, 2).unwrap()];
+            let flank = step(CENTER, d, 3).unwrap();
+            cases.push(
+                case(format!("capture-{}-{}", name, dir), Category::Capture)
+                    .stones(&pair, color.opponent())
+                    .stones(&[flank], color)
+                    .play(color, CENTER)
+                    .expect(&pair, None),
+            );
+        }
+    }
+
+    cases.push(
+        case("capture-two-directions", Category::Capture)
+            .white(&[(9, 10), (9, 11), (10, 9), (11, 9)])
+            .black(&[(9, 12), (12, 9)])
+            .play(Stone::Black, CENTER)
+            .expect(&[(9, 10), (9, 11), (10, 9), (11, 9)], None),
+    );
+    cases.push(
+        case("capture-both-sides-of-line", Category::Capture)
+            .white(&[(9, 7), (9, 8), (9, 10), (9, 11)])
+            .black(&[(9, 6), (9, 12)])
+            .play(Stone::Black, CENTER)
+            .expect(&[(9, 7), (9, 8), (9, 10), (9, 11)], None),
+    );
+    cases.push(
+        case("capture-counts-only-the-pair", Category::Capture)
+            .white(&[(9, 10), (9, 11), (9, 13)])
+            .black(&[(9, 12)])
+            .play(Stone::Black, CENTER)
+            .expect(&[(9, 10), (9, 11)], None),
+    );
+    cases.push(
+        case("capture-with-existing-captures", Category::Capture)
+            .white(&[(9, 10), (9, 11)])
+            .black(&[(9, 12)])
+            .captures(2, 3)
+            .play(Stone::Black, CENTER)
+            .expect(&[(9, 10), (9, 11)], None),
+    );
+    cases
+}
+
+fn no_capture_cases() -> Vec<RuleCase> {
+    vec![
+        case("three-stones-are-not-captured", Category::NoCapture)
+            .white(&[(9, 10), (9, 11), (9, 12)])
+            .black(&[(9, 13)])
+            .play(Stone::Black, CENTER)
+            .expect(&[], None),
+        case("single-stone-is-not-captured", Category::NoCapture)
+            .white(&[(9, 10)])
+            .black(&[(9, 11)])
+            .play(Stone::Black, CENTER)
+            .expect(&[], None),
+        case("pair-with-empty-far-end", Category::NoCapture)
+            .white(&[(9, 10), (9, 11)])
+            .play(Stone::Black, CENTER)
+            .expect(&[], None),
+        case("pair-with-gap", Category::NoCapture)
+            .white(&[(9, 10), (9, 12)])
+            .black(&[(9, 13)])
+            .play(Stone::Black, CENTER)
+            .expect(&[], None),
+        case("bent-pair", Category::NoCapture)
+            .white(&[(9, 10), (10, 11)])
+            .black(&[(11, 12), (9, 12)])
+            .play(Stone::Black, CENTER)
+            .expect(&[], None),
+        case("moving-into-flanked-pair-is-safe", Category::NoCapture)
+            .black(&[(9, 8), (9, 11)])
+            .white(&[(9, 9)])
+            .play(Stone::White, (9, 10))
+            .expect(&[], None),
+        case("moving-into-flanked-diagonal-is-safe", Category::NoCapture)
+            .black(&[(6, 6), (9, 9)])
+            .white(&[(7, 7)])
+            .play(Stone::White, (8, 8))
+            .expect(&[], None),
+        case("own-pair-is-not-captured", Category::NoCapture)
+            .black(&[(9, 10), (9, 11), (9, 12)])
+            .play(Stone::Black, CENTER)
+            .expect(&[], None),
+        case("refilling-captured-gap-is-safe", Category::NoCapture)
+            .black(&[(5, 5), (5, 8)])
+            .white(&[(5, 6)])
+            .captures(1, 0)
+            .play(Stone::White, (5, 7))
+            .expect(&[], None),
+    ]
+}
+
+/// X-O-O-X on every edge cell and direction, with the capturing move on the
+/// edge ("from") or the flanking stone on the edge ("toward").
+fn edge_capture_cases() -> Vec<RuleCase> {
+    let mut cases = Vec::new();
+
+    for edge in border_cells() {
+        for (dr, dc, dir) in DIRECTIONS {
+            let d = (dr, dc);
+            let Some(far) = step(edge, d, 3) else {
+                continue;
+            };
+            let pair = [step(edge, d, 1).unwrap(), step(edge, d, 2).unwrap()];
+
+            cases.push(
+                case(format!("edge-capture-from-{}-{}-{}", edge.0, edge.1, dir), Category::EdgeCapture)
+                    .white(&pair)
+                    .black(&[far])
+                    .play(Stone::Black, edge)
+                    .expect(&pair, None),
+            );
+            // Along the edge both ends are border cells: the "from" case of
+            // the other end already covers it.
+            if !is_border(far) {
+                cases.push(
+                    case(format!("edge-capture-toward-{}-{}-{}", edge.0, edge.1, dir), Category::EdgeCapture)
+                        .black(&pair)
+                        .white(&[edge])
+                        .play(Stone::White, far)
+                        .expect(&pair, None),
+                );
+            }
+        }
+    }
+    cases
+}
+
+/// A pair touching the edge has no flanking cell beyond it: never captured.
+fn edge_pinned_pair_cases() -> Vec<RuleCase> {
+    let mut cases = Vec::new();
+
+    for edge in border_cells() {
+        for (dr, dc, dir) in DIRECTIONS {
+            let d = (dr, dc);
+            if step(edge, d, -1).is_some() {
+                continue;
+            }
+            let Some(mov) = step(edge, d, 2) else {
+                continue;
+            };
+            let pair = [edge, step(edge, d, 1).unwrap()];
+            cases.push(
+                case(format!("edge-pinned-pair-{}-{}-{}", edge.0, edge.1, dir), Category::NoCapture)
+                    .white(&pair)
+                    .play(Stone::Black, mov)
+                    .expect(&[], None),
+            );
+        }
+    }
+    cases
+}
+
+fn five_cases() -> Vec<RuleCase> {
+    vec![
+        case("five-horizontal", Category::Five)
+            .black(&[(9, 5), (9, 6), (9, 7), (9, 8)])
+            .play(Stone::Black, (9, 9))
+            .expect(&[], Some(Stone::Black)),
+        case("five-vertical-filled-in-middle", Category::Five)
+            .black(&[(5, 9), (6, 9), (8, 9), (9, 9)])
+            .play(Stone::Black, (7, 9))
+            .expect(&[], Some(Stone::Black)),
+        case("five-diagonal", Category::Five)
+            .black(&[(5, 5), (6, 6), (7, 7), (8, 8)])
+            .play(Stone::Black, (9, 9))
+            .expect(&[], Some(Stone::Black)),
+        case("five-anti-diagonal", Category::Five)
+            .black(&[(5, 13), (6, 12), (7, 11), (8, 10)])
+            .play(Stone::Black, (9, 9))
+            .expect(&[], Some(Stone::Black)),
+        case("five-white", Category::Five)
+            .white(&[(3, 3), (3, 4), (3, 5), (3, 6)])
+            .play(Stone::White, (3, 7))
+            .expect(&[], Some(Stone::White)),
+        case("five-blocked-both-ends-still-wins", Category::Five)
+            .black(&[(9, 5), (9, 6), (9, 7), (9, 8)])
+            .white(&[(9, 4), (9, 10)])
+            .play(Stone::Black, (9, 9))
+            .expect(&[], Some(Stone::Black)),
+        case("overline-wins", Category::Five)
+            .black(&[(9, 4), (9, 5), (9, 6), (9, 8), (9, 9)])
+            .play(Stone::Black, (9, 7))
+            .expect(&[], Some(Stone::Black)),
+        case("seven-in-a-row-wins", Category::Five)
+            .black(&[(4, 9), (5, 9), (6, 9), (8, 9), (9, 9), (10, 9)])
+            .play(Stone::Black, (7, 9))
+            .expect(&[], Some(Stone::Black)),
+        case("four-does-not-win", Category::Five)
+            .black(&[(9, 5), (9, 6), (9, 7)])
+            .play(Stone::Black, (9, 8))
+            .expect(&[], None),
+        case("split-five-does-not-win", Category::Five)
+            .black(&[(9, 5), (9, 6), (9, 8), (9, 9)])
+            .play(Stone::Black, (9, 10))
+            .expect(&[], None),
+        case("five-mixed-colors-does-not-win", Category::Five)
+            .black(&[(9, 5), (9, 6), (9, 8)])
+            .white(&[(9, 7)])
+            .play(Stone::Black, (9, 9))
+            .expect(&[], None),
+        case("five-that-also-captures", Category::Five)
+            .black(&[(9, 5), (9, 6), (9, 7), (9, 8), (12, 12)])
+            .white(&[(10, 10), (11, 11)])
+            .play(Stone::Black, (9, 9))
+            .expect(&[(10, 10), (11, 11)], Some(Stone::Black)),
+    ]
+}
+
+/// Fives lying along each edge and along the corner diagonals.
+fn edge_five_cases() -> Vec<RuleCase> {
+    let mut cases = Vec::new();
+    let last = (BOARD_SIZE - 1) as u8;
+    let edges = [
+        ((0, 0), (0, 1), "top"),
+        ((last, 0), (0, 1), "bottom"),
+        ((0, 0), (1, 0), "left"),
+        ((0, last), (1, 0), "right"),
+    ];
+
+    for (origin, d, edge) in edges {
+        for start in 0..=(BOARD_SIZE as i32 - 5) {
+            let line: Vec<(u8, u8)> = (0..5).map(|k| step(origin, d, start + k).unwrap()).collect();
+            for gap in [0, 2, 4] {
+                let stones: Vec<_> = line.iter().enumerate().filter(|&(i, _)| i != gap).map(|(_, &p)| p).collect();
+                cases.push(
+                    case(format!("edge-five-{}-{}-gap{}", edge, start, gap), Category::Five)
+                        .black(&stones)
+                        .play(Stone::Black, line[gap])
+                        .expect(&[], Some(Stone::Black)),
+                );
+            }
+        }
+    }
+
+    let corners = [
+        ((0, 0), (1, 1), "nw"),
+        ((0, last), (1, -1), "ne"),
+        ((last, 0), (-1, 1), "sw"),
+        ((last, last), (-1, -1), "se"),
+    ];
+    for (corner, d, name) in corners {
+        let line: Vec<(u8, u8)> = (0..5).map(|k| step(corner, d, k).unwrap()).collect();
+        for gap in 0..5 {
+            let stones: Vec<_> = line.iter().enumerate().filter(|&(i, _)| i != gap).map(|(_, &p)| p).collect();
+            cases.push(
+                case(format!("corner-five-{}-gap{}", name, gap), Category::Five)
+                    .white(&stones)
+                    .play(Stone::White, line[gap])
+                    .expect(&[], Some(Stone::White)),
+            );
+        }
+    }
+    cases
+}
+
+fn capture_win_cases() -> Vec<RuleCase> {
+    let east_pair = [(9, 10), (9, 11)];
+    let south_pair = [(10, 9), (11, 9)];
+
+    let mut all_pairs = Vec::new();
+    let mut all_flanks = Vec::new();
+    for (dr, dc, _) in DIRECTIONS {
+        all_pairs.push(step(CENTER, (dr, dc), 1).unwrap());
+        all_pairs.push(step(CENTER, (dr, dc), 2).unwrap());
+        all_flanks.push(step(CENTER, (dr, dc), 3).unwrap());
+    }
+
+    vec![
+        case("fifth-pair-wins", Category::CaptureWin)
+            .white(&east_pair)
+            .black(&[(9, 12)])
+            .captures(4, 0)
+            .play(Stone::Black, CENTER)
+            .expect(&east_pair, Some(Stone::Black)),
+        case("fifth-pair-wins-white", Category::CaptureWin)
+            .black(&east_pair)
+            .white(&[(9, 12)])
+            .captures(0, 4)
+            .play(Stone::White, CENTER)
+            .expect(&east_pair, Some(Stone::White)),
+        case("fourth-pair-does-not-win", Category::CaptureWin)
+            .white(&east_pair)
+            .black(&[(9, 12)])
+            .captures(3, 0)
+            .play(Stone::Black, CENTER)
+            .expect(&east_pair, None),
+        case("two-pairs-from-three-wins", Category::CaptureWin)
+            .white(&[east_pair[0], east_pair[1], south_pair[0], south_pair[1]])
+            .black(&[(9, 12), (12, 9)])
+            .captures(3, 0)
+            .play(Stone::Black, CENTER)
+            .expect(&[east_pair[0], east_pair[1], south_pair[0], south_pair[1]], Some(Stone::Black)),
+        case("eight-pairs-in-one-move-wins", Category::CaptureWin)
+            .white(&all_pairs)
+            .black(&all_flanks)
+            .play(Stone::Black, CENTER)
+            .expect(&all_pairs, Some(Stone::Black)),
+        case("opponent-at-four-pairs-quiet-move", Category::CaptureWin)
+            .black(&[(3, 3)])
+            .white(&[(15, 15)])
+            .captures(0, 4)
+            .play(Stone::Black, CENTER)
+            .expect(&[], None),
+        case("five-with-fifth-pair", Category::CaptureWin)
+            .black(&[(9, 5), (9, 6), (9, 7), (9, 8), (12, 12)])
+            .white(&[(10, 10), (11, 11)])
+            .captures(4, 0)
+            .play(Stone::Black, (9, 9))
+            .expect(&[(10, 10), (11, 11)], Some(Stone::Black)),
+    ]
+}
+
+fn double_three_cases() -> Vec<RuleCase> {
+    // Open threes through the center: horizontal (9,7)-(9,9), vertical (7,9)-(9,9)
+    let horizontal = [(9, 7), (9, 8)];
+    let vertical = [(7, 9), (8, 9)];
+
+    vec![
+        case("double-three-cross", Category::DoubleThree)
+            .black(&horizontal)
+            .black(&vertical)
+            .play(Stone::Black, CENTER)
+            .forbidden(),
+        case("double-three-white", Category::DoubleThree)
+            .white(&horizontal)
+            .white(&vertical)
+            .play(Stone::White, CENTER)
+            .forbidden(),
+        case("double-three-placed-stone-in-middle", Category::DoubleThree)
+            .black(&[(9, 8), (9, 10), (8, 9), (10, 9)])
+            .play(Stone::Black, CENTER)
+            .forbidden(),
+        case("double-three-placed-stone-at-end", Category::DoubleThree)
+            .black(&[(9, 10), (9, 11), (10, 9), (11, 9)])
+            .play(Stone::Black, CENTER)
+            .forbidden(),
+        case("double-three-diagonals", Category::DoubleThree)
+            .black(&[(7, 7), (8, 8), (7, 11), (8, 10)])
+            .play(Stone::Black, CENTER)
+            .forbidden(),
+        case("double-three-gapped-and-solid", Category::DoubleThree)
+            .black(&[(9, 6), (9, 7)])
+            .black(&vertical)
+            .play(Stone::Black, CENTER)
+            .forbidden(),
+        case("double-three-both-gapped", Category::DoubleThree)
+            .black(&[(9, 6), (9, 7), (6, 9), (7, 9)])
+            .play(Stone::Black, CENTER)
+            .forbidden(),
+        case("double-three-gap-next-to-move", Category::DoubleThree)
+            .black(&[(9, 11), (9, 12)])
+            .black(&vertical)
+            .play(Stone::Black, CENTER)
+            .forbidden(),
+        case("double-three-opponent-two-cells-away", Category::DoubleThree)
+            .black(&horizontal)
+            .black(&vertical)
+            .white(&[(9, 5)])
+            .play(Stone::Black, CENTER)
+            .forbidden(),
+        case("three-blocked-by-adjacent-opponent", Category::DoubleThree)
+            .black(&horizontal)
+            .black(&vertical)
+            .white(&[(9, 10)])
+            .play(Stone::Black, CENTER)
+            .expect(&[], None),
+        case("three-blocked-at-far-end", Category::DoubleThree)
+            .black(&horizontal)
+            .black(&vertical)
+            .white(&[(9, 6)])
+            .play(Stone::Black, CENTER)
+            .expect(&[], None),
+        case("three-against-top-edge-is-not-free", Category::DoubleThree)
+            .black(&[(0, 1), (0, 2), (1, 3), (2, 3)])
+            .play(Stone::Black, (0, 3))
+            .expect(&[], None),
+        case("three-against-left-edge-is-not-free", Category::DoubleThree)
+            .black(&[(1, 0), (2, 0), (3, 1), (3, 2)])
+            .play(Stone::Black, (3, 0))
+            .expect(&[], None),
+        case("spread-stones-are-not-a-three", Category::DoubleThree)
+            .black(&[(9, 5), (9, 7)])
+            .black(&vertical)
+            .play(Stone::Black, CENTER)
+            .expect(&[], None),
+        case("three-and-four-is-allowed", Category::DoubleThree)
+            .black(&horizontal)
+            .black(&[(6, 9), (7, 9), (8, 9)])
+            .play(Stone::Black, CENTER)
+            .expect(&[], None),
+        case("three-and-two-is-allowed", Category::DoubleThree)
+            .black(&[(9, 8)])
+            .black(&vertical)
+            .play(Stone::Black, CENTER)
+            .expect(&[], None),
+        case("double-three-with-capture-is-allowed", Category::DoubleThree)
+            .black(&horizontal)
+            .black(&vertical)
+            .white(&[(10, 10), (11, 11)])
+            .black(&[(12, 12)])
+            .play(Stone::Black, CENTER)
+            .expect(&[(10, 10), (11, 11)], None),
+        case("double-three-with-capture-is-allowed-white", Category::DoubleThree)
+            .white(&horizontal)
+            .white(&vertical)
+            .black(&[(8, 10), (7, 11)])
+            .white(&[(6, 12)])
+            .play(Stone::White, CENTER)
+            .expect(&[(7, 11), (8, 10)], None),
+    ]
+}
+
+fn breakable_five_cases() -> Vec<RuleCase> {
+    // Black's five on row 9 with a vertical pair (9,7)-(10,7) that White,
+    // already on (8,7), can capture by playing (11,7).
+    let four = [(9, 5), (9, 6), (9, 7), (9, 8)];
+    let five = [(9, 5), (9, 6), (9, 7), (9, 8), (9, 9)];
+    let pair_tail = (10, 7);
+    let flank = (8, 7);
+    let break_point = (11, 7);
+
+    vec![
+        case("breakable-five-does-not-win-yet", Category::BreakableFive)
+            .black(&four)
+            .black(&[pair_tail])
+            .white(&[flank])
+            .play(Stone::Black, (9, 9))
+            .expect(&[], None),
+        case("five-with-filled-break-point-wins", Category::BreakableFive)
+            .black(&four)
+            .black(&[pair_tail, break_point])
+            .white(&[flank])
+            .play(Stone::Black, (9, 9))
+            .expect(&[], Some(Stone::Black)),
+        case("unflanked-pair-cannot-break-five", Category::BreakableFive)
+            .black(&four)
+            .black(&[pair_tail])
+            .play(Stone::Black, (9, 9))
+            .expect(&[], Some(Stone::Black)),
+        case("breakable-five-diagonal-pair", Category::BreakableFive)
+            .black(&[(5, 9), (6, 9), (7, 9), (8, 9), (8, 10)])
+            .white(&[(6, 8)])
+            .play(Stone::Black, (9, 9))
+            .expect(&[], None),
+        case("unbroken-five-wins-for-its-owner", Category::BreakableFive)
+            .black(&five)
+            .black(&[pair_tail])
+            .white(&[flank])
+            .play(Stone::White, (0, 0))
+            .expect(&[], Some(Stone::Black)),
+        case("capture-breaks-five", Category::BreakableFive)
+            .black(&five)
+            .black(&[pair_tail])
+            .white(&[flank])
+            .play(Stone::White, break_point)
+            .expect(&[(9, 7), pair_tail], None),
+        case("breaking-capture-as-fifth-pair-wins", Category::BreakableFive)
+            .black(&five)
+            .black(&[pair_tail])
+            .white(&[flank])
+            .captures(0, 4)
+            .play(Stone::White, break_point)
+            .expect(&[(9, 7), pair_tail], Some(Stone::White)),
+        case("capture-elsewhere-does-not-break-five", Category::BreakableFive)
+            .black(&five)
+            .black(&[pair_tail, (3, 4), (3, 5)])
+            .white(&[flank, (3, 3)])
+            .play(Stone::White, (3, 6))
+            .expect(&[(3, 4), (3, 5)], Some(Stone::Black)),
+        case("fifth-pair-elsewhere-beats-standing-five", Category::BreakableFive)
+            .black(&five)
+            .black(&[pair_tail, (3, 4), (3, 5)])
+            .white(&[flank, (3, 3)])
+            .captures(0, 4)
+            .play(Stone::White, (3, 6))
+            .expect(&[(3, 4), (3, 5)], Some(Stone::White)),
+        case("own-five-loses-to-standing-five", Category::BreakableFive)
+            .black(&five)
+            .black(&[pair_tail])
+            .white(&[flank, (15, 3), (15, 4), (15, 5), (15, 6)])
+            .play(Stone::White, (15, 7))
+            .expect(&[], Some(Stone::Black)),
+        // After the break: (9,7) and (10,7) are empty, White holds (8,7) and (11,7)
+        case("reformed-five-without-pair-wins", Category::BreakableFive)
+            .black(&[(9, 5), (9, 6), (9, 8), (9, 9)])
+            .white(&[flank, break_point])
+            .captures(0, 1)
+            .play(Stone::Black, (9, 7))
+            .expect(&[], Some(Stone::Black)),
+        case("reformed-five-breakable-again", Category::BreakableFive)
+            .black(&[(9, 5), (9, 6), (9, 8), (9, 9), (10, 8)])
+            .white(&[flank, break_point, (8, 8)])
+            .captures(0, 1)
+            .play(Stone::Black, (9, 7))
+            .expect(&[], None),
+    ]
+}
@@ -0,0 +1,261 @@
+//! Ninuki-renju rule conformance suite (feature `conformance`)
+//!
+//! A table of rule cases, each a position plus one move and the expected
+//! ruling: whether the move is legal, which stones it captures and whether
+//! it decides the game. The cases cover the corners of the rules that
+//! engines most often get wrong:
+//!
+//! - Captures against every edge and corner, in all eight directions
+//! - Patterns that look like captures but are not (`X O O O X`, moving into
+//!   a flanked pair, pairs pinned against the edge)
+//! - Double-three corner cases (gapped threes, edge-blocked threes, the
+//!   capture exception)
+//! - Capture wins, several captures in one move
+//! - Breakable fives, breaking them, and re-forming them after the break
+//!
+//! The crate's own rules run the suite under `cargo test --features
+//! conformance`. Other engines are checked through a [`RulesAdapter`]; the
+//! `gomoku-conformance` binary drives an external process over the line
+//! protocol in [`protocol`]:
+//!
+//! ```text
+//! cargo run --features conformance --bin gomoku-conformance -- --engine ./my-engine
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use gomoku::conformance::{run_suite, ReferenceRules};
+//!
+//! let report = run_suite(&mut ReferenceRules);
+//! assert!(report.is_pass(), "{}", report);
+//! ```
+
+pub mod cases;
+pub mod protocol;
+
+use std::fmt;
+
+use crate::board::{Board, Pos, Stone};
+use crate::rules::{execute_captures, is_valid_move, winner_after_move, RuleSet};
+
+pub use cases::all_cases;
+pub use protocol::{serve, ProcessAdapter};
+
+/// Rule area a case exercises
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    /// Plain X-O-O-X captures and multi-captures
+    Capture,
+    /// Captures with a stone on the board edge
+    EdgeCapture,
+    /// Patterns that must NOT capture
+    NoCapture,
+    /// Five-in-a-row (including edges and overlines)
+    Five,
+    /// Winning by the fifth captured pair
+    CaptureWin,
+    /// Double-three and its exceptions
+    DoubleThree,
+    /// Breakable fives and breaking them
+    BreakableFive,
+    /// Occupied cells and other basic legality
+    Legality,
+}
+
+/// Ruling on one move
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// Whether the move may be played
+    pub legal: bool,
+    /// Captured stones, sorted by index (empty for illegal moves)
+    pub captured: Vec<Pos>,
+    /// Winner after the move, if the game is decided
+    pub winner: Option<Stone>,
+}
+
+impl Outcome {
+    /// Ruling for a forbidden move
+    pub fn illegal() -> Self {
+        Self { legal: false, captured: Vec::new(), winner: None }
+    }
+
+    /// Ruling for a legal move (captured stones are sorted)
+    pub fn legal(mut captured: Vec<Pos>, winner: Option<Stone>) -> Self {
+        captured.sort_unstable_by_key(|p| p.to_index());
+        Self { legal: true, captured, winner }
+    }
+}
+
+/// A position, a move and the expected ruling
+#[derive(Debug, Clone)]
+pub struct RuleCase {
+    /// Unique, descriptive case name
+    pub name: String,
+    /// Rule area
+    pub category: Category,
+    /// Position before the move (stones and capture counts)
+    pub board: Board,
+    /// Color making the move
+    pub to_move: Stone,
+    /// The move
+    pub mov: Pos,
+    /// Expected ruling
+    pub expected: Outcome,
+}
+
+/// An engine's rules, as seen by the conformance suite.
+pub trait RulesAdapter {
+    /// Rule the move `mov` by `color` in `board`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description if the engine could not be queried.
+    fn rule(&mut self, board: &Board, color: Stone, mov: Pos) -> Result<Outcome, String>;
+}
+
+/// The crate's own rules (`crate::rules`) with [`RuleSet::NINUKI`].
+///
+/// Legality is [`is_valid_move`] and the win ruling is [`winner_after_move`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReferenceRules;
+
+impl RulesAdapter for ReferenceRules {
+    fn rule(&mut self, board: &Board, color: Stone, mov: Pos) -> Result<Outcome, String> {
+        if !is_valid_move(RuleSet::NINUKI, board, mov, color) {
+            return Ok(Outcome::illegal());
+        }
+
+        let mut board = board.clone();
+        board.place_stone(mov, color);
+        let captured = execute_captures(RuleSet::NINUKI, &mut board, mov, color);
+        let winner = winner_after_move(RuleSet::NINUKI, &board, mov, color);
+        Ok(Outcome::legal(captured, winner))
+    }
+}
+
+/// A case the adapter got wrong
+#[derive(Debug, Clone)]
+pub struct Failure {
+    /// Case name
+    pub name: String,
+    /// Rule area
+    pub category: Category,
+    /// Expected ruling
+    pub expected: Outcome,
+    /// What the adapter answered (or why it could not)
+    pub actual: Result<Outcome, String>,
+}
+
+/// Result of running the suite
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    /// Number of cases run
+    pub total: usize,
+    /// Cases that did not match
+    pub failures: Vec<Failure>,
+}
+
+impl Report {
+    /// Number of cases that matched
+    pub fn passed(&self) -> usize {
+        self.total - self.failures.len()
+    }
+
+    /// Whether every case matched
+    pub fn is_pass(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}/{} cases passed", self.passed(), self.total)?;
+        for failure in &self.failures {
+            write!(f, "FAIL [{:?}] {}: expected {:?}, got ", failure.category, failure.name, failure.expected)?;
+            match &failure.actual {
+                Ok(outcome) => writeln!(f, "{:?}", outcome)?,
+                Err(e) => writeln!(f, "error: {}", e)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Run every case in [`all_cases`] against `adapter`.
+pub fn run_suite(adapter: &mut dyn RulesAdapter) -> Report {
+    run_cases(adapter, &all_cases())
+}
+
+/// Run the given cases against `adapter`.
+///
+/// Captured stones in the adapter's answer are compared as a set.
+pub fn run_cases(adapter: &mut dyn RulesAdapter, cases: &[RuleCase]) -> Report {
+    let mut report = Report { total: cases.len(), failures: Vec::new() };
+
+    for case in cases {
+        let actual = adapter
+            .rule(&case.board, case.to_move, case.mov)
+            .map(|o| if o.legal { Outcome::legal(o.captured, o.winner) } else { o });
+        if actual.as_ref() != Ok(&case.expected) {
+            report.failures.push(Failure {
+                name: case.name.clone(),
+                category: case.category,
+                expected: case.expected.clone(),
+                actual,
+            });
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_reference_rules_pass_suite() {
+        let report = run_suite(&mut ReferenceRules);
+        assert!(report.is_pass(), "{}", report);
+    }
+
+    #[test]
+    fn test_suite_size_and_coverage() {
+        let cases = all_cases();
+        assert!(cases.len() >= 500, "Suite has only {} cases", cases.len());
+
+        let names: HashSet<_> = cases.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names.len(), cases.len(), "Case names must be unique");
+
+        for category in [
+            Category::Capture,
+            Category::EdgeCapture,
+            Category::NoCapture,
+            Category::Five,
+            Category::CaptureWin,
+            Category::DoubleThree,
+            Category::BreakableFive,
+            Category::Legality,
+        ] {
+            assert!(cases.iter().any(|c| c.category == category), "No cases for {:?}", category);
+        }
+    }
+
+    #[test]
+    fn test_report_lists_failures() {
+        /// Adapter that never captures
+        struct NoCaptures;
+        impl RulesAdapter for NoCaptures {
+            fn rule(&mut self, board: &Board, color: Stone, mov: Pos) -> Result<Outcome, String> {
+                let outcome = ReferenceRules.rule(board, color, mov)?;
+                Ok(Outcome { captured: Vec::new(), ..outcome })
+            }
+        }
+
+        let report = run_suite(&mut NoCaptures);
+        assert!(!report.is_pass());
+        assert!(report.failures.iter().all(|f| f.expected.legal && !f.expected.captured.is_empty()));
+        assert!(report.to_string().contains("FAIL [EdgeCapture]"));
+    }
+}
@@ -0,0 +1,274 @@
+//! Line protocol for checking an external engine's rules
+//!
+//! The suite writes one query per line to the engine's stdin and reads one
+//! answer line from its stdout.
+//!
+//! ```text
+//! query:   rule <B|W> <row> <col> <black_captures> <white_captures> <cells>
+//! answers: illegal
+//!          legal <winner> [<row>,<col> ...]
+//!          error <message>
+//! ```
+//!
+//! `<cells>` is the position before the move: 361 characters, row-major,
+//! `.` empty, `B` Black, `W` White. `<winner>` is `-` (game goes on), `B` or
+//! `W`, followed by the captured stones in any order. The suite sends `quit`
+//! when it is done.
+//!
+//! ```text
+//! > rule B 9 9 0 0 ..........(361 cells)
+//! < legal - 9,10 9,11
+//! ```
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use super::{Outcome, RulesAdapter};
+use crate::board::{Board, Pos, Stone, BOARD_SIZE, TOTAL_CELLS};
+use crate::rules::RuleSet;
+
+/// Format the query line for `mov` by `color` in `board`.
+pub fn format_query(board: &Board, color: Stone, mov: Pos) -> String {
+    let cells: String = (0..TOTAL_CELLS).map(|i| stone_char(board.get(Pos::from_index(i)))).collect();
+    format!(
+        "rule {} {} {} {} {} {}",
+        stone_char(color),
+        mov.row,
+        mov.col,
+        board.captures(Stone::Black),
+        board.captures(Stone::White),
+        cells
+    )
+}
+
+/// Parse a query line.
+///
+/// # Errors
+///
+/// Returns a description of the first malformed field.
+pub fn parse_query(line: &str) -> Result<(Board, Stone, Pos), String> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [cmd, color, row, col, black_caps, white_caps, cells] = fields[..] else {
+        return Err(format!("expected 7 fields, got {}", fields.len()));
+    };
+    if cmd != "rule" {
+        return Err(format!("unknown command '{}'", cmd));
+    }
+
+    let color = match parse_stone(color)? {
+        Stone::Empty => return Err("color must be B or W".to_string()),
+        stone => stone,
+    };
+    let mov = parse_pos(row, col)?;
+    if cells.len() != TOTAL_CELLS {
+        return Err(format!("expected {} cells, got {}", TOTAL_CELLS, cells.len()));
+    }
+
+    let mut board = Board::new();
+    for (i, ch) in cells.chars().enumerate() {
+        match parse_stone(&ch.to_string())? {
+            Stone::Empty => {}
+            stone => board.place_stone(Pos::from_index(i), stone),
+        }
+    }
+    for (color, caps) in [(Stone::Black, black_caps), (Stone::White, white_caps)] {
+        let pairs = caps.parse().map_err(|_| format!("bad capture count '{}'", caps))?;
+        board.set_captures(RuleSet::NINUKI, color, pairs).map_err(|e| e.to_string())?;
+    }
+    Ok((board, color, mov))
+}
+
+/// Format the answer line for an outcome.
+pub fn format_outcome(outcome: &Outcome) -> String {
+    if !outcome.legal {
+        return "illegal".to_string();
+    }
+    let mut line = format!("legal {}", outcome.winner.map_or('-', stone_char));
+    for p in &outcome.captured {
+        line.push_str(&format!(" {},{}", p.row, p.col));
+    }
+    line
+}
+
+/// Parse an answer line.
+///
+/// # Errors
+///
+/// Returns the engine's message for `error` answers, or a description of
+/// the malformed field.
+pub fn parse_outcome(line: &str) -> Result<Outcome, String> {
+    let mut fields = line.split_whitespace();
+    match fields.next() {
+        Some("illegal") => Ok(Outcome::illegal()),
+        Some("legal") => {
+            let winner = match fields.next() {
+                Some("-") => None,
+                Some(w) => match parse_stone(w)? {
+                    Stone::Empty => None,
+                    stone => Some(stone),
+                },
+                None => return Err("missing winner".to_string()),
+            };
+            let captured = fields
+                .map(|cell| {
+                    let (row, col) = cell.split_once(',').ok_or(format!("bad cell '{}'", cell))?;
+                    parse_pos(row, col)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Outcome::legal(captured, winner))
+        }
+        Some("error") => Err(line.trim_start()["error".len()..].trim().to_string()),
+        _ => Err(format!("unexpected answer '{}'", line.trim())),
+    }
+}
+
+/// Answer queries from `input` with `adapter` until `quit` or end of input.
+///
+/// This is the engine side of the protocol; `gomoku-conformance --serve`
+/// runs it with the crate's own rules.
+///
+/// # Errors
+///
+/// Returns I/O errors from reading or writing.
+pub fn serve(adapter: &mut dyn RulesAdapter, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line == "quit" {
+            break;
+        }
+        let answer = parse_query(line)
+            .and_then(|(board, color, mov)| adapter.rule(&board, color, mov))
+            .map_or_else(|e| format!("error {}", e), |o| format_outcome(&o));
+        writeln!(output, "{}", answer)?;
+        output.flush()?;
+    }
+    Ok(())
+}
+
+/// An external engine process speaking the protocol on stdin/stdout.
+pub struct ProcessAdapter {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl ProcessAdapter {
+    /// Start `program` with `args`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error if the process cannot be started.
+    pub fn spawn(program: &str, args: &[String]) -> io::Result<Self> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().ok_or_else(|| io::Error::other("no stdin"))?;
+        let stdout = child.stdout.take().ok_or_else(|| io::Error::other("no stdout"))?;
+        Ok(Self { child, stdin, stdout: BufReader::new(stdout) })
+    }
+}
+
+impl RulesAdapter for ProcessAdapter {
+    fn rule(&mut self, board: &Board, color: Stone, mov: Pos) -> Result<Outcome, String> {
+        writeln!(self.stdin, "{}", format_query(board, color, mov)).map_err(|e| e.to_string())?;
+        self.stdin.flush().map_err(|e| e.to_string())?;
+
+        let mut line = String::new();
+        match self.stdout.read_line(&mut line) {
+            Ok(0) => Err("engine closed its output".to_string()),
+            Ok(_) => parse_outcome(&line),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+impl Drop for ProcessAdapter {
+    fn drop(&mut self) {
+        let _ = writeln!(self.stdin, "quit");
+        let _ = self.stdin.flush();
+        let _ = self.child.wait();
+    }
+}
+
+fn stone_char(stone: Stone) -> char {
+    match stone {
+        Stone::Empty => '.',
+        Stone::Black => 'B',
+        Stone::White => 'W',
+    }
+}
+
+fn parse_stone(s: &str) -> Result<Stone, String> {
+    match s {
+        "." => Ok(Stone::Empty),
+        "B" => Ok(Stone::Black),
+        "W" => Ok(Stone::White),
+        _ => Err(format!("bad stone '{}'", s)),
+    }
+}
+
+fn parse_pos(row: &str, col: &str) -> Result<Pos, String> {
+    let parse = |s: &str| s.parse::<u8>().ok().filter(|&v| usize::from(v) < BOARD_SIZE);
+    match (parse(row), parse(col)) {
+        (Some(r), Some(c)) => Ok(Pos::new(r, c)),
+        _ => Err(format!("bad position '{},{}'", row, col)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conformance::{all_cases, run_cases, ReferenceRules};
+
+    /// Runs every query through `serve` in memory, like an external engine.
+    struct LoopbackAdapter;
+
+    impl RulesAdapter for LoopbackAdapter {
+        fn rule(&mut self, board: &Board, color: Stone, mov: Pos) -> Result<Outcome, String> {
+            let query = format!("{}\nquit\n", format_query(board, color, mov));
+            let mut answer = Vec::new();
+            serve(&mut ReferenceRules, query.as_bytes(), &mut answer).map_err(|e| e.to_string())?;
+            parse_outcome(&String::from_utf8(answer).map_err(|e| e.to_string())?)
+        }
+    }
+
+    #[test]
+    fn test_suite_passes_over_protocol() {
+        let report = run_cases(&mut LoopbackAdapter, &all_cases());
+        assert!(report.is_pass(), "{}", report);
+    }
+
+    #[test]
+    fn test_query_round_trip() {
+        let mut board = Board::new();
+        board.place_stone(Pos::new(0, 0), Stone::Black);
+        board.place_stone(Pos::new(18, 18), Stone::White);
+        board.set_captures(RuleSet::NINUKI, Stone::Black, 2).unwrap();
+
+        let (parsed, color, mov) = parse_query(&format_query(&board, Stone::White, Pos::new(3, 4))).unwrap();
+        assert_eq!(parsed.bitboards(), board.bitboards());
+        assert_eq!((parsed.captures(Stone::Black), parsed.captures(Stone::White)), (2, 0));
+        assert_eq!(color, Stone::White);
+        assert_eq!(mov, Pos::new(3, 4));
+    }
+
+    #[test]
+    fn test_malformed_lines() {
+        assert!(parse_query("rule B 9 9 0 0 ...").is_err());
+        assert!(parse_query("rule X 9 9 0 0").is_err());
+        assert!(parse_outcome("legal").is_err());
+        assert!(parse_outcome("legal - 9,19").is_err());
+        assert_eq!(parse_outcome("error unsupported").unwrap_err(), "unsupported");
+        assert_eq!(parse_outcome("\u{3000}\u{3000}error unsupported").unwrap_err(), "unsupported");
+
+        let mut answer = Vec::new();
+        serve(&mut ReferenceRules, "hello\n".as_bytes(), &mut answer).unwrap();
+        assert!(String::from_utf8(answer).unwrap().starts_with("error "));
+    }
+}
@@ -3,12 +3,13 @@
 //! This module provides the main AI engine that orchestrates all search algorithms
 //! to find the best move in any given position. The search follows a priority pipeline:
 //!
-//! 0. **Opening book**: Fast early-game responses (center on empty board)
-//! 0.5. **Break five**: Break opponent's breakable five via capture
-//! 1. **Immediate win**: Check for any move that wins instantly (5-in-a-row or capture)
-//! 2. **Block opponent**: Block opponent's immediate winning move
-//! 3-4. **VCF**: Victory by Continuous Fours (both sides)
-//! 5. **Alpha-Beta**: Full search with iterative deepening and time management
+//! - Stage 0 **Opening book**: Fast early-game responses (center on empty board)
+//! - Stage 0.5 **Break five**: Break opponent's breakable five via capture
+//! - Stage 1 **Immediate win**: Check for any move that wins instantly (5-in-a-row or capture)
+//! - Stage 2 **Block opponent**: Block opponent's immediate winning move
+//! - Stage 2.5 **Single reply**: Play the only move that doesn't lose at once
+//! - Stage 3-4 **VCF**: Victory by Continuous Fours (both sides)
+//! - Stage 5 **Alpha-Beta**: Full search with iterative deepening and time management
 //!
 //! # Example
 //!
@@ -29,45 +30,85 @@
 //! println!("Time: {}ms", result.time_ms);
 //! ```
 
-use crate::board::{Board, Pos, Stone, BOARD_SIZE};
+use crate::board::{Board, Pos, Stone, Symmetry, BOARD_SIZE};
+use crate::clock::TimeManager;
+use crate::eval::{evaluate, PatternScore};
+use crate::logging::{log_debug, log_info, log_warn};
 use crate::rules::{
-    can_break_five_by_capture, execute_captures_fast, find_five_break_moves,
-    find_five_line_at_pos, find_five_positions, has_five_at_pos, is_valid_move, undo_captures,
+    can_break_five_by_capture, count_captures, count_free_threes, execute_captures_fast,
+    find_capture_threats, find_five_break_moves, find_five_positions, find_fours,
+    find_open_threes, has_five_at_pos, has_five_in_row, is_valid_move, legal_moves_iter, undo_captures, RuleSet,
+    ThreatKind,
 };
-use crate::search::{SearchResult, Searcher, ThreatSearcher};
-use std::fs::OpenOptions;
-use std::io::Write;
-use std::time::Instant;
+use crate::search::{
+    find_resistant_move, find_swindle_move, mate_distance, resolve_break, resolve_five, CycleOutcome, ProofLine,
+    SearchControl, SearchInfo, SearchLimits, SearchParams, SearchResult, Searcher, ThreatSearcher, ZobristTable,
+};
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+    task::{Context as TaskContext, Poll, Waker},
+};
+use crate::time::{Clock, Instant, NODES_PER_MS};
 
-/// Format a board position as human-readable notation (e.g., "J10")
-pub fn pos_to_notation(pos: Pos) -> String {
-    // Columns: A=0, B=1, ..., H=7, J=8 (skip I), K=9, ...
-    let col_char = if pos.col < 8 {
-        (b'A' + pos.col) as char
-    } else {
-        (b'A' + pos.col + 1) as char // skip 'I'
-    };
-    // Rows: 1=0, 2=1, ..., 19=18 (board display: bottom=1, top=19)
-    format!("{}{}", col_char, pos.row + 1)
-}
+/// Alpha-beta score at or below which swindle mode takes over (hopeless defense).
+const SWINDLE_TRIGGER: i32 = -PatternScore::OPEN_FOUR;
+
+/// Node budget of the opponent's search after each candidate in
+/// speculative defense.
+const RESIST_NODES: u64 = 4_000;
+
+/// Logistic scale for [`AIEngine::win_probability`]: a score of this size
+/// corresponds to ~73% expected result (two open threes' worth of advantage).
+const WIN_PROBABILITY_SCALE: f64 = 2.0 * PatternScore::OPEN_THREE as f64;
+
+/// Largest static advantage at which [`AIEngine::should_accept_draw`] still
+/// agrees to a draw (the position is level within an open two).
+const DRAW_ACCEPT_MAX_SCORE: i32 = PatternScore::OPEN_TWO;
+
+/// Node budget per millisecond of time limit in deterministic mode (about
+/// the single-threaded search speed, so moves take roughly as long as the
+/// time limit).
+const DETERMINISTIC_NODES_PER_MS: u64 = 100;
+
+/// Share of the time limit (percent) asked for when the opponent threatens
+/// an open four or the capture win
+const CRISIS_TIME_PERCENT: u64 = 180;
+
+/// Share of the time limit (percent) asked for when either side has fours
+/// or open threes
+const TACTICAL_TIME_PERCENT: u64 = 130;
+
+/// Most time the bank holds, in time limits
+const TIME_BANK_LIMITS: u64 = 4;
+
+/// Positions with at most this many stones are searched in their
+/// [`Symmetry::canonical`] orientation, so symmetric openings share TT entries
+const CANONICAL_SEARCH_STONES: u32 = 8;
 
-/// Write a log message to both gomoku_ai.log and stderr
-pub fn ai_log(msg: &str) {
-    if let Ok(mut file) = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("gomoku_ai.log")
-    {
-        let _ = writeln!(file, "{}", msg);
-        let _ = file.flush();
-    }
-    eprintln!("{}", msg);
+/// Root moves searched for an [`OpeningVariety`] choice, and the node
+/// budget of each
+const VARIETY_LINES: usize = 4;
+const VARIETY_NODES: u64 = 20_000;
+
+/// Format a board position as human-readable notation (e.g., "J10") in the
+/// configured [`crate::notation`]
+pub fn pos_to_notation(pos: Pos) -> String {
+    crate::notation::notation().format(pos)
 }
 
 /// Type of search that produced the result.
 ///
 /// This indicates which phase of the search hierarchy found the move.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SearchType {
     /// Found immediate winning move (5-in-a-row or capture win)
     ImmediateWin,
@@ -77,12 +118,16 @@ pub enum SearchType {
     Defense,
     /// Regular alpha-beta search result
     AlphaBeta,
+    /// Trappy move chosen in a lost position (swindle mode or speculative
+    /// defense)
+    Swindle,
 }
 
 /// Result of a move search with detailed statistics.
 ///
 /// Contains the best move found along with metadata about the search.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MoveResult {
     /// Best move found, if any
     pub best_move: Option<Pos>,
@@ -100,16 +145,20 @@ pub struct MoveResult {
     pub tt_usage: u8,
     /// Nodes per second (kN/s)
     pub nps: u64,
+    /// Forced result in this many moves: positive when we win, negative
+    /// when we lose, `None` when the search proved neither
+    pub mate_in: Option<i32>,
+    /// Threads the search ran on (1 for the single-threaded stages)
+    pub threads: usize,
+    /// Immediate and VCF wins written out with the opponent's forced
+    /// replies ([`ProofLine::verify`] checks them)
+    pub proof: Option<ProofLine>,
 }
 
 impl MoveResult {
     /// Compute nodes per second in kN/s
     fn compute_nps(nodes: u64, time_ms: u64) -> u64 {
-        if time_ms == 0 {
-            0
-        } else {
-            nodes * 1000 / time_ms / 1000
-        }
+        (nodes * 1000).checked_div(time_ms).unwrap_or(0) / 1000
     }
 
     /// Create a result for an immediate win
@@ -124,14 +173,17 @@ impl MoveResult {
             depth: 0,
             tt_usage: 0,
             nps: 0,
+            mate_in: Some(1),
+            threads: 1,
+            proof: None,
         }
     }
 
-    /// Create a result for a VCF win
+    /// Create a result for a VCF win played out over `sequence` (our moves only)
     #[inline]
-    fn vcf_win(pos: Pos, time_ms: u64, nodes: u64) -> Self {
+    fn vcf_win(sequence: &[Pos], time_ms: u64, nodes: u64) -> Self {
         Self {
-            best_move: Some(pos),
+            best_move: sequence.first().copied(),
             score: 900_000,
             search_type: SearchType::VCF,
             time_ms,
@@ -139,6 +191,9 @@ impl MoveResult {
             depth: 0,
             tt_usage: 0,
             nps: Self::compute_nps(nodes, time_ms),
+            mate_in: Some(sequence.len() as i32),
+            threads: 1,
+            proof: None,
         }
     }
 
@@ -154,6 +209,9 @@ impl MoveResult {
             depth: 0,
             tt_usage: 0,
             nps: 0,
+            mate_in: None,
+            threads: 1,
+            proof: None,
         }
     }
 
@@ -169,6 +227,9 @@ impl MoveResult {
             depth: result.depth,
             tt_usage,
             nps: Self::compute_nps(result.nodes, time_ms),
+            mate_in: mate_distance(result.score),
+            threads: result.threads,
+            proof: None,
         }
     }
 
@@ -184,6 +245,9 @@ impl MoveResult {
             depth: 0,
             tt_usage: 0,
             nps: 0,
+            mate_in: None,
+            threads: 1,
+            proof: None,
         }
     }
 
@@ -199,7 +263,285 @@ impl MoveResult {
             depth: 0,
             tt_usage: 0,
             nps: 0,
+            mate_in: None,
+            threads: 1,
+            proof: None,
+        }
+    }
+}
+
+/// Why a hinted move is good, most important reason first (see
+/// [`AIEngine::get_hint`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HintReason {
+    /// Completes five in a row
+    WinsByFive,
+    /// Captures the pair that reaches the capture win
+    WinsByCapture,
+    /// First move of a forced win in this many moves
+    ForcedWin { moves: i32 },
+    /// Sets a trap in a lost position (swindle mode)
+    Swindle,
+    /// Breaks the opponent's five by capturing from it
+    BreaksFive,
+    /// Takes the point where the opponent would complete five
+    BlocksFive,
+    /// Makes two threats at once: two fours, or a four and an open three
+    DoubleThreat { fours: u8, threes: u8 },
+    /// Makes an open four (wins next move unless broken by capture)
+    OpenFour,
+    /// Captures this many pairs
+    Captures { pairs: u8 },
+    /// Takes the point where the opponent would make an open four
+    BlocksOpenFour,
+    /// Makes a four the opponent must answer
+    Four,
+    /// Takes the point where the opponent would make a four
+    BlocksFour,
+    /// Takes the point where the opponent would make an open three
+    BlocksOpenThree,
+    /// Makes an open three
+    OpenThree,
+    /// No direct tactic: the search's best developing move
+    Positional,
+}
+
+impl fmt::Display for HintReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::WinsByFive => write!(f, "completes five in a row"),
+            Self::WinsByCapture => write!(f, "captures the winning pair and wins"),
+            Self::ForcedWin { moves } => write!(f, "starts a forced win in {} moves", moves),
+            Self::Swindle => write!(f, "sets a trap in a lost position"),
+            Self::BreaksFive => write!(f, "breaks the opponent's five by capture"),
+            Self::BlocksFive => write!(f, "blocks the opponent's five"),
+            Self::DoubleThreat { fours, .. } if fours >= 2 => write!(f, "creates a double threat (two fours)"),
+            Self::DoubleThreat { .. } => write!(f, "creates a double threat (four and open three)"),
+            Self::OpenFour => write!(f, "makes an open four"),
+            Self::Captures { pairs: 1 } => write!(f, "captures a pair"),
+            Self::Captures { pairs } => write!(f, "captures {} pairs", pairs),
+            Self::BlocksOpenFour => write!(f, "stops an open four"),
+            Self::Four => write!(f, "makes a four, forcing a reply"),
+            Self::BlocksFour => write!(f, "stops a four"),
+            Self::BlocksOpenThree => write!(f, "prevents an open three"),
+            Self::OpenThree => write!(f, "makes an open three"),
+            Self::Positional => write!(f, "strengthens the position"),
+        }
+    }
+}
+
+/// A suggested move with the reason for it, for learners
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hint {
+    /// The suggested move
+    pub mov: Pos,
+    pub reason: HintReason,
+    /// The search that chose the move
+    pub result: MoveResult,
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", pos_to_notation(self.mov), self.reason)
+    }
+}
+
+/// Stage of the game, by stones played (captured ones included)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamePhase {
+    /// Up to 10 stones played
+    Opening,
+    /// 11 to 40 stones played
+    Midgame,
+    /// More than 40 stones played
+    Endgame,
+}
+
+impl GamePhase {
+    /// Phase of `board` under `rules`
+    #[must_use]
+    pub fn of(rules: RuleSet, board: &Board) -> Self {
+        let pair_size = u32::from(rules.pair_size());
+        let captured = pair_size * (u32::from(board.captures(Stone::Black)) + u32::from(board.captures(Stone::White)));
+        match board.stone_count() + captured {
+            0..=10 => Self::Opening,
+            11..=40 => Self::Midgame,
+            _ => Self::Endgame,
+        }
+    }
+
+    /// Display name
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Opening => "Opening",
+            Self::Midgame => "Midgame",
+            Self::Endgame => "Endgame",
+        }
+    }
+}
+
+/// Threats one side has on the board (see [`crate::rules::find_threats`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThreatSummary {
+    /// Fours with two completion points
+    pub open_fours: u32,
+    /// Fours with one completion point
+    pub fours: u32,
+    pub open_threes: u32,
+    /// Opponent pairs capturable with one move
+    pub capture_threats: u32,
+    /// Pairs still needed for the capture win
+    pub captures_to_win: u8,
+}
+
+impl ThreatSummary {
+    fn of(rules: RuleSet, board: &Board, color: Stone) -> Self {
+        let mut summary = Self {
+            open_threes: find_open_threes(board, color).len() as u32,
+            capture_threats: find_capture_threats(rules, board, color).len() as u32,
+            captures_to_win: board.captures_to_win(rules, color),
+            ..Self::default()
+        };
+        for four in find_fours(board, color) {
+            match four.kind {
+                ThreatKind::OpenFour => summary.open_fours += 1,
+                _ => summary.fours += 1,
+            }
+        }
+        summary
+    }
+
+    /// Whether the side threatens to win with its next move: a four, or a
+    /// capture that reaches the capture win
+    #[must_use]
+    pub fn threatens_win(&self) -> bool {
+        self.open_fours + self.fours > 0 || (self.captures_to_win <= 1 && self.capture_threats > 0)
+    }
+
+    /// Whether the side has any four or open three
+    #[must_use]
+    pub fn is_tactical(&self) -> bool {
+        self.open_fours + self.fours + self.open_threes > 0
+    }
+}
+
+/// Who is closer to the capture win
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CaptureRace {
+    /// Both sides need as many pairs
+    Even,
+    /// `leader` needs `by` fewer pairs than the other side
+    Ahead { leader: Stone, by: u8 },
+}
+
+impl fmt::Display for CaptureRace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Even => write!(f, "captures even"),
+            Self::Ahead { leader, by } => write!(f, "{:?} ahead by {} capture{}", leader, by, if by == 1 { "" } else { "s" }),
+        }
+    }
+}
+
+/// Static summary of a position, from [`classify_position`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PositionClass {
+    pub phase: GamePhase,
+    pub black: ThreatSummary,
+    pub white: ThreatSummary,
+    pub capture_race: CaptureRace,
+}
+
+impl PositionClass {
+    /// Threats of `color`
+    #[must_use]
+    pub fn threats(&self, color: Stone) -> &ThreatSummary {
+        if color == Stone::White { &self.white } else { &self.black }
+    }
+
+    /// Whether either side has a four or open three, or is one capture
+    /// from the capture win
+    #[must_use]
+    pub fn is_tactical(&self) -> bool {
+        [&self.black, &self.white].iter().any(|side| side.is_tactical() || side.captures_to_win <= 1)
+    }
+
+    /// Whether either side is within `pairs` captures of the capture win
+    #[must_use]
+    pub fn capture_win_within(&self, pairs: u8) -> bool {
+        self.black.captures_to_win <= pairs || self.white.captures_to_win <= pairs
+    }
+}
+
+impl fmt::Display for PositionClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}", self.phase.name(), self.capture_race)?;
+        for (color, side) in [(Stone::Black, &self.black), (Stone::White, &self.white)] {
+            if side.threatens_win() {
+                write!(f, ", {:?} threatens to win", color)?;
+            } else if side.open_threes > 0 {
+                write!(f, ", {:?} has an open three", color)?;
+            }
         }
+        Ok(())
+    }
+}
+
+/// Classify `board` without searching: game phase, each side's threats and
+/// the capture race.
+///
+/// ```
+/// use gomoku::engine::{classify_position, CaptureRace, GamePhase};
+/// use gomoku::rules::RuleSet;
+/// use gomoku::{Board, Pos, Stone};
+///
+/// let mut board = Board::new();
+/// for col in 7..10 {
+///     board.place_stone(Pos::new(9, col), Stone::Black);
+/// }
+/// board.set_captures(RuleSet::NINUKI, Stone::Black, 2).unwrap();
+/// let class = classify_position(RuleSet::NINUKI, &board);
+/// assert_eq!(class.phase, GamePhase::Opening);
+/// assert_eq!(class.black.open_threes, 1);
+/// assert_eq!(class.capture_race, CaptureRace::Ahead { leader: Stone::Black, by: 2 });
+/// ```
+#[must_use]
+pub fn classify_position(rules: RuleSet, board: &Board) -> PositionClass {
+    let black = ThreatSummary::of(rules, board, Stone::Black);
+    let white = ThreatSummary::of(rules, board, Stone::White);
+    let capture_race = match black.captures_to_win.cmp(&white.captures_to_win) {
+        std::cmp::Ordering::Equal => CaptureRace::Even,
+        std::cmp::Ordering::Less => {
+            CaptureRace::Ahead { leader: Stone::Black, by: white.captures_to_win - black.captures_to_win }
+        }
+        std::cmp::Ordering::Greater => {
+            CaptureRace::Ahead { leader: Stone::White, by: black.captures_to_win - white.captures_to_win }
+        }
+    };
+    PositionClass { phase: GamePhase::of(rules, board), black, white, capture_race }
+}
+
+/// Randomized choice among the engine's early moves, so repeated games
+/// don't all follow one line (see [`AIEngine::set_opening_variety`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpeningVariety {
+    /// Randomize through this game move (stones played, counting captured
+    /// ones)
+    pub moves: u32,
+    /// Choose among root moves scoring within this much of the best
+    pub margin: i32,
+}
+
+impl Default for OpeningVariety {
+    fn default() -> Self {
+        Self { moves: 8, margin: PatternScore::CLOSED_TWO }
     }
 }
 
@@ -242,6 +584,26 @@ pub struct AIEngine {
     max_depth: i8,
     /// Time limit for search in milliseconds
     time_limit_ms: u64,
+    /// Play trappy moves instead of the objectively best defense when lost
+    swindle_mode: bool,
+    /// When lost, play the move the opponent's search finds hardest to win
+    /// against (takes precedence over `swindle_mode`)
+    speculative_defense: bool,
+    /// Resign when the search score drops to this value or below (`None` = never)
+    resign_threshold: Option<i32>,
+    /// Rules the engine plays by (kept in step with both searchers)
+    rules: RuleSet,
+    /// Single-threaded search with a node budget instead of the clock
+    deterministic: bool,
+    /// Time saved on easy moves (ms), spent on crisis moves so the average
+    /// stays within the time limit
+    time_bank_ms: u64,
+    /// Random early moves (`None` = always the best move)
+    opening_variety: Option<OpeningVariety>,
+    /// Seed of the Zobrist keys and of `rng`
+    seed: u64,
+    /// State of the engine's random generator (opening variety)
+    rng: u64,
 }
 
 impl AIEngine {
@@ -266,6 +628,15 @@ impl AIEngine {
             threat_searcher: ThreatSearcher::with_depths(30, 12),
             max_depth: 20,
             time_limit_ms: 500,
+            swindle_mode: false,
+            speculative_defense: false,
+            resign_threshold: None,
+            rules: RuleSet::NINUKI,
+            deterministic: false,
+            time_bank_ms: 0,
+            opening_variety: None,
+            seed: ZobristTable::DEFAULT_SEED,
+            rng: ZobristTable::DEFAULT_SEED,
         }
     }
 
@@ -292,6 +663,15 @@ impl AIEngine {
             threat_searcher: ThreatSearcher::with_depths(30, 12),
             max_depth,
             time_limit_ms,
+            swindle_mode: false,
+            speculative_defense: false,
+            resign_threshold: None,
+            rules: RuleSet::NINUKI,
+            deterministic: false,
+            time_bank_ms: 0,
+            opening_variety: None,
+            seed: ZobristTable::DEFAULT_SEED,
+            rng: ZobristTable::DEFAULT_SEED,
         }
     }
 
@@ -355,31 +735,84 @@ impl AIEngine {
     /// 3. Alpha-beta search (handles offense, defense, and blocking)
     #[must_use]
     pub fn get_move_with_stats(&mut self, board: &Board, color: Stone) -> MoveResult {
+        self.search_pipeline(board, color, None)
+    }
+
+    /// Get the best move with the alpha-beta stage bounded by `limits`
+    /// instead of the engine's time limit and deterministic setting.
+    ///
+    /// The tactical stages (immediate wins, forced defenses, VCF) run as in
+    /// [`AIEngine::get_move_with_stats`], except when `limits` restricts the
+    /// root moves ([`SearchLimits::restrict_to`]): then only the alpha-beta
+    /// stage runs, over those moves. An unset `limits.max_depth` uses the
+    /// engine's maximum depth.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gomoku::search::SearchLimits;
+    /// use gomoku::{AIEngine, Board, Pos, Stone};
+    ///
+    /// let mut board = Board::new();
+    /// board.place_stone(Pos::new(9, 9), Stone::Black);
+    /// board.place_stone(Pos::new(10, 10), Stone::White);
+    /// board.place_stone(Pos::new(9, 10), Stone::Black);
+    ///
+    /// let mut engine = AIEngine::with_config(8, 10, 500);
+    /// let result = engine.get_move_with_limits(&board, Stone::White, &SearchLimits::nodes(10_000));
+    /// assert!(result.best_move.is_some());
+    /// ```
+    #[must_use]
+    pub fn get_move_with_limits(&mut self, board: &Board, color: Stone, limits: &SearchLimits) -> MoveResult {
+        let limits = SearchLimits { max_depth: limits.max_depth.or(Some(self.max_depth)), ..*limits };
+        if limits.root_moves.is_some() {
+            // The tactical stages could answer with a move outside the set
+            return self.alpha_beta_stage(board, color, Some(&limits), Instant::now());
+        }
+        self.search_pipeline(board, color, Some(&limits))
+    }
+
+    /// Get the best move under a game clock: `remaining` is the time left
+    /// for this player and `increment` the time added per move (the period
+    /// length under byo-yomi). The budget comes from [`TimeManager`]; see
+    /// [`GameClock::allowance`](crate::clock::GameClock::allowance).
+    #[must_use]
+    pub fn get_move_with_clock(
+        &mut self,
+        board: &Board,
+        color: Stone,
+        remaining: Duration,
+        increment: Duration,
+    ) -> MoveResult {
+        let budget = TimeManager::default().allocate(self.rules, remaining, increment, board, color);
+        self.get_move_with_limits(board, color, &SearchLimits::time(budget.max(Duration::from_millis(1))))
+    }
+
+    /// Full move pipeline; `limits` replaces the time/deterministic budget of
+    /// the alpha-beta stage.
+    fn search_pipeline(&mut self, board: &Board, color: Stone, limits: Option<&SearchLimits>) -> MoveResult {
         let start = Instant::now();
         // Actual game move number: stones on board + captured stones (removed) + 1
-        let total_captured = 2 * (board.captures(Stone::Black) as u32 + board.captures(Stone::White) as u32);
+        let pair_size = u32::from(self.rules.pair_size());
+        let total_captured = pair_size * (board.captures(Stone::Black) as u32 + board.captures(Stone::White) as u32);
         let move_num = board.stone_count() + total_captured + 1;
         let color_str = if color == Stone::Black { "Black" } else { "White" };
 
-        // Dynamic heuristic phase detection
-        let phase_total = board.stone_count()
-            + (board.captures(Stone::Black) as u32 + board.captures(Stone::White) as u32) * 2;
-        let phase_str = match phase_total {
-            0..=10 => "Opening",
-            11..=40 => "Midgame",
-            _ => "Endgame",
-        };
-
         let separator = "=".repeat(60);
-        ai_log(&format!(
+        log_info!(
             "\n{}\n[Move #{} | AI: {} | Stones: {} | B-cap: {} W-cap: {} | Phase: {}]",
             separator, move_num, color_str, board.stone_count(),
-            board.captures(Stone::Black), board.captures(Stone::White), phase_str
-        ));
+            board.captures(Stone::Black), board.captures(Stone::White), GamePhase::of(self.rules, board).name()
+        );
+
+        // Opening variety replaces the book after the first move; forced
+        // stages still come first
+        let variety = self.opening_variety.filter(|v| limits.is_none() && move_num <= v.moves);
 
         // 0. Opening book for fast early game response
-        if let Some(opening_move) = self.get_opening_move(board, color) {
-            ai_log(&format!("  Stage 0 OPENING: {} (book move)", pos_to_notation(opening_move)));
+        let book = variety.is_none() || board.stone_count() == 0;
+        if let Some(opening_move) = self.get_opening_move(board, color).filter(|_| book) {
+            log_debug!("  Stage 0 OPENING: {} (book move)", pos_to_notation(opening_move));
             return MoveResult::alpha_beta(
                 opening_move,
                 0,
@@ -392,209 +825,165 @@ impl AIEngine {
         // In Ninuki-renju, a breakable five gives opponent ONE chance to capture.
         // If they fail, the five-holder wins. This is a forced response.
         let opponent = color.opponent();
-        if let Some(opp_five) = find_five_positions(board, opponent) {
-            if can_break_five_by_capture(board, &opp_five, opponent) {
-                let break_moves = find_five_break_moves(board, &opp_five, opponent);
+        if let Some(opp_five) = find_five_positions(self.rules, board, opponent) {
+            if can_break_five_by_capture(self.rules, board, &opp_five, opponent) {
+                let break_moves = find_five_break_moves(self.rules, board, &opp_five, opponent);
                 let valid_breaks: Vec<Pos> = break_moves
                     .into_iter()
-                    .filter(|&p| is_valid_move(board, p, color))
+                    .filter(|&p| is_valid_move(self.rules, board, p, color))
                     .collect();
                 let break_strs: Vec<String> =
                     valid_breaks.iter().map(|p| pos_to_notation(*p)).collect();
-                ai_log(&format!(
+                log_debug!(
                     "  Stage 0.5 BREAK FIVE: opponent five exists! Break moves: [{}]",
                     break_strs.join(", ")
-                ));
-                if valid_breaks.len() == 1 {
-                    // Check if the single break allows opponent to recreate an UNBREAKABLE five
-                    let brk = valid_breaks[0];
-                    let mut test_board = board.clone();
-                    test_board.place_stone(brk, color);
-                    let cap_info = execute_captures_fast(&mut test_board, brk, color);
-                    let mut recreates_unbreakable = false;
-                    for i in 0..cap_info.count as usize {
-                        let cap_pos = cap_info.positions[i];
-                        test_board.place_stone(cap_pos, opponent);
-                        if has_five_at_pos(&test_board, cap_pos, opponent) {
-                            // Recreation possible — check if recreated five is unbreakable
-                            if let Some(new_five) =
-                                find_five_line_at_pos(&test_board, cap_pos, opponent)
-                            {
-                                if !can_break_five_by_capture(&test_board, &new_five, opponent) {
-                                    recreates_unbreakable = true;
-                                }
-                            }
-                        }
-                        test_board.remove_stone(cap_pos);
-                        if recreates_unbreakable {
-                            break;
-                        }
-                    }
-                    if recreates_unbreakable {
-                        ai_log(&format!(
-                            "  >>> FORCED BREAK {} rejected: opponent recreates UNBREAKABLE five — falling through to alpha-beta",
-                            pos_to_notation(brk)
-                        ));
-                        // Fall through to alpha-beta for a strategic alternative
-                    } else {
-                        ai_log(&format!(
-                            "  >>> FORCED BREAK: {}",
-                            pos_to_notation(brk)
-                        ));
-                        return MoveResult::defense(
-                            brk,
-                            -900_000,
-                            start.elapsed().as_millis() as u64,
-                            1,
-                        );
-                    }
-                } else if valid_breaks.is_empty() {
-                    ai_log("  Stage 0.5 BREAK FIVE: NO valid break moves — opponent wins!");
+                );
+                if valid_breaks.is_empty() {
+                    log_debug!("  Stage 0.5 BREAK FIVE: NO valid break moves — opponent wins!");
                     // Fall through to alpha-beta for best losing move
                 } else {
-                    // Multiple break moves: evaluate each with quick search
-                    // to pick the best position after breaking.
-                    // IMPORTANT: Check if opponent can recreate the five by replaying
-                    // at a captured position. If so, the break leads to an infinite
-                    // loop (AI breaks → opponent replays → AI breaks → ...).
-                    // Only accept breaks where recreation is impossible.
-                    let mut best_move = valid_breaks[0];
-                    let mut best_score = i32::MIN;
-                    let mut any_safe_break = false;
+                    // Follow each break through the opponent's replays at the
+                    // captured points (see `search::cycle`). A break they answer
+                    // with a five that ends unbreakable, or with a capture win,
+                    // loses anyway; of the others prefer the outcome worst for
+                    // the opponent, then the better position after breaking.
+                    let mut best: Option<(CycleOutcome, i32, Pos)> = None;
                     let mut test_board = board.clone();
                     for &brk in &valid_breaks {
-                        test_board.place_stone(brk, color);
-                        let cap_info = execute_captures_fast(&mut test_board, brk, color);
-
-                        // Check if opponent can recreate an UNBREAKABLE five
-                        // Breakable recreation (cycle) is acceptable — White must break anyway
-                        let mut recreates_unbreakable = false;
-                        for i in 0..cap_info.count as usize {
-                            let cap_pos = cap_info.positions[i];
-                            test_board.place_stone(cap_pos, opponent);
-                            if has_five_at_pos(&test_board, cap_pos, opponent) {
-                                if let Some(new_five) =
-                                    find_five_line_at_pos(&test_board, cap_pos, opponent)
-                                {
-                                    if !can_break_five_by_capture(
-                                        &test_board,
-                                        &new_five,
-                                        opponent,
-                                    ) {
-                                        recreates_unbreakable = true;
-                                    }
-                                }
-                            }
-                            test_board.remove_stone(cap_pos);
-                            if recreates_unbreakable {
-                                break;
-                            }
-                        }
-
-                        if !recreates_unbreakable {
-                            let score = crate::eval::evaluate(&test_board, color);
-                            if score > best_score || !any_safe_break {
-                                best_score = score;
-                                best_move = brk;
-                            }
-                            any_safe_break = true;
-                        } else {
-                            ai_log(&format!(
+                        let outcome = resolve_break(self.rules, board, &opp_five, opponent, brk);
+                        if outcome == CycleOutcome::Win {
+                            log_debug!(
                                 "    Break {} rejected: opponent recreates UNBREAKABLE five",
                                 pos_to_notation(brk)
-                            ));
+                            );
+                            continue;
                         }
-
+                        test_board.place_stone(brk, color);
+                        let cap_info = execute_captures_fast(self.rules, &mut test_board, brk, color);
+                        let score = crate::eval::evaluate(self.rules, &test_board, color);
                         undo_captures(&mut test_board, color, &cap_info);
                         test_board.remove_stone(brk);
+                        if best.is_none_or(|(o, s, _)| outcome < o || (outcome == o && score > s)) {
+                            best = Some((outcome, score, brk));
+                        }
                     }
-                    if any_safe_break {
-                        ai_log(&format!(
-                            "  >>> BEST BREAK: {} (eval={})",
-                            pos_to_notation(best_move),
-                            best_score
-                        ));
-                        return MoveResult::defense(
-                            best_move,
-                            -900_000,
-                            start.elapsed().as_millis() as u64,
-                            valid_breaks.len() as u64,
-                        );
+                    match best {
+                        Some((outcome, _, brk)) if valid_breaks.len() == 1 => {
+                            log_debug!("  >>> FORCED BREAK: {} ({:?})", pos_to_notation(brk), outcome);
+                            let result = MoveResult::defense(brk, -900_000, start.elapsed().as_millis() as u64, 1);
+                            return self.forced_reply(result, limits);
+                        }
+                        Some((outcome, score, brk)) => {
+                            log_debug!(
+                                "  >>> BEST BREAK: {} ({:?}, eval={})",
+                                pos_to_notation(brk),
+                                outcome,
+                                score
+                            );
+                            return MoveResult::defense(
+                                brk,
+                                -900_000,
+                                start.elapsed().as_millis() as u64,
+                                valid_breaks.len() as u64,
+                            );
+                        }
+                        None => log_debug!(
+                            "  Stage 0.5: All breaks lead to UNBREAKABLE recreation — falling through to alpha-beta"
+                        ),
                     }
-                    ai_log(
-                        "  Stage 0.5: All breaks lead to UNBREAKABLE recreation — falling through to alpha-beta"
-                    );
-                    // Fall through to alpha-beta for best strategic move
                 }
             } else {
                 // Opponent's five is unbreakable — game should have already ended
-                ai_log("  Stage 0.5 WARNING: Opponent has UNBREAKABLE five!");
+                log_warn!("  Stage 0.5 WARNING: Opponent has UNBREAKABLE five!");
             }
         }
 
         // 1. Check for immediate winning move (5-in-a-row or capture win)
         if let Some(win_move) = self.find_immediate_win(board, color) {
-            ai_log(&format!("  Stage 1 IMMEDIATE WIN: {}", pos_to_notation(win_move)));
-            return MoveResult::immediate_win(win_move, start.elapsed().as_millis() as u64);
+            log_debug!("  Stage 1 IMMEDIATE WIN: {}", pos_to_notation(win_move));
+            return MoveResult {
+                proof: Some(ProofLine::new(color, vec![win_move])),
+                ..MoveResult::immediate_win(win_move, start.elapsed().as_millis() as u64)
+            };
         }
-        ai_log("  Stage 1 Immediate win: none");
+        log_debug!("  Stage 1 Immediate win: none");
 
         // 2. Check if opponent can win immediately - MUST block
         let opponent_threats = self.find_winning_moves(board, opponent);
-        ai_log(&format!("  Stage 2 Opponent threats: {} positions{}", opponent_threats.len(),
+        log_debug!("  Stage 2 Opponent threats: {} positions{}", opponent_threats.len(),
             if opponent_threats.is_empty() { String::new() }
             else { format!(" [{}]", opponent_threats.iter().map(|p| pos_to_notation(*p)).collect::<Vec<_>>().join(", ")) }
-        ));
+        );
         if opponent_threats.len() == 1 {
             let block_pos = opponent_threats[0];
-            if is_valid_move(board, block_pos, color) {
-                ai_log(&format!("  >>> DEFENSE (block immediate): {}", pos_to_notation(block_pos)));
-                return MoveResult::defense(
-                    block_pos,
-                    -900_000,
-                    start.elapsed().as_millis() as u64,
-                    1,
-                );
+            if is_valid_move(self.rules, board, block_pos, color) {
+                log_debug!("  >>> DEFENSE (block immediate): {}", pos_to_notation(block_pos));
+                let result = MoveResult::defense(block_pos, -900_000, start.elapsed().as_millis() as u64, 1);
+                return self.forced_reply(result, limits);
+            }
+        }
+
+        // 2.5 Single reply: the only legal move, or the only one the
+        // opponent can't answer with a win
+        if let Some(only) = self.find_single_reply(board, color, &opponent_threats) {
+            log_debug!("  >>> SINGLE REPLY: {}", pos_to_notation(only));
+            let mut after = board.clone();
+            after.place_stone(only, color);
+            execute_captures_fast(self.rules, &mut after, only, color);
+            let score = evaluate(self.rules, &after, color);
+            let result = MoveResult::defense(only, score, start.elapsed().as_millis() as u64, 1);
+            return self.forced_reply(result, limits);
+        }
+
+        if opponent_threats.len() >= 2 {
+            log_warn!("  WARNING: Opponent has OPEN FOUR (2+ wins) - likely lost!");
+            if let Some(trap) = self.find_swindle(board, color) {
+                return MoveResult {
+                    search_type: SearchType::Swindle,
+                    ..MoveResult::defense(trap, -900_000, start.elapsed().as_millis() as u64, 1)
+                };
             }
-        } else if opponent_threats.len() >= 2 {
-            ai_log("  WARNING: Opponent has OPEN FOUR (2+ wins) - likely lost!");
         }
 
         // 3. Search VCF (Victory by Continuous Fours) - our forced win
-        // Skip VCF when opponent has 4+ captures: one more capture = instant win,
-        // so VCF is unreliable (opponent can ignore fours and capture instead).
-        // At 3 captures, find_defense_moves includes strategic captures as defenses,
-        // so VCF is still usable. At 4, too dangerous — let alpha-beta handle it.
+        // Skip VCF when opponent is one capture from the capture win: one more
+        // capture = instant win, so VCF is unreliable (opponent can ignore fours
+        // and capture instead). Two away, find_defense_moves includes strategic
+        // captures as defenses, so VCF is still usable. One away, too dangerous
+        // — let alpha-beta handle it.
         let opp_captures = board.captures(opponent);
-        let vcf_reliable = opp_captures < 4;
+        let vcf_reliable = board.captures_to_win(self.rules, opponent) > 1;
         if vcf_reliable {
             let vcf_result = self.threat_searcher.search_vcf(board, color);
             if vcf_result.found && !vcf_result.winning_sequence.is_empty() {
                 let seq: Vec<String> = vcf_result.winning_sequence.iter().map(|p| pos_to_notation(*p)).collect();
-                ai_log(&format!("  Stage 3 OUR VCF FOUND: sequence=[{}]", seq.join(" -> ")));
-                return MoveResult::vcf_win(
-                    vcf_result.winning_sequence[0],
-                    start.elapsed().as_millis() as u64,
-                    self.threat_searcher.nodes(),
-                );
+                log_debug!("  Stage 3 OUR VCF FOUND: sequence=[{}]", seq.join(" -> "));
+                return MoveResult {
+                    proof: vcf_result.proof.clone(),
+                    ..MoveResult::vcf_win(
+                        &vcf_result.winning_sequence,
+                        start.elapsed().as_millis() as u64,
+                        self.threat_searcher.nodes(),
+                    )
+                };
             }
-            ai_log(&format!("  Stage 3 Our VCF: not found ({}nodes)", self.threat_searcher.nodes()));
+            log_debug!("  Stage 3 Our VCF: not found ({}nodes)", self.threat_searcher.nodes());
         } else {
-            ai_log(&format!("  Stage 3 VCF SKIPPED: opponent has {} captures (unreliable)", opp_captures));
+            log_debug!("  Stage 3 VCF SKIPPED: opponent has {} captures (unreliable)", opp_captures);
         }
 
         // 4. Check opponent VCF - if opponent has a forced win, we must block
-        // Skip when WE have 4+ captures (opponent's VCF is unreliable — we can capture)
+        // Skip when WE are one capture from winning (opponent's VCF is unreliable — we can capture)
         let our_captures = board.captures(color);
-        let opp_vcf_reliable = our_captures < 4;
+        let opp_vcf_reliable = board.captures_to_win(self.rules, color) > 1;
         if opp_vcf_reliable {
             let opp_vcf = self.threat_searcher.search_vcf(board, opponent);
             if opp_vcf.found && !opp_vcf.winning_sequence.is_empty() {
                 let seq: Vec<String> = opp_vcf.winning_sequence.iter().map(|p| pos_to_notation(*p)).collect();
-                ai_log(&format!("  Stage 4 OPPONENT VCF FOUND: sequence=[{}]", seq.join(" -> ")));
+                log_debug!("  Stage 4 OPPONENT VCF FOUND: sequence=[{}]", seq.join(" -> "));
                 let block_pos = opp_vcf.winning_sequence[0];
-                if is_valid_move(board, block_pos, color) {
-                    ai_log(&format!("  >>> DEFENSE (block VCF): {}", pos_to_notation(block_pos)));
+                if is_valid_move(self.rules, board, block_pos, color) {
+                    log_debug!("  >>> DEFENSE (block VCF): {}", pos_to_notation(block_pos));
                     return MoveResult::defense(
                         block_pos,
                         -800_000,
@@ -603,9 +992,9 @@ impl AIEngine {
                     );
                 }
             }
-            ai_log(&format!("  Stage 4 Opponent VCF: not found ({}nodes)", self.threat_searcher.nodes()));
+            log_debug!("  Stage 4 Opponent VCF: not found ({}nodes)", self.threat_searcher.nodes());
         } else {
-            ai_log(&format!("  Stage 4 Opponent VCF SKIPPED: we have {} captures (can counter)", our_captures));
+            log_debug!("  Stage 4 Opponent VCF SKIPPED: we have {} captures (can counter)", our_captures);
         }
 
         // NOTE: VCT removed from authoritative pipeline.
@@ -613,49 +1002,224 @@ impl AIEngine {
         // Alpha-beta with threat extensions handles tactical sequences correctly.
         // VCF remains sound when capture counts are low.
 
+        if let Some(variety) = variety {
+            if let Some(result) = self.varied_move(board, color, variety.margin, start) {
+                return result;
+            }
+        }
+
         // 5. Alpha-Beta search handles ALL strategy
-        // Adaptive time: allocate more time for critical mid-game, less for
-        // opening (simple) and late-game (narrow trees).
-        let adaptive_time = self.compute_time_limit(board);
-        let result = self.searcher.search_timed(board, color, self.max_depth, adaptive_time);
+        self.alpha_beta_stage(board, color, limits, start)
+    }
+
+    /// Opening variety: a random one of the best root moves, those scoring
+    /// within `margin` of the best. `None` if the search finds no move.
+    fn varied_move(&mut self, board: &Board, color: Stone, margin: i32, start: Instant) -> Option<MoveResult> {
+        let lines = self.searcher.search_multipv(board, color, self.max_depth, VARIETY_NODES, VARIETY_LINES);
+        let best = lines.first()?.score;
+        let close = lines.iter().take_while(|line| line.score >= best.saturating_sub(margin)).count();
+        let pick = lines[(self.next_random() % close as u64) as usize];
+        log_debug!(
+            "  Stage 5 VARIETY: {} (score={}, {} of {} moves within {})",
+            pos_to_notation(pick.mov),
+            pick.score,
+            close,
+            lines.len(),
+            margin
+        );
+        Some(MoveResult {
+            depth: pick.depth,
+            ..MoveResult::alpha_beta(pick.mov, pick.score, start.elapsed().as_millis() as u64, 0)
+        })
+    }
+
+    /// Stage 5 of the pipeline: alpha-beta over the whole position, under
+    /// `limits` if given, else the engine's time/deterministic budget.
+    fn alpha_beta_stage(
+        &mut self,
+        board: &Board,
+        color: Stone,
+        limits: Option<&SearchLimits>,
+        start: Instant,
+    ) -> MoveResult {
+        // Adaptive time: less in the opening, more in a crisis (paid from
+        // the time bank)
+        let adaptive_time = self.compute_time_limit(board, color);
+        // Symmetric openings share TT entries: search the canonical image and
+        // map the move back. Not while reporting progress, whose PVs would
+        // come out transformed.
+        let canonical = (board.stone_count() <= CANONICAL_SEARCH_STONES && self.searcher.info_callback().is_none())
+            .then(|| Symmetry::canonical(board));
+        let (search_board, symmetry) = canonical.as_ref().map_or((board, Symmetry::Identity), |(b, sym)| (b, *sym));
+        let search_limits = limits.map(|limits| SearchLimits {
+            root_moves: limits.root_moves.map(|moves| symmetry.apply_bitboard(&moves)),
+            ..*limits
+        });
+        let mut result = if let Some(limits) = &search_limits {
+            self.searcher.search_with_limits(search_board, color, limits)
+        } else if self.deterministic {
+            let node_limit = adaptive_time * DETERMINISTIC_NODES_PER_MS;
+            self.searcher.search_nodes(search_board, color, self.max_depth, node_limit)
+        } else {
+            let clock = self.searcher.clock();
+            let search_start = clock.now();
+            let result = self.searcher.search_timed(search_board, color, self.max_depth, adaptive_time);
+            let spent = match (search_start, clock.now()) {
+                (Some(start), Some(end)) => end.saturating_sub(start).as_millis() as u64,
+                _ => result.nodes / NODES_PER_MS,
+            };
+            self.settle_time_bank(spent);
+            result
+        };
+        result.best_move = result.best_move.map(|mov| symmetry.inverse().apply(mov));
         let tt_usage = self.searcher.tt_stats().usage_percent;
         let elapsed = start.elapsed().as_millis() as u64;
 
-        ai_log(&format!(
+        log_debug!(
             "  Stage 5 ALPHA-BETA: move={} score={} depth={} nodes={} time={}ms nps={}k tt={}%",
-            result.best_move.map(|p| pos_to_notation(p)).unwrap_or("none".to_string()),
+            result.best_move.map(pos_to_notation).unwrap_or("none".to_string()),
             result.score, result.depth, result.nodes, elapsed,
             MoveResult::compute_nps(result.nodes, elapsed), tt_usage
-        ));
-        ai_log(&format!(
-            "    Stats: beta_cutoffs={} first_move_rate={:.1}% tt_probes={} tt_score_rate={:.1}% tt_move_hits={}",
+        );
+        log_debug!(
+            "    Stats: beta_cutoffs={} first_move_rate={:.1}% tt_probes={} tt_score_rate={:.1}% tt_move_hits={} \
+             eval_hit_rate={:.1}%",
             result.stats.beta_cutoffs,
             result.stats.first_move_rate(),
             result.stats.tt_probes,
             result.stats.tt_score_rate(),
-            result.stats.tt_move_hits
-        ));
+            result.stats.tt_move_hits,
+            result.stats.eval_hit_rate()
+        );
+
+        if result.score <= SWINDLE_TRIGGER {
+            let trap = self
+                .find_swindle(board, color)
+                .filter(|&trap| limits.is_none_or(|limits| limits.allows_root_move(trap)));
+            if let Some(trap) = trap {
+                let elapsed = start.elapsed().as_millis() as u64;
+                return MoveResult {
+                    best_move: Some(trap),
+                    search_type: SearchType::Swindle,
+                    ..MoveResult::from_alphabeta(result, elapsed, tt_usage)
+                };
+            }
+        }
 
         MoveResult::from_alphabeta(result, elapsed, tt_usage)
     }
 
-    /// Compute adaptive time limit based on game phase.
-    ///
-    /// Only reduces time in the opening where positions are simple and
-    /// deep search isn't critical. Mid-game and beyond get full time
-    /// to maintain search depth and playing strength.
-    fn compute_time_limit(&self, board: &Board) -> u64 {
-        let stones = board.stone_count();
+    /// Swindle mode: in a lost position, find the move that gives the
+    /// opponent the most plausible ways to go wrong; with speculative
+    /// defense, the move their search finds hardest to win against. `None`
+    /// if both are off or no move sets a trap.
+    fn find_swindle(&self, board: &Board, color: Stone) -> Option<Pos> {
+        if self.speculative_defense {
+            if let Some(resist) = find_resistant_move(self.rules, board, color, RESIST_NODES) {
+                log_debug!(
+                    "  >>> SPECULATIVE DEFENSE: {} (opponent mate in {}, {} nodes, error chance {:.0}%)",
+                    pos_to_notation(resist.candidate.mov),
+                    resist.opponent_mate_in.map_or("?".to_string(), |moves| moves.to_string()),
+                    resist.opponent_nodes,
+                    resist.candidate.error_chance * 100.0
+                );
+                return Some(resist.candidate.mov);
+            }
+        }
+        if !self.swindle_mode {
+            return None;
+        }
+        let Some(trap) = find_swindle_move(self.rules, board, color) else {
+            log_debug!("  Swindle: no trappy alternative");
+            return None;
+        };
+        log_debug!(
+            "  >>> SWINDLE: {} (error chance {:.0}% over {} replies, spread={:.0})",
+            pos_to_notation(trap.mov),
+            trap.error_chance * 100.0,
+            trap.replies,
+            trap.spread
+        );
+        Some(trap.mov)
+    }
 
-        // Only reduce time in opening — mid-game needs full depth
-        let pct = match stones {
-            0..=2 => 30,      // Very early: center/adjacent, trivial
-            3..=4 => 60,      // Opening: still simple positions
-            _ => 100,         // Mid-game+: full time for deep search
+    /// Time budget (ms) for `color`'s alpha-beta stage.
+    ///
+    /// Opening moves are simple and get a fraction of the time limit. A
+    /// crisis (the opponent has an open three, or a capture threat near the
+    /// capture win) asks for [`CRISIS_TIME_PERCENT`] and a tactical position
+    /// for [`TACTICAL_TIME_PERCENT`], but time beyond the limit only comes
+    /// from the bank of time saved on earlier moves. The search itself
+    /// stops early on a forced move and goes on while the best move keeps
+    /// changing.
+    fn compute_time_limit(&self, board: &Board, color: Stone) -> u64 {
+        let class = classify_position(self.rules, board);
+        let opponent = class.threats(color.opponent());
+        let crisis = opponent.open_threes > 0 || (opponent.captures_to_win <= 2 && opponent.capture_threats > 0);
+        let pct = match board.stone_count() {
+            0..=2 => 30, // Very early: center/adjacent, trivial
+            3..=4 => 60, // Opening: still simple positions
+            _ if crisis => CRISIS_TIME_PERCENT,
+            _ if class.is_tactical() => TACTICAL_TIME_PERCENT,
+            _ => 100,
         };
+        let wanted = self.time_limit_ms.saturating_mul(pct) / 100;
+        // Deterministic budgets can't depend on how long earlier moves took
+        let bank = if self.deterministic { 0 } else { self.time_bank_ms };
+        let extra = wanted.saturating_sub(self.time_limit_ms).min(bank);
 
         // Apply percentage with minimum floor of 300ms
-        (self.time_limit_ms * pct / 100).max(300)
+        (wanted.min(self.time_limit_ms) + extra).max(300)
+    }
+
+    /// Bank the time a timed alpha-beta stage saved against the time
+    /// limit, or draw down what it overspent.
+    fn settle_time_bank(&mut self, elapsed_ms: u64) {
+        let cap = self.time_limit_ms.saturating_mul(TIME_BANK_LIMITS);
+        self.time_bank_ms = self.time_bank_ms.saturating_add(self.time_limit_ms).saturating_sub(elapsed_ms).min(cap);
+    }
+
+    /// A forced move returned without searching: in timed play the time
+    /// the search would have taken goes to the bank.
+    fn forced_reply(&mut self, result: MoveResult, limits: Option<&SearchLimits>) -> MoveResult {
+        if limits.is_none() && !self.deterministic {
+            self.settle_time_bank(result.time_ms);
+        }
+        result
+    }
+
+    /// The only move for `color` that doesn't lose at once: the only legal
+    /// move, or the only one after which the opponent has no immediate win.
+    /// Moves are only tried when the opponent threatens to win
+    /// (`opponent_threats`, or one capture from the capture win); otherwise
+    /// any move survives a ply.
+    fn find_single_reply(&self, board: &Board, color: Stone, opponent_threats: &[Pos]) -> Option<Pos> {
+        let opponent = color.opponent();
+        let mut legal = legal_moves_iter(self.rules, board, color);
+        let first = legal.next()?;
+        if legal.next().is_none() {
+            return Some(first);
+        }
+        if opponent_threats.is_empty() && board.captures_to_win(self.rules, opponent) > 1 {
+            return None;
+        }
+
+        let mut survivor = None;
+        let mut test_board = board.clone();
+        for pos in legal_moves_iter(self.rules, board, color) {
+            test_board.place_stone(pos, color);
+            let cap_info = execute_captures_fast(self.rules, &mut test_board, pos, color);
+            let loses = !self.find_winning_moves(&test_board, opponent).is_empty();
+            undo_captures(&mut test_board, color, &cap_info);
+            test_board.remove_stone(pos);
+            if !loses {
+                if survivor.is_some() {
+                    return None;
+                }
+                survivor = Some(pos);
+            }
+        }
+        survivor
     }
 
     /// Find ALL positions where `color` can win immediately.
@@ -665,39 +1229,32 @@ impl AIEngine {
     /// Uses make/unmake pattern with fast has_five_at_pos check.
     fn find_winning_moves(&self, board: &Board, color: Stone) -> Vec<Pos> {
         let mut wins = Vec::new();
-        let near_capture_win = board.captures(color) >= 4;
+        let near_capture_win = board.captures_to_win(self.rules, color) <= 1;
         let mut test_board = board.clone();
 
-        for r in 0..BOARD_SIZE as u8 {
-            for c in 0..BOARD_SIZE as u8 {
-                let pos = Pos::new(r, c);
-                if !is_valid_move(board, pos, color) {
-                    continue;
-                }
-
-                // Make move
-                test_board.place_stone(pos, color);
-                let cap_info = execute_captures_fast(&mut test_board, pos, color);
-
-                // Fast five-in-a-row check (O(4 directions) vs O(all_stones * 4))
-                if has_five_at_pos(&test_board, pos, color) {
-                    // Only count as win if opponent can't break it by capture
-                    if let Some(five) = find_five_positions(&test_board, color) {
-                        if !can_break_five_by_capture(&test_board, &five, color) {
-                            wins.push(pos);
-                        }
+        for pos in legal_moves_iter(self.rules, board, color) {
+            // Make move
+            test_board.place_stone(pos, color);
+            let cap_info = execute_captures_fast(self.rules, &mut test_board, pos, color);
+
+            // Fast five-in-a-row check (O(4 directions) vs O(all_stones * 4))
+            if has_five_at_pos(self.rules, &test_board, pos, color) {
+                // Only count as win if opponent can't break it by capture
+                if let Some(five) = find_five_positions(self.rules, &test_board, color) {
+                    if !can_break_five_by_capture(self.rules, &test_board, &five, color) {
+                        wins.push(pos);
                     }
                 }
+            }
 
-                // Capture win check
-                if near_capture_win && test_board.captures(color) >= 5 && !wins.contains(&pos) {
-                    wins.push(pos);
-                }
-
-                // Unmake move
-                undo_captures(&mut test_board, color, &cap_info);
-                test_board.remove_stone(pos);
+            // Capture win check
+            if near_capture_win && test_board.captures_to_win(self.rules, color) == 0 && !wins.contains(&pos) {
+                wins.push(pos);
             }
+
+            // Unmake move
+            undo_captures(&mut test_board, color, &cap_info);
+            test_board.remove_stone(pos);
         }
         wins
     }
@@ -707,162 +1264,612 @@ impl AIEngine {
     /// Checks for moves that win instantly via:
     /// - 5-in-a-row (using fast has_five_at_pos)
     /// - Capturing the 5th pair (10 total stones)
+    ///
     /// Uses make/unmake pattern to avoid cloning per position.
     fn find_immediate_win(&self, board: &Board, color: Stone) -> Option<Pos> {
-        let near_capture_win = board.captures(color) >= 4;
+        let near_capture_win = board.captures_to_win(self.rules, color) <= 1;
         let mut test_board = board.clone();
 
-        for r in 0..BOARD_SIZE as u8 {
-            for c in 0..BOARD_SIZE as u8 {
-                let pos = Pos::new(r, c);
-                if !is_valid_move(board, pos, color) {
-                    continue;
-                }
-
-                // Make move
-                test_board.place_stone(pos, color);
-                let cap_info = execute_captures_fast(&mut test_board, pos, color);
-
-                // Check five-in-a-row (fast, O(4 directions))
-                if has_five_at_pos(&test_board, pos, color) {
-                    if let Some(five) = find_five_positions(&test_board, color) {
-                        if !can_break_five_by_capture(&test_board, &five, color) {
-                            // Unbreakable five → immediate win
-                            return Some(pos);
-                        }
-                        // Five is STATICALLY breakable. Check if all breaks are illusory
-                        // (break captures a bracket stone, so replay creates unbreakable five).
-                        if Self::is_illusory_break(&test_board, &five, color) {
-                            return Some(pos);
-                        }
+        for pos in legal_moves_iter(self.rules, board, color) {
+            // Make move
+            test_board.place_stone(pos, color);
+            let cap_info = execute_captures_fast(self.rules, &mut test_board, pos, color);
+
+            // Check five-in-a-row (fast, O(4 directions))
+            if has_five_at_pos(self.rules, &test_board, pos, color) {
+                if let Some(five) = find_five_positions(self.rules, &test_board, color) {
+                    // Unbreakable, or every break answered by a replay that
+                    // ends in an unbreakable five or a capture win
+                    if resolve_five(self.rules, &test_board, &five, color) == CycleOutcome::Win {
+                        return Some(pos);
                     }
                 }
+            }
 
-                // Check capture win
-                if near_capture_win && test_board.captures(color) >= 5 {
-                    return Some(pos);
-                }
-
-                // Unmake move
-                undo_captures(&mut test_board, color, &cap_info);
-                test_board.remove_stone(pos);
+            // Check capture win
+            if near_capture_win && test_board.captures_to_win(self.rules, color) == 0 {
+                return Some(pos);
             }
+
+            // Unmake move
+            undo_captures(&mut test_board, color, &cap_info);
+            test_board.remove_stone(pos);
         }
         None
     }
 
-    /// Check if all break captures on a five are illusory.
+    /// Set the maximum search depth for alpha-beta.
+    ///
+    /// Higher depths give stronger play but take longer.
+    /// Recommended range: 8-14
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - Maximum search depth
+    pub fn set_max_depth(&mut self, depth: i8) {
+        self.max_depth = depth;
+    }
+
+    /// Set the time limit for alpha-beta search (milliseconds).
+    /// The search uses iterative deepening with soft/hard limits derived from this value.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_ms` - Time limit in milliseconds
+    pub fn set_time_limit(&mut self, time_ms: u64) {
+        self.time_limit_ms = time_ms;
+    }
+
+    /// Set or clear opening variety.
+    ///
+    /// With `Some`, the engine's moves through game move
+    /// [`moves`](OpeningVariety::moves) are picked at random among the root
+    /// moves scoring within [`margin`](OpeningVariety::margin) of the best,
+    /// instead of the book and the single best move, so repeated games
+    /// against the engine vary. Wins, forced defenses and VCF still come
+    /// first. The choices come from the engine's random generator
+    /// ([`AIEngine::set_seed`]): the same seed and game give the same moves.
+    /// Off by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gomoku::engine::OpeningVariety;
+    /// use gomoku::{AIEngine, Board, Pos, Stone};
+    ///
+    /// let mut board = Board::new();
+    /// board.place_stone(Pos::new(9, 9), Stone::Black);
+    /// let mut engine = AIEngine::with_config(8, 4, 500);
+    /// engine.set_seed(7);
+    /// engine.set_opening_variety(Some(OpeningVariety::default()));
+    /// assert!(engine.get_move(&board, Stone::White).is_some());
+    /// ```
+    pub fn set_opening_variety(&mut self, variety: Option<OpeningVariety>) {
+        self.opening_variety = variety;
+    }
+
+    /// Opening variety settings, if enabled.
+    #[must_use]
+    pub fn opening_variety(&self) -> Option<OpeningVariety> {
+        self.opening_variety
+    }
+
+    /// Time the alpha-beta search and the time bank by `clock` instead of the
+    /// system clock ([`Searcher::set_clock`]).
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.searcher.set_clock(clock);
+    }
+
+    /// Seed every source of randomness in the engine: the Zobrist keys
+    /// ([`Searcher::set_zobrist_seed`], which drops the transposition table
+    /// when the seed changes) and the generator behind opening variety, so
+    /// games, self-play datasets and bug reproductions replay exactly.
+    /// Defaults to [`ZobristTable::DEFAULT_SEED`].
+    ///
+    /// A transposition table saved under one seed only loads under the same.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = seed;
+        self.searcher.set_zobrist_seed(seed);
+    }
+
+    /// Seed set with [`AIEngine::set_seed`].
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Next value of the engine's random generator (31 bits)
+    fn next_random(&mut self) -> u64 {
+        self.rng = self.rng.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        self.rng >> 33
+    }
+
+    /// Enable or disable deterministic search.
+    ///
+    /// Lazy SMP makes timed searches vary from run to run. In deterministic
+    /// mode the alpha-beta stage runs single-threaded with a fixed node
+    /// budget derived from the time limit, so the same engine state and
+    /// position always give the same move, score, depth and node count.
+    /// Off by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to search deterministically
+    pub fn set_deterministic(&mut self, enabled: bool) {
+        self.deterministic = enabled;
+    }
+
+    /// Whether deterministic search is enabled.
+    #[must_use]
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Clear the transposition table cache and the time saved for later
+    /// moves.
+    ///
+    /// Call this when starting a new game to avoid stale positions.
+    pub fn clear_cache(&mut self) {
+        self.searcher.clear_tt();
+        self.searcher.clear_history();
+        self.time_bank_ms = 0;
+    }
+
+    /// Resize the transposition table to `size_mb` megabytes.
+    ///
+    /// Stored positions are lost but the rest of the engine state is kept.
+    pub fn set_hash_size(&mut self, size_mb: usize) {
+        self.searcher.resize_tt(size_mb);
+    }
+
+    /// Transposition table size in megabytes.
+    #[must_use]
+    pub fn hash_size(&self) -> usize {
+        self.searcher.tt_size_mb()
+    }
+
+    /// Save the transposition table to `path` ([`Searcher::save_tt`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be written.
+    pub fn save_hash(&self, path: impl AsRef<Path>) -> io::Result<usize> {
+        self.searcher.save_tt(path)
+    }
+
+    /// Warm the transposition table from a file written by
+    /// [`AIEngine::save_hash`] ([`Searcher::load_tt`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or doesn't match this
+    /// engine version and rule set.
+    pub fn load_hash(&self, path: impl AsRef<Path>) -> io::Result<usize> {
+        self.searcher.load_tt(path)
+    }
+
+    /// Set the number of search threads used by timed searches (at least 1).
+    ///
+    /// Deterministic mode and node-limited searches stay single-threaded.
+    pub fn set_threads(&mut self, threads: usize) {
+        self.searcher.set_threads(threads);
+    }
+
+    /// Number of search threads used by timed searches.
+    #[must_use]
+    pub fn threads(&self) -> usize {
+        self.searcher.threads()
+    }
+
+    /// Audit every move the alpha-beta search makes and undoes, panicking
+    /// on a corrupted board (see [`Searcher::set_paranoid`]). For debugging;
+    /// searches get several times slower.
+    pub fn set_paranoid(&mut self, enabled: bool) {
+        self.searcher.set_paranoid(enabled);
+    }
+
+    /// Whether searches audit their moves.
+    #[must_use]
+    pub fn is_paranoid(&self) -> bool {
+        self.searcher.is_paranoid()
+    }
+
+    /// Tune the alpha-beta search with `params` (e.g. from
+    /// [`SearchParams::load`]).
     ///
-    /// A break is "illusory" when:
-    /// 1. The break capture removes a five-stone AND a bracket stone
-    /// 2. The five-holder replays the captured five-stone
-    /// 3. The recreated five is unbreakable (bracket stone gone)
+    /// # Errors
     ///
-    /// If ALL break moves are illusory, the five is effectively unbreakable
-    /// and counts as an immediate win (forced 3-ply sequence).
-    fn is_illusory_break(board: &Board, five_positions: &[Pos], five_color: Stone) -> bool {
-        let opponent = five_color.opponent();
-        let break_moves = find_five_break_moves(board, five_positions, five_color);
+    /// Returns an error (and keeps the current parameters) if `params`
+    /// fails [`SearchParams::validate`].
+    pub fn set_search_params(&mut self, params: SearchParams) -> Result<(), String> {
+        self.searcher.set_params(params)
+    }
 
-        if break_moves.is_empty() {
-            return false;
+    /// Parameters the alpha-beta search is tuned by.
+    #[must_use]
+    pub fn search_params(&self) -> SearchParams {
+        self.searcher.params()
+    }
+
+    /// Play by `rules` from now on. Changing the rules clears the
+    /// transposition table and the time bank, like [`AIEngine::clear_cache`];
+    /// other engines keep their own rules.
+    pub fn set_rules(&mut self, rules: RuleSet) {
+        if rules != self.rules {
+            self.searcher.set_rules(rules);
+            self.threat_searcher.set_rules(rules);
+            self.rules = rules;
+            self.clear_cache();
         }
+    }
 
-        for &break_pos in &break_moves {
-            // Simulate opponent's break capture
-            let mut sim = board.clone();
-            sim.place_stone(break_pos, opponent);
-            let cap_info = execute_captures_fast(&mut sim, break_pos, opponent);
+    /// Rules the engine plays by, [`RuleSet::NINUKI`] unless set
+    #[must_use]
+    pub fn rules(&self) -> RuleSet {
+        self.rules
+    }
 
-            // Find which five stones were captured
-            let mut captured_five_stone = None;
-            let mut captured_five_count = 0;
-            for i in 0..cap_info.count as usize {
-                if five_positions.contains(&cap_info.positions[i]) {
-                    captured_five_stone = Some(cap_info.positions[i]);
-                    captured_five_count += 1;
-                }
-            }
+    /// Call `callback` with a [`SearchInfo`] after each depth the
+    /// alpha-beta search completes: score, expected line, nodes and speed.
+    ///
+    /// It runs on the searching thread; the VCF and defense stages, which
+    /// answer without iterating, don't report.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use gomoku::{AIEngine, Board, Pos, Stone};
+    ///
+    /// let mut engine = AIEngine::with_config(8, 6, 1000);
+    /// let depths = Arc::new(Mutex::new(Vec::new()));
+    /// let seen = Arc::clone(&depths);
+    /// engine.set_info_callback(move |info| seen.lock().unwrap().push(info.depth));
+    ///
+    /// let mut board = Board::new();
+    /// board.place_stone(Pos::new(9, 9), Stone::Black);
+    /// board.place_stone(Pos::new(9, 10), Stone::White);
+    /// board.place_stone(Pos::new(10, 10), Stone::Black);
+    /// engine.get_move_with_stats(&board, Stone::White);
+    /// assert!(!depths.lock().unwrap().is_empty());
+    /// ```
+    pub fn set_info_callback(&mut self, callback: impl Fn(SearchInfo) + Send + Sync + 'static) {
+        self.searcher.set_info_callback(Some(Arc::new(callback)));
+    }
 
-            // If two or more five stones captured, can't recreate with one replay
-            if captured_five_count >= 2 {
-                return false;
-            }
+    /// Stop reporting search progress ([`AIEngine::set_info_callback`]).
+    pub fn clear_info_callback(&mut self) {
+        self.searcher.set_info_callback(None);
+    }
 
-            let replay_pos = match captured_five_stone {
-                Some(p) => p,
-                None => return false, // Break doesn't hit five stones (shouldn't happen)
-            };
+    /// Handle for cancelling the alpha-beta search from another thread
+    /// (the move returned is the best found so far) and for watching its
+    /// depth and node count.
+    #[must_use]
+    pub fn search_control(&self) -> Arc<SearchControl> {
+        self.searcher.control()
+    }
 
-            // Position must be empty after capture (it was just captured)
-            if !sim.is_empty(replay_pos) {
-                return false;
+    /// Choose a move for `color` on a new thread
+    /// ([`AIEngine::get_move_with_stats`]). The engine moves into the
+    /// returned handle and comes back with the result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gomoku::{AIEngine, Board, Pos, Stone};
+    ///
+    /// let mut board = Board::new();
+    /// board.place_stone(Pos::new(9, 9), Stone::Black);
+    /// let handle = AIEngine::with_config(8, 6, 300).search_async(&board, Stone::White);
+    /// // ... keep serving events, polling `handle.try_finish()` ...
+    /// let (result, _engine) = handle.wait().unwrap();
+    /// assert!(result.best_move.is_some());
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    #[must_use]
+    pub fn search_async(self, board: &Board, color: Stone) -> SearchHandle {
+        let board = board.clone();
+        self.search_async_with(move |engine| engine.get_move_with_stats(&board, color))
+    }
+
+    /// Run `search` on this engine on a new thread, for searches other
+    /// than [`AIEngine::search_async`]'s (a clock, limits).
+    ///
+    /// Clears a pending cancel first. An info callback already set still
+    /// sees every iteration.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[must_use]
+    pub fn search_async_with(
+        mut self,
+        search: impl FnOnce(&mut AIEngine) -> MoveResult + Send + 'static,
+    ) -> SearchHandle {
+        let control = self.search_control();
+        control.reset();
+        let info = Arc::new(Mutex::new(None));
+        let previous = self.searcher.info_callback();
+        let latest = Arc::clone(&info);
+        let forward = previous.clone();
+        self.searcher.set_info_callback(Some(Arc::new(move |update: SearchInfo| {
+            if let Some(forward) = &forward {
+                forward(update.clone());
+            }
+            if let Ok(mut latest) = latest.lock() {
+                *latest = Some(update);
             }
+        })));
+
+        let shared = Arc::new((Mutex::new(HandleState::default()), Condvar::new()));
+        let finished = Arc::clone(&shared);
+        std::thread::spawn(move || {
+            let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+                let result = search(&mut self);
+                self.searcher.set_info_callback(previous);
+                (result, self)
+            }))
+            .map_err(|_| "search thread panicked".to_string());
+            let (state, done) = &*finished;
+            let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+            state.outcome = Some(outcome);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+            done.notify_all();
+        });
 
-            // Simulate replay
-            sim.place_stone(replay_pos, five_color);
+        SearchHandle { shared, control, info, started: Instant::now() }
+    }
 
-            // Check if five is recreated at replay position
-            if !has_five_at_pos(&sim, replay_pos, five_color) {
-                return false;
-            }
+    /// Enable or disable swindle mode.
+    ///
+    /// When enabled and the alpha-beta search reports a hopeless position,
+    /// the engine replaces the objectively best defense with the move that
+    /// gives a fallible (human) opponent the most plausible ways to go wrong.
+    /// Off by default; only useful against humans.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to play for swindles in lost positions
+    pub fn set_swindle_mode(&mut self, enabled: bool) {
+        self.swindle_mode = enabled;
+    }
 
-            // Check if recreated five is now unbreakable
-            if let Some(new_five) = find_five_line_at_pos(&sim, replay_pos, five_color) {
-                if can_break_five_by_capture(&sim, &new_five, five_color) {
-                    return false; // Recreated five is still breakable → genuine break
-                }
-            } else {
-                return false;
-            }
+    /// Whether swindle mode is enabled.
+    #[must_use]
+    pub fn swindle_mode(&self) -> bool {
+        self.swindle_mode
+    }
+
+    /// Enable or disable speculative defense.
+    ///
+    /// Like swindle mode, it replaces the objectively best defense once the
+    /// search reports a hopeless position, but ranks the candidates by how
+    /// hard they make the win: each one is answered by the opponent's own
+    /// node-limited mate search, and the engine prefers moves whose win that
+    /// search can't prove, then the longest forced wins, then the trappiest
+    /// moves (see [`find_resistant_move`]). Takes precedence over swindle
+    /// mode. Off by default.
+    pub fn set_speculative_defense(&mut self, enabled: bool) {
+        self.speculative_defense = enabled;
+    }
+
+    /// Whether speculative defense is enabled.
+    #[must_use]
+    pub fn speculative_defense(&self) -> bool {
+        self.speculative_defense
+    }
+
+    /// Set the auto-resign threshold.
+    ///
+    /// With `Some(score)`, [`AIEngine::should_resign`] reports `true` once a
+    /// search returns `score` or less from the engine's point of view, e.g.
+    /// `Some(-PatternScore::FIVE + 100)` to resign only proven losses.
+    /// `None` (the default) never resigns.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - Score at or below which to resign
+    pub fn set_resign_threshold(&mut self, threshold: Option<i32>) {
+        self.resign_threshold = threshold;
+    }
+
+    /// Get the auto-resign threshold.
+    #[must_use]
+    pub fn resign_threshold(&self) -> Option<i32> {
+        self.resign_threshold
+    }
+
+    /// Whether the engine should resign instead of playing `result`.
+    ///
+    /// Always `false` when no resign threshold is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gomoku::{AIEngine, MoveResult, Pos, SearchType};
+    ///
+    /// let mut engine = AIEngine::with_config(1, 4, 500);
+    /// let lost = MoveResult {
+    ///     best_move: Some(Pos::new(9, 9)),
+    ///     score: -1_000_000,
+    ///     search_type: SearchType::AlphaBeta,
+    ///     time_ms: 0,
+    ///     nodes: 0,
+    ///     depth: 8,
+    ///     tt_usage: 0,
+    ///     nps: 0,
+    ///     mate_in: Some(-1),
+    ///     threads: 1,
+    ///     proof: None,
+    /// };
+    /// assert!(!engine.should_resign(&lost));
+    ///
+    /// engine.set_resign_threshold(Some(-900_000));
+    /// assert!(engine.should_resign(&lost));
+    /// ```
+    #[must_use]
+    pub fn should_resign(&self, result: &MoveResult) -> bool {
+        self.resign_threshold.is_some_and(|t| result.score <= t)
+    }
+
+    /// Whether `color` should accept a draw offer in this position.
+    ///
+    /// Accepts when the static evaluation (see
+    /// [`AIEngine::evaluate_position`]) shows no meaningful advantage for
+    /// `color`: level positions and worse are drawn, anything better than
+    /// an open two is played on. A game already won on the board is never
+    /// given away.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - Current position
+    /// * `color` - Color deciding on the offer
+    #[must_use]
+    pub fn should_accept_draw(&self, board: &Board, color: Stone) -> bool {
+        self.evaluate_position(board, color) <= DRAW_ACCEPT_MAX_SCORE
+    }
+
+    /// Suggest a move for `color` with the reason it is good.
+    ///
+    /// The move is [`AIEngine::get_move_with_stats`]'s choice; the reason
+    /// comes from the pipeline stage that found it (win, forced win,
+    /// defense, swindle) and, for ordinary moves, from the lines the move
+    /// makes or takes away from the opponent. `None` when there is no move.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gomoku::{AIEngine, Board, HintReason, Pos, Stone};
+    ///
+    /// let mut board = Board::new();
+    /// for col in 5..9 {
+    ///     board.place_stone(Pos::new(9, col), Stone::Black);
+    /// }
+    /// board.place_stone(Pos::new(9, 4), Stone::White);
+    ///
+    /// let mut engine = AIEngine::with_config(8, 6, 200);
+    /// let hint = engine.get_hint(&board, Stone::White).unwrap();
+    /// assert_eq!(hint.reason, HintReason::BlocksFive);
+    /// assert_eq!(hint.to_string(), "K10 blocks the opponent's five");
+    /// ```
+    #[must_use]
+    pub fn get_hint(&mut self, board: &Board, color: Stone) -> Option<Hint> {
+        let result = self.get_move_with_stats(board, color);
+        let mov = result.best_move?;
+        let reason = explain_move(self.rules, board, color, mov, &result);
+        Some(Hint { mov, reason, result })
+    }
+
+    /// Get the current maximum search depth.
+    #[must_use]
+    pub fn max_depth(&self) -> i8 {
+        self.max_depth
+    }
+
+    /// Get transposition table statistics.
+    #[must_use]
+    pub fn tt_stats(&self) -> crate::search::TTStats {
+        self.searcher.tt_stats()
+    }
+
+    /// Static evaluation of a position from the side to move's point of view.
+    ///
+    /// This is the supported entry point for scoring a position without
+    /// searching. The sign convention is the one used throughout the search
+    /// (negamax):
+    ///
+    /// - Positive = good for `side_to_move`, negative = good for the opponent
+    /// - `evaluate_position(b, Black) == -evaluate_position(b, White)`
+    /// - `±PatternScore::FIVE` marks a decided game (five-in-a-row on the
+    ///   board or 5 captured pairs), regardless of whose turn it is
+    ///
+    /// Scores are in the same units as [`PatternScore`]
+    /// (an open three is worth 10 000). Use [`AIEngine::win_probability`]
+    /// to map the score to an expected result.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - Position to evaluate
+    /// * `side_to_move` - Color whose perspective the score is reported from
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gomoku::{AIEngine, Board, Pos, Stone};
+    ///
+    /// let engine = AIEngine::with_config(1, 4, 500);
+    /// let mut board = Board::new();
+    /// board.place_stone(Pos::new(9, 9), Stone::Black);
+    /// board.place_stone(Pos::new(9, 10), Stone::Black);
+    ///
+    /// let black = engine.evaluate_position(&board, Stone::Black);
+    /// let white = engine.evaluate_position(&board, Stone::White);
+    /// assert!(black > 0);
+    /// assert_eq!(black, -white);
+    /// ```
+    #[must_use]
+    pub fn evaluate_position(&self, board: &Board, side_to_move: Stone) -> i32 {
+        debug_assert!(side_to_move != Stone::Empty, "side_to_move must be Black or White");
+        let opponent = side_to_move.opponent();
+
+        // evaluate() assumes no five is on the board (the search checks fives
+        // before reaching a leaf), so handle decided games here.
+        if board.captures_to_win(self.rules, side_to_move) == 0 || has_five_in_row(self.rules, board, side_to_move) {
+            return PatternScore::FIVE;
+        }
+        if board.captures_to_win(self.rules, opponent) == 0 || has_five_in_row(self.rules, board, opponent) {
+            return -PatternScore::FIVE;
         }
 
-        true // All breaks are illusory → effectively unbreakable
+        evaluate(self.rules, board, side_to_move)
     }
 
-    /// Set the maximum search depth for alpha-beta.
-    ///
-    /// Higher depths give stronger play but take longer.
-    /// Recommended range: 8-14
+    /// Convert an evaluation score into an expected result in `[0.0, 1.0]`.
     ///
-    /// # Arguments
+    /// Uses a logistic curve scaled to the pattern weights: an open three
+    /// (`PatternScore::OPEN_THREE`) is roughly 62%, an open four about 99%,
+    /// and `±PatternScore::FIVE` maps to exactly 1.0 / 0.0. The result is
+    /// from the same perspective as the score that was passed in, so
+    /// `win_probability(s) + win_probability(-s)` is 1.0 (up to rounding).
     ///
-    /// * `depth` - Maximum search depth
-    pub fn set_max_depth(&mut self, depth: i8) {
-        self.max_depth = depth;
-    }
-
-    /// Set the time limit for alpha-beta search (milliseconds).
-    /// The search uses iterative deepening with soft/hard limits derived from this value.
+    /// # Example
     ///
-    /// # Arguments
+    /// ```
+    /// use gomoku::AIEngine;
     ///
-    /// * `time_ms` - Time limit in milliseconds
-    pub fn set_time_limit(&mut self, time_ms: u64) {
-        self.time_limit_ms = time_ms;
+    /// assert_eq!(AIEngine::win_probability(0), 0.5);
+    /// assert!(AIEngine::win_probability(50_000) > 0.9);
+    /// ```
+    #[must_use]
+    pub fn win_probability(score: i32) -> f64 {
+        if score >= PatternScore::FIVE {
+            return 1.0;
+        }
+        if score <= -PatternScore::FIVE {
+            return 0.0;
+        }
+        1.0 / (1.0 + (-f64::from(score) / WIN_PROBABILITY_SCALE).exp())
     }
 
-    /// Clear the transposition table cache.
+    /// Get an opening book move for the first 1-2 moves.
     ///
-    /// Call this when starting a new game to avoid stale positions.
-    pub fn clear_cache(&mut self) {
-        self.searcher.clear_tt();
-        self.searcher.clear_history();
-    }
-
-    /// Get the current maximum search depth.
-    #[must_use]
-    pub fn max_depth(&self) -> i8 {
-        self.max_depth
+    /// The book is consulted on the [`Symmetry::canonical`] image of the
+    /// position and its answer mapped back, so symmetric openings get
+    /// symmetric answers whichever way ties fall.
+    pub(crate) fn get_opening_move(&self, board: &Board, color: Stone) -> Option<Pos> {
+        Self::opening_book_move(board, color)
     }
 
-    /// Get transposition table statistics.
+    /// The opening book's answer for `color` in `board`, if it has one
+    /// (see [`get_opening_move`](Self::get_opening_move)); no engine needed.
     #[must_use]
-    pub fn tt_stats(&self) -> crate::search::TTStats {
-        self.searcher.tt_stats()
+    pub fn opening_book_move(board: &Board, color: Stone) -> Option<Pos> {
+        let (canonical, symmetry) = Symmetry::canonical(board);
+        Self::book_move(&canonical, color).map(|mov| symmetry.inverse().apply(mov))
     }
 
-    /// Get an opening book move for the first 1-2 moves.
+    /// Opening book answer in `board` itself.
     ///
     /// - Empty board: play center (9,9)
     /// - One opponent stone: play diagonally adjacent, preferring center-ward
@@ -871,7 +1878,7 @@ impl AIEngine {
     /// adjacent to the opponent's stone to contest territory and start
     /// building connected patterns. Diagonal placement is strongest because
     /// it creates potential in two diagonal directions simultaneously.
-    pub(crate) fn get_opening_move(&self, board: &Board, color: Stone) -> Option<Pos> {
+    fn book_move(board: &Board, color: Stone) -> Option<Pos> {
         // Empty board → center is universally optimal
         if board.stone_count() == 0 {
             return Some(Pos::new(9, 9));
@@ -971,12 +1978,209 @@ impl AIEngine {
     }
 }
 
+/// Directions of the four lines through a point
+const LINE_DIRECTIONS: [(i32, i32); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+/// Lines through `pos` after `color` plays there (captures ignored): the
+/// number of directions with a five, with a four, and whether one of the
+/// fours is open (`_XXXX_`)
+fn line_threats(board: &Board, pos: Pos, color: Stone) -> (u8, u8, bool) {
+    let mut board = board.clone();
+    board.place_stone(pos, color);
+    let (mut fives, mut fours, mut open_four) = (0, 0, false);
+    for (dr, dc) in LINE_DIRECTIONS {
+        // Cells -5..=5 along the line; `None` off the board
+        let cells: Vec<Option<Stone>> = (-5..=5)
+            .map(|k| {
+                let (r, c) = (i32::from(pos.row) + k * dr, i32::from(pos.col) + k * dc);
+                #[allow(clippy::cast_sign_loss)]
+                Pos::is_valid(r, c).then(|| board.get(Pos::new(r as u8, c as u8)))
+            })
+            .collect();
+        let run = |from: usize, len: usize| cells[from..from + len].iter().filter(|&&c| c == Some(color)).count();
+        let empty = |i: usize| cells[i] == Some(Stone::Empty);
+
+        // Windows of five containing the played point (index 5)
+        let windows = (1..=5).filter(|&i| cells[i..i + 5].iter().all(|&c| c == Some(color) || c == Some(Stone::Empty)));
+        let counts: Vec<usize> = windows.map(|i| run(i, 5)).collect();
+        if counts.contains(&5) {
+            fives += 1;
+        } else if counts.contains(&4) {
+            fours += 1;
+            // Four in a row with both ends empty
+            open_four |= (1..=4).any(|i| empty(i) && run(i + 1, 4) == 4 && empty(i + 5));
+        }
+    }
+    (fives, fours, open_four)
+}
+
+/// Reason for `mov`, the move `result` chose for `color` on `board` under `rules`
+fn explain_move(rules: RuleSet, board: &Board, color: Stone, mov: Pos, result: &MoveResult) -> HintReason {
+    let opponent = color.opponent();
+    let (fives, fours, open_four) = line_threats(board, mov, color);
+    let pairs = count_captures(rules, board, mov, color);
+
+    if fives > 0 {
+        return HintReason::WinsByFive;
+    }
+    if pairs > 0 && pairs >= board.captures_to_win(rules, color) {
+        return HintReason::WinsByCapture;
+    }
+    if let Some(moves) = result.mate_in.filter(|&n| n > 1) {
+        return HintReason::ForcedWin { moves };
+    }
+    if result.search_type == SearchType::Swindle {
+        return HintReason::Swindle;
+    }
+    if pairs > 0 && find_five_positions(rules, board, opponent).is_some() {
+        return HintReason::BreaksFive;
+    }
+
+    let (opp_fives, opp_fours, opp_open_four) = line_threats(board, mov, opponent);
+    if opp_fives > 0 {
+        return HintReason::BlocksFive;
+    }
+    let threes = count_free_threes(board, mov, color);
+    if fours >= 2 || (fours >= 1 && threes >= 1) {
+        return HintReason::DoubleThreat { fours, threes };
+    }
+    if open_four {
+        HintReason::OpenFour
+    } else if pairs > 0 {
+        HintReason::Captures { pairs }
+    } else if opp_open_four {
+        HintReason::BlocksOpenFour
+    } else if fours > 0 {
+        HintReason::Four
+    } else if opp_fours > 0 {
+        HintReason::BlocksFour
+    } else if count_free_threes(board, mov, opponent) > 0 {
+        HintReason::BlocksOpenThree
+    } else if threes > 0 {
+        HintReason::OpenThree
+    } else {
+        HintReason::Positional
+    }
+}
+
 impl Default for AIEngine {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// What an asynchronous search gives back: its result and the engine, or
+/// why the search thread died
+pub type SearchOutcome = Result<(MoveResult, AIEngine), String>;
+
+/// Completion slot shared with the search thread
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+struct HandleState {
+    outcome: Option<SearchOutcome>,
+    waker: Option<Waker>,
+}
+
+/// A search running on its own thread ([`AIEngine::search_async`]).
+///
+/// Poll it with [`SearchHandle::try_finish`] (once per frame in a GUI),
+/// block with [`SearchHandle::wait`] or `.await` it. The engine, with its
+/// transposition table, comes back with the result; dropping the handle
+/// lets the search run out and drops the engine with it.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct SearchHandle {
+    shared: Arc<(Mutex<HandleState>, Condvar)>,
+    control: Arc<SearchControl>,
+    info: Arc<Mutex<Option<SearchInfo>>>,
+    started: Instant,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SearchHandle {
+    fn state(&self) -> MutexGuard<'_, HandleState> {
+        self.shared.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Whether the search has ended and its outcome not been taken yet.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.state().outcome.is_some()
+    }
+
+    /// The outcome, if the search has ended. Returns it once; later calls
+    /// (and `wait` or `.await`) get `None` or block forever.
+    pub fn try_finish(&mut self) -> Option<SearchOutcome> {
+        self.state().outcome.take()
+    }
+
+    /// Block until the search ends.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the search thread panicked.
+    pub fn wait(self) -> SearchOutcome {
+        let (state, done) = &*self.shared;
+        let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            if let Some(outcome) = state.outcome.take() {
+                return outcome;
+            }
+            state = done.wait(state).unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Stop the search; it ends with the best move found so far.
+    pub fn cancel(&self) {
+        self.control.cancel();
+    }
+
+    /// Whether [`SearchHandle::cancel`] was called.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.control.is_cancelled()
+    }
+
+    /// Deepest alpha-beta iteration completed so far.
+    #[must_use]
+    pub fn depth(&self) -> i8 {
+        self.control.depth()
+    }
+
+    /// Nodes searched so far over all threads.
+    #[must_use]
+    pub fn nodes(&self) -> u64 {
+        self.control.nodes()
+    }
+
+    /// The last completed iteration: score, expected line and speed.
+    #[must_use]
+    pub fn info(&self) -> Option<SearchInfo> {
+        self.info.lock().ok()?.clone()
+    }
+
+    /// Time since the search started.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Future for SearchHandle {
+    type Output = SearchOutcome;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<SearchOutcome> {
+        let mut state = self.state();
+        match state.outcome.take() {
+            Some(outcome) => Poll::Ready(outcome),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1057,6 +2261,28 @@ mod tests {
         assert_eq!(move_result.best_move, Some(Pos::new(8, 10)));
     }
 
+    #[test]
+    fn test_symmetric_openings_get_symmetric_moves() {
+        // Book (one stone) and searched (diagonal pair) openings alike
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.place_stone(Pos::new(8, 8), Stone::White);
+        board.place_stone(Pos::new(8, 10), Stone::Black);
+        let mut single = Board::new();
+        single.place_stone(Pos::new(6, 11), Stone::Black);
+
+        for (board, color) in [(board, Stone::White), (single, Stone::White)] {
+            let mut engine = AIEngine::with_config(8, 6, 500);
+            engine.set_deterministic(true);
+            let mov = engine.get_move(&board, color).unwrap();
+            for sym in [Symmetry::Rotate90, Symmetry::FlipCols, Symmetry::AntiTranspose] {
+                engine.clear_cache();
+                let image = engine.get_move(&board.transform(sym), color);
+                assert_eq!(image, Some(sym.apply(mov)), "{:?}", sym);
+            }
+        }
+    }
+
     #[test]
     fn test_opening_book_skips_diagonal_pair() {
         // K10(B) + L9(B) = diagonal pair → book should NOT apply
@@ -1121,7 +2347,7 @@ mod tests {
     fn test_capture_win_detection() {
         let mut board = Board::new();
         // Set up near capture win scenario
-        board.black_captures = 4; // 4 pairs = 8 stones
+        board.set_captures(RuleSet::NINUKI, Stone::Black, 4).unwrap(); // 4 pairs = 8 stones
 
         // Place a capturable pair - this creates an immediate win via capture
         // B-W-W-? pattern at row 9, Black plays at col 11 to capture
@@ -1182,6 +2408,170 @@ mod tests {
         // This test just ensures no panic
     }
 
+    #[test]
+    fn test_deterministic_mode_reproduces() {
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.place_stone(Pos::new(9, 10), Stone::White);
+        board.place_stone(Pos::new(10, 9), Stone::Black);
+        board.place_stone(Pos::new(8, 10), Stone::White);
+        board.place_stone(Pos::new(10, 11), Stone::Black);
+        board.place_stone(Pos::new(8, 8), Stone::White);
+
+        let run = || {
+            let mut engine = AIEngine::with_config(8, 20, 300);
+            engine.set_deterministic(true);
+            assert!(engine.is_deterministic());
+            engine.get_move_with_stats(&board, Stone::Black)
+        };
+        let (a, b) = (run(), run());
+        assert_eq!(a.search_type, SearchType::AlphaBeta);
+        assert_eq!((a.best_move, a.score, a.depth, a.nodes), (b.best_move, b.score, b.depth, b.nodes));
+    }
+
+    #[test]
+    fn test_set_hash_size() {
+        let mut engine = AIEngine::with_config(1, 20, 300);
+        let before = engine.tt_stats().size;
+        engine.set_hash_size(2);
+        assert_eq!(engine.tt_stats().size, before * 2);
+
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        assert!(engine.get_move(&board, Stone::White).is_some());
+    }
+
+    #[test]
+    fn test_set_threads() {
+        let mut engine = AIEngine::with_config(1, 20, 100);
+        engine.set_threads(3);
+        assert_eq!(engine.threads(), 3);
+        engine.set_threads(0);
+        assert_eq!(engine.threads(), 1);
+    }
+
+    #[test]
+    fn test_time_budget_follows_complexity_and_bank() {
+        let mut engine = AIEngine::with_config(1, 10, 500);
+        let mut board = Board::new();
+        for i in 0..3 {
+            board.place_stone(Pos::new(2, 2 + 4 * i), Stone::Black);
+            board.place_stone(Pos::new(16, 2 + 4 * i), Stone::White);
+        }
+        assert_eq!(engine.compute_time_limit(&board, Stone::Black), 500);
+
+        // White open three: a crisis for Black, paid from the bank
+        for col in 7..10 {
+            board.place_stone(Pos::new(9, col), Stone::White);
+        }
+        assert_eq!(engine.compute_time_limit(&board, Stone::Black), 500);
+        engine.settle_time_bank(100);
+        assert_eq!(engine.compute_time_limit(&board, Stone::Black), 900);
+        assert_eq!(engine.compute_time_limit(&board, Stone::White), 650);
+
+        engine.set_deterministic(true);
+        assert_eq!(engine.compute_time_limit(&board, Stone::Black), 500);
+        engine.set_deterministic(false);
+
+        // Overspending draws the bank down; it never holds more than the cap
+        engine.settle_time_bank(900);
+        assert_eq!(engine.time_bank_ms, 0);
+        for _ in 0..10 {
+            engine.settle_time_bank(0);
+        }
+        assert_eq!(engine.time_bank_ms, 500 * TIME_BANK_LIMITS);
+        engine.clear_cache();
+        assert_eq!(engine.time_bank_ms, 0);
+    }
+
+    #[test]
+    fn test_single_reply_is_played_at_once() {
+        // Full board but for K10, in runs of at most two in every direction
+        let mut board = Board::new();
+        let last = Pos::new(9, 9);
+        for pos in (0..crate::board::TOTAL_CELLS).map(Pos::from_index).filter(|&pos| pos != last) {
+            let black = (pos.col + 2 * pos.row) % 4 < 2;
+            board.place_stone(pos, if black { Stone::Black } else { Stone::White });
+        }
+        let mut engine = AIEngine::with_config(1, 10, 500);
+        assert_eq!(engine.find_single_reply(&board, Stone::Black, &[]), Some(last));
+        assert_eq!(engine.find_single_reply(&Board::new(), Stone::Black, &[]), None);
+
+        let result = engine.get_move_with_stats(&board, Stone::Black);
+        assert_eq!(result.best_move, Some(last));
+        assert_eq!(result.search_type, SearchType::Defense);
+        // The search time went to the bank
+        assert!(engine.time_bank_ms > 0);
+
+        // White one capture from the win, threatening K10-L10 from M10
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 8), Stone::White);
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.place_stone(Pos::new(9, 10), Stone::Black);
+        board.set_captures(RuleSet::NINUKI, Stone::White, 4).unwrap();
+        let threats = engine.find_winning_moves(&board, Stone::White);
+        assert_eq!(engine.find_single_reply(&board, Stone::Black, &threats), Some(Pos::new(9, 11)));
+    }
+
+    #[test]
+    fn test_restricted_search_skips_tactical_stages() {
+        // White must block at K10, but is asked about two other moves
+        let mut board = Board::new();
+        for col in 5..9 {
+            board.place_stone(Pos::new(9, col), Stone::Black);
+        }
+        board.place_stone(Pos::new(9, 4), Stone::White);
+        board.place_stone(Pos::new(3, 3), Stone::White);
+        let mut engine = AIEngine::with_config(4, 4, 500);
+        let options = [Pos::new(3, 4), Pos::new(15, 15)];
+        let limits = SearchLimits::depth(2).restrict_to(options);
+        let result = engine.get_move_with_limits(&board, Stone::White, &limits);
+        assert_eq!(result.search_type, SearchType::AlphaBeta);
+        assert!(result.best_move.is_some_and(|mov| options.contains(&mov)));
+        assert!(result.score <= -crate::search::MATE_THRESHOLD, "both lose to the five, got {}", result.score);
+    }
+
+    #[test]
+    fn test_search_async_cancel_and_await() {
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.place_stone(Pos::new(10, 10), Stone::White);
+        let handle = AIEngine::with_config(8, 30, 10_000).search_async(&board, Stone::Black);
+        let start = Instant::now();
+        while handle.info().is_none() {
+            assert!(start.elapsed() < Duration::from_secs(5), "no iteration reported");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert!(handle.depth() >= 1 && handle.nodes() > 0);
+        handle.cancel();
+        let (result, engine) = handle.wait().unwrap();
+        assert!(result.best_move.is_some());
+        assert!(start.elapsed() < Duration::from_secs(5), "cancel did not stop the search");
+
+        // Awaited, on a thread woken by the search
+        struct Unpark(std::thread::Thread);
+        impl std::task::Wake for Unpark {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+        let waker = Waker::from(Arc::new(Unpark(std::thread::current())));
+        let mut cx = TaskContext::from_waker(&waker);
+        for col in 5..9 {
+            board.place_stone(Pos::new(3, col), Stone::Black);
+        }
+        let mut handle = engine.search_async(&board, Stone::Black);
+        let outcome = loop {
+            if let Poll::Ready(outcome) = Pin::new(&mut handle).poll(&mut cx) {
+                break outcome;
+            }
+            std::thread::park_timeout(Duration::from_millis(100));
+        };
+        let (result, _) = outcome.unwrap();
+        assert_eq!(result.search_type, SearchType::ImmediateWin);
+        assert!(handle.try_finish().is_none());
+    }
+
     #[test]
     fn test_engine_default() {
         let engine = AIEngine::default();
@@ -1195,10 +2585,13 @@ mod tests {
         let win = MoveResult::immediate_win(pos, 10);
         assert_eq!(win.search_type, SearchType::ImmediateWin);
         assert_eq!(win.score, 1_000_000);
+        assert_eq!(win.mate_in, Some(1));
 
-        let vcf = MoveResult::vcf_win(pos, 20, 100);
+        let vcf = MoveResult::vcf_win(&[pos, Pos::new(9, 10), Pos::new(9, 11)], 20, 100);
         assert_eq!(vcf.search_type, SearchType::VCF);
         assert_eq!(vcf.score, 900_000);
+        assert_eq!(vcf.best_move, Some(pos));
+        assert_eq!(vcf.mate_in, Some(3));
 
         let defense = MoveResult::defense(pos, -100_000, 40, 50);
         assert_eq!(defense.search_type, SearchType::Defense);
@@ -1262,6 +2655,34 @@ mod tests {
         assert!(black_move2.is_some());
     }
 
+    #[test]
+    fn test_forced_wins_carry_verified_proofs() {
+        // Closed threes on row 10 (E10 White) and column J (J15 White):
+        // J10 makes a four and a broken four at once
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 4), Stone::White);
+        board.place_stone(Pos::new(4, 8), Stone::White);
+        for i in 5..8 {
+            board.place_stone(Pos::new(9, i), Stone::Black);
+            board.place_stone(Pos::new(i, 8), Stone::Black);
+        }
+        let mut engine = AIEngine::with_config(8, 6, 500);
+        let vcf = engine.get_move_with_stats(&board, Stone::Black);
+        assert_eq!(vcf.search_type, SearchType::VCF);
+        let proof = vcf.proof.expect("VCF result carries its line");
+        assert_eq!(proof.moves.first().copied(), vcf.best_move);
+        assert_eq!(proof.attacker_moves().count() as i32, vcf.mate_in.unwrap());
+        assert_eq!(proof.verify(RuleSet::NINUKI, &board), Ok(()));
+
+        board.place_stone(Pos::new(9, 8), Stone::Black);
+        board.place_stone(Pos::new(9, 9), Stone::White);
+        let win = engine.get_move_with_stats(&board, Stone::Black);
+        assert_eq!(win.search_type, SearchType::ImmediateWin);
+        let proof = win.proof.expect("immediate win carries its line");
+        assert_eq!(proof.moves, vec![Pos::new(8, 8)]);
+        assert_eq!(proof.verify(RuleSet::NINUKI, &board), Ok(()));
+    }
+
     #[test]
     fn test_search_type_equality() {
         assert_eq!(SearchType::ImmediateWin, SearchType::ImmediateWin);
@@ -1456,14 +2877,14 @@ mod tests {
         board.place_stone(Pos::new(7, 6), Stone::White);   // G8
 
         // Verify Black has a five
-        let five = find_five_positions(&board, Stone::Black);
+        let five = find_five_positions(RuleSet::NINUKI, &board, Stone::Black);
         assert!(five.is_some(), "Black should have a five-in-a-row");
         let five_positions = five.unwrap();
         assert!(five_positions.len() >= 5);
 
         // Verify the five is breakable
         assert!(
-            can_break_five_by_capture(&board, &five_positions, Stone::Black),
+            can_break_five_by_capture(RuleSet::NINUKI, &board, &five_positions, Stone::Black),
             "Black's five should be breakable by White"
         );
 
@@ -1478,7 +2899,7 @@ mod tests {
         // Place the stone and check if a capture removes part of the five
         let mut test = board.clone();
         test.place_stone(ai_move, Stone::White);
-        let caps = crate::rules::get_captured_positions(&test, ai_move, Stone::White);
+        let caps = crate::rules::get_captured_positions(RuleSet::NINUKI, &test, ai_move, Stone::White);
         let breaks_five = caps.iter().any(|cap| five_positions.contains(cap));
         assert!(
             breaks_five,
@@ -1571,21 +2992,21 @@ mod tests {
         let o10 = Pos::new(9, 13);
 
         // 1. is_valid_move should allow both
-        assert!(is_valid_move(&board, j10, Stone::Black), "J10 should be valid for Black");
-        assert!(is_valid_move(&board, o10, Stone::Black), "O10 should be valid for Black");
+        assert!(is_valid_move(RuleSet::NINUKI, &board, j10, Stone::Black), "J10 should be valid for Black");
+        assert!(is_valid_move(RuleSet::NINUKI, &board, o10, Stone::Black), "O10 should be valid for Black");
 
         // 2. has_five_at_pos should detect five after placing
         let mut test_board = board.clone();
         test_board.place_stone(j10, Stone::Black);
         assert!(
-            has_five_at_pos(&test_board, j10, Stone::Black),
+            has_five_at_pos(RuleSet::NINUKI, &test_board, j10, Stone::Black),
             "J10 should create five-in-a-row"
         );
         test_board.remove_stone(j10);
 
         test_board.place_stone(o10, Stone::Black);
         assert!(
-            has_five_at_pos(&test_board, o10, Stone::Black),
+            has_five_at_pos(RuleSet::NINUKI, &test_board, o10, Stone::Black),
             "O10 should create five-in-a-row"
         );
         test_board.remove_stone(o10);
@@ -1695,17 +3116,18 @@ mod tests {
         let m9 = Pos::new(8, 11);
         let mut test = board.clone();
         test.place_stone(m9, Stone::White);
-        assert!(has_five_at_pos(&test, m9, Stone::White), "M9 should create five");
+        assert!(has_five_at_pos(RuleSet::NINUKI, &test, m9, Stone::White), "M9 should create five");
 
         // STATIC check: the five IS breakable (O7 captures N8+M9)
-        let five = find_five_positions(&test, Stone::White).unwrap();
+        let five = find_five_positions(RuleSet::NINUKI, &test, Stone::White).unwrap();
         assert!(
-            can_break_five_by_capture(&test, &five, Stone::White),
+            can_break_five_by_capture(RuleSet::NINUKI, &test, &five, Stone::White),
             "M9 five should be STATICALLY breakable (O7 captures N8+M9)"
         );
         // But the break is illusory: after O7 captures, White replays M9 → unbreakable
-        assert!(
-            AIEngine::is_illusory_break(&test, &five, Stone::White),
+        assert_eq!(
+            resolve_five(RuleSet::NINUKI, &test, &five, Stone::White),
+            CycleOutcome::Win,
             "M9 five break via O7 should be illusory (recreation = unbreakable)"
         );
 
@@ -1813,4 +3235,220 @@ mod tests {
             result.score
         );
     }
+
+    #[test]
+    fn test_evaluate_position_sign_convention() {
+        let engine = AIEngine::with_config(1, 4, 500);
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.place_stone(Pos::new(9, 10), Stone::Black);
+        board.place_stone(Pos::new(9, 11), Stone::Black);
+        board.place_stone(Pos::new(5, 5), Stone::White);
+
+        let black = engine.evaluate_position(&board, Stone::Black);
+        let white = engine.evaluate_position(&board, Stone::White);
+        assert!(black > 0, "Open three should favor Black, got {}", black);
+        assert_eq!(black, -white, "Scores must be negamax-symmetric");
+    }
+
+    #[test]
+    fn test_evaluate_position_decided_games() {
+        let engine = AIEngine::with_config(1, 4, 500);
+        let mut board = Board::new();
+        for c in 5..10 {
+            board.place_stone(Pos::new(9, c), Stone::White);
+        }
+        assert_eq!(engine.evaluate_position(&board, Stone::White), PatternScore::FIVE);
+        assert_eq!(engine.evaluate_position(&board, Stone::Black), -PatternScore::FIVE);
+
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.add_captures(Stone::Black, 5);
+        assert_eq!(engine.evaluate_position(&board, Stone::Black), PatternScore::FIVE);
+        assert_eq!(engine.evaluate_position(&board, Stone::White), -PatternScore::FIVE);
+    }
+
+    #[test]
+    fn test_win_probability_curve() {
+        assert_eq!(AIEngine::win_probability(0), 0.5);
+        assert_eq!(AIEngine::win_probability(PatternScore::FIVE), 1.0);
+        assert_eq!(AIEngine::win_probability(-PatternScore::FIVE), 0.0);
+
+        let three = AIEngine::win_probability(PatternScore::OPEN_THREE);
+        let four = AIEngine::win_probability(PatternScore::OPEN_FOUR);
+        assert!(three > 0.5 && three < four, "three={} four={}", three, four);
+        assert!(four > 0.99, "Open four should be nearly decisive, got {}", four);
+
+        for score in [1_000, 10_000, 50_000, 250_000] {
+            let sum = AIEngine::win_probability(score) + AIEngine::win_probability(-score);
+            assert!((sum - 1.0).abs() < 1e-12, "Curve must be symmetric at {}", score);
+        }
+    }
+
+    #[test]
+    fn test_should_accept_draw_only_when_not_better() {
+        let engine = AIEngine::with_config(1, 4, 500);
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.place_stone(Pos::new(9, 10), Stone::White);
+        assert!(engine.should_accept_draw(&board, Stone::Black));
+        assert!(engine.should_accept_draw(&board, Stone::White));
+
+        for c in 5..8 {
+            board.place_stone(Pos::new(12, c), Stone::Black);
+        }
+        assert!(!engine.should_accept_draw(&board, Stone::Black), "Open three is worth playing on");
+        assert!(engine.should_accept_draw(&board, Stone::White));
+    }
+
+    #[test]
+    fn test_swindle_mode_in_lost_position() {
+        // White has an open four (two winning points); Black is lost but
+        // has an open three of its own to build a trap with.
+        let mut board = Board::new();
+        for c in 5..9 {
+            board.place_stone(Pos::new(12, c), Stone::White);
+        }
+        for c in 5..8 {
+            board.place_stone(Pos::new(6, c), Stone::Black);
+        }
+        board.place_stone(Pos::new(3, 14), Stone::Black);
+
+        let mut engine = AIEngine::with_config(8, 6, 300);
+        assert!(!engine.swindle_mode());
+        let plain = engine.get_move_with_stats(&board, Stone::Black);
+        assert_ne!(plain.search_type, SearchType::Swindle);
+
+        engine.set_swindle_mode(true);
+        engine.clear_cache();
+        let result = engine.get_move_with_stats(&board, Stone::Black);
+        assert_eq!(result.search_type, SearchType::Swindle);
+        assert_eq!(result.best_move.map(|p| p.row), Some(6), "Trap should extend Black's three");
+
+        // Every move loses to the open four at once, so speculative defense
+        // picks a trap too
+        engine.set_swindle_mode(false);
+        engine.set_speculative_defense(true);
+        engine.clear_cache();
+        let result = engine.get_move_with_stats(&board, Stone::Black);
+        assert_eq!(result.search_type, SearchType::Swindle);
+        assert_eq!(result.best_move.map(|p| p.row), Some(6));
+    }
+
+    #[test]
+    fn test_opening_variety() {
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        let reply = |variety: Option<OpeningVariety>, seed| {
+            let mut engine = AIEngine::with_config(8, 4, 200);
+            engine.set_seed(seed);
+            engine.set_opening_variety(variety);
+            engine.get_move_with_stats(&board, Stone::White).best_move.unwrap()
+        };
+        let book = AIEngine::new().get_opening_move(&board, Stone::White);
+        assert_eq!(Some(reply(None, 3)), book);
+
+        // Every searched line is close enough: the seed alone picks
+        let wide = Some(OpeningVariety { margin: i32::MAX, ..OpeningVariety::default() });
+        assert_eq!(reply(wide, 3), reply(wide, 3));
+        let moves: std::collections::HashSet<Pos> = (0..8).map(|seed| reply(wide, seed)).collect();
+        assert!(moves.len() > 1, "{:?}", moves);
+
+        // Past the randomized moves the book is back
+        let short = Some(OpeningVariety { moves: 1, margin: i32::MAX });
+        assert_eq!(Some(reply(short, 5)), book);
+    }
+
+    #[test]
+    fn test_seed_reaches_zobrist_keys() {
+        let mut engine = AIEngine::with_config(8, 4, 200);
+        engine.set_deterministic(true);
+        assert_eq!(engine.seed(), ZobristTable::DEFAULT_SEED);
+        assert_eq!(engine.searcher.zobrist_seed(), ZobristTable::DEFAULT_SEED);
+
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.place_stone(Pos::new(9, 10), Stone::White);
+        engine.set_seed(42);
+        assert_eq!(engine.searcher.zobrist_seed(), 42);
+        let first = engine.get_move_with_stats(&board, Stone::Black);
+
+        // Same seed, fresh engine: the same search
+        let mut again = AIEngine::with_config(8, 4, 200);
+        again.set_deterministic(true);
+        again.set_seed(42);
+        let second = again.get_move_with_stats(&board, Stone::Black);
+        assert_eq!(first.best_move, second.best_move);
+        assert_eq!(first.score, second.score);
+        assert_eq!(first.nodes, second.nodes);
+    }
+
+    #[test]
+    fn test_hint_reasons() {
+        let mut engine = AIEngine::with_config(8, 6, 200);
+
+        // Black completes four in a row
+        let mut board = Board::new();
+        for col in 5..9 {
+            board.place_stone(Pos::new(9, col), Stone::Black);
+        }
+        let hint = engine.get_hint(&board, Stone::Black).unwrap();
+        assert_eq!(hint.reason, HintReason::WinsByFive);
+
+        // Pattern reasons for a fixed move
+        let plain = MoveResult::alpha_beta(Pos::new(9, 9), 0, 0, 0);
+        let mut board = Board::new();
+        for col in 6..9 {
+            board.place_stone(Pos::new(9, col), Stone::Black);
+        }
+        assert_eq!(explain_move(RuleSet::NINUKI, &board, Stone::Black, Pos::new(9, 9), &plain), HintReason::OpenFour);
+        assert_eq!(
+            explain_move(RuleSet::NINUKI, &board, Stone::White, Pos::new(9, 9), &plain),
+            HintReason::BlocksOpenFour
+        );
+        assert_eq!(explain_move(RuleSet::NINUKI, &board, Stone::Black, Pos::new(9, 4), &plain), HintReason::Four);
+        assert_eq!(explain_move(RuleSet::NINUKI, &board, Stone::Black, Pos::new(0, 0), &plain), HintReason::Positional);
+
+        // White K10 brackets Black H10-J10 against White G10
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 6), Stone::White);
+        board.place_stone(Pos::new(9, 7), Stone::Black);
+        board.place_stone(Pos::new(9, 8), Stone::Black);
+        let reason = explain_move(RuleSet::NINUKI, &board, Stone::White, Pos::new(9, 9), &plain);
+        assert_eq!(reason, HintReason::Captures { pairs: 1 });
+        assert_eq!(reason.to_string(), "captures a pair");
+
+        let sequence = [Pos::new(0, 0), Pos::new(0, 1), Pos::new(0, 2)];
+        let forced = MoveResult::vcf_win(&sequence, 0, 0);
+        assert_eq!(
+            explain_move(RuleSet::NINUKI, &Board::new(), Stone::Black, sequence[0], &forced),
+            HintReason::ForcedWin { moves: 3 }
+        );
+    }
+    #[test]
+    fn test_classify_position() {
+        let class = classify_position(RuleSet::NINUKI, &Board::new());
+        assert_eq!(class.phase, GamePhase::Opening);
+        assert_eq!(class.capture_race, CaptureRace::Even);
+        assert_eq!(class.black, ThreatSummary { captures_to_win: 5, ..ThreatSummary::default() });
+        assert!(!class.is_tactical());
+
+        // Black open four on row 10; White threatens the pair G6-H6
+        let mut board = Board::new();
+        for col in 5..9 {
+            board.place_stone(Pos::new(9, col), Stone::Black);
+        }
+        board.place_stone(Pos::new(5, 5), Stone::White);
+        board.place_stone(Pos::new(5, 6), Stone::Black);
+        board.place_stone(Pos::new(5, 7), Stone::Black);
+        board.set_captures(RuleSet::NINUKI, Stone::White, 4).unwrap();
+        let class = classify_position(RuleSet::NINUKI, &board);
+        assert_eq!(class.phase, GamePhase::Midgame);
+        assert_eq!(class.black.open_fours, 1);
+        assert!(class.black.threatens_win());
+        assert_eq!(class.threats(Stone::White).capture_threats, 1);
+        assert!(class.white.threatens_win());
+        assert_eq!(class.capture_race, CaptureRace::Ahead { leader: Stone::White, by: 4 });
+        assert_eq!(class.to_string(), "Midgame, White ahead by 4 captures, Black threatens to win, White threatens to win");
+    }
 }
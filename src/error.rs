@@ -0,0 +1,75 @@
+//! Crate-level error type
+//!
+//! [`Pos::new`](crate::Pos::new) only debug-asserts its bounds and
+//! [`Board::place_stone`](crate::Board::place_stone) trusts its caller. Input
+//! from outside the engine (servers, front ends, bindings) goes through the
+//! fallible variants instead, [`Pos::try_new`](crate::Pos::try_new),
+//! [`Board::try_place_stone`](crate::Board::try_place_stone) and
+//! [`Board::set_captures`](crate::Board::set_captures), which report a
+//! [`GomokuError`].
+//!
+//! ```
+//! use gomoku::{Board, GomokuError, Pos, Stone};
+//!
+//! assert_eq!(Pos::try_new(19, 0), Err(GomokuError::OutOfBounds { row: 19, col: 0 }));
+//!
+//! let mut board = Board::new();
+//! let center = Pos::try_new(9, 9).unwrap();
+//! board.try_place_stone(center, Stone::Black).unwrap();
+//! assert_eq!(board.try_place_stone(center, Stone::White), Err(GomokuError::Occupied(center)));
+//! ```
+
+use core::fmt;
+
+use crate::board::{Pos, BOARD_SIZE};
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::notation::{notation, Notation};
+
+/// Bad input to the board API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GomokuError {
+    /// Row or column is not below `BOARD_SIZE`
+    OutOfBounds { row: u8, col: u8 },
+    /// The point already holds a stone
+    Occupied(Pos),
+    /// `Stone::Empty` was given where a color is needed
+    NoColor,
+    /// A capture count above the rule set's capture win
+    TooManyCaptures { pairs: u8, max: u8 },
+}
+
+impl fmt::Display for GomokuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::OutOfBounds { row, col } => {
+                write!(f, "({}, {}) is off the board (0-{})", row, col, BOARD_SIZE - 1)
+            }
+            Self::Occupied(pos) => write!(f, "{} is occupied", notation().format(pos)),
+            Self::NoColor => write!(f, "expected a Black or White stone"),
+            Self::TooManyCaptures { pairs, max } => write!(f, "{} captured pairs is more than the capture win ({})", pairs, max),
+        }
+    }
+}
+
+impl GomokuError {
+    /// The message, naming a point in `notation`. `Display` uses the
+    /// process-wide [`notation()`].
+    ///
+    /// ```
+    /// use gomoku::notation::{Notation, Origin};
+    /// use gomoku::{GomokuError, Pos};
+    ///
+    /// let bottom = Notation { origin: Origin::BottomLeft, skip_i: true };
+    /// assert_eq!(GomokuError::Occupied(Pos::new(18, 0)).to_string_with_notation(bottom), "A1 is occupied");
+    /// ```
+    #[must_use]
+    pub fn to_string_with_notation(&self, notation: Notation) -> String {
+        match *self {
+            Self::Occupied(pos) => format!("{} is occupied", notation.format(pos)),
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl core::error::Error for GomokuError {}
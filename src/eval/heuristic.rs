@@ -8,12 +8,14 @@
 //! - Positional bonuses (center control)
 
 use crate::board::{Bitboard, Board, Pos, Stone, BOARD_SIZE};
+use crate::rules::RuleSet;
 
+use super::pattern_table::{color_index, LineMasks};
 use super::patterns::{capture_score, PatternScore};
 
 /// Direction vectors for line checking (4 directions)
 /// Each direction only needs to be checked once (we scan both ways from each stone)
-const DIRECTIONS: [(i32, i32); 4] = [
+pub(crate) const DIRECTIONS: [(i32, i32); 4] = [
     (0, 1),  // Horizontal
     (1, 0),  // Vertical
     (1, 1),  // Diagonal SE
@@ -50,7 +52,7 @@ fn detect_phase(board: &Board) -> GamePhase {
 
 /// Phase-dependent weight multipliers: (position, vulnerability, capture_score).
 /// 100 = 1.0x baseline. Uses integer math to avoid float overhead.
-const PHASE_WEIGHTS: [(i32, i32, i32); 3] = [
+pub(crate) const PHASE_WEIGHTS: [(i32, i32, i32); 3] = [
     (150, 50, 80),   // Opening: position emphasis, low vuln/capture weight
     (100, 100, 100),  // Midgame: balanced (baseline)
     (60, 150, 130),   // Endgame: position de-emphasized, vuln/capture critical
@@ -64,38 +66,74 @@ const PHASE_WEIGHTS: [(i32, i32, i32); 3] = [
 /// - `PatternScore::FIVE` indicates immediate win
 /// - `-PatternScore::FIVE` indicates immediate loss
 ///
+/// Uses the board's incremental accumulator when enabled under the same
+/// rules (see [`Board::enable_incremental_eval`]), otherwise scores every
+/// line through the pattern table ([`super::pattern_table`]).
+///
 /// # Arguments
+/// * `rules` - The rule set the position is played under
 /// * `board` - The current board state
 /// * `color` - The color to evaluate for
 ///
 /// # Returns
 /// An i32 score representing the position evaluation
 #[must_use]
-pub fn evaluate(board: &Board, color: Stone) -> i32 {
+pub fn evaluate(rules: RuleSet, board: &Board, color: Stone) -> i32 {
+    evaluate_impl(rules, board, color, true)
+}
+
+/// Evaluate by scanning every stone cell by cell, ignoring any incremental
+/// accumulator and the pattern table.
+///
+/// Always equal to [`evaluate`]; useful to verify the fast paths.
+#[must_use]
+pub fn evaluate_full(rules: RuleSet, board: &Board, color: Stone) -> i32 {
+    evaluate_impl(rules, board, color, false)
+}
+
+fn evaluate_impl(rules: RuleSet, board: &Board, color: Stone, fast: bool) -> i32 {
     let opponent = color.opponent();
 
     // Quick capture-win check (O(1) - just reads stored count).
     // Alpha-beta already checks five-in-a-row via has_five_at_pos() at each node,
     // so by the time evaluate() is called at leaf nodes, no five-in-a-row exists.
     // We only need to check capture wins here.
-    if board.captures(color) >= 5 {
+    if board.captures_to_win(rules, color) == 0 {
         return PatternScore::FIVE;
     }
-    if board.captures(opponent) >= 5 {
+    if board.captures_to_win(rules, opponent) == 0 {
         return -PatternScore::FIVE;
     }
 
     let phase = detect_phase(board);
     let (pos_mul, vuln_mul, cap_mul) = PHASE_WEIGHTS[phase as usize];
 
-    let cap_score = capture_score(board.captures(color), board.captures(opponent));
+    let cap_score = capture_score(rules, board.captures(color), board.captures(opponent));
     let cap_score = cap_score * cap_mul / 100;
 
     // Single-pass evaluation per color: patterns + position + vulnerability combined.
-    // SYMMETRIC for negamax: evaluate(board, Black) == -evaluate(board, White).
+    // SYMMETRIC for negamax: evaluate(rules, board, Black) == -evaluate(rules, board, White).
     // pos_mul applied identically to both sides → factors out of (my - opp).
-    let (my_score, my_vuln) = evaluate_color(board, color, pos_mul);
-    let (opp_score, opp_vuln) = evaluate_color(board, opponent, pos_mul);
+    let ((my_score, my_vuln), (opp_score, opp_vuln)) = match board.eval_accumulator() {
+        _ if !fast => (
+            evaluate_color(rules, board, color, pos_mul),
+            evaluate_color(rules, board, opponent, pos_mul),
+        ),
+        Some(acc) if acc.rules() == rules => (
+            acc.color_score(color, phase as usize),
+            acc.color_score(opponent, phase as usize),
+        ),
+        _ => {
+            let totals = LineMasks::from_board(board).totals(rules);
+            let score = |stone: Stone| {
+                let position = board
+                    .stones(stone)
+                    .map_or(0, |bb| bb.iter_ones().map(|pos| position_bonus(pos, pos_mul)).sum());
+                color_score(&totals[color_index(stone)], position)
+            };
+            (score(color), score(opponent))
+        }
+    };
 
     let my_caps = board.captures(color);
     let opp_caps = board.captures(opponent);
@@ -125,6 +163,61 @@ fn vuln_weight(opp_captures: u8) -> i32 {
     }
 }
 
+/// Per-color pattern tallies: everything in a color's score except the
+/// position bonus. Each term depends only on one line, so the totals can
+/// be kept as a sum over lines (see [`super::incremental`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct LineStats {
+    /// Sum of line pattern scores
+    pub(crate) patterns: i32,
+    pub(crate) open_fours: i32,
+    pub(crate) closed_fours: i32,
+    pub(crate) open_threes: i32,
+    pub(crate) open_twos: i32,
+    /// Adjacent same-color pairs
+    pub(crate) links: i32,
+    /// Pairs capturable by the opponent
+    pub(crate) vuln: i32,
+}
+
+impl LineStats {
+    pub(crate) fn add(&mut self, other: &Self) {
+        self.patterns += other.patterns;
+        self.open_fours += other.open_fours;
+        self.closed_fours += other.closed_fours;
+        self.open_threes += other.open_threes;
+        self.open_twos += other.open_twos;
+        self.links += other.links;
+        self.vuln += other.vuln;
+    }
+
+    /// Count one line pattern score.
+    #[inline]
+    pub(crate) fn add_pattern(&mut self, pattern_score: i32) {
+        self.patterns += pattern_score;
+
+        if pattern_score >= PatternScore::OPEN_FOUR {
+            self.open_fours += 1;
+        } else if pattern_score >= PatternScore::CLOSED_FOUR {
+            self.closed_fours += 1;
+        } else if pattern_score >= PatternScore::OPEN_THREE {
+            self.open_threes += 1;
+        } else if (PatternScore::OPEN_TWO..PatternScore::CLOSED_THREE).contains(&pattern_score) {
+            self.open_twos += 1;
+        }
+    }
+
+    pub(crate) fn sub(&mut self, other: &Self) {
+        self.patterns -= other.patterns;
+        self.open_fours -= other.open_fours;
+        self.closed_fours -= other.closed_fours;
+        self.open_threes -= other.open_threes;
+        self.open_twos -= other.open_twos;
+        self.links -= other.links;
+        self.vuln -= other.vuln;
+    }
+}
+
 /// Single-pass evaluation for one color using direct bitboard access.
 ///
 /// Combines pattern scoring, position bonus, and capture vulnerability
@@ -132,109 +225,140 @@ fn vuln_weight(opp_captures: u8) -> i32 {
 /// lookups (1 op) instead of board.get() (2 ops) for ~2.5x speedup.
 ///
 /// Returns (total_score, vulnerable_pair_count).
-#[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-fn evaluate_color(board: &Board, color: Stone, pos_mul: i32) -> (i32, i32) {
+fn evaluate_color(rules: RuleSet, board: &Board, color: Stone, pos_mul: i32) -> (i32, i32) {
     let Some(my_bb) = board.stones(color) else {
         return (0, 0);
     };
     // color is always Black or White, so opponent always returns Some
     let opp_bb = board.stones(color.opponent()).unwrap();
 
-    let center = (BOARD_SIZE / 2) as i32;
-
-    let mut score = 0;
-    let mut open_fours = 0i32;
-    let mut closed_fours = 0i32;
-    let mut open_threes = 0i32;
-    let mut vuln = 0i32;
-    let mut open_twos = 0i32;
-
+    let mut stats = LineStats::default();
+    let mut position = 0;
     for pos in my_bb.iter_ones() {
-        // --- Pattern scoring (4 directions) with line-start filter ---
         for &(dr, dc) in &DIRECTIONS {
-            // Line-start filter: skip if prev pos has same-color stone.
-            // This ensures each line segment is counted exactly once.
-            // Moved from evaluate_line to caller → eliminates ~60% of function calls.
-            let prev_r = i32::from(pos.row) - dr;
-            let prev_c = i32::from(pos.col) - dc;
-            if Pos::is_valid(prev_r, prev_c)
-                && my_bb.get(Pos::new(prev_r as u8, prev_c as u8))
-            {
-                continue;
-            }
-            // prev is either off-board or not our stone. Check if open end.
-            let prev_open = Pos::is_valid(prev_r, prev_c)
-                && !opp_bb.get(Pos::new(prev_r as u8, prev_c as u8));
-
-            let pattern_score = evaluate_line(my_bb, opp_bb, pos, dr, dc, prev_open);
-            score += pattern_score;
-
-            if pattern_score >= PatternScore::OPEN_FOUR {
-                open_fours += 1;
-            } else if pattern_score >= PatternScore::CLOSED_FOUR {
-                closed_fours += 1;
-            } else if pattern_score >= PatternScore::OPEN_THREE {
-                open_threes += 1;
-            } else if pattern_score >= PatternScore::OPEN_TWO
-                && pattern_score < PatternScore::CLOSED_THREE
-            {
-                open_twos += 1;
-            }
+            tally_direction(my_bb, opp_bb, pos, dr, dc, rules, &mut stats);
         }
+        position += position_bonus(pos, pos_mul);
+    }
 
-        // --- Position bonus (center control, phase-adjusted) ---
-        let dist = (i32::from(pos.row) - center).abs() + (i32::from(pos.col) - center).abs();
-        score += (MAX_CENTER_DIST - dist) * POSITION_WEIGHT * pos_mul / 100;
+    color_score(&stats, position)
+}
 
-        // --- Connectivity bonus: unidirectional (positive only) ---
-        // Each adjacent pair counted once from the stone with lower dir offset.
-        // Score 160 = original bidirectional 80×2, so total per pair is identical.
-        for &(dr, dc) in &DIRECTIONS {
-            let nr = i32::from(pos.row) + dr;
-            let nc = i32::from(pos.col) + dc;
-            if Pos::is_valid(nr, nc) && my_bb.get(Pos::new(nr as u8, nc as u8)) {
-                score += 160;
+/// Position bonus (center control, phase-adjusted) for one stone.
+#[inline]
+pub(crate) fn position_bonus(pos: Pos, pos_mul: i32) -> i32 {
+    let center = (BOARD_SIZE / 2) as i32;
+    let dist = (i32::from(pos.row) - center).abs() + (i32::from(pos.col) - center).abs();
+    (MAX_CENTER_DIST - dist) * POSITION_WEIGHT * pos_mul / 100
+}
+
+/// Whether `pos` lies between two opponent stones on the line `(dr, dc)`
+/// with at most five cells between them. Under the Caro rule any five made
+/// there is blocked at both ends, so the segment is worthless.
+#[allow(clippy::cast_sign_loss)]
+pub(crate) fn caro_squeezed(opp_bb: &Bitboard, pos: Pos, dr: i32, dc: i32) -> bool {
+    let mut room = 1;
+    for sign in [-1, 1] {
+        let mut k = 1;
+        loop {
+            let r = i32::from(pos.row) + sign * dr * k;
+            let c = i32::from(pos.col) + sign * dc * k;
+            if room > 5 || !Pos::is_valid(r, c) {
+                return false; // The board edge doesn't block
+            }
+            if opp_bb.get(Pos::new(r as u8, c as u8)) {
+                break;
             }
+            room += 1;
+            k += 1;
         }
+    }
+    true
+}
 
-        // --- Vulnerability: ally-ally pair capturable by opponent ---
-        for &(dr, dc) in &DIRECTIONS {
-            let r1 = i32::from(pos.row) + dr;
-            let c1 = i32::from(pos.col) + dc;
-            if !Pos::is_valid(r1, c1) { continue; }
-            let p1 = Pos::new(r1 as u8, c1 as u8);
-            if !my_bb.get(p1) { continue; }
-
-            let rb = i32::from(pos.row) - dr;
-            let cb = i32::from(pos.col) - dc;
-            let ra = r1 + dr;
-            let ca = c1 + dc;
-
-            // Before position (rb, cb)
-            let (b_empty, b_opp) = if Pos::is_valid(rb, cb) {
-                let pb = Pos::new(rb as u8, cb as u8);
-                let is_opp = opp_bb.get(pb);
-                (!is_opp && !my_bb.get(pb), is_opp)
-            } else {
-                (false, false)
-            };
-
-            // After position (ra, ca)
-            let (a_empty, a_opp) = if Pos::is_valid(ra, ca) {
-                let pa = Pos::new(ra as u8, ca as u8);
-                let is_opp = opp_bb.get(pa);
-                (!is_opp && !my_bb.get(pa), is_opp)
-            } else {
-                (false, false)
-            };
-
-            // empty-ally-ally-opp: opponent plays at empty to capture
-            if b_empty && a_opp { vuln += 1; }
-            // opp-ally-ally-empty: opponent plays at empty to capture
-            if b_opp && a_empty { vuln += 1; }
+/// Tally the pattern, connectivity and vulnerability terms for the stone
+/// at `pos` in direction `(dr, dc)`. Only reads cells on that line.
+/// Under the Caro rule, segments squeezed between opponent stones score
+/// nothing.
+#[inline]
+#[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn tally_direction(
+    my_bb: &Bitboard,
+    opp_bb: &Bitboard,
+    pos: Pos,
+    dr: i32,
+    dc: i32,
+    rules: RuleSet,
+    stats: &mut LineStats,
+) {
+    // --- Pattern scoring with line-start filter ---
+    // Skip if prev pos has same-color stone.
+    // This ensures each line segment is counted exactly once.
+    // Moved from evaluate_line to caller → eliminates ~60% of function calls.
+    let prev_r = i32::from(pos.row) - dr;
+    let prev_c = i32::from(pos.col) - dc;
+    let prev_valid = Pos::is_valid(prev_r, prev_c);
+    if !(prev_valid && my_bb.get(Pos::new(prev_r as u8, prev_c as u8))) {
+        // prev is either off-board or not our stone. Check if open end.
+        let prev_open = prev_valid && !opp_bb.get(Pos::new(prev_r as u8, prev_c as u8));
+
+        if !(rules.caro() && caro_squeezed(opp_bb, pos, dr, dc)) {
+            stats.add_pattern(evaluate_line(my_bb, opp_bb, pos, dr, dc, prev_open, rules.overline_wins()));
         }
     }
 
+    // --- Connectivity: unidirectional (positive only) ---
+    // Each adjacent pair counted once from the stone with lower dir offset.
+    let r1 = i32::from(pos.row) + dr;
+    let c1 = i32::from(pos.col) + dc;
+    if !Pos::is_valid(r1, c1) {
+        return;
+    }
+    let p1 = Pos::new(r1 as u8, c1 as u8);
+    if !my_bb.get(p1) {
+        return;
+    }
+    stats.links += 1;
+
+    // --- Vulnerability: ally-ally pair capturable by opponent ---
+    let rb = prev_r;
+    let cb = prev_c;
+    let ra = r1 + dr;
+    let ca = c1 + dc;
+
+    // Before position (rb, cb)
+    let (b_empty, b_opp) = if prev_valid {
+        let pb = Pos::new(rb as u8, cb as u8);
+        let is_opp = opp_bb.get(pb);
+        (!is_opp && !my_bb.get(pb), is_opp)
+    } else {
+        (false, false)
+    };
+
+    // After position (ra, ca)
+    let (a_empty, a_opp) = if Pos::is_valid(ra, ca) {
+        let pa = Pos::new(ra as u8, ca as u8);
+        let is_opp = opp_bb.get(pa);
+        (!is_opp && !my_bb.get(pa), is_opp)
+    } else {
+        (false, false)
+    };
+
+    // empty-ally-ally-opp: opponent plays at empty to capture
+    if b_empty && a_opp { stats.vuln += 1; }
+    // opp-ally-ally-empty: opponent plays at empty to capture
+    if b_opp && a_empty { stats.vuln += 1; }
+}
+
+/// Turn a color's tallies and position bonus into (score, vulnerable pairs).
+pub(crate) fn color_score(stats: &LineStats, position: i32) -> (i32, i32) {
+    // Score 160 per link = original bidirectional 80×2
+    let mut score = stats.patterns + position + stats.links * 160;
+    let open_fours = stats.open_fours;
+    let closed_fours = stats.closed_fours;
+    let open_threes = stats.open_threes;
+    let open_twos = stats.open_twos;
+
     // Multiple threat combination bonuses
     // These are CRITICAL: multi-direction threats are often unblockable.
     if open_fours >= 1 && (closed_fours >= 1 || open_threes >= 1) {
@@ -262,7 +386,7 @@ fn evaluate_color(board: &Board, color: Stone, pos_mul: i32) -> (i32, i32) {
         score += 3_000;
     }
 
-    (score, vuln)
+    (score, stats.vuln)
 }
 
 /// Evaluate a single line pattern from a position in a given direction.
@@ -281,6 +405,7 @@ fn evaluate_line(
     dr: i32,
     dc: i32,
     prev_open: bool,
+    overline_wins: bool,
 ) -> i32 {
     let mut count = 1; // Start with the stone at pos
     let mut open_ends = u8::from(prev_open);
@@ -322,13 +447,26 @@ fn evaluate_line(
         c += dc;
     }
 
+    line_score(count, has_gap, total_span, open_ends, overline_wins)
+}
+
+/// Score a scanned line segment: `count` stones over `total_span` cells
+/// (one more than `count` when `has_gap`), with `open_ends` empty ends.
+/// Unless `overline_wins`, six or more in a row is worth nothing.
+///
+/// Shared by [`evaluate_line`] and the pattern table
+/// ([`super::pattern_table`]) so both classify segments identically.
+pub(crate) fn line_score(count: i32, has_gap: bool, total_span: i32, open_ends: u8, overline_wins: bool) -> i32 {
     // Score based on pattern type
     // Gap patterns: count stones (not gap), but span determines if filling gap completes 5
     // Important: gap patterns are NEVER actual five-in-a-row (that requires consecutive stones).
     // Filling the gap is always one move away, so the best a gap pattern can be is OPEN_FOUR.
     if has_gap {
         match count {
-            5.. => PatternScore::OPEN_FOUR, // 5+ stones with gap: filling gap wins (unstoppable)
+            5.. if overline_wins => PatternScore::OPEN_FOUR, // 5+ stones with gap: filling gap wins (unstoppable)
+            // Filling the gap makes an overline; at best an end completes a five
+            5.. if open_ends > 0 => PatternScore::CLOSED_FOUR,
+            5.. => 0,
             4 if total_span == 5 => PatternScore::OPEN_FOUR, // OO_OO or O_OOO in exactly 5 span
             4 => PatternScore::CLOSED_FOUR, // 4 with gap but wider span
             3 if open_ends == 2 => PatternScore::OPEN_THREE, // _O_OO_ or _OO_O_: filling gap → open four
@@ -337,6 +475,7 @@ fn evaluate_line(
         }
     } else {
         match (count, open_ends) {
+            (6.., _) if !overline_wins => 0, // Dead: can't become exactly five
             (5.., _) => PatternScore::FIVE,
             (4, 2) => PatternScore::OPEN_FOUR,
             (4, 1) => PatternScore::CLOSED_FOUR,
@@ -356,7 +495,7 @@ mod tests {
     #[test]
     fn test_evaluate_empty_board() {
         let board = Board::new();
-        let score = evaluate(&board, Stone::Black);
+        let score = evaluate(RuleSet::NINUKI, &board, Stone::Black);
         assert_eq!(score, 0, "Empty board should have score 0");
     }
 
@@ -365,7 +504,7 @@ mod tests {
         let mut board = Board::new();
         board.place_stone(Pos::new(9, 9), Stone::Black);
 
-        let score = evaluate(&board, Stone::Black);
+        let score = evaluate(RuleSet::NINUKI, &board, Stone::Black);
         assert!(score > 0, "Center position should be valuable, got {}", score);
     }
 
@@ -377,8 +516,8 @@ mod tests {
         let mut board_corner = Board::new();
         board_corner.place_stone(Pos::new(0, 0), Stone::Black);
 
-        let center_score = evaluate(&board_center, Stone::Black);
-        let corner_score = evaluate(&board_corner, Stone::Black);
+        let center_score = evaluate(RuleSet::NINUKI, &board_center, Stone::Black);
+        let corner_score = evaluate(RuleSet::NINUKI, &board_corner, Stone::Black);
 
         assert!(
             center_score > corner_score,
@@ -397,7 +536,7 @@ mod tests {
 
         // Note: evaluate() no longer checks five-in-a-row (alpha-beta does that).
         // But the pattern scoring should still produce a very high positive score.
-        let score = evaluate(&board, Stone::Black);
+        let score = evaluate(RuleSet::NINUKI, &board, Stone::Black);
         assert!(score >= PatternScore::FIVE, "Five in a row should produce very high score, got {}", score);
     }
 
@@ -410,7 +549,7 @@ mod tests {
 
         // Note: evaluate() no longer checks five-in-a-row (alpha-beta does that).
         // But the pattern scoring should still produce a very negative score.
-        let score = evaluate(&board, Stone::Black);
+        let score = evaluate(RuleSet::NINUKI, &board, Stone::Black);
         assert!(score <= -PatternScore::FIVE, "Opponent five should produce very low score, got {}", score);
     }
 
@@ -419,7 +558,7 @@ mod tests {
         let mut board = Board::new();
         board.add_captures(Stone::Black, 5);
 
-        let score = evaluate(&board, Stone::Black);
+        let score = evaluate(RuleSet::NINUKI, &board, Stone::Black);
         assert_eq!(score, PatternScore::FIVE, "Capture win should be winning score");
     }
 
@@ -428,7 +567,7 @@ mod tests {
         let mut board = Board::new();
         board.add_captures(Stone::White, 5);
 
-        let score = evaluate(&board, Stone::Black);
+        let score = evaluate(RuleSet::NINUKI, &board, Stone::Black);
         assert_eq!(score, -PatternScore::FIVE, "Opponent capture win should be losing");
     }
 
@@ -440,7 +579,7 @@ mod tests {
             board.place_stone(Pos::new(9, i), Stone::Black);
         }
 
-        let score = evaluate(&board, Stone::Black);
+        let score = evaluate(RuleSet::NINUKI, &board, Stone::Black);
         assert!(score > 0, "Open four should have positive score, got {}", score);
         assert!(
             score < PatternScore::FIVE,
@@ -457,7 +596,7 @@ mod tests {
             board.place_stone(Pos::new(9, i), Stone::Black);
         }
 
-        let score = evaluate(&board, Stone::Black);
+        let score = evaluate(RuleSet::NINUKI, &board, Stone::Black);
         assert!(score > 0, "Closed four should have positive score");
     }
 
@@ -469,7 +608,7 @@ mod tests {
             board.place_stone(Pos::new(9, i), Stone::Black);
         }
 
-        let score = evaluate(&board, Stone::Black);
+        let score = evaluate(RuleSet::NINUKI, &board, Stone::Black);
         assert!(score > 0, "Open three should have positive score");
     }
 
@@ -486,8 +625,8 @@ mod tests {
         board.place_stone(Pos::new(5, 5), Stone::White);
         board.place_stone(Pos::new(5, 6), Stone::White); // Open two for White
 
-        let black_score = evaluate(&board, Stone::Black);
-        let white_score = evaluate(&board, Stone::White);
+        let black_score = evaluate(RuleSet::NINUKI, &board, Stone::Black);
+        let white_score = evaluate(RuleSet::NINUKI, &board, Stone::White);
 
         assert_eq!(
             black_score, -white_score,
@@ -512,8 +651,8 @@ mod tests {
             board2.place_stone(Pos::new(9, i), Stone::White);
         }
 
-        let our_advantage = evaluate(&board1, Stone::Black);
-        let their_advantage = evaluate(&board2, Stone::Black);
+        let our_advantage = evaluate(RuleSet::NINUKI, &board1, Stone::Black);
+        let their_advantage = evaluate(RuleSet::NINUKI, &board2, Stone::Black);
 
         assert!(
             our_advantage > 0,
@@ -538,7 +677,7 @@ mod tests {
         board.place_stone(Pos::new(10, 10), Stone::Black);
         board.place_stone(Pos::new(10, 11), Stone::Black);
 
-        let score = evaluate(&board, Stone::Black);
+        let score = evaluate(RuleSet::NINUKI, &board, Stone::Black);
         assert!(
             score > 0,
             "Multiple patterns should give positive score, got {}",
@@ -554,7 +693,7 @@ mod tests {
             board.place_stone(Pos::new(5 + i, 5 + i), Stone::Black);
         }
 
-        let score = evaluate(&board, Stone::Black);
+        let score = evaluate(RuleSet::NINUKI, &board, Stone::Black);
         assert!(
             score > 0,
             "Diagonal pattern should be detected and scored positively"
@@ -568,8 +707,8 @@ mod tests {
         board.place_stone(Pos::new(9, 9), Stone::Black);
         board.place_stone(Pos::new(9, 10), Stone::White);
 
-        let black_score = evaluate(&board, Stone::Black);
-        let white_score = evaluate(&board, Stone::White);
+        let black_score = evaluate(RuleSet::NINUKI, &board, Stone::Black);
+        let white_score = evaluate(RuleSet::NINUKI, &board, Stone::White);
 
         // Scores should be roughly opposite (not exactly due to position bonuses)
         // But the sign should be opposite if one has advantage
@@ -593,8 +732,8 @@ mod tests {
         board2.place_stone(Pos::new(9, 9), Stone::Black);
         board2.add_captures(Stone::Black, 2);
 
-        let score1 = evaluate(&board1, Stone::Black);
-        let score2 = evaluate(&board2, Stone::Black);
+        let score1 = evaluate(RuleSet::NINUKI, &board1, Stone::Black);
+        let score2 = evaluate(RuleSet::NINUKI, &board2, Stone::Black);
 
         assert!(
             score2 > score1,
@@ -615,11 +754,22 @@ mod tests {
             board.place_stone(Pos::new(i, 18), Stone::White);
         }
 
-        let score = evaluate(&board, Stone::Black);
+        let score = evaluate(RuleSet::NINUKI, &board, Stone::Black);
         assert!(
             score >= PatternScore::NEAR_CAPTURE_WIN,
             "4 captures should be highly valuable, got {}",
             score
         );
     }
+
+    #[test]
+    fn test_line_score_without_overlines() {
+        assert_eq!(line_score(6, false, 6, 1, true), PatternScore::FIVE);
+        assert_eq!(line_score(6, false, 6, 1, false), 0);
+        assert_eq!(line_score(5, false, 5, 0, false), PatternScore::FIVE);
+        // OOO_OO: the gap makes six, only an open end can still make five
+        assert_eq!(line_score(5, true, 6, 2, true), PatternScore::OPEN_FOUR);
+        assert_eq!(line_score(5, true, 6, 1, false), PatternScore::CLOSED_FOUR);
+        assert_eq!(line_score(5, true, 6, 0, false), 0);
+    }
 }
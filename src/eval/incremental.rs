@@ -0,0 +1,186 @@
+//! Incrementally updated evaluation terms
+//!
+//! Every pattern, connectivity and vulnerability term in [`evaluate`] reads
+//! cells along a single line, so the per-color totals are a sum over the
+//! 112 board lines (19 rows, 19 columns, 37 diagonals each way).
+//! [`EvalAccumulator`] keeps that sum. Placing or removing a stone patches
+//! the line masks and marks the four lines through it dirty; dirty lines
+//! are re-tallied from the pattern table ([`super::pattern_table`]) when
+//! the position is evaluated, so the many temporary place/remove pairs made
+//! by rule checks cost almost nothing.
+//!
+//! The accumulator lives in [`Board`] and is opt-in
+//! ([`Board::enable_incremental_eval`]); the search turns it on for its
+//! working copy and calls [`Board::refresh_eval`] before evaluating.
+//! [`evaluate_full`] always rescans and must give the same score.
+//!
+//! [`evaluate`]: super::evaluate
+//! [`evaluate_full`]: super::evaluate_full
+
+use crate::board::{Board, Pos, Stone};
+use crate::rules::RuleSet;
+
+use super::heuristic::{color_score, position_bonus, LineStats, DIRECTIONS, PHASE_WEIGHTS};
+use super::pattern_table::{color_index, line_of, LineMasks, LINE_COUNT};
+
+/// Per-line tallies for both colors plus their running totals.
+#[derive(Debug, Clone)]
+pub(crate) struct EvalAccumulator {
+    /// Line occupancy, patched on every place/remove
+    masks: LineMasks,
+    /// `[color][line]`
+    lines: [[LineStats; LINE_COUNT]; 2],
+    /// `[color]`: sum over all lines, stale for dirty lines
+    totals: [LineStats; 2],
+    /// Lines changed since their tally was stored (bit per line)
+    dirty: u128,
+    /// `[color][phase]`: position bonus at each phase weight
+    position: [[i32; 3]; 2],
+    /// Rules the lines are tallied under
+    rules: RuleSet,
+}
+
+impl EvalAccumulator {
+    /// Tally every line of `board` from scratch under `rules`.
+    pub(crate) fn from_board(board: &Board, rules: RuleSet) -> Self {
+        let mut acc = Self {
+            masks: LineMasks::from_board(board),
+            lines: [[LineStats::default(); LINE_COUNT]; 2],
+            totals: [LineStats::default(); 2],
+            dirty: 0,
+            position: [[0; 3]; 2],
+            rules,
+        };
+        for idx in 0..LINE_COUNT {
+            let stats = acc.masks.tally(idx, rules);
+            for (color, line) in stats.iter().enumerate() {
+                acc.lines[color][idx] = *line;
+                acc.totals[color].add(line);
+            }
+        }
+        for stone in [Stone::Black, Stone::White] {
+            if let Some(bb) = board.stones(stone) {
+                for pos in bb.iter_ones() {
+                    acc.add_position(pos, stone, 1);
+                }
+            }
+        }
+        acc
+    }
+
+    /// Rules the lines are tallied under
+    #[inline]
+    pub(crate) fn rules(&self) -> RuleSet {
+        self.rules
+    }
+
+    fn add_position(&mut self, pos: Pos, stone: Stone, sign: i32) {
+        let position = &mut self.position[color_index(stone)];
+        for (phase, &(pos_mul, _, _)) in PHASE_WEIGHTS.iter().enumerate() {
+            position[phase] += sign * position_bonus(pos, pos_mul);
+        }
+    }
+
+    /// Record that `stone` was placed at (`placed`) or removed from `pos`.
+    #[inline]
+    pub(crate) fn update(&mut self, pos: Pos, stone: Stone, placed: bool) {
+        for dir in 0..DIRECTIONS.len() {
+            self.dirty |= 1u128 << line_of(dir, pos).0;
+        }
+        if placed {
+            self.masks.set(pos, stone);
+        } else {
+            self.masks.clear(pos, stone);
+        }
+        self.add_position(pos, stone, if placed { 1 } else { -1 });
+    }
+
+    /// Re-tally dirty lines and store the results.
+    pub(crate) fn refresh(&mut self) {
+        let mut dirty = self.dirty;
+        while dirty != 0 {
+            let idx = dirty.trailing_zeros() as usize;
+            dirty &= dirty - 1;
+            let stats = self.masks.tally(idx, self.rules);
+            for (color, line) in stats.iter().enumerate() {
+                self.totals[color].sub(&self.lines[color][idx]);
+                self.totals[color].add(line);
+                self.lines[color][idx] = *line;
+            }
+        }
+        self.dirty = 0;
+    }
+
+    /// (score, vulnerable pairs) for `color` at the given phase index.
+    ///
+    /// Dirty lines are re-tallied on the fly without being stored.
+    pub(crate) fn color_score(&self, color: Stone, phase: usize) -> (i32, i32) {
+        let idx = color_index(color);
+        let mut totals = self.totals[idx];
+        let mut dirty = self.dirty;
+        while dirty != 0 {
+            let line = dirty.trailing_zeros() as usize;
+            dirty &= dirty - 1;
+            let stats = self.masks.tally(line, self.rules);
+            totals.sub(&self.lines[idx][line]);
+            totals.add(&stats[idx]);
+        }
+        color_score(&totals, self.position[idx][phase])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use crate::alloc_prelude::*;
+    use crate::eval::{evaluate, evaluate_full};
+    use crate::rules::{execute_captures_fast, undo_captures};
+
+    #[test]
+    fn test_incremental_matches_full_through_make_unmake() {
+        let mut board = Board::new();
+        board.enable_incremental_eval(RuleSet::NINUKI);
+        assert!(board.has_incremental_eval());
+
+        // Pseudo-random game with captures, checking after every make and unmake
+        let mut seed: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut color = Stone::Black;
+        let mut made = Vec::new();
+        for _ in 0..120 {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            let idx = ((seed >> 33) % 81) as u8;
+            let pos = Pos::new(5 + idx / 9, 5 + idx % 9);
+            if !board.is_empty(pos) {
+                continue;
+            }
+            board.place_stone(pos, color);
+            let info = execute_captures_fast(RuleSet::NINUKI, &mut board, pos, color);
+            // Both with pending dirty lines and after refreshing them
+            for side in [Stone::Black, Stone::White] {
+                assert_eq!(evaluate(RuleSet::NINUKI, &board, side), evaluate_full(RuleSet::NINUKI, &board, side));
+            }
+            if made.len() % 2 == 0 {
+                board.refresh_eval();
+                assert_eq!(evaluate(RuleSet::NINUKI, &board, color), evaluate_full(RuleSet::NINUKI, &board, color));
+            }
+            made.push((pos, color, info));
+            color = color.opponent();
+        }
+        assert!(made.iter().any(|(_, _, info)| info.pairs > 0), "test game should capture");
+
+        while let Some((pos, color, info)) = made.pop() {
+            undo_captures(&mut board, color, &info);
+            board.remove_stone(pos);
+            assert_eq!(
+                evaluate(RuleSet::NINUKI, &board, Stone::Black),
+                evaluate_full(RuleSet::NINUKI, &board, Stone::Black)
+            );
+        }
+        board.refresh_eval();
+        let fresh = EvalAccumulator::from_board(&board, RuleSet::NINUKI);
+        let acc = board.eval_accumulator().unwrap();
+        assert_eq!(acc.totals, fresh.totals);
+        assert_eq!(acc.totals, [LineStats::default(); 2]);
+    }
+}
@@ -8,7 +8,9 @@
 //! - Positional bonuses (center control)
 
 pub mod heuristic;
+pub(crate) mod incremental;
+pub(crate) mod pattern_table;
 pub mod patterns;
 
-pub use heuristic::evaluate;
+pub use heuristic::{evaluate, evaluate_full};
 pub use patterns::{capture_score, PatternScore};
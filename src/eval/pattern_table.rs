@@ -0,0 +1,463 @@
+//! Pattern lookup table over line windows
+//!
+//! Each of the 112 board lines (19 rows, 19 columns, 37 diagonals each way)
+//! is kept as a pair of occupancy masks, one bit per cell ([`LineMasks`]).
+//! Scoring a stone's segment then needs no cell-by-cell scanning: the
+//! 9-cell window starting at the stone (the stone plus 8 cells ahead) and
+//! whether the cell behind it is open form an index into a precomputed
+//! table of pattern scores, similar to Rapfi/PentaZen style evaluators.
+//!
+//! The few windows whose segment runs past the window (e.g. `OOOOO_O`,
+//! where the scan reaches the 9th cell ahead) are marked in the table and
+//! scored by the scalar scan instead, so results always match
+//! [`evaluate_full`](super::evaluate_full).
+
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::board::{Board, Pos, Stone, BOARD_SIZE};
+use crate::rules::RuleSet;
+
+use super::heuristic::{line_score, LineStats, DIRECTIONS};
+use super::patterns::PatternScore;
+
+const DIAGONALS: usize = 2 * BOARD_SIZE - 1;
+
+/// Rows, columns, diagonals and anti-diagonals.
+pub(crate) const LINE_COUNT: usize = 2 * BOARD_SIZE + 2 * DIAGONALS;
+
+/// Cells ahead of the stone covered by a table window.
+const WINDOW: u32 = 8;
+
+const WINDOW_MASK: u32 = (1 << WINDOW) - 1;
+
+/// Table index bit for "cell behind the stone is empty".
+const PREV_OPEN: usize = 1 << (2 * WINDOW);
+
+/// Pattern scores by table code; `OVERFLOW` marks windows the scan outgrows.
+const CODE_SCORES: [i32; 8] = [
+    0,
+    PatternScore::CLOSED_TWO,
+    PatternScore::OPEN_TWO,
+    PatternScore::CLOSED_THREE,
+    PatternScore::OPEN_THREE,
+    PatternScore::CLOSED_FOUR,
+    PatternScore::OPEN_FOUR,
+    PatternScore::FIVE,
+];
+
+const OVERFLOW: u8 = CODE_SCORES.len() as u8;
+
+/// Index into the color arrays: 0 = Black, 1 = White.
+#[inline]
+pub(crate) fn color_index(stone: Stone) -> usize {
+    usize::from(stone == Stone::White)
+}
+
+/// Line index and first cell of the line through `pos` in direction `dir`.
+#[inline]
+pub(crate) fn line_of(dir: usize, pos: Pos) -> (usize, Pos) {
+    let (r, c) = (pos.row, pos.col);
+    let last = (BOARD_SIZE - 1) as u8;
+    match dir {
+        0 => (usize::from(r), Pos::new(r, 0)),
+        1 => (BOARD_SIZE + usize::from(c), Pos::new(0, c)),
+        2 => {
+            let idx = 2 * BOARD_SIZE + usize::from(r) + BOARD_SIZE - 1 - usize::from(c);
+            let start = if r >= c { Pos::new(r - c, 0) } else { Pos::new(0, c - r) };
+            (idx, start)
+        }
+        _ => {
+            let sum = r + c;
+            let idx = 2 * BOARD_SIZE + DIAGONALS + usize::from(sum);
+            let row = sum.saturating_sub(last);
+            (idx, Pos::new(row, sum - row))
+        }
+    }
+}
+
+/// Line index and bit offset of `pos` within the line in direction `dir`.
+#[inline]
+fn cell_of(dir: usize, pos: Pos) -> (usize, u32) {
+    let (idx, start) = line_of(dir, pos);
+    let offset = if dir == 0 { pos.col } else { pos.row - start.row };
+    (idx, u32::from(offset))
+}
+
+/// Direction and first cell of every line, by line index.
+pub(crate) static LINE_STARTS: [(usize, Pos); LINE_COUNT] = {
+    let mut starts = [(0, Pos { row: 0, col: 0 }); LINE_COUNT];
+    let n = BOARD_SIZE as u8;
+    let mut i = 0u8;
+    while i < n {
+        starts[i as usize] = (0, Pos { row: i, col: 0 });
+        starts[BOARD_SIZE + i as usize] = (1, Pos { row: 0, col: i });
+        i += 1;
+    }
+    // Diagonals (1, 1): index 2n + r + n - 1 - c
+    let mut d = 0;
+    while d < DIAGONALS {
+        let (row, col) = if d < BOARD_SIZE { (0, (BOARD_SIZE - 1 - d) as u8) } else { ((d + 1 - BOARD_SIZE) as u8, 0) };
+        starts[2 * BOARD_SIZE + d] = (2, Pos { row, col });
+        // Anti-diagonals (1, -1): index 2n + diagonals + r + c
+        let row = if d < BOARD_SIZE { 0 } else { (d + 1 - BOARD_SIZE) as u8 };
+        starts[2 * BOARD_SIZE + DIAGONALS + d] = (3, Pos { row, col: d as u8 - row });
+        d += 1;
+    }
+    starts
+};
+
+/// Number of cells in every line, by line index.
+static LINE_LENS: [u32; LINE_COUNT] = {
+    let mut lens = [BOARD_SIZE as u32; LINE_COUNT];
+    let mut i = 2 * BOARD_SIZE;
+    while i < LINE_COUNT {
+        let (dir, start) = LINE_STARTS[i];
+        let (row, col) = (start.row as u32, start.col as u32);
+        let n = BOARD_SIZE as u32;
+        lens[i] = if dir == 2 {
+            n - if row > col { row } else { col }
+        } else if col + 1 < n - row {
+            col + 1
+        } else {
+            n - row
+        };
+        i += 1;
+    }
+    lens
+};
+
+/// Scan the segment starting at a stone, like `evaluate_line` in
+/// [`super::heuristic`]. Bit `k - 1` of `my`/`blocked` is the cell `k`
+/// steps ahead; off-board cells count as blocked. Returns `None` if the
+/// scan would read past `limit` cells ahead.
+fn scan(my: u32, blocked: u32, prev_open: bool, limit: u32, overline_wins: bool) -> Option<i32> {
+    let bit = |mask: u32, k: u32| (mask >> (k - 1)) & 1 == 1;
+    let mut count = 1;
+    let mut open_ends = u8::from(prev_open);
+    let mut has_gap = false;
+    let mut total_span = 1;
+
+    let mut k = 1;
+    loop {
+        if k > limit {
+            return None;
+        }
+        if bit(my, k) {
+            count += 1;
+            total_span += 1;
+        } else if bit(blocked, k) {
+            break;
+        } else if !has_gap {
+            if k + 1 > limit {
+                return None;
+            }
+            if bit(my, k + 1) {
+                has_gap = true;
+                total_span += 1;
+                k += 1;
+                continue;
+            }
+            open_ends += 1;
+            break;
+        } else {
+            open_ends += 1;
+            break;
+        }
+        k += 1;
+    }
+
+    Some(line_score(count, has_gap, total_span, open_ends, overline_wins))
+}
+
+/// Table index of the window starting one cell ahead of the stone.
+#[inline]
+fn window_index(prev_open: bool, my: u32, blocked: u32) -> usize {
+    let window = ((blocked & WINDOW_MASK) << WINDOW) | (my & WINDOW_MASK);
+    (usize::from(prev_open) * PREV_OPEN) | window as usize
+}
+
+/// Pattern code for every (prev open, blocked window, own window) index,
+/// one table with overlines winning and one without.
+#[cfg(feature = "std")]
+fn table(overline_wins: bool) -> &'static [u8] {
+    static TABLES: [OnceLock<Box<[u8]>>; 2] = [OnceLock::new(), OnceLock::new()];
+    TABLES[usize::from(overline_wins)].get_or_init(|| build_table(overline_wins))
+}
+
+/// [`table`] without `std`'s `OnceLock`: built on first use and leaked; a
+/// thread that loses the race to publish its copy leaks that one too.
+#[cfg(not(feature = "std"))]
+fn table(overline_wins: bool) -> &'static [u8] {
+    use core::sync::atomic::{AtomicPtr, Ordering};
+
+    static TABLES: [AtomicPtr<u8>; 2] = [AtomicPtr::new(core::ptr::null_mut()), AtomicPtr::new(core::ptr::null_mut())];
+    let slot = &TABLES[usize::from(overline_wins)];
+    let mut ptr = slot.load(Ordering::Acquire);
+    if ptr.is_null() {
+        let built = Box::leak(build_table(overline_wins)).as_mut_ptr();
+        ptr = match slot.compare_exchange(core::ptr::null_mut(), built, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => built,
+            Err(published) => published,
+        };
+    }
+    // SAFETY: every pointer stored in TABLES comes from a leaked (never
+    // freed, never written again) table of 2 * PREV_OPEN codes.
+    unsafe { core::slice::from_raw_parts(ptr, 2 * PREV_OPEN) }
+}
+
+fn build_table(overline_wins: bool) -> Box<[u8]> {
+    (0..2 * PREV_OPEN)
+        .map(|idx| {
+            let my = idx as u32 & WINDOW_MASK;
+            let blocked = (idx >> WINDOW) as u32 & WINDOW_MASK;
+            match scan(my, blocked, idx & PREV_OPEN != 0, WINDOW, overline_wins) {
+                Some(score) => CODE_SCORES.iter().position(|&s| s == score).unwrap() as u8,
+                None => OVERFLOW,
+            }
+        })
+        .collect()
+}
+
+/// Per-line occupancy masks for both colors.
+#[derive(Debug, Clone)]
+pub(crate) struct LineMasks {
+    /// `[color][line]`: bit `i` = `i`-th cell from the line start
+    stones: [[u32; LINE_COUNT]; 2],
+}
+
+impl LineMasks {
+    /// Masks for every stone on `board`.
+    pub(crate) fn from_board(board: &Board) -> Self {
+        let mut masks = Self { stones: [[0; LINE_COUNT]; 2] };
+        for stone in [Stone::Black, Stone::White] {
+            if let Some(bb) = board.stones(stone) {
+                for pos in bb.iter_ones() {
+                    masks.set(pos, stone);
+                }
+            }
+        }
+        masks
+    }
+
+    /// Add `stone` at `pos` to the four lines through it.
+    #[inline]
+    pub(crate) fn set(&mut self, pos: Pos, stone: Stone) {
+        let lines = &mut self.stones[color_index(stone)];
+        for dir in 0..DIRECTIONS.len() {
+            let (idx, offset) = cell_of(dir, pos);
+            lines[idx] |= 1 << offset;
+        }
+    }
+
+    /// Remove `stone` at `pos` from the four lines through it.
+    #[inline]
+    pub(crate) fn clear(&mut self, pos: Pos, stone: Stone) {
+        let lines = &mut self.stones[color_index(stone)];
+        for dir in 0..DIRECTIONS.len() {
+            let (idx, offset) = cell_of(dir, pos);
+            lines[idx] &= !(1 << offset);
+        }
+    }
+
+    /// Tally one line for both colors under `rules`.
+    pub(crate) fn tally(&self, line: usize, rules: RuleSet) -> [LineStats; 2] {
+        let table = table(rules.overline_wins());
+        let off_board = !0u32 << LINE_LENS[line];
+        let mut stats = [LineStats::default(); 2];
+        for (color, stats) in stats.iter_mut().enumerate() {
+            let my = self.stones[color][line];
+            let opp = self.stones[1 - color][line];
+            let mut bits = my;
+            while bits != 0 {
+                let i = bits.trailing_zeros();
+                bits &= bits - 1;
+                tally_stone(table, my, opp, opp | off_board, i, rules, stats);
+            }
+        }
+        stats
+    }
+
+    /// Tally every line under `rules`: per-color totals.
+    pub(crate) fn totals(&self, rules: RuleSet) -> [LineStats; 2] {
+        let mut totals = [LineStats::default(); 2];
+        for line in 0..LINE_COUNT {
+            if self.stones[0][line] | self.stones[1][line] == 0 {
+                continue;
+            }
+            for (total, stats) in totals.iter_mut().zip(self.tally(line, rules)) {
+                total.add(&stats);
+            }
+        }
+        totals
+    }
+}
+
+/// Whether bit `i` lies between two opponent stones at most five cells
+/// apart; mirrors `caro_squeezed` in [`super::heuristic`].
+#[inline]
+fn caro_squeezed(opp: u32, i: u32) -> bool {
+    let behind = opp & ((1 << i) - 1);
+    let ahead = opp >> (i + 1);
+    behind != 0 && ahead != 0 && {
+        let left = u32::BITS - 1 - behind.leading_zeros();
+        let right = i + 1 + ahead.trailing_zeros();
+        right - left - 1 <= 5
+    }
+}
+
+/// Tally the pattern, connectivity and vulnerability terms for the stone at
+/// bit `i` of a line; mirrors `tally_direction` in [`super::heuristic`].
+#[inline]
+fn tally_stone(table: &[u8], my: u32, opp: u32, blocked: u32, i: u32, rules: RuleSet, stats: &mut LineStats) {
+    let prev = |mask: u32| i > 0 && (mask >> (i - 1)) & 1 == 1;
+
+    // --- Pattern: table lookup, once per segment (line-start filter) ---
+    if !prev(my) && (!rules.caro() || !caro_squeezed(opp, i)) {
+        let prev_open = i > 0 && !prev(opp);
+        let ahead_my = my >> (i + 1);
+        let ahead_blocked = blocked >> (i + 1);
+        let code = table[window_index(prev_open, ahead_my, ahead_blocked)];
+        let pattern_score = if code == OVERFLOW {
+            // Every line fits in the masks, so the scan always terminates
+            scan(ahead_my, ahead_blocked, prev_open, u32::BITS, rules.overline_wins()).unwrap_or(0)
+        } else {
+            CODE_SCORES[usize::from(code)]
+        };
+        stats.add_pattern(pattern_score);
+    }
+
+    // --- Connectivity and vulnerability: needs an ally right ahead ---
+    if (my >> (i + 1)) & 1 == 0 {
+        return;
+    }
+    stats.links += 1;
+
+    let b_empty = i > 0 && !prev(my | opp);
+    let b_opp = prev(opp);
+    let a_empty = ((my | blocked) >> (i + 2)) & 1 == 0;
+    let a_opp = (opp >> (i + 2)) & 1 == 1;
+
+    // empty-ally-ally-opp: opponent plays at empty to capture
+    if b_empty && a_opp { stats.vuln += 1; }
+    // opp-ally-ally-empty: opponent plays at empty to capture
+    if b_opp && a_empty { stats.vuln += 1; }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::heuristic;
+    use super::*;
+    use crate::eval::{evaluate, evaluate_full};
+
+    #[test]
+    fn test_line_of_covers_every_line_once() {
+        let mut starts = [None; LINE_COUNT];
+        let mut lens = [0; LINE_COUNT];
+        for dir in 0..4 {
+            for r in 0..BOARD_SIZE as u8 {
+                for c in 0..BOARD_SIZE as u8 {
+                    let (idx, start) = line_of(dir, Pos::new(r, c));
+                    assert!(*starts[idx].get_or_insert((dir, start)) == (dir, start));
+                    let (cell_idx, offset) = cell_of(dir, Pos::new(r, c));
+                    assert_eq!(cell_idx, idx);
+                    lens[idx] = lens[idx].max(offset + 1);
+                }
+            }
+        }
+        for (idx, start) in starts.iter().enumerate() {
+            assert_eq!(*start, Some(LINE_STARTS[idx]), "line {}", idx);
+        }
+        assert_eq!(lens, LINE_LENS);
+    }
+
+    #[test]
+    fn test_table_matches_unbounded_scan() {
+        for overline_wins in [true, false] {
+            let table = table(overline_wins);
+            for prev_open in [false, true] {
+                for my in 0..=WINDOW_MASK {
+                    for blocked in (0..=WINDOW_MASK).filter(|b| b & my == 0) {
+                        let idx = window_index(prev_open, my, blocked);
+                        // Cells past the window: try both empty and blocked
+                        for beyond in [0, !WINDOW_MASK] {
+                            let full = scan(my, blocked | beyond, prev_open, u32::BITS, overline_wins).unwrap();
+                            match table[idx] {
+                                OVERFLOW => {}
+                                code => assert_eq!(CODE_SCORES[usize::from(code)], full),
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_overflow_window_scored() {
+        // OOOOO_O: the gap check reaches past the window
+        let mut board = Board::new();
+        for c in [2, 3, 4, 5, 6, 8] {
+            board.place_stone(Pos::new(9, c), Stone::Black);
+        }
+        let masks = LineMasks::from_board(&board);
+        let stats = masks.tally(9, RuleSet::NINUKI);
+        assert_eq!(stats[0].open_fours, 1);
+        assert_eq!(
+            evaluate(RuleSet::NINUKI, &board, Stone::Black),
+            evaluate_full(RuleSet::NINUKI, &board, Stone::Black)
+        );
+    }
+
+    #[test]
+    fn test_table_evaluate_matches_full_on_random_boards() {
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        for _ in 0..200 {
+            let mut board = Board::new();
+            for _ in 0..60 {
+                seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+                // Dense 11x11 area so long and gapped segments show up
+                let idx = ((seed >> 33) % 121) as u8;
+                let stone = if (seed >> 20) & 1 == 0 { Stone::Black } else { Stone::White };
+                let pos = Pos::new(4 + idx / 11, 4 + idx % 11);
+                if board.is_empty(pos) {
+                    board.place_stone(pos, stone);
+                }
+            }
+            for side in [Stone::Black, Stone::White] {
+                assert_eq!(evaluate(RuleSet::NINUKI, &board, side), evaluate_full(RuleSet::NINUKI, &board, side));
+            }
+        }
+    }
+
+    #[test]
+    fn test_caro_squeezed_matches_scalar() {
+        let mut seed: u64 = 0x9E37_79B9_7F4A_7C15;
+        for _ in 0..100 {
+            let mut board = Board::new();
+            for _ in 0..40 {
+                seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+                let pos = Pos::from_index(((seed >> 33) % crate::board::TOTAL_CELLS as u64) as usize);
+                let stone = if (seed >> 20) & 1 == 0 { Stone::Black } else { Stone::White };
+                if board.is_empty(pos) {
+                    board.place_stone(pos, stone);
+                }
+            }
+            let masks = LineMasks::from_board(&board);
+            for pos in board.stones_of(Stone::Black) {
+                for (dir, &(dr, dc)) in DIRECTIONS.iter().enumerate() {
+                    let (line, offset) = cell_of(dir, pos);
+                    assert_eq!(
+                        caro_squeezed(masks.stones[1][line], offset),
+                        heuristic::caro_squeezed(&board.bitboards().1, pos, dr, dc),
+                        "{:?} dir {}",
+                        pos,
+                        dir
+                    );
+                }
+            }
+        }
+    }
+}
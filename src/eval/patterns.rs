@@ -3,6 +3,8 @@
 //! These constants define the scoring weights for various board patterns.
 //! Carefully tuned for strong play with Ninuki-renju rules.
 
+use crate::rules::RuleSet;
+
 /// Pattern scores for evaluation
 /// These are carefully tuned for strong play
 pub struct PatternScore;
@@ -11,7 +13,7 @@ impl PatternScore {
     // Winning patterns
     /// Five in a row - immediate win
     pub const FIVE: i32 = 1_000_000;
-    /// Capture win (5 pairs captured)
+    /// Capture win (5 pairs captured by default)
     pub const CAPTURE_WIN: i32 = 1_000_000;
 
     // Strong attacking patterns
@@ -40,7 +42,7 @@ impl PatternScore {
     pub const CAPTURE_THREAT: i32 = 8_000;
     /// Value per captured pair
     pub const CAPTURE_PAIR: i32 = 5_000;
-    /// 4 pairs captured (one more = win) - must be >> OPEN_FOUR
+    /// One pair short of the capture win (4 by default) - must be >> OPEN_FOUR
     pub const NEAR_CAPTURE_WIN: i32 = 80_000;
 
     // Note: Defense-first behavior is handled by move ordering (score_move),
@@ -50,32 +52,43 @@ impl PatternScore {
 
 /// Capture-based scoring with non-linear weights
 ///
-/// The scoring is exponential as captures approach the winning threshold.
-/// MUST be symmetric for negamax: capture_score(a, b) == -capture_score(b, a).
+/// The scoring is exponential as captures approach the winning threshold
+/// of `rules` ([`RuleSet::capture_win_pairs`]).
+/// MUST be symmetric for negamax: capture_score(r, a, b) == -capture_score(r, b, a).
 ///
 /// # Arguments
+/// * `rules` - Rule set whose capture win the scores lead up to
 /// * `my_captures` - Number of pairs captured by the player
 /// * `opp_captures` - Number of pairs captured by the opponent
 ///
 /// # Returns
 /// Score differential (positive = advantage, negative = disadvantage)
-pub fn capture_score(my_captures: u8, opp_captures: u8) -> i32 {
+pub fn capture_score(rules: RuleSet, my_captures: u8, opp_captures: u8) -> i32 {
+    capture_score_to(my_captures, opp_captures, rules.capture_win_pairs())
+}
+
+/// [`capture_score`] when `win_pairs` pairs win
+fn capture_score_to(my_captures: u8, opp_captures: u8, win_pairs: u8) -> i32 {
     // Non-linear scoring - closer to win = exponentially more valuable
     // Each level must be significantly higher than pattern threats at that stage
     // to ensure the AI treats capture accumulation as a serious strategic factor.
-    const CAP_WEIGHTS: [i32; 6] = [
-        0,
-        5_000,     // 1 capture: significant (> CLOSED_THREE, forces AI to avoid giving first capture)
-        7_000,     // 2 captures: moderate (> CLOSED_THREE)
-        20_000,    // 3 captures: serious threat (> OPEN_THREE)
-        PatternScore::NEAR_CAPTURE_WIN, // 4 captures: 80K, near-winning
+    // Indexed by pairs still missing; with the default 5 to win:
+    const CAP_WEIGHTS: [i32; 5] = [
         PatternScore::CAPTURE_WIN,      // 5 captures: 1M, game over
+        PatternScore::NEAR_CAPTURE_WIN, // 4 captures: 80K, near-winning
+        20_000,    // 3 captures: serious threat (> OPEN_THREE)
+        7_000,     // 2 captures: moderate (> CLOSED_THREE)
+        5_000,     // 1 capture: significant (> CLOSED_THREE, forces AI to avoid giving first capture)
     ];
+    let weight = |captures: u8| {
+        if captures == 0 {
+            return 0;
+        }
+        let missing = win_pairs.saturating_sub(captures);
+        CAP_WEIGHTS[usize::from(missing).min(CAP_WEIGHTS.len() - 1)]
+    };
 
-    let my_score = CAP_WEIGHTS[my_captures.min(5) as usize];
-    let opp_score = CAP_WEIGHTS[opp_captures.min(5) as usize];
-
-    my_score - opp_score
+    weight(my_captures) - weight(opp_captures)
 }
 
 #[cfg(test)]
@@ -83,6 +96,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_pattern_score_hierarchy() {
         // Verify score hierarchy makes sense
         assert!(PatternScore::FIVE > PatternScore::OPEN_FOUR);
@@ -95,34 +109,34 @@ mod tests {
 
     #[test]
     fn test_capture_score_zero() {
-        assert_eq!(capture_score(0, 0), 0);
+        assert_eq!(capture_score(RuleSet::NINUKI, 0, 0), 0);
     }
 
     #[test]
     fn test_capture_score_advantage() {
-        let score = capture_score(2, 0);
+        let score = capture_score(RuleSet::NINUKI, 2, 0);
         assert!(score > 0, "Should be positive for capture advantage");
     }
 
     #[test]
     fn test_capture_score_near_win() {
-        let score = capture_score(4, 0);
+        let score = capture_score(RuleSet::NINUKI, 4, 0);
         assert!(score >= 60_000, "4 captures should be highly valuable (near-win)");
     }
 
     #[test]
     fn test_capture_score_symmetric() {
-        // Negamax requires: capture_score(a, b) == -capture_score(b, a)
-        let score_1_0 = capture_score(1, 0);
-        let score_0_1 = capture_score(0, 1);
+        // Negamax requires: capture_score(r, a, b) == -capture_score(r, b, a)
+        let score_1_0 = capture_score(RuleSet::NINUKI, 1, 0);
+        let score_0_1 = capture_score(RuleSet::NINUKI, 0, 1);
         assert_eq!(
             score_1_0, -score_0_1,
             "capture_score must be symmetric: (1,0)={}, (0,1)={}",
             score_1_0, score_0_1
         );
 
-        let score_2_1 = capture_score(2, 1);
-        let score_1_2 = capture_score(1, 2);
+        let score_2_1 = capture_score(RuleSet::NINUKI, 2, 1);
+        let score_1_2 = capture_score(RuleSet::NINUKI, 1, 2);
         assert_eq!(
             score_2_1, -score_1_2,
             "capture_score must be symmetric: (2,1)={}, (1,2)={}",
@@ -132,7 +146,7 @@ mod tests {
 
     #[test]
     fn test_capture_score_win() {
-        let score = capture_score(5, 0);
+        let score = capture_score(RuleSet::NINUKI, 5, 0);
         assert_eq!(score, PatternScore::CAPTURE_WIN);
     }
 
@@ -141,8 +155,8 @@ mod tests {
         // Verify negamax property: score(a,b) == -score(b,a) for all values
         for a in 0..=5u8 {
             for b in 0..=5u8 {
-                let score_ab = capture_score(a, b);
-                let score_ba = capture_score(b, a);
+                let score_ab = capture_score(RuleSet::NINUKI, a, b);
+                let score_ba = capture_score(RuleSet::NINUKI, b, a);
                 assert_eq!(
                     score_ab, -score_ba,
                     "Negamax symmetry violated: capture_score({},{})={}, capture_score({},{})={}",
@@ -151,4 +165,16 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_capture_score_follows_win_threshold() {
+        // The default threshold reproduces the fixed table
+        assert_eq!(capture_score_to(4, 0, 5), PatternScore::NEAR_CAPTURE_WIN);
+        assert_eq!(capture_score_to(1, 0, 5), 5_000);
+        // Weights track the pairs still missing, not the pairs taken
+        assert_eq!(capture_score_to(2, 0, 3), PatternScore::NEAR_CAPTURE_WIN);
+        assert_eq!(capture_score_to(1, 0, 1), PatternScore::CAPTURE_WIN);
+        assert_eq!(capture_score_to(4, 0, 10), 5_000);
+        assert_eq!(capture_score_to(0, 0, 1), 0);
+    }
 }
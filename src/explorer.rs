@@ -0,0 +1,155 @@
+//! Opening explorer: what happened next from a position
+//!
+//! [`explore`] looks a position up in a [`GameArchive`] and gathers the
+//! games that reached it (transpositions included), their results, and
+//! the replies played from it with each reply's score for the side to
+//! move. The opening book's answer is marked among them, or added when no
+//! archived game tried it. The GUI shows it as the Opening Explorer panel
+//! next to the live or reviewed position.
+//!
+//! ```
+//! use gomoku::archive::{ArchivedGame, GameArchive, GameScore};
+//! use gomoku::explorer::explore;
+//! use gomoku::record::GameRecord;
+//! use gomoku::{Board, Stone};
+//!
+//! let mut archive = GameArchive::new();
+//! for (moves, result) in [("K10 L11", GameScore::BlackWins), ("K10 L9", GameScore::WhiteWins)] {
+//!     let moves = GameRecord::parse(moves).unwrap().moves;
+//!     archive.append(ArchivedGame::new("a", "b", result, "-", moves)).unwrap();
+//! }
+//! let explored = explore(&archive, &Board::new(), Stone::Black);
+//! assert_eq!(explored.games, 2);
+//! assert_eq!(explored.replies[0].games, 2);
+//! assert!(explored.replies[0].book, "K10 is the book's first move");
+//! ```
+
+use std::collections::HashMap;
+
+use crate::archive::{GameArchive, GameScore};
+use crate::board::{Board, Pos, Stone};
+use crate::engine::AIEngine;
+
+/// Results after one reply from the explored position, for the side that
+/// played it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplyStats {
+    pub pos: Pos,
+    /// Archived games that continued with this move
+    pub games: usize,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    /// The opening book plays this move here
+    pub book: bool,
+}
+
+impl ReplyStats {
+    /// Score of the side that played the reply, 0.0 to 1.0; `None` when
+    /// none of its games finished
+    #[must_use]
+    pub fn score(&self) -> Option<f64> {
+        let finished = self.wins + self.draws + self.losses;
+        (finished > 0).then(|| (self.wins as f64 + self.draws as f64 * 0.5) / finished as f64)
+    }
+}
+
+/// What the archive and the book know about a position
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exploration {
+    /// Archived games that reached the position
+    pub games: usize,
+    pub black_wins: usize,
+    pub white_wins: usize,
+    pub draws: usize,
+    /// Replies, most played first; a book move no game tried comes last
+    pub replies: Vec<ReplyStats>,
+}
+
+/// Explore `board` with `to_move` to play: the archive's games through it
+/// and the replies played, with the book's move marked
+#[must_use]
+pub fn explore(archive: &GameArchive, board: &Board, to_move: Stone) -> Exploration {
+    let mut explored = Exploration { games: 0, black_wins: 0, white_wins: 0, draws: 0, replies: Vec::new() };
+    let mut replies: HashMap<Pos, usize> = HashMap::new();
+    for hit in archive.find(board, to_move) {
+        let game = &archive.games()[hit.game];
+        explored.games += 1;
+        match game.result {
+            GameScore::BlackWins => explored.black_wins += 1,
+            GameScore::WhiteWins => explored.white_wins += 1,
+            GameScore::Draw => explored.draws += 1,
+            GameScore::Unfinished => {}
+        }
+        let Some(&pos) = game.moves.get(hit.ply) else { continue };
+        let index = *replies.entry(pos).or_insert_with(|| {
+            explored.replies.push(ReplyStats { pos, games: 0, wins: 0, draws: 0, losses: 0, book: false });
+            explored.replies.len() - 1
+        });
+        let reply = &mut explored.replies[index];
+        reply.games += 1;
+        match game.result.points(to_move) {
+            Some(p) if p > 0.75 => reply.wins += 1,
+            Some(p) if p > 0.25 => reply.draws += 1,
+            Some(_) => reply.losses += 1,
+            None => {}
+        }
+    }
+    // Stable: equally played replies stay in the order they were first seen
+    explored.replies.sort_by_key(|reply| std::cmp::Reverse(reply.games));
+
+    if let Some(book) = AIEngine::opening_book_move(board, to_move) {
+        match explored.replies.iter_mut().find(|reply| reply.pos == book) {
+            Some(reply) => reply.book = true,
+            None => explored.replies.push(ReplyStats { pos: book, games: 0, wins: 0, draws: 0, losses: 0, book: true }),
+        }
+    }
+    explored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive::ArchivedGame;
+    use crate::record::GameRecord;
+    use crate::rules::RuleSet;
+
+    #[test]
+    fn test_replies_score_for_the_side_to_move() {
+        let mut archive = GameArchive::new();
+        for (moves, result) in [
+            ("K10 L11 K11", GameScore::WhiteWins),
+            ("K10 L11 M12", GameScore::BlackWins),
+            ("K10 L11 M12", GameScore::BlackWins),
+            ("K10 L11 M12 N13", GameScore::Draw),
+            ("K10 L11", GameScore::Unfinished),
+            ("K10 J9 K11", GameScore::BlackWins),
+        ] {
+            let moves = GameRecord::parse(moves).unwrap().moves;
+            archive.append(ArchivedGame::new("a", "b", result, "-", moves)).unwrap();
+        }
+
+        let boards = GameRecord::parse("K10 L11").unwrap().boards(RuleSet::NINUKI).unwrap();
+        let explored = explore(&archive, &boards[2], Stone::Black);
+        assert_eq!(explored.games, 5);
+        assert_eq!((explored.black_wins, explored.white_wins, explored.draws), (2, 1, 1));
+
+        let m12 = &explored.replies[0];
+        assert_eq!(m12.pos, GameRecord::parse("M12").unwrap().moves[0]);
+        assert_eq!((m12.games, m12.wins, m12.draws, m12.losses), (3, 2, 1, 0));
+        assert!((m12.score().unwrap() - 5.0 / 6.0).abs() < 1e-9);
+        assert_eq!(explored.replies[1].score(), Some(0.0));
+        assert!(explored.replies.iter().all(|reply| !reply.book), "The book has no third move here");
+    }
+
+    #[test]
+    fn test_book_move_is_listed_without_games() {
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        let explored = explore(&GameArchive::new(), &board, Stone::White);
+        assert_eq!(explored.games, 0);
+        let [book] = explored.replies.as_slice() else { panic!("{:?}", explored.replies) };
+        assert!(book.book && book.games == 0 && book.score().is_none());
+        assert_eq!(Some(book.pos), AIEngine::opening_book_move(&board, Stone::White));
+    }
+}
@@ -0,0 +1,339 @@
+//! C ABI for embedding the engine in C/C++/C# front-ends (feature `ffi`)
+//!
+//! Build the shared library with
+//! `cargo rustc --lib --release --features ffi --crate-type cdylib`. The
+//! matching header is `include/gomoku.h`, generated with
+//! `cbindgen --config cbindgen.toml --output include/gomoku.h src/ffi.rs`.
+//!
+//! ```c
+//! GomokuEngine *engine = gomoku_engine_new(10, 500);
+//! uint8_t cells[GOMOKU_BOARD_CELLS] = {0};
+//! cells[9 * 19 + 9] = GOMOKU_BLACK;
+//! gomoku_engine_set_position(engine, cells, GOMOKU_BOARD_CELLS, 0, 0);
+//!
+//! GomokuMove mv;
+//! if (gomoku_engine_best_move(engine, GOMOKU_WHITE, &mv) == GOMOKU_OK) {
+//!     printf("%u %u\n", mv.row, mv.col);
+//! }
+//! gomoku_engine_free(engine);
+//! ```
+//!
+//! All functions are panic-safe: a Rust panic never unwinds into the caller.
+//! Functions returning a status report it as `GOMOKU_ERR_INTERNAL`, the
+//! others just return.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::board::{Board, Pos, Stone, BOARD_SIZE, TOTAL_CELLS};
+use crate::engine::AIEngine;
+
+/// Number of cells expected by `gomoku_engine_set_position` (19 × 19).
+///
+/// Written as a literal so the generated header is self-contained.
+pub const GOMOKU_BOARD_CELLS: usize = 361;
+
+/// Cell / color value: empty intersection
+pub const GOMOKU_EMPTY: u8 = 0;
+/// Cell / color value: Black
+pub const GOMOKU_BLACK: u8 = 1;
+/// Cell / color value: White
+pub const GOMOKU_WHITE: u8 = 2;
+
+/// Success
+pub const GOMOKU_OK: i32 = 0;
+/// Search finished but there is no legal move
+pub const GOMOKU_NO_MOVE: i32 = 1;
+/// A required pointer argument was null
+pub const GOMOKU_ERR_NULL: i32 = -1;
+/// An argument was out of range (cell value, color, array length)
+pub const GOMOKU_ERR_INVALID_ARG: i32 = -2;
+/// The engine panicked; the handle should be freed
+pub const GOMOKU_ERR_INTERNAL: i32 = -3;
+
+/// Opaque engine handle: an AI engine plus the position it searches.
+pub struct GomokuEngine {
+    engine: AIEngine,
+    board: Board,
+}
+
+/// Best move reported by `gomoku_engine_best_move`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GomokuMove {
+    /// Row, 0-based from the top
+    pub row: u8,
+    /// Column, 0-based from the left
+    pub col: u8,
+    /// Alpha-beta depth reached (0 for book/VCF/defense moves)
+    pub depth: i8,
+    /// Evaluation from the mover's perspective
+    pub score: i32,
+    /// Nodes searched
+    pub nodes: u64,
+    /// Thinking time in milliseconds
+    pub time_ms: u64,
+    /// The engine would rather resign than play this move
+    /// (see `gomoku_engine_set_resign_threshold`)
+    pub resign: bool,
+}
+
+/// Create an engine. Free it with `gomoku_engine_free`.
+///
+/// Uses a 64 MB transposition table. Returns null on failure.
+#[no_mangle]
+pub extern "C" fn gomoku_engine_new(max_depth: i8, time_limit_ms: u64) -> *mut GomokuEngine {
+    catch_unwind(|| {
+        Box::into_raw(Box::new(GomokuEngine {
+            engine: AIEngine::with_config(64, max_depth, time_limit_ms),
+            board: Board::new(),
+        }))
+    })
+    .unwrap_or(std::ptr::null_mut())
+}
+
+/// Destroy an engine created by `gomoku_engine_new`. Null is ignored.
+///
+/// # Safety
+///
+/// `engine` must be null or a pointer returned by `gomoku_engine_new` that
+/// has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn gomoku_engine_free(engine: *mut GomokuEngine) {
+    if !engine.is_null() {
+        let _ = catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(engine))));
+    }
+}
+
+/// Replace the engine's position.
+///
+/// `cells` holds `GOMOKU_BOARD_CELLS` values in row-major order, each
+/// `GOMOKU_EMPTY`, `GOMOKU_BLACK` or `GOMOKU_WHITE`. Capture counts are in
+/// pairs (0-5, or up to the rule set's capture win). On error the previous position is kept.
+///
+/// # Safety
+///
+/// `engine` must be a live handle and `cells` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn gomoku_engine_set_position(
+    engine: *mut GomokuEngine,
+    cells: *const u8,
+    len: usize,
+    black_captures: u8,
+    white_captures: u8,
+) -> i32 {
+    let Some(handle) = engine.as_mut() else {
+        return GOMOKU_ERR_NULL;
+    };
+    if cells.is_null() {
+        return GOMOKU_ERR_NULL;
+    }
+    if len != GOMOKU_BOARD_CELLS {
+        return GOMOKU_ERR_INVALID_ARG;
+    }
+    let cells = std::slice::from_raw_parts(cells, len);
+
+    catch_unwind(AssertUnwindSafe(|| {
+        let mut board = Board::new();
+        for (idx, &cell) in cells.iter().enumerate() {
+            match stone_from_u8(cell) {
+                Some(Stone::Empty) => {}
+                Some(stone) => board.place_stone(Pos::from_index(idx), stone),
+                None => return GOMOKU_ERR_INVALID_ARG,
+            }
+        }
+        let rules = handle.engine.rules();
+        if board.set_captures(rules, Stone::Black, black_captures).is_err()
+            || board.set_captures(rules, Stone::White, white_captures).is_err()
+        {
+            return GOMOKU_ERR_INVALID_ARG;
+        }
+        handle.board = board;
+        GOMOKU_OK
+    }))
+    .unwrap_or(GOMOKU_ERR_INTERNAL)
+}
+
+/// Search the best move for `color` in the current position.
+///
+/// Writes the result to `out` and returns `GOMOKU_OK`, or returns
+/// `GOMOKU_NO_MOVE` (leaving `out` untouched) if there is no legal move.
+///
+/// # Safety
+///
+/// `engine` must be a live handle and `out` must point to a writable `GomokuMove`.
+#[no_mangle]
+pub unsafe extern "C" fn gomoku_engine_best_move(
+    engine: *mut GomokuEngine,
+    color: u8,
+    out: *mut GomokuMove,
+) -> i32 {
+    let Some(handle) = engine.as_mut() else {
+        return GOMOKU_ERR_NULL;
+    };
+    let Some(out) = out.as_mut() else {
+        return GOMOKU_ERR_NULL;
+    };
+    let stone = match stone_from_u8(color) {
+        Some(Stone::Empty) | None => return GOMOKU_ERR_INVALID_ARG,
+        Some(stone) => stone,
+    };
+
+    let searched = catch_unwind(AssertUnwindSafe(|| {
+        handle.engine.get_move_with_stats(&handle.board, stone)
+    }));
+    let Ok(result) = searched else {
+        return GOMOKU_ERR_INTERNAL;
+    };
+    let Some(pos) = result.best_move else {
+        return GOMOKU_NO_MOVE;
+    };
+
+    *out = GomokuMove {
+        row: pos.row,
+        col: pos.col,
+        depth: result.depth,
+        score: result.score,
+        nodes: result.nodes,
+        time_ms: result.time_ms,
+        resign: handle.engine.should_resign(&result),
+    };
+    GOMOKU_OK
+}
+
+/// Ask whether `color` accepts a draw offer in the current position.
+///
+/// Returns 1 to accept, 0 to decline, or a negative error code.
+///
+/// # Safety
+///
+/// `engine` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn gomoku_engine_should_accept_draw(engine: *const GomokuEngine, color: u8) -> i32 {
+    let Some(handle) = engine.as_ref() else {
+        return GOMOKU_ERR_NULL;
+    };
+    let stone = match stone_from_u8(color) {
+        Some(Stone::Empty) | None => return GOMOKU_ERR_INVALID_ARG,
+        Some(stone) => stone,
+    };
+    catch_unwind(AssertUnwindSafe(|| {
+        i32::from(handle.engine.should_accept_draw(&handle.board, stone))
+    }))
+    .unwrap_or(GOMOKU_ERR_INTERNAL)
+}
+
+/// Set the auto-resign threshold: `GomokuMove.resign` is set once the search
+/// score drops to `threshold` or below. Pass `enabled = false` to never resign
+/// (the default).
+///
+/// # Safety
+///
+/// `engine` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn gomoku_engine_set_resign_threshold(
+    engine: *mut GomokuEngine,
+    enabled: bool,
+    threshold: i32,
+) {
+    if let Some(handle) = engine.as_mut() {
+        let _ = catch_unwind(AssertUnwindSafe(|| {
+            handle.engine.set_resign_threshold(enabled.then_some(threshold));
+        }));
+    }
+}
+
+/// Clear the transposition table and history (call between games).
+///
+/// # Safety
+///
+/// `engine` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn gomoku_engine_clear_cache(engine: *mut GomokuEngine) {
+    if let Some(handle) = engine.as_mut() {
+        let _ = catch_unwind(AssertUnwindSafe(|| handle.engine.clear_cache()));
+    }
+}
+
+fn stone_from_u8(value: u8) -> Option<Stone> {
+    match value {
+        GOMOKU_EMPTY => Some(Stone::Empty),
+        GOMOKU_BLACK => Some(Stone::Black),
+        GOMOKU_WHITE => Some(Stone::White),
+        _ => None,
+    }
+}
+
+// Row-major layout of `cells` relies on this.
+const _: () = assert!(GOMOKU_BOARD_CELLS == TOTAL_CELLS && TOTAL_CELLS == BOARD_SIZE * BOARD_SIZE);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ffi_round_trip_finds_win() {
+        let mut cells = [GOMOKU_EMPTY; GOMOKU_BOARD_CELLS];
+        for col in 5..9 {
+            cells[9 * BOARD_SIZE + col] = GOMOKU_BLACK;
+        }
+        cells[9 * BOARD_SIZE + 4] = GOMOKU_WHITE;
+
+        let engine = gomoku_engine_new(4, 200);
+        assert!(!engine.is_null());
+        unsafe {
+            let status = gomoku_engine_set_position(engine, cells.as_ptr(), cells.len(), 0, 0);
+            assert_eq!(status, GOMOKU_OK);
+
+            let mut mv = GomokuMove::default();
+            assert_eq!(gomoku_engine_best_move(engine, GOMOKU_BLACK, &mut mv), GOMOKU_OK);
+            assert_eq!((mv.row, mv.col), (9, 9));
+            assert!(!mv.resign);
+            assert_eq!(gomoku_engine_should_accept_draw(engine, GOMOKU_BLACK), 0);
+
+            // Against an open four White is lost: with a threshold set, the
+            // engine flags resignation
+            let mut lost = [GOMOKU_EMPTY; GOMOKU_BOARD_CELLS];
+            for col in 5..9 {
+                lost[3 * BOARD_SIZE + col] = GOMOKU_BLACK;
+            }
+            lost[12 * BOARD_SIZE + 12] = GOMOKU_WHITE;
+            gomoku_engine_set_position(engine, lost.as_ptr(), lost.len(), 0, 0);
+            gomoku_engine_set_resign_threshold(engine, true, -800_000);
+            assert_eq!(gomoku_engine_best_move(engine, GOMOKU_WHITE, &mut mv), GOMOKU_OK);
+            assert!(mv.resign);
+            assert_eq!(gomoku_engine_should_accept_draw(engine, GOMOKU_WHITE), 1);
+
+            gomoku_engine_free(engine);
+        }
+    }
+
+    #[test]
+    fn test_ffi_rejects_bad_arguments() {
+        let engine = gomoku_engine_new(4, 200);
+        let cells = [GOMOKU_EMPTY; GOMOKU_BOARD_CELLS];
+        let mut bad_cells = cells;
+        bad_cells[0] = 7;
+        let mut mv = GomokuMove::default();
+        unsafe {
+            assert_eq!(
+                gomoku_engine_set_position(engine, cells.as_ptr(), 100, 0, 0),
+                GOMOKU_ERR_INVALID_ARG
+            );
+            assert_eq!(
+                gomoku_engine_set_position(engine, bad_cells.as_ptr(), bad_cells.len(), 0, 0),
+                GOMOKU_ERR_INVALID_ARG
+            );
+            assert_eq!(
+                gomoku_engine_set_position(std::ptr::null_mut(), cells.as_ptr(), cells.len(), 0, 0),
+                GOMOKU_ERR_NULL
+            );
+            assert_eq!(gomoku_engine_best_move(engine, GOMOKU_EMPTY, &mut mv), GOMOKU_ERR_INVALID_ARG);
+            assert_eq!(
+                gomoku_engine_best_move(engine, GOMOKU_BLACK, std::ptr::null_mut()),
+                GOMOKU_ERR_NULL
+            );
+            gomoku_engine_free(engine);
+            gomoku_engine_free(std::ptr::null_mut());
+        }
+    }
+}
@@ -0,0 +1,743 @@
+//! Game state machine: turn order, legality, captures and the result
+//!
+//! A [`Game`] owns the board and the side to move. [`Game::play`] checks
+//! the move against the rules, applies captures, decides the game and
+//! reports what happened as [`GameEvent`]s. The game is drawn when the side
+//! to move has no legal move left.
+//!
+//! A player can also end the game without a move: [`Game::resign`], or
+//! [`Game::offer_draw`] and the opponent's [`Game::accept_draw`]. An offer
+//! stands until the opponent accepts it, declines it or moves.
+//!
+//! Ninuki endgame rule: a five the opponent can break by capturing a pair
+//! out of it does not win at once. The game records it as a
+//! [`PendingFive`]; the opponent has exactly the next move to break it,
+//! and if the line is still standing after that move its holder wins.
+//!
+//! Each game keeps its own [`RuleSet`]: [`Game::new`] plays Ninuki-renju,
+//! [`Game::with_rules`] another variant.
+//!
+//! [`Game::push_move`] returns the [`MoveRecord`] of the move, which undoes
+//! it, captures included, on any board; [`Game::pop_move`] takes back the
+//! last move of the game itself.
+//!
+//! ```
+//! use gomoku::game::{Game, GameEvent, GameOutcome, WinReason};
+//! use gomoku::{Pos, Stone};
+//!
+//! let mut game = Game::new();
+//! for col in 0..4 {
+//!     game.play(Pos::new(9, col)).unwrap();
+//!     game.play(Pos::new(0, col * 2)).unwrap();
+//! }
+//! let events = game.play(Pos::new(9, 4)).unwrap();
+//! let win = GameOutcome::Win { winner: Stone::Black, reason: WinReason::FiveInRow };
+//! assert_eq!(events.last(), Some(&GameEvent::GameOver(win)));
+//! assert!(game.play(Pos::new(5, 5)).is_err());
+//! ```
+
+use crate::board::{Board, Pos, Stone};
+use crate::notation::{notation, Notation};
+use crate::rules::{
+    can_break_five_by_capture, execute_captures, find_five_line_at_pos, find_five_positions,
+    foul, has_five_at_pos, legal_moves_iter, RuleSet,
+};
+
+/// How a game was won
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WinReason {
+    /// An unbroken five in a row
+    FiveInRow,
+    /// Five captured pairs
+    Capture,
+    /// The loser resigned
+    Resignation,
+}
+
+/// Result of a finished game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameOutcome {
+    Win { winner: Stone, reason: WinReason },
+    /// The side to move had no legal move
+    Draw,
+    /// Both players agreed to a draw
+    AgreedDraw,
+}
+
+impl GameOutcome {
+    /// The winner, `None` for a draw
+    #[must_use]
+    pub fn winner(self) -> Option<Stone> {
+        match self {
+            Self::Win { winner, .. } => Some(winner),
+            Self::Draw | Self::AgreedDraw => None,
+        }
+    }
+}
+
+/// A breakable five waiting for the opponent's one chance to break it
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PendingFive {
+    /// Color that made the five
+    pub holder: Stone,
+    /// Stones of the five
+    pub line: Vec<Pos>,
+    /// Number of moves played ([`Game::moves`]) once the chance is used:
+    /// the five must be broken by the move that reaches it
+    pub deadline: usize,
+}
+
+/// One move with everything needed to take it back
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoveRecord {
+    pub pos: Pos,
+    pub color: Stone,
+    /// Opponent stones removed by the move (pairs)
+    pub captured: Vec<Pos>,
+    /// Black's and White's captured pairs before the move
+    pub previous_captures: (u8, u8),
+}
+
+impl MoveRecord {
+    /// Undo the move on `board`, which must be the position right after it:
+    /// removes the stone, puts the captured stones back and restores the
+    /// capture counts.
+    pub fn undo(&self, board: &mut Board) {
+        board.remove_stone(self.pos);
+        for &pos in &self.captured {
+            board.place_stone(pos, self.color.opponent());
+        }
+        let (black, white) = self.previous_captures;
+        board.sub_captures(Stone::Black, board.captures(Stone::Black).saturating_sub(black));
+        board.sub_captures(Stone::White, board.captures(Stone::White).saturating_sub(white));
+    }
+}
+
+/// A played move and the five that was pending before it
+#[derive(Debug, Clone)]
+struct Played {
+    record: MoveRecord,
+    pending_before: Option<PendingFive>,
+}
+
+/// What a move did, in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameEvent {
+    /// A stone was placed
+    MovePlayed { pos: Pos, color: Stone },
+    /// `by` captured the stones at `positions` (pairs)
+    Captured { by: Stone, positions: Vec<Pos> },
+    /// The move made a five the opponent must break next move
+    BreakableFive(PendingFive),
+    /// The move ended the game
+    GameOver(GameOutcome),
+}
+
+/// A game in progress or finished
+#[derive(Debug, Clone)]
+pub struct Game {
+    /// Rules the game is played by
+    rules: RuleSet,
+    board: Board,
+    to_move: Stone,
+    /// Moves played since the start position
+    history: Vec<Played>,
+    /// Five waiting to be broken by the side to move
+    pending_five: Option<PendingFive>,
+    /// Side whose draw offer is standing
+    draw_offer: Option<Stone>,
+    outcome: Option<GameOutcome>,
+    /// Notation of positions in error messages; the process-wide one if unset
+    notation: Option<Notation>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Game {
+    /// A new Ninuki-renju game on the empty board, Black to move.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::from_position(RuleSet::NINUKI, Board::new(), Stone::Black)
+    }
+
+    /// A new game by `rules` on the empty board, Black to move.
+    ///
+    /// ```
+    /// use gomoku::game::Game;
+    /// use gomoku::rules::{Forbidden, RuleSet};
+    ///
+    /// let renju = RuleSet::NINUKI.with_forbidden(Forbidden::Renju);
+    /// let game = Game::with_rules(renju);
+    /// assert_eq!(game.rules(), renju);
+    /// assert_eq!(Game::new().rules(), RuleSet::NINUKI);
+    /// ```
+    #[must_use]
+    pub fn with_rules(rules: RuleSet) -> Self {
+        Self::from_position(rules, Board::new(), Stone::Black)
+    }
+
+    /// Continue by `rules` from an arbitrary position (captures included)
+    /// with `to_move` to play. A five already on the board is treated as
+    /// just made: `to_move` gets one move to break it.
+    #[must_use]
+    pub fn from_position(rules: RuleSet, board: Board, to_move: Stone) -> Self {
+        let holder = to_move.opponent();
+        let pending_five =
+            find_five_positions(rules, &board, holder).map(|line| PendingFive { holder, line, deadline: 1 });
+        Self {
+            rules,
+            board,
+            to_move,
+            history: Vec::new(),
+            pending_five,
+            draw_offer: None,
+            outcome: None,
+            notation: None,
+        }
+    }
+
+    /// Name positions in error messages in `notation` instead of the
+    /// process-wide [`notation()`].
+    ///
+    /// ```
+    /// use gomoku::game::Game;
+    /// use gomoku::notation::{Notation, Origin};
+    /// use gomoku::Pos;
+    ///
+    /// let bottom = Notation { origin: Origin::BottomLeft, skip_i: true };
+    /// let mut game = Game::new().with_notation(bottom);
+    /// game.play(Pos::new(0, 0)).unwrap();
+    /// assert_eq!(game.play(Pos::new(0, 0)).unwrap_err(), "A19 is illegal: occupied");
+    /// ```
+    #[must_use]
+    pub fn with_notation(self, notation: Notation) -> Self {
+        Self { notation: Some(notation), ..self }
+    }
+
+    fn notation(&self) -> Notation {
+        self.notation.unwrap_or_else(notation)
+    }
+
+    /// Start by `rules` from a set-up position: handicap stones or a
+    /// position copied from another game. `captures` is (Black, White) in
+    /// pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `to_move` or a stone is [`Stone::Empty`], a point is off the board or listed
+    /// twice, a side has already reached the capture win, or the side to
+    /// move already has a five (the game would be over).
+    pub fn from_setup(
+        rules: RuleSet,
+        stones: &[(Pos, Stone)],
+        captures: (u8, u8),
+        to_move: Stone,
+    ) -> Result<Self, String> {
+        Self::setup(rules, stones, captures, to_move, None)
+    }
+
+    /// [`Game::from_setup`], naming positions in `notation`, here and in
+    /// the game's later errors ([`Game::with_notation`]).
+    ///
+    /// # Errors
+    ///
+    /// As [`Game::from_setup`].
+    pub fn from_setup_with_notation(
+        rules: RuleSet,
+        stones: &[(Pos, Stone)],
+        captures: (u8, u8),
+        to_move: Stone,
+        notation: Notation,
+    ) -> Result<Self, String> {
+        Self::setup(rules, stones, captures, to_move, Some(notation))
+    }
+
+    fn setup(
+        rules: RuleSet,
+        stones: &[(Pos, Stone)],
+        captures: (u8, u8),
+        to_move: Stone,
+        notation: Option<Notation>,
+    ) -> Result<Self, String> {
+        let name = |pos| notation.unwrap_or_else(crate::notation::notation).format(pos);
+        if to_move == Stone::Empty {
+            return Err("side to move must be Black or White".to_string());
+        }
+        let mut board = Board::new();
+        for &(pos, color) in stones {
+            if !Pos::is_valid(i32::from(pos.row), i32::from(pos.col)) {
+                return Err(format!("({}, {}) is off the board", pos.row, pos.col));
+            }
+            if color == Stone::Empty {
+                return Err(format!("{}: a set-up stone must be Black or White", name(pos)));
+            }
+            if !board.is_empty(pos) {
+                return Err(format!("{} is set up twice", name(pos)));
+            }
+            board.place_stone(pos, color);
+        }
+        for (color, name, pairs) in [(Stone::Black, "Black", captures.0), (Stone::White, "White", captures.1)] {
+            board.set_captures(rules, color, pairs).map_err(|e| format!("{}: {}", name, e))?;
+            if board.captures_to_win(rules, color) == 0 {
+                return Err(format!("{} has already won by capture", name));
+            }
+        }
+        if find_five_positions(rules, &board, to_move).is_some() {
+            return Err("the side to move already has five in a row".to_string());
+        }
+        Ok(Self { notation, ..Self::from_position(rules, board, to_move) })
+    }
+
+    /// Rules the game is played by
+    #[must_use]
+    pub fn rules(&self) -> RuleSet {
+        self.rules
+    }
+
+    #[must_use]
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Side to move (still set after the game has ended)
+    #[must_use]
+    pub fn to_move(&self) -> Stone {
+        self.to_move
+    }
+
+    /// Moves played since the start position
+    #[must_use]
+    pub fn moves(&self) -> Vec<Pos> {
+        self.history.iter().map(|played| played.record.pos).collect()
+    }
+
+    #[must_use]
+    pub fn move_count(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Records of the moves played since the start position, oldest first
+    pub fn history(&self) -> impl Iterator<Item = &MoveRecord> {
+        self.history.iter().map(|played| &played.record)
+    }
+
+    /// The result, once the game is over
+    #[must_use]
+    pub fn outcome(&self) -> Option<GameOutcome> {
+        self.outcome
+    }
+
+    #[must_use]
+    pub fn is_over(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    /// The opponent's five that the side to move must break by capture
+    /// this move, or lose
+    #[must_use]
+    pub fn pending_five(&self) -> Option<&PendingFive> {
+        self.pending_five.as_ref()
+    }
+
+    /// The side whose draw offer is waiting for an answer
+    #[must_use]
+    pub fn draw_offer(&self) -> Option<Stone> {
+        self.draw_offer
+    }
+
+    /// Legal moves for the side to move (none once the game is over)
+    #[must_use]
+    pub fn legal_moves(&self) -> Vec<Pos> {
+        if self.is_over() {
+            return Vec::new();
+        }
+        legal_moves_iter(self.rules, &self.board, self.to_move).collect()
+    }
+
+    /// Play `pos` for the side to move and report what happened.
+    ///
+    /// # Errors
+    ///
+    /// As [`Game::push_move`].
+    pub fn play(&mut self, pos: Pos) -> Result<Vec<GameEvent>, String> {
+        let record = self.push_move(pos)?;
+        let mut events = vec![GameEvent::MovePlayed { pos, color: record.color }];
+        if !record.captured.is_empty() {
+            events.push(GameEvent::Captured { by: record.color, positions: record.captured });
+        }
+        if let Some(pending) = &self.pending_five {
+            events.push(GameEvent::BreakableFive(pending.clone()));
+        }
+        events.extend(self.outcome.map(GameEvent::GameOver));
+        Ok(events)
+    }
+
+    /// Play `pos` for the side to move and return its record.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, leaving the game unchanged, when the game is over
+    /// or the move is off the board, occupied or forbidden (the error names
+    /// the foul: double-three, or under Renju double-four or overline, and
+    /// the point in the game's notation, see [`Game::with_notation`]).
+    pub fn push_move(&mut self, pos: Pos) -> Result<MoveRecord, String> {
+        if self.is_over() {
+            return Err("game is over".to_string());
+        }
+        if !Pos::is_valid(i32::from(pos.row), i32::from(pos.col)) {
+            return Err(format!("({}, {}) is off the board", pos.row, pos.col));
+        }
+        let color = self.to_move;
+        if let Some(why) = why_illegal(self.rules, &self.board, pos, color) {
+            return Err(format!("{} is illegal: {}", self.notation().format(pos), why));
+        }
+
+        let previous_captures = (self.board.captures(Stone::Black), self.board.captures(Stone::White));
+        self.board.place_stone(pos, color);
+        let captured = execute_captures(self.rules, &mut self.board, pos, color);
+        let record = MoveRecord { pos, color, captured, previous_captures };
+        let pending_before = self.pending_five.take();
+        self.history.push(Played { record: record.clone(), pending_before: pending_before.clone() });
+        self.to_move = color.opponent();
+        // Moving instead of answering declines the opponent's offer
+        if self.draw_offer == Some(color.opponent()) {
+            self.draw_offer = None;
+        }
+        self.outcome = self.decide(pos, color, pending_before);
+        Ok(record)
+    }
+
+    /// Take back the last move, captures included, and with it a
+    /// resignation or draw agreement that followed it. Returns its record,
+    /// or `None` at the start position.
+    pub fn pop_move(&mut self) -> Option<MoveRecord> {
+        let played = self.history.pop()?;
+        played.record.undo(&mut self.board);
+        self.to_move = played.record.color;
+        self.pending_five = played.pending_before;
+        self.draw_offer = None;
+        self.outcome = None;
+        Some(played.record)
+    }
+
+    /// `color` resigns, on either side's turn; the opponent wins.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the game is over or `color` is
+    /// [`Stone::Empty`].
+    pub fn resign(&mut self, color: Stone) -> Result<GameOutcome, String> {
+        self.check_player(color)?;
+        let outcome = GameOutcome::Win { winner: color.opponent(), reason: WinReason::Resignation };
+        self.draw_offer = None;
+        self.outcome = Some(outcome);
+        Ok(outcome)
+    }
+
+    /// `color` offers a draw, on either side's turn. The offer stands
+    /// until the opponent accepts it, declines it or moves.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the game is over, `color` is
+    /// [`Stone::Empty`] or an offer is already standing.
+    pub fn offer_draw(&mut self, color: Stone) -> Result<(), String> {
+        self.check_player(color)?;
+        if self.draw_offer.is_some() {
+            return Err("a draw offer is already standing".to_string());
+        }
+        self.draw_offer = Some(color);
+        Ok(())
+    }
+
+    /// `color` accepts the opponent's draw offer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the game is over, `color` is
+    /// [`Stone::Empty`] or the opponent has no standing offer.
+    pub fn accept_draw(&mut self, color: Stone) -> Result<GameOutcome, String> {
+        self.check_player(color)?;
+        if self.draw_offer != Some(color.opponent()) {
+            return Err("no draw offer to accept".to_string());
+        }
+        self.draw_offer = None;
+        self.outcome = Some(GameOutcome::AgreedDraw);
+        Ok(GameOutcome::AgreedDraw)
+    }
+
+    /// `color` declines the opponent's draw offer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the game is over, `color` is
+    /// [`Stone::Empty`] or the opponent has no standing offer.
+    pub fn decline_draw(&mut self, color: Stone) -> Result<(), String> {
+        self.check_player(color)?;
+        if self.draw_offer != Some(color.opponent()) {
+            return Err("no draw offer to decline".to_string());
+        }
+        self.draw_offer = None;
+        Ok(())
+    }
+
+    fn check_player(&self, color: Stone) -> Result<(), String> {
+        if self.is_over() {
+            return Err("game is over".to_string());
+        }
+        if color == Stone::Empty {
+            return Err("only Black or White can do that".to_string());
+        }
+        Ok(())
+    }
+
+    /// Result after `color` played `pos` (captures applied), given the
+    /// five `color` had to break. Sets `pending_five` for a new breakable
+    /// five. In order:
+    /// 1. The mover reaching 5 captured pairs wins.
+    /// 2. An opponent five the move did not break wins for the opponent.
+    /// 3. A five made by the move wins unless the opponent can break it.
+    fn decide(&mut self, pos: Pos, color: Stone, pending: Option<PendingFive>) -> Option<GameOutcome> {
+        let win = |winner, reason| Some(GameOutcome::Win { winner, reason });
+        if self.board.captures_to_win(self.rules, color) == 0 {
+            return win(color, WinReason::Capture);
+        }
+        if let Some(pending) = pending {
+            if pending.line.iter().all(|&p| self.board.get(p) == pending.holder) {
+                return win(pending.holder, WinReason::FiveInRow);
+            }
+        }
+        if has_five_at_pos(self.rules, &self.board, pos, color) {
+            if let Some(line) = find_five_line_at_pos(self.rules, &self.board, pos, color) {
+                if !can_break_five_by_capture(self.rules, &self.board, &line, color) {
+                    return win(color, WinReason::FiveInRow);
+                }
+                let deadline = self.history.len() + 1;
+                self.pending_five = Some(PendingFive { holder: color, line, deadline });
+            }
+        }
+        if legal_moves_iter(self.rules, &self.board, self.to_move).next().is_none() {
+            return Some(GameOutcome::Draw);
+        }
+        None
+    }
+}
+
+/// Why `color` may not play `pos` on `board` under `rules`: occupied or the
+/// foul it makes; `None` for a legal move
+pub(crate) fn why_illegal(rules: RuleSet, board: &Board, pos: Pos, color: Stone) -> Option<String> {
+    if !board.is_empty(pos) {
+        return Some("occupied".to_string());
+    }
+    foul(rules, board, pos, color).map(|foul| format!("forbidden {}", foul.to_string().to_ascii_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play_all(game: &mut Game, moves: &[(u8, u8)]) {
+        for &(r, c) in moves {
+            game.play(Pos::new(r, c)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_turns_captures_and_illegal_moves() {
+        let mut game = Game::new();
+        // Black J10, White K10 L10, Black M10 captures
+        play_all(&mut game, &[(9, 8), (9, 9), (0, 0), (9, 10)]);
+        let events = game.play(Pos::new(9, 11)).unwrap();
+        assert_eq!(events[0], GameEvent::MovePlayed { pos: Pos::new(9, 11), color: Stone::Black });
+        let GameEvent::Captured { by, positions } = &events[1] else { panic!("{:?}", events) };
+        assert_eq!(*by, Stone::Black);
+        assert!(positions.len() == 2 && positions.contains(&Pos::new(9, 9)) && positions.contains(&Pos::new(9, 10)));
+        assert_eq!(events.len(), 2);
+        assert_eq!(game.board().captures(Stone::Black), 1);
+        assert_eq!(game.to_move(), Stone::White);
+        assert_eq!(game.move_count(), 5);
+
+        assert!(game.play(Pos::new(0, 0)).unwrap_err().contains("occupied"));
+        assert!(game.play(Pos { row: 19, col: 0 }).is_err());
+        assert_eq!(game.to_move(), Stone::White, "Rejected moves keep the turn");
+    }
+
+    #[test]
+    fn test_forbidden_move_is_rejected() {
+        // Black J10 K10 and M8 M9: M10 would make two open threes
+        let mut game = Game::new();
+        play_all(&mut game, &[(9, 8), (0, 0), (9, 9), (0, 2), (7, 11), (0, 4), (8, 11), (0, 6)]);
+        let error = game.play(Pos::new(9, 11)).unwrap_err();
+        assert_eq!(error, "M10 is illegal: forbidden double-three");
+        assert!(game.legal_moves().iter().all(|&p| p != Pos::new(9, 11)));
+    }
+
+    #[test]
+    fn test_games_keep_their_own_rules() {
+        // Black A10 B10 C10 E10 F10: D10 makes six in a row
+        let moves = [(9, 0), (0, 0), (9, 1), (0, 2), (9, 2), (0, 4), (9, 4), (0, 6), (9, 5), (0, 8)];
+        let mut renju = Game::with_rules(RuleSet::RENJU);
+        let mut ninuki = Game::new();
+        play_all(&mut renju, &moves);
+        play_all(&mut ninuki, &moves);
+
+        assert_eq!(renju.play(Pos::new(9, 3)).unwrap_err(), "D10 is illegal: forbidden overline");
+        assert!(ninuki.play(Pos::new(9, 3)).is_ok());
+        assert!(RuleSet::NINUKI.with_captures(0, 5).is_err());
+    }
+
+    #[test]
+    fn test_breakable_five_gives_one_chance() {
+        // Black F10-J10 five-to-be; White pair on column K flanked so that
+        // Black's K10 stone of the five can be captured: K9 White, K11 Black
+        let mut board = Board::new();
+        for col in 5..9 {
+            board.place_stone(Pos::new(9, col), Stone::Black);
+        }
+        board.place_stone(Pos::new(8, 9), Stone::White);
+        board.place_stone(Pos::new(10, 9), Stone::Black);
+        let mut game = Game::from_position(RuleSet::NINUKI, board.clone(), Stone::Black);
+
+        // K10 completes a five that White can break by capturing K10-K11 at K12
+        let events = game.play(Pos::new(9, 9)).unwrap();
+        assert!(!game.is_over(), "Breakable five does not win yet: {:?}", events);
+        let pending = game.pending_five().unwrap().clone();
+        assert_eq!(pending.holder, Stone::Black);
+        assert_eq!(pending.deadline, 2);
+        assert!(pending.line.contains(&Pos::new(9, 9)) && pending.line.len() >= 5);
+        assert_eq!(events.last(), Some(&GameEvent::BreakableFive(pending)));
+
+        // White plays elsewhere: Black wins
+        let events = game.play(Pos::new(0, 0)).unwrap();
+        let win = GameOutcome::Win { winner: Stone::Black, reason: WinReason::FiveInRow };
+        assert_eq!(events.last(), Some(&GameEvent::GameOver(win)));
+        assert_eq!(game.outcome().and_then(GameOutcome::winner), Some(Stone::Black));
+        assert!(game.legal_moves().is_empty());
+
+        // Taking back the losing move reopens the game with the five pending
+        game.pop_move().unwrap();
+        assert!(!game.is_over());
+        assert_eq!(game.pending_five().map(|p| p.holder), Some(Stone::Black));
+
+        // White breaks the five instead
+        let mut game = Game::from_position(RuleSet::NINUKI, board, Stone::Black);
+        game.play(Pos::new(9, 9)).unwrap();
+        let events = game.play(Pos::new(11, 9)).unwrap();
+        assert!(matches!(events[1], GameEvent::Captured { by: Stone::White, .. }));
+        assert!(!game.is_over());
+        assert!(game.pending_five().is_none());
+    }
+
+    #[test]
+    fn test_five_on_the_start_position_is_pending() {
+        let mut board = Board::new();
+        for col in 0..5 {
+            board.place_stone(Pos::new(9, col), Stone::White);
+        }
+        let mut game = Game::from_position(RuleSet::NINUKI, board, Stone::Black);
+        assert_eq!(game.pending_five().map(|p| (p.holder, p.deadline)), Some((Stone::White, 1)));
+        game.play(Pos::new(0, 0)).unwrap();
+        assert_eq!(game.outcome().and_then(GameOutcome::winner), Some(Stone::White));
+    }
+
+    #[test]
+    fn test_from_setup() {
+        // Three handicap stones, White to move, Black one pair ahead
+        let stones = [(Pos::new(9, 9), Stone::Black), (Pos::new(3, 3), Stone::Black), (Pos::new(15, 15), Stone::Black)];
+        let game = Game::from_setup(RuleSet::NINUKI, &stones, (1, 0), Stone::White).unwrap();
+        assert_eq!(game.to_move(), Stone::White);
+        assert_eq!(game.board().stone_count(), 3);
+        assert_eq!(game.board().captures(Stone::Black), 1);
+        assert_eq!(game.move_count(), 0);
+
+        assert!(Game::from_setup(RuleSet::NINUKI, &stones, (0, 0), Stone::Empty).is_err());
+        let twice = [(Pos::new(9, 9), Stone::Black), (Pos::new(9, 9), Stone::White)];
+        assert!(Game::from_setup(RuleSet::NINUKI, &twice, (0, 0), Stone::Black).unwrap_err().contains("twice"));
+        assert!(Game::from_setup(RuleSet::NINUKI, &[(Pos::new(9, 9), Stone::Empty)], (0, 0), Stone::Black).is_err());
+        assert!(Game::from_setup(RuleSet::NINUKI, &stones, (0, 5), Stone::Black).unwrap_err().contains("White"));
+
+        // A five of the side to move means the game is over already
+        let five: Vec<_> = (0..5).map(|col| (Pos::new(9, col), Stone::Black)).collect();
+        assert!(Game::from_setup(RuleSet::NINUKI, &five, (0, 0), Stone::Black).unwrap_err().contains("five"));
+        assert!(Game::from_setup(RuleSet::NINUKI, &five, (0, 0), Stone::White).unwrap().pending_five().is_some());
+    }
+
+    #[test]
+    fn test_pop_move_restores_captures() {
+        let mut game = Game::new();
+        play_all(&mut game, &[(9, 8), (9, 9), (0, 0), (9, 10)]);
+        let before = game.board().clone();
+        let record = game.push_move(Pos::new(9, 11)).unwrap();
+        assert_eq!(record.captured.len(), 2);
+        assert_eq!(record.previous_captures, (0, 0));
+
+        // The record undoes the move on a copy of the board
+        let mut board = game.board().clone();
+        record.undo(&mut board);
+        assert_eq!((board.bitboards(), board.captures(Stone::Black)), (before.bitboards(), 0));
+
+        assert_eq!(game.pop_move(), Some(record));
+        assert_eq!(game.board().bitboards(), before.bitboards());
+        assert_eq!(game.board().captures(Stone::Black), 0);
+        assert_eq!(game.to_move(), Stone::Black);
+        assert_eq!(game.moves(), vec![Pos::new(9, 8), Pos::new(9, 9), Pos::new(0, 0), Pos::new(9, 10)]);
+        assert_eq!(game.history().count(), 4);
+    }
+
+    #[test]
+    fn test_capture_win() {
+        let mut board = Board::new();
+        board.set_captures(RuleSet::NINUKI, Stone::Black, 4).unwrap();
+        board.place_stone(Pos::new(9, 8), Stone::Black);
+        board.place_stone(Pos::new(9, 9), Stone::White);
+        board.place_stone(Pos::new(9, 10), Stone::White);
+        let mut game = Game::from_position(RuleSet::NINUKI, board, Stone::Black);
+        let events = game.play(Pos::new(9, 11)).unwrap();
+        assert_eq!(
+            game.outcome(),
+            Some(GameOutcome::Win { winner: Stone::Black, reason: WinReason::Capture })
+        );
+        assert_eq!(events.len(), 3);
+    }
+
+    #[test]
+    fn test_resign_and_draw_agreement() {
+        let mut game = Game::new();
+        play_all(&mut game, &[(9, 9)]);
+        let won = GameOutcome::Win { winner: Stone::Black, reason: WinReason::Resignation };
+        assert_eq!(game.resign(Stone::White), Ok(won));
+        assert_eq!(game.outcome(), Some(won));
+        assert!(game.play(Pos::new(9, 10)).is_err());
+        assert!(game.offer_draw(Stone::White).is_err());
+        // Taking back the move takes back the resignation
+        game.pop_move();
+        assert!(!game.is_over());
+
+        play_all(&mut game, &[(9, 9)]);
+        game.offer_draw(Stone::Black).unwrap();
+        assert!(game.offer_draw(Stone::White).is_err());
+        assert!(game.accept_draw(Stone::Black).is_err(), "Own offer");
+        game.decline_draw(Stone::White).unwrap();
+        game.offer_draw(Stone::Black).unwrap();
+        // White moves instead of answering
+        play_all(&mut game, &[(9, 10)]);
+        assert_eq!(game.draw_offer(), None);
+        assert!(game.accept_draw(Stone::White).is_err());
+
+        game.offer_draw(Stone::Black).unwrap();
+        play_all(&mut game, &[(10, 10)]);
+        assert_eq!(game.draw_offer(), Some(Stone::Black), "The offer stands through the offerer's own move");
+        assert_eq!(game.accept_draw(Stone::White), Ok(GameOutcome::AgreedDraw));
+        assert_eq!(game.outcome().and_then(GameOutcome::winner), None);
+        assert!(game.is_over());
+    }
+}
@@ -15,10 +15,54 @@
 //! - [`eval`]: Position evaluation and heuristics
 //! - [`search`]: Search algorithms (alpha-beta, VCF/VCT)
 //! - [`engine`]: Main AI engine integrating all components
+//! - [`error`]: Error type of the fallible board API
+//! - [`prelude`]: The board, rules, evaluation, threat and search types in one import
+//! - [`game`]: Game state machine (turns, legality, captures, result)
+//! - [`clock`]: Game clocks and per-move time allocation
+//! - [`testsuite`]: Puzzle/tactics regression suite
+//! - [`record`]: Game records in SGF or move-list form
+//! - [`archive`]: Games database indexed by position, with an openings report
+//! - [`explorer`]: Opening explorer over the archive and the opening book
+//! - [`report`]: Post-game summary (accuracy, blunders, captures, search) as Markdown or JSON
+//! - [`notation`]: Coordinate notation (`K10`) and its configuration
+//! - [`bench`]: Fixed-depth node-count benchmark
+//! - [`annotate`]: Move-quality annotation (blunder detection) of played games
+//! - [`tune`]: SPSA self-play tuning of the search parameters
+//! - [`play`]: Terminal games against the engine (`gomoku-cli play`)
+//! - [`pool`]: Pre-built engines lent out per game or request
+//! - [`logging`]: Leveled engine logging to a configurable sink (off by default)
+//! - [`time`]: Time sources of searches (system, manual, none)
+//! - `conformance`: Rule conformance suite (feature `conformance`)
+//! - `ffi`: C ABI with a generated header (feature `ffi`)
+//! - `render`: Headless board images, PNG per move and animated GIF (feature `render`)
+//! - `bridge`: Unattended online play through a JSON line protocol (feature `bridge`)
+//! - `server`: HTTP/JSON engine server (feature `server`)
+//! - `tui`: Full-screen terminal front-end on ratatui (feature `tui`)
+//! - `testing`: proptest playouts and rule properties for downstream tests (feature `proptest`)
+//! - `wasm`: JavaScript bindings (feature `wasm`)
+//! - `ui`: egui desktop GUI (feature `ui`)
+//!
+//! # Cargo features
+//!
+//! The defaults are `std` and `ui`. Only the `gomoku` GUI binary needs
+//! `ui`; a crate embedding the engine depends on it with
+//! `default-features = false, features = ["std"]` (plus `server`, `ffi`,
+//! `serde`, ... as needed) and never compiles egui or eframe.
+//!
+//! # `no_std`
+//!
+//! Everything above is on by default through the `std` and `ui` features.
+//! With `default-features = false` only `board`, `rules`, `eval`, `search`,
+//! `notation` and `error` are built, as `no_std` with `alloc`, for embedded
+//! or sandboxed hosts: searches then run single-threaded, and without a
+//! clock a time limit is spent as nodes ([`time::NODES_PER_MS`]) unless the
+//! host supplies one ([`Searcher::set_clock`](search::Searcher::set_clock)).
+//! Saving and loading tables and parameter files need `std`.
 //!
 //! # Quick Start
 //!
 //! ```
+//! # #[cfg(feature = "std")] {
 //! use gomoku::{AIEngine, Board, Stone, Pos};
 //!
 //! // Create a new game with faster config for doc test
@@ -33,6 +77,7 @@
 //!     board.place_stone(pos, Stone::White);
 //!     println!("AI plays at ({}, {})", pos.row, pos.col);
 //! }
+//! # }
 //! ```
 //!
 //! # Search Priority
@@ -51,13 +96,83 @@
 //! - Transposition table for avoiding redundant searches
 //! - Move ordering for better pruning
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod annotate;
+#[cfg(feature = "std")]
+pub mod archive;
+#[cfg(feature = "std")]
+pub mod bench;
+#[cfg(feature = "bridge")]
+pub mod bridge;
 pub mod board;
+#[cfg(feature = "std")]
+pub mod clock;
+#[cfg(feature = "conformance")]
+pub mod conformance;
+#[cfg(feature = "std")]
 pub mod engine;
+pub mod error;
 pub mod eval;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod explorer;
+#[cfg(feature = "std")]
+pub mod game;
+#[cfg(feature = "std")]
+pub mod logging;
+pub mod notation;
+#[cfg(feature = "std")]
+pub mod play;
+#[cfg(feature = "std")]
+pub mod pool;
+pub mod prelude;
+#[cfg(feature = "render")]
+pub mod render;
+#[cfg(feature = "std")]
+pub mod record;
+#[cfg(feature = "std")]
+pub mod report;
 pub mod rules;
 pub mod search;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "std")]
+pub mod testsuite;
+#[cfg(all(feature = "std", any(test, feature = "proptest")))]
+pub mod testing;
+pub mod time;
+#[cfg(feature = "tui")]
+pub mod tui;
+#[cfg(feature = "std")]
+pub mod tune;
+#[cfg(feature = "ui")]
 pub mod ui;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// What the `std` prelude would supply, for the modules that also build
+/// without it
+#[cfg(not(feature = "std"))]
+mod alloc_prelude {
+    pub(crate) use alloc::boxed::Box;
+    pub(crate) use alloc::format;
+    pub(crate) use alloc::string::{String, ToString};
+    pub(crate) use alloc::vec;
+    pub(crate) use alloc::vec::Vec;
+}
 
 // Re-export commonly used types for convenience
 pub use board::{Board, Pos, Stone, BOARD_SIZE};
-pub use engine::{AIEngine, MoveResult, SearchType, ai_log, pos_to_notation};
+pub use eval::{capture_score, evaluate, PatternScore};
+pub use rules::{Threat, ThreatKind};
+pub use search::{ThreatResult, ThreatSearcher};
+#[cfg(feature = "std")]
+pub use engine::{classify_position, AIEngine, Hint, HintReason, MoveResult, PositionClass, SearchType, pos_to_notation};
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub use engine::SearchHandle;
+pub use error::GomokuError;
@@ -0,0 +1,235 @@
+//! Engine logging
+//!
+//! The engine reports its move pipeline (opening book, threats, VCF, search
+//! statistics) and the GUI reports game events through this module. Nothing
+//! is logged until a sink is installed, so embedding the library never
+//! writes files or prints on its own.
+//!
+//! ```no_run
+//! use gomoku::logging::{self, FileSink, LogLevel};
+//!
+//! // Everything down to per-stage search details, appended to a file
+//! logging::set_sink(LogLevel::Debug, FileSink::append("gomoku_ai.log").unwrap());
+//!
+//! // Any closure is a sink
+//! logging::set_sink(LogLevel::Info, |level: LogLevel, msg: &str| println!("[{}] {}", level, msg));
+//!
+//! // Back to silence
+//! logging::disable();
+//! ```
+//!
+//! With the `log` feature, [`LogCrateSink`] forwards messages to the `log`
+//! facade (target `gomoku`), and from there to `env_logger`, `tracing`, etc.
+
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, RwLock};
+
+/// Message severity, most severe first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    /// Something went wrong
+    Error = 1,
+    /// Suspicious or lost positions
+    Warn = 2,
+    /// Game events and one summary line per engine move
+    Info = 3,
+    /// Per-stage details of the move pipeline
+    Debug = 4,
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Error => "ERROR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+        })
+    }
+}
+
+/// Destination for log messages.
+///
+/// Implemented for closures taking `(LogLevel, &str)`.
+pub trait LogSink: Send + Sync {
+    /// Write one message (no trailing newline).
+    fn log(&self, level: LogLevel, msg: &str);
+}
+
+impl<F: Fn(LogLevel, &str) + Send + Sync> LogSink for F {
+    fn log(&self, level: LogLevel, msg: &str) {
+        self(level, msg);
+    }
+}
+
+/// Writes messages to stderr.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StderrSink;
+
+impl LogSink for StderrSink {
+    fn log(&self, _level: LogLevel, msg: &str) {
+        eprintln!("{}", msg);
+    }
+}
+
+/// Appends messages to a file, flushing after each one.
+#[derive(Debug)]
+pub struct FileSink {
+    file: Mutex<File>,
+}
+
+impl FileSink {
+    /// Open (or create) `path` for appending.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from opening the file.
+    pub fn append(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { file: Mutex::new(file) })
+    }
+}
+
+impl LogSink for FileSink {
+    fn log(&self, _level: LogLevel, msg: &str) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = writeln!(file, "{}", msg);
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Forwards messages to the `log` crate under target `gomoku`.
+#[cfg(feature = "log")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogCrateSink;
+
+#[cfg(feature = "log")]
+impl LogSink for LogCrateSink {
+    fn log(&self, level: LogLevel, msg: &str) {
+        let level = match level {
+            LogLevel::Error => log::Level::Error,
+            LogLevel::Warn => log::Level::Warn,
+            LogLevel::Info => log::Level::Info,
+            LogLevel::Debug => log::Level::Debug,
+        };
+        log::log!(target: "gomoku", level, "{}", msg);
+    }
+}
+
+/// Most verbose level logged; 0 = off. Checked before formatting.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+static SINK: RwLock<Option<Box<dyn LogSink>>> = RwLock::new(None);
+
+/// Send messages at `level` and more severe to `sink`, replacing any
+/// previous sink.
+pub fn set_sink(level: LogLevel, sink: impl LogSink + 'static) {
+    if let Ok(mut current) = SINK.write() {
+        *current = Some(Box::new(sink));
+    }
+    MAX_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Change the level of the installed sink; `None` stops logging but keeps
+/// the sink.
+pub fn set_level(level: Option<LogLevel>) {
+    MAX_LEVEL.store(level.map_or(0, |l| l as u8), Ordering::Relaxed);
+}
+
+/// Stop logging and drop the sink (closing its file, if any).
+pub fn disable() {
+    MAX_LEVEL.store(0, Ordering::Relaxed);
+    if let Ok(mut current) = SINK.write() {
+        *current = None;
+    }
+}
+
+/// Whether a message at `level` would be written.
+#[inline]
+#[must_use]
+pub fn enabled(level: LogLevel) -> bool {
+    level as u8 <= MAX_LEVEL.load(Ordering::Relaxed)
+}
+
+/// Write `msg` at `level` if enabled.
+pub fn log(level: LogLevel, msg: &str) {
+    if !enabled(level) {
+        return;
+    }
+    if let Ok(sink) = SINK.read() {
+        if let Some(sink) = sink.as_ref() {
+            sink.log(level, msg);
+        }
+    }
+}
+
+/// `format!`-style logging at a level; arguments are only formatted when
+/// the level is enabled.
+macro_rules! log_at {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::logging::enabled($level) {
+            $crate::logging::log($level, &format!($($arg)*));
+        }
+    };
+}
+
+macro_rules! log_warn {
+    ($($arg:tt)*) => { $crate::logging::log_at!($crate::logging::LogLevel::Warn, $($arg)*) };
+}
+
+macro_rules! log_info {
+    ($($arg:tt)*) => { $crate::logging::log_at!($crate::logging::LogLevel::Info, $($arg)*) };
+}
+
+macro_rules! log_debug {
+    ($($arg:tt)*) => { $crate::logging::log_at!($crate::logging::LogLevel::Debug, $($arg)*) };
+}
+
+pub(crate) use {log_at, log_debug, log_info, log_warn};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_level_order() {
+        assert!(LogLevel::Error < LogLevel::Warn);
+        assert!(LogLevel::Info < LogLevel::Debug);
+        assert_eq!(LogLevel::Warn.to_string(), "WARN");
+    }
+
+    #[test]
+    fn test_sink_receives_enabled_levels_only() {
+        // The logger is global and other tests log concurrently, so only
+        // look for this test's own messages.
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let captured = Arc::clone(&seen);
+        set_sink(LogLevel::Info, move |level: LogLevel, msg: &str| {
+            if msg.starts_with("logging-test") {
+                captured.lock().unwrap().push((level, msg.to_string()));
+            }
+        });
+
+        log_info!("logging-test {}", 1);
+        log_debug!("logging-test {}", 2);
+        log_warn!("logging-test {}", 3);
+        set_level(None);
+        log_warn!("logging-test {}", 4);
+        disable();
+        log_warn!("logging-test {}", 5);
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                (LogLevel::Info, "logging-test 1".to_string()),
+                (LogLevel::Warn, "logging-test 3".to_string()),
+            ]
+        );
+    }
+}
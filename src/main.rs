@@ -1,10 +1,23 @@
 //! Gomoku AI Engine GUI
 //!
 //! A graphical interface for playing Gomoku with AI or against another player.
+//!
+//! The command-line tools (bench, annotate, play, ...) are the `gomoku-cli`
+//! binary, which needs no GUI.
 
+use gomoku::logging::{self, FileSink, LogLevel, LogSink, StderrSink};
 use gomoku::ui::GomokuApp;
 
 fn main() -> Result<(), eframe::Error> {
+    // Engine decisions go to stderr and, when writable, gomoku_ai.log
+    let file = FileSink::append("gomoku_ai.log").ok();
+    logging::set_sink(LogLevel::Debug, move |level: LogLevel, msg: &str| {
+        if let Some(file) = &file {
+            file.log(level, msg);
+        }
+        StderrSink.log(level, msg);
+    });
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1000.0, 750.0])
@@ -19,3 +32,4 @@ fn main() -> Result<(), eframe::Error> {
         Box::new(|cc| Ok(Box::new(GomokuApp::new(cc)))),
     )
 }
+
@@ -0,0 +1,285 @@
+//! Board coordinate notation (`K10`)
+//!
+//! A point is written as a column letter and a row number. Conventions
+//! differ between programs: which corner is `A1`, and whether the letter
+//! `I` is skipped. A [`Notation`] describes one convention and both formats
+//! and parses with it.
+//!
+//! [`pos_to_notation`](crate::pos_to_notation) and [`Pos::from_notation`]
+//! use the process-wide notation the GUI sets with [`set_notation`], and so
+//! do engine logs and displays. Everything else takes a [`Notation`]
+//! explicitly: the record and report readers and writers, and the record
+//! replays (`*_with_notation`, with [`Notation::DEFAULT`] otherwise, which
+//! puts row 1 at `pos.row == 0` and skips `I`), terminal play, and the
+//! server and bridge configurations.
+//!
+//! Error messages that name a point fall back to the process-wide notation
+//! unless given one: a [`Game`](crate::game::Game) made
+//! [`with_notation`](crate::game::Game::with_notation) (or
+//! [`from_setup_with_notation`](crate::game::Game::from_setup_with_notation)),
+//! [`infer_move_with_notation`](crate::rules::infer_move_with_notation) and
+//! [`GomokuError::to_string_with_notation`](crate::GomokuError::to_string_with_notation).
+//! Record replays, terminal play, the server and the bridge pass theirs. The
+//! binaries take it as `--notation`, spelled as [`Notation`]'s `FromStr`
+//! reads it: the corner of `A1` (`top-left`, `bottom-left`, `top-right`,
+//! `bottom-right`), with `+i` when the letter `I` is used.
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! use gomoku::notation::{self, Notation, Origin};
+//! use gomoku::record::GameRecord;
+//! use gomoku::{pos_to_notation, Pos};
+//!
+//! assert_eq!(pos_to_notation(Pos::new(0, 8)), "J1");
+//!
+//! // Bridge to a program that counts rows from the bottom and uses I
+//! notation::set_notation(Notation { origin: Origin::BottomLeft, skip_i: false });
+//! assert_eq!(pos_to_notation(Pos::new(0, 8)), "I19");
+//! assert_eq!(Pos::from_notation("I19"), Ok(Pos::new(0, 8)));
+//!
+//! // A saved game doesn't change unless asked to
+//! let record = GameRecord::parse("J1").unwrap();
+//! assert_eq!(record.moves, [Pos::new(0, 8)]);
+//! assert_eq!(record.to_move_list(), "J1");
+//!
+//! let bottom: Notation = "bottom-left+i".parse().unwrap();
+//! assert_eq!(record.to_move_list_with_notation(bottom), "I19");
+//! assert_eq!(bottom.to_string(), "bottom-left+i");
+//! # }
+//! ```
+
+use core::fmt;
+use core::str::FromStr;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::board::{Pos, BOARD_SIZE};
+
+/// Corner of `A1`. `pos.row == 0` is the top edge (as the GUI draws the
+/// board and SGF counts rows) and `pos.col == 0` the left edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Origin {
+    /// Row 1 is `pos.row == 0`
+    #[default]
+    TopLeft,
+    /// Row 1 is the last row, as on printed Go and Gomoku diagrams
+    BottomLeft,
+    TopRight,
+    BottomRight,
+}
+
+impl Origin {
+    pub const ALL: [Self; 4] = [Self::TopLeft, Self::BottomLeft, Self::TopRight, Self::BottomRight];
+
+    /// Display name
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::TopLeft => "Top left",
+            Self::BottomLeft => "Bottom left",
+            Self::TopRight => "Top right",
+            Self::BottomRight => "Bottom right",
+        }
+    }
+
+    /// Spelling in a notation spec (`bottom-left`)
+    fn spec(self) -> &'static str {
+        match self {
+            Self::TopLeft => "top-left",
+            Self::BottomLeft => "bottom-left",
+            Self::TopRight => "top-right",
+            Self::BottomRight => "bottom-right",
+        }
+    }
+
+    fn rows_down(self) -> bool {
+        matches!(self, Self::TopLeft | Self::TopRight)
+    }
+
+    fn columns_right(self) -> bool {
+        matches!(self, Self::TopLeft | Self::BottomLeft)
+    }
+}
+
+/// A coordinate convention
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Notation {
+    /// Where `A1` is
+    pub origin: Origin,
+    /// Letters skip `I` (`A`-`T`); otherwise columns are `A`-`S`
+    pub skip_i: bool,
+}
+
+impl Default for Notation {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Notation {
+    /// The engine's own convention
+    pub const DEFAULT: Self = Self { origin: Origin::TopLeft, skip_i: true };
+
+    /// Letter of board column `col`
+    #[must_use]
+    pub fn column_letter(self, col: u8) -> char {
+        let index = if self.origin.columns_right() { col } else { BOARD_SIZE as u8 - 1 - col };
+        let skip = u8::from(self.skip_i && index >= 8);
+        (b'A' + index + skip) as char
+    }
+
+    /// Number (1-based) of board row `row`
+    #[must_use]
+    pub fn row_number(self, row: u8) -> u8 {
+        if self.origin.rows_down() { row + 1 } else { BOARD_SIZE as u8 - row }
+    }
+
+    /// Last column letter (`T`, or `S` when `I` is used)
+    fn last_letter(self) -> char {
+        self.column_letter(if self.origin.columns_right() { BOARD_SIZE as u8 - 1 } else { 0 })
+    }
+
+    /// `pos` in this notation
+    #[must_use]
+    pub fn format(self, pos: Pos) -> String {
+        format!("{}{}", self.column_letter(pos.col), self.row_number(pos.row))
+    }
+
+    /// Parse a point in this notation (letter in either case).
+    ///
+    /// # Errors
+    ///
+    /// Returns a message saying what is wrong with `s`.
+    pub fn parse(self, s: &str) -> Result<Pos, String> {
+        let s = s.trim();
+        let mut chars = s.chars();
+        let letter = chars.next().ok_or("empty move")?.to_ascii_uppercase();
+        if !letter.is_ascii_uppercase() {
+            return Err(format!("'{}': expected a column letter A-{}", s, self.last_letter()));
+        }
+        if letter == 'I' && self.skip_i {
+            return Err(format!("'{}': there is no column I (H is followed by J)", s));
+        }
+        let index = letter as u8 - b'A' - u8::from(self.skip_i && letter > 'I');
+        if index >= BOARD_SIZE as u8 {
+            return Err(format!("'{}': column {} is off the board (A-{})", s, letter, self.last_letter()));
+        }
+        let number: u8 = chars
+            .as_str()
+            .parse()
+            .map_err(|_| format!("'{}': expected a row number after the column", s))?;
+        if !(1..=BOARD_SIZE as u8).contains(&number) {
+            return Err(format!("'{}': row {} is off the board (1-{})", s, number, BOARD_SIZE));
+        }
+        let col = if self.origin.columns_right() { index } else { BOARD_SIZE as u8 - 1 - index };
+        let row = if self.origin.rows_down() { number - 1 } else { BOARD_SIZE as u8 - number };
+        Ok(Pos::new(row, col))
+    }
+
+    const fn to_bits(self) -> u8 {
+        let origin = match self.origin {
+            Origin::TopLeft => 0,
+            Origin::BottomLeft => 1,
+            Origin::TopRight => 2,
+            Origin::BottomRight => 3,
+        };
+        origin | if self.skip_i { 4 } else { 0 }
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        Self { origin: Origin::ALL[usize::from(bits & 3)], skip_i: bits & 4 != 0 }
+    }
+}
+
+impl fmt::Display for Notation {
+    /// The spec `FromStr` reads back: `top-left`, or `bottom-left+i` when
+    /// `I` is used
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.origin.spec(), if self.skip_i { "" } else { "+i" })
+    }
+}
+
+impl FromStr for Notation {
+    type Err = String;
+
+    /// Parse a spec like `bottom-left` or `top-left+i`; `default` is
+    /// [`Notation::DEFAULT`].
+    fn from_str(s: &str) -> Result<Self, String> {
+        let s = s.trim().to_ascii_lowercase();
+        if s == "default" {
+            return Ok(Self::DEFAULT);
+        }
+        let (corner, skip_i) = match s.strip_suffix("+i") {
+            Some(corner) => (corner, false),
+            None => (s.as_str(), true),
+        };
+        let origin = Origin::ALL.into_iter().find(|origin| origin.spec() == corner).ok_or_else(|| {
+            format!("notation '{}': expected top-left, bottom-left, top-right or bottom-right, optionally +i", s)
+        })?;
+        Ok(Self { origin, skip_i })
+    }
+}
+
+/// Process-wide notation
+static NOTATION: AtomicU8 = AtomicU8::new(Notation::DEFAULT.to_bits());
+
+/// Use `notation` for display and logs from now on. Records, reports,
+/// terminal play and the server take theirs explicitly.
+pub fn set_notation(notation: Notation) {
+    NOTATION.store(notation.to_bits(), Ordering::Relaxed);
+}
+
+/// The notation in use
+#[must_use]
+pub fn notation() -> Notation {
+    Notation::from_bits(NOTATION.load(Ordering::Relaxed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_notation_round_trips() {
+        for origin in Origin::ALL {
+            for skip_i in [true, false] {
+                let notation = Notation { origin, skip_i };
+                assert_eq!(Notation::from_bits(notation.to_bits()), notation);
+                for index in 0..crate::board::TOTAL_CELLS {
+                    let pos = Pos::from_index(index);
+                    assert_eq!(notation.parse(&notation.format(pos)), Ok(pos), "{:?}", notation);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_origins_and_letters() {
+        let corner = Pos::new(0, 0);
+        let formats: Vec<String> = Origin::ALL
+            .iter()
+            .map(|&origin| Notation { origin, skip_i: true }.format(corner))
+            .collect();
+        assert_eq!(formats, ["A1", "A19", "T1", "T19"]);
+
+        let with_i = Notation { origin: Origin::TopLeft, skip_i: false };
+        assert_eq!(with_i.format(Pos::new(9, 8)), "I10");
+        assert_eq!(with_i.format(Pos::new(9, 18)), "S10");
+        assert!(with_i.parse("T1").unwrap_err().contains("A-S"));
+        assert!(Notation::DEFAULT.parse("I1").unwrap_err().contains("no column I"));
+    }
+
+    #[test]
+    fn test_notation_specs_round_trip() {
+        for origin in Origin::ALL {
+            for skip_i in [true, false] {
+                let notation = Notation { origin, skip_i };
+                assert_eq!(notation.to_string().parse::<Notation>(), Ok(notation));
+            }
+        }
+        assert_eq!("default".parse::<Notation>(), Ok(Notation::DEFAULT));
+        assert_eq!(" Bottom-Left ".parse::<Notation>(), Ok(Notation { origin: Origin::BottomLeft, skip_i: true }));
+        assert!("middle".parse::<Notation>().unwrap_err().contains("bottom-left"));
+    }
+}
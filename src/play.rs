@@ -0,0 +1,278 @@
+//! Terminal play
+//!
+//! [`play`] runs a game against the engine over text streams: the board is
+//! drawn in ASCII or Unicode after every move, the human enters moves in
+//! board notation (`K10`, in [`PlayConfig::notation`]) and the engine
+//! replies. Rules, captures and the
+//! result come from [`Game`], as everywhere outside the GUI. `gomoku-cli play`
+//! runs it on the terminal.
+//!
+//! Besides moves the prompt takes `undo` (the last move of each side),
+//! `resign`, `draw` (an offer the engine answers at once), `help` and
+//! `quit`.
+//!
+//! ```
+//! use gomoku::play::{play, PlayConfig};
+//! use gomoku::Stone;
+//!
+//! let config = PlayConfig { human: Stone::Black, max_depth: 2, time_limit_ms: 50, ..PlayConfig::default() };
+//! let mut out = Vec::new();
+//! let outcome = play("K10\nquit\n".as_bytes(), &mut out, &config).unwrap();
+//! assert_eq!(outcome, None);
+//! assert!(String::from_utf8(out).unwrap().contains("White plays"));
+//! ```
+
+use std::io::{self, BufRead, Write};
+
+use crate::board::{Board, Pos, Stone, BOARD_SIZE};
+use crate::engine::AIEngine;
+use crate::game::{Game, GameEvent, GameOutcome, WinReason};
+use crate::notation::Notation;
+
+/// Transposition table of the engine played against (MB)
+const PLAY_HASH_MB: usize = 64;
+
+/// Who plays what, and how the board is drawn
+#[derive(Debug, Clone, Copy)]
+pub struct PlayConfig {
+    /// Color the human plays; the engine takes the other
+    pub human: Stone,
+    /// Draw stones as `●`/`○` instead of `X`/`O`
+    pub unicode: bool,
+    /// Engine search depth and time per move
+    pub max_depth: i8,
+    pub time_limit_ms: u64,
+    /// Coordinates the board is labelled with and moves are read and
+    /// written in
+    pub notation: Notation,
+}
+
+impl Default for PlayConfig {
+    /// Human plays Black, Unicode board, depth 20 and 500ms per engine
+    /// move, [`Notation::DEFAULT`]
+    fn default() -> Self {
+        Self { human: Stone::Black, unicode: true, max_depth: 20, time_limit_ms: 500, notation: Notation::DEFAULT }
+    }
+}
+
+/// `board` as text, top row first with coordinates in `notation` on all
+/// sides, the captured pairs below it, and `last_move` in brackets.
+#[must_use]
+pub fn board_text(board: &Board, last_move: Option<Pos>, unicode: bool, notation: Notation) -> String {
+    let mut out = String::new();
+    board.write_grid(&mut out, last_move, unicode, notation).expect("writing to a String");
+    out.push('\n');
+    out
+}
+
+fn color_name(color: Stone) -> &'static str {
+    if color == Stone::Black { "Black" } else { "White" }
+}
+
+/// One line per event of a move, leaving out the move itself
+fn describe_event(event: &GameEvent, notation: Notation) -> Option<String> {
+    match event {
+        GameEvent::MovePlayed { .. } => None,
+        GameEvent::Captured { by, positions } => {
+            let stones: Vec<String> = positions.iter().map(|&pos| notation.format(pos)).collect();
+            Some(format!("{} captures {}", color_name(*by), stones.join(" ")))
+        }
+        GameEvent::BreakableFive(pending) => Some(format!(
+            "{} has a five that can be broken: {} must capture out of it now",
+            color_name(pending.holder),
+            color_name(pending.holder.opponent())
+        )),
+        GameEvent::GameOver(outcome) => Some(describe_outcome(*outcome)),
+    }
+}
+
+fn describe_outcome(outcome: GameOutcome) -> String {
+    match outcome {
+        GameOutcome::Win { winner, reason: WinReason::FiveInRow } => {
+            format!("{} wins with five in a row", color_name(winner))
+        }
+        GameOutcome::Win { winner, reason: WinReason::Capture } => format!("{} wins by captures", color_name(winner)),
+        GameOutcome::Win { winner, reason: WinReason::Resignation } => {
+            format!("{} resigns: {} wins", color_name(winner.opponent()), color_name(winner))
+        }
+        GameOutcome::Draw => "Draw: no legal move left".to_string(),
+        GameOutcome::AgreedDraw => "Draw by agreement".to_string(),
+    }
+}
+
+/// Play one game: the human's moves are read from `input`, one per line,
+/// and the board, the engine's replies and the result are written to
+/// `out`. Returns the result, or `None` when the human quits or the input
+/// ends first.
+///
+/// # Errors
+///
+/// Returns an error if reading `input` or writing `out` fails.
+pub fn play(input: impl BufRead, mut out: impl Write, config: &PlayConfig) -> io::Result<Option<GameOutcome>> {
+    let mut engine = AIEngine::with_config(PLAY_HASH_MB, config.max_depth, config.time_limit_ms);
+    let mut game = Game::new().with_notation(config.notation);
+    let mut lines = input.lines();
+
+    writeln!(
+        out,
+        "You play {}. Enter moves like {}; also undo, resign, draw, help, quit.",
+        color_name(config.human),
+        config.notation.format(Pos::new(BOARD_SIZE as u8 / 2, BOARD_SIZE as u8 / 2))
+    )?;
+    write!(out, "{}", board_text(game.board(), None, config.unicode, config.notation))?;
+
+    while !game.is_over() {
+        let color = game.to_move();
+        let pos = if color == config.human {
+            write!(out, "{}> ", color_name(color))?;
+            out.flush()?;
+            let Some(line) = lines.next().transpose()? else {
+                writeln!(out)?;
+                return Ok(None);
+            };
+            let entry = line.trim();
+            match entry.to_ascii_lowercase().as_str() {
+                "" => continue,
+                "quit" | "q" => return Ok(None),
+                "help" | "?" => {
+                    writeln!(
+                        out,
+                        "Moves are a column letter and a row number (K10). Commands: undo, resign, draw, help, quit."
+                    )?;
+                    continue;
+                }
+                "resign" => {
+                    match game.resign(color) {
+                        Ok(outcome) => writeln!(out, "{}", describe_outcome(outcome))?,
+                        Err(e) => writeln!(out, "{}", e)?,
+                    }
+                    continue;
+                }
+                "draw" => {
+                    // The engine answers the offer at once
+                    let engine_color = color.opponent();
+                    let answer = game.offer_draw(color).and_then(|()| {
+                        if engine.should_accept_draw(game.board(), engine_color) {
+                            game.accept_draw(engine_color).map(describe_outcome)
+                        } else {
+                            let declined = format!("{} declines the draw", color_name(engine_color));
+                            game.decline_draw(engine_color).map(|()| declined)
+                        }
+                    });
+                    match answer {
+                        Ok(answer) => writeln!(out, "{}", answer)?,
+                        Err(e) => writeln!(out, "{}", e)?,
+                    }
+                    continue;
+                }
+                "undo" | "u" => {
+                    // Back to the human's previous turn
+                    game.pop_move();
+                    if game.to_move() != config.human {
+                        game.pop_move();
+                    }
+                    let last = game.history().last().map(|record| record.pos);
+                    write!(out, "{}", board_text(game.board(), last, config.unicode, config.notation))?;
+                    continue;
+                }
+                _ => match config.notation.parse(entry) {
+                    Ok(pos) => pos,
+                    Err(e) => {
+                        writeln!(out, "{}", e)?;
+                        continue;
+                    }
+                },
+            }
+        } else {
+            let result = engine.get_move_with_stats(game.board(), color);
+            let Some(pos) = result.best_move else {
+                writeln!(out, "{} has no move", color_name(color))?;
+                return Ok(None);
+            };
+            writeln!(
+                out,
+                "{} plays {} (depth {}, score {}, {}ms)",
+                color_name(color),
+                config.notation.format(pos),
+                result.depth,
+                result.score,
+                result.time_ms
+            )?;
+            pos
+        };
+
+        match game.play(pos) {
+            Ok(events) => {
+                write!(out, "{}", board_text(game.board(), Some(pos), config.unicode, config.notation))?;
+                for line in events.iter().filter_map(|event| describe_event(event, config.notation)) {
+                    writeln!(out, "{}", line)?;
+                }
+            }
+            Err(e) => writeln!(out, "{}", e)?,
+        }
+    }
+    Ok(game.outcome())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_board_text_marks_stones_and_last_move() {
+        let mut board = Board::new();
+        let (black, white) = (Pos::new(0, 0), Pos::new(0, 1));
+        board.place_stone(black, Stone::Black);
+        board.place_stone(white, Stone::White);
+
+        let n = Notation { origin: crate::notation::Origin::BottomRight, skip_i: false };
+        let ascii = board_text(&board, Some(white), false, n);
+        let lines: Vec<&str> = ascii.lines().collect();
+        assert_eq!(lines.len(), BOARD_SIZE + 3);
+        assert!(lines[0].starts_with(&format!("    {} {}", n.column_letter(0), n.column_letter(1))));
+        let first = format!("{:>2}  X[O]. .", n.row_number(0));
+        assert!(lines[1].starts_with(&first), "{:?}", lines[1]);
+        assert!(lines[2].ends_with(&format!(" . {}", n.row_number(1))), "{:?}", lines[2]);
+        assert_eq!(lines[BOARD_SIZE + 2], "Captured pairs: Black 0, White 0");
+
+        let unicode = board_text(&board, None, true, Notation::DEFAULT);
+        assert!(unicode.contains(" ● ○ · "));
+    }
+
+    #[test]
+    fn test_play_rejects_bad_input_and_undoes() {
+        let config = PlayConfig { human: Stone::White, unicode: false, max_depth: 2, time_limit_ms: 50, ..PlayConfig::default() };
+        let input = "Z99\nhelp\nK9\nundo\n";
+        let mut out = Vec::new();
+        assert_eq!(play(input.as_bytes(), &mut out, &config).unwrap(), None);
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("You play White."));
+        assert!(out.contains("'Z99'"), "Bad moves are explained");
+        assert!(out.contains("Commands: undo, resign, draw, help, quit"));
+        // The engine opened, replied to K9 (unless it was taken), and undo
+        // went back to White's first turn: one engine move on the board
+        assert!(out.matches("Black plays").count() >= 2);
+        let empty = board_text(&Board::new(), None, false, config.notation);
+        let header = empty.lines().next().unwrap();
+        let headers: Vec<usize> = out.match_indices(header).map(|(at, _)| at).collect();
+        let last_board = &out[headers[headers.len() - 2]..];
+        let rows: String = last_board.lines().skip(1).take(BOARD_SIZE).map(|line| &line[3..]).collect();
+        assert_eq!(rows.matches('X').count(), 1, "{}", last_board);
+        assert_eq!(rows.matches('O').count(), 0);
+    }
+
+    #[test]
+    fn test_play_draw_offer_and_resign() {
+        let config = PlayConfig { human: Stone::Black, unicode: false, max_depth: 2, time_limit_ms: 50, ..PlayConfig::default() };
+        let mut out = Vec::new();
+        let outcome = play("K10\nresign\n".as_bytes(), &mut out, &config).unwrap();
+        assert_eq!(outcome, Some(GameOutcome::Win { winner: Stone::White, reason: WinReason::Resignation }));
+        assert!(String::from_utf8(out).unwrap().ends_with("Black resigns: White wins\n"));
+
+        // The engine takes a draw on the empty board, where nobody is ahead
+        let mut out = Vec::new();
+        assert_eq!(play("draw\n".as_bytes(), &mut out, &config).unwrap(), Some(GameOutcome::AgreedDraw));
+        assert!(String::from_utf8(out).unwrap().ends_with("Draw by agreement\n"));
+    }
+}
@@ -0,0 +1,242 @@
+//! Pool of ready-made engines
+//!
+//! Allocating an [`AIEngine`] means allocating (and zeroing) its
+//! transposition table, which costs more than a short search. An
+//! [`EnginePool`] builds its engines once and lends them out: a
+//! [`PooledEngine`] is an engine for one game or request, and dropping it
+//! clears its caches, restores the pool's settings and hands it back for
+//! the next checkout. The server and the tuning match runner use one.
+//! Holders that pass their engine between threads themselves, like the
+//! GUI's games, [`EnginePool::take`] one and [`EnginePool::check_in`] it.
+//!
+//! ```
+//! use gomoku::pool::EnginePool;
+//! use gomoku::{Board, Pos, Stone};
+//!
+//! let pool = EnginePool::new(1, 4, 4, 200);
+//! let mut board = Board::new();
+//! board.place_stone(Pos::new(9, 9), Stone::Black);
+//! {
+//!     let mut engine = pool.checkout();
+//!     engine.set_max_depth(2);
+//!     assert!(engine.get_move(&board, Stone::White).is_some());
+//!     assert_eq!(pool.idle(), 0);
+//! }
+//! // Back in the pool, with the pool's depth again
+//! assert_eq!(pool.idle(), 1);
+//! ```
+
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+
+use crate::engine::AIEngine;
+use crate::rules::RuleSet;
+use crate::search::{SearchParams, ZobristTable};
+use crate::time::SystemClock;
+
+/// Engine settings a returned engine is reset to
+#[derive(Debug, Clone, Copy)]
+struct PoolConfig {
+    /// Idle engines kept (more checkouts build extra engines, dropped on return)
+    size: usize,
+    tt_size_mb: usize,
+    max_depth: i8,
+    time_limit_ms: u64,
+    threads: usize,
+}
+
+struct PoolInner {
+    config: PoolConfig,
+    idle: Mutex<Vec<AIEngine>>,
+}
+
+impl PoolInner {
+    fn build(&self) -> AIEngine {
+        let config = self.config;
+        let mut engine = AIEngine::with_config(config.tt_size_mb, config.max_depth, config.time_limit_ms);
+        engine.set_threads(config.threads);
+        engine
+    }
+
+    /// Clear `engine`'s caches and settings and keep it if there's room.
+    fn check_in(&self, mut engine: AIEngine) {
+        let config = self.config;
+        if engine.hash_size() != config.tt_size_mb {
+            engine.set_hash_size(config.tt_size_mb);
+        }
+        engine.clear_cache();
+        engine.clear_info_callback();
+        engine.set_max_depth(config.max_depth);
+        engine.set_time_limit(config.time_limit_ms);
+        engine.set_threads(config.threads);
+        engine.set_deterministic(false);
+        engine.set_swindle_mode(false);
+        engine.set_speculative_defense(false);
+        engine.set_opening_variety(None);
+        engine.set_seed(ZobristTable::DEFAULT_SEED);
+        engine.set_clock(Arc::new(SystemClock));
+        engine.set_resign_threshold(None);
+        engine.set_paranoid(false);
+        // Defaults always validate
+        let _ = engine.set_search_params(SearchParams::default());
+        engine.set_rules(RuleSet::NINUKI);
+
+        if let Ok(mut idle) = self.idle.lock() {
+            if idle.len() < config.size {
+                idle.push(engine);
+            }
+        }
+    }
+}
+
+/// Shared set of pre-built engines; clones share the same engines
+#[derive(Clone)]
+pub struct EnginePool {
+    inner: Arc<PoolInner>,
+}
+
+impl EnginePool {
+    /// Build `size` engines, as [`AIEngine::with_config`] with these
+    /// arguments, ready for checkout.
+    #[must_use]
+    pub fn new(size: usize, tt_size_mb: usize, max_depth: i8, time_limit_ms: u64) -> Self {
+        let threads = crate::search::Searcher::auto_threads();
+        Self::with_threads(size, tt_size_mb, max_depth, time_limit_ms, threads)
+    }
+
+    /// [`EnginePool::new`] with engines searching on `threads` threads.
+    #[must_use]
+    pub fn with_threads(size: usize, tt_size_mb: usize, max_depth: i8, time_limit_ms: u64, threads: usize) -> Self {
+        let config = PoolConfig { size, tt_size_mb, max_depth, time_limit_ms, threads: threads.max(1) };
+        let inner = PoolInner { config, idle: Mutex::new(Vec::with_capacity(size)) };
+        let engines: Vec<AIEngine> = (0..size).map(|_| inner.build()).collect();
+        if let Ok(mut idle) = inner.idle.lock() {
+            *idle = engines;
+        }
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// An engine with cleared caches and the pool's settings. Taken from
+    /// the idle engines, or built if all are checked out.
+    #[must_use]
+    pub fn checkout(&self) -> PooledEngine {
+        PooledEngine { engine: Some(self.take()), pool: Arc::clone(&self.inner) }
+    }
+
+    /// Like [`EnginePool::checkout`], but the engine is the caller's to
+    /// move around; it only comes back through [`EnginePool::check_in`].
+    #[must_use]
+    pub fn take(&self) -> AIEngine {
+        let engine = self.inner.idle.lock().ok().and_then(|mut idle| idle.pop());
+        engine.unwrap_or_else(|| self.inner.build())
+    }
+
+    /// Hand back an engine from [`EnginePool::take`], resetting it as a
+    /// dropped [`PooledEngine`] is.
+    pub fn check_in(&self, engine: AIEngine) {
+        self.inner.check_in(engine);
+    }
+
+    /// Engines waiting for checkout.
+    #[must_use]
+    pub fn idle(&self) -> usize {
+        self.inner.idle.lock().map_or(0, |idle| idle.len())
+    }
+
+    /// Idle engines the pool keeps.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.inner.config.size
+    }
+}
+
+/// An engine checked out of an [`EnginePool`]; returns to it when dropped
+pub struct PooledEngine {
+    /// Always `Some` until dropped
+    engine: Option<AIEngine>,
+    pool: Arc<PoolInner>,
+}
+
+impl Deref for PooledEngine {
+    type Target = AIEngine;
+
+    fn deref(&self) -> &AIEngine {
+        self.engine.as_ref().expect("pooled engine present until drop")
+    }
+}
+
+impl DerefMut for PooledEngine {
+    fn deref_mut(&mut self) -> &mut AIEngine {
+        self.engine.as_mut().expect("pooled engine present until drop")
+    }
+}
+
+impl Drop for PooledEngine {
+    fn drop(&mut self) {
+        if let Some(engine) = self.engine.take() {
+            self.pool.check_in(engine);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::{Board, Pos, Stone};
+
+    #[test]
+    fn test_returned_engines_are_reset_and_reused() {
+        let pool = EnginePool::with_threads(2, 1, 4, 200, 1);
+        assert_eq!((pool.size(), pool.idle()), (2, 2));
+
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+
+        {
+            let mut engine = pool.checkout();
+            engine.set_threads(3);
+            engine.set_swindle_mode(true);
+            engine.set_speculative_defense(true);
+            engine.set_seed(99);
+            engine.set_rules(RuleSet::NINUKI.with_captures(3, 5).unwrap());
+            engine.set_search_params(SearchParams { max_root_moves: 5, ..SearchParams::default() }).unwrap();
+            assert!(engine.get_move(&board, Stone::White).is_some());
+        }
+        let engine = pool.checkout();
+        assert_eq!(engine.threads(), 1);
+        assert_eq!(engine.search_params(), SearchParams::default());
+        assert!(!engine.swindle_mode());
+        assert!(!engine.speculative_defense());
+        assert_eq!(engine.seed(), ZobristTable::DEFAULT_SEED);
+        assert_eq!(engine.rules(), RuleSet::NINUKI);
+        drop(engine);
+
+        // Extra checkouts beyond the pool size are built, then dropped
+        let engines: Vec<PooledEngine> = (0..3).map(|_| pool.checkout()).collect();
+        assert_eq!(pool.idle(), 0);
+        drop(engines);
+        assert_eq!(pool.idle(), 2);
+
+        // A reused engine searches like a fresh one
+        let mut engine = pool.checkout();
+        engine.set_deterministic(true);
+        let mut fresh = AIEngine::with_config(1, 4, 200);
+        fresh.set_deterministic(true);
+        assert_eq!(engine.get_move(&board, Stone::White), fresh.get_move(&board, Stone::White));
+    }
+
+    #[test]
+    fn test_taken_engines_come_back_through_check_in() {
+        let pool = EnginePool::with_threads(1, 1, 4, 200, 1);
+        let mut engine = pool.take();
+        assert_eq!(pool.idle(), 0);
+        engine.set_hash_size(2);
+        engine.set_max_depth(9);
+        pool.check_in(engine);
+
+        assert_eq!(pool.idle(), 1);
+        let engine = pool.checkout();
+        assert_eq!(engine.hash_size(), 1);
+        assert_eq!(engine.max_depth(), 4);
+    }
+}
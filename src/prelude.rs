@@ -0,0 +1,42 @@
+//! The types and functions most tools need, in one import
+//!
+//! `use gomoku::prelude::*;` brings in the board, the rules queries, the
+//! static evaluation with its pattern scores, threat detection and the
+//! searcher. Everything here is also reachable at its module path; the
+//! prelude only gathers what third-party tooling (analysis scripts,
+//! trainers, alternative front-ends) reaches for first. Names are only
+//! added to it, not renamed or removed, outside a breaking release.
+//!
+//! ```
+//! use gomoku::prelude::*;
+//!
+//! let mut board = Board::new();
+//! for col in 7..10 {
+//!     board.place_stone(Pos::new(9, col), Stone::Black);
+//! }
+//! let threats = find_threats(RuleSet::NINUKI, &board, Stone::Black);
+//! assert!(threats.iter().any(|threat| threat.kind == ThreatKind::OpenThree));
+//! assert!(evaluate(RuleSet::NINUKI, &board, Stone::Black) > 0);
+//! assert!(PatternScore::OPEN_FOUR > PatternScore::OPEN_THREE);
+//! assert_eq!(capture_score(RuleSet::NINUKI, 2, 2), 0);
+//! ```
+//!
+//! The engine, games and notation helpers need the `std` feature; the
+//! rest is in the `no_std` build as well.
+
+pub use crate::board::{Board, Pos, Stone, BOARD_SIZE};
+pub use crate::error::GomokuError;
+pub use crate::eval::{capture_score, evaluate, evaluate_full, PatternScore};
+pub use crate::notation::{notation, Notation};
+pub use crate::rules::{
+    check_winner, execute_captures, find_threats, foul, is_valid_move, legal_moves,
+    winner_after_move, Foul, RuleSet, Threat, ThreatKind,
+};
+pub use crate::search::{
+    mate_distance, SearchLimits, SearchParams, Searcher, ThreatResult, ThreatSearcher, ZobristTable, MATE_THRESHOLD,
+};
+
+#[cfg(feature = "std")]
+pub use crate::engine::{pos_to_notation, AIEngine, MoveResult};
+#[cfg(feature = "std")]
+pub use crate::game::{Game, GameEvent, GameOutcome, WinReason};
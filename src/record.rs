@@ -0,0 +1,408 @@
+//! Game records: saving and loading move sequences
+//!
+//! Two formats are read and written:
+//! - SGF (`GM[4]`, the Gomoku game type): `(;GM[4]FF[4]SZ[19];B[jj];W[kj])`.
+//!   Coordinates are column then row letters, `a` = column A / row 0.
+//!   Only the main line is read; variations are skipped.
+//! - A plain move list in board notation, Black first and alternating:
+//!   `K10 L10 K11`. Lines starting with `#` are comments. The notation is
+//!   [`Notation::DEFAULT`], not the one set for display, unless another is
+//!   passed to the `*_with_notation` methods.
+//!
+//! A game that ended without a move keeps how in [`GameRecord::result`]:
+//! SGF `RE[B+R]` / `RE[W+R]` for a resignation and `RE[0]` for an agreed
+//! draw, and the same words (`B+R`, `W+R`, `draw`) after the last move of
+//! a move list. Other endings follow from the moves and aren't written.
+//!
+//! [`GameRecord::parse`] accepts either (SGF is recognized by its leading
+//! `(`), [`GameRecord::boards`] replays the moves under a rule set and
+//! [`GameRecord::to_game`] replays the whole game. A record doesn't store
+//! the rules; the caller says which ones the game was played by.
+//!
+//! ```
+//! use gomoku::record::GameRecord;
+//! use gomoku::rules::RuleSet;
+//!
+//! let record = GameRecord::parse("K10 L11 K11 W+R").unwrap();
+//! let sgf = record.to_sgf();
+//! assert_eq!(GameRecord::parse(&sgf).unwrap(), record);
+//! assert_eq!(record.boards(RuleSet::NINUKI).unwrap().len(), 4);
+//! let game = record.to_game(RuleSet::NINUKI).unwrap();
+//! assert_eq!(game.outcome().unwrap().winner(), Some(gomoku::Stone::White));
+//! ```
+
+use crate::board::{Board, Pos, Stone, BOARD_SIZE};
+use crate::game::{why_illegal, Game, GameOutcome, WinReason};
+use crate::notation::Notation;
+use crate::rules::{execute_captures, RuleSet};
+
+/// A game as the sequence of its moves; Black moves first and colors
+/// alternate.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameRecord {
+    pub moves: Vec<Pos>,
+    /// A resignation or agreed draw after the moves; `None` when the game
+    /// went on or ended on the board
+    pub result: Option<GameOutcome>,
+}
+
+impl GameRecord {
+    /// A record of `moves` without a result.
+    #[must_use]
+    pub fn new(moves: Vec<Pos>) -> Self {
+        Self { moves, result: None }
+    }
+
+    /// Color of the stone placed by move `index` (0-based).
+    #[must_use]
+    pub fn color_of(index: usize) -> Stone {
+        if index.is_multiple_of(2) { Stone::Black } else { Stone::White }
+    }
+
+    /// Parse an SGF record or a plain move list.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first problem found.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        Self::parse_with_notation(text, Notation::DEFAULT)
+    }
+
+    /// Parse an SGF record or a plain move list in `notation`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first problem found.
+    pub fn parse_with_notation(text: &str, notation: Notation) -> Result<Self, String> {
+        if text.trim_start().starts_with('(') {
+            Self::from_sgf(text)
+        } else {
+            Self::from_move_list_with_notation(text, notation)
+        }
+    }
+
+    /// Parse a plain move list (`K10 L10 ...`), optionally ended by a
+    /// result (`B+R`, `W+R` or `draw`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first token that is not a board point,
+    /// or a result that isn't last.
+    pub fn from_move_list(text: &str) -> Result<Self, String> {
+        Self::from_move_list_with_notation(text, Notation::DEFAULT)
+    }
+
+    /// Parse a plain move list in `notation`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first token that is not a board point,
+    /// or a result that isn't last.
+    pub fn from_move_list_with_notation(text: &str, notation: Notation) -> Result<Self, String> {
+        let mut tokens: Vec<&str> = text
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .flat_map(str::split_whitespace)
+            .collect();
+        let result = tokens.last().and_then(|token| parse_result(token));
+        if result.is_some() {
+            tokens.pop();
+        }
+        let moves = tokens
+            .into_iter()
+            .enumerate()
+            .map(|(i, token)| match parse_result(token) {
+                Some(_) => Err(format!("move {}: the result '{}' must come last", i + 1, token)),
+                None => notation.parse(token).map_err(|e| format!("move {}: {}", i + 1, e)),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { moves, result })
+    }
+
+    /// Parse the main line of an SGF game.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for malformed SGF, a game type or board size other
+    /// than Gomoku on 19x19, passes, or colors that do not alternate.
+    pub fn from_sgf(text: &str) -> Result<Self, String> {
+        let mut moves = Vec::new();
+        let mut result = None;
+        for (ident, value) in sgf_main_line(text)? {
+            match ident.as_str() {
+                "GM" if value != "4" => return Err(format!("GM[{}] is not a Gomoku game", value)),
+                "SZ" if value != BOARD_SIZE.to_string() => {
+                    return Err(format!("SZ[{}]: only {}x{} boards are supported", value, BOARD_SIZE, BOARD_SIZE))
+                }
+                "B" | "W" => {
+                    let n = moves.len() + 1;
+                    let color = if ident == "B" { Stone::Black } else { Stone::White };
+                    if color != Self::color_of(moves.len()) {
+                        return Err(format!("move {}: expected {:?} to move", n, Self::color_of(moves.len())));
+                    }
+                    moves.push(sgf_point(&value).ok_or(format!("move {}: bad point '{}'", n, value))?);
+                }
+                // Other results (`B+`, `W+T`, ...) follow from the moves or aren't kept
+                "RE" => result = parse_result(&value),
+                _ => {}
+            }
+        }
+        Ok(Self { moves, result })
+    }
+
+    /// The record as a plain move list.
+    #[must_use]
+    pub fn to_move_list(&self) -> String {
+        self.to_move_list_with_notation(Notation::DEFAULT)
+    }
+
+    /// The record as a plain move list in `notation`.
+    #[must_use]
+    pub fn to_move_list_with_notation(&self, notation: Notation) -> String {
+        let mut tokens: Vec<String> = self.moves.iter().map(|&p| notation.format(p)).collect();
+        tokens.extend(self.result.and_then(result_text).map(str::to_string));
+        tokens.join(" ")
+    }
+
+    /// The record as SGF.
+    #[must_use]
+    pub fn to_sgf(&self) -> String {
+        let mut sgf = format!("(;GM[4]FF[4]SZ[{}]", BOARD_SIZE);
+        if let Some(result) = self.result.and_then(result_text) {
+            let result = if result == "draw" { "0" } else { result };
+            sgf.push_str(&format!("RE[{}]", result));
+        }
+        for (i, pos) in self.moves.iter().enumerate() {
+            let color = if Self::color_of(i) == Stone::Black { 'B' } else { 'W' };
+            sgf.push_str(&format!(";{}[{}{}]", color, (b'a' + pos.col) as char, (b'a' + pos.row) as char));
+        }
+        sgf.push(')');
+        sgf
+    }
+
+    /// Replay the moves by `rules`: the board before the first move, then
+    /// after each.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first illegal move and why: an occupied
+    /// point or the foul it makes.
+    pub fn boards(&self, rules: RuleSet) -> Result<Vec<Board>, String> {
+        self.boards_with_notation(rules, Notation::DEFAULT)
+    }
+
+    /// [`GameRecord::boards`], naming the illegal move in `notation`.
+    ///
+    /// # Errors
+    ///
+    /// As [`GameRecord::boards`].
+    pub fn boards_with_notation(&self, rules: RuleSet, notation: Notation) -> Result<Vec<Board>, String> {
+        let mut board = Board::new();
+        let mut boards = Vec::with_capacity(self.moves.len() + 1);
+        boards.push(board.clone());
+        for (i, &pos) in self.moves.iter().enumerate() {
+            let color = Self::color_of(i);
+            if let Some(why) = why_illegal(rules, &board, pos, color) {
+                return Err(format!("move {} ({}) is illegal: {}", i + 1, notation.format(pos), why));
+            }
+            board.place_stone(pos, color);
+            execute_captures(rules, &mut board, pos, color);
+            boards.push(board.clone());
+        }
+        Ok(boards)
+    }
+
+    /// Replay the moves and the result as a [`Game`] by `rules`. A result
+    /// the moves already decided is left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first illegal move.
+    pub fn to_game(&self, rules: RuleSet) -> Result<Game, String> {
+        self.to_game_with_notation(rules, Notation::DEFAULT)
+    }
+
+    /// [`GameRecord::to_game`], naming positions in `notation` in its errors
+    /// and the game's ([`Game::with_notation`]).
+    ///
+    /// # Errors
+    ///
+    /// As [`GameRecord::to_game`].
+    pub fn to_game_with_notation(&self, rules: RuleSet, notation: Notation) -> Result<Game, String> {
+        let mut game = Game::with_rules(rules).with_notation(notation);
+        for (i, &pos) in self.moves.iter().enumerate() {
+            game.push_move(pos).map_err(|e| format!("move {}: {}", i + 1, e))?;
+        }
+        if game.is_over() {
+            return Ok(game);
+        }
+        match self.result {
+            Some(GameOutcome::Win { winner, reason: WinReason::Resignation }) => {
+                game.resign(winner.opponent())?;
+            }
+            Some(GameOutcome::AgreedDraw) => {
+                let offerer = game.to_move();
+                game.offer_draw(offerer)?;
+                game.accept_draw(offerer.opponent())?;
+            }
+            _ => {}
+        }
+        Ok(game)
+    }
+}
+
+/// A resignation or agreed draw as written in records: `B+R`, `W+R`, or
+/// `draw` (`0` in SGF)
+fn result_text(result: GameOutcome) -> Option<&'static str> {
+    match result {
+        GameOutcome::Win { winner: Stone::Black, reason: WinReason::Resignation } => Some("B+R"),
+        GameOutcome::Win { winner: Stone::White, reason: WinReason::Resignation } => Some("W+R"),
+        GameOutcome::AgreedDraw => Some("draw"),
+        _ => None,
+    }
+}
+
+/// Read a result written by [`result_text`], case-insensitively; SGF's
+/// `0` and `Draw` mean a draw too.
+fn parse_result(text: &str) -> Option<GameOutcome> {
+    let resigned = |winner| Some(GameOutcome::Win { winner, reason: WinReason::Resignation });
+    match text.to_ascii_uppercase().as_str() {
+        "B+R" | "B+RESIGN" => resigned(Stone::Black),
+        "W+R" | "W+RESIGN" => resigned(Stone::White),
+        "0" | "DRAW" => Some(GameOutcome::AgreedDraw),
+        _ => None,
+    }
+}
+
+/// SGF point `"jj"` (column, row) to a position.
+fn sgf_point(value: &str) -> Option<Pos> {
+    let bytes = value.as_bytes();
+    let coord = |b: u8| (b'a'..b'a' + BOARD_SIZE as u8).contains(&b).then(|| b - b'a');
+    match bytes {
+        [col, row] => Some(Pos::new(coord(*row)?, coord(*col)?)),
+        _ => None,
+    }
+}
+
+/// Properties of the main line, in order, with one entry per value.
+fn sgf_main_line(text: &str) -> Result<Vec<(String, String)>, String> {
+    let text = text.trim();
+    if !text.starts_with("(;") {
+        return Err("SGF must start with '(;'".to_string());
+    }
+    let mut props = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut depth = 0usize;
+    let mut ident = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '(' => depth += 1,
+            // The end of the first variation ends the main line
+            ')' => break,
+            ';' => ident.clear(),
+            '[' => {
+                if ident.is_empty() {
+                    return Err("property value without a name".to_string());
+                }
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => value.extend(chars.next()),
+                        Some(']') => break,
+                        Some(c) => value.push(c),
+                        None => return Err(format!("unterminated value for {}", ident)),
+                    }
+                }
+                props.push((ident.clone(), value));
+                // More values for the same property may follow
+                while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                    chars.next();
+                }
+                if chars.peek() != Some(&'[') {
+                    ident.clear();
+                }
+            }
+            c if c.is_ascii_uppercase() => ident.push(c),
+            c if c.is_whitespace() => {}
+            c => return Err(format!("unexpected '{}'", c)),
+        }
+    }
+    if depth == 0 {
+        return Err("SGF must start with '(;'".to_string());
+    }
+    Ok(props)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sgf_round_trip() {
+        let record = GameRecord::new(vec![Pos::new(9, 9), Pos::new(9, 10), Pos::new(0, 18)]);
+        let sgf = record.to_sgf();
+        assert_eq!(sgf, "(;GM[4]FF[4]SZ[19];B[jj];W[kj];B[sa])");
+        assert_eq!(GameRecord::from_sgf(&sgf).unwrap(), record);
+    }
+
+    #[test]
+    fn test_sgf_main_line_and_errors() {
+        let sgf = "(;GM[4]SZ[19]C[a \\] comment]\n;B[jj];W[kk](;B[ll];W[mm])(;B[aa]))";
+        let record = GameRecord::from_sgf(sgf).unwrap();
+        assert_eq!(record.to_move_list(), "K10 L11 M12 N13");
+
+        assert!(GameRecord::from_sgf("(;GM[1];B[jj])").unwrap_err().contains("GM[1]"));
+        assert!(GameRecord::from_sgf("(;SZ[15];B[jj])").is_err());
+        assert!(GameRecord::from_sgf("(;B[jj];B[kk])").unwrap_err().contains("move 2"));
+        assert!(GameRecord::from_sgf("(;B[])").is_err());
+        assert!(GameRecord::from_sgf("(;B[jj]").is_ok(), "Missing close paren is tolerated");
+    }
+
+    #[test]
+    fn test_move_list_and_replay() {
+        let record = GameRecord::parse("# opening\nK10 L10\nK11 l11").unwrap();
+        assert_eq!(record.moves.len(), 4);
+        assert_eq!(GameRecord::parse(&record.to_move_list()).unwrap(), record);
+        assert!(GameRecord::parse("K10 Z99").unwrap_err().contains("move 2"));
+
+        let bottom = Notation { origin: crate::notation::Origin::BottomLeft, skip_i: false };
+        assert_eq!(record.to_move_list_with_notation(bottom), "J10 K10 J9 K9");
+        assert_eq!(GameRecord::parse_with_notation("J10 K10 J9 K9", bottom).unwrap(), record);
+        let occupied = GameRecord::parse_with_notation("A1 A1", bottom).unwrap();
+        assert_eq!(occupied.boards_with_notation(RuleSet::NINUKI, bottom).unwrap_err(), "move 2 (A1) is illegal: occupied");
+        assert_eq!(occupied.to_game_with_notation(RuleSet::NINUKI, bottom).unwrap_err(), "move 2: A1 is illegal: occupied");
+
+        let boards = record.boards(RuleSet::NINUKI).unwrap();
+        assert_eq!(boards.len(), 5);
+        assert_eq!(boards[4].get(Pos::new(10, 10)), Stone::White);
+
+        // Black J10 and M10 bracket White K10-L10
+        let capture = GameRecord::parse("J10 K10 A1 L10 M10").unwrap();
+        let last = capture.boards(RuleSet::NINUKI).unwrap().pop().unwrap();
+        assert_eq!(last.captures(Stone::Black), 1);
+        assert!(last.is_empty(Pos::new(9, 9)) && last.is_empty(Pos::new(9, 10)));
+
+        let occupied = GameRecord::parse("K10 K10").unwrap();
+        assert_eq!(occupied.boards(RuleSet::NINUKI).unwrap_err(), "move 2 (K10) is illegal: occupied");
+    }
+
+    #[test]
+    fn test_results() {
+        let resigned = GameRecord::parse("K10 L11 K11\nB+R").unwrap();
+        assert_eq!(resigned.moves.len(), 3);
+        assert_eq!(resigned.to_move_list(), "K10 L11 K11 B+R");
+        assert!(resigned.to_sgf().contains("RE[B+R]"));
+        assert_eq!(GameRecord::parse(&resigned.to_sgf()).unwrap(), resigned);
+        let game = resigned.to_game(RuleSet::NINUKI).unwrap();
+        assert_eq!(game.outcome(), Some(GameOutcome::Win { winner: Stone::Black, reason: WinReason::Resignation }));
+
+        let drawn = GameRecord::parse("(;GM[4]RE[0];B[jj];W[kk])").unwrap();
+        assert_eq!(drawn.result, Some(GameOutcome::AgreedDraw));
+        assert_eq!(drawn.to_move_list(), "K10 L11 draw");
+        assert_eq!(drawn.to_game(RuleSet::NINUKI).unwrap().outcome(), Some(GameOutcome::AgreedDraw));
+
+        // Results the moves decide aren't kept
+        assert_eq!(GameRecord::from_sgf("(;RE[B+5];B[jj])").unwrap().result, None);
+        assert!(GameRecord::parse("K10 draw L11").unwrap_err().contains("must come last"));
+    }
+}
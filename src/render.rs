@@ -0,0 +1,338 @@
+//! Headless board rendering to PNG and animated GIF
+//!
+//! [`BoardImage::render`] draws a position the way the GUI shows it (wood
+//! board, grid, star points, stones, last-move marker) into an indexed
+//! image without a window or GPU. [`render_game`] draws one image per move
+//! of a game; each writes as a PNG ([`BoardImage::write_png`]) and the
+//! whole game as a looping animated GIF ([`write_gif`]); [`export_game`]
+//! writes either to disk, as `gomoku-cli export <file> <out.gif | dir>` does for
+//! a saved game.
+//!
+//! Needs the `render` feature.
+//!
+//! ```
+//! use gomoku::render::{render_game, write_gif, RenderStyle};
+//! use gomoku::rules::RuleSet;
+//! use gomoku::Pos;
+//!
+//! let style = RenderStyle { cell: 8, ..RenderStyle::default() };
+//! let frames = render_game(RuleSet::NINUKI, &[Pos::new(9, 9), Pos::new(9, 10)], &style).unwrap();
+//! assert_eq!(frames.len(), 2);
+//!
+//! let mut png = Vec::new();
+//! frames[1].write_png(&mut png).unwrap();
+//! let mut gif = Vec::new();
+//! write_gif(&frames, &style, &mut gif).unwrap();
+//! assert!(gif.starts_with(b"GIF89a"));
+//! ```
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::board::{Board, Pos, Stone, BOARD_SIZE};
+use crate::record::GameRecord;
+use crate::rules::RuleSet;
+
+/// Palette of every image, as in the GUI's classic theme (8 entries for
+/// the GIF color table)
+pub const PALETTE: [[u8; 3]; 8] = [
+    [222, 184, 135], // board
+    [60, 40, 20],    // grid lines and star points
+    [25, 25, 30],    // black stone
+    [250, 250, 252], // white stone
+    [190, 190, 195], // white stone rim
+    [230, 60, 60],   // last-move marker
+    [139, 90, 43],   // board border
+    [0, 0, 0],
+];
+
+const BOARD: u8 = 0;
+const GRID: u8 = 1;
+const BLACK: u8 = 2;
+const WHITE: u8 = 3;
+const WHITE_RIM: u8 = 4;
+const MARKER: u8 = 5;
+const BORDER: u8 = 6;
+
+/// Star points (hoshi) of the 19x19 board
+const STAR_POINTS: [usize; 3] = [3, 9, 15];
+
+/// LZW minimum code size of the 8-color palette
+const GIF_CODE_SIZE: u8 = 3;
+
+/// Size and timing of rendered images
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderStyle {
+    /// Pixels between grid lines (and from the outer lines to the edge),
+    /// 4 to 1024
+    pub cell: u32,
+    /// GIF frame time, in milliseconds
+    pub frame_ms: u32,
+    /// How long the GIF holds the final position, in milliseconds
+    pub final_frame_ms: u32,
+}
+
+impl Default for RenderStyle {
+    /// 24-pixel cells, 0.7s per move, final position held 3s
+    fn default() -> Self {
+        Self { cell: 24, frame_ms: 700, final_frame_ms: 3_000 }
+    }
+}
+
+/// A rendered position: one [`PALETTE`] index per pixel, row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl BoardImage {
+    /// Draw `board`, marking `last_move` if given.
+    #[must_use]
+    pub fn render(board: &Board, last_move: Option<Pos>, style: &RenderStyle) -> Self {
+        let cell = style.cell.clamp(4, 1024) as i32;
+        let size = (cell * (BOARD_SIZE as i32 + 1)) as u32;
+        let mut image = Self { width: size, height: size, pixels: vec![BOARD; (size * size) as usize] };
+        let center = |index: usize| cell + index as i32 * cell;
+
+        let last = size as i32 - 1;
+        image.fill_rect(0, 0, last, 1, BORDER);
+        image.fill_rect(0, last - 1, last, last, BORDER);
+        image.fill_rect(0, 0, 1, last, BORDER);
+        image.fill_rect(last - 1, 0, last, last, BORDER);
+        let (first, end) = (center(0), center(BOARD_SIZE - 1));
+        for i in 0..BOARD_SIZE {
+            image.fill_rect(center(i), first, center(i), end, GRID);
+            image.fill_rect(first, center(i), end, center(i), GRID);
+        }
+        for &row in &STAR_POINTS {
+            for &col in &STAR_POINTS {
+                image.fill_circle(center(col), center(row), cell / 8 + 1, GRID);
+            }
+        }
+
+        let radius = cell * 45 / 100;
+        for index in 0..BOARD_SIZE * BOARD_SIZE {
+            let pos = Pos::from_index(index);
+            let (x, y) = (center(pos.col as usize), center(pos.row as usize));
+            match board.get(pos) {
+                Stone::Black => image.fill_circle(x, y, radius, BLACK),
+                Stone::White => {
+                    image.fill_circle(x, y, radius, WHITE_RIM);
+                    image.fill_circle(x, y, radius - 1, WHITE);
+                }
+                Stone::Empty => {}
+            }
+        }
+        if let Some(pos) = last_move {
+            image.fill_circle(center(pos.col as usize), center(pos.row as usize), cell / 6 + 1, MARKER);
+        }
+        image
+    }
+
+    /// Color of the pixel at (`x`, `y`)
+    #[must_use]
+    pub fn rgb(&self, x: u32, y: u32) -> [u8; 3] {
+        PALETTE[usize::from(self.pixels[(y * self.width + x) as usize])]
+    }
+
+    fn fill_rect(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: u8) {
+        for y in y0.max(0)..=y1.min(self.height as i32 - 1) {
+            for x in x0.max(0)..=x1.min(self.width as i32 - 1) {
+                self.pixels[(y as u32 * self.width + x as u32) as usize] = color;
+            }
+        }
+    }
+
+    fn fill_circle(&mut self, cx: i32, cy: i32, radius: i32, color: u8) {
+        for y in cy - radius..=cy + radius {
+            for x in cx - radius..=cx + radius {
+                let (dx, dy) = (x - cx, y - cy);
+                if dx * dx + dy * dy <= radius * radius {
+                    self.fill_rect(x, y, x, y, color);
+                }
+            }
+        }
+    }
+
+    /// Write the image as an indexed PNG.
+    ///
+    /// # Errors
+    ///
+    /// Returns the write or encoding error.
+    pub fn write_png(&self, out: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width, self.height);
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_palette(PALETTE.concat());
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer.write_image_data(&self.pixels).map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)
+    }
+}
+
+/// One image per move of `moves` (Black first, played by `rules`), each
+/// marking its move.
+///
+/// # Errors
+///
+/// Returns an error naming the first illegal move.
+pub fn render_game(rules: RuleSet, moves: &[Pos], style: &RenderStyle) -> Result<Vec<BoardImage>, String> {
+    let boards = GameRecord::new(moves.to_vec()).boards(rules)?;
+    Ok(boards[1..].iter().zip(moves).map(|(board, &mov)| BoardImage::render(board, Some(mov), style)).collect())
+}
+
+/// Draw `moves`, played by `rules`, into `out`: an animated GIF for a `.gif` path, else one
+/// PNG per move (`move_001.png`, ...) in the directory `out`, created if
+/// needed. Returns the number of moves drawn.
+///
+/// # Errors
+///
+/// Returns an error naming the first illegal move, or the file error.
+pub fn export_game(rules: RuleSet, moves: &[Pos], out: &Path, style: &RenderStyle) -> Result<usize, String> {
+    let frames = render_game(rules, moves, style)?;
+    let io_error = |path: &Path, e: io::Error| format!("{}: {}", path.display(), e);
+    if out.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("gif")) {
+        let file = File::create(out).map_err(|e| io_error(out, e))?;
+        write_gif(&frames, style, BufWriter::new(file)).map_err(|e| io_error(out, e))?;
+    } else {
+        fs::create_dir_all(out).map_err(|e| io_error(out, e))?;
+        for (i, frame) in frames.iter().enumerate() {
+            let path = out.join(format!("move_{:03}.png", i + 1));
+            let file = File::create(&path).map_err(|e| io_error(&path, e))?;
+            frame.write_png(BufWriter::new(file)).map_err(|e| io_error(&path, e))?;
+        }
+    }
+    Ok(frames.len())
+}
+
+/// Write `frames` as a looping animated GIF, [`RenderStyle::frame_ms`]
+/// apart, holding the last one [`RenderStyle::final_frame_ms`].
+///
+/// # Errors
+///
+/// Returns the write error, or `InvalidInput` for no frames or frames of
+/// different sizes.
+pub fn write_gif(frames: &[BoardImage], style: &RenderStyle, mut out: impl Write) -> io::Result<()> {
+    let Some(first) = frames.first() else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "no frames"));
+    };
+    if frames.iter().any(|frame| (frame.width, frame.height) != (first.width, first.height)) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "frames differ in size"));
+    }
+    let (width, height) = (first.width as u16, first.height as u16);
+
+    // Header, logical screen with an 8-color global table, endless loop
+    out.write_all(b"GIF89a")?;
+    out.write_all(&width.to_le_bytes())?;
+    out.write_all(&height.to_le_bytes())?;
+    out.write_all(&[0xF2, 0, 0])?;
+    out.write_all(&PALETTE.concat())?;
+    out.write_all(b"\x21\xFF\x0BNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+    for (i, frame) in frames.iter().enumerate() {
+        let ms = if i + 1 == frames.len() { style.final_frame_ms } else { style.frame_ms };
+        let delay = (ms / 10).min(u32::from(u16::MAX)) as u16;
+        // Graphic control: the delay; image descriptor: the full screen
+        out.write_all(&[0x21, 0xF9, 4, 0])?;
+        out.write_all(&delay.to_le_bytes())?;
+        out.write_all(&[0, 0, 0x2C, 0, 0, 0, 0])?;
+        out.write_all(&width.to_le_bytes())?;
+        out.write_all(&height.to_le_bytes())?;
+        out.write_all(&[0, GIF_CODE_SIZE])?;
+        let data = weezl::encode::Encoder::new(weezl::BitOrder::Lsb, GIF_CODE_SIZE)
+            .encode(&frame.pixels)
+            .map_err(io::Error::other)?;
+        for block in data.chunks(255) {
+            out.write_all(&[block.len() as u8])?;
+            out.write_all(block)?;
+        }
+        out.write_all(&[0])?;
+    }
+    out.write_all(b"\x3B")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small() -> RenderStyle {
+        RenderStyle { cell: 8, ..RenderStyle::default() }
+    }
+
+    #[test]
+    fn test_render_draws_stones_and_marker() {
+        let mut board = Board::new();
+        board.place_stone(Pos::new(0, 0), Stone::Black);
+        board.place_stone(Pos::new(18, 18), Stone::White);
+        let image = BoardImage::render(&board, Some(Pos::new(18, 18)), &RenderStyle::default());
+        assert_eq!((image.width, image.height), (480, 480));
+
+        // Points are 24 pixels apart from (24, 24); stones have radius 10
+        // and the marker 5
+        assert_eq!(image.rgb(30, 24), PALETTE[BLACK as usize]);
+        assert_eq!(image.rgb(456 + 7, 456), PALETTE[WHITE as usize]);
+        assert_eq!(image.rgb(456 + 10, 456), PALETTE[WHITE_RIM as usize]);
+        assert_eq!(image.rgb(456, 456), PALETTE[MARKER as usize]);
+        // An empty point on the grid, and the board between lines
+        assert_eq!(image.rgb(144, 144), PALETTE[GRID as usize]);
+        assert_eq!(image.rgb(156, 156), PALETTE[BOARD as usize]);
+    }
+
+    #[test]
+    fn test_png_round_trip() {
+        let frames = render_game(RuleSet::NINUKI, &[Pos::new(9, 9), Pos::new(9, 10)], &small()).unwrap();
+        let mut png = Vec::new();
+        frames[1].write_png(&mut png).unwrap();
+
+        let mut reader = png::Decoder::new(io::Cursor::new(png)).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (frames[1].width, frames[1].height));
+        assert_eq!(pixels, frames[1].pixels);
+    }
+
+    #[test]
+    fn test_gif_frames_decode() {
+        let moves = [Pos::new(9, 9), Pos::new(9, 10), Pos::new(10, 10)];
+        let frames = render_game(RuleSet::NINUKI, &moves, &small()).unwrap();
+        let mut gif = Vec::new();
+        write_gif(&frames, &small(), &mut gif).unwrap();
+        assert!(gif.starts_with(b"GIF89a") && gif.ends_with(b"\x3B"));
+
+        // Walk the blocks after the header, screen, color table and loop
+        // extension, decoding every image
+        let mut at = 6 + 7 + 24 + 19;
+        let mut decoded = Vec::new();
+        let mut delays = Vec::new();
+        while gif[at] != 0x3B {
+            match gif[at] {
+                0x21 => {
+                    delays.push(u16::from_le_bytes([gif[at + 4], gif[at + 5]]));
+                    at += 8;
+                }
+                0x2C => {
+                    at += 10;
+                    let code_size = gif[at];
+                    at += 1;
+                    let mut data = Vec::new();
+                    while gif[at] != 0 {
+                        let len = usize::from(gif[at]);
+                        data.extend_from_slice(&gif[at + 1..at + 1 + len]);
+                        at += 1 + len;
+                    }
+                    at += 1;
+                    decoded.push(weezl::decode::Decoder::new(weezl::BitOrder::Lsb, code_size).decode(&data).unwrap());
+                }
+                other => panic!("unexpected block {:#x}", other),
+            }
+        }
+        assert_eq!(decoded, frames.iter().map(|f| f.pixels.clone()).collect::<Vec<_>>());
+        assert_eq!(delays, vec![70, 70, 300]);
+
+        assert!(write_gif(&[], &small(), &mut Vec::new()).is_err());
+        assert!(render_game(RuleSet::NINUKI, &[Pos::new(9, 9), Pos::new(9, 9)], &small()).is_err());
+    }
+}
@@ -0,0 +1,389 @@
+//! Post-game summary reports
+//!
+//! A [`GameReport`] sums a finished game up per player: accuracy and
+//! inaccuracies, mistakes and blunders from the move-quality
+//! [`GameAnnotation`], the captures made and suffered, and the average
+//! depth and time of the engine's moves from their [`MoveResult`]s, with
+//! the move that swung the game the most. It prints as Markdown
+//! ([`GameReport::to_markdown`], also its `Display`) and exports as JSON
+//! ([`GameReport::to_json`]).
+//!
+//! ```
+//! use gomoku::report::GameReport;
+//! use gomoku::rules::RuleSet;
+//! use gomoku::{Pos, Stone};
+//!
+//! let moves = [Pos::new(9, 9), Pos::new(9, 10), Pos::new(10, 10)];
+//! let report = GameReport::new(RuleSet::NINUKI, &moves).unwrap().with_scores(&[Some(0), Some(-20), Some(900)]);
+//! assert_eq!(report.player(Stone::Black).moves, 2);
+//! assert_eq!(report.biggest_swing.unwrap().index, 2);
+//! assert!(report.to_json().starts_with('{'));
+//! ```
+
+use std::fmt::{self, Write};
+
+use crate::annotate::{GameAnnotation, MoveQuality};
+use crate::board::{Pos, Stone};
+use crate::engine::{pos_to_notation, AIEngine, MoveResult};
+use crate::notation::Notation;
+use crate::record::GameRecord;
+use crate::rules::RuleSet;
+
+/// One player's side of a [`GameReport`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerReport {
+    pub color: Stone,
+    /// Moves played
+    pub moves: usize,
+    /// Average of 100 × (1 − win probability given away) over the
+    /// annotated moves (`None` without an annotation)
+    pub accuracy: Option<f64>,
+    pub inaccuracies: usize,
+    pub mistakes: usize,
+    pub blunders: usize,
+    /// Pairs this player captured
+    pub pairs_captured: u8,
+    /// Pairs the opponent captured from this player
+    pub pairs_lost: u8,
+    /// Moves that captured at least one pair
+    pub capturing_moves: usize,
+    /// Engine moves summarized by [`GameReport::with_engine_moves`]
+    pub engine_moves: usize,
+    /// Average depth of the engine moves that searched (depth > 0)
+    pub avg_depth: Option<f64>,
+    /// Average time of the engine moves, in milliseconds
+    pub avg_time_ms: Option<f64>,
+}
+
+impl PlayerReport {
+    fn new(color: Stone) -> Self {
+        Self {
+            color,
+            moves: 0,
+            accuracy: None,
+            inaccuracies: 0,
+            mistakes: 0,
+            blunders: 0,
+            pairs_captured: 0,
+            pairs_lost: 0,
+            capturing_moves: 0,
+            engine_moves: 0,
+            avg_depth: None,
+            avg_time_ms: None,
+        }
+    }
+
+    /// Share of the game's captured pairs taken by this player (`None` if
+    /// nothing was captured)
+    #[must_use]
+    pub fn capture_efficiency(&self) -> Option<f64> {
+        let total = u32::from(self.pairs_captured) + u32::from(self.pairs_lost);
+        (total > 0).then(|| f64::from(self.pairs_captured) / f64::from(total))
+    }
+}
+
+/// The largest change of Black's win probability between consecutive
+/// scored moves
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Swing {
+    /// Index of the move into the game's moves
+    pub index: usize,
+    /// Black's win probability before and after the move
+    pub before: f64,
+    pub after: f64,
+}
+
+impl Swing {
+    /// The biggest swing of `scores`, one per move from Black's side
+    /// (`None` for moves that weren't scored)
+    #[must_use]
+    pub fn find(scores: &[Option<i32>]) -> Option<Self> {
+        let mut prev: Option<f64> = None;
+        let mut best: Option<Self> = None;
+        for (index, score) in scores.iter().enumerate() {
+            let Some(score) = *score else { continue };
+            let prob = AIEngine::win_probability(score);
+            if let Some(before) = prev {
+                let swing = Self { index, before, after: prob };
+                if best.is_none_or(|best| swing.size() > best.size()) {
+                    best = Some(swing);
+                }
+            }
+            prev = Some(prob);
+        }
+        best
+    }
+
+    /// How far Black's win probability moved
+    #[must_use]
+    pub fn size(&self) -> f64 {
+        (self.after - self.before).abs()
+    }
+}
+
+/// Summary of a finished game
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameReport {
+    /// The game's moves, Black first
+    pub moves: Vec<Pos>,
+    /// Black's and White's reports
+    pub players: [PlayerReport; 2],
+    pub biggest_swing: Option<Swing>,
+}
+
+impl GameReport {
+    /// Report on `moves` (Black first), played by `rules`, from the stones
+    /// alone: moves and captures per player.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first illegal move.
+    pub fn new(rules: RuleSet, moves: &[Pos]) -> Result<Self, String> {
+        let boards = GameRecord::new(moves.to_vec()).boards(rules)?;
+        let mut players = [PlayerReport::new(Stone::Black), PlayerReport::new(Stone::White)];
+        for (i, pair) in boards.windows(2).enumerate() {
+            let color = GameRecord::color_of(i);
+            let player = &mut players[Self::slot(color)];
+            player.moves += 1;
+            if pair[1].captures(color) > pair[0].captures(color) {
+                player.capturing_moves += 1;
+            }
+        }
+        let last = boards.last().expect("boards start with the empty board");
+        for player in &mut players {
+            player.pairs_captured = last.captures(player.color);
+            player.pairs_lost = last.captures(player.color.opponent());
+        }
+        Ok(Self { moves: moves.to_vec(), players, biggest_swing: None })
+    }
+
+    /// Set the biggest swing from per-move scores from Black's side
+    /// (`None` for unscored moves), as the GUI keeps them.
+    #[must_use]
+    pub fn with_scores(mut self, scores: &[Option<i32>]) -> Self {
+        self.biggest_swing = Swing::find(scores);
+        self
+    }
+
+    /// Accuracy and move-quality counts from `annotation` of the same
+    /// moves; its scores replace any given to [`GameReport::with_scores`].
+    #[must_use]
+    pub fn with_annotation(mut self, annotation: &GameAnnotation) -> Self {
+        for player in &mut self.players {
+            let annotated: Vec<_> = annotation.moves.iter().filter(|m| m.color == player.color).collect();
+            if !annotated.is_empty() {
+                let total: f64 = annotated.iter().map(|m| 100.0 * (1.0 - m.loss())).sum();
+                player.accuracy = Some(total / annotated.len() as f64);
+            }
+            player.inaccuracies = annotation.count(player.color, MoveQuality::Inaccuracy);
+            player.mistakes = annotation.count(player.color, MoveQuality::Mistake);
+            player.blunders = annotation.count(player.color, MoveQuality::Blunder);
+        }
+        let scores: Vec<Option<i32>> = annotation
+            .moves
+            .iter()
+            .map(|m| Some(if m.color == Stone::Black { m.played_score } else { -m.played_score }))
+            .collect();
+        self.biggest_swing = Swing::find(&scores);
+        self
+    }
+
+    /// Average depth and time of `color`'s engine moves from their results.
+    #[must_use]
+    pub fn with_engine_moves<'a>(mut self, color: Stone, results: impl IntoIterator<Item = &'a MoveResult>) -> Self {
+        let player = &mut self.players[Self::slot(color)];
+        let results: Vec<&MoveResult> = results.into_iter().collect();
+        player.engine_moves = results.len();
+        let searched: Vec<_> = results.iter().filter(|r| r.depth > 0).collect();
+        player.avg_depth = (!searched.is_empty())
+            .then(|| searched.iter().map(|r| f64::from(r.depth)).sum::<f64>() / searched.len() as f64);
+        player.avg_time_ms = (!results.is_empty())
+            .then(|| results.iter().map(|r| r.time_ms as f64).sum::<f64>() / results.len() as f64);
+        self
+    }
+
+    /// `color`'s report
+    #[must_use]
+    pub fn player(&self, color: Stone) -> &PlayerReport {
+        &self.players[Self::slot(color)]
+    }
+
+    fn slot(color: Stone) -> usize {
+        usize::from(color != Stone::Black)
+    }
+
+    /// The report as a Markdown table per player and the biggest swing.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let cell = |value: Option<f64>, unit: &str| value.map_or_else(|| "-".to_string(), |v| format!("{:.1}{}", v, unit));
+        let [black, white] = &self.players;
+        let mut out = String::from("# Game report\n\n");
+        let _ = writeln!(out, "{} moves\n", self.moves.len());
+        out.push_str("| | Black | White |\n|---|---|---|\n");
+        let mut row = |name: &str, b: String, w: String| {
+            let _ = writeln!(out, "| {} | {} | {} |", name, b, w);
+        };
+        row("Moves", black.moves.to_string(), white.moves.to_string());
+        row("Accuracy", cell(black.accuracy, "%"), cell(white.accuracy, "%"));
+        row("Inaccuracies", black.inaccuracies.to_string(), white.inaccuracies.to_string());
+        row("Mistakes", black.mistakes.to_string(), white.mistakes.to_string());
+        row("Blunders", black.blunders.to_string(), white.blunders.to_string());
+        row("Pairs captured", black.pairs_captured.to_string(), white.pairs_captured.to_string());
+        row("Capturing moves", black.capturing_moves.to_string(), white.capturing_moves.to_string());
+        let efficiency = |p: &PlayerReport| cell(p.capture_efficiency().map(|e| e * 100.0), "%");
+        row("Capture efficiency", efficiency(black), efficiency(white));
+        row("Engine moves", black.engine_moves.to_string(), white.engine_moves.to_string());
+        row("Average depth", cell(black.avg_depth, ""), cell(white.avg_depth, ""));
+        row("Average time", cell(black.avg_time_ms, " ms"), cell(white.avg_time_ms, " ms"));
+        if let Some(swing) = self.biggest_swing.filter(|swing| swing.index < self.moves.len()) {
+            let _ = writeln!(
+                out,
+                "\nBiggest swing: move {} ({} {}), Black's win chance {:.0}% to {:.0}%",
+                swing.index + 1,
+                if GameRecord::color_of(swing.index) == Stone::Black { "Black" } else { "White" },
+                pos_to_notation(self.moves[swing.index]),
+                swing.before * 100.0,
+                swing.after * 100.0
+            );
+        }
+        out
+    }
+
+    /// The report as a JSON object, moves in [`Notation::DEFAULT`].
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        self.to_json_with_notation(Notation::DEFAULT)
+    }
+
+    /// The report as a JSON object, moves in `notation`.
+    #[must_use]
+    pub fn to_json_with_notation(&self, notation: Notation) -> String {
+        let num = |value: Option<f64>| value.map_or_else(|| "null".to_string(), |v| format!("{:.3}", v));
+        let player = |p: &PlayerReport| {
+            format!(
+                "{{\"moves\":{},\"accuracy\":{},\"inaccuracies\":{},\"mistakes\":{},\"blunders\":{},\
+                 \"pairs_captured\":{},\"pairs_lost\":{},\"capturing_moves\":{},\"capture_efficiency\":{},\
+                 \"engine_moves\":{},\"avg_depth\":{},\"avg_time_ms\":{}}}",
+                p.moves,
+                num(p.accuracy),
+                p.inaccuracies,
+                p.mistakes,
+                p.blunders,
+                p.pairs_captured,
+                p.pairs_lost,
+                p.capturing_moves,
+                num(p.capture_efficiency()),
+                p.engine_moves,
+                num(p.avg_depth),
+                num(p.avg_time_ms)
+            )
+        };
+        let moves: Vec<String> =
+            self.moves.iter().map(|&pos| format!("\"{}\"", notation.format(pos))).collect();
+        let swing = self.biggest_swing.filter(|swing| swing.index < self.moves.len()).map_or_else(
+            || "null".to_string(),
+            |swing| {
+                format!(
+                    "{{\"move\":{},\"pos\":\"{}\",\"before\":{:.3},\"after\":{:.3}}}",
+                    swing.index + 1,
+                    notation.format(self.moves[swing.index]),
+                    swing.before,
+                    swing.after
+                )
+            },
+        );
+        format!(
+            "{{\"moves\":[{}],\"black\":{},\"white\":{},\"biggest_swing\":{}}}",
+            moves.join(","),
+            player(&self.players[0]),
+            player(&self.players[1]),
+            swing
+        )
+    }
+}
+
+impl fmt::Display for GameReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_markdown())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::annotate::MoveAnnotation;
+    use crate::engine::SearchType;
+
+    /// Black captures White's pair at (9, 10)-(9, 11) with (9, 12)
+    fn capture_game() -> Vec<Pos> {
+        [(9, 9), (9, 10), (0, 0), (9, 11), (9, 12)].iter().map(|&(r, c)| Pos::new(r, c)).collect()
+    }
+
+    #[test]
+    fn test_report_counts_moves_and_captures() {
+        let report = GameReport::new(RuleSet::NINUKI, &capture_game()).unwrap();
+        let (black, white) = (report.player(Stone::Black), report.player(Stone::White));
+        assert_eq!((black.moves, white.moves), (3, 2));
+        assert_eq!((black.pairs_captured, black.pairs_lost, black.capturing_moves), (1, 0, 1));
+        assert_eq!(black.capture_efficiency(), Some(1.0));
+        assert_eq!(white.capture_efficiency(), Some(0.0));
+        assert_eq!(black.accuracy, None);
+        assert!(GameReport::new(RuleSet::NINUKI, &[Pos::new(9, 9), Pos::new(9, 9)]).is_err());
+    }
+
+    #[test]
+    fn test_report_from_annotation_and_results() {
+        let moves = capture_game();
+        let annotation = GameAnnotation {
+            moves: moves
+                .iter()
+                .enumerate()
+                .map(|(i, &mov)| MoveAnnotation {
+                    mov,
+                    color: GameRecord::color_of(i),
+                    best_move: if i == 3 { Some(Pos::new(9, 12)) } else { Some(mov) },
+                    best_score: 0,
+                    // White's F10 lets Black capture: Black jumps to +2000
+                    played_score: if i == 3 { -2_000 } else if i == 4 { 2_000 } else { 0 },
+                    quality: if i == 3 { MoveQuality::Blunder } else { MoveQuality::Best },
+                })
+                .collect(),
+        };
+        let result = |depth, time_ms| MoveResult {
+            best_move: None,
+            score: 0,
+            search_type: SearchType::AlphaBeta,
+            time_ms,
+            nodes: 0,
+            depth,
+            tt_usage: 0,
+            nps: 0,
+            mate_in: None,
+            threads: 1,
+            proof: None,
+        };
+        let results = [result(10, 300), result(0, 0)];
+        let report = GameReport::new(RuleSet::NINUKI, &moves)
+            .unwrap()
+            .with_annotation(&annotation)
+            .with_engine_moves(Stone::White, &results);
+
+        let white = report.player(Stone::White);
+        assert_eq!(white.blunders, 1);
+        assert!(white.accuracy.unwrap() < 100.0);
+        assert_eq!(report.player(Stone::Black).accuracy, Some(100.0));
+        assert_eq!((white.engine_moves, white.avg_depth, white.avg_time_ms), (2, Some(10.0), Some(150.0)));
+        assert_eq!(report.biggest_swing.unwrap().index, 3);
+
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("| Blunders | 0 | 1 |"), "{}", markdown);
+        assert!(markdown.contains("Biggest swing: move 4 (White"), "{}", markdown);
+        let json = report.to_json();
+        assert!(json.contains("\"white\":{\"moves\":2,"), "{}", json);
+        assert!(json.contains("\"biggest_swing\":{\"move\":4,"), "{}", json);
+
+        let bottom = Notation { origin: crate::notation::Origin::BottomLeft, skip_i: true };
+        let first = format!("\"moves\":[\"{}\",", bottom.format(moves[0]));
+        assert!(report.to_json_with_notation(bottom).starts_with(&format!("{{{}", first)));
+    }
+}
@@ -2,9 +2,18 @@
 //!
 //! Capture pattern: X-O-O-X where X is the capturing player's stone
 //! and O is the opponent's stone. Only exactly 2 stones can be captured.
+//!
+//! The group size is [`RuleSet::pair_size`](super::RuleSet::pair_size):
+//! with 3, `X-O-O-O-X` captures instead. "Pair" means a captured group of
+//! that size throughout.
 
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
 use crate::board::{Board, Pos, Stone};
 
+use super::forbidden::is_valid_move;
+use super::ruleset::{RuleSet, MAX_PAIR_SIZE};
+
 /// Direction vectors for capture checking (4 directions)
 const DIRECTIONS: [(i32, i32); 4] = [
     (0, 1),  // Horizontal →
@@ -13,6 +22,25 @@ const DIRECTIONS: [(i32, i32); 4] = [
     (1, -1), // Diagonal ↙
 ];
 
+/// The cell `k` steps from `pos` along `(dr, dc)`; the caller has checked
+/// it is on the board
+#[inline]
+fn step(pos: Pos, dr: i32, dc: i32, k: i32) -> Pos {
+    Pos::new((pos.row as i32 + dr * k) as u8, (pos.col as i32 + dc * k) as u8)
+}
+
+/// Whether `stone` at `pos` captures along `(dr, dc)`: exactly `size`
+/// opponent stones, then a `stone` (`X-O-O-X` for 2)
+#[inline]
+fn flanks(board: &Board, pos: Pos, stone: Stone, dr: i32, dc: i32, size: i32) -> bool {
+    if !Pos::is_valid(pos.row as i32 + dr * (size + 1), pos.col as i32 + dc * (size + 1)) {
+        return false;
+    }
+    let opponent = stone.opponent();
+    (1..=size).all(|k| board.get(step(pos, dr, dc, k)) == opponent)
+        && board.get(step(pos, dr, dc, size + 1)) == stone
+}
+
 /// Find positions that would be captured if stone is placed at pos.
 ///
 /// Capture pattern: X-O-O-X where X is the placed stone (at pos) and
@@ -24,41 +52,18 @@ const DIRECTIONS: [(i32, i32); 4] = [
 /// * `stone` - Color of the stone being placed
 ///
 /// # Returns
-/// Vector of positions that would be captured (always even, pairs of stones)
-pub fn get_captured_positions(board: &Board, pos: Pos, stone: Stone) -> Vec<Pos> {
+/// Vector of positions that would be captured, pair by pair
+pub fn get_captured_positions(rules: RuleSet, board: &Board, pos: Pos, stone: Stone) -> Vec<Pos> {
     let mut captured = Vec::new();
-    let opponent = stone.opponent();
+    let size = i32::from(rules.pair_size());
 
     for &(dr, dc) in &DIRECTIONS {
         // Check both directions along this line
         for sign in [-1i32, 1i32] {
             let dr = dr * sign;
             let dc = dc * sign;
-
-            // Pattern: placed_stone(pos) - opp(+1) - opp(+2) - our_stone(+3)
-            let r1 = pos.row as i32 + dr;
-            let c1 = pos.col as i32 + dc;
-            let r2 = pos.row as i32 + dr * 2;
-            let c2 = pos.col as i32 + dc * 2;
-            let r3 = pos.row as i32 + dr * 3;
-            let c3 = pos.col as i32 + dc * 3;
-
-            // Check bounds for the farthest position
-            if !Pos::is_valid(r3, c3) {
-                continue;
-            }
-
-            let pos1 = Pos::new(r1 as u8, c1 as u8);
-            let pos2 = Pos::new(r2 as u8, c2 as u8);
-            let pos3 = Pos::new(r3 as u8, c3 as u8);
-
-            // Check pattern: [placed] - opp - opp - our
-            if board.get(pos1) == opponent
-                && board.get(pos2) == opponent
-                && board.get(pos3) == stone
-            {
-                captured.push(pos1);
-                captured.push(pos2);
+            if flanks(board, pos, stone, dr, dc, size) {
+                captured.extend((1..=size).map(|k| step(pos, dr, dc, k)));
             }
         }
     }
@@ -80,15 +85,15 @@ pub fn get_captured_positions(board: &Board, pos: Pos, stone: Stone) -> Vec<Pos>
 ///
 /// # Returns
 /// Vector of positions that were captured
-pub fn execute_captures(board: &mut Board, pos: Pos, stone: Stone) -> Vec<Pos> {
-    let captured = get_captured_positions(board, pos, stone);
+pub fn execute_captures(rules: RuleSet, board: &mut Board, pos: Pos, stone: Stone) -> Vec<Pos> {
+    let captured = get_captured_positions(rules, board, pos, stone);
 
     for &cap_pos in &captured {
         board.remove_stone(cap_pos);
     }
 
     // Add capture count (pairs, not individual stones)
-    let pairs = captured.len() / 2;
+    let pairs = captured.len() / usize::from(rules.pair_size());
     board.add_captures(stone, pairs as u8);
 
     captured
@@ -98,73 +103,25 @@ pub fn execute_captures(board: &mut Board, pos: Pos, stone: Stone) -> Vec<Pos> {
 ///
 /// This is useful for quick checking without actually executing captures.
 #[inline]
-pub fn has_capture(board: &Board, pos: Pos, stone: Stone) -> bool {
-    let opponent = stone.opponent();
-
-    for &(dr, dc) in &DIRECTIONS {
-        for sign in [-1i32, 1i32] {
-            let dr = dr * sign;
-            let dc = dc * sign;
-
-            let r1 = pos.row as i32 + dr;
-            let c1 = pos.col as i32 + dc;
-            let r2 = pos.row as i32 + dr * 2;
-            let c2 = pos.col as i32 + dc * 2;
-            let r3 = pos.row as i32 + dr * 3;
-            let c3 = pos.col as i32 + dc * 3;
-
-            if !Pos::is_valid(r3, c3) {
-                continue;
-            }
-
-            let pos1 = Pos::new(r1 as u8, c1 as u8);
-            let pos2 = Pos::new(r2 as u8, c2 as u8);
-            let pos3 = Pos::new(r3 as u8, c3 as u8);
-
-            if board.get(pos1) == opponent
-                && board.get(pos2) == opponent
-                && board.get(pos3) == stone
-            {
-                return true;
-            }
-        }
-    }
-
-    false
+pub fn has_capture(rules: RuleSet, board: &Board, pos: Pos, stone: Stone) -> bool {
+    count_captures_fast(rules, board, pos, stone) > 0
 }
 
 /// Count how many pairs would be captured by a move.
 #[inline]
-pub fn count_captures(board: &Board, pos: Pos, stone: Stone) -> u8 {
-    count_captures_fast(board, pos, stone)
+pub fn count_captures(rules: RuleSet, board: &Board, pos: Pos, stone: Stone) -> u8 {
+    count_captures_fast(rules, board, pos, stone)
 }
 
 /// Count how many pairs would be captured by a move (no heap allocation).
 #[inline]
-pub fn count_captures_fast(board: &Board, pos: Pos, stone: Stone) -> u8 {
-    let opponent = stone.opponent();
+pub fn count_captures_fast(rules: RuleSet, board: &Board, pos: Pos, stone: Stone) -> u8 {
+    let size = i32::from(rules.pair_size());
     let mut pairs = 0u8;
 
     for &(dr, dc) in &DIRECTIONS {
         for sign in [-1i32, 1i32] {
-            let dr = dr * sign;
-            let dc = dc * sign;
-
-            let r3 = pos.row as i32 + dr * 3;
-            let c3 = pos.col as i32 + dc * 3;
-
-            if !Pos::is_valid(r3, c3) {
-                continue;
-            }
-
-            let pos1 = Pos::new((pos.row as i32 + dr) as u8, (pos.col as i32 + dc) as u8);
-            let pos2 = Pos::new((pos.row as i32 + dr * 2) as u8, (pos.col as i32 + dc * 2) as u8);
-            let pos3 = Pos::new(r3 as u8, c3 as u8);
-
-            if board.get(pos1) == opponent
-                && board.get(pos2) == opponent
-                && board.get(pos3) == stone
-            {
+            if flanks(board, pos, stone, dr * sign, dc * sign, size) {
                 pairs += 1;
             }
         }
@@ -173,8 +130,8 @@ pub fn count_captures_fast(board: &Board, pos: Pos, stone: Stone) -> u8 {
     pairs
 }
 
-/// Maximum captured positions per move (8 directions × 2 stones each)
-pub const MAX_CAPTURES: usize = 16;
+/// Maximum captured positions per move (8 directions × the largest group)
+pub const MAX_CAPTURES: usize = 8 * MAX_PAIR_SIZE;
 
 /// Result of capture execution without heap allocation
 #[derive(Clone, Copy)]
@@ -184,10 +141,10 @@ pub struct CaptureInfo {
     pub pairs: u8,
 }
 
-/// Execute captures and return info without heap allocation.
-/// Use with `undo_captures` for make/unmake pattern.
-pub fn execute_captures_fast(board: &mut Board, pos: Pos, stone: Stone) -> CaptureInfo {
-    let opponent = stone.opponent();
+/// [`get_captured_positions`] without heap allocation: the stones `stone`
+/// at `pos` would capture, and how many groups. The board is unchanged.
+pub fn captured_positions_fast(rules: RuleSet, board: &Board, pos: Pos, stone: Stone) -> CaptureInfo {
+    let size = i32::from(rules.pair_size());
     let mut info = CaptureInfo {
         positions: [Pos::new(0, 0); MAX_CAPTURES],
         count: 0,
@@ -199,28 +156,42 @@ pub fn execute_captures_fast(board: &mut Board, pos: Pos, stone: Stone) -> Captu
             let dr = dr * sign;
             let dc = dc * sign;
 
-            let r3 = pos.row as i32 + dr * 3;
-            let c3 = pos.col as i32 + dc * 3;
-
-            if !Pos::is_valid(r3, c3) {
-                continue;
+            if flanks(board, pos, stone, dr, dc, size) {
+                for k in 1..=size {
+                    info.positions[info.count as usize] = step(pos, dr, dc, k);
+                    info.count += 1;
+                }
+                info.pairs += 1;
             }
+        }
+    }
+
+    info
+}
 
-            let pos1 = Pos::new((pos.row as i32 + dr) as u8, (pos.col as i32 + dc) as u8);
-            let pos2 = Pos::new((pos.row as i32 + dr * 2) as u8, (pos.col as i32 + dc * 2) as u8);
-            let pos3 = Pos::new(r3 as u8, c3 as u8);
-
-            if board.get(pos1) == opponent
-                && board.get(pos2) == opponent
-                && board.get(pos3) == stone
-            {
-                let idx = info.count as usize;
-                info.positions[idx] = pos1;
-                info.positions[idx + 1] = pos2;
-                info.count += 2;
+/// Execute captures and return info without heap allocation.
+/// Use with `undo_captures` for make/unmake pattern.
+pub fn execute_captures_fast(rules: RuleSet, board: &mut Board, pos: Pos, stone: Stone) -> CaptureInfo {
+    let size = i32::from(rules.pair_size());
+    let mut info = CaptureInfo {
+        positions: [Pos::new(0, 0); MAX_CAPTURES],
+        count: 0,
+        pairs: 0,
+    };
+
+    for &(dr, dc) in &DIRECTIONS {
+        for sign in [-1i32, 1i32] {
+            let dr = dr * sign;
+            let dc = dc * sign;
+
+            if flanks(board, pos, stone, dr, dc, size) {
+                for k in 1..=size {
+                    let captured = step(pos, dr, dc, k);
+                    info.positions[info.count as usize] = captured;
+                    info.count += 1;
+                    board.remove_stone(captured);
+                }
                 info.pairs += 1;
-                board.remove_stone(pos1);
-                board.remove_stone(pos2);
             }
         }
     }
@@ -239,6 +210,65 @@ pub fn undo_captures(board: &mut Board, stone: Stone, info: &CaptureInfo) {
     board.sub_captures(stone, info.pairs);
 }
 
+/// [`see_capture`] value when the exchange reaches the capture win.
+pub const SEE_CAPTURE_WIN: i32 = 100;
+
+/// Plies of the capture-recapture chain examined by [`see_capture`].
+const SEE_MAX_PLIES: u8 = 4;
+
+/// Static exchange evaluation of `stone` playing at `pos`.
+///
+/// Plays the move, then lets the sides alternate their best capture that
+/// involves the stone just placed (taking a pair that contains it, or
+/// replaying into the cells it vacated), each side free to stop instead.
+/// Returns the net pairs won by `stone` over the chain: positive for a
+/// capture that keeps its gain, negative when the pair comes straight back
+/// with interest. Reaching the capture win counts as [`SEE_CAPTURE_WIN`].
+///
+/// `pos` must be empty; `board` is restored before returning.
+pub fn see_capture(rules: RuleSet, board: &mut Board, pos: Pos, stone: Stone) -> i32 {
+    see_exchange(rules, board, pos, stone, SEE_MAX_PLIES)
+}
+
+fn see_exchange(rules: RuleSet, board: &mut Board, pos: Pos, stone: Stone, plies: u8) -> i32 {
+    board.place_stone(pos, stone);
+    let info = execute_captures_fast(rules, board, pos, stone);
+
+    let value = if board.captures_to_win(rules, stone) == 0 {
+        SEE_CAPTURE_WIN
+    } else if plies > 1 {
+        // Any capture of a pair holding `pos` is played from 1 to pair-size
+        // cells away on one of its lines; those cells also cover the
+        // vacated ones.
+        let opponent = stone.opponent();
+        let size = i32::from(rules.pair_size());
+        let mut best_reply = 0; // the opponent may decline to recapture
+        for &(dr, dc) in &DIRECTIONS {
+            for step in (-size..=size).filter(|&k| k != 0) {
+                let r = pos.row as i32 + dr * step;
+                let c = pos.col as i32 + dc * step;
+                if !Pos::is_valid(r, c) {
+                    continue;
+                }
+                let reply = Pos::new(r as u8, c as u8);
+                if board.is_empty(reply)
+                    && count_captures_fast(rules, board, reply, opponent) > 0
+                    && is_valid_move(rules, board, reply, opponent)
+                {
+                    best_reply = best_reply.max(see_exchange(rules, board, reply, opponent, plies - 1));
+                }
+            }
+        }
+        i32::from(info.pairs) - best_reply
+    } else {
+        i32::from(info.pairs)
+    };
+
+    undo_captures(board, stone, &info);
+    board.remove_stone(pos);
+    value
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,7 +283,7 @@ mod tests {
         board.place_stone(Pos::new(9, 8), Stone::White);
         board.place_stone(Pos::new(9, 9), Stone::Black);
 
-        let captured = get_captured_positions(&board, Pos::new(9, 6), Stone::Black);
+        let captured = get_captured_positions(RuleSet::NINUKI, &board, Pos::new(9, 6), Stone::Black);
         assert_eq!(captured.len(), 2);
         assert!(captured.contains(&Pos::new(9, 7)));
         assert!(captured.contains(&Pos::new(9, 8)));
@@ -268,7 +298,7 @@ mod tests {
         board.place_stone(Pos::new(8, 9), Stone::White);
         board.place_stone(Pos::new(9, 9), Stone::Black);
 
-        let captured = get_captured_positions(&board, Pos::new(6, 9), Stone::Black);
+        let captured = get_captured_positions(RuleSet::NINUKI, &board, Pos::new(6, 9), Stone::Black);
         assert_eq!(captured.len(), 2);
         assert!(captured.contains(&Pos::new(7, 9)));
         assert!(captured.contains(&Pos::new(8, 9)));
@@ -283,7 +313,7 @@ mod tests {
         board.place_stone(Pos::new(8, 8), Stone::White);
         board.place_stone(Pos::new(9, 9), Stone::Black);
 
-        let captured = get_captured_positions(&board, Pos::new(6, 6), Stone::Black);
+        let captured = get_captured_positions(RuleSet::NINUKI, &board, Pos::new(6, 6), Stone::Black);
         assert_eq!(captured.len(), 2);
         assert!(captured.contains(&Pos::new(7, 7)));
         assert!(captured.contains(&Pos::new(8, 8)));
@@ -298,12 +328,28 @@ mod tests {
         board.place_stone(Pos::new(8, 6), Stone::White);
         board.place_stone(Pos::new(9, 5), Stone::Black);
 
-        let captured = get_captured_positions(&board, Pos::new(6, 8), Stone::Black);
+        let captured = get_captured_positions(RuleSet::NINUKI, &board, Pos::new(6, 8), Stone::Black);
         assert_eq!(captured.len(), 2);
         assert!(captured.contains(&Pos::new(7, 7)));
         assert!(captured.contains(&Pos::new(8, 6)));
     }
 
+    #[test]
+    fn test_captured_positions_fast_matches() {
+        let mut board = Board::new();
+        // Two pairs at once from (9, 9): left and down
+        for (r, c, stone) in [(9, 6, Stone::Black), (9, 7, Stone::White), (9, 8, Stone::White),
+                              (10, 9, Stone::White), (11, 9, Stone::White), (12, 9, Stone::Black)] {
+            board.place_stone(Pos::new(r, c), stone);
+        }
+        let pos = Pos::new(9, 9);
+        let info = captured_positions_fast(RuleSet::NINUKI, &board, pos, Stone::Black);
+        assert_eq!((info.count, info.pairs), (4, 2));
+        assert_eq!(info.positions[..4].to_vec(), get_captured_positions(RuleSet::NINUKI, &board, pos, Stone::Black));
+        assert!(board.get(Pos::new(9, 7)) == Stone::White, "board unchanged");
+        assert_eq!(captured_positions_fast(RuleSet::NINUKI, &board, Pos::new(0, 0), Stone::Black).count, 0);
+    }
+
     #[test]
     fn test_no_capture_single_stone() {
         let mut board = Board::new();
@@ -312,7 +358,7 @@ mod tests {
         board.place_stone(Pos::new(9, 7), Stone::White);
         board.place_stone(Pos::new(9, 8), Stone::Black);
 
-        let captured = get_captured_positions(&board, Pos::new(9, 6), Stone::Black);
+        let captured = get_captured_positions(RuleSet::NINUKI, &board, Pos::new(9, 6), Stone::Black);
         assert_eq!(captured.len(), 0);
     }
 
@@ -326,10 +372,26 @@ mod tests {
         board.place_stone(Pos::new(9, 9), Stone::White);
         board.place_stone(Pos::new(9, 10), Stone::Black);
 
-        let captured = get_captured_positions(&board, Pos::new(9, 6), Stone::Black);
+        let captured = get_captured_positions(RuleSet::NINUKI, &board, Pos::new(9, 6), Stone::Black);
         assert_eq!(captured.len(), 0);
     }
 
+    #[test]
+    fn test_flanks_three_stone_groups() {
+        let mut board = Board::new();
+        // B _ W W W B: a group of three, not a pair
+        board.place_stone(Pos::new(9, 5), Stone::Black);
+        for col in 7..10 {
+            board.place_stone(Pos::new(9, col), Stone::White);
+        }
+        board.place_stone(Pos::new(9, 10), Stone::Black);
+
+        assert!(flanks(&board, Pos::new(9, 6), Stone::Black, 0, 1, 3));
+        assert!(!flanks(&board, Pos::new(9, 6), Stone::Black, 0, 1, 2));
+        // B W W W B with the far stone off the board
+        assert!(!flanks(&board, Pos::new(9, 16), Stone::Black, 0, 1, 3));
+    }
+
     #[test]
     fn test_execute_capture() {
         let mut board = Board::new();
@@ -340,7 +402,7 @@ mod tests {
 
         // Place at 9,6 to capture
         board.place_stone(Pos::new(9, 6), Stone::Black);
-        let captured = execute_captures(&mut board, Pos::new(9, 6), Stone::Black);
+        let captured = execute_captures(RuleSet::NINUKI, &mut board, Pos::new(9, 6), Stone::Black);
 
         assert_eq!(captured.len(), 2);
         assert_eq!(board.captures(Stone::Black), 1); // 1 pair
@@ -363,7 +425,7 @@ mod tests {
         board.place_stone(Pos::new(9, 9), Stone::Black);
 
         board.place_stone(Pos::new(9, 6), Stone::Black);
-        let captured = execute_captures(&mut board, Pos::new(9, 6), Stone::Black);
+        let captured = execute_captures(RuleSet::NINUKI, &mut board, Pos::new(9, 6), Stone::Black);
 
         assert_eq!(captured.len(), 4); // 2 pairs = 4 stones
         assert_eq!(board.captures(Stone::Black), 2); // 2 pairs
@@ -377,9 +439,9 @@ mod tests {
         board.place_stone(Pos::new(9, 8), Stone::White);
         board.place_stone(Pos::new(9, 9), Stone::Black);
 
-        assert!(has_capture(&board, Pos::new(9, 6), Stone::Black));
-        assert!(!has_capture(&board, Pos::new(9, 6), Stone::White));
-        assert!(!has_capture(&board, Pos::new(0, 0), Stone::Black));
+        assert!(has_capture(RuleSet::NINUKI, &board, Pos::new(9, 6), Stone::Black));
+        assert!(!has_capture(RuleSet::NINUKI, &board, Pos::new(9, 6), Stone::White));
+        assert!(!has_capture(RuleSet::NINUKI, &board, Pos::new(0, 0), Stone::Black));
     }
 
     #[test]
@@ -393,7 +455,7 @@ mod tests {
         board.place_stone(Pos::new(9, 8), Stone::White);
         board.place_stone(Pos::new(9, 9), Stone::Black);
 
-        assert_eq!(count_captures(&board, Pos::new(9, 6), Stone::Black), 2);
+        assert_eq!(count_captures(RuleSet::NINUKI, &board, Pos::new(9, 6), Stone::Black), 2);
     }
 
     #[test]
@@ -406,7 +468,7 @@ mod tests {
         board.place_stone(Pos::new(5, 9), Stone::White);
 
         board.place_stone(Pos::new(5, 6), Stone::White);
-        let captured = execute_captures(&mut board, Pos::new(5, 6), Stone::White);
+        let captured = execute_captures(RuleSet::NINUKI, &mut board, Pos::new(5, 6), Stone::White);
 
         assert_eq!(captured.len(), 2);
         assert_eq!(board.captures(Stone::White), 1);
@@ -423,7 +485,7 @@ mod tests {
         board.place_stone(Pos::new(0, 3), Stone::White);
         board.place_stone(Pos::new(0, 4), Stone::Black);
 
-        let captured = get_captured_positions(&board, Pos::new(0, 1), Stone::Black);
+        let captured = get_captured_positions(RuleSet::NINUKI, &board, Pos::new(0, 1), Stone::Black);
         assert_eq!(captured.len(), 2);
     }
 
@@ -435,7 +497,7 @@ mod tests {
         board.place_stone(Pos::new(0, 1), Stone::White);
 
         // Checking capture at edge should not panic
-        let captured = get_captured_positions(&board, Pos::new(0, 2), Stone::Black);
+        let captured = get_captured_positions(RuleSet::NINUKI, &board, Pos::new(0, 2), Stone::Black);
         assert_eq!(captured.len(), 0);
     }
 
@@ -467,10 +529,60 @@ mod tests {
         board.place_stone(Pos::new(12, 9), Stone::Black);
 
         board.place_stone(center, Stone::Black);
-        let captured = execute_captures(&mut board, center, Stone::Black);
+        let captured = execute_captures(RuleSet::NINUKI, &mut board, center, Stone::Black);
 
         // Should capture 4 pairs = 8 stones
         assert_eq!(captured.len(), 8);
         assert_eq!(board.captures(Stone::Black), 4);
     }
+
+    /// B _ W W B on row 9: Black captures at (9, 6)
+    fn see_setup() -> Board {
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 5), Stone::Black);
+        board.place_stone(Pos::new(9, 7), Stone::White);
+        board.place_stone(Pos::new(9, 8), Stone::White);
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board
+    }
+
+    #[test]
+    fn test_see_clean_capture() {
+        let mut board = see_setup();
+        let before = board.clone();
+        assert_eq!(see_capture(RuleSet::NINUKI, &mut board, Pos::new(9, 6), Stone::Black), 1);
+        assert_eq!(board.bitboards(), before.bitboards());
+        assert_eq!(board.captures(Stone::Black), 0);
+    }
+
+    #[test]
+    fn test_see_even_trade() {
+        let mut board = see_setup();
+        // The capturing stone forms a vertical pair White can take from (8, 6)
+        board.place_stone(Pos::new(10, 6), Stone::Black);
+        board.place_stone(Pos::new(11, 6), Stone::White);
+        assert_eq!(see_capture(RuleSet::NINUKI, &mut board, Pos::new(9, 6), Stone::Black), 0);
+    }
+
+    #[test]
+    fn test_see_losing_capture() {
+        let mut board = see_setup();
+        board.place_stone(Pos::new(10, 6), Stone::Black);
+        board.place_stone(Pos::new(11, 6), Stone::White);
+        // White's recapture at (8, 6) also takes a second pair on row 8
+        board.place_stone(Pos::new(8, 7), Stone::Black);
+        board.place_stone(Pos::new(8, 8), Stone::Black);
+        board.place_stone(Pos::new(8, 9), Stone::White);
+        assert_eq!(see_capture(RuleSet::NINUKI, &mut board, Pos::new(9, 6), Stone::Black), -1);
+    }
+
+    #[test]
+    fn test_see_capture_win() {
+        let mut board = see_setup();
+        board.add_captures(Stone::Black, 4);
+        board.place_stone(Pos::new(10, 6), Stone::Black);
+        board.place_stone(Pos::new(11, 6), Stone::White);
+        // Winning by the 5th pair ends the exchange
+        assert_eq!(see_capture(RuleSet::NINUKI, &mut board, Pos::new(9, 6), Stone::Black), SEE_CAPTURE_WIN);
+    }
 }
@@ -1,12 +1,23 @@
-//! Double-three forbidden move rules for Gomoku
+//! Forbidden move rules for Gomoku
 //!
 //! A double-three is a move that creates two or more free-threes simultaneously.
 //! Free-three: 3 stones in a row with both ends open, that can become an
 //! unstoppable open-four if not blocked.
 //!
 //! Exception: Double-three via capture IS allowed.
+//!
+//! Under the Renju rule set ([`Forbidden::Renju`]) only Black is restricted,
+//! and double-fours and overlines are forbidden too. A three there is a line
+//! that can become a straight four (`_XXXX_`) with a move that is not itself
+//! a foul, so deciding a double-three may recurse.
+
+use core::fmt;
 
-use crate::board::{Board, Pos, Stone};
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::board::{Bitboard, Board, Pos, Stone, BOARD_SIZE};
+
+use super::ruleset::{Forbidden, RuleSet};
 
 use super::capture::has_capture;
 #[cfg(test)]
@@ -330,21 +341,161 @@ pub fn count_free_threes(board: &Board, pos: Pos, stone: Stone) -> u8 {
 ///
 /// # Returns
 /// `true` if the move is a forbidden double-three, `false` otherwise
-pub fn is_double_three(board: &Board, pos: Pos, stone: Stone) -> bool {
+pub fn is_double_three(rules: RuleSet, board: &Board, pos: Pos, stone: Stone) -> bool {
     // Exception: if this move captures, double-three is allowed
     // Use has_capture (no Vec allocation) instead of get_captured_positions
-    if has_capture(board, pos, stone) {
+    if has_capture(rules, board, pos, stone) {
         return false;
     }
 
     count_free_threes(board, pos, stone) >= 2
 }
 
+/// Why a move is forbidden
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Foul {
+    DoubleThree,
+    DoubleFour,
+    Overline,
+}
+
+impl fmt::Display for Foul {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::DoubleThree => "Double-three",
+            Self::DoubleFour => "Double-four",
+            Self::Overline => "Overline",
+        })
+    }
+}
+
+/// Why `stone` may not play the empty point `pos` under `rules`, or `None`
+/// if it may.
+pub fn foul(rules: RuleSet, board: &Board, pos: Pos, stone: Stone) -> Option<Foul> {
+    match rules.forbidden() {
+        Forbidden::DoubleThree => is_double_three(rules, board, pos, stone).then_some(Foul::DoubleThree),
+        Forbidden::Renju if stone == Stone::Black => renju_foul(board.bitboards().0, board.occupancy(), pos),
+        Forbidden::Renju => None,
+    }
+}
+
+/// Cell `k` steps from `pos` along `(dr, dc)`, if on the board
+fn step(pos: Pos, dr: i32, dc: i32, k: i32) -> Option<Pos> {
+    let r = pos.row as i32 + dr * k;
+    let c = pos.col as i32 + dc * k;
+    #[allow(clippy::cast_sign_loss)]
+    Pos::is_valid(r, c).then(|| Pos::new(r as u8, c as u8))
+}
+
+/// Offsets (from `pos`) of the first and last stone of the unbroken run of
+/// `black` through `pos` along `(dr, dc)`
+fn run(black: Bitboard, pos: Pos, dr: i32, dc: i32) -> (i32, i32) {
+    let extent = |sign: i32| {
+        (1..).take_while(|&k| step(pos, dr, dc, sign * k).is_some_and(|p| black.get(p))).count() as i32
+    };
+    (-extent(-1), extent(1))
+}
+
+/// Empty points on the line through `pos` that give Black exactly five
+/// with the stone at `pos`
+fn five_points(black: Bitboard, occupied: Bitboard, pos: Pos, dr: i32, dc: i32) -> Vec<i32> {
+    (-4..=4)
+        .filter(|&k| k != 0)
+        .filter(|&k| {
+            step(pos, dr, dc, k).is_some_and(|q| {
+                if occupied.get(q) {
+                    return false;
+                }
+                let mut with_q = black;
+                with_q.set(q);
+                let (lo, hi) = run(with_q, pos, dr, dc);
+                hi - lo + 1 == 5 && (lo..=hi).contains(&k)
+            })
+        })
+        .collect()
+}
+
+/// Points that turn the line through `pos` into a straight four: four in
+/// a row containing `pos`, both ends empty, and each end an exact five
+fn straight_four_points(black: Bitboard, occupied: Bitboard, pos: Pos, dr: i32, dc: i32) -> Vec<Pos> {
+    let empty = |k: i32| step(pos, dr, dc, k).is_some_and(|p| !occupied.get(p));
+    let not_black = |k: i32| step(pos, dr, dc, k).is_none_or(|p| !black.get(p));
+    (-3..=3)
+        .filter(|&k| k != 0 && empty(k))
+        .filter(|&k| {
+            let mut with_q = black;
+            with_q.set(step(pos, dr, dc, k).expect("checked by empty"));
+            let (lo, hi) = run(with_q, pos, dr, dc);
+            hi - lo + 1 == 4
+                && (lo..=hi).contains(&k)
+                && empty(lo - 1)
+                && empty(hi + 1)
+                && not_black(lo - 2)
+                && not_black(hi + 2)
+        })
+        .filter_map(|k| step(pos, dr, dc, k))
+        .collect()
+}
+
+/// Renju foul of a Black stone at the empty point `pos`, given Black's
+/// stones and all occupied points
+fn renju_foul(black: Bitboard, occupied: Bitboard, pos: Pos) -> Option<Foul> {
+    let mut black = black;
+    let mut occupied = occupied;
+    black.set(pos);
+    occupied.set(pos);
+
+    let mut overline = false;
+    let mut fours = 0;
+    let mut three_points: Vec<Vec<Pos>> = Vec::new();
+    for &(dr, dc) in &DIRECTIONS {
+        let (lo, hi) = run(black, pos, dr, dc);
+        match hi - lo + 1 {
+            5 => return None, // Five wins even if it is also a foul
+            6.. => overline = true,
+            _ => {}
+        }
+        let points = five_points(black, occupied, pos, dr, dc);
+        // A straight four has two five points, five apart; anything else
+        // with two points is two fours in one line (`X_XXX_X`)
+        let line_fours = match points.as_slice() {
+            [] => 0,
+            [a, b] if b - a == 5 => 1,
+            _ => points.len(),
+        };
+        fours += line_fours;
+        if line_fours == 0 {
+            let points = straight_four_points(black, occupied, pos, dr, dc);
+            if !points.is_empty() {
+                three_points.push(points);
+            }
+        }
+    }
+
+    if overline {
+        return Some(Foul::Overline);
+    }
+    if fours >= 2 {
+        return Some(Foul::DoubleFour);
+    }
+    if three_points.len() < 2 {
+        return None;
+    }
+    // A three only counts if one of its straight fours can be played
+    let real_threes = three_points
+        .iter()
+        .filter(|points| points.iter().any(|&q| renju_foul(black, occupied, q).is_none()))
+        .count();
+    (real_threes >= 2).then_some(Foul::DoubleThree)
+}
+
 /// Check if a move is valid (not forbidden)
 ///
 /// A move is valid if:
 /// 1. The position is empty
-/// 2. It doesn't create a double-three (unless it captures)
+/// 2. It isn't a [`foul`] under `rules` (in Ninuki-renju, a
+///    double-three that doesn't capture)
 ///
 /// # Arguments
 /// * `board` - Current board state
@@ -353,18 +504,38 @@ pub fn is_double_three(board: &Board, pos: Pos, stone: Stone) -> bool {
 ///
 /// # Returns
 /// `true` if the move is valid, `false` if forbidden
-pub fn is_valid_move(board: &Board, pos: Pos, stone: Stone) -> bool {
+pub fn is_valid_move(rules: RuleSet, board: &Board, pos: Pos, stone: Stone) -> bool {
     // Must be empty
     if !board.is_empty(pos) {
         return false;
     }
 
-    // Must not be double-three (unless capture exception applies)
-    if is_double_three(board, pos, stone) {
-        return false;
-    }
+    foul(rules, board, pos, stone).is_none()
+}
 
-    true
+/// Legal moves for `color`: empty points that are not forbidden, in
+/// row-major order.
+///
+/// # Example
+///
+/// ```
+/// use gomoku::rules::{legal_moves, RuleSet};
+/// use gomoku::{Board, Pos, Stone};
+///
+/// let mut board = Board::new();
+/// board.place_stone(Pos::new(9, 9), Stone::Black);
+/// assert_eq!(legal_moves(RuleSet::NINUKI, &board, Stone::White).len(), 19 * 19 - 1);
+/// ```
+#[must_use]
+pub fn legal_moves(rules: RuleSet, board: &Board, color: Stone) -> Vec<Pos> {
+    legal_moves_iter(rules, board, color).collect()
+}
+
+/// Lazy [`legal_moves`], for callers that stop at the first match
+pub fn legal_moves_iter(rules: RuleSet, board: &Board, color: Stone) -> impl Iterator<Item = Pos> + '_ {
+    (0..BOARD_SIZE as u8)
+        .flat_map(|r| (0..BOARD_SIZE as u8).map(move |c| Pos::new(r, c)))
+        .filter(move |&pos| is_valid_move(rules, board, pos, color))
 }
 
 #[cfg(test)]
@@ -375,20 +546,20 @@ mod tests {
     fn test_not_double_three_empty_board() {
         let board = Board::new();
         // Empty board - no double three possible
-        assert!(!is_double_three(&board, Pos::new(9, 9), Stone::Black));
+        assert!(!is_double_three(RuleSet::NINUKI, &board, Pos::new(9, 9), Stone::Black));
     }
 
     #[test]
     fn test_valid_move_empty_pos() {
         let board = Board::new();
-        assert!(is_valid_move(&board, Pos::new(9, 9), Stone::Black));
+        assert!(is_valid_move(RuleSet::NINUKI, &board, Pos::new(9, 9), Stone::Black));
     }
 
     #[test]
     fn test_invalid_move_occupied() {
         let mut board = Board::new();
         board.place_stone(Pos::new(9, 9), Stone::Black);
-        assert!(!is_valid_move(&board, Pos::new(9, 9), Stone::White));
+        assert!(!is_valid_move(RuleSet::NINUKI, &board, Pos::new(9, 9), Stone::White));
     }
 
     #[test]
@@ -453,11 +624,11 @@ mod tests {
 
         // Placing at (9,9) should be double-three
         assert!(
-            is_double_three(&board, Pos::new(9, 9), Stone::Black),
+            is_double_three(RuleSet::NINUKI, &board, Pos::new(9, 9), Stone::Black),
             "Cross pattern should be double-three"
         );
         assert!(
-            !is_valid_move(&board, Pos::new(9, 9), Stone::Black),
+            !is_valid_move(RuleSet::NINUKI, &board, Pos::new(9, 9), Stone::Black),
             "Double-three should be invalid move"
         );
     }
@@ -485,7 +656,7 @@ mod tests {
         let free_threes = count_free_threes(&board, Pos::new(9, 9), Stone::Black);
         assert_eq!(free_threes, 2, "Should create 2 diagonal free-threes");
         assert!(
-            is_double_three(&board, Pos::new(9, 9), Stone::Black),
+            is_double_three(RuleSet::NINUKI, &board, Pos::new(9, 9), Stone::Black),
             "Diagonal cross should be double-three"
         );
     }
@@ -501,11 +672,11 @@ mod tests {
         let free_threes = count_free_threes(&board, Pos::new(9, 9), Stone::Black);
         assert_eq!(free_threes, 1, "Should be exactly 1 free-three");
         assert!(
-            !is_double_three(&board, Pos::new(9, 9), Stone::Black),
+            !is_double_three(RuleSet::NINUKI, &board, Pos::new(9, 9), Stone::Black),
             "Single free-three should not be double-three"
         );
         assert!(
-            is_valid_move(&board, Pos::new(9, 9), Stone::Black),
+            is_valid_move(RuleSet::NINUKI, &board, Pos::new(9, 9), Stone::Black),
             "Single free-three should be valid"
         );
     }
@@ -559,12 +730,12 @@ mod tests {
         // After capture, W W removed, so horizontal pattern changes
 
         // First verify capture exists
-        let captures = get_captured_positions(&board2, Pos::new(9, 6), Stone::Black);
+        let captures = get_captured_positions(RuleSet::NINUKI, &board2, Pos::new(9, 6), Stone::Black);
         assert_eq!(captures.len(), 2, "Should capture 2 stones");
 
         // Even if this creates free-threes, it's allowed because of capture
         assert!(
-            !is_double_three(&board2, Pos::new(9, 6), Stone::Black),
+            !is_double_three(RuleSet::NINUKI, &board2, Pos::new(9, 6), Stone::Black),
             "Double-three with capture should be allowed"
         );
     }
@@ -735,11 +906,11 @@ mod tests {
             "H10 should create 2 free-threes (horizontal F10-G10-H10, vertical H10-H11-H12)"
         );
         assert!(
-            is_double_three(&board, pos, Stone::Black),
+            is_double_three(RuleSet::NINUKI, &board, pos, Stone::Black),
             "H10 should be a forbidden double-three"
         );
         assert!(
-            !is_valid_move(&board, pos, Stone::Black),
+            !is_valid_move(RuleSet::NINUKI, &board, pos, Stone::Black),
             "H10 should be an invalid move"
         );
     }
@@ -781,8 +952,76 @@ mod tests {
         let free_threes = count_free_threes(&board, Pos::new(9, 9), Stone::Black);
         assert!(free_threes >= 2, "Should be at least double-three");
         assert!(
-            is_double_three(&board, Pos::new(9, 9), Stone::Black),
+            is_double_three(RuleSet::NINUKI, &board, Pos::new(9, 9), Stone::Black),
             "Triple free-three is still forbidden"
         );
     }
+
+    #[test]
+    fn test_legal_moves_skip_occupied_and_forbidden() {
+        // K10 would give Black two open threes
+        let mut board = Board::new();
+        for pos in [Pos::new(9, 7), Pos::new(9, 8), Pos::new(7, 9), Pos::new(8, 9)] {
+            board.place_stone(pos, Stone::Black);
+        }
+        let black = legal_moves(RuleSet::NINUKI, &board, Stone::Black);
+        assert_eq!(black.len(), 19 * 19 - 5);
+        assert!(!black.contains(&Pos::new(9, 9)) && !black.contains(&Pos::new(9, 7)));
+        assert!(legal_moves(RuleSet::NINUKI, &board, Stone::White).contains(&Pos::new(9, 9)));
+        assert_eq!(legal_moves_iter(RuleSet::NINUKI, &board, Stone::Black).next(), Some(Pos::new(0, 0)));
+        assert!(black.windows(2).all(|w| (w[0].row, w[0].col) < (w[1].row, w[1].col)));
+    }
+
+    /// Renju foul of Black at `pos` with Black stones `black` and White
+    /// stones `white`
+    fn renju(black: &[(u8, u8)], white: &[(u8, u8)], pos: (u8, u8)) -> Option<Foul> {
+        let mut board = Board::new();
+        for &(r, c) in black {
+            board.place_stone(Pos::new(r, c), Stone::Black);
+        }
+        for &(r, c) in white {
+            board.place_stone(Pos::new(r, c), Stone::White);
+        }
+        renju_foul(board.bitboards().0, board.occupancy(), Pos::new(pos.0, pos.1))
+    }
+
+    #[test]
+    fn test_renju_overline_and_five() {
+        // Six in a row is a foul, exactly five always wins
+        assert_eq!(renju(&[(9, 3), (9, 4), (9, 5), (9, 7), (9, 8)], &[], (9, 6)), Some(Foul::Overline));
+        assert_eq!(renju(&[(9, 4), (9, 5), (9, 7), (9, 8)], &[], (9, 6)), None);
+        // A five that is also a double-four is still a five
+        let black = [(9, 4), (9, 5), (9, 7), (9, 8), (6, 6), (7, 6), (8, 6)];
+        assert_eq!(renju(&black, &[], (9, 6)), None);
+    }
+
+    #[test]
+    fn test_renju_double_four() {
+        // Fours on row 10 and column J meeting at J10
+        let black = [(9, 5), (9, 6), (9, 7), (6, 8), (7, 8), (8, 8)];
+        assert_eq!(renju(&black, &[], (9, 8)), Some(Foul::DoubleFour));
+        // A four blocked at one end still counts
+        assert_eq!(renju(&black, &[(9, 4)], (9, 8)), Some(Foul::DoubleFour));
+        // Two fours in one line: X_X(X)X_X
+        assert_eq!(renju(&[(9, 4), (9, 6), (9, 8), (9, 10)], &[], (9, 7)), Some(Foul::DoubleFour));
+        // Four-three is allowed
+        assert_eq!(renju(&[(9, 6), (9, 7), (9, 8), (8, 9), (10, 9)], &[], (9, 9)), None);
+    }
+
+    #[test]
+    fn test_renju_double_three_is_recursive() {
+        // Split three on row 10 (H10 . K10 L10, J10 completes the straight
+        // four) crossing a three on column K
+        let cross = [(9, 7), (9, 10), (8, 9), (10, 9)];
+        assert_eq!(renju(&cross, &[], (9, 9)), Some(Foul::DoubleThree));
+        // A blocked three doesn't count
+        assert_eq!(renju(&cross, &[(9, 6)], (9, 9)), None);
+
+        // J10 becomes an overline point on column J: the row-10 three can
+        // never be a straight four, so K10 is only one three
+        let mut black = cross.to_vec();
+        black.extend([(5, 8), (6, 8), (7, 8), (8, 8), (10, 8)]);
+        assert_eq!(renju(&black, &[], (9, 8)), Some(Foul::Overline));
+        assert_eq!(renju(&black, &[], (9, 9)), None);
+    }
 }
@@ -3,19 +3,33 @@
 //! This module implements the rule set for Gomoku including:
 //! - Capture rules (pair capture)
 //! - Win conditions (5-in-a-row, capture win)
-//! - Forbidden moves (double-three)
+//! - Forbidden moves (double-three, or the Renju fouls)
+//! - Rule-set selection
+//! - Position legality, and the move between two positions
+//! - Threat queries (fours, open threes, capture threats)
 
 pub mod capture;
 pub mod forbidden;
+pub mod position;
+pub mod ruleset;
+pub mod threats;
 pub mod win;
 
 // Re-exports for convenient access
 pub use capture::{
-    count_captures, count_captures_fast, execute_captures, execute_captures_fast,
-    get_captured_positions, has_capture, undo_captures, CaptureInfo,
+    captured_positions_fast, count_captures, count_captures_fast, execute_captures, execute_captures_fast,
+    get_captured_positions, has_capture, see_capture, undo_captures, CaptureInfo,
+    SEE_CAPTURE_WIN,
+};
+pub use forbidden::{
+    count_free_threes, foul, is_double_three, is_valid_move, legal_moves, legal_moves_iter, Foul,
+};
+pub use position::{infer_move, infer_move_with_notation, validate_position, InferredMove, PositionError};
+pub use ruleset::{Forbidden, RuleSet, MAX_CAPTURE_WIN_PAIRS, MAX_PAIR_SIZE};
+pub use threats::{
+    find_capture_threats, find_fours, find_open_threes, find_threats, Threat, ThreatKind,
 };
-pub use forbidden::{count_free_threes, is_double_three, is_valid_move};
 pub use win::{
     can_break_five_by_capture, check_winner, find_five_break_moves, find_five_line_at_pos,
-    find_five_positions, has_five_at_pos, has_five_in_row,
+    find_five_positions, has_five_at_pos, has_five_in_row, winner_after_move,
 };
@@ -0,0 +1,291 @@
+//! Position legality
+//!
+//! A position from a protocol, a file or the board editor can be one no
+//! game reaches. [`validate_position`] reports every reason it finds:
+//! - Move counts: every stone ever placed is on the board or was captured,
+//!   so Black (moving first) has played as many moves as White or one more
+//! - Capture counts beyond the capture win, or both sides at it
+//! - Fives for both sides
+//!
+//! Handicap positions break the move-count check on purpose, so callers
+//! decide which errors to enforce.
+//!
+//! Protocols that send whole boards instead of moves (such as the pbrain
+//! `BOARD` command) are bridged by [`infer_move`], which finds the move,
+//! captures included, between two snapshots.
+
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::board::{Board, Change, Pos, Stone};
+use crate::notation::{notation, Notation};
+
+use super::capture::execute_captures;
+use super::ruleset::RuleSet;
+use super::win::find_five_positions;
+
+/// Why a position can't come from a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PositionError {
+    /// Stones placed by each side, counting captured ones, don't alternate
+    /// from Black
+    MoveCounts { black: u32, white: u32 },
+    /// `color` has captured more pairs than the `max` that win the game
+    TooManyCaptures { color: Stone, pairs: u8, max: u8 },
+    /// Both sides have reached the capture win; the game ends at the first
+    BothCaptureWins,
+    /// Both sides have five in a row
+    BothFives,
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::MoveCounts { black, white } => write!(
+                f,
+                "Black has played {} stones and White {} (captured ones included); Black must have played as many or one more",
+                black, white
+            ),
+            Self::TooManyCaptures { color, pairs, max } => write!(
+                f,
+                "{:?} has captured {} pairs, more than the {} that win",
+                color, pairs, max
+            ),
+            Self::BothCaptureWins => write!(f, "both sides have reached the capture win"),
+            Self::BothFives => write!(f, "both sides have five in a row"),
+        }
+    }
+}
+
+impl core::error::Error for PositionError {}
+
+/// Check that `board` can arise in a game under `rules`.
+///
+/// # Errors
+///
+/// Returns every [`PositionError`] found, in the order they are listed.
+pub fn validate_position(rules: RuleSet, board: &Board) -> Result<(), Vec<PositionError>> {
+    let mut errors = Vec::new();
+    let pair_size = u32::from(rules.pair_size());
+    let win_pairs = rules.capture_win_pairs();
+
+    // Stones each side placed: on the board, or taken by the other side
+    let (black_stones, white_stones) = board.bitboards();
+    let black = black_stones.count() + pair_size * u32::from(board.captures(Stone::White));
+    let white = white_stones.count() + pair_size * u32::from(board.captures(Stone::Black));
+    if !(white..=white + 1).contains(&black) {
+        errors.push(PositionError::MoveCounts { black, white });
+    }
+
+    for color in [Stone::Black, Stone::White] {
+        let pairs = board.captures(color);
+        if pairs > win_pairs {
+            errors.push(PositionError::TooManyCaptures { color, pairs, max: win_pairs });
+        }
+    }
+    if board.captures_to_win(rules, Stone::Black) == 0 && board.captures_to_win(rules, Stone::White) == 0 {
+        errors.push(PositionError::BothCaptureWins);
+    }
+
+    if find_five_positions(rules, board, Stone::Black).is_some()
+        && find_five_positions(rules, board, Stone::White).is_some()
+    {
+        errors.push(PositionError::BothFives);
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// The move that leads from one position to another
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InferredMove {
+    pub pos: Pos,
+    pub stone: Stone,
+    /// Opponent stones the move captured, pair by pair
+    pub captured: Vec<Pos>,
+}
+
+/// Reconstruct the single move, with the captures it made under `rules`,
+/// that turns `prev` into `next`. Only the stones are compared:
+/// whole-board protocols don't carry capture counts, and the move isn't
+/// checked for legality.
+///
+/// # Errors
+///
+/// Returns a message if no stone or more than one was added, or if the
+/// stones that disappeared aren't exactly the ones the move captures,
+/// naming the points in the process-wide [`notation()`].
+///
+/// ```
+/// use gomoku::rules::{infer_move, RuleSet};
+/// use gomoku::{Board, Pos, Stone};
+///
+/// // White plays J10 against the Black pair K10-L10, flanked by M10
+/// let mut prev = Board::new();
+/// prev.place_stone(Pos::new(9, 9), Stone::Black);
+/// prev.place_stone(Pos::new(9, 10), Stone::Black);
+/// prev.place_stone(Pos::new(9, 11), Stone::White);
+/// let mut next = prev.clone();
+/// next.place_stone(Pos::new(9, 8), Stone::White);
+/// next.remove_stone(Pos::new(9, 9));
+/// next.remove_stone(Pos::new(9, 10));
+///
+/// let mov = infer_move(RuleSet::NINUKI, &prev, &next).unwrap();
+/// assert_eq!((mov.pos, mov.stone), (Pos::new(9, 8), Stone::White));
+/// assert_eq!(mov.captured, vec![Pos::new(9, 9), Pos::new(9, 10)]);
+/// ```
+pub fn infer_move(rules: RuleSet, prev: &Board, next: &Board) -> Result<InferredMove, String> {
+    infer_move_with_notation(rules, prev, next, notation())
+}
+
+/// [`infer_move`], naming the points in its errors in `notation`.
+///
+/// # Errors
+///
+/// As [`infer_move`].
+pub fn infer_move_with_notation(
+    rules: RuleSet,
+    prev: &Board,
+    next: &Board,
+    notation: Notation,
+) -> Result<InferredMove, String> {
+    let changes = prev.diff(next);
+    let mut added = changes.iter().filter(|&&(_, _, change)| change == Change::Added);
+    let (pos, stone) = match (added.next(), added.count()) {
+        (None, _) => return Err("no stone was added".to_string()),
+        (Some(&(pos, stone, _)), 0) => (pos, stone),
+        (Some(_), more) => return Err(format!("{} stones were added, not one", more + 1)),
+    };
+
+    let mut board = prev.clone();
+    board.place_stone(pos, stone);
+    let captured = execute_captures(rules, &mut board, pos, stone);
+    if board.bitboards() != next.bitboards() {
+        let removed = changes.iter().filter(|&&(_, _, change)| change == Change::Removed).map(|&(p, _, _)| p);
+        return Err(format!(
+            "{:?} at {} captures {}, but {} disappeared",
+            stone,
+            notation.format(pos),
+            point_list(captured.iter().copied(), notation),
+            point_list(removed, notation),
+        ));
+    }
+    Ok(InferredMove { pos, stone, captured })
+}
+
+/// `K10 L10`, or `nothing`
+fn point_list(points: impl Iterator<Item = Pos>, notation: Notation) -> String {
+    let names: Vec<String> = points.map(|pos| notation.format(pos)).collect();
+    if names.is_empty() {
+        "nothing".to_string()
+    } else {
+        names.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_positions_are_valid() {
+        let mut board = Board::new();
+        assert_eq!(validate_position(RuleSet::NINUKI, &board), Ok(()));
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        assert_eq!(validate_position(RuleSet::NINUKI, &board), Ok(()));
+
+        // Three Black stones; one White stone and a pair Black captured
+        board.place_stone(Pos::new(9, 11), Stone::Black);
+        board.place_stone(Pos::new(0, 0), Stone::Black);
+        board.place_stone(Pos::new(1, 1), Stone::White);
+        board.set_captures(RuleSet::NINUKI, Stone::Black, 1).unwrap();
+        assert_eq!(validate_position(RuleSet::NINUKI, &board), Ok(()));
+    }
+
+    #[test]
+    fn test_impossible_positions() {
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::White);
+        assert_eq!(
+            validate_position(RuleSet::NINUKI, &board),
+            Err(vec![PositionError::MoveCounts { black: 0, white: 1 }])
+        );
+
+        // Fives for both, and captures that don't match the stones
+        let mut board = Board::new();
+        for col in 0..5 {
+            board.place_stone(Pos::new(0, col), Stone::Black);
+            board.place_stone(Pos::new(5, col), Stone::White);
+        }
+        board.set_captures(RuleSet::NINUKI, Stone::Black, 2).unwrap();
+        let errors = validate_position(RuleSet::NINUKI, &board).unwrap_err();
+        assert_eq!(errors, vec![PositionError::MoveCounts { black: 5, white: 9 }, PositionError::BothFives]);
+        assert!(errors[0].to_string().contains("captured ones included"));
+
+        let mut board = Board::new();
+        board.set_captures(RuleSet::NINUKI, Stone::Black, 5).unwrap();
+        board.add_captures(Stone::White, 6);
+        let errors = validate_position(RuleSet::NINUKI, &board).unwrap_err();
+        assert!(errors.contains(&PositionError::TooManyCaptures { color: Stone::White, pairs: 6, max: 5 }));
+        assert!(errors.contains(&PositionError::BothCaptureWins));
+    }
+
+    #[test]
+    fn test_infer_move() {
+        let mut prev = Board::new();
+        prev.place_stone(Pos::new(9, 9), Stone::Black);
+        let mut next = prev.clone();
+        next.place_stone(Pos::new(10, 10), Stone::White);
+        let mov = infer_move(RuleSet::NINUKI, &prev, &next).unwrap();
+        assert_eq!(mov, InferredMove { pos: Pos::new(10, 10), stone: Stone::White, captured: vec![] });
+
+        // Two pairs at once, up and to the left of the move
+        let mut prev = Board::new();
+        for (row, col, stone) in [(5, 5, Stone::Black), (5, 3, Stone::White), (5, 4, Stone::White)] {
+            prev.place_stone(Pos::new(row, col), stone);
+        }
+        for (row, col, stone) in [(2, 2, Stone::Black), (3, 2, Stone::White), (4, 2, Stone::White)] {
+            prev.place_stone(Pos::new(row, col), stone);
+        }
+        let mut next = prev.clone();
+        next.place_stone(Pos::new(5, 2), Stone::Black);
+        for pos in [Pos::new(5, 3), Pos::new(5, 4), Pos::new(3, 2), Pos::new(4, 2)] {
+            next.remove_stone(pos);
+        }
+        let mov = infer_move(RuleSet::NINUKI, &prev, &next).unwrap();
+        assert_eq!((mov.pos, mov.stone, mov.captured.len()), (Pos::new(5, 2), Stone::Black, 4));
+    }
+
+    #[test]
+    fn test_infer_move_rejects_unreachable_positions() {
+        let mut prev = Board::new();
+        prev.place_stone(Pos::new(9, 9), Stone::Black);
+        assert_eq!(infer_move(RuleSet::NINUKI, &prev, &prev), Err("no stone was added".to_string()));
+
+        let mut next = prev.clone();
+        next.place_stone(Pos::new(0, 0), Stone::White);
+        next.place_stone(Pos::new(0, 1), Stone::Black);
+        assert!(infer_move(RuleSet::NINUKI, &prev, &next).unwrap_err().contains("2 stones were added"));
+
+        // A stone vanishes without being captured
+        let mut next = prev.clone();
+        next.remove_stone(Pos::new(9, 9));
+        next.place_stone(Pos::new(0, 0), Stone::White);
+        let message = infer_move(RuleSet::NINUKI, &prev, &next).unwrap_err();
+        assert!(message.contains("captures nothing, but K10 disappeared"), "{}", message);
+
+        let mut next = prev.clone();
+        next.place_stone(Pos::new(18, 0), Stone::White);
+        next.remove_stone(Pos::new(9, 9));
+        let bottom = Notation { origin: crate::notation::Origin::BottomLeft, skip_i: true };
+        let message = infer_move_with_notation(RuleSet::NINUKI, &prev, &next, bottom).unwrap_err();
+        assert!(message.contains("White at A1 captures nothing"), "{}", message);
+    }
+}
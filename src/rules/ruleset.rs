@@ -0,0 +1,277 @@
+//! Rule-set selection
+//!
+//! The engine plays Ninuki-renju by default. A [`RuleSet`] selects another
+//! variant. It is a plain value: a [`crate::Game`], an [`crate::AIEngine`]
+//! and a [`crate::search::Searcher`] each keep their own, and every rules
+//! and evaluation function takes the rule set to check by, so games under
+//! different rules can run side by side.
+//!
+//! Transposition-table entries don't record the rules they were searched
+//! under; [`crate::AIEngine::set_rules`] clears the engine's caches when
+//! they change.
+//!
+//! ```
+//! use gomoku::rules::{check_winner, RuleSet};
+//! use gomoku::{Board, Pos, Stone};
+//!
+//! // Black five on row 10 with White stones at both ends
+//! let mut board = Board::new();
+//! for col in 5..10 {
+//!     board.place_stone(Pos::new(9, col), Stone::Black);
+//! }
+//! for col in [4, 10] {
+//!     board.place_stone(Pos::new(9, col), Stone::White);
+//! }
+//! assert_eq!(check_winner(RuleSet::NINUKI, &board), Some(Stone::Black));
+//!
+//! let caro = RuleSet::NINUKI.with_caro(true);
+//! assert_eq!(check_winner(caro, &board), None);
+//! ```
+//!
+//! Captures take groups of [`RuleSet::pair_size`] stones. The capture
+//! settings are checked when they are set, so every `RuleSet` in hand is
+//! in range:
+//!
+//! ```
+//! use gomoku::rules::{get_captured_positions, RuleSet};
+//! use gomoku::{Board, Pos, Stone};
+//!
+//! // Black at F10 and K10 around three White stones
+//! let mut board = Board::new();
+//! board.place_stone(Pos::new(9, 5), Stone::Black);
+//! for col in 6..9 {
+//!     board.place_stone(Pos::new(9, col), Stone::White);
+//! }
+//! assert!(get_captured_positions(RuleSet::NINUKI, &board, Pos::new(9, 9), Stone::Black).is_empty());
+//!
+//! let triples = RuleSet::NINUKI.with_captures(3, 3).unwrap();
+//! assert_eq!(get_captured_positions(triples, &board, Pos::new(9, 9), Stone::Black).len(), 3);
+//! assert_eq!(board.captures_to_win(triples, Stone::Black), 3);
+//! assert!(RuleSet::NINUKI.with_captures(0, 5).is_err());
+//! ```
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+
+/// Which moves are forbidden
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Forbidden {
+    /// Ninuki-renju: a double-three is forbidden for both colors unless
+    /// the move captures
+    #[default]
+    DoubleThree,
+    /// Renju: Black may not make a double-three, a double-four or an
+    /// overline; a three only counts if its open four can legally be
+    /// made. A move that makes exactly five is always allowed, and White
+    /// has no restrictions.
+    Renju,
+}
+
+impl Forbidden {
+    pub const ALL: [Self; 2] = [Self::DoubleThree, Self::Renju];
+
+    /// Display name
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::DoubleThree => "Double-three (both colors)",
+            Self::Renju => "Renju (Black: 3-3, 4-4, overline)",
+        }
+    }
+}
+
+/// Largest [`RuleSet::pair_size`]
+pub const MAX_PAIR_SIZE: usize = 3;
+
+/// Largest [`RuleSet::capture_win_pairs`]
+pub const MAX_CAPTURE_WIN_PAIRS: u8 = 15;
+
+/// Rules of the game being played
+///
+/// Built from [`RuleSet::NINUKI`] or [`RuleSet::RENJU`] with the `with_*`
+/// methods; [`RuleSet::with_captures`] range-checks the capture settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RuleSetData"))]
+pub struct RuleSet {
+    forbidden: Forbidden,
+    caro: bool,
+    overline_wins: bool,
+    pair_size: u8,
+    capture_win_pairs: u8,
+}
+
+/// Serialized form of [`RuleSet`], checked on the way in
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RuleSetData {
+    forbidden: Forbidden,
+    caro: bool,
+    overline_wins: bool,
+    pair_size: u8,
+    capture_win_pairs: u8,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RuleSetData> for RuleSet {
+    type Error = String;
+
+    fn try_from(data: RuleSetData) -> Result<Self, String> {
+        Self::NINUKI
+            .with_forbidden(data.forbidden)
+            .with_caro(data.caro)
+            .with_overline_wins(data.overline_wins)
+            .with_captures(data.pair_size, data.capture_win_pairs)
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self::NINUKI
+    }
+}
+
+impl RuleSet {
+    /// Ninuki-renju, the default
+    pub const NINUKI: Self = Self {
+        forbidden: Forbidden::DoubleThree,
+        caro: false,
+        overline_wins: true,
+        pair_size: 2,
+        capture_win_pairs: 5,
+    };
+
+    /// Renju fouls for Black. Captures and the capture win still apply.
+    /// Black can't make an overline at all; White's still wins.
+    pub const RENJU: Self = Self::NINUKI.with_forbidden(Forbidden::Renju);
+
+    /// Which moves are forbidden
+    #[must_use]
+    pub const fn forbidden(self) -> Forbidden {
+        self.forbidden
+    }
+
+    /// Caro: a five (or longer line) with opponent stones right at both
+    /// ends doesn't win. The board edge is not a block.
+    #[must_use]
+    pub const fn caro(self) -> bool {
+        self.caro
+    }
+
+    /// Six or more in a row wins; otherwise only exactly five does
+    #[must_use]
+    pub const fn overline_wins(self) -> bool {
+        self.overline_wins
+    }
+
+    /// Stones in a capturable group (`X-O-O-X` for 2), 2 to
+    /// [`MAX_PAIR_SIZE`]. Exactly this many are taken, never more or fewer.
+    #[must_use]
+    pub const fn pair_size(self) -> u8 {
+        self.pair_size
+    }
+
+    /// Captured pairs that win the game, 1 to [`MAX_CAPTURE_WIN_PAIRS`]
+    #[must_use]
+    pub const fn capture_win_pairs(self) -> u8 {
+        self.capture_win_pairs
+    }
+
+    /// These rules with `forbidden` moves
+    #[must_use]
+    pub const fn with_forbidden(self, forbidden: Forbidden) -> Self {
+        Self { forbidden, ..self }
+    }
+
+    /// These rules with the Caro blocked-five rule on or off
+    #[must_use]
+    pub const fn with_caro(self, caro: bool) -> Self {
+        Self { caro, ..self }
+    }
+
+    /// These rules with overlines winning or not
+    #[must_use]
+    pub const fn with_overline_wins(self, overline_wins: bool) -> Self {
+        Self { overline_wins, ..self }
+    }
+
+    /// These rules with groups of `pair_size` stones captured and
+    /// `capture_win_pairs` captures winning.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message naming the setting that is out of range.
+    pub fn with_captures(self, pair_size: u8, capture_win_pairs: u8) -> Result<Self, String> {
+        if !(2..=MAX_PAIR_SIZE as u8).contains(&pair_size) {
+            return Err(format!("pair size {} is not 2-{}", pair_size, MAX_PAIR_SIZE));
+        }
+        if !(1..=MAX_CAPTURE_WIN_PAIRS).contains(&capture_win_pairs) {
+            return Err(format!("capture win at {} pairs is not 1-{}", capture_win_pairs, MAX_CAPTURE_WIN_PAIRS));
+        }
+        Ok(Self { pair_size, capture_win_pairs, ..self })
+    }
+
+    /// The rule set packed into 16 bits; distinct rule sets differ
+    #[cfg(any(feature = "std", test))]
+    pub(crate) const fn to_bits(self) -> u16 {
+        let forbidden = match self.forbidden {
+            Forbidden::DoubleThree => 0,
+            Forbidden::Renju => 1,
+        };
+        forbidden
+            | if self.caro { 2 } else { 0 }
+            | if self.overline_wins { 0 } else { 4 }
+            | (self.pair_size as u16) << 3
+            | (self.capture_win_pairs as u16) << 5
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_set_bits_distinct() {
+        let mut bits = Vec::new();
+        for forbidden in Forbidden::ALL {
+            for caro in [false, true] {
+                for overline_wins in [false, true] {
+                    for (pair_size, capture_win_pairs) in [(2, 5), (3, 1), (2, MAX_CAPTURE_WIN_PAIRS)] {
+                        let rules = RuleSet::NINUKI
+                            .with_forbidden(forbidden)
+                            .with_caro(caro)
+                            .with_overline_wins(overline_wins)
+                            .with_captures(pair_size, capture_win_pairs)
+                            .unwrap();
+                        bits.push(rules.to_bits());
+                    }
+                }
+            }
+        }
+        bits.sort_unstable();
+        bits.dedup();
+        assert_eq!(bits.len(), 2 * 2 * 2 * 3);
+        assert_eq!(RuleSet::default(), RuleSet::NINUKI);
+    }
+
+    #[test]
+    fn test_rule_set_with_captures_checks_range() {
+        let rules = RuleSet::NINUKI;
+        assert!(rules.with_captures(0, 5).unwrap_err().contains("pair size"));
+        assert!(rules.with_captures(1, 5).unwrap_err().contains("pair size"));
+        assert!(rules.with_captures(4, 5).is_err());
+        assert!(rules.with_captures(2, 0).unwrap_err().contains("capture win"));
+        assert!(rules.with_captures(2, 16).is_err());
+        assert_eq!(rules.with_captures(3, 1).map(RuleSet::pair_size), Ok(3));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_rule_set_deserialize_checks_range() {
+        let json = serde_json::to_string(&RuleSet::RENJU).unwrap();
+        assert_eq!(serde_json::from_str::<RuleSet>(&json).unwrap(), RuleSet::RENJU);
+        let zero = json.replace("\"pair_size\":2", "\"pair_size\":0");
+        assert!(serde_json::from_str::<RuleSet>(&zero).is_err());
+    }
+}
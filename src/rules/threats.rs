@@ -0,0 +1,236 @@
+//! Threat queries for GUIs and tools
+//!
+//! Lists the threats a color has on the board without searching:
+//! - Fours: four stones in a five-cell window with the fifth cell empty;
+//!   an open four has two such completion points (`_XXXX_`)
+//! - Open (free) threes: three stones that become an open four with one
+//!   more stone, the same patterns the double-three rule counts
+//! - Capture threats: opponent pairs that can be captured next move
+//!
+//! Each [`Threat`] carries the stones that form it and the empty points
+//! that complete it (or, for the defender, that must be taken).
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::board::{Board, Pos, Stone, BOARD_SIZE};
+
+use super::capture::get_captured_positions;
+use super::forbidden::is_valid_move;
+use super::ruleset::RuleSet;
+
+/// Direction vectors for line checking (4 directions)
+const DIRECTIONS: [(i32, i32); 4] = [
+    (0, 1),  // Horizontal
+    (1, 0),  // Vertical
+    (1, 1),  // Diagonal SE
+    (1, -1), // Diagonal SW
+];
+
+/// Kind of threat, most severe first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ThreatKind {
+    /// Four with two completion points: wins next move unless broken by capture
+    OpenFour,
+    /// Four with one completion point
+    Four,
+    /// Three that becomes an open four
+    OpenThree,
+    /// An opponent pair that can be captured
+    Capture,
+}
+
+/// One threat on the board
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Threat {
+    pub kind: ThreatKind,
+    /// Color making the threat
+    pub color: Stone,
+    /// Stones forming the threat, in line order (for captures, the
+    /// opponent pair that would be taken)
+    pub positions: Vec<Pos>,
+    /// Empty points that carry out the threat: the five points of a four,
+    /// the open-four points of a three, the capturing point of a capture
+    pub points: Vec<Pos>,
+}
+
+/// All threats of `color`: fours, then open threes, then capture threats.
+#[must_use]
+pub fn find_threats(rules: RuleSet, board: &Board, color: Stone) -> Vec<Threat> {
+    let mut threats = find_fours(board, color);
+    threats.extend(find_open_threes(board, color));
+    threats.extend(find_capture_threats(rules, board, color));
+    threats
+}
+
+/// Fours of `color`, open fours first.
+///
+/// A five already on the board is not reported.
+#[must_use]
+pub fn find_fours(board: &Board, color: Stone) -> Vec<Threat> {
+    let mut threats = scan_windows(board, color, 5, |cells| {
+        // Four stones and one empty cell, no opponent
+        let empty: Vec<usize> = (0..5).filter(|&i| cells[i] == Stone::Empty).collect();
+        (empty.len() == 1 && cells.iter().all(|&c| c == color || c == Stone::Empty)).then_some(empty)
+    });
+    for threat in &mut threats {
+        threat.kind = if threat.points.len() >= 2 { ThreatKind::OpenFour } else { ThreatKind::Four };
+    }
+    threats.sort_by_key(|threat| threat.kind);
+    threats
+}
+
+/// Open threes of `color`: `_XXX_`, `_XX_X_` and `_X_XX_` with room to
+/// become an open four. Whether the completing move would be forbidden is
+/// not checked.
+#[must_use]
+pub fn find_open_threes(board: &Board, color: Stone) -> Vec<Threat> {
+    let mut threats = scan_windows(board, color, 6, |cells| {
+        // `_????_` with three stones and one empty cell inside
+        let inner = &cells[1..5];
+        let empty: Vec<usize> = (1..5).filter(|&i| cells[i] == Stone::Empty).collect();
+        (cells[0] == Stone::Empty
+            && cells[5] == Stone::Empty
+            && empty.len() == 1
+            && inner.iter().all(|&c| c == color || c == Stone::Empty))
+        .then_some(empty)
+    });
+    for threat in &mut threats {
+        threat.kind = ThreatKind::OpenThree;
+    }
+    threats
+}
+
+/// Pairs `color` can capture with one legal move, one threat per pair.
+#[must_use]
+pub fn find_capture_threats(rules: RuleSet, board: &Board, color: Stone) -> Vec<Threat> {
+    let mut threats = Vec::new();
+    let pair_size = usize::from(rules.pair_size());
+    for pos in board.candidate_moves().iter_ones() {
+        if !board.is_empty(pos) || !is_valid_move(rules, board, pos, color) {
+            continue;
+        }
+        for pair in get_captured_positions(rules, board, pos, color).chunks(pair_size) {
+            threats.push(Threat {
+                kind: ThreatKind::Capture,
+                color,
+                positions: pair.to_vec(),
+                points: vec![pos],
+            });
+        }
+    }
+    threats
+}
+
+/// Every on-board window of `len` cells in every direction. `matches`
+/// returns the window offsets of the threat's points, or `None`. Windows
+/// with the same stones in the same direction form one threat with the
+/// union of their points.
+fn scan_windows(
+    board: &Board,
+    color: Stone,
+    len: i32,
+    matches: impl Fn(&[Stone]) -> Option<Vec<usize>>,
+) -> Vec<Threat> {
+    let size = BOARD_SIZE as i32;
+    let mut found: Vec<((i32, i32), Threat)> = Vec::new();
+    for (dr, dc) in DIRECTIONS {
+        for row in 0..size {
+            for col in 0..size {
+                if !Pos::is_valid(row + (len - 1) * dr, col + (len - 1) * dc) {
+                    continue;
+                }
+                #[allow(clippy::cast_sign_loss)]
+                let window: Vec<Pos> = (0..len)
+                    .map(|k| Pos::new((row + k * dr) as u8, (col + k * dc) as u8))
+                    .collect();
+                let cells: Vec<Stone> = window.iter().map(|&p| board.get(p)).collect();
+                let Some(points) = matches(&cells) else { continue };
+
+                let positions: Vec<Pos> = window.iter().copied().filter(|&p| board.get(p) == color).collect();
+                let points = points.into_iter().map(|i| window[i]);
+                match found.iter_mut().find(|(dir, t)| *dir == (dr, dc) && t.positions == positions) {
+                    Some((_, threat)) => {
+                        for p in points {
+                            if !threat.points.contains(&p) {
+                                threat.points.push(p);
+                            }
+                        }
+                    }
+                    None => found.push((
+                        (dr, dc),
+                        Threat { kind: ThreatKind::Four, color, positions, points: points.collect() },
+                    )),
+                }
+            }
+        }
+    }
+    found.into_iter().map(|(_, threat)| threat).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board_with(black: &[(u8, u8)], white: &[(u8, u8)]) -> Board {
+        let mut board = Board::new();
+        for &(r, c) in black {
+            board.place_stone(Pos::new(r, c), Stone::Black);
+        }
+        for &(r, c) in white {
+            board.place_stone(Pos::new(r, c), Stone::White);
+        }
+        board
+    }
+
+    #[test]
+    fn test_fours() {
+        // Open four F10-I10, closed four on column A (A1-A4, A5 empty)
+        let board = board_with(&[(9, 5), (9, 6), (9, 7), (9, 8), (0, 0), (1, 0), (2, 0), (3, 0)], &[]);
+        let fours = find_fours(&board, Stone::Black);
+        assert_eq!(fours.len(), 2);
+        assert_eq!(fours[0].kind, ThreatKind::OpenFour);
+        assert_eq!(fours[0].positions.len(), 4);
+        assert!(fours[0].points.contains(&Pos::new(9, 4)) && fours[0].points.contains(&Pos::new(9, 9)));
+        assert_eq!(fours[1].kind, ThreatKind::Four);
+        assert_eq!(fours[1].points, vec![Pos::new(4, 0)]);
+
+        // A split four X_XXX has the gap as its only point
+        let board = board_with(&[(5, 5), (5, 7), (5, 8), (5, 9)], &[(5, 4), (5, 10)]);
+        let fours = find_fours(&board, Stone::Black);
+        assert_eq!(fours.len(), 1);
+        assert_eq!(fours[0].points, vec![Pos::new(5, 6)]);
+        assert!(find_fours(&board, Stone::White).is_empty());
+    }
+
+    #[test]
+    fn test_open_threes() {
+        // _XXX_ on row 10 and a split three _XX_X_ on column C
+        let board = board_with(&[(9, 6), (9, 7), (9, 8), (3, 2), (4, 2), (6, 2)], &[]);
+        let threes = find_open_threes(&board, Stone::Black);
+        assert_eq!(threes.len(), 2);
+        let split = threes.iter().find(|t| t.positions[0].col == 2).unwrap();
+        assert_eq!(split.points, vec![Pos::new(5, 2)]);
+        let solid = threes.iter().find(|t| t.positions[0].row == 9).unwrap();
+        assert!(solid.points.contains(&Pos::new(9, 5)) && solid.points.contains(&Pos::new(9, 9)));
+
+        // Blocked on one side: not open
+        let board = board_with(&[(9, 6), (9, 7), (9, 8)], &[(9, 5)]);
+        assert!(find_open_threes(&board, Stone::Black).is_empty());
+    }
+
+    #[test]
+    fn test_capture_threats() {
+        // White K10 L10 flanked by Black J10: Black captures at M10
+        let board = board_with(&[(9, 8)], &[(9, 9), (9, 10)]);
+        let captures = find_capture_threats(RuleSet::NINUKI, &board, Stone::Black);
+        assert_eq!(captures.len(), 1);
+        assert_eq!(captures[0].points, vec![Pos::new(9, 11)]);
+        assert_eq!(captures[0].positions.len(), 2);
+        assert!(find_capture_threats(RuleSet::NINUKI, &board, Stone::White).is_empty());
+
+        let all = find_threats(RuleSet::NINUKI, &board, Stone::Black);
+        assert_eq!(all, captures);
+    }
+}
@@ -2,14 +2,21 @@
 //!
 //! Win conditions:
 //! 1. Five or more stones in a row
-//! 2. Capture 10 opponent stones (5 pairs)
+//! 2. Capture 10 opponent stones (5 pairs; see [`RuleSet::capture_win_pairs`])
 //!
 //! Endgame capture rule: A 5-in-a-row only wins if the opponent
 //! cannot break it by capturing a pair from the line.
+//!
+//! Under the Caro rule ([`RuleSet::caro`]) a line with opponent stones
+//! right at both ends is not a five, and without
+//! [`RuleSet::overline_wins`] six or more in a row is not a five either.
 
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
 use crate::board::{Board, Pos, Stone};
 
 use super::capture::get_captured_positions;
+use super::ruleset::RuleSet;
 
 /// Direction vectors for line checking (4 directions)
 const DIRECTIONS: [(i32, i32); 4] = [
@@ -19,9 +26,43 @@ const DIRECTIONS: [(i32, i32); 4] = [
     (1, -1), // Diagonal SW
 ];
 
+/// Length of the run of `color` through `pos` along `(dr, dc)`, and
+/// whether opponent stones sit right at both of its ends. `pos` counts as
+/// `color` and isn't read. The board edge doesn't block.
+fn run_through(board: &Board, pos: Pos, color: Stone, dr: i32, dc: i32) -> (usize, bool) {
+    let end = |sign: i32| {
+        let mut k = 1;
+        loop {
+            let r = pos.row as i32 + sign * dr * k;
+            let c = pos.col as i32 + sign * dc * k;
+            if !Pos::is_valid(r, c) {
+                return (k - 1, false);
+            }
+            match board.get(Pos::new(r as u8, c as u8)) {
+                cell if cell == color => k += 1,
+                cell => return (k - 1, cell == color.opponent()),
+            }
+        }
+    };
+    let (ahead, blocked_ahead) = end(1);
+    let (behind, blocked_behind) = end(-1);
+    ((1 + ahead + behind) as usize, blocked_ahead && blocked_behind)
+}
+
+/// Whether the 5+ run of `color` through `pos` wins under `rules`: an
+/// overline only if overlines win, and not blocked at both ends under the
+/// Caro rule
+fn is_winning_run(rules: RuleSet, board: &Board, pos: Pos, color: Stone, dr: i32, dc: i32) -> bool {
+    if rules.overline_wins() && !rules.caro() {
+        return true;
+    }
+    let (len, blocked) = run_through(board, pos, color, dr, dc);
+    (rules.overline_wins() || len == 5) && !(rules.caro() && blocked)
+}
+
 /// Check if there's 5+ in a row for the given color
-pub fn has_five_in_row(board: &Board, stone: Stone) -> bool {
-    find_five_positions(board, stone).is_some()
+pub fn has_five_in_row(rules: RuleSet, board: &Board, stone: Stone) -> bool {
+    find_five_positions(rules, board, stone).is_some()
 }
 
 /// Fast five-in-a-row check at a specific position.
@@ -29,7 +70,7 @@ pub fn has_five_in_row(board: &Board, stone: Stone) -> bool {
 /// Only checks 4 directions from the given position. No allocation.
 /// Much faster than `has_five_in_row` which iterates ALL stones.
 #[inline]
-pub fn has_five_at_pos(board: &Board, pos: Pos, color: Stone) -> bool {
+pub fn has_five_at_pos(rules: RuleSet, board: &Board, pos: Pos, color: Stone) -> bool {
     let sz = 19i8;
     let dirs: [(i8, i8); 4] = [(1, 0), (0, 1), (1, 1), (1, -1)];
     for (dr, dc) in dirs {
@@ -58,7 +99,7 @@ pub fn has_five_at_pos(board: &Board, pos: Pos, color: Stone) -> bool {
                 break;
             }
         }
-        if count >= 5 {
+        if count >= 5 && is_winning_run(rules, board, pos, color, dr.into(), dc.into()) {
             return true;
         }
     }
@@ -70,7 +111,7 @@ pub fn has_five_at_pos(board: &Board, pos: Pos, color: Stone) -> bool {
 /// Like `has_five_at_pos` but returns the positions forming the five.
 /// Only checks 4 directions from the given position. Only call when
 /// `has_five_at_pos` already returned true (rare path, no perf concern).
-pub fn find_five_line_at_pos(board: &Board, pos: Pos, color: Stone) -> Option<Vec<Pos>> {
+pub fn find_five_line_at_pos(rules: RuleSet, board: &Board, pos: Pos, color: Stone) -> Option<Vec<Pos>> {
     let sz = 19i8;
     let dirs: [(i8, i8); 4] = [(1, 0), (0, 1), (1, 1), (1, -1)];
     for (dr, dc) in dirs {
@@ -99,7 +140,7 @@ pub fn find_five_line_at_pos(board: &Board, pos: Pos, color: Stone) -> Option<Ve
                 break;
             }
         }
-        if line.len() >= 5 {
+        if line.len() >= 5 && is_winning_run(rules, board, pos, color, dr.into(), dc.into()) {
             return Some(line);
         }
     }
@@ -110,7 +151,7 @@ pub fn find_five_line_at_pos(board: &Board, pos: Pos, color: Stone) -> Option<Ve
 ///
 /// Returns Some(Vec<Pos>) with at least 5 positions if a winning line exists,
 /// None otherwise.
-pub fn find_five_positions(board: &Board, stone: Stone) -> Option<Vec<Pos>> {
+pub fn find_five_positions(rules: RuleSet, board: &Board, stone: Stone) -> Option<Vec<Pos>> {
     let stones = board.stones(stone)?;
 
     for pos in stones.iter_ones() {
@@ -147,7 +188,7 @@ pub fn find_five_positions(board: &Board, stone: Stone) -> Option<Vec<Pos>> {
                 }
             }
 
-            if line.len() >= 5 {
+            if line.len() >= 5 && is_winning_run(rules, board, pos, stone, dr, dc) {
                 return Some(line);
             }
         }
@@ -160,16 +201,17 @@ pub fn find_five_positions(board: &Board, stone: Stone) -> Option<Vec<Pos>> {
 /// Returns true if the 5-in-row can be broken by the opponent
 /// placing a stone that captures part of the winning line.
 /// This is a STATIC game-rule check (no look-ahead for recreation).
-pub fn can_break_five_by_capture(board: &Board, five_positions: &[Pos], five_color: Stone) -> bool {
+pub fn can_break_five_by_capture(rules: RuleSet, board: &Board, five_positions: &[Pos], five_color: Stone) -> bool {
     let opponent = five_color.opponent();
 
-    // For each empty position within radius 2 of the five stones.
-    // Radius 2 is needed because capture pattern X-O-O-X means the
+    // For each empty position within radius 2 (the pair size) of the five
+    // stones. Radius 2 is needed because capture pattern X-O-O-X means the
     // capturing stone can be up to 2 steps away from the nearest
     // five-stone (e.g., placing at distance 2 captures the pair in between).
+    let radius = i32::from(rules.pair_size());
     for &pos in five_positions {
-        for dr in -2i32..=2 {
-            for dc in -2i32..=2 {
+        for dr in -radius..=radius {
+            for dc in -radius..=radius {
                 if dr == 0 && dc == 0 {
                     continue;
                 }
@@ -187,7 +229,7 @@ pub fn can_break_five_by_capture(board: &Board, five_positions: &[Pos], five_col
                 }
 
                 // Check if opponent placing here would capture part of the five
-                let would_capture = get_captured_positions(board, adj_pos, opponent);
+                let would_capture = get_captured_positions(rules, board, adj_pos, opponent);
                 for cap in would_capture {
                     if five_positions.contains(&cap) {
                         return true;
@@ -206,13 +248,14 @@ pub fn can_break_five_by_capture(board: &Board, five_positions: &[Pos], five_col
 /// where the opponent could place a stone to break the five.
 /// Used by the engine to force a break move when the opponent has a
 /// breakable five on the board.
-pub fn find_five_break_moves(board: &Board, five_positions: &[Pos], five_color: Stone) -> Vec<Pos> {
+pub fn find_five_break_moves(rules: RuleSet, board: &Board, five_positions: &[Pos], five_color: Stone) -> Vec<Pos> {
     let opponent = five_color.opponent();
     let mut break_moves = Vec::new();
+    let radius = i32::from(rules.pair_size());
 
     for &pos in five_positions {
-        for dr in -2i32..=2 {
-            for dc in -2i32..=2 {
+        for dr in -radius..=radius {
+            for dc in -radius..=radius {
                 if dr == 0 && dc == 0 {
                     continue;
                 }
@@ -232,7 +275,7 @@ pub fn find_five_break_moves(board: &Board, five_positions: &[Pos], five_color:
                     continue;
                 }
 
-                let would_capture = get_captured_positions(board, adj_pos, opponent);
+                let would_capture = get_captured_positions(rules, board, adj_pos, opponent);
                 for cap in would_capture {
                     if five_positions.contains(&cap) {
                         break_moves.push(adj_pos);
@@ -250,22 +293,23 @@ pub fn find_five_break_moves(board: &Board, five_positions: &[Pos], five_color:
 /// Returns `Some(Stone)` if there's a winner, `None` otherwise.
 ///
 /// Win conditions checked:
-/// 1. Capture win: 5 pairs (10 stones) captured
+/// 1. Capture win: 5 pairs (10 stones) captured, or the rule set's
+///    [`capture_win_pairs`](RuleSet::capture_win_pairs)
 /// 2. Five-in-a-row win (unless opponent can break it by capture)
-pub fn check_winner(board: &Board) -> Option<Stone> {
-    // Check capture win (10 captures = 5 pairs)
-    if board.captures(Stone::Black) >= 5 {
+pub fn check_winner(rules: RuleSet, board: &Board) -> Option<Stone> {
+    // Check capture win
+    if board.captures_to_win(rules, Stone::Black) == 0 {
         return Some(Stone::Black);
     }
-    if board.captures(Stone::White) >= 5 {
+    if board.captures_to_win(rules, Stone::White) == 0 {
         return Some(Stone::White);
     }
 
     // Check 5-in-a-row win
     for stone in [Stone::Black, Stone::White] {
-        if let Some(five) = find_five_positions(board, stone) {
+        if let Some(five) = find_five_positions(rules, board, stone) {
             // Endgame capture rule: if opponent can break it, no win yet
-            if !can_break_five_by_capture(board, &five, stone) {
+            if !can_break_five_by_capture(rules, board, &five, stone) {
                 return Some(stone);
             }
         }
@@ -274,6 +318,34 @@ pub fn check_winner(board: &Board) -> Option<Stone> {
     None
 }
 
+/// Winner after `color` has just played `pos` (captures already applied)
+///
+/// Unlike [`check_winner`], this knows whose turn it was, which decides
+/// what happens to a breakable five. In order:
+/// 1. The mover reaching the capture win wins.
+/// 2. A five the opponent already had on the board wins for the opponent:
+///    the mover had one chance to break it by capture and did not.
+/// 3. A five made by the move wins unless the opponent can break it by
+///    capturing a pair out of it.
+pub fn winner_after_move(rules: RuleSet, board: &Board, pos: Pos, color: Stone) -> Option<Stone> {
+    let opponent = color.opponent();
+
+    if board.captures_to_win(rules, color) == 0 {
+        return Some(color);
+    }
+    if find_five_positions(rules, board, opponent).is_some() {
+        return Some(opponent);
+    }
+    if has_five_at_pos(rules, board, pos, color) {
+        let unbreakable = find_five_line_at_pos(rules, board, pos, color)
+            .is_some_and(|five| !can_break_five_by_capture(rules, board, &five, color));
+        if unbreakable {
+            return Some(color);
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -284,8 +356,8 @@ mod tests {
         for i in 0..5 {
             board.place_stone(Pos::new(9, i), Stone::Black);
         }
-        assert!(has_five_in_row(&board, Stone::Black));
-        assert!(!has_five_in_row(&board, Stone::White));
+        assert!(has_five_in_row(RuleSet::NINUKI, &board, Stone::Black));
+        assert!(!has_five_in_row(RuleSet::NINUKI, &board, Stone::White));
     }
 
     #[test]
@@ -294,7 +366,7 @@ mod tests {
         for i in 0..5 {
             board.place_stone(Pos::new(i, 9), Stone::Black);
         }
-        assert!(has_five_in_row(&board, Stone::Black));
+        assert!(has_five_in_row(RuleSet::NINUKI, &board, Stone::Black));
     }
 
     #[test]
@@ -303,7 +375,7 @@ mod tests {
         for i in 0..5 {
             board.place_stone(Pos::new(i, i), Stone::White);
         }
-        assert!(has_five_in_row(&board, Stone::White));
+        assert!(has_five_in_row(RuleSet::NINUKI, &board, Stone::White));
     }
 
     #[test]
@@ -312,7 +384,7 @@ mod tests {
         for i in 0..6 {
             board.place_stone(Pos::new(9, i), Stone::Black);
         }
-        assert!(has_five_in_row(&board, Stone::Black));
+        assert!(has_five_in_row(RuleSet::NINUKI, &board, Stone::Black));
     }
 
     #[test]
@@ -321,21 +393,21 @@ mod tests {
         for i in 0..4 {
             board.place_stone(Pos::new(9, i), Stone::Black);
         }
-        assert!(!has_five_in_row(&board, Stone::Black));
+        assert!(!has_five_in_row(RuleSet::NINUKI, &board, Stone::Black));
     }
 
     #[test]
     fn test_capture_win() {
         let mut board = Board::new();
         board.add_captures(Stone::Black, 5);
-        assert_eq!(check_winner(&board), Some(Stone::Black));
+        assert_eq!(check_winner(RuleSet::NINUKI, &board), Some(Stone::Black));
     }
 
     #[test]
     fn test_capture_win_white() {
         let mut board = Board::new();
         board.add_captures(Stone::White, 5);
-        assert_eq!(check_winner(&board), Some(Stone::White));
+        assert_eq!(check_winner(RuleSet::NINUKI, &board), Some(Stone::White));
     }
 
     #[test]
@@ -355,9 +427,9 @@ mod tests {
         }
         board.place_stone(Pos::new(8, 7), Stone::Black);
 
-        let five = find_five_positions(&board, Stone::Black).unwrap();
+        let five = find_five_positions(RuleSet::NINUKI, &board, Stone::Black).unwrap();
         // STATIC check: the five IS physically breakable
-        assert!(can_break_five_by_capture(&board, &five, Stone::Black));
+        assert!(can_break_five_by_capture(RuleSet::NINUKI, &board, &five, Stone::Black));
     }
 
     #[test]
@@ -367,13 +439,13 @@ mod tests {
         for i in 5..10 {
             board.place_stone(Pos::new(9, i), Stone::Black);
         }
-        assert_eq!(check_winner(&board), Some(Stone::Black));
+        assert_eq!(check_winner(RuleSet::NINUKI, &board), Some(Stone::Black));
     }
 
     #[test]
     fn test_no_winner() {
         let board = Board::new();
-        assert_eq!(check_winner(&board), None);
+        assert_eq!(check_winner(RuleSet::NINUKI, &board), None);
     }
 
     #[test]
@@ -383,8 +455,8 @@ mod tests {
         for i in 0..5 {
             board.place_stone(Pos::new(4 + i, 8 - i), Stone::White);
         }
-        assert!(has_five_in_row(&board, Stone::White));
-        assert_eq!(check_winner(&board), Some(Stone::White));
+        assert!(has_five_in_row(RuleSet::NINUKI, &board, Stone::White));
+        assert_eq!(check_winner(RuleSet::NINUKI, &board), Some(Stone::White));
     }
 
     #[test]
@@ -394,8 +466,8 @@ mod tests {
         for i in 0..5 {
             board.place_stone(Pos::new(18, i), Stone::Black);
         }
-        assert!(has_five_in_row(&board, Stone::Black));
-        assert_eq!(check_winner(&board), Some(Stone::Black));
+        assert!(has_five_in_row(RuleSet::NINUKI, &board, Stone::Black));
+        assert_eq!(check_winner(RuleSet::NINUKI, &board), Some(Stone::Black));
     }
 
     #[test]
@@ -405,16 +477,16 @@ mod tests {
         for i in 0..5 {
             board.place_stone(Pos::new(14 + i, 14 + i), Stone::White);
         }
-        assert!(has_five_in_row(&board, Stone::White));
-        assert_eq!(check_winner(&board), Some(Stone::White));
+        assert!(has_five_in_row(RuleSet::NINUKI, &board, Stone::White));
+        assert_eq!(check_winner(RuleSet::NINUKI, &board), Some(Stone::White));
     }
 
     #[test]
     fn test_empty_not_five() {
         let board = Board::new();
-        assert!(!has_five_in_row(&board, Stone::Black));
-        assert!(!has_five_in_row(&board, Stone::White));
-        assert!(find_five_positions(&board, Stone::Empty).is_none());
+        assert!(!has_five_in_row(RuleSet::NINUKI, &board, Stone::Black));
+        assert!(!has_five_in_row(RuleSet::NINUKI, &board, Stone::White));
+        assert!(find_five_positions(RuleSet::NINUKI, &board, Stone::Empty).is_none());
     }
 
     #[test]
@@ -426,6 +498,67 @@ mod tests {
             board.place_stone(Pos::new(9, i), Stone::Black);
         }
         // White wins by capture (checked first)
-        assert_eq!(check_winner(&board), Some(Stone::White));
+        assert_eq!(check_winner(RuleSet::NINUKI, &board), Some(Stone::White));
+    }
+
+    #[test]
+    fn test_winner_after_move_breakable_five() {
+        // Black five on row 9; White on (8,7) can capture (9,7)+(10,7) at (11,7)
+        let mut board = Board::new();
+        for c in 5..10 {
+            board.place_stone(Pos::new(9, c), Stone::Black);
+        }
+        board.place_stone(Pos::new(10, 7), Stone::Black);
+        board.place_stone(Pos::new(8, 7), Stone::White);
+
+        // Breakable: the five-maker does not win yet
+        assert_eq!(winner_after_move(RuleSet::NINUKI, &board, Pos::new(9, 9), Stone::Black), None);
+
+        // White ignores it: the standing five wins
+        board.place_stone(Pos::new(0, 0), Stone::White);
+        assert_eq!(winner_after_move(RuleSet::NINUKI, &board, Pos::new(0, 0), Stone::White), Some(Stone::Black));
+    }
+
+    #[test]
+    fn test_five_blocked_both_ends() {
+        let caro = RuleSet::NINUKI.with_caro(true);
+        // W B B B B B W on row 10
+        let mut board = Board::new();
+        for c in 5..10 {
+            board.place_stone(Pos::new(9, c), Stone::Black);
+        }
+        board.place_stone(Pos::new(9, 4), Stone::White);
+        assert!(is_winning_run(caro, &board, Pos::new(9, 7), Stone::Black, 0, 1));
+        board.place_stone(Pos::new(9, 10), Stone::White);
+        assert!(!is_winning_run(caro, &board, Pos::new(9, 7), Stone::Black, 0, 1));
+        assert_eq!(run_through(&board, Pos::new(9, 7), Stone::Black, 0, 1), (5, true));
+        // Only the line's own ends matter
+        assert_eq!(run_through(&board, Pos::new(9, 7), Stone::Black, 1, 0), (1, false));
+        // Without the Caro rule it is still a five
+        assert!(has_five_at_pos(RuleSet::NINUKI, &board, Pos::new(9, 7), Stone::Black));
+
+        // The board edge is not a block: B B B B B W from A1
+        let mut board = Board::new();
+        for c in 0..5 {
+            board.place_stone(Pos::new(0, c), Stone::Black);
+        }
+        board.place_stone(Pos::new(0, 5), Stone::White);
+        assert!(is_winning_run(caro, &board, Pos::new(0, 2), Stone::Black, 0, 1));
+    }
+
+    #[test]
+    fn test_overline_wins_flag() {
+        let exact = RuleSet::NINUKI.with_overline_wins(false);
+        // Six in a row on column C, an empty point counted as the sixth
+        let mut board = Board::new();
+        for r in [3, 4, 5, 7, 8] {
+            board.place_stone(Pos::new(r, 2), Stone::White);
+        }
+        assert_eq!(run_through(&board, Pos::new(6, 2), Stone::White, 1, 0), (6, false));
+        assert!(is_winning_run(RuleSet::NINUKI, &board, Pos::new(6, 2), Stone::White, 1, 0));
+        assert!(!is_winning_run(exact, &board, Pos::new(6, 2), Stone::White, 1, 0));
+        // Exactly five still wins
+        board.remove_stone(Pos::new(3, 2));
+        assert!(is_winning_run(exact, &board, Pos::new(6, 2), Stone::White, 1, 0));
     }
 }
@@ -8,7 +8,8 @@
 //! - Iterative deepening for time management and move ordering
 //! - Transposition table for avoiding redundant searches
 //! - Early cutoff when winning move is found
-//! - Move generation with proximity filtering
+//! - Move generation with proximity filtering, staged (TT move first, the rest
+//!   scored only if it fails to cut off)
 //! - **Lazy SMP**: parallel search with lock-free shared TT
 //!
 //! # Example
@@ -26,28 +27,119 @@
 //! }
 //! ```
 
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
-use std::time::{Duration, Instant};
-
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, AtomicI8, AtomicU64, Ordering};
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::io::{self, BufReader, BufWriter};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
 use crate::board::{Bitboard, Board, Pos, Stone, BOARD_SIZE};
 use crate::eval::{evaluate, PatternScore};
 use crate::rules::{
     can_break_five_by_capture, count_captures_fast, execute_captures_fast,
     find_five_break_moves, find_five_line_at_pos, has_five_at_pos, has_five_in_row, is_valid_move,
-    undo_captures,
+    see_capture, undo_captures, RuleSet,
 };
+use crate::time::{Clock, SystemClock, NODES_PER_MS};
 
-use super::{AtomicTT, EntryType, TTStats, ZobristTable};
+use super::audit::MoveAudit;
+use super::evalcache::EvalCache;
+use super::history::HistoryTable;
+use super::margins::SHALLOW_PRUNING_DEPTH;
+#[cfg(feature = "trace")]
+use super::trace::{NodeKind, Outcome, SearchTrace, TraceRecorder};
+use super::{AtomicTT, EntryType, PruningMargins, SearchLimits, SearchParams, TTStats, ZobristTable};
 
 /// Infinity score for alpha-beta bounds
 const INF: i32 = PatternScore::FIVE + 1;
 
-/// Maximum moves to consider at root.
-/// Defense-first move ordering puts critical moves at the top,
-/// so we don't need as many to catch all threats.
-const MAX_ROOT_MOVES: usize = 30;
+/// Lead of the best root move over every other (a pair capture's worth)
+/// at which the move counts as forced and deepening stops early.
+const FORCED_MOVE_GAP: i32 = PatternScore::CAPTURE_PAIR;
+
+/// Consecutive iterations with the same best move after which it counts as
+/// an easy move and deepening may stop early
+const EASY_MOVE_ITERATIONS: i8 = 4;
+
+/// Size of the shared static evaluation cache
+const EVAL_CACHE_MB: usize = 2;
+
+/// Initial move arena capacity per worker: a few full candidate lists,
+/// enough for typical search paths without regrowing
+const MOVE_ARENA_CAPACITY: usize = 4096;
+
+/// Forward a call to the worker's trace recorder when tracing is compiled
+/// in and active; expands to nothing without the `trace` feature.
+macro_rules! trace_hook {
+    ($self:ident, $($call:tt)*) => {
+        #[cfg(feature = "trace")]
+        if let Some(trace) = $self.trace.as_mut() {
+            trace.$($call)*;
+        }
+    };
+}
+
+/// Scores at or beyond this magnitude are forced wins or losses.
+///
+/// A win found `n` plies from the root scores `FIVE - n`, so the search
+/// prefers the shortest win and, when lost, the longest defense.
+pub const MATE_THRESHOLD: i32 = PatternScore::FIVE - 100;
+
+/// Moves until a forced result, or `None` for an ordinary score.
+///
+/// Positive for a win in that many of our own moves, negative for a loss
+/// after that many opponent moves.
+///
+/// # Example
+///
+/// ```
+/// use gomoku::search::mate_distance;
+/// use gomoku::eval::PatternScore;
+///
+/// assert_eq!(mate_distance(PatternScore::FIVE - 1), Some(1));
+/// assert_eq!(mate_distance(PatternScore::FIVE - 5), Some(3));
+/// assert_eq!(mate_distance(-(PatternScore::FIVE - 4)), Some(-2));
+/// assert_eq!(mate_distance(15_000), None);
+/// ```
+#[must_use]
+pub fn mate_distance(score: i32) -> Option<i32> {
+    if score.abs() < MATE_THRESHOLD {
+        return None;
+    }
+    let moves = ((PatternScore::FIVE - score.abs() + 1) / 2).max(1);
+    Some(if score > 0 { moves } else { -moves })
+}
+
+/// Make a mate score relative to the node at `ply` before storing it in the
+/// TT, so the entry stays correct when reached at a different ply.
+#[inline]
+fn score_to_tt(score: i32, ply: i32) -> i32 {
+    if score >= MATE_THRESHOLD {
+        score + ply
+    } else if score <= -MATE_THRESHOLD {
+        score - ply
+    } else {
+        score
+    }
+}
 
+/// Inverse of [`score_to_tt`].
+#[inline]
+fn score_from_tt(score: i32, ply: i32) -> i32 {
+    if score >= MATE_THRESHOLD {
+        score - ply
+    } else if score <= -MATE_THRESHOLD {
+        score + ply
+    } else {
+        score
+    }
+}
 
 /// Search statistics for diagnostics and tuning.
 #[derive(Debug, Clone, Default)]
@@ -62,6 +154,10 @@ pub struct SearchStats {
     pub tt_score_hits: u64,
     /// TT probes that provided a best move for ordering
     pub tt_move_hits: u64,
+    /// Static evaluations needed
+    pub eval_probes: u64,
+    /// Static evaluations found in the evaluation cache
+    pub eval_hits: u64,
 }
 
 impl SearchStats {
@@ -83,13 +179,25 @@ impl SearchStats {
         }
     }
 
+    /// Evaluation cache hit rate
+    pub fn eval_hit_rate(&self) -> f64 {
+        if self.eval_probes == 0 {
+            0.0
+        } else {
+            self.eval_hits as f64 / self.eval_probes as f64 * 100.0
+        }
+    }
+
     /// Merge another stats into this one (for combining worker stats)
+    #[cfg(feature = "std")]
     fn merge(&mut self, other: &SearchStats) {
         self.beta_cutoffs += other.beta_cutoffs;
         self.first_move_cutoffs += other.first_move_cutoffs;
         self.tt_probes += other.tt_probes;
         self.tt_score_hits += other.tt_score_hits;
         self.tt_move_hits += other.tt_move_hits;
+        self.eval_probes += other.eval_probes;
+        self.eval_hits += other.eval_hits;
     }
 }
 
@@ -106,6 +214,97 @@ pub struct SearchResult {
     pub nodes: u64,
     /// Search diagnostics
     pub stats: SearchStats,
+    /// Threads that searched (1 unless the search ran Lazy SMP)
+    pub threads: usize,
+}
+
+/// One root move of a [`Searcher::search_multipv`] analysis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RootLine {
+    /// The root move
+    pub mov: Pos,
+    /// Searched score of the move for the side to move
+    pub score: i32,
+    /// Depth completed for this move
+    pub depth: i8,
+}
+
+/// A completed iterative-deepening iteration, as passed to the callback set
+/// with [`Searcher::set_info_callback`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchInfo {
+    /// Depth just completed
+    pub depth: i8,
+    /// Score of the best move for the side to move
+    pub score: i32,
+    /// Expected line from the root, best move first. Followed through the
+    /// transposition table, so it can be shorter than `depth`.
+    pub pv: Vec<Pos>,
+    /// Nodes searched so far over all threads
+    pub nodes: u64,
+    /// Time since the search started (ms)
+    pub time_ms: u64,
+    /// Nodes per second (kN/s)
+    pub nps: u64,
+}
+
+/// Receiver of [`SearchInfo`]. Runs on the searching thread, between
+/// iterations, so it should return quickly.
+pub type InfoCallback = Arc<dyn Fn(SearchInfo) + Send + Sync>;
+
+/// Cancellation and live progress of a [`Searcher`], shared with other
+/// threads through [`Searcher::control`].
+///
+/// A cancel stops the running search (which returns its deepest completed
+/// iteration, as when time runs out) and every later one until
+/// [`SearchControl::reset`].
+#[derive(Debug, Default)]
+pub struct SearchControl {
+    cancelled: AtomicBool,
+    depth: AtomicI8,
+    nodes: AtomicU64,
+}
+
+impl SearchControl {
+    /// Stop the running search as soon as possible.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether [`SearchControl::cancel`] was called since the last reset.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Clear the cancel flag and the progress counters.
+    pub fn reset(&self) {
+        self.cancelled.store(false, Ordering::Relaxed);
+        self.begin_search();
+    }
+
+    /// Deepest iteration completed by the current (or last) search.
+    #[must_use]
+    pub fn depth(&self) -> i8 {
+        self.depth.load(Ordering::Relaxed)
+    }
+
+    /// Nodes searched so far by the current (or last) search over all
+    /// threads; updated every 1024 nodes per thread, exact once it ends.
+    #[must_use]
+    pub fn nodes(&self) -> u64 {
+        self.nodes.load(Ordering::Relaxed)
+    }
+
+    fn begin_search(&self) {
+        self.depth.store(0, Ordering::Relaxed);
+        self.nodes.store(0, Ordering::Relaxed);
+    }
+
+    fn completed_depth(&self, depth: i8) {
+        self.depth.fetch_max(depth, Ordering::Relaxed);
+    }
 }
 
 // =============================================================================
@@ -116,47 +315,216 @@ pub struct SearchResult {
 struct SharedState {
     zobrist: ZobristTable,
     tt: AtomicTT,
+    /// History and countermoves, updated by every worker
+    history: HistoryTable,
+    /// Static evaluations, shared by every worker
+    eval_cache: EvalCache,
     /// Global stop signal — set by main thread when time is up.
     stopped: AtomicBool,
+    /// Outside cancellation and progress reporting
+    control: Arc<SearchControl>,
+    /// Time source of the time limits
+    clock: Arc<dyn Clock>,
+}
+
+// =============================================================================
+// MovePicker: staged move ordering for interior nodes
+// =============================================================================
+
+/// Ordering score of the TT move
+const TT_MOVE_SCORE: i32 = 1_000_000;
+
+/// Top ordering score (850K+: fork or four-level threats) that widens the
+/// move limit. 800K (single block) is NOT tactical enough to warrant more
+/// candidates.
+const TACTICAL_MOVE_SCORE: i32 = 850_000;
+
+/// Where a [`MovePicker`] is in its move list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PickStage {
+    /// Hand out the TT move without scoring anything
+    TtMove,
+    /// Score the remaining candidates
+    Generate,
+    /// Hand out the scored candidates, best first
+    Scored,
+}
+
+/// Staged move generation for one alpha-beta node.
+///
+/// The TT move comes first, unscored. Only when it fails to cut off are the
+/// other candidates scored, and they are then picked one at a time by
+/// selection instead of being sorted. `score_move`'s bands make the later
+/// stages: winning and blocking moves (800K+), captures (600K+), killers
+/// (490-500K), the countermove (400K), then quiet moves by history and shape.
+/// Legality (double-three) is left to the caller, as with
+/// `generate_moves_ordered`.
+///
+/// The scored candidates live on the worker's `move_arena`, above those of
+/// the nodes on the path to this one, so picking allocates nothing.
+struct MovePicker {
+    stage: PickStage,
+    tt_move: Option<Pos>,
+    /// Opponent move the node answers, for the countermove bonus
+    last_move: Pos,
+    /// This node's candidates are `move_arena[start..end]`;
+    /// `move_arena[start..next]` were handed out
+    start: usize,
+    end: usize,
+    next: usize,
+    /// Best score among the generated candidates
+    top_score: i32,
+    tt_move_picked: bool,
+}
+
+impl MovePicker {
+    fn new(tt_move: Option<Pos>, last_move: Pos) -> Self {
+        Self {
+            stage: PickStage::TtMove,
+            tt_move,
+            last_move,
+            start: 0,
+            end: 0,
+            next: 0,
+            top_score: 0,
+            tt_move_picked: false,
+        }
+    }
+
+    /// Whether the node counts as tactical for its move limit. Known once
+    /// the first move is picked: a TT move scores above every threshold.
+    fn is_tactical(&self) -> bool {
+        self.tt_move_picked || self.top_score >= TACTICAL_MOVE_SCORE
+    }
+
+    /// The next move to try and its ordering score
+    fn next(&mut self, worker: &mut WorkerSearcher, board: &mut Board, color: Stone, depth: i8) -> Option<(Pos, i32)> {
+        loop {
+            match self.stage {
+                PickStage::TtMove => {
+                    self.stage = PickStage::Generate;
+                    // Only a candidate can be the TT move (a colliding entry may name anything)
+                    if let Some(mov) = self.tt_move.filter(|&mov| board.candidate_moves().get(mov)) {
+                        self.tt_move_picked = true;
+                        return Some((mov, TT_MOVE_SCORE));
+                    }
+                }
+                PickStage::Generate => {
+                    self.stage = PickStage::Scored;
+                    worker.last_move_for_ordering = Some(self.last_move);
+                    let mut arena = core::mem::take(&mut worker.move_arena);
+                    self.start = arena.len();
+                    if board.is_board_empty() {
+                        arena.push((Pos::new(9, 9), TT_MOVE_SCORE));
+                    } else {
+                        worker.score_candidates(board, color, self.tt_move, depth, &mut arena);
+                    }
+                    if self.tt_move_picked {
+                        if let Some(i) = arena[self.start..].iter().position(|&(mov, _)| Some(mov) == self.tt_move) {
+                            arena.remove(self.start + i);
+                        }
+                    }
+                    (self.next, self.end) = (self.start, arena.len());
+                    self.top_score = arena[self.start..].iter().map(|&(_, score)| score).max().unwrap_or(0);
+                    worker.move_arena = arena;
+                }
+                PickStage::Scored => {
+                    if self.next == self.end {
+                        return None;
+                    }
+                    let moves = &mut worker.move_arena[..self.end];
+                    let rest = &moves[self.next..];
+                    // First of the best, so equal scores keep generation order
+                    let (best, _) = rest.iter().enumerate().fold((0, i32::MIN), |(bi, bs), (i, &(_, score))| {
+                        if score > bs { (i, score) } else { (bi, bs) }
+                    });
+                    let picked = moves[self.next + best];
+                    moves.copy_within(self.next..self.next + best, self.next + 1);
+                    moves[self.next] = picked;
+                    self.next += 1;
+                    return Some(picked);
+                }
+            }
+        }
+    }
 }
 
 // =============================================================================
 // WorkerSearcher: per-thread search state
 // =============================================================================
 
-/// Per-thread search worker. Each worker has its own killer/history tables
-/// and shares the TT + zobrist via Arc<SharedState>.
+/// Per-thread search worker. Each worker has its own killer table and
+/// shares the TT, zobrist and history via Arc<SharedState>.
 struct WorkerSearcher {
     shared: Arc<SharedState>,
     nodes: u64,
+    /// Nodes already added to the shared progress counter
+    nodes_reported: u64,
     max_depth: i8,
     killer_moves: [[Option<Pos>; 2]; 64],
-    history: [[[i32; BOARD_SIZE]; BOARD_SIZE]; 2],
-    countermove: [[[Option<Pos>; BOARD_SIZE]; BOARD_SIZE]; 2],
     last_move_for_ordering: Option<Pos>,
-    start_time: Option<Instant>,
+    /// Search start, on [`WorkerSearcher::now`]'s time line
+    start_time: Option<Duration>,
     time_limit: Option<Duration>,
+    /// Stop after this many nodes (deterministic searches)
+    node_limit: Option<u64>,
     stats: SearchStats,
+    params: SearchParams,
+    /// Distance from the root, for mate-distance scores
+    ply: i32,
+    /// Root moves not to search (`search_multipv` only)
+    root_excluded: Vec<Pos>,
+    /// Move lists of the nodes on the current path, each stacked above its
+    /// parent's and dropped when its node returns; reused across nodes so
+    /// move generation doesn't allocate
+    move_arena: Vec<(Pos, i32)>,
+    /// The only root moves to search ([`SearchLimits::root_moves`])
+    root_only: Option<Bitboard>,
+    /// Lower bound on how far the last root search's best move beat the
+    /// others (`INF` with a single move)
+    root_gap: i32,
+    /// Receives each completed iteration (main worker only)
+    info: Option<InfoCallback>,
+    /// Audit every make/unmake ([`Searcher::set_paranoid`])
+    paranoid: bool,
+    /// Rules the position is played under
+    rules: RuleSet,
+    /// Search tree recorder (`search_traced` only)
+    #[cfg(feature = "trace")]
+    trace: Option<Box<TraceRecorder>>,
 }
 
 impl WorkerSearcher {
     fn new(
         shared: Arc<SharedState>,
         max_depth: i8,
-        start_time: Instant,
+        start_time: Duration,
         time_limit: Duration,
+        params: SearchParams,
+        rules: RuleSet,
     ) -> Self {
         Self {
             shared,
             nodes: 0,
+            nodes_reported: 0,
             max_depth,
             killer_moves: [[None; 2]; 64],
-            history: [[[0; BOARD_SIZE]; BOARD_SIZE]; 2],
-            countermove: [[[None; BOARD_SIZE]; BOARD_SIZE]; 2],
             last_move_for_ordering: None,
             start_time: Some(start_time),
             time_limit: Some(time_limit),
+            node_limit: None,
             stats: SearchStats::default(),
+            params,
+            ply: 0,
+            root_excluded: Vec::new(),
+            move_arena: Vec::with_capacity(MOVE_ARENA_CAPACITY),
+            root_only: None,
+            root_gap: 0,
+            info: None,
+            paranoid: false,
+            rules,
+            #[cfg(feature = "trace")]
+            trace: None,
         }
     }
 
@@ -166,14 +534,24 @@ impl WorkerSearcher {
         self.shared.stopped.load(Ordering::Relaxed)
     }
 
-    /// Check time and set global stop if exceeded.
+    /// Check time (and node budget) and set global stop if exceeded or
+    /// cancelled. Also publishes the node count.
     #[inline]
-    fn check_time(&self) -> bool {
+    fn check_time(&mut self) -> bool {
+        self.report_nodes();
         if self.shared.stopped.load(Ordering::Relaxed) {
             return true;
         }
+        if self.shared.control.is_cancelled() {
+            self.shared.stopped.store(true, Ordering::Relaxed);
+            return true;
+        }
+        if self.node_limit.is_some_and(|limit| self.nodes >= limit) {
+            self.shared.stopped.store(true, Ordering::Relaxed);
+            return true;
+        }
         if let (Some(start), Some(limit)) = (self.start_time, self.time_limit) {
-            if start.elapsed() >= limit {
+            if self.now().saturating_sub(start) >= limit {
                 self.shared.stopped.store(true, Ordering::Relaxed);
                 return true;
             }
@@ -181,6 +559,105 @@ impl WorkerSearcher {
         false
     }
 
+    /// Current time on the shared clock; without one, the nodes this worker
+    /// searched, at [`NODES_PER_MS`] per millisecond.
+    fn now(&self) -> Duration {
+        self.shared.clock.now().unwrap_or_else(|| Duration::from_millis(self.nodes / NODES_PER_MS))
+    }
+
+    /// Add the nodes searched since the last report to the shared count.
+    fn report_nodes(&mut self) {
+        self.shared.control.nodes.fetch_add(self.nodes - self.nodes_reported, Ordering::Relaxed);
+        self.nodes_reported = self.nodes;
+    }
+
+    /// Pass the iteration `result` just completed to the info callback, if
+    /// one is set.
+    fn report_iteration(&mut self, board: &Board, color: Stone, result: &SearchResult, started: Duration) {
+        let Some(callback) = self.info.clone() else {
+            return;
+        };
+        self.report_nodes();
+        let nodes = self.shared.control.nodes();
+        let time_ms = self.now().saturating_sub(started).as_millis() as u64;
+        let pv = result
+            .best_move
+            .map_or_else(Vec::new, |first| self.principal_variation(board, color, first, result.depth));
+        callback(SearchInfo {
+            depth: result.depth,
+            score: result.score,
+            pv,
+            nodes,
+            time_ms,
+            nps: (nodes * 1000).checked_div(time_ms).unwrap_or(0) / 1000,
+        });
+    }
+
+    /// `first`, then the TT move of each following position, up to `depth`
+    /// moves. Stops at a move that isn't legal there and after a five.
+    fn principal_variation(&self, board: &Board, color: Stone, first: Pos, depth: i8) -> Vec<Pos> {
+        let mut board = board.clone();
+        let mut pv = Vec::new();
+        let mut color = color;
+        let mut next = Some(first);
+        while let Some(mov) = next {
+            if pv.len() >= depth.max(1) as usize
+                || !board.is_empty(mov)
+                || !is_valid_move(self.rules, &board, mov, color)
+            {
+                break;
+            }
+            board.place_stone(mov, color);
+            execute_captures_fast(self.rules, &mut board, mov, color);
+            pv.push(mov);
+            if has_five_at_pos(self.rules, &board, mov, color) || board.captures_to_win(self.rules, color) == 0 {
+                break;
+            }
+            color = color.opponent();
+            next = self.shared.tt.get_best_move(self.shared.zobrist.hash(&board, color));
+        }
+        pv
+    }
+
+    /// `evaluate(self.rules, board, color)`, from the evaluation cache when a sibling
+    /// or earlier iteration already computed it.
+    #[inline]
+    fn static_eval(&mut self, board: &mut Board, color: Stone, hash: u64) -> i32 {
+        self.stats.eval_probes += 1;
+        if let Some(score) = self.shared.eval_cache.probe(&self.shared.zobrist, hash, color) {
+            self.stats.eval_hits += 1;
+            return score;
+        }
+        board.refresh_eval();
+        let score = evaluate(self.rules, board, color);
+        self.shared.eval_cache.store(&self.shared.zobrist, hash, color, score);
+        score
+    }
+
+    /// Score for winning at the current ply: shorter wins score higher.
+    #[inline]
+    fn mate_score(&self) -> i32 {
+        PatternScore::FIVE - self.ply
+    }
+
+    /// Probe the TT, converting stored mate scores back to this ply.
+    #[inline]
+    fn probe_tt(&self, hash: u64, depth: i8, alpha: i32, beta: i32) -> Option<(i32, Option<Pos>)> {
+        let (alpha, beta) = (score_to_tt(alpha, self.ply), score_to_tt(beta, self.ply));
+        self.shared
+            .tt
+            .probe(hash, depth, alpha, beta)
+            .map(|(score, mov)| (score_from_tt(score, self.ply), mov))
+    }
+
+    /// Store in the TT with mate scores made relative to this node.
+    #[inline]
+    fn store_tt(&self, hash: u64, depth: i8, score: i32, entry_type: EntryType, best_move: Option<Pos>) {
+        self.shared
+            .tt
+            .store(hash, depth, score_to_tt(score, self.ply), entry_type, best_move);
+    }
+
     /// Iterative deepening search. `start_depth_offset` allows workers
     /// to begin at different depths for natural tree diversification.
     fn search_iterative(
@@ -196,10 +673,12 @@ impl WorkerSearcher {
             depth: 0,
             nodes: 0,
             stats: SearchStats::default(),
+            threads: 1,
         };
 
         let mut work_board = board.clone();
-        let search_start = self.start_time.unwrap_or_else(Instant::now);
+        work_board.enable_incremental_eval(self.rules);
+        let search_start = self.start_time.unwrap_or_else(|| self.now());
         let hard_limit = self.time_limit.unwrap_or(Duration::from_millis(500));
         // soft_limit is for iterative deepening time prediction (when to stop going deeper).
         // At 50% of hard_limit, depth 10 always completes but depth 11+ only starts
@@ -208,9 +687,13 @@ impl WorkerSearcher {
             hard_limit.as_millis() as u64 * 50 / 100
         );
         let mut prev_depth_time = Duration::ZERO;
+        // Last depth at which the best move changed
+        let mut changed_at = 0;
+        // Score of the previous iteration, to spot a falling score
+        let mut prev_score = None;
 
         let min_depth: i8 = if board.stone_count() <= 4 { 8 } else { 10 };
-        const ASP_WINDOW: i32 = 100;
+        let asp_window = self.params.margins.aspiration_window;
 
         // Win/loss confirmation: require TWO consecutive depths to agree on a
         // terminal score before early exit. Prevents illusory wins where depth d
@@ -228,22 +711,17 @@ impl WorkerSearcher {
 
             // History gravity: halve all history scores at each new depth.
             // Ensures recent search results outweigh stale move ordering data.
-            if depth > first_depth {
-                for color_hist in &mut self.history {
-                    for row in color_hist.iter_mut() {
-                        for val in row.iter_mut() {
-                            *val >>= 1;
-                        }
-                    }
-                }
+            // The table is shared, so only the main worker decays it.
+            if depth > first_depth && start_depth_offset == 0 {
+                self.shared.history.decay();
             }
 
-            let depth_start = Instant::now();
+            let depth_start = self.now();
 
             let (mut asp_alpha, mut asp_beta) = if depth >= 3
-                && best_result.score.abs() < PatternScore::FIVE - 100
+                && best_result.score.abs() < MATE_THRESHOLD
             {
-                (best_result.score - ASP_WINDOW, best_result.score + ASP_WINDOW)
+                (best_result.score - asp_window, best_result.score + asp_window)
             } else {
                 (-INF, INF)
             };
@@ -268,16 +746,25 @@ impl WorkerSearcher {
                 break;
             }
 
+            if best_result.best_move.is_some() && result.best_move != best_result.best_move {
+                changed_at = depth;
+            }
+            // The previous best move just got worse: a hard move, whatever the stability
+            let score_dropped = prev_score.is_some_and(|prev: i32| prev - result.score > asp_window);
+            prev_score = Some(result.score);
             best_result = result;
             best_result.depth = depth;
-            let depth_time = depth_start.elapsed();
-            let total_elapsed = search_start.elapsed();
+            self.shared.control.completed_depth(depth);
+            self.report_iteration(board, color, &best_result, search_start);
+            let now = self.now();
+            let depth_time = now.saturating_sub(depth_start);
+            let total_elapsed = now.saturating_sub(search_start);
 
             // Early exit: winning or confirmed loss — only after reaching min_depth
             // AND confirmed over two consecutive depths. This prevents illusory wins
             // where depth d sees FIVE but depth d+1 finds the refutation.
-            let is_winning = best_result.score >= PatternScore::FIVE - 100;
-            let is_losing = best_result.score <= -(PatternScore::FIVE - 100);
+            let is_winning = best_result.score >= MATE_THRESHOLD;
+            let is_losing = best_result.score <= -MATE_THRESHOLD;
 
             if is_winning && prev_was_winning && depth >= min_depth {
                 break;
@@ -300,7 +787,10 @@ impl WorkerSearcher {
             }
 
             // Time check only AFTER min_depth has been completed
-            let remaining = soft_limit.saturating_sub(total_elapsed);
+            let stable_iterations = depth - changed_at;
+            let percent = Self::time_scale_percent(stable_iterations, score_dropped, self.root_gap);
+            let budget = soft_limit * percent / 100;
+            let remaining = budget.saturating_sub(total_elapsed);
             let estimated_next = if prev_depth_time.as_millis() > 0 && depth_time.as_millis() > 0 {
                 let bf = depth_time.as_millis() as f64 / prev_depth_time.as_millis().max(1) as f64;
                 let bf = bf.clamp(1.5, 5.0);
@@ -316,46 +806,95 @@ impl WorkerSearcher {
             }
         }
 
+        self.report_nodes();
         best_result.nodes = self.nodes;
         best_result.stats = self.stats.clone();
         best_result
     }
 
-    /// Root-level search with full alpha-beta window.
-    fn search_root(
-        &mut self,
-        board: &mut Board,
-        color: Stone,
-        depth: i8,
-        mut alpha: i32,
-        beta: i32,
-    ) -> SearchResult {
-        let mut best_move = None;
-        let mut best_score = -INF;
+    /// Share (percent) of the soft time limit to use after an iteration
+    /// that kept the best move for `stable_iterations` iterations: more
+    /// while the best move keeps changing or its score falls, less once it
+    /// has held for [`EASY_MOVE_ITERATIONS`] and least once it is clearly
+    /// forced. The hard limit still stops the search; time left over goes
+    /// back to the engine's time bank.
+    fn time_scale_percent(stable_iterations: i8, score_dropped: bool, root_gap: i32) -> u32 {
+        if stable_iterations <= 1 || score_dropped {
+            160
+        } else if root_gap >= FORCED_MOVE_GAP {
+            40
+        } else if stable_iterations >= EASY_MOVE_ITERATIONS {
+            70
+        } else {
+            100
+        }
+    }
 
-        let hash = self.shared.zobrist.hash(board, color);
+    /// Moves searched at the root, in search order with their ordering
+    /// scores: the TT move first, then the first `max_root_moves` legal
+    /// moves not excluded. A restricted search takes every legal allowed
+    /// move, in move-ordering order where the generator produced it.
+    fn root_moves(&mut self, board: &mut Board, color: Stone, hash: u64, depth: i8) -> Vec<(Pos, i32)> {
         let tt_move = self.shared.tt.get_best_move(hash);
         self.last_move_for_ordering = None;
         let (mut moves, _top_score) = self.generate_moves_ordered(board, color, tt_move, depth);
-        // Lazy double-three: keep the first MAX_ROOT_MOVES valid moves.
+        if let Some(allowed) = self.root_only {
+            moves.retain(|(mov, _)| allowed.get(*mov));
+            // Allowed moves away from the stones are never generated
+            for mov in allowed.iter_ones() {
+                if !moves.iter().any(|(m, _)| *m == mov) {
+                    moves.push((mov, 0));
+                }
+            }
+            moves.retain(|(mov, _)| {
+                !self.root_excluded.contains(mov)
+                    && board.is_empty(*mov)
+                    && is_valid_move(self.rules, board, *mov, color)
+            });
+            return moves;
+        }
+        // Lazy double-three: keep the first max_root_moves valid moves.
         // Forbidden (double-three) moves may score high, so we can't truncate
         // first — that would displace valid defensive moves from the top-N.
         let mut valid_count = 0;
         moves.retain(|(mov, _)| {
-            if valid_count >= MAX_ROOT_MOVES {
+            if valid_count >= self.params.max_root_moves || self.root_excluded.contains(mov) {
                 return false;
             }
-            if is_valid_move(board, *mov, color) {
+            if is_valid_move(self.rules, board, *mov, color) {
                 valid_count += 1;
                 true
             } else {
                 false
             }
         });
+        moves
+    }
+
+    /// Root-level search with full alpha-beta window.
+    fn search_root(
+        &mut self,
+        board: &mut Board,
+        color: Stone,
+        depth: i8,
+        mut alpha: i32,
+        beta: i32,
+    ) -> SearchResult {
+        let mut best_move = None;
+        let mut best_score = -INF;
+        // Fail-soft scores of the other moves are upper bounds, so the gap
+        // to the best of them under-estimates the true lead
+        let mut second_score = -INF;
+        trace_hook!(self, enter(NodeKind::Root, None, color, self.ply, depth, alpha, beta));
+
+        let hash = self.shared.zobrist.hash(board, color);
+        let moves = self.root_moves(board, color, hash, depth);
 
         for (i, (mov, _move_score)) in moves.iter().enumerate() {
+            let audit = self.paranoid.then(|| MoveAudit::before(board, self.rules));
             board.place_stone(*mov, color);
-            let cap_info = execute_captures_fast(board, *mov, color);
+            let cap_info = execute_captures_fast(self.rules, board, *mov, color);
+            self.ply += 1;
 
             let mut child_hash = self.shared.zobrist.update_place(hash, *mov, color);
             for j in 0..cap_info.count as usize {
@@ -373,6 +912,9 @@ impl WorkerSearcher {
                         .zobrist
                         .update_capture_count(child_hash, color, old_count, new_count);
             }
+            if let Some(audit) = &audit {
+                audit.after_make(board, *mov, color, &cap_info, &self.shared.zobrist, child_hash);
+            }
 
             // Threat extension: forcing moves (creating a four) get +1 ply.
             // Forcing moves have only 1-2 legal responses, so the subtree stays narrow.
@@ -415,16 +957,23 @@ impl WorkerSearcher {
                 s
             };
 
+            self.ply -= 1;
             undo_captures(board, color, &cap_info);
             board.remove_stone(*mov);
+            if let Some(audit) = &audit {
+                audit.after_unmake(board, *mov, color);
+            }
 
             if self.is_stopped() {
                 break;
             }
 
             if score > best_score {
+                second_score = best_score;
                 best_score = score;
                 best_move = Some(*mov);
+            } else {
+                second_score = second_score.max(score);
             }
 
             if score >= beta {
@@ -432,9 +981,12 @@ impl WorkerSearcher {
             }
             alpha = alpha.max(score);
         }
+        self.root_gap = if moves.len() > 1 { best_score.saturating_sub(second_score) } else { INF };
 
-        // Store root result in TT for reuse by other workers (Lazy SMP) and next iteration
-        if !self.is_stopped() {
+        // Store root result in TT for reuse by other workers (Lazy SMP) and next iteration.
+        // With excluded or restricted moves the score is not the position's
+        // value, so skip it.
+        if !self.is_stopped() && self.root_excluded.is_empty() && self.root_only.is_none() {
             let entry_type = if best_score >= beta {
                 EntryType::LowerBound
             } else {
@@ -442,6 +994,7 @@ impl WorkerSearcher {
             };
             self.shared.tt.store(hash, depth, best_score, entry_type, best_move);
         }
+        trace_hook!(self, exit(best_score, self.shared.stopped.load(Ordering::Relaxed)));
 
         SearchResult {
             best_move,
@@ -449,6 +1002,7 @@ impl WorkerSearcher {
             depth,
             nodes: self.nodes,
             stats: self.stats.clone(),
+            threads: 1,
         }
     }
 
@@ -497,10 +1051,56 @@ impl WorkerSearcher {
         false
     }
 
+    /// Points where `attacker`, who just played `last_move`, wins next move:
+    /// unbreakable fives on the lines through `last_move`, and captures of
+    /// a group it flanks that reach the capture win.
+    fn horizon_threats(rules: RuleSet, board: &mut Board, last_move: Pos, attacker: Stone) -> Bitboard {
+        let mut threats = Bitboard::new();
+        let at = |k: i32, (dr, dc): (i32, i32)| {
+            let (r, c) = (i32::from(last_move.row) + k * dr, i32::from(last_move.col) + k * dc);
+            Pos::is_valid(r, c).then(|| Pos::new(r as u8, c as u8))
+        };
+
+        for dir in [(1, 0), (0, 1), (1, 1), (1, -1)] {
+            for pos in (-4..=4).filter(|&k| k != 0).filter_map(|k| at(k, dir)) {
+                if board.is_empty(pos)
+                    && has_five_at_pos(rules, board, pos, attacker)
+                    && is_valid_move(rules, board, pos, attacker)
+                    && !Self::five_breakable(rules, board, pos, attacker)
+                {
+                    threats.set(pos);
+                }
+            }
+        }
+
+        let size = i32::from(rules.pair_size());
+        let defender = attacker.opponent();
+        for dir in [(1, 0), (0, 1), (1, 1), (1, -1), (-1, 0), (0, -1), (-1, -1), (-1, 1)] {
+            let flanked = (1..=size).all(|k| at(k, dir).is_some_and(|pos| board.get(pos) == defender));
+            let Some(pos) = at(size + 1, dir).filter(|_| flanked) else { continue };
+            if board.is_empty(pos)
+                && count_captures_fast(rules, board, pos, attacker) >= board.captures_to_win(rules, attacker)
+                && is_valid_move(rules, board, pos, attacker)
+            {
+                threats.set(pos);
+            }
+        }
+        threats
+    }
+
+    /// Whether the five `color` would make at `pos` can be broken by capture
+    fn five_breakable(rules: RuleSet, board: &mut Board, pos: Pos, color: Stone) -> bool {
+        board.place_stone(pos, color);
+        let breakable = find_five_line_at_pos(rules, board, pos, color)
+            .is_some_and(|line| can_break_five_by_capture(rules, board, &line, color));
+        board.remove_stone(pos);
+        breakable
+    }
+
     /// Check if the side to move faces an immediate tactical threat.
-    fn is_threatened(board: &Board, color: Stone, last_move: Pos) -> bool {
+    fn is_threatened(rules: RuleSet, board: &Board, color: Stone, last_move: Pos) -> bool {
         let opp = color.opponent();
-        if board.captures(opp) >= 4 {
+        if board.captures_to_win(rules, opp) <= 1 {
             return true;
         }
         let sz = BOARD_SIZE as i8;
@@ -628,10 +1228,6 @@ impl WorkerSearcher {
         false
     }
 
-    /// Maximum quiescence search depth (plies of forcing moves).
-    /// VCF-style fours are fully forcing, so we can search deep without explosion.
-    const MAX_QS_DEPTH: i8 = 16;
-
     /// Quiescence search at leaf nodes of alpha-beta.
     ///
     /// Instead of returning a static evaluation immediately, we extend the search
@@ -642,9 +1238,36 @@ impl WorkerSearcher {
     /// Design:
     /// - **Stand-pat**: If no forcing move improves alpha, return static eval
     /// - **Forcing moves**: Only fives, four-threats, and capture-wins are searched
+    /// - **Defense**: In the first [`SearchParams::qs_defense_depth`] plies, a
+    ///   five or capture win threatened by the last move rules out standing
+    ///   pat; only wins, blocks of the threat and captures are searched, and
+    ///   with none that holds the node scores as lost
     /// - **Alpha-beta pruning**: Standard cutoffs apply to keep it efficient
-    /// - **Depth-limited**: MAX_QS_DEPTH prevents runaway in complex positions
+    /// - **Depth-limited**: [`SearchParams::max_qs_depth`] plies of forcing moves
+    ///   prevent runaway in complex positions (fours are fully forcing, so QS
+    ///   can go deep without explosion)
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
     fn quiescence(
+        &mut self,
+        board: &mut Board,
+        color: Stone,
+        alpha: i32,
+        beta: i32,
+        last_move: Pos,
+        qs_depth: i8,
+        hash: u64,
+    ) -> i32 {
+        trace_hook!(self, enter(NodeKind::Quiescence, Some(last_move), color, self.ply, qs_depth, alpha, beta));
+        let arena_mark = self.move_arena.len();
+        let score = self.quiescence_node(board, color, alpha, beta, last_move, qs_depth, hash);
+        self.move_arena.truncate(arena_mark);
+        trace_hook!(self, exit(score, self.shared.stopped.load(Ordering::Relaxed)));
+        score
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn quiescence_node(
         &mut self,
         board: &mut Board,
         color: Stone,
@@ -666,13 +1289,14 @@ impl WorkerSearcher {
 
         // Terminal: opponent just won
         let last_player = color.opponent();
-        if board.captures(last_player) >= 5 {
-            return -PatternScore::FIVE;
+        if board.captures_to_win(self.rules, last_player) == 0 {
+            trace_hook!(self, cut(Outcome::Terminal));
+            return -self.mate_score();
         }
-        if has_five_at_pos(board, last_move, last_player) {
+        if has_five_at_pos(self.rules, board, last_move, last_player) {
             // Check breakable five (endgame capture rule)
-            if let Some(five_line) = find_five_line_at_pos(board, last_move, last_player) {
-                if can_break_five_by_capture(board, &five_line, last_player) {
+            if let Some(five_line) = find_five_line_at_pos(self.rules, board, last_move, last_player) {
+                if can_break_five_by_capture(self.rules, board, &five_line, last_player) {
                     // Breakable five: search break moves even in quiescence.
                     // Uses depth=0 so the break-move search recurses into alpha_beta
                     // which enters quiescence for the post-break position.
@@ -681,20 +1305,33 @@ impl WorkerSearcher {
                     );
                 }
             }
-            return -PatternScore::FIVE;
+            trace_hook!(self, cut(Outcome::Terminal));
+            return -self.mate_score();
         }
 
         // TT probe: reuse results from previous searches or other QS nodes.
         // Use depth 0 — any entry (depth >= 0) can satisfy QS queries.
-        if let Some((score, _)) = self.shared.tt.probe(hash, 0, alpha, beta) {
+        if let Some((score, _)) = self.probe_tt(hash, 0, alpha, beta) {
+            trace_hook!(self, cut(Outcome::TtCutoff));
             return score;
         }
 
+        // Threats the last move made are defended, not stood pat against,
+        // or a loss one ply past the horizon hides behind the static eval:
+        // passing then scores as being mated two plies on
+        let threats = if qs_depth < self.params.qs_defense_depth {
+            Self::horizon_threats(self.rules, board, last_move, last_player)
+        } else {
+            Bitboard::new()
+        };
+        let threatened = !threats.is_empty();
+
         // Stand-pat: static evaluation as lower bound
-        let stand_pat = evaluate(board, color);
+        let stand_pat = if threatened { -self.mate_score() + 2 } else { self.static_eval(board, color, hash) };
 
         // Beta cutoff: position is already too good (fail high)
         if stand_pat >= beta {
+            trace_hook!(self, cut(Outcome::StandPat));
             return stand_pat;
         }
 
@@ -704,124 +1341,116 @@ impl WorkerSearcher {
         }
 
         // Depth limit for quiescence
-        if qs_depth >= Self::MAX_QS_DEPTH {
+        if qs_depth >= self.params.max_qs_depth {
             return stand_pat;
         }
 
-        // After depth 4 in QS, only search fives (no more fours)
+        // Deep in QS, only search fives (no more fours)
         // This prevents QS from exploding in complex midgame positions.
-        let fours_allowed = qs_depth < 6;
+        // Fours don't answer a threat to win next move
+        let fours_allowed = !threatened && qs_depth < self.params.qs_fours_depth;
 
         let opponent = color.opponent();
         let sz = BOARD_SIZE as i8;
         let dirs: [(i8, i8); 4] = [(1, 0), (0, 1), (1, 1), (1, -1)];
 
         // Generate forcing moves only: fives, fours, capture-wins.
-        // Use proximity scan (radius 2 from existing stones) instead of full-board.
-        let mut forcing_moves: Vec<(Pos, i32)> = Vec::with_capacity(16);
-        let mut seen = [[false; BOARD_SIZE]; BOARD_SIZE];
-
-        for stone_pos in board.black.iter_ones().chain(board.white.iter_ones()) {
-            for dr in -2i32..=2 {
-                for dc in -2i32..=2 {
-                    let r = i32::from(stone_pos.row) + dr;
-                    let c = i32::from(stone_pos.col) + dc;
-                    if !Pos::is_valid(r, c) { continue; }
-                    #[allow(clippy::cast_sign_loss)]
-                    let (ru, cu) = (r as usize, c as usize);
-                    if seen[ru][cu] { continue; }
-                    seen[ru][cu] = true;
-
-                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-                    let pos = Pos::new(r as u8, c as u8);
-                    if board.get(pos) != Stone::Empty { continue; }
-                    if !is_valid_move(board, pos, color) { continue; }
-
-                    let mut priority = 0i32;
-
-                    // Check five creation / block opponent five / four creation
-                    for &(ddr, ddc) in &dirs {
-                        // Our line
-                        let mut mc = 1i32;
-                        let mut rr = pos.row as i8 + ddr;
-                        let mut cc = pos.col as i8 + ddc;
-                        while rr >= 0 && rr < sz && cc >= 0 && cc < sz
-                            && board.get(Pos::new(rr as u8, cc as u8)) == color
-                        { mc += 1; rr += ddr; cc += ddc; }
-                        let mut mo_p = if rr >= 0 && rr < sz && cc >= 0 && cc < sz
-                            && board.get(Pos::new(rr as u8, cc as u8)) == Stone::Empty { 1 } else { 0 };
-                        rr = pos.row as i8 - ddr;
-                        cc = pos.col as i8 - ddc;
-                        while rr >= 0 && rr < sz && cc >= 0 && cc < sz
-                            && board.get(Pos::new(rr as u8, cc as u8)) == color
-                        { mc += 1; rr -= ddr; cc -= ddc; }
-                        mo_p += if rr >= 0 && rr < sz && cc >= 0 && cc < sz
-                            && board.get(Pos::new(rr as u8, cc as u8)) == Stone::Empty { 1 } else { 0 };
-
-                        if mc >= 5 { priority = 900; break; }
-                        if fours_allowed && mc == 4 && mo_p >= 1 {
-                            priority = priority.max(if mo_p == 2 { 800 } else { 700 });
-                        }
-
-                        // Opponent line
-                        let mut oc = 1i32;
-                        rr = pos.row as i8 + ddr;
-                        cc = pos.col as i8 + ddc;
-                        while rr >= 0 && rr < sz && cc >= 0 && cc < sz
-                            && board.get(Pos::new(rr as u8, cc as u8)) == opponent
-                        { oc += 1; rr += ddr; cc += ddc; }
-                        rr = pos.row as i8 - ddr;
-                        cc = pos.col as i8 - ddc;
-                        while rr >= 0 && rr < sz && cc >= 0 && cc < sz
-                            && board.get(Pos::new(rr as u8, cc as u8)) == opponent
-                        { oc += 1; rr -= ddr; cc -= ddc; }
-
-                        if oc >= 5 { priority = priority.max(850); }
-                    }
+        // Use the board's candidate area (radius 2 from existing stones) instead of full-board.
+        // They go on the move arena, above the moves of the nodes on the path here.
+        let start = self.move_arena.len();
+
+        for pos in board.candidate_moves().iter_ones() {
+            if !is_valid_move(self.rules, board, pos, color) { continue; }
+
+            let mut priority = 0i32;
+
+            // Check five creation / block opponent five / four creation
+            for &(ddr, ddc) in &dirs {
+                // Our line
+                let mut mc = 1i32;
+                let mut rr = pos.row as i8 + ddr;
+                let mut cc = pos.col as i8 + ddc;
+                while rr >= 0 && rr < sz && cc >= 0 && cc < sz
+                    && board.get(Pos::new(rr as u8, cc as u8)) == color
+                { mc += 1; rr += ddr; cc += ddc; }
+                let mut mo_p = if rr >= 0 && rr < sz && cc >= 0 && cc < sz
+                    && board.get(Pos::new(rr as u8, cc as u8)) == Stone::Empty { 1 } else { 0 };
+                rr = pos.row as i8 - ddr;
+                cc = pos.col as i8 - ddc;
+                while rr >= 0 && rr < sz && cc >= 0 && cc < sz
+                    && board.get(Pos::new(rr as u8, cc as u8)) == color
+                { mc += 1; rr -= ddr; cc -= ddc; }
+                mo_p += if rr >= 0 && rr < sz && cc >= 0 && cc < sz
+                    && board.get(Pos::new(rr as u8, cc as u8)) == Stone::Empty { 1 } else { 0 };
+
+                // has_five_at_pos applies the overline and Caro rules
+                if mc >= 5 && has_five_at_pos(self.rules, board, pos, color) { priority = 900; break; }
+                if fours_allowed && mc == 4 && mo_p >= 1 {
+                    priority = priority.max(if mo_p == 2 { 800 } else { 700 });
+                }
 
-                    // Capture-win check
-                    if priority == 0 {
-                        let cap_count = count_captures_fast(board, pos, color);
-                        if cap_count > 0 && board.captures(color) + cap_count >= 5 {
-                            priority = 890;
-                        }
-                    }
+                // Opponent line
+                let mut oc = 1i32;
+                rr = pos.row as i8 + ddr;
+                cc = pos.col as i8 + ddc;
+                while rr >= 0 && rr < sz && cc >= 0 && cc < sz
+                    && board.get(Pos::new(rr as u8, cc as u8)) == opponent
+                { oc += 1; rr += ddr; cc += ddc; }
+                rr = pos.row as i8 - ddr;
+                cc = pos.col as i8 - ddc;
+                while rr >= 0 && rr < sz && cc >= 0 && cc < sz
+                    && board.get(Pos::new(rr as u8, cc as u8)) == opponent
+                { oc += 1; rr -= ddr; cc -= ddc; }
+
+                if oc >= 5 && has_five_at_pos(self.rules, board, pos, opponent) { priority = priority.max(850); }
+            }
 
-                    if priority > 0 {
-                        forcing_moves.push((pos, priority));
-                    }
+            // Capture-win check; under a threat any capture may defend
+            if priority == 0 || threatened {
+                let cap_count = count_captures_fast(self.rules, board, pos, color);
+                if cap_count > 0 && cap_count >= board.captures_to_win(self.rules, color) {
+                    priority = priority.max(890);
+                } else if threatened && cap_count > 0 {
+                    priority = priority.max(840);
                 }
             }
+            if threats.get(pos) {
+                priority = priority.max(860);
+            }
+
+            if priority > 0 {
+                self.move_arena.push((pos, priority));
+            }
         }
 
-        if forcing_moves.is_empty() {
+        let end = self.move_arena.len();
+        if start == end {
             return stand_pat;
         }
 
         // Sort by priority (highest first)
-        forcing_moves.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+        self.move_arena[start..].sort_unstable_by_key(|&(_, priority)| core::cmp::Reverse(priority));
 
         // Move count pruning (PentaZen-style): limit forcing moves per QS node.
         // Fives (900) are always searched. Fours limited to top candidates.
-        let max_qs_moves: usize = if qs_depth <= 2 { 8 } else { 4 };
+        let max_qs_moves = self.params.qs_moves[usize::from(qs_depth > 2)];
 
         let mut best_score = stand_pat;
         let mut best_move: Option<Pos> = None;
-        let mut moves_searched = 0usize;
 
-        for (mov, priority) in &forcing_moves {
-            // Always search fives (priority >= 850), limit fours
-            if *priority < 850 {
-                if moves_searched >= max_qs_moves {
-                    break;
-                }
+        for moves_searched in 0..end - start {
+            let (mov, priority) = self.move_arena[start + moves_searched];
+            // Always search fives (priority >= 850) and defenses, limit fours
+            if priority < 850 && moves_searched >= max_qs_moves && !threatened {
+                break;
             }
-            moves_searched += 1;
-            board.place_stone(*mov, color);
-            let cap_info = execute_captures_fast(board, *mov, color);
+            let audit = self.paranoid.then(|| MoveAudit::before(board, self.rules));
+            board.place_stone(mov, color);
+            let cap_info = execute_captures_fast(self.rules, board, mov, color);
+            self.ply += 1;
 
             // Compute child hash for TT
-            let mut child_hash = self.shared.zobrist.update_place(hash, *mov, color);
+            let mut child_hash = self.shared.zobrist.update_place(hash, mov, color);
             for j in 0..cap_info.count as usize {
                 child_hash = self.shared.zobrist.update_capture(
                     child_hash,
@@ -837,19 +1466,26 @@ impl WorkerSearcher {
                         .zobrist
                         .update_capture_count(child_hash, color, old_count, new_count);
             }
+            if let Some(audit) = &audit {
+                audit.after_make(board, mov, color, &cap_info, &self.shared.zobrist, child_hash);
+            }
 
             let score = -self.quiescence(
                 board,
                 color.opponent(),
                 -beta,
                 -alpha,
-                *mov,
+                mov,
                 qs_depth + 1,
                 child_hash,
             );
 
+            self.ply -= 1;
             undo_captures(board, color, &cap_info);
-            board.remove_stone(*mov);
+            board.remove_stone(mov);
+            if let Some(audit) = &audit {
+                audit.after_unmake(board, mov, color);
+            }
 
             if self.is_stopped() {
                 return 0;
@@ -857,7 +1493,7 @@ impl WorkerSearcher {
 
             if score > best_score {
                 best_score = score;
-                best_move = Some(*mov);
+                best_move = Some(mov);
             }
             if score > alpha {
                 alpha = score;
@@ -879,7 +1515,7 @@ impl WorkerSearcher {
             } else {
                 EntryType::UpperBound
             };
-            self.shared.tt.store(hash, 0, best_score, entry_type, best_move);
+            self.store_tt(hash, 0, best_score, entry_type, best_move);
         }
 
         best_score
@@ -889,6 +1525,7 @@ impl WorkerSearcher {
     /// Called from both alpha_beta and quiescence when `can_break_five_by_capture` is true.
     /// The side to move MUST play a capture that removes a stone from the five,
     /// otherwise they lose (has_five_in_row at next ply returns +FIVE for the five-holder).
+    #[allow(clippy::too_many_arguments)]
     fn search_five_break(
         &mut self,
         board: &mut Board,
@@ -900,12 +1537,12 @@ impl WorkerSearcher {
         five_color: Stone,
         hash: u64,
     ) -> i32 {
-        let break_moves = find_five_break_moves(board, five_positions, five_color);
+        let break_moves = find_five_break_moves(self.rules, board, five_positions, five_color);
         if break_moves.is_empty() {
-            return -PatternScore::FIVE;
+            return -self.mate_score();
         }
 
-        let mut best = -PatternScore::FIVE;
+        let mut best = -self.mate_score();
         for break_pos in &break_moves {
             let break_pos = *break_pos;
             if !board.is_empty(break_pos) {
@@ -913,8 +1550,10 @@ impl WorkerSearcher {
             }
 
             // Make move
+            let audit = self.paranoid.then(|| MoveAudit::before(board, self.rules));
             board.place_stone(break_pos, color);
-            let cap_info = execute_captures_fast(board, break_pos, color);
+            let cap_info = execute_captures_fast(self.rules, board, break_pos, color);
+            self.ply += 1;
 
             // Update Zobrist hash
             let mut child_hash = self.shared.zobrist.update_place(hash, break_pos, color);
@@ -933,6 +1572,9 @@ impl WorkerSearcher {
                         .zobrist
                         .update_capture_count(child_hash, color, old_count, new_count);
             }
+            if let Some(audit) = &audit {
+                audit.after_make(board, break_pos, color, &cap_info, &self.shared.zobrist, child_hash);
+            }
 
             // Recurse: depth-1 into normal alpha-beta (handles depth<=0 → quiescence)
             let search_depth = (depth - 1).max(0);
@@ -948,8 +1590,12 @@ impl WorkerSearcher {
             );
 
             // Unmake move
+            self.ply -= 1;
             undo_captures(board, color, &cap_info);
             board.remove_stone(break_pos);
+            if let Some(audit) = &audit {
+                audit.after_unmake(board, break_pos, color);
+            }
 
             if score > best {
                 best = score;
@@ -969,7 +1615,29 @@ impl WorkerSearcher {
     }
 
     /// Recursive alpha-beta search with negamax formulation.
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
     fn alpha_beta(
+        &mut self,
+        board: &mut Board,
+        color: Stone,
+        depth: i8,
+        alpha: i32,
+        beta: i32,
+        last_move: Pos,
+        hash: u64,
+        allow_null: bool,
+    ) -> i32 {
+        trace_hook!(self, enter(NodeKind::AlphaBeta, Some(last_move), color, self.ply, depth, alpha, beta));
+        let arena_mark = self.move_arena.len();
+        let score = self.alpha_beta_node(board, color, depth, alpha, beta, last_move, hash, allow_null);
+        self.move_arena.truncate(arena_mark);
+        trace_hook!(self, exit(score, self.shared.stopped.load(Ordering::Relaxed)));
+        score
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn alpha_beta_node(
         &mut self,
         board: &mut Board,
         color: Stone,
@@ -983,10 +1651,8 @@ impl WorkerSearcher {
         self.nodes += 1;
 
         // Time check every 1024 nodes
-        if self.nodes & 1023 == 0 {
-            if self.check_time() {
-                return 0;
-            }
+        if self.nodes & 1023 == 0 && self.check_time() {
+            return 0;
         }
 
         if self.is_stopped() {
@@ -995,14 +1661,15 @@ impl WorkerSearcher {
 
         // Fast terminal check
         let last_player = color.opponent();
-        if board.captures(last_player) >= 5 {
-            return -PatternScore::FIVE;
+        if board.captures_to_win(self.rules, last_player) == 0 {
+            trace_hook!(self, cut(Outcome::Terminal));
+            return -self.mate_score();
         }
-        if has_five_at_pos(board, last_move, last_player) {
+        if has_five_at_pos(self.rules, board, last_move, last_player) {
             // Check if the five is breakable by capture (endgame rule).
             // Only called when five exists (rare), so the extra cost is negligible.
-            if let Some(five_line) = find_five_line_at_pos(board, last_move, last_player) {
-                if can_break_five_by_capture(board, &five_line, last_player) {
+            if let Some(five_line) = find_five_line_at_pos(self.rules, board, last_move, last_player) {
+                if can_break_five_by_capture(self.rules, board, &five_line, last_player) {
                     // Breakable five: search only break moves (captures that destroy the five).
                     // The old fixed-score return (-CLOSED_FOUR) missed post-break threats,
                     // causing the AI to play self-destructive captures like K11 in Game 5.
@@ -1011,7 +1678,8 @@ impl WorkerSearcher {
                     );
                 }
             }
-            return -PatternScore::FIVE;
+            trace_hook!(self, cut(Outcome::Terminal));
+            return -self.mate_score();
         }
 
         // Check if the side to move already has an existing five on the board.
@@ -1019,8 +1687,9 @@ impl WorkerSearcher {
         // search tree, but the opponent (last_player) played a non-breaking move.
         // Per game rules, the five-holder wins because the opponent had their
         // chance to break it and didn't take it.
-        if board.stone_count() >= 10 && has_five_in_row(board, color) {
-            return PatternScore::FIVE;
+        if board.stone_count() >= 10 && has_five_in_row(self.rules, board, color) {
+            trace_hook!(self, cut(Outcome::Terminal));
+            return self.mate_score();
         }
 
         if depth <= 0 {
@@ -1029,18 +1698,19 @@ impl WorkerSearcher {
 
         // TT probe
         self.stats.tt_probes += 1;
-        if let Some((score, _best_move)) = self.shared.tt.probe(hash, depth, alpha, beta) {
+        if let Some((score, _best_move)) = self.probe_tt(hash, depth, alpha, beta) {
             self.stats.tt_score_hits += 1;
+            trace_hook!(self, cut(Outcome::TtCutoff));
             return score;
         }
 
         // Pre-compute static eval for pruning decisions.
         // Used by NMP (all depths), RFP (depth 1-3), razoring (depth 1-3),
         // and per-move futility (depth 1-3). evaluate() is O(stones*4) ≈ 1-5μs.
-        let non_terminal = alpha.abs() < PatternScore::FIVE - 100
-            && beta.abs() < PatternScore::FIVE - 100;
+        let non_terminal = alpha.abs() < MATE_THRESHOLD
+            && beta.abs() < MATE_THRESHOLD;
         let static_eval = if non_terminal {
-            evaluate(board, color)
+            self.static_eval(board, color, hash)
         } else {
             0
         };
@@ -1048,24 +1718,26 @@ impl WorkerSearcher {
         // Reverse futility pruning (static null move pruning):
         // At shallow depths, if position is far above beta, even losing
         // margin won't drop below. Cut immediately.
-        // Uses OPEN_THREE (10K) per depth as margin — in Gomoku a single
+        // Margin is one OPEN_THREE per depth by default — in Gomoku a single
         // quiet move can swing eval by up to OPEN_THREE (creating a new threat).
-        if depth <= 3
+        if depth <= SHALLOW_PRUNING_DEPTH
             && non_terminal
-            && static_eval - PatternScore::OPEN_THREE * i32::from(depth) >= beta
+            && static_eval - self.params.margins.reverse_futility_per_depth * i32::from(depth) >= beta
         {
+            trace_hook!(self, cut(Outcome::ReverseFutility));
             return static_eval;
         }
 
         // Razoring: at shallow depths, if static eval is far below alpha,
         // verify with quiescence search. If QS confirms the position is bad, cut.
         // Complementary to RFP (which cuts when eval >> beta).
-        if depth <= 3
+        if depth <= SHALLOW_PRUNING_DEPTH
             && non_terminal
-            && static_eval + PatternScore::OPEN_THREE * i32::from(depth) <= alpha
+            && static_eval + self.params.margins.razor_per_depth * i32::from(depth) <= alpha
         {
             let qs_score = self.quiescence(board, color, alpha, beta, last_move, 0, hash);
             if qs_score <= alpha {
+                trace_hook!(self, cut(Outcome::Razoring));
                 return qs_score;
             }
         }
@@ -1076,15 +1748,17 @@ impl WorkerSearcher {
         // patterns (captures removed our stones, opponent can rebuild threats).
         // R=2 fixed: R=3 was too aggressive, missing critical opponent responses
         // (e.g., opponent replaying captured position to create open four).
-        if allow_null && depth >= 3
+        if allow_null && depth >= self.params.null_move_min_depth
             && non_terminal
             && static_eval >= beta
-            && !Self::is_threatened(board, color, last_move)
+            && !Self::is_threatened(self.rules, board, color, last_move)
         {
-            let r = 2i8;
+            let r = self.params.null_move_reduction;
             let null_depth = (depth - 1 - r).max(0);
 
             let null_hash = self.shared.zobrist.toggle_side(hash);
+            self.ply += 1;
+            trace_hook!(self, label_next(NodeKind::NullMove));
             let null_score = -self.alpha_beta(
                 board,
                 color.opponent(),
@@ -1095,15 +1769,18 @@ impl WorkerSearcher {
                 null_hash,
                 false,
             );
+            self.ply -= 1;
 
             if !self.is_stopped() && null_score >= beta {
                 if depth <= 8 {
+                    trace_hook!(self, cut(Outcome::NullMove));
                     return beta;
                 }
                 let verify = self.alpha_beta(
                     board, color, depth - r, alpha, beta, last_move, hash, false,
                 );
                 if !self.is_stopped() && verify >= beta {
+                    trace_hook!(self, cut(Outcome::NullMove));
                     return beta;
                 }
             }
@@ -1125,82 +1802,57 @@ impl WorkerSearcher {
             }
         }
 
-        self.last_move_for_ordering = Some(last_move);
-        let (mut moves, top_score) = self.generate_moves_ordered(board, color, tt_move, depth);
-        if moves.is_empty() {
-            return evaluate(board, color);
-        }
-
-        // Adaptive move limit: reduce in quiet positions (no tactical patterns).
-        // Tactical threshold: 850K+ means real fork/four-level threats.
-        // 800K (single block) is NOT tactical enough to warrant more candidates.
-        let is_tactical = top_score >= 850_000;
-
-        let max_moves = if is_tactical {
-            match depth {
-                0..=1 => 5,
-                2..=3 => 7,
-                4..=5 => 9,
-                _ => 12,
-            }
-        } else {
-            match depth {
-                0..=1 => 3,
-                2..=3 => 5,
-                4..=5 => 7,
-                _ => 9,
-            }
-        };
-        // Lazy double-three: keep the first max_moves valid moves.
-        // Scan sorted list and accept valid moves until we have enough.
-        // This avoids truncate-then-retain which can displace defensive moves.
-        {
-            let mut valid_count = 0;
-            moves.retain(|(mov, _)| {
-                if valid_count >= max_moves {
-                    return false;
-                }
-                if is_valid_move(board, *mov, color) {
-                    valid_count += 1;
-                    true
-                } else {
-                    false
-                }
-            });
-        }
-
         // Futility pruning setup (reuses static_eval from shallow pruning block)
-        let futility_ok = depth <= 3 && non_terminal;
-        let futility_margin = match depth {
-            1 => PatternScore::CLOSED_FOUR,
-            2 => PatternScore::OPEN_FOUR,
-            _ => PatternScore::OPEN_FOUR + PatternScore::OPEN_THREE, // depth 3: 110K
-        };
+        let futility_ok = depth <= SHALLOW_PRUNING_DEPTH && non_terminal;
+        let futility_margin = self.params.margins.futility_margin(depth);
 
         let mut best_score = -INF;
         let mut best_move = None;
         let mut entry_type = EntryType::UpperBound;
 
-        for (i, (mov, move_score)) in moves.iter().enumerate() {
-            // Futility pruning (uses pre-computed move score — no redundant score_move call)
-            if futility_ok && i > 0 && static_eval + futility_margin <= alpha {
-                if *move_score < 800_000 {
-                    continue;
-                }
+        // Moves come lazily, best first: a TT-move cutoff never scores the rest.
+        let mut picker = MovePicker::new(tt_move, last_move);
+        let mut picked_any = false;
+        let mut searched = 0;
+        while let Some((mov, move_score)) = picker.next(self, board, color, depth) {
+            picked_any = true;
+            // Adaptive move limit: reduce in quiet positions (no tactical patterns).
+            // Lazy double-three: count only valid moves towards the limit, so
+            // forbidden moves can't displace defensive ones.
+            if searched >= self.params.move_limit(depth, picker.is_tactical()) {
+                break;
+            }
+            if !is_valid_move(self.rules, board, mov, color) {
+                continue;
+            }
+            let i = searched;
+            searched += 1;
+
+            // Futility pruning (uses pre-computed move score — no redundant score_move call)
+            if futility_ok && i > 0 && static_eval + futility_margin <= alpha && move_score < 800_000 {
+                trace_hook!(self, skip(mov, color.opponent(), self.ply + 1, depth - 1, Outcome::Futility));
+                continue;
             }
 
             // Late Move Pruning (LMP): at shallow depths, skip quiet moves entirely
             // after trying the first few. Done BEFORE make_move for zero overhead.
             // Note: threshold intentionally exceeds move limits at these depths,
             // so this mainly serves as a safety net for positions with many candidates.
-            if i > 0 && depth <= 3 && i >= (3 + depth as usize * 2) && *move_score < 800_000 {
+            if i > 0 && depth <= 3 && i >= (3 + depth as usize * 2) && move_score < 800_000 {
+                trace_hook!(self, skip(mov, color.opponent(), self.ply + 1, depth - 1, Outcome::LateMove));
                 continue;
             }
 
-            board.place_stone(*mov, color);
-            let cap_info = execute_captures_fast(board, *mov, color);
+            // Start loading the child's TT bucket before making the move;
+            // most moves capture nothing, so this is already the final hash.
+            let mut child_hash = self.shared.zobrist.update_place(hash, mov, color);
+            self.shared.tt.prefetch(child_hash);
+
+            let audit = self.paranoid.then(|| MoveAudit::before(board, self.rules));
+            board.place_stone(mov, color);
+            let cap_info = execute_captures_fast(self.rules, board, mov, color);
+            self.ply += 1;
 
-            let mut child_hash = self.shared.zobrist.update_place(hash, *mov, color);
             for j in 0..cap_info.count as usize {
                 child_hash = self.shared.zobrist.update_capture(
                     child_hash,
@@ -1215,6 +1867,10 @@ impl WorkerSearcher {
                     self.shared
                         .zobrist
                         .update_capture_count(child_hash, color, old_count, new_count);
+                self.shared.tt.prefetch(child_hash);
+            }
+            if let Some(audit) = &audit {
+                audit.after_make(board, mov, color, &cap_info, &self.shared.zobrist, child_hash);
             }
 
             let is_capture = cap_info.pairs > 0;
@@ -1222,7 +1878,7 @@ impl WorkerSearcher {
             // Threat extension: forcing moves (creating a four) get +1 ply.
             // Fours have only 1-2 legal responses → narrow subtree, minimal cost.
             // Only extend at depth >= 2: at depth 1, quiescence already handles threats.
-            let extension = if depth >= 2 && Self::move_creates_four(board, *mov, color) { 1i8 } else { 0i8 };
+            let extension = if depth >= 2 && Self::move_creates_four(board, mov, color) { 1i8 } else { 0i8 };
 
             // PVS + LMR
             let score = if i == 0 {
@@ -1232,7 +1888,7 @@ impl WorkerSearcher {
                     depth - 1 + extension,
                     -beta,
                     -alpha,
-                    *mov,
+                    mov,
                     child_hash,
                     true,
                 )
@@ -1240,14 +1896,16 @@ impl WorkerSearcher {
                 // LMR: logarithmic reduction + score-aware adjustment (Stockfish-inspired).
                 // Captures, extensions, shallow depths, and PV move get no reduction.
                 // Quiet moves (score < 500K) get +1 extra reduction — they rarely refute.
-                let reduction = if is_capture || extension > 0 || depth < 2 {
+                // Losing captures are scored as quiet moves, so they are reduced too.
+                let reduction = if (is_capture && move_score >= 500_000) || extension > 0 || depth < 2 {
                     0i8
                 } else {
-                    let d = depth as f32;
-                    let m = i as f32;
-                    let mut r = (d.sqrt() * m.sqrt() / 2.0) as i8;
+                    // f64 roots rounded to f32 are exactly the f32 roots
+                    let d = super::sqrt(f64::from(depth)) as f32;
+                    let m = super::sqrt(i as f64) as f32;
+                    let mut r = (d * m * self.params.lmr_percent as f32 / 100.0) as i8;
                     // Score-aware: quiet moves with no tactical value get more reduction
-                    if *move_score < 500_000 { r += 1; }
+                    if move_score < 500_000 { r += self.params.lmr_quiet_extra; }
                     r.max(1).min(depth - 2)
                 };
                 let search_depth = (depth - 1 + extension - reduction).max(0);
@@ -1258,7 +1916,7 @@ impl WorkerSearcher {
                     search_depth,
                     -(alpha + 1),
                     -alpha,
-                    *mov,
+                    mov,
                     child_hash,
                     true,
                 );
@@ -1270,7 +1928,7 @@ impl WorkerSearcher {
                         depth - 1 + extension,
                         -(alpha + 1),
                         -alpha,
-                        *mov,
+                        mov,
                         child_hash,
                         true,
                     );
@@ -1283,7 +1941,7 @@ impl WorkerSearcher {
                         depth - 1 + extension,
                         -beta,
                         -alpha,
-                        *mov,
+                        mov,
                         child_hash,
                         true,
                     );
@@ -1291,8 +1949,12 @@ impl WorkerSearcher {
                 s
             };
 
+            self.ply -= 1;
             undo_captures(board, color, &cap_info);
-            board.remove_stone(*mov);
+            board.remove_stone(mov);
+            if let Some(audit) = &audit {
+                audit.after_unmake(board, mov, color);
+            }
 
             if self.is_stopped() {
                 return 0;
@@ -1300,7 +1962,7 @@ impl WorkerSearcher {
 
             if score > best_score {
                 best_score = score;
-                best_move = Some(*mov);
+                best_move = Some(mov);
             }
 
             if score >= beta {
@@ -1310,19 +1972,14 @@ impl WorkerSearcher {
                 }
                 #[allow(clippy::cast_sign_loss)]
                 let ply = (self.max_depth - depth).max(0) as usize;
-                if ply < 64 {
-                    if self.killer_moves[ply][0] != Some(*mov) {
-                        self.killer_moves[ply][1] = self.killer_moves[ply][0];
-                        self.killer_moves[ply][0] = Some(*mov);
-                    }
+                if ply < 64 && self.killer_moves[ply][0] != Some(mov) {
+                    self.killer_moves[ply][1] = self.killer_moves[ply][0];
+                    self.killer_moves[ply][0] = Some(mov);
                 }
-                let cidx = if color == Stone::Black { 0 } else { 1 };
-                self.history[cidx][mov.row as usize][mov.col as usize] +=
-                    i32::from(depth) * i32::from(depth);
+                self.shared.history.reward(color, mov, depth);
 
                 // Countermove: record best response to opponent's last move
-                let opp_idx = if color == Stone::Black { 1 } else { 0 };
-                self.countermove[opp_idx][last_move.row as usize][last_move.col as usize] = Some(*mov);
+                self.shared.history.set_countermove(color.opponent(), last_move, mov);
 
                 entry_type = EntryType::LowerBound;
                 break;
@@ -1334,9 +1991,10 @@ impl WorkerSearcher {
             }
         }
 
-        self.shared
-            .tt
-            .store(hash, depth, best_score, entry_type, best_move);
+        if !picked_any {
+            return evaluate(self.rules, board, color);
+        }
+        self.store_tt(hash, depth, best_score, entry_type, best_move);
 
         best_score
     }
@@ -1345,52 +2003,21 @@ impl WorkerSearcher {
     #[must_use]
     #[cfg(test)]
     fn generate_moves(&self, board: &Board, color: Stone) -> Vec<Pos> {
-        let mut moves = Vec::with_capacity(50);
-        let mut seen = [[false; BOARD_SIZE]; BOARD_SIZE];
-
         if board.is_board_empty() {
             return vec![Pos::new(9, 9)];
         }
 
-        let radius = 2i32;
-
-        for pos in board.black.iter_ones().chain(board.white.iter_ones()) {
-            for dr in -radius..=radius {
-                for dc in -radius..=radius {
-                    let r = i32::from(pos.row) + dr;
-                    let c = i32::from(pos.col) + dc;
-
-                    if !Pos::is_valid(r, c) {
-                        continue;
-                    }
-
-                    #[allow(clippy::cast_sign_loss)]
-                    let r_usize = r as usize;
-                    #[allow(clippy::cast_sign_loss)]
-                    let c_usize = c as usize;
-
-                    if seen[r_usize][c_usize] {
-                        continue;
-                    }
-                    seen[r_usize][c_usize] = true;
-
-                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-                    let new_pos = Pos::new(r as u8, c as u8);
-
-                    if is_valid_move(board, new_pos, color) {
-                        moves.push(new_pos);
-                    }
-                }
-            }
-        }
-
-        moves
+        board
+            .candidate_moves()
+            .iter_ones()
+            .filter(|&new_pos| is_valid_move(self.rules, board, new_pos, color))
+            .collect()
     }
 
     /// Score a move for ordering purposes (defense-first philosophy).
     fn score_move(
         &self,
-        board: &Board,
+        board: &mut Board,
         mov: Pos,
         color: Stone,
         tt_move: Option<Pos>,
@@ -1399,12 +2026,13 @@ impl WorkerSearcher {
         let opponent = color.opponent();
 
         if tt_move == Some(mov) {
-            return 1_000_000;
+            return TT_MOVE_SCORE;
         }
 
         // Direct bitboard access: 1 lookup per check vs board.get()'s 2.
-        let my_bb = board.stones(color).unwrap();
-        let opp_bb = board.stones(opponent).unwrap();
+        // Copies, so the capture SEE below can play moves on `board`.
+        let (my_stones, opp_stones) = (*board.stones(color).unwrap(), *board.stones(opponent).unwrap());
+        let (my_bb, opp_bb) = (&my_stones, &opp_stones);
 
         let dirs: [(i8, i8); 4] = [(1, 0), (0, 1), (1, 1), (1, -1)];
         let mut my_five = false;
@@ -1427,14 +2055,14 @@ impl WorkerSearcher {
             let (mc, mo, mc_gap, mc_consec, oc, oo, oc_gap, oc_consec) =
                 Self::count_line_both(my_bb, opp_bb, mov, dr, dc);
 
-            if mc_consec >= 5 {
+            if mc_consec >= 5 && has_five_at_pos(self.rules, board, mov, color) {
                 my_five = true;
             } else if mc >= 5 && mc_gap {
                 // Gap-five: e.g. OO_OO — filling the gap creates five-in-a-row.
                 // Treat as open four (one move away from winning).
                 my_open_four_count += 1;
             }
-            if oc_consec >= 5 {
+            if oc_consec >= 5 && has_five_at_pos(self.rules, board, mov, opponent) {
                 opp_five = true;
             } else if oc >= 5 && oc_gap {
                 opp_open_four_count += 1;
@@ -1495,12 +2123,12 @@ impl WorkerSearcher {
             return 895_000;
         }
 
-        let capture_count = i32::from(count_captures_fast(board, mov, color));
-        if capture_count > 0 && i32::from(board.captures(color)) + capture_count >= 5 {
+        let capture_count = i32::from(count_captures_fast(self.rules, board, mov, color));
+        if capture_count > 0 && capture_count >= i32::from(board.captures_to_win(self.rules, color)) {
             return 890_000;
         }
-        let opp_capture = i32::from(count_captures_fast(board, mov, opponent));
-        if opp_capture > 0 && i32::from(board.captures(opponent)) + opp_capture >= 5 {
+        let opp_capture = i32::from(count_captures_fast(self.rules, board, mov, opponent));
+        if opp_capture > 0 && opp_capture >= i32::from(board.captures_to_win(self.rules, opponent)) {
             return 885_000;
         }
 
@@ -1531,11 +2159,11 @@ impl WorkerSearcher {
         }
 
         // Capture-based urgency (opponent near capture win)
-        let opp_caps = board.captures(opponent);
-        if opp_capture > 0 && opp_caps >= 3 {
+        let opp_to_win = board.captures_to_win(self.rules, opponent);
+        if opp_capture > 0 && opp_to_win <= 2 {
             return 855_000;
         }
-        if opp_capture > 0 && opp_caps >= 2 {
+        if opp_capture > 0 && opp_to_win <= 3 {
             return 845_000;
         }
 
@@ -1561,11 +2189,13 @@ impl WorkerSearcher {
             return 800_000;
         }
 
-        if capture_count > 0 {
-            let my_caps = i32::from(board.captures(color));
-            let cap_urgency = if my_caps + capture_count >= 4 {
+        // Losing captures (the pair comes straight back, SEE < 0) fall through
+        // and are ordered like quiet moves.
+        if capture_count > 0 && see_capture(self.rules, board, mov, color) >= 0 {
+            let my_to_win = i32::from(board.captures_to_win(self.rules, color));
+            let cap_urgency = if capture_count + 1 >= my_to_win {
                 150_000
-            } else if my_caps >= 2 {
+            } else if my_to_win <= 3 {
                 80_000
             } else {
                 50_000
@@ -1574,7 +2204,8 @@ impl WorkerSearcher {
         }
 
         if opp_capture > 0 {
-            return 550_000 + i32::from(opp_caps) * 30_000;
+            // Up to 4 steps for how close the opponent is to the capture win
+            return 550_000 + i32::from(5 - opp_to_win.min(5)) * 30_000;
         }
 
         // Immediate capture penalty: detect if placing here creates a pair
@@ -1586,8 +2217,8 @@ impl WorkerSearcher {
             let r = mov.row as i8;
             let c = mov.col as i8;
             let sz = BOARD_SIZE as i8;
-            let opp_caps = i32::from(board.captures(opponent));
-            let setup_weight = if opp_caps >= 3 { 100_000 } else if opp_caps >= 2 { 75_000 } else { 50_000 };
+            // Pair-shaped (pair size 2); larger groups just see less of it
+            let setup_weight = if opp_to_win <= 2 { 100_000 } else if opp_to_win <= 3 { 75_000 } else { 50_000 };
             for &(dr, dc) in &dirs {
                 for sign in [-1i8, 1i8] {
                     // Cells relative to mov: -1*sign, 0(mov), +1*sign, +2*sign
@@ -1638,14 +2269,12 @@ impl WorkerSearcher {
 
         // Countermove bonus: if this move is the best recorded response to opponent's last move
         if let Some(lm) = self.last_move_for_ordering {
-            let opp_idx = if color == Stone::Black { 1 } else { 0 };
-            if self.countermove[opp_idx][lm.row as usize][lm.col as usize] == Some(mov) {
+            if self.shared.history.countermove(color.opponent(), lm) == Some(mov) {
                 return 400_000 - capture_penalty;
             }
         }
 
-        let cidx = if color == Stone::Black { 0 } else { 1 };
-        let hist = self.history[cidx][mov.row as usize][mov.col as usize];
+        let hist = self.shared.history.score(color, mov);
 
         #[allow(clippy::cast_possible_wrap)]
         let center = (BOARD_SIZE / 2) as i32;
@@ -1691,59 +2320,42 @@ impl WorkerSearcher {
     /// and score-aware pruning decisions (LMR, futility, LMP).
     fn generate_moves_ordered(
         &self,
-        board: &Board,
+        board: &mut Board,
         color: Stone,
         tt_move: Option<Pos>,
         depth: i8,
     ) -> (Vec<(Pos, i32)>, i32) {
-        let mut seen = [[false; BOARD_SIZE]; BOARD_SIZE];
-
         if board.is_board_empty() {
             return (vec![(Pos::new(9, 9), 1_000_000)], 0);
         }
-
-        let radius = 2i32;
-        let mut scored: Vec<(Pos, i32)> = Vec::with_capacity(50);
-
-        for pos in board.black.iter_ones().chain(board.white.iter_ones()) {
-            for dr in -radius..=radius {
-                for dc in -radius..=radius {
-                    let r = i32::from(pos.row) + dr;
-                    let c = i32::from(pos.col) + dc;
-
-                    if !Pos::is_valid(r, c) {
-                        continue;
-                    }
-
-                    #[allow(clippy::cast_sign_loss)]
-                    let r_usize = r as usize;
-                    #[allow(clippy::cast_sign_loss)]
-                    let c_usize = c as usize;
-
-                    if seen[r_usize][c_usize] {
-                        continue;
-                    }
-                    seen[r_usize][c_usize] = true;
-
-                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-                    let new_pos = Pos::new(r as u8, c as u8);
-
-                    // Lazy double-three: only check is_empty here (2 bb ops).
-                    // Full is_valid_move (80+ bb ops for double-three) deferred to
-                    // the search loop where adaptive limits prune most candidates.
-                    if board.is_empty(new_pos) {
-                        let score = self.score_move(board, new_pos, color, tt_move, depth);
-                        scored.push((new_pos, score));
-                    }
-                }
-            }
-        }
-
-        scored.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+        let mut scored = Vec::new();
+        self.score_candidates(board, color, tt_move, depth, &mut scored);
+        scored.sort_unstable_by_key(|&(_, score)| core::cmp::Reverse(score));
         let top_score = scored.first().map_or(0, |(_, s)| *s);
         (scored, top_score)
     }
 
+    /// Append every candidate move with its [`score_move`](Self::score_move)
+    /// score, unsorted, to `out`.
+    fn score_candidates(
+        &self,
+        board: &mut Board,
+        color: Stone,
+        tt_move: Option<Pos>,
+        depth: i8,
+        out: &mut Vec<(Pos, i32)>,
+    ) {
+        // Empty cells within radius 2 of any stone, kept up to date by the board.
+        // Lazy double-three: full is_valid_move (80+ bb ops for double-three)
+        // deferred to the search loop where adaptive limits prune most candidates.
+        out.extend(
+            board
+                .candidate_moves()
+                .iter_ones()
+                .map(|new_pos| (new_pos, self.score_move(board, new_pos, color, tt_move, depth))),
+        );
+    }
+
     /// Scan a line from `pos` in both directions for both colors simultaneously.
     ///
     /// Merges two separate scans into one bidirectional pass, halving cell lookups.
@@ -2078,14 +2690,16 @@ pub struct Searcher {
     shared: Arc<SharedState>,
     max_depth: i8,
     num_threads: usize,
-    // Per-search state for single-threaded `search()` API
-    history: [[[i32; BOARD_SIZE]; BOARD_SIZE]; 2],
+    params: SearchParams,
+    info: Option<InfoCallback>,
+    paranoid: bool,
+    rules: RuleSet,
 }
 
 impl Searcher {
     /// Create a new searcher with the specified transposition table size.
     ///
-    /// Uses all available CPU cores for parallel search (Lazy SMP).
+    /// Uses the available CPU cores, up to 8, for parallel search (Lazy SMP).
     ///
     /// # Arguments
     ///
@@ -2100,25 +2714,61 @@ impl Searcher {
     /// ```
     #[must_use]
     pub fn new(tt_size_mb: usize) -> Self {
-        let num_threads = std::thread::available_parallelism()
-            .map(|n| n.get().min(8))
-            .unwrap_or(4);
-        Self::with_threads(tt_size_mb, num_threads)
+        Self::with_threads(tt_size_mb, Self::auto_threads())
     }
 
-    /// Create a new searcher with explicit thread count.
+    /// Most threads a searcher runs; [`Searcher::with_threads`] and
+    /// [`Searcher::set_threads`] clamp to it
+    pub const MAX_THREADS: usize = 64;
+
+    /// Thread count [`Searcher::new`] picks: the available cores, at most
+    /// 8 (Lazy SMP gains little beyond that); 1 without `std`.
+    /// [`Searcher::set_threads`] overrides it in either direction.
+    #[must_use]
+    pub fn auto_threads() -> usize {
+        #[cfg(feature = "std")]
+        let threads = std::thread::available_parallelism().map(|n| n.get().min(8)).unwrap_or(4);
+        #[cfg(not(feature = "std"))]
+        let threads = 1;
+        threads
+    }
+
+    /// Create a searcher tuned by `params` instead of the defaults, with
+    /// the thread count of [`Searcher::new`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `params` fails [`SearchParams::validate`].
+    pub fn with_params(tt_size_mb: usize, params: SearchParams) -> Result<Self, String> {
+        let mut searcher = Self::new(tt_size_mb);
+        searcher.set_params(params)?;
+        Ok(searcher)
+    }
+
+    /// Create a new searcher with explicit thread count, 1 to
+    /// [`Searcher::MAX_THREADS`].
+    ///
+    /// On `wasm32` and without `std` there are no OS threads, so the count
+    /// is always 1.
     #[must_use]
     pub fn with_threads(tt_size_mb: usize, num_threads: usize) -> Self {
-        let num_threads = num_threads.max(1);
+        let num_threads = Self::clamp_threads(num_threads);
         Self {
             shared: Arc::new(SharedState {
                 zobrist: ZobristTable::new(),
                 tt: AtomicTT::new(tt_size_mb),
+                history: HistoryTable::new(),
+                eval_cache: EvalCache::new(EVAL_CACHE_MB),
                 stopped: AtomicBool::new(false),
+                control: Arc::default(),
+                clock: Arc::new(SystemClock),
             }),
             max_depth: 10,
             num_threads,
-            history: [[[0; BOARD_SIZE]; BOARD_SIZE]; 2],
+            params: SearchParams::default(),
+            info: None,
+            paranoid: false,
+            rules: RuleSet::NINUKI,
         }
     }
 
@@ -2127,57 +2777,8 @@ impl Searcher {
     /// Used by tests and when precise deterministic behavior is needed.
     #[must_use]
     pub fn search(&mut self, board: &Board, color: Stone, max_depth: i8) -> SearchResult {
-        self.shared.stopped.store(false, Ordering::Relaxed);
-        self.max_depth = max_depth;
-
-        let mut worker = WorkerSearcher {
-            shared: Arc::clone(&self.shared),
-            nodes: 0,
-            max_depth,
-            killer_moves: [[None; 2]; 64],
-            history: self.history,
-            countermove: [[[None; BOARD_SIZE]; BOARD_SIZE]; 2],
-            last_move_for_ordering: None,
-            start_time: None,
-            time_limit: None,
-            stats: SearchStats::default(),
-        };
-
-        let mut best_result = SearchResult {
-            best_move: None,
-            score: 0,
-            depth: 0,
-            nodes: 0,
-            stats: SearchStats::default(),
-        };
-
-        let mut work_board = board.clone();
-        let mut prev_was_winning = false;
-        let mut prev_was_losing = false;
-
-        for depth in 1..=max_depth {
-            let result = worker.search_root(&mut work_board, color, depth, -INF, INF);
-            best_result = result;
-            best_result.depth = depth;
-
-            let is_winning = best_result.score >= PatternScore::FIVE - 100;
-            let is_losing = best_result.score <= -(PatternScore::FIVE - 100);
-
-            if is_winning && prev_was_winning && depth >= 12 {
-                break;
-            }
-            if is_losing && prev_was_losing && depth >= 10 {
-                break;
-            }
-
-            prev_was_winning = is_winning;
-            prev_was_losing = is_losing;
-        }
-
-        best_result.nodes = worker.nodes;
-        best_result.stats = worker.stats.clone();
-        self.history = worker.history;
-        best_result
+        let mut worker = self.single_worker(max_depth, None, None);
+        self.run_single(&mut worker, board, color, max_depth, false)
     }
 
     /// Search with smart time management using Lazy SMP parallel search.
@@ -2193,41 +2794,100 @@ impl Searcher {
         max_depth: i8,
         time_limit_ms: u64,
     ) -> SearchResult {
-        self.shared.stopped.store(false, Ordering::Relaxed);
-        self.max_depth = max_depth;
-        let start = Instant::now();
         // Hard limit for check_time(): generous enough to guarantee min_depth (10)
         // but tight enough to keep average under 500ms.
         // At 500ms input: hard=750ms, soft=375ms.
-        let time_limit = Duration::from_millis(time_limit_ms * 3 / 2);
+        self.search_smp(board, color, max_depth, Duration::from_millis(time_limit_ms.saturating_mul(3) / 2), None)
+    }
+
+    /// Search within `limits`.
+    ///
+    /// Pure time/depth limits use Lazy SMP with `max_time` as the hard limit.
+    /// Node and mate limits search single-threaded so that node counts are
+    /// exact and runs reproduce. Without any node or time limit the search
+    /// runs to the depth limit (default [`MAX_SEARCH_DEPTH`](super::MAX_SEARCH_DEPTH)).
+    #[must_use]
+    pub fn search_with_limits(&mut self, board: &Board, color: Stone, limits: &SearchLimits) -> SearchResult {
+        let max_depth = limits.effective_depth();
+        match limits.max_time {
+            Some(max_time) if limits.allows_parallel() => {
+                self.search_smp(board, color, max_depth, max_time, limits.root_moves)
+            }
+            _ => {
+                let mut worker = self.single_worker(max_depth, limits.max_nodes, limits.max_time);
+                worker.root_only = limits.root_moves;
+                self.run_single(&mut worker, board, color, max_depth, limits.mate_in.is_some())
+            }
+        }
+    }
+
+    /// Lazy SMP iterative deepening with a hard time limit, on the root
+    /// moves in `root_only` if given.
+    fn search_smp(
+        &mut self,
+        board: &Board,
+        color: Stone,
+        max_depth: i8,
+        time_limit: Duration,
+        root_only: Option<Bitboard>,
+    ) -> SearchResult {
+        self.shared.stopped.store(false, Ordering::Relaxed);
+        self.shared.control.begin_search();
+        self.shared.tt.new_search();
+        self.max_depth = max_depth;
+        let start = self.clock_start();
 
-        // Spawn helper threads (workers 1..N)
+        // Spawn helper threads (workers 1..N); without `std` the main
+        // worker searches alone, and so do the helpers the OS refuses
+        #[cfg(feature = "std")]
         let handles: Vec<_> = (1..self.num_threads)
-            .map(|thread_id| {
+            .filter_map(|thread_id| {
                 let shared = Arc::clone(&self.shared);
                 let board_clone = board.clone();
                 let start_depth_offset = thread_id as i8;
-
-                std::thread::spawn(move || {
-                    let mut worker =
-                        WorkerSearcher::new(shared, max_depth, start, time_limit);
-                    worker.search_iterative(&board_clone, color, max_depth, start_depth_offset)
-                })
+                let params = self.params;
+                let paranoid = self.paranoid;
+                let rules = self.rules;
+
+                std::thread::Builder::new()
+                    .spawn(move || {
+                        let mut worker =
+                            WorkerSearcher::new(shared, max_depth, start, time_limit, params, rules);
+                        worker.root_only = root_only;
+                        worker.paranoid = paranoid;
+                        worker.search_iterative(&board_clone, color, max_depth, start_depth_offset)
+                    })
+                    .ok()
             })
             .collect();
+        #[cfg(feature = "std")]
+        let threads = 1 + handles.len();
+        #[cfg(not(feature = "std"))]
+        let threads = 1;
 
         // Main thread = worker 0
         let mut main_worker = WorkerSearcher {
             shared: Arc::clone(&self.shared),
             nodes: 0,
+            nodes_reported: 0,
             max_depth,
             killer_moves: [[None; 2]; 64],
-            history: self.history,
-            countermove: [[[None; BOARD_SIZE]; BOARD_SIZE]; 2],
             last_move_for_ordering: None,
             start_time: Some(start),
             time_limit: Some(time_limit),
+            node_limit: None,
             stats: SearchStats::default(),
+            params: self.params,
+            ply: 0,
+            root_excluded: Vec::new(),
+            move_arena: Vec::with_capacity(MOVE_ARENA_CAPACITY),
+            root_only,
+            root_gap: 0,
+            info: self.info.clone(),
+            paranoid: self.paranoid,
+            rules: self.rules,
+            #[cfg(feature = "trace")]
+            trace: None,
         };
         let main_result = main_worker.search_iterative(board, color, max_depth, 0);
 
@@ -2236,9 +2896,12 @@ impl Searcher {
 
         // Collect results — pick best (deepest search, then highest score)
         let mut best = main_result;
+        #[cfg_attr(not(feature = "std"), allow(unused_mut))]
         let mut total_nodes = best.nodes;
+        #[cfg_attr(not(feature = "std"), allow(unused_mut))]
         let mut merged_stats = best.stats.clone();
 
+        #[cfg(feature = "std")]
         for handle in handles {
             if let Ok(result) = handle.join() {
                 total_nodes += result.nodes;
@@ -2253,13 +2916,312 @@ impl Searcher {
 
         best.nodes = total_nodes;
         best.stats = merged_stats;
-        self.history = main_worker.history;
+        best.threads = threads;
         best
     }
 
-    /// Clear history heuristic and killer moves.
+    /// Single-threaded iterative deepening bounded by a node budget instead
+    /// of the clock.
+    ///
+    /// The same searcher state, position and budget always give the same
+    /// result. Returns the deepest fully searched iteration (or the partial
+    /// first iteration if the budget runs out before depth 1 completes).
+    #[must_use]
+    pub fn search_nodes(
+        &mut self,
+        board: &Board,
+        color: Stone,
+        max_depth: i8,
+        node_limit: u64,
+    ) -> SearchResult {
+        let mut worker = self.single_worker(max_depth, Some(node_limit), None);
+        self.run_single(&mut worker, board, color, max_depth, false)
+    }
+
+    /// The best `lines` root moves for `color`, best first, each with its
+    /// own searched score.
+    ///
+    /// Runs one [`Searcher::search_nodes`]-style search per line, excluding
+    /// the moves of the lines already found, so later lines cost as much as
+    /// the first. Returns fewer lines when the position has fewer legal
+    /// moves or a search runs out of budget before finding any move.
+    #[must_use]
+    pub fn search_multipv(
+        &mut self,
+        board: &Board,
+        color: Stone,
+        max_depth: i8,
+        node_limit: u64,
+        lines: usize,
+    ) -> Vec<RootLine> {
+        let mut found: Vec<RootLine> = Vec::with_capacity(lines);
+        while found.len() < lines {
+            let mut worker = self.single_worker(max_depth, Some(node_limit), None);
+            worker.root_excluded = found.iter().map(|line| line.mov).collect();
+            let result = self.run_single(&mut worker, board, color, max_depth, false);
+            let Some(mov) = result.best_move else { break };
+            found.push(RootLine { mov, score: result.score, depth: result.depth });
+        }
+        found
+    }
+
+    /// Single-threaded iterative deepening bounded by a node budget that
+    /// also records the search tree, up to `trace_budget` nodes.
+    ///
+    /// Otherwise identical to [`Searcher::search_nodes`]; see
+    /// [`super::trace`] for what the trace contains.
+    #[cfg(feature = "trace")]
+    pub fn search_traced(
+        &mut self,
+        board: &Board,
+        color: Stone,
+        max_depth: i8,
+        trace_budget: usize,
+    ) -> (SearchResult, SearchTrace) {
+        let mut worker = self.single_worker(max_depth, None, None);
+        worker.trace = Some(Box::new(TraceRecorder::new(trace_budget)));
+        let result = self.run_single(&mut worker, board, color, max_depth, false);
+        let trace = worker.trace.take().map(|t| t.finish()).unwrap_or_default();
+        (result, trace)
+    }
+
+    /// Fresh single-threaded worker sharing this searcher's TT and history.
+    fn single_worker(&mut self, max_depth: i8, node_limit: Option<u64>, time_limit: Option<Duration>) -> WorkerSearcher {
+        self.shared.stopped.store(false, Ordering::Relaxed);
+        self.shared.control.begin_search();
+        self.shared.tt.new_search();
+        self.max_depth = max_depth;
+
+        WorkerSearcher {
+            shared: Arc::clone(&self.shared),
+            nodes: 0,
+            nodes_reported: 0,
+            max_depth,
+            killer_moves: [[None; 2]; 64],
+            last_move_for_ordering: None,
+            start_time: time_limit.map(|_| self.clock_start()),
+            time_limit,
+            node_limit: node_limit.map(|n| n.max(1)),
+            stats: SearchStats::default(),
+            params: self.params,
+            ply: 0,
+            root_excluded: Vec::new(),
+            move_arena: Vec::with_capacity(MOVE_ARENA_CAPACITY),
+            root_only: None,
+            root_gap: 0,
+            info: self.info.clone(),
+            paranoid: self.paranoid,
+            rules: self.rules,
+            #[cfg(feature = "trace")]
+            trace: None,
+        }
+    }
+
+    /// Single-threaded iterative deepening on `worker`, within its node and
+    /// time limits. `stop_on_win` ends the search at the first iteration
+    /// that proves a win (mate searches).
+    fn run_single(
+        &mut self,
+        worker: &mut WorkerSearcher,
+        board: &Board,
+        color: Stone,
+        max_depth: i8,
+        stop_on_win: bool,
+    ) -> SearchResult {
+        let mut best_result = SearchResult {
+            best_move: None,
+            score: 0,
+            depth: 0,
+            nodes: 0,
+            stats: SearchStats::default(),
+            threads: 1,
+        };
+
+        let started = worker.now();
+        let mut work_board = board.clone();
+        work_board.enable_incremental_eval(self.rules);
+        let min_depth: i8 = if board.stone_count() <= 4 { 8 } else { 10 };
+        let mut prev_was_winning = false;
+        let mut prev_was_losing = false;
+
+        for depth in 1..=max_depth {
+            let result = worker.search_root(&mut work_board, color, depth, -INF, INF);
+            if worker.is_stopped() {
+                if best_result.best_move.is_none() {
+                    best_result = result;
+                }
+                break;
+            }
+            best_result = result;
+            best_result.depth = depth;
+            worker.shared.control.completed_depth(depth);
+            worker.report_iteration(board, color, &best_result, started);
+
+            let is_winning = best_result.score >= MATE_THRESHOLD;
+            let is_losing = best_result.score <= -MATE_THRESHOLD;
+            if is_winning && stop_on_win {
+                break;
+            }
+            if (is_winning && prev_was_winning || is_losing && prev_was_losing) && depth >= min_depth {
+                break;
+            }
+            prev_was_winning = is_winning;
+            prev_was_losing = is_losing;
+        }
+
+        self.shared.stopped.store(false, Ordering::Relaxed);
+        self.shared.tt.new_search();
+        worker.report_nodes();
+        best_result.nodes = worker.nodes;
+        best_result.stats = worker.stats.clone();
+        best_result
+    }
+
+    /// Candidate moves for `color` in the order a search root would try
+    /// them, with their ordering scores (no TT move, current history).
+    ///
+    /// Includes forbidden moves; the search filters those lazily.
+    #[must_use]
+    pub fn ordered_moves(&self, board: &Board, color: Stone) -> Vec<(Pos, i32)> {
+        let worker = WorkerSearcher::new(
+            Arc::clone(&self.shared),
+            self.max_depth,
+            Duration::ZERO,
+            Duration::MAX,
+            self.params,
+            self.rules,
+        );
+        let mut board = board.clone();
+        worker.generate_moves_ordered(&mut board, color, None, self.max_depth).0
+    }
+
+    /// The moves a search of `board` would consider at the root, in the
+    /// order it would try them, with their move-ordering scores.
+    ///
+    /// Unlike [`Searcher::ordered_moves`] the list is what the root really
+    /// searches: the TT move from earlier searches first, forbidden moves
+    /// removed, and cut to the root move limit. Cheap (no search), so it
+    /// suits visualizing what the engine is considering.
+    #[must_use]
+    pub fn root_move_scores(&self, board: &Board, color: Stone) -> Vec<(Pos, i32)> {
+        let mut worker = WorkerSearcher::new(
+            Arc::clone(&self.shared),
+            self.max_depth,
+            Duration::ZERO,
+            Duration::MAX,
+            self.params,
+            self.rules,
+        );
+        let hash = self.shared.zobrist.hash(board, color);
+        worker.root_moves(&mut board.clone(), color, hash, self.max_depth)
+    }
+
+    /// Handle for cancelling this searcher's searches from another thread
+    /// and watching their progress.
+    #[must_use]
+    pub fn control(&self) -> Arc<SearchControl> {
+        Arc::clone(&self.shared.control)
+    }
+
+    /// Check the board after every move the alpha-beta search makes and
+    /// undoes, and panic at the first inconsistency (see
+    /// [`Board::check_invariants`]). Much slower; for debugging make/unmake
+    /// code. Threat searches aren't audited.
+    pub fn set_paranoid(&mut self, enabled: bool) {
+        self.paranoid = enabled;
+    }
+
+    /// Whether searches audit their moves ([`Searcher::set_paranoid`]).
+    #[must_use]
+    pub fn is_paranoid(&self) -> bool {
+        self.paranoid
+    }
+
+    /// Number of threads used by timed (Lazy SMP) searches.
+    #[must_use]
+    pub fn threads(&self) -> usize {
+        self.num_threads
+    }
+
+    /// Change the thread count for subsequent timed searches (1 to
+    /// [`Searcher::MAX_THREADS`]; always 1 on `wasm32` and without `std`).
+    /// The TT and history are kept.
+    pub fn set_threads(&mut self, num_threads: usize) {
+        self.num_threads = Self::clamp_threads(num_threads);
+    }
+
+    fn clamp_threads(num_threads: usize) -> usize {
+        if cfg!(any(target_arch = "wasm32", not(feature = "std"))) { 1 } else { num_threads.clamp(1, Self::MAX_THREADS) }
+    }
+
+    /// Call `callback` after every iteration a search completes (`None`
+    /// to stop). Under Lazy SMP only the main thread's iterations are
+    /// reported; their node counts include the helpers'.
+    pub fn set_info_callback(&mut self, callback: Option<InfoCallback>) {
+        self.info = callback;
+    }
+
+    /// The callback set with [`Searcher::set_info_callback`]
+    #[must_use]
+    pub fn info_callback(&self) -> Option<InfoCallback> {
+        self.info.clone()
+    }
+
+    /// Replace the pruning margins used by subsequent searches.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error (and keeps the current margins) if `margins` fails
+    /// [`PruningMargins::validate`].
+    pub fn set_margins(&mut self, margins: PruningMargins) -> Result<(), String> {
+        margins.validate()?;
+        self.params.margins = margins;
+        Ok(())
+    }
+
+    /// Get the pruning margins in use.
+    #[must_use]
+    pub fn margins(&self) -> PruningMargins {
+        self.params.margins
+    }
+
+    /// Replace all search parameters used by subsequent searches.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error (and keeps the current parameters) if `params`
+    /// fails [`SearchParams::validate`].
+    pub fn set_params(&mut self, params: SearchParams) -> Result<(), String> {
+        params.validate()?;
+        self.params = params;
+        Ok(())
+    }
+
+    /// Get the search parameters in use.
+    #[must_use]
+    pub fn params(&self) -> SearchParams {
+        self.params
+    }
+
+    /// Search by `rules` from now on. Stored entries and cached evaluations
+    /// are dropped when the rules change, since they don't record the rules
+    /// they were found under.
+    pub fn set_rules(&mut self, rules: RuleSet) {
+        if rules != self.rules {
+            self.rules = rules;
+            self.clear_tt();
+        }
+    }
+
+    /// Rules the searches play by, [`RuleSet::NINUKI`] unless set
+    #[must_use]
+    pub fn rules(&self) -> RuleSet {
+        self.rules
+    }
+
+    /// Clear the history heuristic and countermoves.
     pub fn clear_history(&mut self) {
-        self.history = [[[0; BOARD_SIZE]; BOARD_SIZE]; 2];
+        self.shared.history.clear();
     }
 
     /// Get statistics about the transposition table.
@@ -2268,9 +3230,126 @@ impl Searcher {
         self.shared.tt.stats()
     }
 
-    /// Clear the transposition table.
+    /// Clear the transposition table and the evaluation cache.
     pub fn clear_tt(&self) {
         self.shared.tt.clear();
+        self.shared.eval_cache.clear();
+    }
+
+    /// Save the transposition table to `path`, so a later session (or
+    /// another program) can start from it with [`Searcher::load_tt`].
+    /// Returns the number of entries written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be created or written.
+    #[cfg(feature = "std")]
+    pub fn save_tt(&self, path: impl AsRef<Path>) -> io::Result<usize> {
+        self.shared.tt.save(BufWriter::new(File::create(path)?), self.rules, self.shared.zobrist.seed())
+    }
+
+    /// Add the entries saved in `path` to the transposition table (see
+    /// [`AtomicTT::load`]). Returns the number of entries read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read, isn't a table saved by
+    /// this engine version or was saved under another rule set or Zobrist
+    /// seed.
+    #[cfg(feature = "std")]
+    pub fn load_tt(&self, path: impl AsRef<Path>) -> io::Result<usize> {
+        self.shared.tt.load(BufReader::new(File::open(path)?), self.rules, self.shared.zobrist.seed())
+    }
+
+    /// Regenerate the Zobrist keys from `seed` ([`ZobristTable::with_seed`]).
+    ///
+    /// Stored entries and cached evaluations are dropped, since their keys
+    /// no longer match; the history is kept. Nothing changes when the keys
+    /// already come from `seed`.
+    pub fn set_zobrist_seed(&mut self, seed: u64) {
+        if self.shared.zobrist.seed() == seed {
+            return;
+        }
+        let zobrist = ZobristTable::with_seed(seed);
+        if let Some(shared) = Arc::get_mut(&mut self.shared) {
+            shared.zobrist = zobrist;
+            self.clear_tt();
+        } else {
+            // A worker still holds the old state; give this searcher its own.
+            self.shared = Arc::new(SharedState {
+                zobrist,
+                tt: AtomicTT::new(self.shared.tt.size_mb()),
+                history: self.shared.history.clone(),
+                eval_cache: EvalCache::new(EVAL_CACHE_MB),
+                stopped: AtomicBool::new(false),
+                control: Arc::clone(&self.shared.control),
+                clock: Arc::clone(&self.shared.clock),
+            });
+        }
+    }
+
+    /// Seed of the Zobrist keys
+    #[must_use]
+    pub fn zobrist_seed(&self) -> u64 {
+        self.shared.zobrist.seed()
+    }
+
+    /// Read time limits off `clock` instead of the [`SystemClock`]. With a
+    /// clock that has no time, time limits become node budgets (see
+    /// [`crate::time`]).
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        if let Some(shared) = Arc::get_mut(&mut self.shared) {
+            shared.clock = clock;
+        } else {
+            // A worker still holds the old state; give this searcher its own.
+            self.shared = Arc::new(SharedState {
+                zobrist: self.shared.zobrist.clone(),
+                tt: AtomicTT::new(self.shared.tt.size_mb()),
+                history: self.shared.history.clone(),
+                eval_cache: EvalCache::new(EVAL_CACHE_MB),
+                stopped: AtomicBool::new(false),
+                control: Arc::clone(&self.shared.control),
+                clock,
+            });
+        }
+    }
+
+    /// Time source of the time limits
+    #[must_use]
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        Arc::clone(&self.shared.clock)
+    }
+
+    /// Start of a search on the clock, or zero nodes without one
+    fn clock_start(&self) -> Duration {
+        self.shared.clock.now().unwrap_or(Duration::ZERO)
+    }
+
+    /// Transposition table size in megabytes ([`AtomicTT::size_mb`]).
+    #[must_use]
+    pub fn tt_size_mb(&self) -> usize {
+        self.shared.tt.size_mb()
+    }
+
+    /// Reallocate the transposition table with a new size in megabytes.
+    ///
+    /// All stored entries are dropped; the zobrist table and history are kept.
+    pub fn resize_tt(&mut self, tt_size_mb: usize) {
+        let tt = AtomicTT::new(tt_size_mb);
+        if let Some(shared) = Arc::get_mut(&mut self.shared) {
+            shared.tt = tt;
+        } else {
+            // A worker still holds the old state; give this searcher its own.
+            self.shared = Arc::new(SharedState {
+                zobrist: self.shared.zobrist.clone(),
+                tt,
+                history: self.shared.history.clone(),
+                eval_cache: EvalCache::new(EVAL_CACHE_MB),
+                stopped: AtomicBool::new(false),
+                control: Arc::clone(&self.shared.control),
+                clock: Arc::clone(&self.shared.clock),
+            });
+        }
     }
 }
 
@@ -2336,19 +3415,34 @@ mod tests {
         let shared = Arc::new(SharedState {
             zobrist: ZobristTable::new(),
             tt: AtomicTT::new(1),
+            history: HistoryTable::new(),
+            eval_cache: EvalCache::new(1),
             stopped: AtomicBool::new(false),
+            control: Arc::default(),
+            clock: Arc::new(SystemClock),
         });
         let worker = WorkerSearcher {
             shared,
             nodes: 0,
+            nodes_reported: 0,
+            rules: RuleSet::NINUKI,
             max_depth: 10,
             killer_moves: [[None; 2]; 64],
-            history: [[[0; BOARD_SIZE]; BOARD_SIZE]; 2],
-            countermove: [[[None; BOARD_SIZE]; BOARD_SIZE]; 2],
             last_move_for_ordering: None,
             start_time: None,
             time_limit: None,
+            node_limit: None,
             stats: SearchStats::default(),
+            params: SearchParams::default(),
+            ply: 0,
+            root_excluded: Vec::new(),
+            move_arena: Vec::with_capacity(MOVE_ARENA_CAPACITY),
+            root_only: None,
+            root_gap: 0,
+            info: None,
+            paranoid: false,
+            #[cfg(feature = "trace")]
+            trace: None,
         };
         let mut board = Board::new();
         board.place_stone(Pos::new(9, 9), Stone::Black);
@@ -2378,6 +3472,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_paranoid_search_matches_normal_search() {
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 5), Stone::Black);
+        board.place_stone(Pos::new(9, 7), Stone::White);
+        board.place_stone(Pos::new(9, 8), Stone::White);
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.place_stone(Pos::new(10, 8), Stone::White);
+
+        let mut normal = Searcher::with_threads(4, 1);
+        let mut paranoid = Searcher::with_threads(4, 1);
+        paranoid.set_paranoid(true);
+        assert!(paranoid.is_paranoid() && !normal.is_paranoid());
+        // Every make/unmake is audited (a failure panics), and nothing changes
+        let expected = normal.search(&board, Stone::Black, 4);
+        let audited = paranoid.search(&board, Stone::Black, 4);
+        assert_eq!((audited.best_move, audited.score, audited.nodes), (expected.best_move, expected.score, expected.nodes));
+    }
+
     #[test]
     fn test_tt_stats_after_search() {
         let mut searcher = Searcher::new(16);
@@ -2419,11 +3532,45 @@ mod tests {
 
         let result = searcher.search(&board, Stone::Black, 2);
         assert!(
-            result.score >= PatternScore::FIVE - 100,
+            result.score >= MATE_THRESHOLD,
             "Should detect winning position"
         );
     }
 
+    #[test]
+    fn test_search_prefers_shortest_win() {
+        // Four on row 9 (win now) and an open three on row 3 (win later)
+        let mut board = Board::new();
+        for col in 0..4 {
+            board.place_stone(Pos::new(9, col), Stone::Black);
+        }
+        for col in 7..10 {
+            board.place_stone(Pos::new(3, col), Stone::Black);
+        }
+        board.place_stone(Pos::new(15, 15), Stone::White);
+        board.place_stone(Pos::new(16, 3), Stone::White);
+
+        let mut searcher = Searcher::with_threads(16, 1);
+        let result = searcher.search(&board, Stone::Black, 6);
+        assert_eq!(result.best_move, Some(Pos::new(9, 4)));
+        assert_eq!(result.score, PatternScore::FIVE - 1);
+        assert_eq!(mate_distance(result.score), Some(1));
+    }
+
+    #[test]
+    fn test_mate_scores_survive_tt_round_trip() {
+        for ply in [0, 1, 7] {
+            for score in [PatternScore::FIVE - 9, -(PatternScore::FIVE - 9), 1234, -50_000] {
+                assert_eq!(score_from_tt(score_to_tt(score, ply), ply), score);
+            }
+        }
+        // A win 3 plies below a node at ply 4 is stored as "win in 3"
+        assert_eq!(score_to_tt(PatternScore::FIVE - 7, 4), PatternScore::FIVE - 3);
+        assert_eq!(mate_distance(PatternScore::FIVE - 3), Some(2));
+        assert_eq!(mate_distance(-PatternScore::FIVE), Some(-1));
+        assert_eq!(mate_distance(MATE_THRESHOLD - 1), None);
+    }
+
     #[test]
     fn test_search_losing_score() {
         let mut searcher = Searcher::new(16);
@@ -2438,24 +3585,138 @@ mod tests {
         assert_eq!(result.best_move, Some(Pos::new(9, 4)));
     }
 
+    #[test]
+    fn test_ordered_moves_put_winning_move_first() {
+        let mut board = Board::new();
+        for col in 5..9 {
+            board.place_stone(Pos::new(9, col), Stone::Black);
+        }
+        board.place_stone(Pos::new(9, 4), Stone::White);
+        board.place_stone(Pos::new(3, 3), Stone::White);
+
+        let searcher = Searcher::with_threads(1, 1);
+        let moves = searcher.ordered_moves(&board, Stone::Black);
+        assert_eq!(moves[0].0, Pos::new(9, 9));
+        assert!(moves.windows(2).all(|w| w[0].1 >= w[1].1));
+    }
+
+    #[test]
+    fn test_root_move_scores_are_the_searched_root_moves() {
+        // K10 would give Black two open threes
+        let mut board = Board::new();
+        for pos in [Pos::new(9, 7), Pos::new(9, 8), Pos::new(7, 9), Pos::new(8, 9)] {
+            board.place_stone(pos, Stone::Black);
+        }
+        board.place_stone(Pos::new(3, 3), Stone::White);
+        let forbidden = Pos::new(9, 9);
+        assert!(crate::rules::is_double_three(RuleSet::NINUKI, &board, forbidden, Stone::Black));
+
+        let mut searcher = Searcher::with_threads(1, 1);
+        assert!(searcher.ordered_moves(&board, Stone::Black).iter().any(|&(mov, _)| mov == forbidden));
+        let moves = searcher.root_move_scores(&board, Stone::Black);
+        assert!(!moves.is_empty() && moves.len() <= SearchParams::default().max_root_moves);
+        assert!(moves.iter().all(|&(mov, _)| mov != forbidden));
+
+        // The TT move of a finished search comes first
+        let result = searcher.search_nodes(&board, Stone::Black, 4, 20_000);
+        assert_eq!(searcher.root_move_scores(&board, Stone::Black)[0].0, result.best_move.unwrap());
+    }
+
+    #[test]
+    fn test_multipv_lines_are_distinct_and_best_first() {
+        let mut board = Board::new();
+        for col in 5..9 {
+            board.place_stone(Pos::new(9, col), Stone::Black);
+        }
+        board.place_stone(Pos::new(9, 4), Stone::White);
+        board.place_stone(Pos::new(3, 3), Stone::White);
+
+        let mut searcher = Searcher::with_threads(1, 1);
+        let lines = searcher.search_multipv(&board, Stone::Black, 4, 20_000, 3);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].mov, Pos::new(9, 9));
+        assert_eq!(mate_distance(lines[0].score), Some(1));
+        assert!(lines[1..].iter().all(|line| line.mov != Pos::new(9, 9)));
+        assert_ne!(lines[1].mov, lines[2].mov);
+    }
+
+    #[test]
+    fn test_thread_count_is_clamped() {
+        let mut searcher = Searcher::with_threads(1, 0);
+        assert_eq!(searcher.threads(), 1);
+        searcher.set_threads(100_000);
+        let expected = if cfg!(any(target_arch = "wasm32", not(feature = "std"))) { 1 } else { Searcher::MAX_THREADS };
+        assert_eq!(searcher.threads(), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_search_control_reports_progress_and_cancels() {
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.place_stone(Pos::new(9, 10), Stone::White);
+
+        let mut searcher = Searcher::with_threads(4, 2);
+        let control = searcher.control();
+        let result = searcher.search_nodes(&board, Stone::Black, 6, 20_000);
+        assert_eq!(control.depth(), result.depth);
+        assert_eq!(control.nodes(), result.nodes);
+
+        // Cancel from another thread during a long timed search
+        let canceller = {
+            let control = Arc::clone(&control);
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(100));
+                control.cancel();
+            })
+        };
+        let start = crate::time::Instant::now();
+        let result = searcher.search_timed(&board, Stone::Black, 30, 60_000);
+        canceller.join().unwrap();
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert!(result.best_move.is_some());
+
+        // Stays cancelled until reset
+        let cancelled = searcher.search(&board, Stone::Black, 20);
+        assert!(cancelled.nodes < 100_000);
+        control.reset();
+        assert!(!control.is_cancelled());
+        assert_eq!(searcher.search(&board, Stone::Black, 3).depth, 3);
+    }
+
     #[test]
     fn test_generate_moves_excludes_forbidden() {
         let shared = Arc::new(SharedState {
             zobrist: ZobristTable::new(),
             tt: AtomicTT::new(1),
+            history: HistoryTable::new(),
+            eval_cache: EvalCache::new(1),
             stopped: AtomicBool::new(false),
+            control: Arc::default(),
+            clock: Arc::new(SystemClock),
         });
         let worker = WorkerSearcher {
             shared,
             nodes: 0,
+            nodes_reported: 0,
+            rules: RuleSet::NINUKI,
             max_depth: 10,
             killer_moves: [[None; 2]; 64],
-            history: [[[0; BOARD_SIZE]; BOARD_SIZE]; 2],
-            countermove: [[[None; BOARD_SIZE]; BOARD_SIZE]; 2],
             last_move_for_ordering: None,
             start_time: None,
             time_limit: None,
+            node_limit: None,
             stats: SearchStats::default(),
+            params: SearchParams::default(),
+            ply: 0,
+            root_excluded: Vec::new(),
+            move_arena: Vec::with_capacity(MOVE_ARENA_CAPACITY),
+            root_only: None,
+            root_gap: 0,
+            info: None,
+            paranoid: false,
+            #[cfg(feature = "trace")]
+            trace: None,
         };
         let mut board = Board::new();
 
@@ -2517,6 +3778,260 @@ mod tests {
         assert!(result.nodes > 0, "Should search some nodes");
     }
 
+    #[test]
+    fn test_search_nodes_is_reproducible() {
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.place_stone(Pos::new(9, 10), Stone::White);
+        board.place_stone(Pos::new(10, 9), Stone::Black);
+        board.place_stone(Pos::new(8, 10), Stone::White);
+
+        let run = || Searcher::with_threads(16, 1).search_nodes(&board, Stone::Black, 20, 20_000);
+        let (a, b) = (run(), run());
+        assert!(a.best_move.is_some());
+        assert_eq!((a.best_move, a.score, a.depth, a.nodes), (b.best_move, b.score, b.depth, b.nodes));
+        // Budget is checked every 1024 nodes (4096 in quiescence)
+        assert!(a.nodes < 20_000 + 4096, "Overran node budget: {}", a.nodes);
+    }
+
+    #[test]
+    fn test_clock_sources() {
+        use crate::time::{ManualClock, NoClock};
+
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.place_stone(Pos::new(9, 10), Stone::White);
+
+        // No clock: the 150ms hard limit is 15,000 nodes, the same every run
+        let run = || {
+            let mut searcher = Searcher::with_threads(16, 1);
+            searcher.set_clock(Arc::new(NoClock));
+            searcher.search_timed(&board, Stone::Black, 30, 100)
+        };
+        let (a, b) = (run(), run());
+        assert!(a.best_move.is_some());
+        assert_eq!((a.best_move, a.score, a.depth, a.nodes), (b.best_move, b.score, b.depth, b.nodes));
+        assert!(a.nodes < 150 * NODES_PER_MS + 4096, "Overran node budget: {}", a.nodes);
+
+        // A clock that stands still never runs out
+        let clock = Arc::new(ManualClock::new());
+        let mut searcher = Searcher::with_threads(16, 1);
+        searcher.set_clock(clock.clone());
+        assert_eq!(searcher.clock().now(), Some(Duration::ZERO));
+        assert_eq!(searcher.search_timed(&board, Stone::Black, 5, 1).depth, 5);
+    }
+
+    #[test]
+    fn test_move_picker_stages() {
+        let mut board = Board::new();
+        for (r, c, stone) in [(9, 9, Stone::Black), (9, 10, Stone::White), (10, 10, Stone::Black), (8, 8, Stone::White)] {
+            board.place_stone(Pos::new(r, c), stone);
+        }
+        let last = Pos::new(8, 8);
+        let mut searcher = Searcher::with_threads(1, 1);
+        let mut worker = searcher.single_worker(6, None, None);
+        worker.last_move_for_ordering = Some(last);
+        let (ordered, _) = worker.generate_moves_ordered(&mut board, Stone::Black, None, 4);
+        let tt_move = ordered.last().unwrap().0;
+
+        // TT move first, before anything is scored
+        let mut picker = MovePicker::new(Some(tt_move), last);
+        assert_eq!(picker.next(&mut worker, &mut board, Stone::Black, 4), Some((tt_move, TT_MOVE_SCORE)));
+        assert!(picker.is_tactical());
+        assert!(worker.move_arena.is_empty());
+
+        // Then every other candidate exactly once, best first
+        let mut rest = Vec::new();
+        while let Some(picked) = picker.next(&mut worker, &mut board, Stone::Black, 4) {
+            rest.push(picked);
+        }
+        assert_eq!(rest.len(), ordered.len() - 1);
+        assert!(rest.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert!(!rest.iter().any(|&(mov, _)| mov == tt_move));
+        let mut expected: Vec<i32> = ordered.iter().filter(|&&(mov, _)| mov != tt_move).map(|&(_, s)| s).collect();
+        expected.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(rest.iter().map(|&(_, s)| s).collect::<Vec<_>>(), expected);
+        assert_eq!(worker.move_arena.len(), rest.len());
+
+        // A TT move that isn't a candidate is skipped
+        let mut picker = MovePicker::new(Some(Pos::new(0, 0)), last);
+        assert_eq!(picker.next(&mut worker, &mut board, Stone::Black, 4), Some(ordered[0]));
+    }
+
+    #[test]
+    fn test_resize_tt() {
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.place_stone(Pos::new(9, 10), Stone::White);
+        let mut searcher = Searcher::with_threads(1, 1);
+        let _ = searcher.search(&board, Stone::Black, 4);
+        let scores = |searcher: &Searcher| -> Vec<i32> {
+            (0..crate::board::TOTAL_CELLS).map(|i| searcher.shared.history.score(Stone::Black, Pos::from_index(i))).collect()
+        };
+        let history = scores(&searcher);
+        assert!(history.iter().any(|&score| score > 0));
+        let small = searcher.tt_stats().size;
+
+        searcher.resize_tt(4);
+        assert_eq!(searcher.tt_stats().size, small * 4);
+        assert_eq!(searcher.tt_stats().used, 0);
+        assert_eq!(scores(&searcher), history);
+
+        let result = searcher.search(&board, Stone::Black, 4);
+        assert!(result.best_move.is_some());
+        assert!(searcher.tt_stats().used > 0);
+    }
+
+    #[test]
+    fn test_search_with_limits() {
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.place_stone(Pos::new(9, 10), Stone::White);
+        board.place_stone(Pos::new(10, 9), Stone::Black);
+        board.place_stone(Pos::new(8, 10), Stone::White);
+        let mut searcher = Searcher::with_threads(16, 2);
+
+        let result = searcher.search_with_limits(&board, Stone::Black, &SearchLimits::depth(3));
+        assert_eq!(result.depth, 3);
+        assert_eq!(result.threads, 1, "Depth-limited searches run serially");
+
+        #[cfg(feature = "std")]
+        {
+            let start = crate::time::Instant::now();
+            let limits = SearchLimits::time(Duration::from_millis(100));
+            let result = searcher.search_with_limits(&board, Stone::Black, &limits);
+            assert!(result.best_move.is_some());
+            assert!(start.elapsed() < Duration::from_millis(400), "Took {:?}", start.elapsed());
+            assert_eq!(result.threads, 2);
+        }
+    }
+
+    #[test]
+    fn test_search_restricted_root_moves() {
+        // Black wins at L10; restricted away from it
+        let mut board = Board::new();
+        for col in 6..10 {
+            board.place_stone(Pos::new(9, col), Stone::Black);
+        }
+        board.place_stone(Pos::new(9, 5), Stone::White);
+        let mut searcher = Searcher::with_threads(4, 2);
+
+        let corner = Pos::new(0, 0);
+        let limits = SearchLimits::depth(3).restrict_to([corner, Pos::new(12, 12)]);
+        let result = searcher.search_with_limits(&board, Stone::Black, &limits);
+        assert!(matches!(result.best_move, Some(p) if p == corner || p == Pos::new(12, 12)));
+        assert!(result.score < MATE_THRESHOLD);
+
+        // A single move far from the stones is still searched, timed too
+        let limits = SearchLimits::time(Duration::from_millis(100)).restrict_to([corner]);
+        assert_eq!(searcher.search_with_limits(&board, Stone::Black, &limits).best_move, Some(corner));
+        // The restricted searches left no root entry behind
+        let unrestricted = searcher.search_with_limits(&board, Stone::Black, &SearchLimits::depth(1));
+        assert!(unrestricted.score >= MATE_THRESHOLD);
+    }
+
+    #[test]
+    fn test_forced_root_move_shortens_time() {
+        // Black completes five at K10; nothing else comes close
+        let mut board = Board::new();
+        for col in 6..10 {
+            board.place_stone(Pos::new(9, col), Stone::Black);
+        }
+        board.place_stone(Pos::new(9, 5), Stone::White);
+        let mut searcher = Searcher::with_threads(4, 1);
+        let mut worker = searcher.single_worker(4, None, None);
+        let result = worker.search_root(&mut board.clone(), Stone::Black, 2, -INF, INF);
+        assert_eq!(result.best_move, Some(Pos::new(9, 10)));
+        assert!(worker.root_gap >= FORCED_MOVE_GAP, "gap {}", worker.root_gap);
+
+        let mut corner = Bitboard::new();
+        corner.set(Pos::new(0, 0));
+        worker.root_only = Some(corner);
+        let _ = worker.search_root(&mut board, Stone::Black, 2, -INF, INF);
+        assert_eq!(worker.root_gap, INF);
+
+        assert_eq!(WorkerSearcher::time_scale_percent(2, false, 0), 100);
+        assert!(WorkerSearcher::time_scale_percent(2, false, FORCED_MOVE_GAP) < 100);
+        assert!(WorkerSearcher::time_scale_percent(1, false, FORCED_MOVE_GAP) > 100);
+    }
+
+    #[test]
+    fn test_stable_best_move_shortens_time() {
+        let easy = WorkerSearcher::time_scale_percent(EASY_MOVE_ITERATIONS, false, 0);
+        assert!(easy < 100);
+        assert!(WorkerSearcher::time_scale_percent(EASY_MOVE_ITERATIONS, false, FORCED_MOVE_GAP) < easy);
+        // A late flip or a falling score gets extra time however long the move held
+        assert_eq!(WorkerSearcher::time_scale_percent(0, false, 0), 160);
+        assert_eq!(WorkerSearcher::time_scale_percent(EASY_MOVE_ITERATIONS + 5, true, FORCED_MOVE_GAP), 160);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_info_callback_reports_each_iteration() {
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.place_stone(Pos::new(9, 10), Stone::White);
+        board.place_stone(Pos::new(10, 9), Stone::Black);
+        let infos = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&infos);
+        let mut searcher = Searcher::with_threads(16, 1);
+        searcher.set_info_callback(Some(Arc::new(move |info| sink.lock().unwrap().push(info))));
+
+        let result = searcher.search(&board, Stone::White, 4);
+        let infos = infos.lock().unwrap();
+        assert_eq!(infos.iter().map(|info| info.depth).collect::<Vec<_>>(), [1, 2, 3, 4]);
+        assert!(infos.windows(2).all(|pair| pair[0].nodes <= pair[1].nodes));
+        let last = infos.last().unwrap();
+        assert_eq!(last.score, result.score);
+        assert_eq!(last.pv.first().copied(), result.best_move);
+        assert!(last.pv.len() <= 4);
+        for (i, mov) in last.pv.iter().enumerate() {
+            assert!(board.is_empty(*mov) && !last.pv[..i].contains(mov), "PV {:?}", last.pv);
+        }
+
+        searcher.set_info_callback(None);
+        let _ = searcher.search(&board, Stone::White, 2);
+        assert_eq!(infos.len(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_save_and_load_tt() {
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.place_stone(Pos::new(9, 10), Stone::White);
+        let mut searcher = Searcher::with_threads(1, 1);
+        let result = searcher.search(&board, Stone::Black, 4);
+
+        let path = std::env::temp_dir().join(format!("gomoku-tt-test-{}.bin", std::process::id()));
+        let saved = searcher.save_tt(&path).unwrap();
+        assert!(saved > 0);
+        let warm = Searcher::with_threads(1, 1);
+        let loaded = warm.load_tt(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), saved);
+        assert_eq!(warm.tt_stats().used, saved);
+        assert_eq!(warm.shared.tt.get_best_move(warm.shared.zobrist.hash(&board, Stone::Black)), result.best_move);
+        assert!(warm.load_tt(&path).is_err());
+    }
+
+    #[test]
+    fn test_search_mate_in() {
+        // Open three: win in 3 (open four, then five)
+        let mut board = Board::new();
+        for col in 7..10 {
+            board.place_stone(Pos::new(9, col), Stone::Black);
+        }
+        board.place_stone(Pos::new(3, 3), Stone::White);
+        board.place_stone(Pos::new(15, 15), Stone::White);
+
+        let mut searcher = Searcher::with_threads(16, 1);
+        let result = searcher.search_with_limits(&board, Stone::Black, &SearchLimits::mate_in(3));
+        assert!(result.score >= MATE_THRESHOLD, "Should prove the win, got {}", result.score);
+        assert!(result.depth <= 5);
+        assert!(matches!(result.best_move, Some(p) if p == Pos::new(9, 6) || p == Pos::new(9, 10)));
+    }
+
     /// Test that quiescence search detects forced wins beyond the regular search depth.
     /// Setup: Black has three in a row with both ends open → four → five is forced.
     /// Even at depth 1, QS should see the winning sequence.
@@ -2557,10 +4072,42 @@ mod tests {
         let result = searcher.search(&board, Stone::Black, 1);
         assert_eq!(result.best_move, Some(Pos::new(9, 11)),
             "Should find the five-completion move");
-        assert!(result.score >= PatternScore::FIVE - 100,
+        assert!(result.score >= MATE_THRESHOLD,
             "Should be a winning score, got {}", result.score);
     }
 
+    /// A double capture-win threat made by the last move is a loss that
+    /// standing pat would hide.
+    #[test]
+    fn test_quiescence_defends_capture_win_threats() {
+        let mut board = Board::new();
+        // White's last move at K10 flanks two Black pairs; White needs one more
+        let last = Pos::new(9, 9);
+        for pos in [last, Pos::new(0, 0)] {
+            board.place_stone(pos, Stone::White);
+        }
+        for pos in [Pos::new(9, 10), Pos::new(9, 11), Pos::new(10, 9), Pos::new(11, 9), Pos::new(5, 5)] {
+            board.place_stone(pos, Stone::Black);
+        }
+        board.set_captures(RuleSet::NINUKI, Stone::White, RuleSet::NINUKI.capture_win_pairs() - 1).unwrap();
+        board.enable_incremental_eval(RuleSet::NINUKI);
+        assert_eq!(WorkerSearcher::horizon_threats(RuleSet::NINUKI, &mut board, last, Stone::White).count(), 2);
+
+        let qs_score = |qs_defense_depth: i8, board: &mut Board| {
+            let params = SearchParams { qs_defense_depth, ..SearchParams::default() };
+            let shared = Searcher::with_threads(1, 1).shared;
+            let hash = shared.zobrist.hash(board, Stone::Black);
+            let mut worker = WorkerSearcher::new(shared, 4, Duration::ZERO, Duration::MAX, params, RuleSet::NINUKI);
+            worker.quiescence(board, Stone::Black, -INF, INF, last, 0, hash)
+        };
+        assert!(qs_score(0, &mut board) > -MATE_THRESHOLD, "without defense QS stands pat");
+        assert!(qs_score(4, &mut board) <= -MATE_THRESHOLD, "one block can't stop two capture wins");
+
+        // With a single threat the block holds
+        board.remove_stone(Pos::new(11, 9));
+        assert!(qs_score(4, &mut board) > -MATE_THRESHOLD);
+    }
+
     /// Test that the search correctly detects an existing five on the board
     /// that the opponent failed to break. In the game rules, if a breakable
     /// five persists because the defender played a non-breaking move, the
@@ -2601,7 +4148,7 @@ mod tests {
         board.place_stone(Pos::new(1, 1), Stone::White);
 
         // Verify Black has five
-        let five = find_five_positions(&board, Stone::Black);
+        let five = find_five_positions(RuleSet::NINUKI, &board, Stone::Black);
         assert!(five.is_some(), "Black should have five-in-a-row");
 
         // White to move — Black already has five on the board.
@@ -2610,7 +4157,7 @@ mod tests {
         let result = searcher.search(&board, Stone::White, 4);
 
         // White should see this as a losing position
-        assert!(result.score <= -(PatternScore::FIVE - 100),
+        assert!(result.score <= -MATE_THRESHOLD,
             "White should detect Black's existing five as a loss, got score {}",
             result.score);
     }
@@ -2804,4 +4351,59 @@ mod tests {
         }
         assert!(total_checks > 5000, "Should have checked many positions, got {}", total_checks);
     }
+
+    #[test]
+    fn test_set_margins_rejects_invalid() {
+        let mut searcher = Searcher::new(1);
+        let bad = PruningMargins {
+            reverse_futility_per_depth: PatternScore::FIVE,
+            ..PruningMargins::default()
+        };
+        assert!(searcher.set_margins(bad).is_err());
+        assert_eq!(searcher.margins(), PruningMargins::default(), "Invalid margins must not be applied");
+
+        let mut tighter = PruningMargins::default();
+        tighter.futility[0] = PatternScore::OPEN_THREE;
+        assert!(searcher.set_margins(tighter).is_ok());
+        assert_eq!(searcher.margins(), tighter);
+    }
+
+    #[test]
+    fn test_eval_cache_serves_repeated_evaluations() {
+        let mut board = Board::new();
+        for (r, c, stone) in [(9, 9, Stone::Black), (9, 10, Stone::White), (10, 10, Stone::Black), (8, 8, Stone::White)] {
+            board.place_stone(Pos::new(r, c), stone);
+        }
+        let mut searcher = Searcher::with_threads(4, 1);
+        let first = searcher.search_nodes(&board, Stone::Black, 20, 5_000);
+        assert!(first.stats.eval_hits > 0 && first.stats.eval_hits < first.stats.eval_probes);
+
+        // A warm cache doesn't change what the search finds
+        searcher.shared.tt.clear();
+        searcher.clear_history();
+        let again = searcher.search_nodes(&board, Stone::Black, 20, 5_000);
+        assert_eq!((again.best_move, again.score, again.nodes), (first.best_move, first.score, first.nodes));
+        assert!(again.stats.eval_hits > first.stats.eval_hits);
+    }
+
+    #[test]
+    fn test_search_params_apply_to_searches() {
+        let bad = SearchParams { null_move_reduction: 0, ..SearchParams::default() };
+        assert!(Searcher::with_params(1, bad).is_err());
+
+        let narrow = SearchParams { max_root_moves: 4, ..SearchParams::default() };
+        let mut searcher = Searcher::with_params(1, narrow).unwrap();
+        assert!(searcher.set_params(bad).is_err());
+        assert_eq!(searcher.params(), narrow);
+
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        assert_eq!(searcher.root_move_scores(&board, Stone::White).len(), 4);
+        // Margins set separately land in the parameters
+        let mut tighter = PruningMargins::default();
+        tighter.futility[0] = PatternScore::OPEN_THREE;
+        searcher.set_margins(tighter).unwrap();
+        assert_eq!(searcher.params().margins, tighter);
+        assert!(searcher.search(&board, Stone::White, 3).best_move.is_some());
+    }
 }
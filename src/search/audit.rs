@@ -0,0 +1,112 @@
+//! Make/unmake auditing for paranoid searches
+//!
+//! With [`Searcher::set_paranoid`](super::Searcher::set_paranoid) the
+//! alpha-beta workers take a [`MoveAudit`] before each move they make and
+//! check the board after the move and again after undoing it, panicking at
+//! the first inconsistency instead of letting a bad unmake corrupt the rest
+//! of the search. Checked after the move: [`Board::check_invariants`], that
+//! exactly the mover's stone was added and the captured stones removed,
+//! the capture counters, and the incrementally updated hash. After the
+//! undo: the board is back to the snapshot.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::board::{Bitboard, Board, Pos, Stone};
+use crate::rules::{CaptureInfo, RuleSet};
+
+use super::zobrist::ZobristTable;
+
+/// Stones and capture counters before a move, and the rules it is made by
+pub(super) struct MoveAudit {
+    black: Bitboard,
+    white: Bitboard,
+    black_captures: u8,
+    white_captures: u8,
+    rules: RuleSet,
+}
+
+impl MoveAudit {
+    pub(super) fn before(board: &Board, rules: RuleSet) -> Self {
+        let (black, white) = board.bitboards();
+        Self {
+            black,
+            white,
+            black_captures: board.captures(Stone::Black),
+            white_captures: board.captures(Stone::White),
+            rules,
+        }
+    }
+
+    /// Check `board` after `color` played `mov`, capturing `cap`, and
+    /// `hash` against a full hash with the opponent to move.
+    pub(super) fn after_make(
+        &self,
+        board: &Board,
+        mov: Pos,
+        color: Stone,
+        cap: &CaptureInfo,
+        zobrist: &ZobristTable,
+        hash: u64,
+    ) {
+        let fail = |what: &str| panic!("paranoid search: after {:?} at {:?}: {}", color, mov, what);
+        if let Err(e) = board.check_invariants(self.rules) {
+            fail(&e);
+        }
+
+        let (mut own, mut opponent) = self.sides(color);
+        own.set(mov);
+        for &pos in &cap.positions[..cap.count as usize] {
+            if !opponent.get(pos) {
+                fail(&format!("captured {:?}, which held no opponent stone", pos));
+            }
+            opponent.clear(pos);
+        }
+        let (black, white) = if color == Stone::Black { (own, opponent) } else { (opponent, own) };
+        if board.bitboards() != (black, white) {
+            fail("stones differ from the move and its captures");
+        }
+
+        if u32::from(cap.count) != u32::from(cap.pairs) * u32::from(self.rules.pair_size()) {
+            fail(&format!("{} stones captured in {} pairs", cap.count, cap.pairs));
+        }
+        let expected = self.captures(color).saturating_add(cap.pairs);
+        if board.captures(color) != expected || board.captures(color.opponent()) != self.captures(color.opponent()) {
+            fail(&format!("capture counters {}/{} (Black/White)", board.captures(Stone::Black), board.captures(Stone::White)));
+        }
+
+        if zobrist.hash(board, color.opponent()) != hash {
+            fail("incremental hash differs from a full hash");
+        }
+    }
+
+    /// Check that undoing `color`'s `mov` restored the board.
+    pub(super) fn after_unmake(&self, board: &Board, mov: Pos, color: Stone) {
+        let fail = |what: &str| panic!("paranoid search: undoing {:?} at {:?}: {}", color, mov, what);
+        if let Err(e) = board.check_invariants(self.rules) {
+            fail(&e);
+        }
+        if board.bitboards() != (self.black, self.white) {
+            fail("stones not restored");
+        }
+        if (board.captures(Stone::Black), board.captures(Stone::White)) != (self.black_captures, self.white_captures) {
+            fail(&format!("capture counters {}/{} (Black/White)", board.captures(Stone::Black), board.captures(Stone::White)));
+        }
+    }
+
+    /// (mover's, opponent's) stones before the move
+    fn sides(&self, color: Stone) -> (Bitboard, Bitboard) {
+        if color == Stone::Black {
+            (self.black, self.white)
+        } else {
+            (self.white, self.black)
+        }
+    }
+
+    fn captures(&self, color: Stone) -> u8 {
+        if color == Stone::Black {
+            self.black_captures
+        } else {
+            self.white_captures
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Break-and-replay cycles
+//!
+//! A five the opponent can break by capture isn't a win yet: the opponent
+//! captures from it, and the five's owner may replay a captured stone to
+//! make it again, which the opponent may break again. [`resolve_five`]
+//! follows these exchanges to their end, with the breaker trying every
+//! legal break and the owner every replay that recreates a five:
+//! - **Win**: the owner gets a five that can't be broken (or a breaker
+//!   with no legal break), or a replay reaches the capture win
+//! - **Loss**: a break reaches the breaker's capture win
+//! - **Escaped**: a break the owner can't answer with a new five; play
+//!   goes on normally
+//! - **Draw**: a position (stones and capture counts) repeats
+//!
+//! The exchanges always end: every break captures at least one pair, so
+//! the breaker reaches the capture win within
+//! [`capture_win_pairs`](crate::rules::RuleSet::capture_win_pairs) breaks,
+//! and capture counts that only grow keep positions from repeating. The
+//! repetition check is kept so a loop ends even if that stops holding.
+//!
+//! ```
+//! use gomoku::rules::{find_five_positions, RuleSet};
+//! use gomoku::search::{resolve_five, CycleOutcome};
+//! use gomoku::{Board, Pos, Stone};
+//!
+//! let mut board = Board::new();
+//! for col in 5..10 {
+//!     board.place_stone(Pos::new(9, col), Stone::Black);
+//! }
+//! let five = find_five_positions(RuleSet::NINUKI, &board, Stone::Black).unwrap();
+//! assert_eq!(resolve_five(RuleSet::NINUKI, &board, &five, Stone::Black), CycleOutcome::Win);
+//! ```
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::board::{Bitboard, Board, Pos, Stone};
+use crate::rules::{
+    can_break_five_by_capture, execute_captures_fast, find_five_break_moves, find_five_line_at_pos, has_five_at_pos,
+    is_valid_move, undo_captures, RuleSet,
+};
+
+/// End of a break-and-replay exchange for the five's owner, worst first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CycleOutcome {
+    /// A break reaches the breaker's capture win
+    Loss,
+    /// A position repeats
+    Draw,
+    /// A break the owner can't answer with a new five; the game goes on
+    Escaped,
+    /// The owner ends with a five that can't be broken or a capture win
+    Win,
+}
+
+/// Stones and capture counts, compared for repetitions
+type PositionKey = (Bitboard, Bitboard, u8, u8);
+
+/// How the exchange over `owner`'s five `five` on `board` ends, the other
+/// side to move and both sides playing their best.
+#[must_use]
+pub fn resolve_five(rules: RuleSet, board: &Board, five: &[Pos], owner: Stone) -> CycleOutcome {
+    Resolver::new(rules, board).five(five, owner)
+}
+
+/// How the exchange over `owner`'s five `five` ends after the other side
+/// breaks it at `brk` (a move from [`find_five_break_moves`]).
+#[must_use]
+pub fn resolve_break(rules: RuleSet, board: &Board, five: &[Pos], owner: Stone, brk: Pos) -> CycleOutcome {
+    let mut resolver = Resolver::new(rules, board);
+    resolver.path.push(resolver.key());
+    resolver.after_break(five, owner, brk)
+}
+
+struct Resolver {
+    rules: RuleSet,
+    board: Board,
+    /// Positions with a five on the board, from the first one down
+    path: Vec<PositionKey>,
+}
+
+impl Resolver {
+    fn new(rules: RuleSet, board: &Board) -> Self {
+        let mut board = board.clone();
+        board.disable_incremental_eval();
+        Self { rules, board, path: Vec::new() }
+    }
+
+    fn key(&self) -> PositionKey {
+        let board = &self.board;
+        let (black, white) = board.bitboards();
+        (black, white, board.captures(Stone::Black), board.captures(Stone::White))
+    }
+
+    /// Breaker to move against `owner`'s five
+    fn five(&mut self, five: &[Pos], owner: Stone) -> CycleOutcome {
+        let key = self.key();
+        if self.path.contains(&key) {
+            return CycleOutcome::Draw;
+        }
+        if !can_break_five_by_capture(self.rules, &self.board, five, owner) {
+            return CycleOutcome::Win;
+        }
+
+        self.path.push(key);
+        let breaker = owner.opponent();
+        let mut outcome = CycleOutcome::Win;
+        for brk in find_five_break_moves(self.rules, &self.board, five, owner) {
+            if is_valid_move(self.rules, &self.board, brk, breaker) {
+                outcome = outcome.min(self.after_break(five, owner, brk));
+                if outcome == CycleOutcome::Loss {
+                    break;
+                }
+            }
+        }
+        self.path.pop();
+        outcome
+    }
+
+    /// The breaker plays `brk`; the owner replays a captured five stone or
+    /// lets the five go
+    fn after_break(&mut self, five: &[Pos], owner: Stone, brk: Pos) -> CycleOutcome {
+        let breaker = owner.opponent();
+        self.board.place_stone(brk, breaker);
+        let cap = execute_captures_fast(self.rules, &mut self.board, brk, breaker);
+
+        let mut outcome = CycleOutcome::Escaped;
+        if self.board.captures_to_win(self.rules, breaker) == 0 {
+            outcome = CycleOutcome::Loss;
+        } else {
+            for &replay in cap.positions[..cap.count as usize].iter().filter(|pos| five.contains(pos)) {
+                if !is_valid_move(self.rules, &self.board, replay, owner) {
+                    continue;
+                }
+                self.board.place_stone(replay, owner);
+                let replay_cap = execute_captures_fast(self.rules, &mut self.board, replay, owner);
+                let replayed = if self.board.captures_to_win(self.rules, owner) == 0 {
+                    CycleOutcome::Win
+                } else if has_five_at_pos(self.rules, &self.board, replay, owner) {
+                    match find_five_line_at_pos(self.rules, &self.board, replay, owner) {
+                        Some(line) => self.five(&line, owner),
+                        None => CycleOutcome::Escaped,
+                    }
+                } else {
+                    CycleOutcome::Escaped
+                };
+                undo_captures(&mut self.board, owner, &replay_cap);
+                self.board.remove_stone(replay);
+                outcome = outcome.max(replayed);
+                if outcome == CycleOutcome::Win {
+                    break;
+                }
+            }
+        }
+
+        undo_captures(&mut self.board, breaker, &cap);
+        self.board.remove_stone(brk);
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{find_five_positions, RuleSet};
+
+    /// Black five on row 9 (cols 5-9) with White at (8, 7) over a Black
+    /// stone at (10, 7): White at (11, 7) captures (9, 7) and (10, 7).
+    fn breakable_five() -> (Board, Vec<Pos>) {
+        let mut board = Board::new();
+        for col in 5..10 {
+            board.place_stone(Pos::new(9, col), Stone::Black);
+        }
+        board.place_stone(Pos::new(8, 7), Stone::White);
+        board.place_stone(Pos::new(10, 7), Stone::Black);
+        let five = find_five_positions(RuleSet::NINUKI, &board, Stone::Black).unwrap();
+        (board, five)
+    }
+
+    #[test]
+    fn test_replay_after_break_wins() {
+        // After the break Black replays (9, 7); the pair below is gone, so
+        // the new five can't be broken
+        let (board, five) = breakable_five();
+        assert!(can_break_five_by_capture(RuleSet::NINUKI, &board, &five, Stone::Black));
+        assert_eq!(resolve_break(RuleSet::NINUKI, &board, &five, Stone::Black, Pos::new(11, 7)), CycleOutcome::Win);
+        assert_eq!(resolve_five(RuleSet::NINUKI, &board, &five, Stone::Black), CycleOutcome::Win);
+    }
+
+    #[test]
+    fn test_break_reaching_capture_win_loses() {
+        let (mut board, five) = breakable_five();
+        board.set_captures(RuleSet::NINUKI, Stone::White, RuleSet::NINUKI.capture_win_pairs() - 1).unwrap();
+        assert_eq!(resolve_five(RuleSet::NINUKI, &board, &five, Stone::Black), CycleOutcome::Loss);
+    }
+
+    #[test]
+    fn test_replay_into_capture_win_is_declined() {
+        // A second bracket over (9, 8): a replay leaves the five breakable
+        // once more, and that second break would be White's last pair, so
+        // Black lets the five go instead
+        let (mut board, five) = breakable_five();
+        board.place_stone(Pos::new(8, 8), Stone::White);
+        board.place_stone(Pos::new(10, 8), Stone::Black);
+        board.set_captures(RuleSet::NINUKI, Stone::White, RuleSet::NINUKI.capture_win_pairs() - 2).unwrap();
+        assert_eq!(resolve_break(RuleSet::NINUKI, &board, &five, Stone::Black, Pos::new(11, 7)), CycleOutcome::Escaped);
+        assert_eq!(resolve_five(RuleSet::NINUKI, &board, &five, Stone::Black), CycleOutcome::Escaped);
+
+        // One pair further from it, Black's last replay stands
+        board.sub_captures(Stone::White, 1);
+        assert_eq!(resolve_five(RuleSet::NINUKI, &board, &five, Stone::Black), CycleOutcome::Win);
+    }
+
+    #[test]
+    fn test_break_taking_two_five_stones_escapes() {
+        // (11, 7) also takes (10, 8) and (9, 9) toward White at (8, 10): no
+        // single replay restores the five
+        let (mut board, five) = breakable_five();
+        board.place_stone(Pos::new(10, 8), Stone::Black);
+        board.place_stone(Pos::new(8, 10), Stone::White);
+        assert_eq!(resolve_five(RuleSet::NINUKI, &board, &five, Stone::Black), CycleOutcome::Escaped);
+    }
+}
@@ -0,0 +1,141 @@
+//! Static evaluation cache
+//!
+//! Quiescence stand-pats and the pruning checks of shallow nodes evaluate
+//! the same positions again and again across siblings and iterations. An
+//! [`EvalCache`] remembers [`evaluate`](crate::eval::evaluate) results by
+//! the position's zobrist hash with the side-to-move key removed: the
+//! evaluation is symmetric (`evaluate(RuleSet::NINUKI, b, Black) == -evaluate(RuleSet::NINUKI, b, White)`),
+//! so one entry, stored from Black's side, serves both colors.
+//!
+//! Each entry is a single atomic word (hash check bits and score), so the
+//! Lazy SMP workers share one cache without locks and a racing write can
+//! never pair a score with the wrong position.
+//!
+//! ```
+//! use gomoku::search::{EvalCache, ZobristTable};
+//! use gomoku::{Board, Pos, Stone};
+//!
+//! let zobrist = ZobristTable::new();
+//! let mut board = Board::new();
+//! board.place_stone(Pos::new(9, 9), Stone::Black);
+//!
+//! let cache = EvalCache::new(1);
+//! let hash = zobrist.hash(&board, Stone::White);
+//! assert_eq!(cache.probe(&zobrist, hash, Stone::White), None);
+//! cache.store(&zobrist, hash, Stone::White, -120);
+//! assert_eq!(cache.probe(&zobrist, hash, Stone::White), Some(-120));
+//! // Same stones with Black to move: the same entry, from Black's side
+//! assert_eq!(cache.probe(&zobrist, zobrist.hash(&board, Stone::Black), Stone::Black), Some(120));
+//! ```
+
+use core::sync::atomic::{AtomicU64, Ordering};
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::board::Stone;
+
+use super::ZobristTable;
+
+/// Lock-free cache of static evaluations
+pub struct EvalCache {
+    /// `check << 32 | score`, 0 when empty
+    entries: Vec<AtomicU64>,
+}
+
+/// Position key of a search hash: the same for either side to move
+#[inline]
+fn position_key(zobrist: &ZobristTable, hash: u64, side_to_move: Stone) -> u64 {
+    if side_to_move == Stone::Black { zobrist.toggle_side(hash) } else { hash }
+}
+
+/// Hash bits kept to verify an entry; never 0, so an empty slot never matches
+#[inline]
+fn check_bits(key: u64) -> u64 {
+    (key >> 32) | 1
+}
+
+impl EvalCache {
+    /// Create a cache of about `size_mb` megabytes (at least 1024 entries).
+    #[must_use]
+    pub fn new(size_mb: usize) -> Self {
+        let len = (size_mb * 1024 * 1024 / core::mem::size_of::<AtomicU64>()).max(1024);
+        Self { entries: (0..len).map(|_| AtomicU64::new(0)).collect() }
+    }
+
+    #[inline]
+    fn slot(&self, key: u64) -> &AtomicU64 {
+        &self.entries[(key as usize) % self.entries.len()]
+    }
+
+    /// Cached evaluation for `side_to_move` of the position with search
+    /// hash `hash`, if stored.
+    #[inline]
+    #[must_use]
+    pub fn probe(&self, zobrist: &ZobristTable, hash: u64, side_to_move: Stone) -> Option<i32> {
+        let key = position_key(zobrist, hash, side_to_move);
+        let entry = self.slot(key).load(Ordering::Relaxed);
+        if entry >> 32 != check_bits(key) {
+            return None;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let black = entry as u32 as i32;
+        Some(if side_to_move == Stone::Black { black } else { -black })
+    }
+
+    /// Remember `score`, the evaluation for `side_to_move`, replacing
+    /// whatever shared the slot.
+    #[inline]
+    pub fn store(&self, zobrist: &ZobristTable, hash: u64, side_to_move: Stone, score: i32) {
+        let key = position_key(zobrist, hash, side_to_move);
+        let black = if side_to_move == Stone::Black { score } else { -score };
+        #[allow(clippy::cast_sign_loss)]
+        let entry = check_bits(key) << 32 | u64::from(black as u32);
+        self.slot(key).store(entry, Ordering::Relaxed);
+    }
+
+    /// Drop every entry.
+    pub fn clear(&self) {
+        for entry in &self.entries {
+            entry.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::{Board, Pos};
+    use crate::eval::evaluate;
+    use crate::rules::RuleSet;
+
+    #[test]
+    fn test_eval_cache_matches_evaluate() {
+        let zobrist = ZobristTable::new();
+        let cache = EvalCache::new(1);
+        let mut board = Board::new();
+        for (i, (r, c)) in [(9, 9), (9, 10), (10, 10), (8, 8), (10, 9)].into_iter().enumerate() {
+            board.place_stone(Pos::new(r, c), if i % 2 == 0 { Stone::Black } else { Stone::White });
+            for color in [Stone::Black, Stone::White] {
+                let hash = zobrist.hash(&board, color);
+                let score = evaluate(RuleSet::NINUKI, &board, color);
+                cache.store(&zobrist, hash, color, score);
+                assert_eq!(cache.probe(&zobrist, hash, color), Some(score));
+                let other = color.opponent();
+                assert_eq!(
+                    cache.probe(&zobrist, zobrist.hash(&board, other), other),
+                    Some(evaluate(RuleSet::NINUKI, &board, other))
+                );
+            }
+        }
+
+        // Another position in the same slot evicts, never aliases
+        let hash = zobrist.hash(&board, Stone::Black);
+        let colliding = hash ^ (1 << 40);
+        cache.store(&zobrist, colliding, Stone::Black, 7);
+        assert_eq!(cache.probe(&zobrist, hash, Stone::Black), None);
+        assert_eq!(cache.probe(&zobrist, colliding, Stone::Black), Some(7));
+
+        cache.clear();
+        assert_eq!(cache.probe(&zobrist, colliding, Stone::Black), None);
+    }
+}
@@ -0,0 +1,121 @@
+//! Move-ordering history shared by the search threads
+//!
+//! The history heuristic (beta cutoffs per move, weighted by depth) and the
+//! countermove table (last cutoff reply to each opponent move) live in one
+//! lock-free [`HistoryTable`] that every Lazy SMP worker reads and updates,
+//! so helper threads order moves by each other's cutoffs. Updates are
+//! relaxed atomics: a racing update may be lost, which only costs ordering
+//! quality. Killer moves stay per worker, since workers search different
+//! depths at the same time.
+//!
+//! The table belongs to the [`Searcher`](super::Searcher) and carries over
+//! between its searches until [`Searcher::clear_history`](super::Searcher::clear_history).
+
+use core::sync::atomic::{AtomicI32, AtomicU16, Ordering};
+
+use crate::board::{Pos, Stone, TOTAL_CELLS};
+
+/// History scores and countermoves for both colors
+pub(crate) struct HistoryTable {
+    scores: [[AtomicI32; TOTAL_CELLS]; 2],
+    /// Reply index + 1 by the color and point of the move answered; 0 is none
+    countermoves: [[AtomicU16; TOTAL_CELLS]; 2],
+}
+
+#[inline]
+fn color_index(color: Stone) -> usize {
+    if color == Stone::Black { 0 } else { 1 }
+}
+
+impl HistoryTable {
+    pub(crate) fn new() -> Self {
+        Self {
+            scores: core::array::from_fn(|_| core::array::from_fn(|_| AtomicI32::new(0))),
+            countermoves: core::array::from_fn(|_| core::array::from_fn(|_| AtomicU16::new(0))),
+        }
+    }
+
+    /// History score of `color` playing `pos`
+    #[inline]
+    pub(crate) fn score(&self, color: Stone, pos: Pos) -> i32 {
+        self.scores[color_index(color)][pos.to_index()].load(Ordering::Relaxed)
+    }
+
+    /// Record a beta cutoff by `color` playing `pos` with `depth` left
+    #[inline]
+    pub(crate) fn reward(&self, color: Stone, pos: Pos, depth: i8) {
+        let bonus = i32::from(depth) * i32::from(depth);
+        self.scores[color_index(color)][pos.to_index()].fetch_add(bonus, Ordering::Relaxed);
+    }
+
+    /// Halve every score, so recent cutoffs outweigh stale ones
+    pub(crate) fn decay(&self) {
+        for score in self.scores.iter().flatten() {
+            score.store(score.load(Ordering::Relaxed) >> 1, Ordering::Relaxed);
+        }
+    }
+
+    /// Last cutoff reply to `mover` playing `last`
+    #[inline]
+    pub(crate) fn countermove(&self, mover: Stone, last: Pos) -> Option<Pos> {
+        match self.countermoves[color_index(mover)][last.to_index()].load(Ordering::Relaxed) {
+            0 => None,
+            reply => Some(Pos::from_index(usize::from(reply) - 1)),
+        }
+    }
+
+    /// Record `reply` as the cutoff answer to `mover` playing `last`
+    #[inline]
+    pub(crate) fn set_countermove(&self, mover: Stone, last: Pos, reply: Pos) {
+        #[allow(clippy::cast_possible_truncation)]
+        let reply = reply.to_index() as u16 + 1;
+        self.countermoves[color_index(mover)][last.to_index()].store(reply, Ordering::Relaxed);
+    }
+
+    /// Forget all scores and countermoves
+    pub(crate) fn clear(&self) {
+        self.scores.iter().flatten().for_each(|score| score.store(0, Ordering::Relaxed));
+        self.countermoves.iter().flatten().for_each(|reply| reply.store(0, Ordering::Relaxed));
+    }
+}
+
+impl Clone for HistoryTable {
+    fn clone(&self) -> Self {
+        let table = Self::new();
+        for (to, from) in table.scores.iter().flatten().zip(self.scores.iter().flatten()) {
+            to.store(from.load(Ordering::Relaxed), Ordering::Relaxed);
+        }
+        for (to, from) in table.countermoves.iter().flatten().zip(self.countermoves.iter().flatten()) {
+            to.store(from.load(Ordering::Relaxed), Ordering::Relaxed);
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_rewards_decay_and_clear() {
+        let table = HistoryTable::new();
+        let pos = Pos::new(9, 9);
+        table.reward(Stone::Black, pos, 4);
+        table.reward(Stone::Black, pos, 2);
+        assert_eq!(table.score(Stone::Black, pos), 20);
+        assert_eq!(table.score(Stone::White, pos), 0);
+        table.decay();
+        assert_eq!(table.score(Stone::Black, pos), 10);
+
+        let (last, reply) = (Pos::new(0, 0), Pos::new(18, 18));
+        assert_eq!(table.countermove(Stone::White, last), None);
+        table.set_countermove(Stone::White, last, reply);
+        assert_eq!(table.countermove(Stone::White, last), Some(reply));
+        assert_eq!(table.countermove(Stone::Black, last), None);
+
+        let copy = table.clone();
+        table.clear();
+        assert_eq!((table.score(Stone::Black, pos), table.countermove(Stone::White, last)), (0, None));
+        assert_eq!((copy.score(Stone::Black, pos), copy.countermove(Stone::White, last)), (10, Some(reply)));
+    }
+}
@@ -0,0 +1,164 @@
+//! Search limits
+//!
+//! [`SearchLimits`] bounds a search by any combination of depth, nodes,
+//! wall-clock time and mate distance, and can restrict the moves searched at
+//! the root. Unset fields are unbounded; the search stops at whichever limit
+//! is hit first.
+//!
+//! # Example
+//!
+//! ```
+//! use gomoku::board::{Board, Pos, Stone};
+//! use gomoku::search::{SearchLimits, Searcher};
+//!
+//! let mut board = Board::new();
+//! board.place_stone(Pos::new(9, 9), Stone::Black);
+//!
+//! let mut searcher = Searcher::new(1);
+//! let limits = SearchLimits::nodes(5_000).with_depth(6);
+//! let result = searcher.search_with_limits(&board, Stone::White, &limits);
+//! assert!(result.depth <= 6);
+//! ```
+
+use core::time::Duration;
+
+use crate::board::{Bitboard, Pos};
+
+/// Deepest iteration when no depth limit is given.
+pub const MAX_SEARCH_DEPTH: i8 = 40;
+
+/// Constraints on a single search. `None` means unbounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchLimits {
+    /// Stop after this many nodes
+    pub max_nodes: Option<u64>,
+    /// Deepest iteration (plies)
+    pub max_depth: Option<i8>,
+    /// Hard wall-clock limit
+    pub max_time: Option<Duration>,
+    /// Look only for a forced win in this many of our own moves: caps the
+    /// depth at `2 * n - 1` plies and stops at the first iteration that
+    /// proves a win
+    pub mate_in: Option<u8>,
+    /// Consider only these moves at the root (`searchmoves`); they are
+    /// searched even when the move generator would skip them
+    pub root_moves: Option<Bitboard>,
+}
+
+impl SearchLimits {
+    /// Limit by node count only.
+    #[must_use]
+    pub fn nodes(max_nodes: u64) -> Self {
+        Self { max_nodes: Some(max_nodes), ..Self::default() }
+    }
+
+    /// Limit by depth only.
+    #[must_use]
+    pub fn depth(max_depth: i8) -> Self {
+        Self { max_depth: Some(max_depth), ..Self::default() }
+    }
+
+    /// Limit by wall-clock time only.
+    #[must_use]
+    pub fn time(max_time: Duration) -> Self {
+        Self { max_time: Some(max_time), ..Self::default() }
+    }
+
+    /// Search for a forced win in `moves` moves.
+    #[must_use]
+    pub fn mate_in(moves: u8) -> Self {
+        Self { mate_in: Some(moves), ..Self::default() }
+    }
+
+    /// Add a node limit.
+    #[must_use]
+    pub fn with_nodes(self, max_nodes: u64) -> Self {
+        Self { max_nodes: Some(max_nodes), ..self }
+    }
+
+    /// Add a depth limit.
+    #[must_use]
+    pub fn with_depth(self, max_depth: i8) -> Self {
+        Self { max_depth: Some(max_depth), ..self }
+    }
+
+    /// Add a time limit.
+    #[must_use]
+    pub fn with_time(self, max_time: Duration) -> Self {
+        Self { max_time: Some(max_time), ..self }
+    }
+
+    /// Search only `moves` at the root. Positions off the board are ignored.
+    #[must_use]
+    pub fn restrict_to(self, moves: impl IntoIterator<Item = Pos>) -> Self {
+        let mut root_moves = Bitboard::default();
+        for mov in moves {
+            if Pos::is_valid(i32::from(mov.row), i32::from(mov.col)) {
+                root_moves.set(mov);
+            }
+        }
+        Self { root_moves: Some(root_moves), ..self }
+    }
+
+    /// Whether the limits let the search play `mov` at the root.
+    #[must_use]
+    pub fn allows_root_move(&self, mov: Pos) -> bool {
+        self.root_moves.is_none_or(|moves| moves.get(mov))
+    }
+
+    /// Deepest iteration allowed by the depth and mate limits.
+    #[must_use]
+    pub fn effective_depth(&self) -> i8 {
+        let depth = self.max_depth.unwrap_or(MAX_SEARCH_DEPTH);
+        let mate_depth = self.mate_in.map_or(MAX_SEARCH_DEPTH, |n| {
+            (i16::from(n.max(1)) * 2 - 1).min(i16::from(MAX_SEARCH_DEPTH)) as i8
+        });
+        depth.min(mate_depth).clamp(1, MAX_SEARCH_DEPTH)
+    }
+
+    /// Whether the search can run on several threads: only pure time/depth
+    /// limits can, since node counts and mate proofs must be exact.
+    #[must_use]
+    pub fn allows_parallel(&self) -> bool {
+        self.max_time.is_some() && self.max_nodes.is_none() && self.mate_in.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use crate::alloc_prelude::*;
+
+    #[test]
+    fn test_effective_depth() {
+        assert_eq!(SearchLimits::default().effective_depth(), MAX_SEARCH_DEPTH);
+        assert_eq!(SearchLimits::depth(8).effective_depth(), 8);
+        assert_eq!(SearchLimits::mate_in(3).effective_depth(), 5);
+        assert_eq!(SearchLimits::mate_in(3).with_depth(4).effective_depth(), 4);
+        assert_eq!(SearchLimits::mate_in(0).effective_depth(), 1);
+        assert_eq!(SearchLimits::depth(0).effective_depth(), 1);
+    }
+
+    #[test]
+    fn test_allows_parallel() {
+        let timed = SearchLimits::time(Duration::from_millis(100));
+        assert!(timed.allows_parallel());
+        assert!(!timed.with_nodes(1000).allows_parallel());
+        assert!(!SearchLimits::depth(6).allows_parallel());
+        assert!(!SearchLimits { mate_in: Some(2), ..timed }.allows_parallel());
+    }
+
+    #[test]
+    fn test_restrict_to() {
+        let limits = SearchLimits::depth(4).restrict_to(vec![Pos::new(9, 9), Pos::new(0, 18)]);
+        assert!(limits.allows_root_move(Pos::new(0, 18)));
+        assert!(!limits.allows_root_move(Pos::new(9, 10)));
+        assert!(SearchLimits::default().allows_root_move(Pos::new(9, 10)));
+        assert_eq!(limits.max_depth, Some(4));
+
+        let off_board = SearchLimits::default().restrict_to([Pos { row: 255, col: 255 }, Pos::new(3, 3)]);
+        assert!(off_board.allows_root_move(Pos::new(3, 3)));
+        assert!(!off_board.allows_root_move(Pos::new(9, 9)));
+    }
+}
@@ -0,0 +1,188 @@
+//! Pruning margins for the alpha-beta search
+//!
+//! Reverse futility, razoring, futility pruning and the aspiration window all
+//! compare a static evaluation against alpha/beta with some slack. That slack
+//! only makes sense relative to the evaluation's scale, so the defaults are
+//! derived from [`PatternScore`] instead of being written as raw numbers.
+//! If the pattern weights are retuned, the margins follow, and
+//! [`PruningMargins::validate`] flags combinations that no longer fit the
+//! evaluation (e.g. a margin large enough to reach the win threshold).
+//!
+//! # Example
+//!
+//! ```
+//! use gomoku::search::{PruningMargins, Searcher};
+//!
+//! let mut margins = PruningMargins::default();
+//! margins.futility[0] /= 2; // prune more aggressively at depth 1
+//!
+//! let mut searcher = Searcher::new(1);
+//! searcher.set_margins(margins).unwrap();
+//! ```
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use super::MATE_THRESHOLD;
+use crate::eval::PatternScore;
+
+/// Deepest remaining depth at which the shallow-depth pruning rules apply.
+pub const SHALLOW_PRUNING_DEPTH: i8 = 3;
+
+/// Evaluation-relative margins used by the search's pruning rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PruningMargins {
+    /// Half-width of the aspiration window around the previous iteration's score
+    pub aspiration_window: i32,
+    /// Reverse futility pruning: cut when `eval - margin * depth >= beta`
+    pub reverse_futility_per_depth: i32,
+    /// Razoring: drop to quiescence when `eval + margin * depth <= alpha`
+    pub razor_per_depth: i32,
+    /// Per-move futility margins for remaining depth 1, 2 and 3
+    pub futility: [i32; SHALLOW_PRUNING_DEPTH as usize],
+}
+
+impl PruningMargins {
+    /// Margins derived from the current [`PatternScore`] weights.
+    ///
+    /// - Aspiration window: a tenth of an open two (small positional shift)
+    /// - RFP / razoring: one open three per ply, the largest swing a single
+    ///   quiet move can cause by creating a new threat
+    /// - Futility: closed four, open four, open four + open three
+    #[must_use]
+    pub const fn from_pattern_scores() -> Self {
+        Self {
+            aspiration_window: PatternScore::OPEN_TWO / 10,
+            reverse_futility_per_depth: PatternScore::OPEN_THREE,
+            razor_per_depth: PatternScore::OPEN_THREE,
+            futility: [
+                PatternScore::CLOSED_FOUR,
+                PatternScore::OPEN_FOUR,
+                PatternScore::OPEN_FOUR + PatternScore::OPEN_THREE,
+            ],
+        }
+    }
+
+    /// Futility margin for the given remaining depth (clamped to 1..=3).
+    #[inline]
+    #[must_use]
+    pub fn futility_margin(&self, depth: i8) -> i32 {
+        let idx = depth.clamp(1, SHALLOW_PRUNING_DEPTH) - 1;
+        self.futility[idx as usize]
+    }
+
+    /// Check that the margins are consistent with the evaluation scale.
+    ///
+    /// Rejects margins that are non-positive, futility margins that shrink
+    /// with depth, an aspiration window wider than one pruning step, and
+    /// any margin that could push a score into the decided (win/loss) range.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first inconsistency found.
+    pub fn validate(&self) -> Result<(), String> {
+        let depth = i32::from(SHALLOW_PRUNING_DEPTH);
+
+        if self.aspiration_window <= 0
+            || self.reverse_futility_per_depth <= 0
+            || self.razor_per_depth <= 0
+            || self.futility.iter().any(|&m| m <= 0)
+        {
+            return Err(format!("margins must be positive: {:?}", self));
+        }
+        if self.futility.windows(2).any(|w| w[0] > w[1]) {
+            return Err(format!(
+                "futility margins must not shrink with depth: {:?}",
+                self.futility
+            ));
+        }
+        if self.aspiration_window >= self.reverse_futility_per_depth {
+            return Err(format!(
+                "aspiration window {} is not finer than a pruning step {}",
+                self.aspiration_window, self.reverse_futility_per_depth
+            ));
+        }
+
+        // Saturate so an absurd per-depth margin is reported, not overflowed
+        let widest = self
+            .reverse_futility_per_depth
+            .saturating_mul(depth)
+            .max(self.razor_per_depth.saturating_mul(depth))
+            .max(self.futility[self.futility.len() - 1]);
+        if widest >= MATE_THRESHOLD {
+            return Err(format!(
+                "margin {} reaches the decided-score range ({}); eval scale mismatch",
+                widest, MATE_THRESHOLD
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for PruningMargins {
+    fn default() -> Self {
+        Self::from_pattern_scores()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_margins_match_eval_scale() {
+        let margins = PruningMargins::default();
+        assert_eq!(margins.validate(), Ok(()));
+        assert_eq!(margins.aspiration_window, 100);
+        assert_eq!(margins.futility_margin(1), PatternScore::CLOSED_FOUR);
+        assert_eq!(margins.futility_margin(3), 110_000);
+    }
+
+    #[test]
+    fn test_futility_margin_clamps_depth() {
+        let margins = PruningMargins::default();
+        assert_eq!(margins.futility_margin(0), margins.futility[0]);
+        assert_eq!(margins.futility_margin(9), margins.futility[2]);
+    }
+
+    #[test]
+    fn test_validate_rejects_mismatched_scale() {
+        let margins = PruningMargins {
+            razor_per_depth: PatternScore::FIVE / 2,
+            ..PruningMargins::default()
+        };
+        assert!(margins.validate().is_err(), "Razor margin reaching FIVE must be rejected");
+
+        let margins = PruningMargins {
+            futility: [PatternScore::OPEN_FOUR, PatternScore::CLOSED_FOUR, PatternScore::OPEN_FOUR],
+            ..PruningMargins::default()
+        };
+        assert!(margins.validate().is_err(), "Shrinking futility margins must be rejected");
+
+        let margins = PruningMargins {
+            aspiration_window: PatternScore::OPEN_FOUR,
+            ..PruningMargins::default()
+        };
+        assert!(margins.validate().is_err(), "Aspiration window wider than a pruning step");
+
+        let margins = PruningMargins {
+            reverse_futility_per_depth: 0,
+            ..PruningMargins::default()
+        };
+        assert!(margins.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_overflowing_margins() {
+        let margins = PruningMargins {
+            reverse_futility_per_depth: 2_000_000_000,
+            ..PruningMargins::default()
+        };
+        assert!(margins.validate().is_err());
+
+        let margins = PruningMargins {
+            razor_per_depth: i32::MAX,
+            ..PruningMargins::default()
+        };
+        assert!(margins.validate().is_err());
+    }
+}
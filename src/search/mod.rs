@@ -3,15 +3,91 @@
 //! Contains:
 //! - Zobrist hashing for position identification
 //! - Transposition table for caching search results
+//! - Evaluation cache for repeated static evaluations
 //! - Alpha-Beta search with iterative deepening
+//! - History and countermove tables shared by the Lazy SMP threads
+//! - Search limits (depth, nodes, time, mate distance)
+//! - Pruning margins derived from the evaluation scale
+//! - Tunable search parameters, loadable from a config file
 //! - VCF/VCT threat search for forced wins
+//! - Forced-win lines with replies, checked against every defense
+//! - Swindle analysis for lost positions against fallible opponents
+//! - Search tree tracing for debugging (`trace` feature)
+//! - Break-and-replay cycle resolution for capture-breakable fives
+//! - Make/unmake auditing for debugging (`Searcher::set_paranoid`)
 
 pub mod alphabeta;
+mod audit;
+pub mod cycle;
+pub mod evalcache;
+mod history;
+pub mod limits;
+pub mod margins;
+pub mod params;
+pub mod proof;
+pub mod swindle;
 pub mod threat;
+#[cfg(feature = "trace")]
+pub mod trace;
 pub mod tt;
 pub mod zobrist;
 
-pub use alphabeta::{SearchResult, SearchStats, Searcher};
+pub use alphabeta::{
+    mate_distance, InfoCallback, RootLine, SearchControl, SearchInfo, SearchResult, SearchStats, Searcher,
+    MATE_THRESHOLD,
+};
+pub use cycle::{resolve_break, resolve_five, CycleOutcome};
+pub use evalcache::EvalCache;
+pub use limits::{SearchLimits, MAX_SEARCH_DEPTH};
+pub use margins::PruningMargins;
+pub use params::SearchParams;
+pub use proof::ProofLine;
+pub use swindle::{find_resistant_move, find_swindle_move, Resistance, SwindleCandidate};
 pub use threat::{ThreatResult, ThreatSearcher};
 pub use tt::{AtomicTT, EntryType, TTEntry, TTStats, TranspositionTable};
 pub use zobrist::ZobristTable;
+
+/// Square root; `core` has no float math, so without `std` this rounds
+/// an integer square root of the scaled mantissa, giving the same correctly
+/// rounded result.
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    x.sqrt()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    if x.is_nan() || x < 0.0 {
+        return f64::NAN;
+    }
+    if x == 0.0 || x == f64::INFINITY {
+        return x;
+    }
+    // x = mant * 2^exp with a 53-bit mant (subnormals normalized) and even exp
+    let bits = x.to_bits();
+    let (mut mant, mut exp) = match ((bits >> 52) & 0x7ff) as i32 {
+        0 => {
+            let shift = (bits.leading_zeros() - 11) as i32;
+            (bits << shift, -1074 - shift)
+        }
+        biased => ((bits & ((1 << 52) - 1)) | (1 << 52), biased - 1075),
+    };
+    if exp & 1 != 0 {
+        mant <<= 1;
+        exp -= 1;
+    }
+    // The root of mant << 60 has 57 bits; round off 4 to nearest, a
+    // nonzero remainder pushing a halfway root up
+    let scaled = u128::from(mant) << 60;
+    let root = scaled.isqrt();
+    let exact = root * root == scaled;
+    let (mut q, low) = ((root >> 4) as u64, root & 0xf);
+    if low > 8 || (low == 8 && (!exact || q & 1 == 1)) {
+        q += 1;
+    }
+    // The root is q * 2^(exp / 2 - 26) with 2^52 <= q <= 2^53; q = 2^53
+    // carries into the exponent
+    let biased = (1023 + 52 + exp / 2 - 26) as u64;
+    f64::from_bits((biased << 52) + (q - (1 << 52)))
+}
@@ -0,0 +1,258 @@
+//! Tunable search parameters
+//!
+//! [`SearchParams`] gathers the numbers the alpha-beta search is tuned by:
+//! the [`PruningMargins`], late move reductions, null-move pruning,
+//! quiescence depth caps and how many moves each node searches. A
+//! [`Searcher`](super::Searcher) takes them at construction or through
+//! [`Searcher::set_params`](super::Searcher::set_params).
+//!
+//! For tuning runs they are read from a text file of `key = value` lines,
+//! where lists are comma-separated and `#` starts a comment. Keys left out
+//! keep their defaults, and [`SearchParams`]'s `Display` writes every key
+//! back in the same format.
+//!
+//! ```
+//! use gomoku::search::{SearchParams, Searcher};
+//!
+//! let params = SearchParams::parse("null_move_reduction = 3\nmax_moves = 4, 6, 8, 10").unwrap();
+//! assert_eq!(params.null_move_reduction, 3);
+//! assert_eq!(SearchParams::parse(&params.to_string()), Ok(params));
+//!
+//! let searcher = Searcher::with_params(1, params).unwrap();
+//! assert_eq!(searcher.params().max_moves, [4, 6, 8, 10]);
+//! ```
+
+use core::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use super::margins::PruningMargins;
+
+/// Depth bands of [`SearchParams::max_moves`]: remaining depth 0-1, 2-3,
+/// 4-5 and 6 or more
+pub const MOVE_LIMIT_BANDS: usize = 4;
+
+/// Numbers the alpha-beta search is tuned by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchParams {
+    pub margins: PruningMargins,
+    /// Late move reduction: `sqrt(depth) * sqrt(move index) * lmr_percent / 100` plies
+    pub lmr_percent: u32,
+    /// Extra reduction for quiet moves (no four, capture or block)
+    pub lmr_quiet_extra: i8,
+    /// Plies the null-move search is reduced by
+    pub null_move_reduction: i8,
+    /// Shallowest remaining depth that tries a null move
+    pub null_move_min_depth: i8,
+    /// Deepest quiescence ply
+    pub max_qs_depth: i8,
+    /// Quiescence plies that still search fours; deeper ones only fives
+    pub qs_fours_depth: i8,
+    /// Quiescence plies that defend against a five or capture win the last
+    /// move threatens instead of standing pat
+    pub qs_defense_depth: i8,
+    /// Forcing moves per quiescence node in its first two plies, then deeper
+    pub qs_moves: [usize; 2],
+    /// Legal moves searched at the root
+    pub max_root_moves: usize,
+    /// Moves per node in quiet positions, by [`MOVE_LIMIT_BANDS`]
+    pub max_moves: [usize; MOVE_LIMIT_BANDS],
+    /// Moves per node in tactical positions (fork or four threats), by
+    /// [`MOVE_LIMIT_BANDS`]
+    pub max_tactical_moves: [usize; MOVE_LIMIT_BANDS],
+}
+
+impl Default for SearchParams {
+    fn default() -> Self {
+        Self {
+            margins: PruningMargins::default(),
+            lmr_percent: 50,
+            lmr_quiet_extra: 1,
+            null_move_reduction: 2,
+            null_move_min_depth: 3,
+            max_qs_depth: 16,
+            qs_fours_depth: 6,
+            qs_defense_depth: 4,
+            qs_moves: [8, 4],
+            max_root_moves: 30,
+            max_moves: [3, 5, 7, 9],
+            max_tactical_moves: [5, 7, 9, 12],
+        }
+    }
+}
+
+impl SearchParams {
+    /// Move limit for a node at remaining `depth`
+    #[inline]
+    #[must_use]
+    pub fn move_limit(&self, depth: i8, tactical: bool) -> usize {
+        let band = (depth.max(0) as usize / 2).min(MOVE_LIMIT_BANDS - 1);
+        if tactical { self.max_tactical_moves[band] } else { self.max_moves[band] }
+    }
+
+    /// Check that every parameter is usable.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first problem found, including any
+    /// from [`PruningMargins::validate`].
+    pub fn validate(&self) -> Result<(), String> {
+        self.margins.validate()?;
+        if !(1..=200).contains(&self.lmr_percent) {
+            return Err(format!("lmr_percent {} is not 1-200", self.lmr_percent));
+        }
+        if self.lmr_quiet_extra < 0 {
+            return Err(format!("lmr_quiet_extra {} is negative", self.lmr_quiet_extra));
+        }
+        if self.null_move_reduction < 1 || self.null_move_min_depth < 1 {
+            return Err(format!(
+                "null-move reduction {} and minimum depth {} must be at least 1",
+                self.null_move_reduction, self.null_move_min_depth
+            ));
+        }
+        let within = 0..=self.max_qs_depth;
+        if self.max_qs_depth < 1 || !within.contains(&self.qs_fours_depth) || !within.contains(&self.qs_defense_depth) {
+            return Err(format!(
+                "quiescence depth {} must be positive and the fours depth {} and defense depth {} within it",
+                self.max_qs_depth, self.qs_fours_depth, self.qs_defense_depth
+            ));
+        }
+        if self.qs_moves.contains(&0) || self.max_root_moves == 0 {
+            return Err("move limits must be positive".to_string());
+        }
+        for (name, limits) in [("max_moves", &self.max_moves), ("max_tactical_moves", &self.max_tactical_moves)] {
+            if limits.contains(&0) || limits.windows(2).any(|w| w[0] > w[1]) {
+                return Err(format!("{} {:?} must be positive and not shrink with depth", name, limits));
+            }
+        }
+        Ok(())
+    }
+
+    /// Parameters from `key = value` lines, starting from the defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns the line number and problem of the first bad line, or the
+    /// [`validate`](Self::validate) error of the result.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut params = Self::default();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or(format!("line {}: expected key = value", i + 1))?;
+            params.set(key.trim(), value.trim()).map_err(|e| format!("line {}: {}", i + 1, e))?;
+        }
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// [`parse`](Self::parse) the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the file can't be read or doesn't parse.
+    #[cfg(feature = "std")]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Self::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let margins = &mut self.margins;
+        match key {
+            "aspiration_window" => margins.aspiration_window = parse_one(value)?,
+            "reverse_futility_per_depth" => margins.reverse_futility_per_depth = parse_one(value)?,
+            "razor_per_depth" => margins.razor_per_depth = parse_one(value)?,
+            "futility" => margins.futility = parse_list(value)?,
+            "lmr_percent" => self.lmr_percent = parse_one(value)?,
+            "lmr_quiet_extra" => self.lmr_quiet_extra = parse_one(value)?,
+            "null_move_reduction" => self.null_move_reduction = parse_one(value)?,
+            "null_move_min_depth" => self.null_move_min_depth = parse_one(value)?,
+            "max_qs_depth" => self.max_qs_depth = parse_one(value)?,
+            "qs_fours_depth" => self.qs_fours_depth = parse_one(value)?,
+            "qs_defense_depth" => self.qs_defense_depth = parse_one(value)?,
+            "qs_moves" => self.qs_moves = parse_list(value)?,
+            "max_root_moves" => self.max_root_moves = parse_one(value)?,
+            "max_moves" => self.max_moves = parse_list(value)?,
+            "max_tactical_moves" => self.max_tactical_moves = parse_list(value)?,
+            _ => return Err(format!("unknown parameter '{}'", key)),
+        }
+        Ok(())
+    }
+}
+
+fn parse_one<T: core::str::FromStr>(value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("bad value '{}'", value))
+}
+
+fn parse_list<T: core::str::FromStr + Copy + Default, const N: usize>(value: &str) -> Result<[T; N], String> {
+    let items: Vec<T> = value.split(',').map(|item| parse_one(item.trim())).collect::<Result<_, _>>()?;
+    items.try_into().map_err(|_| format!("expected {} comma-separated values in '{}'", N, value))
+}
+
+/// Comma-separated list, as [`SearchParams::parse`] reads it
+fn join<T: fmt::Display>(items: &[T]) -> String {
+    items.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+}
+
+impl fmt::Display for SearchParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let margins = &self.margins;
+        writeln!(f, "aspiration_window = {}", margins.aspiration_window)?;
+        writeln!(f, "reverse_futility_per_depth = {}", margins.reverse_futility_per_depth)?;
+        writeln!(f, "razor_per_depth = {}", margins.razor_per_depth)?;
+        writeln!(f, "futility = {}", join(&margins.futility))?;
+        writeln!(f, "lmr_percent = {}", self.lmr_percent)?;
+        writeln!(f, "lmr_quiet_extra = {}", self.lmr_quiet_extra)?;
+        writeln!(f, "null_move_reduction = {}", self.null_move_reduction)?;
+        writeln!(f, "null_move_min_depth = {}", self.null_move_min_depth)?;
+        writeln!(f, "max_qs_depth = {}", self.max_qs_depth)?;
+        writeln!(f, "qs_fours_depth = {}", self.qs_fours_depth)?;
+        writeln!(f, "qs_defense_depth = {}", self.qs_defense_depth)?;
+        writeln!(f, "qs_moves = {}", join(&self.qs_moves))?;
+        writeln!(f, "max_root_moves = {}", self.max_root_moves)?;
+        writeln!(f, "max_moves = {}", join(&self.max_moves))?;
+        writeln!(f, "max_tactical_moves = {}", join(&self.max_tactical_moves))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_are_valid_and_round_trip() {
+        let params = SearchParams::default();
+        assert_eq!(params.validate(), Ok(()));
+        assert_eq!(SearchParams::parse(&params.to_string()), Ok(params));
+        assert_eq!(SearchParams::parse("# nothing overridden\n\n"), Ok(params));
+
+        assert_eq!(params.move_limit(0, false), 3);
+        assert_eq!(params.move_limit(3, true), 7);
+        assert_eq!(params.move_limit(40, false), 9);
+    }
+
+    #[test]
+    fn test_parse_overrides_and_errors() {
+        let params = SearchParams::parse("futility = 40000, 90000, 100000  # tighter\nlmr_percent=60").unwrap();
+        assert_eq!(params.margins.futility, [40_000, 90_000, 100_000]);
+        assert_eq!(params.lmr_percent, 60);
+        assert_eq!(params.max_root_moves, SearchParams::default().max_root_moves);
+
+        assert!(SearchParams::parse("lmr_percent").unwrap_err().contains("line 1"));
+        assert!(SearchParams::parse("\nnull_move = 3").unwrap_err().contains("line 2: unknown parameter"));
+        assert!(SearchParams::parse("max_moves = 3, 5").unwrap_err().contains("expected 4"));
+        assert!(SearchParams::parse("qs_moves = 8, x").unwrap_err().contains("bad value 'x'"));
+        // Parses, but doesn't validate
+        assert!(SearchParams::parse("max_moves = 9, 7, 5, 3").unwrap_err().contains("shrink"));
+        assert!(SearchParams::parse("qs_fours_depth = 20").is_err());
+        assert!(SearchParams::parse("razor_per_depth = 0").unwrap_err().contains("positive"));
+        assert!(SearchParams::parse("reverse_futility_per_depth = 2000000000").is_err());
+        assert!(SearchParams::parse("razor_per_depth = 2000000000").is_err());
+    }
+}
@@ -0,0 +1,235 @@
+//! Forced-win lines
+//!
+//! A [`ProofLine`] is a forced win written out in full: the attacker's
+//! moves with the defender's only replies between them, ending on the
+//! winning move. The engine attaches one to immediate and VCF wins so a GUI
+//! can play the win out, and [`ProofLine::verify`] replays it against every
+//! defense, independently of the search that found it.
+//!
+//! ```
+//! use gomoku::rules::RuleSet;
+//! use gomoku::search::ProofLine;
+//! use gomoku::{Board, Pos, Stone};
+//!
+//! let mut board = Board::new();
+//! board.place_stone(Pos::new(9, 4), Stone::White);
+//! for col in 5..9 {
+//!     board.place_stone(Pos::new(9, col), Stone::Black);
+//! }
+//! let line = ProofLine::new(Stone::Black, vec![Pos::new(9, 9)]);
+//! assert!(line.verify(RuleSet::NINUKI, &board).is_ok());
+//! assert!(ProofLine::new(Stone::Black, vec![Pos::new(0, 0)]).verify(RuleSet::NINUKI, &board).is_err());
+//! ```
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use core::fmt;
+
+use crate::board::{Board, Pos, Stone};
+use crate::notation::notation;
+use crate::rules::{
+    execute_captures_fast, find_five_line_at_pos, has_five_at_pos, is_valid_move, undo_captures, RuleSet,
+};
+
+use super::cycle::{resolve_five, CycleOutcome};
+
+/// A forced win: `attacker`'s moves at even indices, the defender's forced
+/// replies at odd ones. The last move wins on the spot, or (after a four
+/// with no defense) leaves the defender without a reply that stops the win.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProofLine {
+    pub attacker: Stone,
+    pub moves: Vec<Pos>,
+}
+
+impl ProofLine {
+    pub fn new(attacker: Stone, moves: Vec<Pos>) -> Self {
+        Self { attacker, moves }
+    }
+
+    /// The attacker's moves, without the replies
+    pub fn attacker_moves(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.moves.iter().step_by(2).copied()
+    }
+
+    /// Check that the line is a forced win under `rules` from `board`, the
+    /// attacker to move: every move is legal, each attacker move threatens a win that
+    /// every defender move but the listed reply leaves standing (and the
+    /// reply is no win for the defender), and the line ends on a win.
+    ///
+    /// Conservative: a defender five of any kind counts as an escape.
+    ///
+    /// # Errors
+    ///
+    /// The first move where the proof fails.
+    pub fn verify(&self, rules: RuleSet, board: &Board) -> Result<(), String> {
+        let attacker = self.attacker;
+        let defender = attacker.opponent();
+        let name = |pos: Pos| notation().format(pos);
+        let mut board = board.clone();
+        board.disable_incremental_eval();
+
+        let mut index = 0;
+        loop {
+            let Some(&mov) = self.moves.get(index) else {
+                return Err("the line is empty".to_string());
+            };
+            if !is_valid_move(rules, &board, mov, attacker) {
+                return Err(format!("{} is not a legal move for {:?}", name(mov), attacker));
+            }
+            if wins_at(rules, &mut board, mov, attacker) {
+                return if index + 1 == self.moves.len() {
+                    Ok(())
+                } else {
+                    Err(format!("{} already wins, but the line goes on", name(mov)))
+                };
+            }
+            board.place_stone(mov, attacker);
+            execute_captures_fast(rules, &mut board, mov, attacker);
+
+            let empty: Vec<Pos> = (0..crate::board::TOTAL_CELLS)
+                .map(Pos::from_index)
+                .filter(|&pos| board.is_empty(pos))
+                .collect();
+            let threats: Vec<Pos> = empty
+                .iter()
+                .copied()
+                .filter(|&pos| is_valid_move(rules, &board, pos, attacker) && wins_at(rules, &mut board, pos, attacker))
+                .collect();
+            if threats.is_empty() {
+                return Err(format!("{} threatens no win", name(mov)));
+            }
+
+            let reply = self.moves.get(index + 1).copied();
+            for &other in &empty {
+                if Some(other) != reply
+                    && is_valid_move(rules, &board, other, defender)
+                    && !still_wins(rules, &mut board, other, defender, &threats)
+                {
+                    return Err(format!(
+                        "{} answers {}{}",
+                        name(other),
+                        name(mov),
+                        reply.map_or(String::new(), |reply| format!(" as well as {}", name(reply)))
+                    ));
+                }
+            }
+
+            let Some(reply) = reply else {
+                return Ok(());
+            };
+            if !is_valid_move(rules, &board, reply, defender) {
+                return Err(format!("{} is not a legal move for {:?}", name(reply), defender));
+            }
+            board.place_stone(reply, defender);
+            execute_captures_fast(rules, &mut board, reply, defender);
+            if board.captures_to_win(rules, defender) == 0 || has_five_at_pos(rules, &board, reply, defender) {
+                return Err(format!("{} wins for {:?}", name(reply), defender));
+            }
+            if index + 2 == self.moves.len() {
+                return Err(format!("the line ends on {}'s reply", name(reply)));
+            }
+            index += 2;
+        }
+    }
+}
+
+impl fmt::Display for ProofLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, &pos) in self.moves.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", notation().format(pos))?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether `color` playing `pos` wins on the spot: the capture win, or a
+/// five that survives the breaks (see [`resolve_five`])
+fn wins_at(rules: RuleSet, board: &mut Board, pos: Pos, color: Stone) -> bool {
+    board.place_stone(pos, color);
+    let cap = execute_captures_fast(rules, board, pos, color);
+    let wins = board.captures_to_win(rules, color) == 0
+        || (has_five_at_pos(rules, board, pos, color)
+            && find_five_line_at_pos(rules, board, pos, color)
+                .is_some_and(|five| resolve_five(rules, board, &five, color) == CycleOutcome::Win));
+    undo_captures(board, color, &cap);
+    board.remove_stone(pos);
+    wins
+}
+
+/// Whether one of `threats` still wins for the attacker after `defender`
+/// plays `pos`, and `pos` wins nothing for the defender
+fn still_wins(rules: RuleSet, board: &mut Board, pos: Pos, defender: Stone, threats: &[Pos]) -> bool {
+    let attacker = defender.opponent();
+    board.place_stone(pos, defender);
+    let cap = execute_captures_fast(rules, board, pos, defender);
+    let wins = board.captures_to_win(rules, defender) > 0
+        && !has_five_at_pos(rules, board, pos, defender)
+        && threats.iter().any(|&threat| {
+            board.is_empty(threat)
+                && is_valid_move(rules, board, threat, attacker)
+                && wins_at(rules, board, threat, attacker)
+        });
+    undo_captures(board, defender, &cap);
+    board.remove_stone(pos);
+    wins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_four_with_forced_reply() {
+        // Black's four at J10 (closed at E10 by White) forces K10; then the
+        // vertical three on column N becomes an open four
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 4), Stone::White);
+        for col in 5..8 {
+            board.place_stone(Pos::new(9, col), Stone::Black);
+        }
+        for row in 4..7 {
+            board.place_stone(Pos::new(row, 12), Stone::Black);
+        }
+        let four = Pos::new(9, 8);
+        let block = Pos::new(9, 9);
+
+        let open_four = ProofLine::new(Stone::Black, vec![four, block, Pos::new(7, 12)]);
+        assert_eq!(open_four.verify(RuleSet::NINUKI, &board), Ok(()));
+        assert_eq!(open_four.attacker_moves().collect::<Vec<_>>(), vec![four, Pos::new(7, 12)]);
+
+        // Going on after the five
+        let mut after = open_four.moves.clone();
+        after.extend([Pos::new(8, 12), Pos::new(3, 12), Pos::new(0, 0)]);
+        let too_long = ProofLine::new(Stone::Black, after);
+        assert!(too_long.verify(RuleSet::NINUKI, &board).unwrap_err().contains("already wins"));
+
+        // The wrong reply listed: K10 answers too
+        let wrong = ProofLine::new(Stone::Black, vec![four, Pos::new(0, 0), Pos::new(7, 12)]);
+        assert!(wrong.verify(RuleSet::NINUKI, &board).unwrap_err().starts_with(&notation().format(block)));
+
+        // An unforced first move
+        let quiet = ProofLine::new(Stone::Black, vec![Pos::new(0, 0), block, Pos::new(7, 12)]);
+        assert!(quiet.verify(RuleSet::NINUKI, &board).unwrap_err().contains("threatens no win"));
+    }
+
+    #[test]
+    fn test_verify_rejects_reply_that_wins() {
+        // Black's open four at J10 can't be blocked, but White's own four
+        // on row 4 (closed by Black at E4) completes at K4
+        let mut board = Board::new();
+        for col in 5..8 {
+            board.place_stone(Pos::new(9, col), Stone::Black);
+        }
+        for col in 5..9 {
+            board.place_stone(Pos::new(3, col), Stone::White);
+        }
+        board.place_stone(Pos::new(3, 4), Stone::Black);
+        let line = ProofLine::new(Stone::Black, vec![Pos::new(9, 8), Pos::new(3, 9), Pos::new(9, 9)]);
+        assert!(line.verify(RuleSet::NINUKI, &board).unwrap_err().contains("wins for White"));
+    }
+}
@@ -0,0 +1,353 @@
+//! Swindle analysis: picking trappy moves in lost positions
+//!
+//! When the search says a position is lost against best play, every move
+//! scores the same and the engine would play the "most resistant" defense,
+//! which a human opponent usually converts without thinking. A human is not
+//! a perfect player, though: some losing moves leave the opponent many
+//! natural-looking replies that throw the win away.
+//!
+//! For each candidate root move this module looks one ply further at the
+//! opponent's plausible replies (the ones a human would consider: close to
+//! the action, ranked by how good they look statically) and classifies the
+//! outcome after each reply. The swindle score of a candidate is the share
+//! of those replies that are mistakes — replies after which we have an
+//! immediate win or a decisive static advantage.
+//!
+//! Speculative defense ([`find_resistant_move`]) also makes the opponent
+//! work for the win: after each candidate it runs the opponent's own
+//! node-limited mate search and prefers the moves whose win that search
+//! can't prove, then the longest forced wins, then the trappiest moves and
+//! the ones that cost the search the most nodes.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::board::{Board, Pos, Stone};
+use crate::eval::{evaluate, PatternScore};
+use crate::rules::{
+    can_break_five_by_capture, execute_captures_fast, find_five_line_at_pos, has_five_at_pos,
+    is_valid_move, undo_captures, RuleSet,
+};
+
+use super::{mate_distance, SearchLimits, Searcher};
+
+/// Root moves examined (best-looking first).
+const MAX_CANDIDATES: usize = 12;
+
+/// Opponent replies examined per candidate.
+const MAX_REPLIES: usize = 16;
+
+/// Static score after a reply that counts as a decisive mistake.
+const MISTAKE_THRESHOLD: i32 = PatternScore::OPEN_FOUR;
+
+/// Longest forced win (opponent moves) the resistance search looks for.
+const RESIST_MATE_MOVES: u8 = 10;
+
+/// Result of analyzing one root move.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwindleCandidate {
+    /// The root move
+    pub mov: Pos,
+    /// Fraction of plausible replies that hand us a winning position (0.0-1.0)
+    pub error_chance: f64,
+    /// Number of replies analyzed
+    pub replies: usize,
+    /// Worst outcome for us over the analyzed replies (the opponent's best reply)
+    pub worst_outcome: i32,
+    /// Standard deviation of outcomes across replies — high values mean the
+    /// opponent has to find a narrow path
+    pub spread: f64,
+}
+
+/// How hard a root move makes the opponent's win, measured by the
+/// opponent's own search.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resistance {
+    /// Trap analysis of the move
+    pub candidate: SwindleCandidate,
+    /// The opponent's forced win after the move, in their moves; `None`
+    /// when their search proved none within the budget
+    pub opponent_mate_in: Option<i32>,
+    /// Nodes the opponent's search spent proving the win (the whole
+    /// budget when it found none)
+    pub opponent_nodes: u64,
+}
+
+impl Resistance {
+    /// Order by difficulty for the opponent: unproven wins, then longer
+    /// forced wins, then `error_chance`, then search effort.
+    fn cmp_difficulty(&self, other: &Self) -> core::cmp::Ordering {
+        let moves = |r: &Self| r.opponent_mate_in.map_or(i32::MAX, |moves| moves);
+        moves(self)
+            .cmp(&moves(other))
+            .then(self.candidate.error_chance.total_cmp(&other.candidate.error_chance))
+            .then(self.opponent_nodes.cmp(&other.opponent_nodes))
+    }
+}
+
+/// Analyze root moves for `color` and return the trappiest one.
+///
+/// Candidates are ranked by `error_chance`, then `spread`, then
+/// `worst_outcome`. Returns `None` if no candidate gives the opponent any
+/// chance to go wrong (the plain search move should be kept).
+#[must_use]
+pub fn find_swindle_move(rules: RuleSet, board: &Board, color: Stone) -> Option<SwindleCandidate> {
+    analyze_candidates(rules, board, color)
+        .into_iter()
+        .filter(|c| c.error_chance > 0.0)
+        .max_by(|a, b| {
+            a.error_chance
+                .total_cmp(&b.error_chance)
+                .then(a.spread.total_cmp(&b.spread))
+                .then(a.worst_outcome.cmp(&b.worst_outcome))
+        })
+}
+
+/// Speculative defense: of the candidate root moves for `color`, the one
+/// that makes the opponent's win hardest (see [`Resistance`]), each
+/// opponent search limited to `node_budget` nodes, all played by `rules`.
+/// `None` without legal candidates.
+#[must_use]
+pub fn find_resistant_move(rules: RuleSet, board: &Board, color: Stone, node_budget: u64) -> Option<Resistance> {
+    let opponent = color.opponent();
+    let limits = SearchLimits::mate_in(RESIST_MATE_MOVES).with_nodes(node_budget);
+    let mut searcher = Searcher::with_threads(1, 1);
+    searcher.set_rules(rules);
+    let mut work = board.clone();
+
+    analyze_candidates(rules, board, color)
+        .into_iter()
+        .map(|candidate| {
+            work.place_stone(candidate.mov, color);
+            let cap_info = execute_captures_fast(rules, &mut work, candidate.mov, color);
+            searcher.clear_tt();
+            searcher.clear_history();
+            let result = searcher.search_with_limits(&work, opponent, &limits);
+            undo_captures(&mut work, color, &cap_info);
+            work.remove_stone(candidate.mov);
+            Resistance {
+                candidate,
+                opponent_mate_in: mate_distance(result.score).filter(|&moves| moves > 0),
+                opponent_nodes: result.nodes,
+            }
+        })
+        .max_by(Resistance::cmp_difficulty)
+}
+
+/// Analyze the top candidate root moves for `color`.
+#[must_use]
+pub fn analyze_candidates(rules: RuleSet, board: &Board, color: Stone) -> Vec<SwindleCandidate> {
+    let opponent = color.opponent();
+    let mut work = board.clone();
+
+    plausible_moves(rules, &mut work, color, MAX_CANDIDATES)
+        .into_iter()
+        .map(|mov| {
+            work.place_stone(mov, color);
+            let cap_info = execute_captures_fast(rules, &mut work, mov, color);
+
+            let outcomes: Vec<i32> = plausible_moves(rules, &mut work, opponent, MAX_REPLIES)
+                .into_iter()
+                .map(|reply| {
+                    work.place_stone(reply, opponent);
+                    let reply_caps = execute_captures_fast(rules, &mut work, reply, opponent);
+                    let outcome = outcome_after_reply(rules, &mut work, reply, color);
+                    undo_captures(&mut work, opponent, &reply_caps);
+                    work.remove_stone(reply);
+                    outcome
+                })
+                .collect();
+
+            undo_captures(&mut work, color, &cap_info);
+            work.remove_stone(mov);
+            summarize(mov, &outcomes)
+        })
+        .collect()
+}
+
+/// Score for `color` after the opponent just played `reply`.
+fn outcome_after_reply(rules: RuleSet, board: &mut Board, reply: Pos, color: Stone) -> i32 {
+    let opponent = color.opponent();
+    if board.captures_to_win(rules, opponent) == 0 || wins_with_five(rules, board, reply, opponent) {
+        return -PatternScore::FIVE;
+    }
+    if has_immediate_win(rules, board, color) {
+        return PatternScore::FIVE;
+    }
+    evaluate(rules, board, color)
+}
+
+fn summarize(mov: Pos, outcomes: &[i32]) -> SwindleCandidate {
+    if outcomes.is_empty() {
+        return SwindleCandidate {
+            mov,
+            error_chance: 0.0,
+            replies: 0,
+            worst_outcome: 0,
+            spread: 0.0,
+        };
+    }
+    let n = outcomes.len() as f64;
+    let mistakes = outcomes.iter().filter(|&&s| s >= MISTAKE_THRESHOLD).count();
+    let mean = outcomes.iter().map(|&s| f64::from(s)).sum::<f64>() / n;
+    let variance = outcomes
+        .iter()
+        .map(|&s| (f64::from(s) - mean) * (f64::from(s) - mean))
+        .sum::<f64>()
+        / n;
+
+    SwindleCandidate {
+        mov,
+        error_chance: mistakes as f64 / n,
+        replies: outcomes.len(),
+        worst_outcome: outcomes.iter().copied().min().unwrap_or(0),
+        spread: super::sqrt(variance),
+    }
+}
+
+/// Legal moves within 2 cells of any stone, best static evaluation first.
+fn plausible_moves(rules: RuleSet, board: &mut Board, color: Stone, limit: usize) -> Vec<Pos> {
+    let legal: Vec<Pos> = neighborhood(board, 2)
+        .into_iter()
+        .filter(|&p| is_valid_move(rules, board, p, color))
+        .collect();
+    let mut scored: Vec<(Pos, i32)> = legal
+        .into_iter()
+        .map(|p| {
+            board.place_stone(p, color);
+            let cap_info = execute_captures_fast(rules, board, p, color);
+            let score = evaluate(rules, board, color);
+            undo_captures(board, color, &cap_info);
+            board.remove_stone(p);
+            (p, score)
+        })
+        .collect();
+    scored.sort_unstable_by_key(|&(_, score)| core::cmp::Reverse(score));
+    scored.into_iter().take(limit).map(|(p, _)| p).collect()
+}
+
+/// Empty cells within `radius` of any stone.
+fn neighborhood(board: &Board, radius: i32) -> Vec<Pos> {
+    let mut seen = [false; crate::board::TOTAL_CELLS];
+    let mut cells = Vec::new();
+    for (pos, _) in board.occupied() {
+        for dr in -radius..=radius {
+            for dc in -radius..=radius {
+                let r = i32::from(pos.row) + dr;
+                let c = i32::from(pos.col) + dc;
+                if !Pos::is_valid(r, c) {
+                    continue;
+                }
+                #[allow(clippy::cast_sign_loss)]
+                let p = Pos::new(r as u8, c as u8);
+                if !seen[p.to_index()] && board.is_empty(p) {
+                    seen[p.to_index()] = true;
+                    cells.push(p);
+                }
+            }
+        }
+    }
+    cells
+}
+
+/// Whether `color` has a move that wins on the spot (unbreakable five or 5th capture).
+fn has_immediate_win(rules: RuleSet, board: &mut Board, color: Stone) -> bool {
+    let near_capture_win = board.captures_to_win(rules, color) <= 1;
+    for p in neighborhood(board, 1) {
+        if !is_valid_move(rules, board, p, color) {
+            continue;
+        }
+        board.place_stone(p, color);
+        let cap_info = execute_captures_fast(rules, board, p, color);
+        let wins =
+            (near_capture_win && board.captures_to_win(rules, color) == 0) || wins_with_five(rules, board, p, color);
+        undo_captures(board, color, &cap_info);
+        board.remove_stone(p);
+        if wins {
+            return true;
+        }
+    }
+    false
+}
+
+fn wins_with_five(rules: RuleSet, board: &Board, pos: Pos, color: Stone) -> bool {
+    has_five_at_pos(rules, board, pos, color)
+        && find_five_line_at_pos(rules, board, pos, color)
+            .is_some_and(|five| !can_break_five_by_capture(rules, board, &five, color))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_counts_mistakes() {
+        let c = summarize(
+            Pos::new(9, 9),
+            &[-PatternScore::FIVE, PatternScore::FIVE, -PatternScore::FIVE, PatternScore::OPEN_FOUR],
+        );
+        assert_eq!(c.replies, 4);
+        assert!((c.error_chance - 0.5).abs() < 1e-9);
+        assert_eq!(c.worst_outcome, -PatternScore::FIVE);
+        assert!(c.spread > 0.0);
+    }
+
+    #[test]
+    fn test_swindle_prefers_trap_over_passive_defense() {
+        // White threatens to win; Black's own four-in-waiting means any
+        // reply that ignores it loses on the spot.
+        let mut board = Board::new();
+        for c in 5..8 {
+            board.place_stone(Pos::new(9, c), Stone::Black);
+        }
+        for c in 5..8 {
+            board.place_stone(Pos::new(12, c), Stone::White);
+        }
+        board.place_stone(Pos::new(3, 3), Stone::White);
+
+        let best = find_swindle_move(RuleSet::NINUKI, &board, Stone::Black).expect("should find a trappy move");
+        assert!(best.error_chance > 0.0);
+        assert!(best.replies > 0);
+        // The trap extends Black's own three
+        assert_eq!(best.mov.row, 9, "expected a move on Black's line, got {:?}", best.mov);
+    }
+
+    #[test]
+    fn test_resistance_prefers_unproven_wins() {
+        // White's open three becomes an open four unless Black blocks it;
+        // only a block keeps White's search from proving the win
+        let mut board = Board::new();
+        for c in 5..8 {
+            board.place_stone(Pos::new(12, c), Stone::White);
+        }
+        board.place_stone(Pos::new(6, 6), Stone::Black);
+        board.place_stone(Pos::new(3, 14), Stone::Black);
+
+        let best = find_resistant_move(RuleSet::NINUKI, &board, Stone::Black, 5_000).expect("candidates exist");
+        assert_eq!(best.opponent_mate_in, None, "{:?}", best);
+        assert_eq!(best.candidate.mov.row, 12, "expected a block, got {:?}", best.candidate.mov);
+
+        let candidate = |error_chance| SwindleCandidate {
+            mov: Pos::new(0, 0),
+            error_chance,
+            replies: 1,
+            worst_outcome: 0,
+            spread: 0.0,
+        };
+        let quick = Resistance { candidate: candidate(0.5), opponent_mate_in: Some(1), opponent_nodes: 10 };
+        let long = Resistance { candidate: candidate(0.0), opponent_mate_in: Some(3), opponent_nodes: 10 };
+        let costly = Resistance { opponent_nodes: 500, ..long.clone() };
+        assert!(long.cmp_difficulty(&quick).is_gt());
+        assert!(costly.cmp_difficulty(&long).is_gt());
+        assert!(best.cmp_difficulty(&costly).is_gt());
+    }
+
+    #[test]
+    fn test_outcome_detects_opponent_five() {
+        let mut board = Board::new();
+        for c in 0..5 {
+            board.place_stone(Pos::new(0, c), Stone::White);
+        }
+        let outcome = outcome_after_reply(RuleSet::NINUKI, &mut board, Pos::new(0, 4), Stone::Black);
+        assert_eq!(outcome, -PatternScore::FIVE);
+    }
+}
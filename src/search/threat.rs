@@ -7,12 +7,16 @@
 //! These are powerful pruning techniques that can find forced wins much faster
 //! than regular alpha-beta search by only considering forcing moves.
 
-use crate::board::{Board, Pos, Stone, BOARD_SIZE};
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::board::{Bitboard, Board, Pos, Stone, BOARD_SIZE};
 use crate::rules::{
-    can_break_five_by_capture, execute_captures_fast, find_five_positions,
-    get_captured_positions, has_five_at_pos, is_valid_move, undo_captures,
+    can_break_five_by_capture, captured_positions_fast, execute_captures_fast, find_five_positions,
+    has_five_at_pos, is_valid_move, undo_captures, RuleSet,
 };
 
+use super::proof::ProofLine;
+
 /// Direction vectors for line checking (4 directions)
 const DIRECTIONS: [(i32, i32); 4] = [
     (0, 1),  // Horizontal
@@ -28,6 +32,8 @@ pub struct ThreatResult {
     pub winning_sequence: Vec<Pos>,
     /// Whether a forced win was found
     pub found: bool,
+    /// The VCF win with the defender's forced replies
+    pub proof: Option<ProofLine>,
 }
 
 impl ThreatResult {
@@ -37,6 +43,7 @@ impl ThreatResult {
         Self {
             winning_sequence: Vec::new(),
             found: false,
+            proof: None,
         }
     }
 
@@ -46,6 +53,7 @@ impl ThreatResult {
         Self {
             winning_sequence: sequence,
             found: true,
+            proof: None,
         }
     }
 }
@@ -58,6 +66,8 @@ pub struct ThreatSearcher {
     max_vct_depth: u8,
     /// Node counter for statistics
     nodes: u64,
+    /// Rules the threats are played by
+    rules: RuleSet,
 }
 
 impl ThreatSearcher {
@@ -67,6 +77,7 @@ impl ThreatSearcher {
             max_vcf_depth: 30,
             max_vct_depth: 20,
             nodes: 0,
+            rules: RuleSet::NINUKI,
         }
     }
 
@@ -76,9 +87,21 @@ impl ThreatSearcher {
             max_vcf_depth: vcf_depth,
             max_vct_depth: vct_depth,
             nodes: 0,
+            rules: RuleSet::NINUKI,
         }
     }
 
+    /// Search by `rules` from now on.
+    pub fn set_rules(&mut self, rules: RuleSet) {
+        self.rules = rules;
+    }
+
+    /// Rules the searches play by, [`RuleSet::NINUKI`] unless set
+    #[must_use]
+    pub fn rules(&self) -> RuleSet {
+        self.rules
+    }
+
     /// Search for VCF (Victory by Continuous Fours)
     ///
     /// VCF finds winning sequences where each move creates a four (4 in a row
@@ -97,7 +120,8 @@ impl ThreatSearcher {
         let mut work_board = board.clone();
 
         if self.vcf_search_mut(&mut work_board, color, 0, &mut sequence) {
-            ThreatResult::found(sequence)
+            let proof = self.vcf_proof(&mut work_board, color, &sequence);
+            ThreatResult { proof: Some(proof), ..ThreatResult::found(sequence) }
         } else {
             ThreatResult::not_found()
         }
@@ -123,16 +147,16 @@ impl ThreatSearcher {
         for threat_move in threats {
             // Make the threat move
             board.place_stone(threat_move, color);
-            let cap_info = execute_captures_fast(board, threat_move, color);
+            let cap_info = execute_captures_fast(self.rules, board, threat_move, color);
 
             sequence.push(threat_move);
 
             // Check for immediate win by five-in-a-row
             let mut found_win = false;
             let mut is_breakable_five = false;
-            if has_five_at_pos(board, threat_move, color) {
-                if let Some(five) = find_five_positions(board, color) {
-                    if !can_break_five_by_capture(board, &five, color) {
+            if has_five_at_pos(self.rules, board, threat_move, color) {
+                if let Some(five) = find_five_positions(self.rules, board, color) {
+                    if !can_break_five_by_capture(self.rules, board, &five, color) {
                         found_win = true;
                     } else {
                         // Breakable five: opponent can capture to destroy it.
@@ -144,8 +168,8 @@ impl ThreatSearcher {
                 }
             }
 
-            // Check for capture win (5 pairs = 10 stones)
-            if !found_win && board.captures(color) >= 5 {
+            // Check for capture win
+            if !found_win && board.captures_to_win(self.rules, color) == 0 {
                 found_win = true;
             }
 
@@ -195,11 +219,10 @@ impl ThreatSearcher {
             }
 
             // If only one defense, opponent is forced to play there
-            if defenses.len() == 1 {
-                let defense = defenses[0];
+            if let (1, Some(defense)) = (defenses.count(), defenses.iter_ones().next()) {
                 let defender = color.opponent();
                 board.place_stone(defense, defender);
-                let def_cap = execute_captures_fast(board, defense, defender);
+                let def_cap = execute_captures_fast(self.rules, board, defense, defender);
 
                 let result = self.vcf_search_mut(board, color, depth + 1, sequence);
 
@@ -225,6 +248,32 @@ impl ThreatSearcher {
         false
     }
 
+    /// `color`'s VCF `sequence` with the single defense to each four (the
+    /// one the search assumed) between the moves
+    fn vcf_proof(&self, board: &mut Board, color: Stone, sequence: &[Pos]) -> ProofLine {
+        let defender = color.opponent();
+        let mut moves = Vec::with_capacity(sequence.len() * 2);
+        let mut undo = Vec::with_capacity(sequence.len() * 2);
+        for (i, &mov) in sequence.iter().enumerate() {
+            moves.push(mov);
+            if i + 1 == sequence.len() {
+                break;
+            }
+            board.place_stone(mov, color);
+            undo.push((mov, color, execute_captures_fast(self.rules, board, mov, color)));
+            if let Some(defense) = self.find_defense_moves(board, mov, color).iter_ones().next() {
+                moves.push(defense);
+                board.place_stone(defense, defender);
+                undo.push((defense, defender, execute_captures_fast(self.rules, board, defense, defender)));
+            }
+        }
+        for (pos, stone, cap) in undo.iter().rev() {
+            undo_captures(board, *stone, cap);
+            board.remove_stone(*pos);
+        }
+        ProofLine::new(color, moves)
+    }
+
     /// Find all moves that create a four or five (winning move or forcing move)
     ///
     /// This prioritizes winning moves (five) over forcing moves (four).
@@ -235,7 +284,7 @@ impl ThreatSearcher {
         for r in 0..BOARD_SIZE {
             for c in 0..BOARD_SIZE {
                 let pos = Pos::new(r as u8, c as u8);
-                if !is_valid_move(board, pos, color) {
+                if !is_valid_move(self.rules, board, pos, color) {
                     continue;
                 }
 
@@ -253,45 +302,11 @@ impl ThreatSearcher {
         winning_moves
     }
 
-    /// Check if placing at pos creates five or more in a row
+    /// Check if placing at pos creates a winning five (five or more in a
+    /// row, not dead under the Caro rule)
     fn creates_five_or_more(&self, board: &Board, pos: Pos, color: Stone) -> bool {
-        for &(dr, dc) in &DIRECTIONS {
-            let mut count = 1; // The stone we're placing
-
-            // Scan positive direction
-            let mut r = pos.row as i32 + dr;
-            let mut c = pos.col as i32 + dc;
-            while Pos::is_valid(r, c) {
-                let p = Pos::new(r as u8, c as u8);
-                if board.get(p) == color {
-                    count += 1;
-                } else {
-                    break;
-                }
-                r += dr;
-                c += dc;
-            }
-
-            // Scan negative direction
-            r = pos.row as i32 - dr;
-            c = pos.col as i32 - dc;
-            while Pos::is_valid(r, c) {
-                let p = Pos::new(r as u8, c as u8);
-                if board.get(p) == color {
-                    count += 1;
-                } else {
-                    break;
-                }
-                r -= dr;
-                c -= dc;
-            }
-
-            if count >= 5 {
-                return true;
-            }
-        }
-
-        false
+        // has_five_at_pos counts the stone at pos without reading the cell
+        has_five_at_pos(self.rules, board, pos, color)
     }
 
     /// Check if placing at pos creates a four (4 in a row with at least one open end)
@@ -348,19 +363,23 @@ impl ThreatSearcher {
     /// Defense includes:
     /// 1. Blocking moves at the ends of the four
     /// 2. Capture moves that break the four (remove stones from the four pattern)
-    /// 3. ANY capture move when defender has 3+ captures (near capture-win)
-    fn find_defense_moves(&self, board: &Board, threat_move: Pos, attacker: Stone) -> Vec<Pos> {
+    /// 3. ANY capture move when defender is 2 pairs or less from the capture win
+    ///
+    /// Sets rather than lists, so the VCF inner loop doesn't allocate; they
+    /// iterate in index order.
+    fn find_defense_moves(&self, board: &Board, threat_move: Pos, attacker: Stone) -> Bitboard {
         let defender = attacker.opponent();
-        let mut defenses = Vec::new();
-        let mut four_positions: Vec<Pos> = Vec::new();
-        let defender_captures = board.captures(defender);
+        let mut defenses = Bitboard::new();
+        let mut four_positions = Bitboard::new();
+        let defender_to_win = board.captures_to_win(self.rules, defender);
 
         // Find blocking moves at the extension points of the four
         // Also collect the positions of the four-pattern stones
         for &(dr, dc) in &DIRECTIONS {
             let mut count = 1;
-            let mut extension_points = Vec::new();
-            let mut line_positions = vec![threat_move];
+            let mut extension_points = Bitboard::new();
+            let mut line_positions = Bitboard::new();
+            line_positions.set(threat_move);
 
             // Scan positive direction
             let mut r = threat_move.row as i32 + dr;
@@ -370,10 +389,10 @@ impl ThreatSearcher {
                 match board.get(p) {
                     s if s == attacker => {
                         count += 1;
-                        line_positions.push(p);
+                        line_positions.set(p);
                     }
                     Stone::Empty => {
-                        extension_points.push(p);
+                        extension_points.set(p);
                         break;
                     }
                     _ => break,
@@ -390,10 +409,10 @@ impl ThreatSearcher {
                 match board.get(p) {
                     s if s == attacker => {
                         count += 1;
-                        line_positions.push(p);
+                        line_positions.set(p);
                     }
                     Stone::Empty => {
-                        extension_points.push(p);
+                        extension_points.set(p);
                         break;
                     }
                     _ => break,
@@ -404,48 +423,42 @@ impl ThreatSearcher {
 
             // If this direction has a four, the extension points are defenses
             if count == 4 {
-                for ext in extension_points {
-                    if is_valid_move(board, ext, defender) {
-                        defenses.push(ext);
+                for ext in extension_points.iter_ones() {
+                    if is_valid_move(self.rules, board, ext, defender) {
+                        defenses.set(ext);
                     }
                 }
                 // Collect the four-pattern positions for capture validation
-                four_positions.extend(line_positions);
+                four_positions |= line_positions;
             }
         }
 
-        // Deduplicate four_positions
-        four_positions.sort();
-        four_positions.dedup();
-
         // Find capture moves as defenses
         // In Ninuki-renju, the defender can ignore the four and capture instead:
         // - Captures that break the four (remove stones from the four pattern)
-        // - ANY capture when defender has 3+ captures (closing in on capture-win)
-        let capture_is_strategic = defender_captures >= 3;
+        // - ANY capture when defender is 2 pairs from the capture win or closer
+        let capture_is_strategic = defender_to_win <= 2;
         for r in 0..BOARD_SIZE {
             for c in 0..BOARD_SIZE {
                 let pos = Pos::new(r as u8, c as u8);
-                if !is_valid_move(board, pos, defender) {
+                if !is_valid_move(self.rules, board, pos, defender) {
                     continue;
                 }
 
-                let captured = get_captured_positions(board, pos, defender);
-                if !captured.is_empty() {
+                let captured = captured_positions_fast(self.rules, board, pos, defender);
+                if captured.count > 0 {
                     // Add as defense if:
                     // 1. Capture breaks the four pattern, OR
-                    // 2. Defender has 3+ captures (any capture is strategically significant)
+                    // 2. Defender is near the capture win (any capture is strategically significant)
                     if capture_is_strategic
-                        || captured.iter().any(|cap| four_positions.contains(cap))
+                        || captured.positions[..captured.count as usize].iter().any(|&cap| four_positions.get(cap))
                     {
-                        defenses.push(pos);
+                        defenses.set(pos);
                     }
                 }
             }
         }
 
-        defenses.sort();
-        defenses.dedup();
         defenses
     }
 
@@ -468,7 +481,8 @@ impl ThreatSearcher {
 
         // First try VCF (faster and more forcing)
         if self.vcf_search_mut(&mut work_board, color, 0, &mut sequence) {
-            return ThreatResult::found(sequence);
+            let proof = self.vcf_proof(&mut work_board, color, &sequence);
+            return ThreatResult { proof: Some(proof), ..ThreatResult::found(sequence) };
         }
 
         sequence.clear();
@@ -499,16 +513,16 @@ impl ThreatSearcher {
         for threat_move in threats {
             // Make the threat move
             board.place_stone(threat_move, color);
-            let cap_info = execute_captures_fast(board, threat_move, color);
+            let cap_info = execute_captures_fast(self.rules, board, threat_move, color);
 
             sequence.push(threat_move);
 
             // Check for immediate win
             let mut found_win = false;
             let mut is_breakable_five = false;
-            if has_five_at_pos(board, threat_move, color) {
-                if let Some(five) = find_five_positions(board, color) {
-                    if !can_break_five_by_capture(board, &five, color) {
+            if has_five_at_pos(self.rules, board, threat_move, color) {
+                if let Some(five) = find_five_positions(self.rules, board, color) {
+                    if !can_break_five_by_capture(self.rules, board, &five, color) {
                         found_win = true;
                     } else {
                         is_breakable_five = true;
@@ -516,7 +530,7 @@ impl ThreatSearcher {
                 }
             }
 
-            if !found_win && board.captures(color) >= 5 {
+            if !found_win && board.captures_to_win(self.rules, color) == 0 {
                 found_win = true;
             }
 
@@ -573,9 +587,9 @@ impl ThreatSearcher {
             // For VCT, we need to beat ALL possible defenses
             let mut all_defenses_beaten = true;
             let defender = color.opponent();
-            for defense in &defenses {
-                board.place_stone(*defense, defender);
-                let def_cap = execute_captures_fast(board, *defense, defender);
+            for defense in defenses.iter_ones() {
+                board.place_stone(defense, defender);
+                let def_cap = execute_captures_fast(self.rules, board, defense, defender);
 
                 // Recursively try to find a win against this defense
                 let mut sub_sequence = sequence.clone();
@@ -583,7 +597,7 @@ impl ThreatSearcher {
 
                 // Unmake defense
                 undo_captures(board, defender, &def_cap);
-                board.remove_stone(*defense);
+                board.remove_stone(defense);
 
                 if !beaten {
                     all_defenses_beaten = false;
@@ -616,7 +630,7 @@ impl ThreatSearcher {
         for r in 0..BOARD_SIZE {
             for c in 0..BOARD_SIZE {
                 let pos = Pos::new(r as u8, c as u8);
-                if !is_valid_move(board, pos, color) {
+                if !is_valid_move(self.rules, board, pos, color) {
                     continue;
                 }
 
@@ -691,15 +705,18 @@ impl ThreatSearcher {
     /// Defense includes:
     /// 1. Blocking moves at the ends of the threat line
     /// 2. Capture moves that break the threat (only captures that remove stones from the threat pattern)
-    fn find_threat_defenses(&self, board: &Board, threat_move: Pos, attacker: Stone) -> Vec<Pos> {
+    ///
+    /// A set, like [`find_defense_moves`](Self::find_defense_moves).
+    fn find_threat_defenses(&self, board: &Board, threat_move: Pos, attacker: Stone) -> Bitboard {
         let defender = attacker.opponent();
-        let mut defenses = Vec::new();
-        let mut threat_positions: Vec<Pos> = Vec::new();
+        let mut defenses = Bitboard::new();
+        let mut threat_positions = Bitboard::new();
 
         // Find blocking positions along each direction
         // Also collect the positions of the threat-pattern stones
         for &(dr, dc) in &DIRECTIONS {
-            let mut line_positions = vec![threat_move];
+            let mut line_positions = Bitboard::new();
+            line_positions.set(threat_move);
             let mut line_count = 1;
 
             // Check both extension directions from the threat
@@ -713,7 +730,7 @@ impl ThreatSearcher {
                     if board.get(next) == attacker {
                         r += dr * sign;
                         c += dc * sign;
-                        line_positions.push(next);
+                        line_positions.set(next);
                         line_count += 1;
                     } else {
                         break;
@@ -725,42 +742,34 @@ impl ThreatSearcher {
                 let def_c = c + dc * sign;
                 if Pos::is_valid(def_r, def_c) {
                     let p = Pos::new(def_r as u8, def_c as u8);
-                    if board.get(p) == Stone::Empty && is_valid_move(board, p, defender) {
-                        defenses.push(p);
+                    if board.get(p) == Stone::Empty && is_valid_move(self.rules, board, p, defender) {
+                        defenses.set(p);
                     }
                 }
             }
 
             // If this direction has a meaningful threat (3+ stones), collect positions
             if line_count >= 3 {
-                threat_positions.extend(line_positions);
+                threat_positions |= line_positions;
             }
         }
 
-        // Deduplicate threat_positions
-        threat_positions.sort();
-        threat_positions.dedup();
-
         // Add capture defenses that actually break the threat
         // Only include captures that remove stones that are part of the threat pattern
         for r in 0..BOARD_SIZE {
             for c in 0..BOARD_SIZE {
                 let pos = Pos::new(r as u8, c as u8);
-                if !is_valid_move(board, pos, defender) {
+                if !is_valid_move(self.rules, board, pos, defender) {
                     continue;
                 }
-                let captured = get_captured_positions(board, pos, defender);
-                if !captured.is_empty() {
-                    // Only add as defense if any captured stone is part of the threat pattern
-                    if captured.iter().any(|cap| threat_positions.contains(cap)) {
-                        defenses.push(pos);
-                    }
+                let captured = captured_positions_fast(self.rules, board, pos, defender);
+                // Only add as defense if any captured stone is part of the threat pattern
+                if captured.positions[..captured.count as usize].iter().any(|&cap| threat_positions.get(cap)) {
+                    defenses.set(pos);
                 }
             }
         }
 
-        defenses.sort();
-        defenses.dedup();
         defenses
     }
 
@@ -995,7 +1004,7 @@ mod tests {
         let defenses = searcher.find_defense_moves(&board, Pos::new(9, 5), Stone::Black);
 
         // White should be able to block at (9, 4) or (9, 9)
-        assert!(defenses.contains(&Pos::new(9, 4)) || defenses.contains(&Pos::new(9, 9)));
+        assert!(defenses.get(Pos::new(9, 4)) || defenses.get(Pos::new(9, 9)));
     }
 
     #[test]
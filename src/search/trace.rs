@@ -0,0 +1,379 @@
+//! Search tree tracing (`trace` feature)
+//!
+//! [`Searcher::search_traced`](super::Searcher::search_traced) records every
+//! node the search visits, up to a node budget: the move that led there,
+//! depth, alpha-beta window, returned score and why the node ended (TT
+//! cutoff, null move, futility, ...). The trace serializes to JSON and
+//! [`SearchTrace::render`] prints it as an indented tree, which is what the
+//! `gomoku-trace` viewer shows.
+//!
+//! ```
+//! use gomoku::search::Searcher;
+//! use gomoku::{Board, Pos, Stone};
+//!
+//! let mut board = Board::new();
+//! board.place_stone(Pos::new(9, 9), Stone::Black);
+//! let mut searcher = Searcher::with_threads(16, 1);
+//! let (result, trace) = searcher.search_traced(&board, Stone::White, 3, 5_000);
+//! assert!(result.best_move.is_some());
+//! assert_eq!(trace.roots().count(), 3); // one root per iteration
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::board::{Pos, Stone};
+use crate::engine::pos_to_notation;
+
+/// What kind of search produced a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeKind {
+    /// Root of one iterative-deepening iteration
+    Root,
+    /// Full-width alpha-beta node
+    AlphaBeta,
+    /// Quiescence node (forcing moves only)
+    Quiescence,
+    /// Null-move probe (the side to move passed)
+    NullMove,
+    /// Move skipped without being searched
+    Pruned,
+}
+
+/// Why a node returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// Score inside the window
+    Exact,
+    /// Score at or above beta
+    FailHigh,
+    /// Score at or below alpha
+    FailLow,
+    /// Game already over (five or capture win)
+    Terminal,
+    /// Answered from the transposition table
+    TtCutoff,
+    /// Quiescence stand-pat at or above beta
+    StandPat,
+    /// Reverse futility pruning (static eval far above beta)
+    ReverseFutility,
+    /// Razoring (quiescence confirmed eval far below alpha)
+    Razoring,
+    /// Null-move pruning
+    NullMove,
+    /// Futility pruning skipped this move
+    Futility,
+    /// Late move pruning skipped this move
+    LateMove,
+    /// Time or node limit hit; the score is meaningless
+    Stopped,
+}
+
+impl Outcome {
+    /// Name as used in the JSON dump
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Exact => "exact",
+            Self::FailHigh => "fail_high",
+            Self::FailLow => "fail_low",
+            Self::Terminal => "terminal",
+            Self::TtCutoff => "tt_cutoff",
+            Self::StandPat => "stand_pat",
+            Self::ReverseFutility => "reverse_futility",
+            Self::Razoring => "razoring",
+            Self::NullMove => "null_move",
+            Self::Futility => "futility",
+            Self::LateMove => "late_move",
+            Self::Stopped => "stopped",
+        }
+    }
+}
+
+/// One visited (or skipped) node
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TraceNode {
+    /// Index of the parent in [`SearchTrace::nodes`]; `None` for roots
+    pub parent: Option<usize>,
+    pub kind: NodeKind,
+    /// Move that led here (`None` for roots)
+    pub mov: Option<Pos>,
+    /// Side to move at this node
+    pub color: Stone,
+    /// Distance from the root
+    pub ply: i32,
+    /// Remaining depth (quiescence depth counts up from 0)
+    pub depth: i8,
+    pub alpha: i32,
+    pub beta: i32,
+    /// Returned score, from `color`'s point of view; `None` for skipped moves
+    pub score: Option<i32>,
+    pub outcome: Option<Outcome>,
+}
+
+/// A recorded search tree, in visiting order
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct SearchTrace {
+    /// Maximum number of nodes recorded
+    pub budget: usize,
+    /// Whether the search visited more nodes than the budget allowed
+    pub truncated: bool,
+    pub nodes: Vec<TraceNode>,
+}
+
+impl SearchTrace {
+    /// Parse a trace written by [`SearchTrace::to_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the JSON error message.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| e.to_string())
+    }
+
+    /// Serialize the whole trace as compact JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("trace serializes")
+    }
+
+    /// Root nodes (one per iteration), as indices into `nodes`.
+    pub fn roots(&self) -> impl Iterator<Item = usize> + '_ {
+        self.nodes.iter().enumerate().filter(|(_, n)| n.parent.is_none()).map(|(i, _)| i)
+    }
+
+    /// Number of nodes per outcome.
+    #[must_use]
+    pub fn outcome_counts(&self) -> BTreeMap<Outcome, usize> {
+        let mut counts = BTreeMap::new();
+        for outcome in self.nodes.iter().filter_map(|n| n.outcome) {
+            *counts.entry(outcome).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Indented text tree, one node per line, down to `max_ply` below the
+    /// roots (`None` for everything).
+    #[must_use]
+    pub fn render(&self, max_ply: Option<i32>) -> String {
+        let mut children = vec![Vec::new(); self.nodes.len()];
+        for (i, node) in self.nodes.iter().enumerate() {
+            if let Some(parent) = node.parent {
+                children[parent].push(i);
+            }
+        }
+
+        let mut out = String::new();
+        let mut stack: Vec<(usize, usize)> = self.roots().map(|i| (i, 0)).collect();
+        stack.reverse();
+        while let Some((i, indent)) = stack.pop() {
+            let node = &self.nodes[i];
+            let _ = writeln!(out, "{}{}", "  ".repeat(indent), format_node(node));
+            if max_ply.is_some_and(|max| node.ply >= max) {
+                continue;
+            }
+            stack.extend(children[i].iter().rev().map(|&c| (c, indent + 1)));
+        }
+        if self.truncated {
+            let _ = writeln!(out, "... truncated at {} nodes", self.budget);
+        }
+        out
+    }
+}
+
+fn format_node(node: &TraceNode) -> String {
+    let mov = node.mov.map_or_else(|| "root".to_string(), pos_to_notation);
+    let side = if node.color == Stone::Black { 'B' } else { 'W' };
+    let kind = match node.kind {
+        NodeKind::Root | NodeKind::AlphaBeta => "",
+        NodeKind::Quiescence => " qs",
+        NodeKind::NullMove => " null",
+        NodeKind::Pruned => " pruned",
+    };
+    let score = node.score.map_or_else(|| "-".to_string(), |s| s.to_string());
+    let outcome = node.outcome.map_or("", Outcome::as_str);
+    format!(
+        "{}{} {} d{} [{}, {}] {} {}",
+        mov, kind, side, node.depth, node.alpha, node.beta, score, outcome
+    )
+}
+
+/// Records nodes as the search enters and leaves them.
+pub(crate) struct TraceRecorder {
+    trace: SearchTrace,
+    /// Open nodes; `None` for nodes past the budget
+    stack: Vec<Option<usize>>,
+    /// Kind override for the next node entered
+    next_kind: Option<NodeKind>,
+}
+
+impl TraceRecorder {
+    pub(crate) fn new(budget: usize) -> Self {
+        Self {
+            trace: SearchTrace { budget, truncated: false, nodes: Vec::new() },
+            stack: Vec::new(),
+            next_kind: None,
+        }
+    }
+
+    pub(crate) fn finish(self) -> SearchTrace {
+        self.trace
+    }
+
+    /// Label the next node entered (e.g. a null-move probe).
+    pub(crate) fn label_next(&mut self, kind: NodeKind) {
+        self.next_kind = Some(kind);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn enter(
+        &mut self,
+        kind: NodeKind,
+        mov: Option<Pos>,
+        color: Stone,
+        ply: i32,
+        depth: i8,
+        alpha: i32,
+        beta: i32,
+    ) {
+        let kind = self.next_kind.take().unwrap_or(kind);
+        let id = self.push(kind, mov, color, ply, depth, alpha, beta);
+        self.stack.push(id);
+    }
+
+    /// Record why the current node is about to return.
+    pub(crate) fn cut(&mut self, outcome: Outcome) {
+        if let Some(Some(id)) = self.stack.last() {
+            self.trace.nodes[*id].outcome = Some(outcome);
+        }
+    }
+
+    /// Record a move the current node skipped without searching.
+    pub(crate) fn skip(&mut self, mov: Pos, color: Stone, ply: i32, depth: i8, outcome: Outcome) {
+        if let Some(id) = self.push(NodeKind::Pruned, Some(mov), color, ply, depth, 0, 0) {
+            self.trace.nodes[id].outcome = Some(outcome);
+        }
+    }
+
+    /// Leave the current node with its returned score.
+    pub(crate) fn exit(&mut self, score: i32, stopped: bool) {
+        let Some(Some(id)) = self.stack.pop() else {
+            return;
+        };
+        let node = &mut self.trace.nodes[id];
+        node.score = Some(score);
+        node.outcome = if stopped {
+            Some(Outcome::Stopped)
+        } else {
+            node.outcome.or(Some(if score >= node.beta {
+                Outcome::FailHigh
+            } else if score <= node.alpha {
+                Outcome::FailLow
+            } else {
+                Outcome::Exact
+            }))
+        };
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn push(
+        &mut self,
+        kind: NodeKind,
+        mov: Option<Pos>,
+        color: Stone,
+        ply: i32,
+        depth: i8,
+        alpha: i32,
+        beta: i32,
+    ) -> Option<usize> {
+        // Children of unrecorded nodes are dropped too
+        let parent = match self.stack.last() {
+            Some(None) => return None,
+            Some(Some(parent)) => Some(*parent),
+            None => None,
+        };
+        if self.trace.nodes.len() >= self.trace.budget {
+            self.trace.truncated = true;
+            return None;
+        }
+        self.trace.nodes.push(TraceNode {
+            parent,
+            kind,
+            mov,
+            color,
+            ply,
+            depth,
+            alpha,
+            beta,
+            score: None,
+            outcome: None,
+        });
+        Some(self.trace.nodes.len() - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorder_builds_tree_and_classifies() {
+        let mut rec = TraceRecorder::new(10);
+        rec.enter(NodeKind::Root, None, Stone::Black, 0, 2, -100, 100);
+        rec.enter(NodeKind::AlphaBeta, Some(Pos::new(9, 9)), Stone::White, 1, 1, -100, 100);
+        rec.exit(-150, false);
+        rec.skip(Pos::new(9, 10), Stone::Black, 0, 2, Outcome::Futility);
+        rec.label_next(NodeKind::NullMove);
+        rec.enter(NodeKind::AlphaBeta, None, Stone::White, 1, 0, -100, -99);
+        rec.cut(Outcome::TtCutoff);
+        rec.exit(-100, false);
+        rec.exit(150, false);
+        let trace = rec.finish();
+
+        assert_eq!(trace.nodes.len(), 4);
+        assert_eq!(trace.roots().collect::<Vec<_>>(), vec![0]);
+        assert_eq!(trace.nodes[0].outcome, Some(Outcome::FailHigh));
+        assert_eq!(trace.nodes[1].outcome, Some(Outcome::FailLow));
+        assert_eq!(trace.nodes[2].kind, NodeKind::Pruned);
+        assert_eq!(trace.nodes[2].parent, Some(0));
+        assert_eq!(trace.nodes[3].kind, NodeKind::NullMove);
+        assert_eq!(trace.nodes[3].outcome, Some(Outcome::TtCutoff));
+    }
+
+    #[test]
+    fn test_recorder_respects_budget() {
+        let mut rec = TraceRecorder::new(2);
+        rec.enter(NodeKind::Root, None, Stone::Black, 0, 3, -100, 100);
+        rec.enter(NodeKind::AlphaBeta, Some(Pos::new(1, 1)), Stone::White, 1, 2, -100, 100);
+        rec.enter(NodeKind::AlphaBeta, Some(Pos::new(2, 2)), Stone::Black, 2, 1, -100, 100);
+        rec.enter(NodeKind::AlphaBeta, Some(Pos::new(3, 3)), Stone::White, 3, 0, -100, 100);
+        rec.exit(0, false);
+        rec.exit(0, false);
+        rec.exit(0, true);
+        rec.exit(0, false);
+        let trace = rec.finish();
+
+        assert!(trace.truncated);
+        assert_eq!(trace.nodes.len(), 2);
+        assert_eq!(trace.nodes[1].outcome, Some(Outcome::Stopped));
+        assert!(trace.render(None).ends_with("... truncated at 2 nodes\n"));
+    }
+
+    #[test]
+    fn test_trace_json_round_trip() {
+        let mut rec = TraceRecorder::new(10);
+        rec.enter(NodeKind::Root, None, Stone::Black, 0, 1, -100, 100);
+        rec.skip(Pos::new(0, 0), Stone::Black, 0, 1, Outcome::LateMove);
+        rec.exit(5, false);
+        let trace = rec.finish();
+
+        let restored = SearchTrace::from_json(&trace.to_json()).unwrap();
+        assert_eq!(restored.nodes.len(), 2);
+        assert_eq!(restored.nodes[1].mov, Some(Pos::new(0, 0)));
+        assert_eq!(restored.outcome_counts()[&Outcome::LateMove], 1);
+        assert_eq!(restored.render(None), trace.render(None));
+        assert!(trace.to_json().contains("\"late_move\""));
+    }
+}
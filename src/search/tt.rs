@@ -21,9 +21,17 @@
 //! }
 //! ```
 
-use std::sync::atomic::{AtomicU64, Ordering};
-
+use core::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+#[cfg(feature = "std")]
+use crate::board::BOARD_SIZE;
 use crate::board::Pos;
+#[cfg(feature = "std")]
+use crate::rules::RuleSet;
 
 /// Entry type for score interpretation
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -51,13 +59,33 @@ pub struct TTEntry {
     pub best_move: Option<Pos>,
 }
 
+/// How [`TranspositionTable`] stores an entry: 16 bytes, `Option` included.
+#[derive(Debug, Clone, Copy)]
+struct PackedEntry {
+    /// Upper 32 bits of the Zobrist hash; see [`hash_key`]
+    key: u32,
+    score: i32,
+    depth: i8,
+    entry_type: EntryType,
+    best_move: Option<Pos>,
+}
+
+/// Verification key stored in a [`PackedEntry`]: the upper 32 bits of
+/// the hash. A probe compares only these; the slot is `hash % size`, which
+/// doesn't pin down the lower 32 bits, so positions that agree in the upper
+/// half and share a slot are confused (about one probe in 2^32).
+#[inline]
+fn hash_key(hash: u64) -> u32 {
+    (hash >> 32) as u32
+}
+
 /// Transposition table for caching search results.
 ///
 /// Uses a simple direct-mapped approach where each hash maps to exactly
 /// one slot. Collisions are handled by replacement policies based on
 /// search depth.
 pub struct TranspositionTable {
-    entries: Vec<Option<TTEntry>>,
+    entries: Vec<Option<PackedEntry>>,
     size: usize,
 }
 
@@ -77,7 +105,7 @@ impl TranspositionTable {
     /// ```
     #[must_use]
     pub fn new(size_mb: usize) -> Self {
-        let entry_size = std::mem::size_of::<Option<TTEntry>>();
+        let entry_size = core::mem::size_of::<Option<PackedEntry>>();
         let size = (size_mb * 1024 * 1024) / entry_size;
 
         // Ensure at least some entries
@@ -112,7 +140,7 @@ impl TranspositionTable {
         let idx = (hash as usize) % self.size;
         let entry = self.entries[idx]?;
 
-        if entry.hash != hash {
+        if entry.key != hash_key(hash) {
             return None;
         }
 
@@ -151,7 +179,7 @@ impl TranspositionTable {
     pub fn get_best_move(&self, hash: u64) -> Option<Pos> {
         let idx = (hash as usize) % self.size;
         self.entries[idx].and_then(|e| {
-            if e.hash == hash {
+            if e.key == hash_key(hash) {
                 e.best_move
             } else {
                 None
@@ -185,12 +213,12 @@ impl TranspositionTable {
         // Replace if: empty, same position, or new search is deeper
         let should_replace = match &self.entries[idx] {
             None => true,
-            Some(e) => e.hash == hash || e.depth <= depth,
+            Some(e) => e.key == hash_key(hash) || e.depth <= depth,
         };
 
         if should_replace {
-            self.entries[idx] = Some(TTEntry {
-                hash,
+            self.entries[idx] = Some(PackedEntry {
+                key: hash_key(hash),
                 depth,
                 score,
                 entry_type,
@@ -249,6 +277,8 @@ pub struct TTStats {
 /// bits [32..36] row (u5, 0-18)                        5 bits
 /// bits [37..41] col (u5, 0-18)                        5 bits
 /// ```
+///
+/// [`AtomicTT::store`] adds the search generation in bits 42..47.
 fn pack_entry(depth: i8, score: i32, entry_type: EntryType, best_move: Option<Pos>) -> u64 {
     let d = (depth as i16 + 128) as u64 & 0xFF;
     // Clamp score to 21-bit range [-1_048_575, 1_048_575] to prevent silent overflow.
@@ -290,6 +320,49 @@ fn unpack_entry(data: u64) -> (i8, i32, EntryType, Option<Pos>) {
     (depth, score, entry_type, best_move)
 }
 
+/// Number of slots probed per hash index.
+const BUCKET_SIZE: usize = 4;
+
+/// Search generations wrap at 6 bits (stored in data bits 42..47).
+const GENERATION_MASK: u8 = 0x3F;
+
+/// Read the search generation stored above the packed entry fields.
+fn entry_generation(data: u64) -> u8 {
+    ((data >> 42) as u8) & GENERATION_MASK
+}
+
+/// Start of a saved table; the last byte is the format version. Bump it
+/// whenever the hashing, the entry packing or the evaluation changes.
+#[cfg(feature = "std")]
+const SAVE_MAGIC: [u8; 8] = *b"GMKTT\0\0\x02";
+
+#[cfg(feature = "std")]
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// One lock-free slot: 16 bytes.
+#[derive(Default)]
+struct Slot {
+    key: AtomicU64,
+    data: AtomicU64,
+}
+
+impl Slot {
+    #[inline]
+    fn load(&self) -> (u64, u64) {
+        (self.key.load(Ordering::Relaxed), self.data.load(Ordering::Relaxed))
+    }
+}
+
+/// A bucket fills exactly one 64-byte cache line, so a probe touches
+/// a single line of memory.
+#[derive(Default)]
+#[repr(align(64))]
+struct Bucket {
+    slots: [Slot; BUCKET_SIZE],
+}
+
 /// Lock-free transposition table for Lazy SMP parallel search.
 ///
 /// Uses XOR trick (Hyatt 1994): each slot stores `(key, data)` where
@@ -297,32 +370,85 @@ fn unpack_entry(data: u64) -> (i8, i32, EntryType, Option<Pos>) {
 /// Torn reads (partial writes from concurrent threads) fail the hash check
 /// and are treated as cache misses — safe and lock-free.
 ///
+/// Each hash maps to a cache-line bucket of [`BUCKET_SIZE`] slots. A store
+/// reuses the slot holding the same position, else an empty one, else evicts
+/// the slot with the lowest depth after penalising entries from older
+/// searches (see [`AtomicTT::new_search`]).
+///
 /// All methods take `&self` (not `&mut self`), enabling `Arc<AtomicTT>` sharing.
 pub struct AtomicTT {
-    keys: Vec<AtomicU64>,
-    data: Vec<AtomicU64>,
+    buckets: Vec<Bucket>,
+    /// Total slots (`buckets.len() * BUCKET_SIZE`)
     size: usize,
+    generation: AtomicU8,
 }
 
-// AtomicTT is Send+Sync automatically because all its fields (Vec<AtomicU64>, usize)
+// AtomicTT is Send+Sync automatically because all its fields (Vec of atomics, usize, AtomicU8)
 // are Send+Sync. No manual unsafe impl needed.
 
 impl AtomicTT {
     /// Create a new atomic transposition table with the given size in megabytes.
     #[must_use]
     pub fn new(size_mb: usize) -> Self {
-        // Each slot = 2 x AtomicU64 = 16 bytes
-        let slot_size = 16usize;
-        let size = ((size_mb * 1024 * 1024) / slot_size).max(1024);
-
-        let mut keys = Vec::with_capacity(size);
-        let mut data = Vec::with_capacity(size);
-        for _ in 0..size {
-            keys.push(AtomicU64::new(0));
-            data.push(AtomicU64::new(0));
+        let bucket_count = ((size_mb * 1024 * 1024) / core::mem::size_of::<Bucket>()).max(256);
+        let buckets: Vec<Bucket> = (0..bucket_count).map(|_| Bucket::default()).collect();
+
+        Self { buckets, size: bucket_count * BUCKET_SIZE, generation: AtomicU8::new(0) }
+    }
+
+    /// Size in megabytes, as passed to [`AtomicTT::new`] (rounded down)
+    #[must_use]
+    pub fn size_mb(&self) -> usize {
+        self.buckets.len() * core::mem::size_of::<Bucket>() / (1024 * 1024)
+    }
+
+    /// Start a new search generation so entries from earlier searches
+    /// are replaced before current ones.
+    pub fn new_search(&self) {
+        let next = self.generation.load(Ordering::Relaxed).wrapping_add(1) & GENERATION_MASK;
+        self.generation.store(next, Ordering::Relaxed);
+    }
+
+    /// Bucket for `hash`.
+    #[inline]
+    fn bucket(&self, hash: u64) -> &Bucket {
+        &self.buckets[(hash as usize) % self.buckets.len()]
+    }
+
+    /// Hint the CPU to start loading the bucket for `hash` into cache.
+    ///
+    /// Call as soon as a child hash is known so the line is ready by the
+    /// time the child node probes it. A no-op on non-x86_64 targets.
+    #[inline]
+    pub fn prefetch(&self, hash: u64) {
+        #[cfg(target_arch = "x86_64")]
+        {
+            use core::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+            let ptr = core::ptr::from_ref(self.bucket(hash)).cast::<i8>();
+            // SAFETY: prefetch is only a hint; it never faults, even on invalid
+            // addresses, and `ptr` points into a live allocation anyway.
+            unsafe { _mm_prefetch(ptr, _MM_HINT_T0) };
         }
+        #[cfg(not(target_arch = "x86_64"))]
+        let _ = hash;
+    }
+
+    /// Find the packed data for `hash`, if any slot in its bucket holds it.
+    #[inline]
+    fn lookup(&self, hash: u64) -> Option<u64> {
+        for slot in &self.bucket(hash).slots {
+            let (key, raw_data) = slot.load();
 
-        Self { keys, data, size }
+            // Empty slot
+            if key == 0 && raw_data == 0 {
+                continue;
+            }
+            // XOR verification: torn read → hash mismatch → safe miss
+            if key ^ raw_data == hash {
+                return Some(raw_data);
+            }
+        }
+        None
     }
 
     /// Probe the table for a position.
@@ -331,20 +457,7 @@ impl AtomicTT {
     /// Score is 0 if entry exists but depth insufficient (best_move still returned).
     #[must_use]
     pub fn probe(&self, hash: u64, depth: i8, alpha: i32, beta: i32) -> Option<(i32, Option<Pos>)> {
-        let idx = (hash as usize) % self.size;
-        let key = self.keys[idx].load(Ordering::Relaxed);
-        let raw_data = self.data[idx].load(Ordering::Relaxed);
-
-        // Empty slot
-        if key == 0 && raw_data == 0 {
-            return None;
-        }
-
-        // XOR verification: torn read → hash mismatch → safe miss
-        if key ^ raw_data != hash {
-            return None;
-        }
-
+        let raw_data = self.lookup(hash)?;
         let (entry_depth, score, entry_type, best_move) = unpack_entry(raw_data);
 
         if entry_depth >= depth {
@@ -363,24 +476,17 @@ impl AtomicTT {
     /// Get best move from the table for move ordering.
     #[must_use]
     pub fn get_best_move(&self, hash: u64) -> Option<Pos> {
-        let idx = (hash as usize) % self.size;
-        let key = self.keys[idx].load(Ordering::Relaxed);
-        let raw_data = self.data[idx].load(Ordering::Relaxed);
-
-        if key == 0 && raw_data == 0 {
-            return None;
-        }
-        if key ^ raw_data != hash {
-            return None;
-        }
-
+        let raw_data = self.lookup(hash)?;
         let (_depth, _score, _entry_type, best_move) = unpack_entry(raw_data);
         best_move
     }
 
     /// Store a position in the table (&self — safe for concurrent access).
     ///
-    /// Uses depth-preferred replacement: replaces if deeper or same hash.
+    /// Slot choice within the bucket: same position, then an empty slot,
+    /// then the entry with the lowest `depth - 4 * age`, where age counts
+    /// searches since the entry was written. A same-position store without
+    /// a move keeps the previously stored move.
     /// XOR trick: stores key = hash ^ data so concurrent reads can detect torn writes.
     pub fn store(
         &self,
@@ -388,38 +494,137 @@ impl AtomicTT {
         depth: i8,
         score: i32,
         entry_type: EntryType,
-        best_move: Option<Pos>,
+        mut best_move: Option<Pos>,
     ) {
-        let idx = (hash as usize) % self.size;
-
-        // Check replacement policy: replace if empty, same hash, or deeper
-        let existing_data = self.data[idx].load(Ordering::Relaxed);
-        let existing_key = self.keys[idx].load(Ordering::Relaxed);
-        if existing_data != 0 || existing_key != 0 {
-            let existing_hash = existing_key ^ existing_data;
-            if existing_hash != hash {
-                // Different position: only replace if deeper
-                let (existing_depth, _, _, _) = unpack_entry(existing_data);
-                if depth < existing_depth {
-                    return;
+        let generation = self.generation.load(Ordering::Relaxed);
+        let bucket = self.bucket(hash);
+
+        let mut target = 0;
+        let mut target_value = i32::MAX;
+        for (i, slot) in bucket.slots.iter().enumerate() {
+            let (existing_key, existing_data) = slot.load();
+            if existing_key == 0 && existing_data == 0 {
+                if target_value > i32::MIN {
+                    target = i;
+                    target_value = i32::MIN;
                 }
+                continue;
+            }
+            if existing_key ^ existing_data == hash {
+                if best_move.is_none() {
+                    best_move = unpack_entry(existing_data).3;
+                }
+                target = i;
+                break;
+            }
+            let (existing_depth, _, _, _) = unpack_entry(existing_data);
+            let age = generation.wrapping_sub(entry_generation(existing_data)) & GENERATION_MASK;
+            let value = i32::from(existing_depth) - 4 * i32::from(age);
+            if value < target_value {
+                target = i;
+                target_value = value;
             }
         }
 
-        let packed = pack_entry(depth, score, entry_type, best_move);
+        let packed = pack_entry(depth, score, entry_type, best_move) | (u64::from(generation) << 42);
         let key = hash ^ packed;
         // Write data first, then key. This ordering means a concurrent reader
         // either sees old (key, data) pair or gets a hash mismatch on torn read.
-        self.data[idx].store(packed, Ordering::Relaxed);
-        self.keys[idx].store(key, Ordering::Relaxed);
+        let slot = &bucket.slots[target];
+        slot.data.store(packed, Ordering::Relaxed);
+        slot.key.store(key, Ordering::Relaxed);
     }
 
     /// Clear all entries (&self — safe for concurrent access).
     pub fn clear(&self) {
-        for i in 0..self.size {
-            self.keys[i].store(0, Ordering::Relaxed);
-            self.data[i].store(0, Ordering::Relaxed);
+        for slot in self.buckets.iter().flat_map(|b| &b.slots) {
+            slot.key.store(0, Ordering::Relaxed);
+            slot.data.store(0, Ordering::Relaxed);
+        }
+        self.generation.store(0, Ordering::Relaxed);
+    }
+
+    /// Write every stored entry to `out`, after a header recording the
+    /// format version, `rules` and `key_seed`, the
+    /// [`ZobristTable::seed`](super::ZobristTable::seed) of the keys. Returns
+    /// the number of entries.
+    ///
+    /// Entries stored while the table is being written may be missed.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from `out`.
+    #[cfg(feature = "std")]
+    pub fn save(&self, mut out: impl Write, rules: RuleSet, key_seed: u64) -> io::Result<usize> {
+        let entries: Vec<(u64, u64)> = self
+            .buckets
+            .iter()
+            .flat_map(|b| &b.slots)
+            .map(Slot::load)
+            .filter(|&(key, data)| key != 0 || data != 0)
+            .collect();
+        out.write_all(&SAVE_MAGIC)?;
+        out.write_all(&rules.to_bits().to_le_bytes())?;
+        out.write_all(&key_seed.to_le_bytes())?;
+        out.write_all(&(entries.len() as u64).to_le_bytes())?;
+        for (key, data) in &entries {
+            out.write_all(&(key ^ data).to_le_bytes())?;
+            out.write_all(&data.to_le_bytes())?;
+        }
+        out.flush()?;
+        Ok(entries.len())
+    }
+
+    /// Add the entries written by [`AtomicTT::save`] to this table, as if
+    /// stored by the current search. The table sizes need not match; when
+    /// this one is smaller, shallow entries give way to deep ones. Returns
+    /// the number of entries read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidData` error if `input` isn't a saved table of
+    /// this version, was saved under other rules than `rules` or with keys of
+    /// another seed than `key_seed`, or holds a corrupt entry (entries
+    /// before it are kept), and any error from `input`.
+    #[cfg(feature = "std")]
+    pub fn load(&self, mut input: impl Read, rules: RuleSet, key_seed: u64) -> io::Result<usize> {
+        let mut magic = [0; 8];
+        input.read_exact(&mut magic)?;
+        if magic[..7] != SAVE_MAGIC[..7] {
+            return Err(invalid_data("not a saved transposition table"));
         }
+        if magic[7] != SAVE_MAGIC[7] {
+            return Err(invalid_data("transposition table saved by another engine version"));
+        }
+        let mut saved_rules = [0; 2];
+        input.read_exact(&mut saved_rules)?;
+        if u16::from_le_bytes(saved_rules) != rules.to_bits() {
+            return Err(invalid_data("transposition table saved under another rule set"));
+        }
+        let mut seed = [0; 8];
+        input.read_exact(&mut seed)?;
+        if u64::from_le_bytes(seed) != key_seed {
+            return Err(invalid_data("transposition table saved with other Zobrist keys"));
+        }
+        let mut count = [0; 8];
+        input.read_exact(&mut count)?;
+        let count = u64::from_le_bytes(count);
+
+        let (mut hash, mut data) = ([0; 8], [0; 8]);
+        for read in 0..count {
+            input.read_exact(&mut hash)?;
+            input.read_exact(&mut data)?;
+            let (hash, data) = (u64::from_le_bytes(hash), u64::from_le_bytes(data));
+            // Unpacking a move off the board would not be caught later
+            let has_move = (data >> 31) & 1 != 0;
+            let (row, col) = ((data >> 32) & 0x1F, (data >> 37) & 0x1F);
+            if has_move && (row >= BOARD_SIZE as u64 || col >= BOARD_SIZE as u64) {
+                return Err(invalid_data(&format!("corrupt entry {} of {}", read + 1, count)));
+            }
+            let (depth, score, entry_type, best_move) = unpack_entry(data);
+            self.store(hash, depth, score, entry_type, best_move);
+        }
+        Ok(count as usize)
     }
 
     /// Get statistics about table usage.
@@ -427,19 +632,18 @@ impl AtomicTT {
     /// Note: This is approximate under concurrent access.
     #[must_use]
     pub fn stats(&self) -> TTStats {
+        // Sample every 16th bucket for speed (approximate is fine for stats)
+        let step = if self.size > 65536 { 16 } else { 1 };
         let mut used = 0usize;
-        // Sample every 64th entry for speed (approximate is fine for stats)
-        let step = if self.size > 65536 { 64 } else { 1 };
         let mut sampled = 0usize;
-        let mut i = 0;
-        while i < self.size {
-            sampled += 1;
-            let k = self.keys[i].load(Ordering::Relaxed);
-            let d = self.data[i].load(Ordering::Relaxed);
-            if k != 0 || d != 0 {
-                used += 1;
+        for bucket in self.buckets.iter().step_by(step) {
+            for slot in &bucket.slots {
+                sampled += 1;
+                let (k, d) = slot.load();
+                if k != 0 || d != 0 {
+                    used += 1;
+                }
             }
-            i += step;
         }
         let estimated_used = if step > 1 {
             used * self.size / sampled
@@ -681,7 +885,7 @@ mod tests {
     #[test]
     fn test_tt_size_calculation() {
         let tt = TranspositionTable::new(1);
-        let entry_size = std::mem::size_of::<Option<TTEntry>>();
+        let entry_size = core::mem::size_of::<Option<PackedEntry>>();
         let expected_size = (1024 * 1024) / entry_size;
         assert_eq!(tt.size, expected_size.max(1024));
     }
@@ -772,6 +976,32 @@ mod tests {
         assert!(tt.get_best_move(0xFFFF_FFFF_FFFF_FFFF).is_none());
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_atomic_tt_save_load_round_trip() {
+        let tt = AtomicTT::new(1);
+        tt.store(0x1234, 7, -250, EntryType::LowerBound, Some(Pos::new(3, 17)));
+        tt.store(0xABCD_0000_0000_0001, 2, 40, EntryType::UpperBound, None);
+        let mut saved = Vec::new();
+        assert_eq!(tt.save(&mut saved, RuleSet::NINUKI, 7).unwrap(), 2);
+
+        // Into a table of another size
+        let loaded = AtomicTT::new(2);
+        assert_eq!(loaded.load(saved.as_slice(), RuleSet::NINUKI, 7).unwrap(), 2);
+        assert_eq!(loaded.probe(0x1234, 7, -400, -300), Some((-250, Some(Pos::new(3, 17)))));
+        assert_eq!(loaded.probe(0xABCD_0000_0000_0001, 2, 50, 100), Some((40, None)));
+
+        assert!(loaded.load(saved.as_slice(), RuleSet::RENJU, 7).unwrap_err().to_string().contains("rule set"));
+        assert!(loaded.load(saved.as_slice(), RuleSet::NINUKI, 8).unwrap_err().to_string().contains("Zobrist keys"));
+        assert!(loaded.load(&b"not a table at all"[..], RuleSet::NINUKI, 7).is_err());
+        // Truncated, and a move off the board
+        assert!(loaded.load(&saved[..saved.len() - 1], RuleSet::NINUKI, 7).is_err());
+        let last = saved.len() - 16 + 8;
+        saved[last..].copy_from_slice(&(pack_entry(1, 0, EntryType::Exact, None) | 31 << 32 | 1 << 31).to_le_bytes());
+        let error = loaded.load(saved.as_slice(), RuleSet::NINUKI, 7).unwrap_err();
+        assert!(error.to_string().contains("corrupt entry 2 of 2"));
+    }
+
     #[test]
     fn test_atomic_tt_clear() {
         let tt = AtomicTT::new(1);
@@ -808,8 +1038,82 @@ mod tests {
     }
 
     #[test]
+    fn test_atomic_tt_bucket_holds_colliding_entries() {
+        let tt = AtomicTT::new(1);
+        let step = tt.buckets.len() as u64;
+        let hashes: Vec<u64> = (1..=BUCKET_SIZE as u64).map(|k| 7 + k * step).collect();
+
+        for (i, &hash) in hashes.iter().enumerate() {
+            tt.store(hash, 5, i as i32, EntryType::Exact, None);
+        }
+        for (i, &hash) in hashes.iter().enumerate() {
+            assert_eq!(tt.probe(hash, 5, -1000, 1000), Some((i as i32, None)));
+        }
+    }
+
+    #[test]
+    fn test_atomic_tt_evicts_shallowest() {
+        let tt = AtomicTT::new(1);
+        let step = tt.buckets.len() as u64;
+        let depths = [6i8, 2, 8, 4];
+        for (k, &depth) in depths.iter().enumerate() {
+            tt.store(3 + k as u64 * step, depth, 10, EntryType::Exact, None);
+        }
+
+        let newcomer = 3 + 10 * step;
+        tt.store(newcomer, 1, 20, EntryType::Exact, None);
+        assert!(tt.probe(newcomer, 1, -1000, 1000).is_some());
+        assert!(tt.probe(3 + step, 2, -1000, 1000).is_none(), "depth-2 entry should be evicted");
+        for k in [0u64, 2, 3] {
+            assert!(tt.probe(3 + k * step, 1, -1000, 1000).is_some());
+        }
+    }
+
+    #[test]
+    fn test_atomic_tt_prefers_evicting_stale_entries() {
+        let tt = AtomicTT::new(1);
+        let step = tt.buckets.len() as u64;
+        let old = 5 + step;
+        tt.store(old, 9, 10, EntryType::Exact, None);
+        tt.new_search();
+        tt.new_search();
+        tt.new_search();
+        for k in 2..=BUCKET_SIZE as u64 {
+            tt.store(5 + k * step, 3, 10, EntryType::Exact, None);
+        }
+
+        // Three searches old: 9 - 12 < 3, so the deep but stale entry goes first
+        tt.store(5 + 20 * step, 1, 10, EntryType::Exact, None);
+        assert!(tt.probe(old, 1, -1000, 1000).is_none());
+    }
+
+    #[test]
+    fn test_atomic_tt_keeps_move_on_moveless_store() {
+        let tt = AtomicTT::new(1);
+        let hash = 0x123456789ABCDEF0;
+        tt.store(hash, 3, 100, EntryType::Exact, Some(Pos::new(9, 9)));
+        tt.store(hash, 5, 50, EntryType::UpperBound, None);
+        assert_eq!(tt.get_best_move(hash), Some(Pos::new(9, 9)));
+        assert_eq!(tt.probe(hash, 5, 100, 1000), Some((50, Some(Pos::new(9, 9)))));
+    }
+
+    #[test]
+    fn test_entry_layout() {
+        assert_eq!(core::mem::size_of::<PackedEntry>(), 16);
+        assert_eq!(core::mem::size_of::<Option<PackedEntry>>(), 16);
+        assert_eq!(core::mem::size_of::<Slot>(), 16);
+        assert_eq!(core::mem::size_of::<Bucket>(), 64);
+        assert_eq!(core::mem::align_of::<Bucket>(), 64);
+
+        let tt = AtomicTT::new(1);
+        assert_eq!(tt.buckets.as_ptr() as usize % 64, 0);
+        tt.prefetch(0x123456789ABCDEF0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
     fn test_atomic_tt_concurrent_safety() {
-        use std::sync::Arc;
+        use alloc::sync::Arc;
         use std::thread;
 
         let tt = Arc::new(AtomicTT::new(1));
@@ -25,13 +25,18 @@
 //! assert_eq!(hash_incremental, hash2);
 //! ```
 
-use crate::board::{Board, Pos, Stone, TOTAL_CELLS};
+use crate::board::{Board, Pos, Stone, Symmetry, TOTAL_CELLS};
+use crate::rules::MAX_CAPTURE_WIN_PAIRS;
+
+/// Capture counts with their own key; the game ends at the last one
+const CAPTURE_KEYS: usize = MAX_CAPTURE_WIN_PAIRS as usize + 1;
 
 /// Zobrist hash table for position hashing.
 ///
 /// Uses XOR-based hashing with precomputed random values for each
 /// (position, stone color) combination. This allows O(1) incremental
 /// updates when placing or removing stones.
+#[derive(Clone)]
 pub struct ZobristTable {
     /// Random values for black stones at each position
     black: [u64; TOTAL_CELLS],
@@ -39,21 +44,35 @@ pub struct ZobristTable {
     white: [u64; TOTAL_CELLS],
     /// Random value XORed when black is to move
     black_to_move: u64,
-    /// Random values for capture counts: [color][count 0..6]
-    captures: [[u64; 6]; 2],
+    /// Random values for capture counts: [color][count 0..=MAX_CAPTURE_WIN_PAIRS]
+    captures: [[u64; CAPTURE_KEYS]; 2],
+    /// Seed the values were generated from
+    seed: u64,
 }
 
 impl ZobristTable {
+    /// Seed of [`ZobristTable::new`]
+    pub const DEFAULT_SEED: u64 = 0x1234_5678_9ABC_DEF0;
+
     /// Create a new Zobrist table with deterministic random values.
     ///
     /// Uses a linear congruential generator (LCG) with a fixed seed
-    /// to ensure reproducible hashes across different runs.
+    /// ([`ZobristTable::DEFAULT_SEED`]) to ensure reproducible hashes across
+    /// different runs.
     #[must_use]
     pub fn new() -> Self {
+        Self::with_seed(Self::DEFAULT_SEED)
+    }
+
+    /// Create a Zobrist table from `seed`. The same seed gives the same
+    /// table on every run and platform.
+    #[must_use]
+    pub fn with_seed(seed: u64) -> Self {
+        let table_seed = seed;
         // Use a simple LCG for deterministic "random" values
         // Same seed = same table = reproducible hashes
         // Constants from Knuth's MMIX LCG
-        let mut seed: u64 = 0x1234_5678_9ABC_DEF0;
+        let mut seed = seed;
         let mut next_rand = || {
             seed = seed
                 .wrapping_mul(6_364_136_223_846_793_005)
@@ -69,10 +88,10 @@ impl ZobristTable {
             white[i] = next_rand();
         }
 
-        let mut captures = [[0u64; 6]; 2];
-        for color in 0..2 {
-            for count in 0..6 {
-                captures[color][count] = next_rand();
+        let mut captures = [[0u64; CAPTURE_KEYS]; 2];
+        for per_color in &mut captures {
+            for key in per_color.iter_mut() {
+                *key = next_rand();
             }
         }
 
@@ -81,9 +100,16 @@ impl ZobristTable {
             white,
             black_to_move: next_rand(),
             captures,
+            seed: table_seed,
         }
     }
 
+    /// Seed the table was generated from
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
     /// Compute the full hash for a board position.
     ///
     /// This iterates over all stones on the board. For incremental updates
@@ -92,11 +118,11 @@ impl ZobristTable {
     pub fn hash(&self, board: &Board, side_to_move: Stone) -> u64 {
         let mut h = 0u64;
 
-        for pos in board.black.iter_ones() {
+        for pos in board.stones_of(Stone::Black) {
             h ^= self.black[pos.to_index()];
         }
 
-        for pos in board.white.iter_ones() {
+        for pos in board.stones_of(Stone::White) {
             h ^= self.white[pos.to_index()];
         }
 
@@ -106,8 +132,8 @@ impl ZobristTable {
 
         // Include capture counts in hash to distinguish positions with same stones
         // but different capture counts (affects win conditions)
-        h ^= self.captures[0][board.captures(Stone::Black).min(5) as usize];
-        h ^= self.captures[1][board.captures(Stone::White).min(5) as usize];
+        h ^= self.captures[0][board.captures(Stone::Black).min(MAX_CAPTURE_WIN_PAIRS) as usize];
+        h ^= self.captures[1][board.captures(Stone::White).min(MAX_CAPTURE_WIN_PAIRS) as usize];
 
         h
     }
@@ -176,8 +202,18 @@ impl ZobristTable {
     #[must_use]
     pub fn update_capture_count(&self, hash: u64, color: Stone, old_count: u8, new_count: u8) -> u64 {
         let cidx = if color == Stone::Black { 0 } else { 1 };
-        hash ^ self.captures[cidx][old_count.min(5) as usize]
-             ^ self.captures[cidx][new_count.min(5) as usize]
+        hash ^ self.captures[cidx][old_count.min(MAX_CAPTURE_WIN_PAIRS) as usize]
+             ^ self.captures[cidx][new_count.min(MAX_CAPTURE_WIN_PAIRS) as usize]
+    }
+
+    /// Hash of `board`'s [`Symmetry::canonical`] image: the same for all
+    /// eight rotations and reflections of a position.
+    ///
+    /// Costs a full hash plus the canonicalization, so it suits early-game
+    /// and book lookups, not per-node use.
+    #[must_use]
+    pub fn canonical_hash(&self, board: &Board, side_to_move: Stone) -> u64 {
+        self.hash(&Symmetry::canonical(board).0, side_to_move)
     }
 }
 
@@ -220,6 +256,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_zobrist_seeds() {
+        let board = Board::new();
+        let seeded = ZobristTable::with_seed(ZobristTable::DEFAULT_SEED);
+        assert_eq!(seeded.hash(&board, Stone::Black), ZobristTable::new().hash(&board, Stone::Black));
+        assert_eq!(seeded.seed(), ZobristTable::DEFAULT_SEED);
+
+        let other = ZobristTable::with_seed(42);
+        assert_eq!(other.seed(), 42);
+        assert_eq!(other.hash(&board, Stone::Black), ZobristTable::with_seed(42).hash(&board, Stone::Black));
+        assert_ne!(other.hash(&board, Stone::Black), seeded.hash(&board, Stone::Black));
+    }
+
     #[test]
     fn test_zobrist_incremental() {
         let zt = ZobristTable::new();
@@ -330,6 +379,21 @@ mod tests {
         assert_eq!(h1, h2);
     }
 
+    #[test]
+    fn test_canonical_hash_ignores_orientation() {
+        let zt = ZobristTable::new();
+        let mut board = Board::new();
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.place_stone(Pos::new(8, 11), Stone::White);
+        let canonical = zt.canonical_hash(&board, Stone::Black);
+        for sym in Symmetry::ALL {
+            assert_eq!(zt.canonical_hash(&board.transform(sym), Stone::Black), canonical);
+        }
+        assert_ne!(zt.canonical_hash(&board, Stone::White), canonical);
+        board.place_stone(Pos::new(8, 10), Stone::Black);
+        assert_ne!(zt.canonical_hash(&board, Stone::Black), canonical);
+    }
+
     #[test]
     fn test_zobrist_collision_resistance() {
         let zt = ZobristTable::new();
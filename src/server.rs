@@ -0,0 +1,857 @@
+//! HTTP/JSON engine server (feature `server`)
+//!
+//! Hosts any number of games, each with its own [`AIEngine`](crate::AIEngine) (and so its
+//! own transposition table), behind a small JSON API. Engines come from an
+//! [`EnginePool`], so creating a game or analyzing a position doesn't
+//! allocate a table. Every connection is
+//! served on its own thread, up to [`ServerConfig::max_connections`] at a
+//! time, and dropped if it stalls for [`IO_TIMEOUT`]; games are locked
+//! individually, so searches in different games run in parallel.
+//!
+//! | Method   | Path                       | Body                | Response            |
+//! |----------|----------------------------|---------------------|---------------------|
+//! | `GET`    | `/health`                  |                     | `{"status":"ok"}`   |
+//! | `POST`   | `/games`                   | [`NewGameRequest`]  | [`GameView`] (201)  |
+//! | `GET`    | `/games/{id}`              |                     | [`GameView`]        |
+//! | `DELETE` | `/games/{id}`              |                     | 204                 |
+//! | `PUT`    | `/games/{id}/position`     | [`PositionRequest`] | [`GameView`]        |
+//! | `POST`   | `/games/{id}/moves`        | [`MoveRequest`]     | [`MoveResponse`]    |
+//! | `POST`   | `/games/{id}/engine-move`  |                     | [`MoveResponse`]    |
+//! | `POST`   | `/games/{id}/resign`       | [`PlayerRequest`]   | [`GameView`]        |
+//! | `POST`   | `/games/{id}/offer-draw`   | [`PlayerRequest`]   | [`GameView`]        |
+//! | `POST`   | `/games/{id}/accept-draw`  | [`PlayerRequest`]   | [`GameView`]        |
+//! | `POST`   | `/games/{id}/decline-draw` | [`PlayerRequest`]   | [`GameView`]        |
+//! | `POST`   | `/analyze`                 | [`PositionRequest`] | [`MoveResult`]      |
+//!
+//! Colors are `"Black"` / `"White"`, cells are `{"row": r, "col": c}`
+//! (0-based); a move may also be given in board notation (`"K10"`, in
+//! [`ServerConfig::notation`]).
+//! Errors come back as `{"error": "..."}` with a 4xx status.
+//!
+//! ```text
+//! cargo run --release --features server --bin gomoku-server -- --addr 127.0.0.1:8080
+//! curl -X POST localhost:8080/games -d '{}'
+//! curl -X POST localhost:8080/games/1/moves -d '{"row":9,"col":9}'
+//! curl -X POST localhost:8080/games/1/engine-move
+//! ```
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::board::{Board, Pos, Stone};
+use crate::engine::MoveResult;
+use crate::pool::{EnginePool, PooledEngine};
+use crate::error::GomokuError;
+use crate::game::{Game, GameEvent, GameOutcome};
+use crate::logging::log_warn;
+use crate::notation::Notation;
+use crate::rules::{validate_position, RuleSet};
+use crate::search::{SearchLimits, Searcher, MAX_SEARCH_DEPTH};
+
+/// Largest request body accepted (a full position is ~20 KB of JSON)
+const MAX_BODY_BYTES: usize = 1 << 20;
+
+/// Longest time limit a game may ask for (ms)
+pub const MAX_TIME_LIMIT_MS: u64 = 10 * 60 * 1000;
+
+/// How long a connection may wait on a read or write before it's dropped
+pub const IO_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Pause after a failed accept before trying again
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// Engine settings and limits for a server instance
+#[derive(Debug, Clone, Copy)]
+pub struct ServerConfig {
+    /// Transposition table size per game (MB)
+    pub tt_size_mb: usize,
+    /// Default maximum search depth for new games
+    pub max_depth: i8,
+    /// Default time limit for new games (ms)
+    pub time_limit_ms: u64,
+    /// Maximum number of simultaneous games
+    pub max_games: usize,
+    /// Default and most search threads per game
+    pub threads: usize,
+    /// Engines kept ready for new games and `/analyze` requests
+    pub pool_size: usize,
+    /// Connections served at once; more are answered 503 and closed
+    pub max_connections: usize,
+    /// Rules every game and analysis is played by
+    pub rules: RuleSet,
+    /// Coordinates moves given as text are read in
+    pub notation: Notation,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            tt_size_mb: 16,
+            max_depth: 20,
+            time_limit_ms: 500,
+            max_games: 64,
+            threads: Searcher::auto_threads(),
+            pool_size: 4,
+            max_connections: 256,
+            rules: RuleSet::NINUKI,
+            notation: Notation::DEFAULT,
+        }
+    }
+}
+
+/// Body of `POST /games/{id}/moves`: a cell (`{"row":9,"col":9}`) or
+/// board notation (`"K10"`)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum MoveRequest {
+    Cell(Pos),
+    Notation(String),
+}
+
+/// Body of `POST /games`; omitted fields use the server defaults
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NewGameRequest {
+    /// 1 to [`MAX_SEARCH_DEPTH`]
+    pub max_depth: Option<i8>,
+    /// 1 to [`MAX_TIME_LIMIT_MS`]
+    pub time_limit_ms: Option<u64>,
+    /// Capped at [`ServerConfig::threads`]
+    pub threads: Option<usize>,
+}
+
+/// Body of `POST /games/{id}/resign` and the draw routes: the player
+/// acting. Omitted, it's the side to move, or for an answer to a draw
+/// offer the side it was made to.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PlayerRequest {
+    pub color: Option<Stone>,
+}
+
+/// A position to load or analyze
+#[derive(Debug, Clone, Deserialize)]
+pub struct PositionRequest {
+    #[serde(default)]
+    pub black: Vec<Pos>,
+    #[serde(default)]
+    pub white: Vec<Pos>,
+    #[serde(default)]
+    pub black_captures: u8,
+    #[serde(default)]
+    pub white_captures: u8,
+    pub to_move: Stone,
+    /// Reject positions no game reaches ([`validate_position`]); off by
+    /// default so handicap and composed positions load
+    #[serde(default)]
+    pub strict: bool,
+    /// `/analyze` only: consider just these moves (`searchmoves`); empty
+    /// means all. The tactical shortcuts are skipped for such a search.
+    #[serde(default)]
+    pub search_moves: Vec<Pos>,
+}
+
+/// A game as returned by the API
+#[derive(Debug, Clone, Serialize)]
+pub struct GameView {
+    pub id: u64,
+    pub to_move: Stone,
+    pub black: Vec<Pos>,
+    pub white: Vec<Pos>,
+    pub black_captures: u8,
+    pub white_captures: u8,
+    pub winner: Option<Stone>,
+    /// How the game ended, once it has
+    pub outcome: Option<GameOutcome>,
+    /// The side whose draw offer is waiting for an answer
+    pub draw_offer: Option<Stone>,
+    /// Moves played through the API since the game (or position) was set
+    pub moves: usize,
+}
+
+/// Result of playing a move
+#[derive(Debug, Clone, Serialize)]
+pub struct MoveResponse {
+    #[serde(rename = "move")]
+    pub mov: Pos,
+    pub color: Stone,
+    pub captured: Vec<Pos>,
+    pub winner: Option<Stone>,
+    /// Search statistics (engine moves only)
+    pub stats: Option<MoveResult>,
+    pub game: GameView,
+}
+
+/// An HTTP response: status code and JSON body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn json(status: u16, value: &impl Serialize) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Self { status, body },
+            Err(e) => Self::error(500, &e.to_string()),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self { status, body: serde_json::json!({ "error": message }).to_string() }
+    }
+
+    fn empty(status: u16) -> Self {
+        Self { status, body: String::new() }
+    }
+}
+
+/// One hosted game
+struct ServerGame {
+    engine: PooledEngine,
+    game: Game,
+}
+
+impl ServerGame {
+    fn view(&self, id: u64) -> GameView {
+        let board = self.game.board();
+        GameView {
+            id,
+            to_move: self.game.to_move(),
+            black: board.stones_of(Stone::Black).collect(),
+            white: board.stones_of(Stone::White).collect(),
+            black_captures: board.captures(Stone::Black),
+            white_captures: board.captures(Stone::White),
+            winner: self.winner(),
+            outcome: self.game.outcome(),
+            draw_offer: self.game.draw_offer(),
+            moves: self.game.move_count(),
+        }
+    }
+
+    fn winner(&self) -> Option<Stone> {
+        self.game.outcome().and_then(|outcome| outcome.winner())
+    }
+
+    /// Apply a move with full rules; returns the captured stones and the
+    /// winner
+    fn play(&mut self, pos: Pos) -> Result<(Vec<Pos>, Option<Stone>), String> {
+        let captured = self
+            .game
+            .play(pos)?
+            .into_iter()
+            .flat_map(|event| match event {
+                GameEvent::Captured { positions, .. } => positions,
+                _ => Vec::new(),
+            })
+            .collect();
+        Ok((captured, self.winner()))
+    }
+}
+
+/// Game registry and request router
+pub struct Server {
+    config: ServerConfig,
+    engines: EnginePool,
+    games: Mutex<HashMap<u64, Arc<Mutex<ServerGame>>>>,
+    next_id: AtomicU64,
+}
+
+impl Server {
+    /// Create a server with no games.
+    #[must_use]
+    pub fn new(config: ServerConfig) -> Self {
+        let engines = EnginePool::with_threads(
+            config.pool_size,
+            config.tt_size_mb,
+            config.max_depth,
+            config.time_limit_ms,
+            config.threads,
+        );
+        Self {
+            config,
+            engines,
+            games: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Number of hosted games.
+    pub fn game_count(&self) -> usize {
+        self.games.lock().map_or(0, |games| games.len())
+    }
+
+    /// Route one request. `body` is the raw request body (JSON or empty).
+    pub fn handle(&self, method: &str, path: &str, body: &str) -> Response {
+        let segments: Vec<&str> = path
+            .split('?')
+            .next()
+            .unwrap_or("")
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        match (method, segments.as_slice()) {
+            ("GET", ["health"]) => Response::json(200, &serde_json::json!({ "status": "ok" })),
+            ("POST", ["games"]) => self.create_game(body),
+            ("POST", ["analyze"]) => self.analyze(body),
+            (_, ["games", id, rest @ ..]) => {
+                let Ok(id) = id.parse::<u64>() else {
+                    return Response::error(404, "no such game");
+                };
+                match (method, rest) {
+                    ("DELETE", []) => self.delete_game(id),
+                    ("GET", []) => self.with_game(id, |game| Response::json(200, &game.view(id))),
+                    ("PUT", ["position"]) => self.set_position(id, body),
+                    ("POST", ["moves"]) => self.play_move(id, body),
+                    ("POST", ["engine-move"]) => self.engine_move(id),
+                    ("POST", [action @ ("resign" | "offer-draw" | "accept-draw" | "decline-draw")]) => {
+                        self.player_action(id, action, body)
+                    }
+                    _ => Response::error(404, "not found"),
+                }
+            }
+            _ => Response::error(404, "not found"),
+        }
+    }
+
+    fn create_game(&self, body: &str) -> Response {
+        let request: NewGameRequest = match parse_body(body) {
+            Ok(r) => r,
+            Err(resp) => return resp,
+        };
+        if request.max_depth.is_some_and(|depth| !(1..=MAX_SEARCH_DEPTH).contains(&depth)) {
+            return Response::error(400, &format!("max_depth must be 1 to {}", MAX_SEARCH_DEPTH));
+        }
+        if request.time_limit_ms.is_some_and(|ms| !(1..=MAX_TIME_LIMIT_MS).contains(&ms)) {
+            return Response::error(400, &format!("time_limit_ms must be 1 to {}", MAX_TIME_LIMIT_MS));
+        }
+        let Ok(mut games) = self.games.lock() else {
+            return Response::error(500, "server state poisoned");
+        };
+        if games.len() >= self.config.max_games {
+            return Response::error(429, "too many games");
+        }
+
+        let mut engine = self.engines.checkout();
+        engine.set_rules(self.config.rules);
+        engine.set_max_depth(request.max_depth.unwrap_or(self.config.max_depth));
+        engine.set_time_limit(request.time_limit_ms.unwrap_or(self.config.time_limit_ms));
+        // Clients may ask for fewer threads than the server allows, not more
+        engine.set_threads(request.threads.map_or(self.config.threads, |threads| threads.min(self.config.threads)));
+        let game = Game::from_position(self.config.rules, Board::new(), Stone::Black).with_notation(self.config.notation);
+        let game = ServerGame { engine, game };
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let view = game.view(id);
+        games.insert(id, Arc::new(Mutex::new(game)));
+        Response::json(201, &view)
+    }
+
+    fn delete_game(&self, id: u64) -> Response {
+        match self.games.lock().map(|mut games| games.remove(&id)) {
+            Ok(Some(_)) => Response::empty(204),
+            Ok(None) => Response::error(404, "no such game"),
+            Err(_) => Response::error(500, "server state poisoned"),
+        }
+    }
+
+    fn set_position(&self, id: u64, body: &str) -> Response {
+        let request: PositionRequest = match parse_body(body) {
+            Ok(r) => r,
+            Err(resp) => return resp,
+        };
+        let board = match board_from_request(self.config.rules, &request) {
+            Ok(b) => b,
+            Err(e) => return Response::error(400, &e),
+        };
+        self.with_game(id, |game| {
+            game.game = Game::from_position(game.game.rules(), board, request.to_move).with_notation(self.config.notation);
+            game.engine.clear_cache();
+            Response::json(200, &game.view(id))
+        })
+    }
+
+    fn play_move(&self, id: u64, body: &str) -> Response {
+        let pos = match parse_body(body) {
+            Ok(MoveRequest::Cell(cell)) => match Pos::try_new(cell.row, cell.col) {
+                Ok(pos) => pos,
+                Err(e) => return Response::error(400, &e.to_string()),
+            },
+            Ok(MoveRequest::Notation(text)) => match self.config.notation.parse(&text) {
+                Ok(pos) => pos,
+                Err(e) => return Response::error(400, &e),
+            },
+            Err(resp) => return resp,
+        };
+        self.with_game(id, |game| {
+            let color = game.game.to_move();
+            match game.play(pos) {
+                Ok((captured, winner)) => Response::json(200, &MoveResponse {
+                    mov: pos,
+                    color,
+                    captured,
+                    winner,
+                    stats: None,
+                    game: game.view(id),
+                }),
+                Err(e) => Response::error(409, &e),
+            }
+        })
+    }
+
+    fn engine_move(&self, id: u64) -> Response {
+        self.with_game(id, |game| {
+            if game.game.is_over() {
+                return Response::error(409, "game is over");
+            }
+            let color = game.game.to_move();
+            let result = game.engine.get_move_with_stats(game.game.board(), color);
+            let Some(pos) = result.best_move else {
+                return Response::error(409, "no legal move");
+            };
+            match game.play(pos) {
+                Ok((captured, winner)) => Response::json(200, &MoveResponse {
+                    mov: pos,
+                    color,
+                    captured,
+                    winner,
+                    stats: Some(result),
+                    game: game.view(id),
+                }),
+                Err(e) => Response::error(500, &e),
+            }
+        })
+    }
+
+    /// Stateless search on a submitted position (a cleared pooled engine per request)
+    fn analyze(&self, body: &str) -> Response {
+        let request: PositionRequest = match parse_body(body) {
+            Ok(r) => r,
+            Err(resp) => return resp,
+        };
+        let board = match board_from_request(self.config.rules, &request) {
+            Ok(b) => b,
+            Err(e) => return Response::error(400, &e),
+        };
+        if let Some(e) = request.search_moves.iter().find_map(|p| Pos::try_new(p.row, p.col).err()) {
+            return Response::error(400, &e.to_string());
+        }
+        let mut engine = self.engines.checkout();
+        engine.set_rules(self.config.rules);
+        let result = if request.search_moves.is_empty() {
+            engine.get_move_with_stats(&board, request.to_move)
+        } else {
+            let limits = SearchLimits::time(Duration::from_millis(self.config.time_limit_ms))
+                .restrict_to(request.search_moves.iter().copied());
+            engine.get_move_with_limits(&board, request.to_move, &limits)
+        };
+        Response::json(200, &result)
+    }
+
+    /// Resign, or offer, accept or decline a draw, for the player in `body`.
+    fn player_action(&self, id: u64, action: &str, body: &str) -> Response {
+        let request: PlayerRequest = match parse_body(body) {
+            Ok(r) => r,
+            Err(resp) => return resp,
+        };
+        self.with_game(id, |game| {
+            let answering = game.game.draw_offer().map_or(game.game.to_move(), Stone::opponent);
+            let result = match action {
+                "resign" => game.game.resign(request.color.unwrap_or(game.game.to_move())).map(drop),
+                "offer-draw" => game.game.offer_draw(request.color.unwrap_or(game.game.to_move())),
+                "accept-draw" => game.game.accept_draw(request.color.unwrap_or(answering)).map(drop),
+                _ => game.game.decline_draw(request.color.unwrap_or(answering)),
+            };
+            match result {
+                Ok(()) => Response::json(200, &game.view(id)),
+                Err(e) => Response::error(409, &e),
+            }
+        })
+    }
+
+    /// Run `f` on game `id` while holding only that game's lock.
+    fn with_game(&self, id: u64, f: impl FnOnce(&mut ServerGame) -> Response) -> Response {
+        let game = match self.games.lock() {
+            Ok(games) => games.get(&id).cloned(),
+            Err(_) => return Response::error(500, "server state poisoned"),
+        };
+        let Some(game) = game else {
+            return Response::error(404, "no such game");
+        };
+        let Ok(mut game) = game.lock() else {
+            return Response::error(500, "game state poisoned");
+        };
+        f(&mut game)
+    }
+}
+
+/// Parse a JSON body; an empty body is read as `{}`.
+fn parse_body<T: serde::de::DeserializeOwned>(body: &str) -> Result<T, Response> {
+    let body = if body.trim().is_empty() { "{}" } else { body };
+    serde_json::from_str(body).map_err(|e| Response::error(400, &format!("invalid JSON: {}", e)))
+}
+
+fn board_from_request(rules: RuleSet, request: &PositionRequest) -> Result<Board, String> {
+    if request.to_move == Stone::Empty {
+        return Err("to_move must be Black or White".to_string());
+    }
+    let mut board = Board::new();
+    for (stones, color) in [(&request.black, Stone::Black), (&request.white, Stone::White)] {
+        for &pos in stones {
+            board.try_place_stone(pos, color).map_err(|e| match e {
+                GomokuError::Occupied(_) => format!("position ({}, {}) listed twice", pos.row, pos.col),
+                e => e.to_string(),
+            })?;
+        }
+    }
+    board.set_captures(rules, Stone::Black, request.black_captures).map_err(|e| e.to_string())?;
+    board.set_captures(rules, Stone::White, request.white_captures).map_err(|e| e.to_string())?;
+    if request.strict {
+        if let Err(errors) = validate_position(rules, &board) {
+            let reasons: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            return Err(format!("impossible position: {}", reasons.join("; ")));
+        }
+    }
+    Ok(board)
+}
+
+/// Accept connections on `listener` forever, one thread per connection.
+/// Past [`ServerConfig::max_connections`] open connections, new ones are
+/// answered 503 and closed. A failed accept (out of file descriptors, a
+/// connection aborted while queued) is logged and the loop goes on.
+pub fn run(server: Arc<Server>, listener: TcpListener) -> io::Result<()> {
+    let open = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                log_warn!("Accepting a connection failed: {}", e);
+                // Out of descriptors fails again at once; let connections close
+                thread::sleep(ACCEPT_RETRY_DELAY);
+                continue;
+            }
+        };
+        if stream.set_read_timeout(Some(IO_TIMEOUT)).is_err() || stream.set_write_timeout(Some(IO_TIMEOUT)).is_err() {
+            continue;
+        }
+        if open.fetch_add(1, Ordering::AcqRel) >= server.config.max_connections {
+            open.fetch_sub(1, Ordering::AcqRel);
+            let _ = write_response(&mut stream, &Response::error(503, "too many connections"));
+            continue;
+        }
+        let slot = ConnectionSlot(Arc::clone(&open));
+        let server = Arc::clone(&server);
+        // A refused spawn drops the closure, and with it the stream and slot
+        let _ = thread::Builder::new().spawn(move || {
+            let _slot = slot;
+            let _ = handle_connection(&server, stream);
+        });
+    }
+    Ok(())
+}
+
+/// One counted open connection; frees its place when dropped
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Serve one HTTP/1.1 request and close the connection.
+fn handle_connection(server: &Server, stream: TcpStream) -> io::Result<()> {
+    let reader = BufReader::new(stream.try_clone()?);
+    serve_request(server, reader, stream)
+}
+
+/// Read one HTTP/1.1 request from `reader`, handle it and write the response
+/// to `stream`. This is what each connection runs; malformed requests get a
+/// 4xx response.
+///
+/// # Errors
+///
+/// Returns I/O errors from reading or writing, including a body shorter than
+/// its `Content-Length`.
+pub fn serve_request(server: &Server, mut reader: impl BufRead, mut stream: impl Write) -> io::Result<()> {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return write_response(&mut stream, &Response::error(400, "bad request line"));
+    };
+
+    let mut content_length = 0usize;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return write_response(&mut stream, &Response::error(413, "body too large"));
+    }
+
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body)?;
+    let response = match String::from_utf8(body) {
+        Ok(body) => server.handle(method, path, &body),
+        Err(_) => Response::error(400, "body is not UTF-8"),
+    };
+    write_response(&mut stream, &response)
+}
+
+fn write_response(stream: &mut impl Write, response: &Response) -> io::Result<()> {
+    let reason = match response.status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        429 => "Too Many Requests",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::io::Read;
+
+    fn small_server() -> Server {
+        Server::new(ServerConfig {
+            tt_size_mb: 1,
+            max_depth: 4,
+            time_limit_ms: 200,
+            max_games: 2,
+            threads: 1,
+            pool_size: 1,
+            max_connections: 1,
+            rules: RuleSet::NINUKI,
+            notation: Notation::DEFAULT,
+        })
+    }
+
+    fn json(response: &Response) -> Value {
+        serde_json::from_str(&response.body).unwrap()
+    }
+
+    #[test]
+    fn test_game_lifecycle() {
+        let server = small_server();
+        let created = server.handle("POST", "/games", "{}");
+        assert_eq!(created.status, 201);
+        let id = json(&created)["id"].as_u64().unwrap();
+        // The game took the pooled engine
+        assert_eq!(server.engines.idle(), 0);
+
+        let moved = server.handle("POST", &format!("/games/{}/moves", id), r#"{"row":9,"col":9}"#);
+        assert_eq!(moved.status, 200, "{}", moved.body);
+        assert_eq!(json(&moved)["game"]["to_move"], "White");
+
+        // Occupied cell, given in notation
+        let again = server.handle("POST", &format!("/games/{}/moves", id), r#""K10""#);
+        assert_eq!(again.status, 409);
+        assert_eq!(server.handle("POST", &format!("/games/{}/moves", id), r#""I10""#).status, 400);
+
+        let reply = server.handle("POST", &format!("/games/{}/engine-move", id), "");
+        assert_eq!(reply.status, 200, "{}", reply.body);
+        let reply = json(&reply);
+        assert_eq!(reply["color"], "White");
+        assert!(reply["stats"]["nodes"].is_u64());
+        assert!(reply["stats"]["threads"].is_u64());
+        assert_eq!(reply["game"]["moves"], 2);
+
+        assert_eq!(server.handle("DELETE", &format!("/games/{}", id), "").status, 204);
+        assert_eq!(server.handle("GET", &format!("/games/{}", id), "").status, 404);
+        assert_eq!(server.engines.idle(), 1);
+    }
+
+    #[test]
+    fn test_position_and_engine_win() {
+        let server = small_server();
+        let id = json(&server.handle("POST", "/games", ""))["id"].as_u64().unwrap();
+        let position = r#"{
+            "black": [{"row":9,"col":5},{"row":9,"col":6},{"row":9,"col":7},{"row":9,"col":8}],
+            "white": [{"row":9,"col":4}],
+            "to_move": "Black"
+        }"#;
+        assert_eq!(server.handle("PUT", &format!("/games/{}/position", id), position).status, 200);
+
+        let reply = json(&server.handle("POST", &format!("/games/{}/engine-move", id), ""));
+        assert_eq!(reply["move"], serde_json::json!({ "row": 9, "col": 9 }));
+        assert_eq!(reply["winner"], "Black");
+
+        let over = server.handle("POST", &format!("/games/{}/engine-move", id), "");
+        assert_eq!(over.status, 409);
+
+        // Restricted to a move that doesn't win
+        let analysis = r#"{
+            "black": [{"row":9,"col":5},{"row":9,"col":6},{"row":9,"col":7},{"row":9,"col":8}],
+            "white": [{"row":9,"col":4},{"row":0,"col":0}],
+            "to_move": "Black",
+            "search_moves": [{"row":3,"col":3}]
+        }"#;
+        let reply = json(&server.handle("POST", "/analyze", analysis));
+        assert_eq!(reply["best_move"], serde_json::json!({ "row": 3, "col": 3 }));
+
+        let off_board = r#"{
+            "black": [{"row":9,"col":9}],
+            "to_move": "Black",
+            "search_moves": [{"row":255,"col":255}]
+        }"#;
+        assert_eq!(server.handle("POST", "/analyze", off_board).status, 400);
+    }
+
+    #[test]
+    fn test_resign_and_draw_agreement() {
+        let server = small_server();
+        let id = json(&server.handle("POST", "/games", ""))["id"].as_u64().unwrap();
+        let path = |action: &str| format!("/games/{}/{}", id, action);
+        server.handle("POST", &path("moves"), r#""K10""#);
+
+        // Black offers on White's turn; White moves instead of answering
+        let offered = json(&server.handle("POST", &path("offer-draw"), r#"{"color":"Black"}"#));
+        assert_eq!(offered["draw_offer"], "Black");
+        assert_eq!(server.handle("POST", &path("accept-draw"), r#"{"color":"Black"}"#).status, 409);
+        let moved = json(&server.handle("POST", &path("moves"), r#""L11""#));
+        assert_eq!(moved["game"]["draw_offer"], Value::Null);
+        assert_eq!(server.handle("POST", &path("accept-draw"), "").status, 409);
+
+        server.handle("POST", &path("offer-draw"), "");
+        let drawn = json(&server.handle("POST", &path("accept-draw"), ""));
+        assert_eq!(drawn["outcome"], "AgreedDraw");
+        assert_eq!(drawn["winner"], Value::Null);
+        assert_eq!(server.handle("POST", &path("resign"), "").status, 409);
+
+        let id = json(&server.handle("POST", "/games", ""))["id"].as_u64().unwrap();
+        let resigned = json(&server.handle("POST", &format!("/games/{}/resign", id), r#"{"color":"Black"}"#));
+        assert_eq!(resigned["winner"], "White");
+        assert_eq!(resigned["outcome"]["Win"]["reason"], "Resignation");
+    }
+
+    #[test]
+    fn test_rejects_bad_requests() {
+        let server = small_server();
+        assert_eq!(server.handle("GET", "/nope", "").status, 404);
+        assert_eq!(server.handle("POST", "/games", "{not json").status, 400);
+        assert_eq!(server.handle("POST", "/analyze", r#"{"to_move":"Empty"}"#).status, 400);
+        let white_first = r#"{"white":[{"row":9,"col":9}],"to_move":"Black","strict":true}"#;
+        let reply = server.handle("POST", "/analyze", white_first);
+        assert_eq!(reply.status, 400);
+        assert!(json(&reply)["error"].as_str().unwrap().contains("impossible position"));
+
+        for bad in [
+            r#"{"max_depth":0}"#,
+            r#"{"max_depth":-3}"#,
+            r#"{"max_depth":100}"#,
+            r#"{"time_limit_ms":0}"#,
+            r#"{"time_limit_ms":18446744073709551615}"#,
+        ] {
+            assert_eq!(server.handle("POST", "/games", bad).status, 400, "{}", bad);
+        }
+        assert_eq!(server.game_count(), 0);
+
+        // More threads than the server allows runs with its limit
+        let id = json(&server.handle("POST", "/games", r#"{"threads":100000}"#))["id"].as_u64().unwrap();
+        let games = server.games.lock().unwrap();
+        assert_eq!(games[&id].lock().unwrap().engine.threads(), 1);
+        drop(games);
+        server.handle("POST", "/games", "");
+        assert_eq!(server.handle("POST", "/games", "").status, 429);
+        assert_eq!(server.game_count(), 2);
+    }
+
+    #[test]
+    fn test_moves_in_configured_notation() {
+        let bottom = Notation { origin: crate::notation::Origin::BottomLeft, skip_i: false };
+        let server = Server::new(ServerConfig { notation: bottom, ..small_server().config });
+        let id = json(&server.handle("POST", "/games", "{}"))["id"].as_u64().unwrap();
+        let moved = server.handle("POST", &format!("/games/{}/moves", id), r#""I19""#);
+        assert_eq!(moved.status, 200, "{}", moved.body);
+        assert_eq!(json(&moved)["move"], serde_json::json!({"row": 0, "col": 8}));
+        assert_eq!(server.handle("POST", &format!("/games/{}/moves", id), r#""T1""#).status, 400);
+        let occupied = server.handle("POST", &format!("/games/{}/moves", id), r#""I19""#);
+        assert!(json(&occupied)["error"].as_str().unwrap().starts_with("I19 is illegal"), "{}", occupied.body);
+    }
+
+    #[test]
+    fn test_http_round_trip() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = Arc::new(small_server());
+        thread::spawn(move || run(server, listener));
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        let body = r#"{"black":[{"row":9,"col":9}],"to_move":"White"}"#;
+        write!(
+            stream,
+            "POST /analyze HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{}", response);
+        let (_, json_body) = response.split_once("\r\n\r\n").unwrap();
+        let result: Value = serde_json::from_str(json_body).unwrap();
+        assert!(result["best_move"]["row"].is_u64());
+    }
+
+    #[test]
+    fn test_connection_limit() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || run(Arc::new(small_server()), listener));
+
+        // An idle connection holds the only place
+        let _idle = TcpStream::connect(addr).unwrap();
+        let mut refused = TcpStream::connect(addr).unwrap();
+        let mut response = String::new();
+        refused.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 503 Service Unavailable"), "{}", response);
+    }
+
+    #[test]
+    fn test_serve_request_in_memory() {
+        let server = small_server();
+        let mut output = Vec::new();
+        serve_request(&server, &b"GET /health HTTP/1.1\r\n\r\n"[..], &mut output).unwrap();
+        let response = String::from_utf8(output).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{}", response);
+        assert!(response.ends_with(r#"{"status":"ok"}"#), "{}", response);
+
+        let mut output = Vec::new();
+        serve_request(&server, &b"\r\n"[..], &mut output).unwrap();
+        assert!(String::from_utf8(output).unwrap().starts_with("HTTP/1.1 400 Bad Request"));
+
+        // Body shorter than its Content-Length
+        let truncated = &b"POST /games HTTP/1.1\r\nContent-Length: 10\r\n\r\n{}"[..];
+        assert!(serve_request(&server, truncated, &mut Vec::new()).is_err());
+    }
+}
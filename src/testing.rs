@@ -0,0 +1,191 @@
+//! Property-based testing of the rules
+//!
+//! [proptest](https://docs.rs/proptest) strategies for random legal games
+//! ([`playout`]) and board symmetries ([`symmetry`]), and the properties the
+//! rules must keep on every position of such a game: make/undo of captures
+//! restores the board ([`captures_undo`]), the incremental Zobrist hash
+//! matches a fresh one ([`zobrist_incremental`]), and double-three
+//! detection doesn't depend on how the board is turned
+//! ([`double_three_symmetric`]). The crate runs them on its own rules in
+//! its tests; a downstream crate changing the rules or adding moves reuses
+//! them with the `proptest` feature.
+//!
+//! Playouts keep to the cells next to the stones, so captures and
+//! double-threes come up often. They shrink towards fewer moves and towards
+//! the first candidate move at each ply.
+//!
+//! ```
+//! use gomoku::testing::{captures_undo, playout};
+//! use proptest::prelude::*;
+//!
+//! proptest!(ProptestConfig::with_cases(8), |(game in playout(30))| {
+//!     captures_undo(&game)?;
+//! });
+//! ```
+
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+
+use crate::board::{Board, Pos, Stone, Symmetry};
+use crate::game::Game;
+use crate::rules::{execute_captures_fast, is_double_three, is_valid_move, undo_captures};
+use crate::search::ZobristTable;
+
+/// A legal game of up to `max_plies` moves from the empty board, stopped
+/// early if it ends
+pub fn playout(max_plies: usize) -> impl Strategy<Value = Game> {
+    prop::collection::vec(any::<u16>(), 0..=max_plies).prop_map(|choices| {
+        let mut game = Game::new();
+        for choice in choices {
+            let moves = candidate_moves(&game);
+            if moves.is_empty() {
+                break;
+            }
+            let pos = moves[usize::from(choice) % moves.len()];
+            if game.play(pos).is_err() || game.is_over() {
+                break;
+            }
+        }
+        game
+    })
+}
+
+/// One of the eight rotations and reflections of the board
+pub fn symmetry() -> impl Strategy<Value = Symmetry> {
+    prop::sample::select(Symmetry::ALL.to_vec())
+}
+
+/// Legal moves next to a stone, or the center on the empty board
+fn candidate_moves(game: &Game) -> Vec<Pos> {
+    if game.is_over() {
+        return Vec::new();
+    }
+    let board = game.board();
+    if board.is_board_empty() {
+        return vec![Pos::new(9, 9)];
+    }
+    board.empty_near(1).filter(|&pos| is_valid_move(game.rules(), board, pos, game.to_move())).collect()
+}
+
+/// The positions of `game` from its start, each with the side to move
+/// and the move played from it
+fn positions(game: &Game) -> Vec<(Board, Stone, Pos)> {
+    let mut replay = Game::new();
+    let mut out = Vec::with_capacity(game.move_count());
+    for pos in game.moves() {
+        out.push((replay.board().clone(), replay.to_move(), pos));
+        replay.play(pos).expect("a played move replays");
+    }
+    out
+}
+
+/// Each move of `game` made with the search's capture execution and then
+/// undone leaves the stones and capture counts as they were.
+///
+/// # Errors
+///
+/// Fails on the first move whose undo differs.
+pub fn captures_undo(game: &Game) -> Result<(), TestCaseError> {
+    for (before, color, pos) in positions(game) {
+        let mut board = before.clone();
+        board.place_stone(pos, color);
+        let info = execute_captures_fast(game.rules(), &mut board, pos, color);
+        undo_captures(&mut board, color, &info);
+        board.remove_stone(pos);
+        prop_assert_eq!(board.bitboards(), before.bitboards(), "stones after undoing {:?}", pos);
+        prop_assert_eq!(
+            (board.captures(Stone::Black), board.captures(Stone::White)),
+            (before.captures(Stone::Black), before.captures(Stone::White)),
+            "capture counts after undoing {:?}",
+            pos
+        );
+        prop_assert!(board.check_invariants(game.rules()).is_ok(), "{:?}", board.check_invariants(game.rules()));
+    }
+    Ok(())
+}
+
+/// The hash kept up to date move by move, the way the search does, equals
+/// [`ZobristTable::hash`] of every position of `game`.
+///
+/// # Errors
+///
+/// Fails on the first position whose hashes differ.
+pub fn zobrist_incremental(game: &Game) -> Result<(), TestCaseError> {
+    let table = ZobristTable::new();
+    let mut board = Board::new();
+    let mut hash = table.hash(&board, Stone::Black);
+    for (_, color, pos) in positions(game) {
+        board.place_stone(pos, color);
+        let info = execute_captures_fast(game.rules(), &mut board, pos, color);
+        hash = table.update_place(hash, pos, color);
+        for &captured in &info.positions[..usize::from(info.count)] {
+            hash = table.update_capture(hash, captured, color.opponent());
+        }
+        if info.pairs > 0 {
+            let new_count = board.captures(color);
+            hash = table.update_capture_count(hash, color, new_count - info.pairs, new_count);
+        }
+        prop_assert_eq!(hash, table.hash(&board, color.opponent()), "after {:?}", pos);
+    }
+    Ok(())
+}
+
+/// Every empty cell of the final position of `game` is a double-three for
+/// a color exactly when its image is one on the board turned by `sym`.
+///
+/// # Errors
+///
+/// Fails on the first cell where the two disagree.
+pub fn double_three_symmetric(game: &Game, sym: Symmetry) -> Result<(), TestCaseError> {
+    let board = game.board();
+    let turned = board.transform(sym);
+    for pos in board.empty_near(2) {
+        for color in [Stone::Black, Stone::White] {
+            prop_assert_eq!(
+                is_double_three(game.rules(), board, pos, color),
+                is_double_three(game.rules(), &turned, pos.transform(sym), color),
+                "{:?} for {:?} under {:?}",
+                pos,
+                color,
+                sym
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::strategy::ValueTree;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn test_captures_undo(game in playout(80)) {
+            captures_undo(&game)?;
+        }
+
+        #[test]
+        fn test_zobrist_incremental(game in playout(80)) {
+            zobrist_incremental(&game)?;
+        }
+
+        #[test]
+        fn test_double_three_symmetric(game in playout(40), sym in symmetry()) {
+            double_three_symmetric(&game, sym)?;
+        }
+    }
+
+    #[test]
+    fn test_playouts_capture() {
+        // Dense local play captures within a few dozen moves in most games
+        let mut runner = proptest::test_runner::TestRunner::deterministic();
+        let captured = (0..32)
+            .map(|_| playout(80).new_tree(&mut runner).unwrap().current())
+            .filter(|game| game.history().any(|record| !record.captured.is_empty()))
+            .count();
+        assert!(captured >= 16, "{} of 32 playouts captured", captured);
+    }
+}
@@ -0,0 +1,435 @@
+//! Puzzle/tactics regression suite
+//!
+//! Positions with expected best moves, in an EPD-like text format, one
+//! puzzle per line:
+//!
+//! ```text
+//! <rows> <side> <black_captures> <white_captures> <opcode> <operands>; ...
+//! ```
+//!
+//! - `<rows>`: 19 rows separated by `/`, from `pos.row == 18` (labelled 19
+//!   under [`Notation::DEFAULT`](crate::notation::Notation::DEFAULT)) down
+//!   to `pos.row == 0` (labelled 1), as on a printed diagram; the GUI draws
+//!   the board the other way up. Each row is `B`, `W` and run lengths of
+//!   empty cells (`19` for an empty row, `4B14` for one stone in column E).
+//! - `<side>`: `B` or `W`, the color to move.
+//! - Opcodes:
+//!   - `bm` moves in board notation (`K10`, always
+//!     [`Notation::DEFAULT`](crate::notation::Notation::DEFAULT)); the
+//!     engine must pick one of them
+//!   - `am` moves the engine must not pick
+//!   - `id "name"` puzzle name
+//!   - `kind` one of `win`, `block`, `break`, `other`
+//!
+//! Blank lines and lines starting with `#` are ignored. Puzzles are
+//! Ninuki-renju positions: capture counts are checked against
+//! [`RuleSet::NINUKI`] and the engine plays by it.
+//!
+//! A forced win the engine reports (immediate or VCF) only passes if its
+//! full line, replies included, holds up under `ProofLine::verify`.
+//!
+//! ```
+//! use gomoku::testsuite::{parse_suite, run_puzzles, SuiteConfig};
+//!
+//! let suite = parse_suite(
+//!     "19/19/19/19/19/19/19/19/19/BBBB15/19/19/19/19/19/19/19/19/19 B 0 0 bm E10; id \"open four\"; kind win;",
+//! ).unwrap();
+//! let report = run_puzzles(&suite, &SuiteConfig::default());
+//! assert!(report.is_pass(), "{}", report);
+//!
+//! // The first line's stones are on row 19, the last line's on row 1
+//! use gomoku::notation::Notation;
+//! use gomoku::testsuite::parse_position;
+//! use gomoku::{Pos, Stone};
+//!
+//! let (board, _) = parse_position("B18/19/19/19/19/19/19/19/19/19/19/19/19/19/19/19/19/19/18W B 0 0").unwrap();
+//! assert_eq!(board.get(Pos::new(18, 0)), Stone::Black);
+//! assert_eq!(board.get(Pos::new(0, 18)), Stone::White);
+//! assert_eq!(Notation::DEFAULT.format(Pos::new(18, 0)), "A19");
+//! ```
+
+use std::fmt;
+use std::time::Instant;
+
+use crate::board::{Board, Pos, Stone, BOARD_SIZE};
+use crate::engine::{pos_to_notation, AIEngine};
+use crate::logging::log_warn;
+use crate::notation::Notation;
+use crate::rules::RuleSet;
+use crate::search::SearchParams;
+
+/// What a puzzle tests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PuzzleKind {
+    /// Side to move wins by force (five, capture win, VCF)
+    Win,
+    /// Side to move must stop a winning threat
+    Block,
+    /// Side to move must break a five by capture
+    BreakFive,
+    /// Anything else
+    Other,
+}
+
+impl PuzzleKind {
+    fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "win" => Ok(Self::Win),
+            "block" => Ok(Self::Block),
+            "break" => Ok(Self::BreakFive),
+            "other" => Ok(Self::Other),
+            _ => Err(format!("unknown kind '{}'", s)),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Win => "win",
+            Self::Block => "block",
+            Self::BreakFive => "break",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// A position with its expected answer
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    /// Name (`id`), or `line N` if the line has none
+    pub id: String,
+    pub kind: PuzzleKind,
+    pub board: Board,
+    pub to_move: Stone,
+    /// Accepted moves (`bm`); empty means any move not in `avoid`
+    pub best: Vec<Pos>,
+    /// Rejected moves (`am`)
+    pub avoid: Vec<Pos>,
+}
+
+impl Puzzle {
+    /// Whether `mov` solves the puzzle
+    pub fn is_solved_by(&self, mov: Pos) -> bool {
+        (self.best.is_empty() || self.best.contains(&mov)) && !self.avoid.contains(&mov)
+    }
+}
+
+/// Parse a suite file.
+///
+/// # Errors
+///
+/// Returns the first malformed line with its line number.
+pub fn parse_suite(text: &str) -> Result<Vec<Puzzle>, String> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|(i, line)| parse_puzzle(line, i + 1).map_err(|e| format!("line {}: {}", i + 1, e)))
+        .collect()
+}
+
+/// Parse one puzzle line (`line_no` names puzzles without an `id`).
+///
+/// # Errors
+///
+/// Returns a description of the first malformed field.
+pub fn parse_puzzle(line: &str, line_no: usize) -> Result<Puzzle, String> {
+    let mut fields = line.trim().splitn(5, char::is_whitespace);
+    let (Some(rows), Some(side), Some(black_caps), Some(white_caps)) =
+        (fields.next(), fields.next(), fields.next(), fields.next())
+    else {
+        return Err("expected <rows> <side> <black_captures> <white_captures>".to_string());
+    };
+
+    let (board, to_move) = parse_fields(rows, side, black_caps, white_caps)?;
+
+    let mut puzzle = Puzzle {
+        id: format!("line {}", line_no),
+        kind: PuzzleKind::Other,
+        board,
+        to_move,
+        best: Vec::new(),
+        avoid: Vec::new(),
+    };
+    for op in fields.next().unwrap_or("").split(';').map(str::trim).filter(|op| !op.is_empty()) {
+        let (code, operands) = op.split_once(char::is_whitespace).unwrap_or((op, ""));
+        let operands = operands.trim();
+        match code {
+            "bm" => puzzle.best = parse_moves(operands)?,
+            "am" => puzzle.avoid = parse_moves(operands)?,
+            "id" => puzzle.id = operands.trim_matches('"').to_string(),
+            "kind" => puzzle.kind = PuzzleKind::parse(operands)?,
+            _ => return Err(format!("unknown opcode '{}'", code)),
+        }
+    }
+    if puzzle.best.is_empty() && puzzle.avoid.is_empty() {
+        return Err("puzzle needs bm or am".to_string());
+    }
+    Ok(puzzle)
+}
+
+/// Parse a position written by [`format_position`].
+///
+/// # Errors
+///
+/// Returns a description of the first malformed field.
+pub fn parse_position(text: &str) -> Result<(Board, Stone), String> {
+    let fields: Vec<&str> = text.split_whitespace().collect();
+    let [rows, side, black_caps, white_caps] = fields[..] else {
+        return Err("expected <rows> <side> <black_captures> <white_captures>".to_string());
+    };
+    parse_fields(rows, side, black_caps, white_caps)
+}
+
+fn parse_fields(rows: &str, side: &str, black_caps: &str, white_caps: &str) -> Result<(Board, Stone), String> {
+    let mut board = parse_rows(rows)?;
+    for (color, caps) in [(Stone::Black, black_caps), (Stone::White, white_caps)] {
+        let pairs = caps.parse().map_err(|_| format!("bad capture count '{}'", caps))?;
+        board.set_captures(RuleSet::NINUKI, color, pairs).map_err(|e| e.to_string())?;
+    }
+    let to_move = match side {
+        "B" => Stone::Black,
+        "W" => Stone::White,
+        _ => return Err(format!("bad side to move '{}'", side)),
+    };
+    Ok((board, to_move))
+}
+
+/// Format a position as the first four fields of a puzzle line.
+pub fn format_position(board: &Board, to_move: Stone) -> String {
+    let rows: Vec<String> = (0..BOARD_SIZE as u8)
+        .rev()
+        .map(|row| {
+            let mut out = String::new();
+            let mut empty = 0;
+            for col in 0..BOARD_SIZE as u8 {
+                match board.get(Pos::new(row, col)) {
+                    Stone::Empty => empty += 1,
+                    stone => {
+                        if empty > 0 {
+                            out.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        out.push(if stone == Stone::Black { 'B' } else { 'W' });
+                    }
+                }
+            }
+            if empty > 0 {
+                out.push_str(&empty.to_string());
+            }
+            out
+        })
+        .collect();
+    format!(
+        "{} {} {} {}",
+        rows.join("/"),
+        if to_move == Stone::White { 'W' } else { 'B' },
+        board.captures(Stone::Black),
+        board.captures(Stone::White)
+    )
+}
+
+fn parse_rows(rows: &str) -> Result<Board, String> {
+    let rows: Vec<&str> = rows.split('/').collect();
+    if rows.len() != BOARD_SIZE {
+        return Err(format!("expected {} rows, got {}", BOARD_SIZE, rows.len()));
+    }
+
+    let mut board = Board::new();
+    for (i, text) in rows.iter().enumerate() {
+        let row = (BOARD_SIZE - 1 - i) as u8;
+        let mut col = 0usize;
+        let mut run = 0usize;
+        for ch in text.chars() {
+            match ch {
+                '0'..='9' => {
+                    run = run * 10 + ch as usize - '0' as usize;
+                    if run > BOARD_SIZE {
+                        return Err(format!("row {} is too long", row + 1));
+                    }
+                }
+                'B' | 'W' => {
+                    col += run;
+                    run = 0;
+                    if col >= BOARD_SIZE {
+                        return Err(format!("row {} is too long", row + 1));
+                    }
+                    let stone = if ch == 'B' { Stone::Black } else { Stone::White };
+                    board.place_stone(Pos::new(row, col as u8), stone);
+                    col += 1;
+                }
+                _ => return Err(format!("bad character '{}' in row {}", ch, row + 1)),
+            }
+        }
+        if col + run != BOARD_SIZE {
+            return Err(format!("row {} has {} cells", row + 1, col + run));
+        }
+    }
+    Ok(board)
+}
+
+fn parse_moves(operands: &str) -> Result<Vec<Pos>, String> {
+    operands
+        .split_whitespace()
+        .map(|s| Notation::DEFAULT.parse(s).map_err(|e| format!("bad move {}", e)))
+        .collect()
+}
+
+/// Engine settings for a suite run
+#[derive(Debug, Clone, Copy)]
+pub struct SuiteConfig {
+    /// Time budget per puzzle (ms)
+    pub time_limit_ms: u64,
+    pub max_depth: i8,
+    pub tt_size_mb: usize,
+    /// Search tuning, for parameter tuning runs
+    pub params: SearchParams,
+    /// Audit every search move ([`AIEngine::set_paranoid`])
+    pub paranoid: bool,
+}
+
+impl Default for SuiteConfig {
+    fn default() -> Self {
+        Self { time_limit_ms: 500, max_depth: 20, tt_size_mb: 16, params: SearchParams::default(), paranoid: false }
+    }
+}
+
+/// Engine answer for one puzzle
+#[derive(Debug, Clone)]
+pub struct PuzzleResult {
+    pub id: String,
+    pub kind: PuzzleKind,
+    pub played: Option<Pos>,
+    pub passed: bool,
+    /// Why the engine's forced-win line ([`MoveResult::proof`]) doesn't
+    /// hold, if it gave one that fails [`ProofLine::verify`]
+    ///
+    /// [`MoveResult::proof`]: crate::MoveResult::proof
+    /// [`ProofLine::verify`]: crate::search::ProofLine::verify
+    pub proof_error: Option<String>,
+    pub time_ms: u64,
+}
+
+/// Result of a suite run
+#[derive(Debug, Clone, Default)]
+pub struct SuiteReport {
+    pub results: Vec<PuzzleResult>,
+}
+
+impl SuiteReport {
+    /// Number of puzzles solved
+    pub fn passed(&self) -> usize {
+        self.results.iter().filter(|r| r.passed).count()
+    }
+
+    /// Whether every puzzle was solved
+    pub fn is_pass(&self) -> bool {
+        self.results.iter().all(|r| r.passed)
+    }
+}
+
+impl fmt::Display for SuiteReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for r in &self.results {
+            writeln!(
+                f,
+                "{} [{}] {}: {} ({}ms)",
+                if r.passed { "PASS" } else { "FAIL" },
+                r.kind.as_str(),
+                r.id,
+                r.played.map_or("no move".to_string(), pos_to_notation),
+                r.time_ms
+            )?;
+            if let Some(e) = &r.proof_error {
+                writeln!(f, "    unsound win: {}", e)?;
+            }
+        }
+        writeln!(f, "{}/{} puzzles solved", self.passed(), self.results.len())
+    }
+}
+
+/// Run the engine on every puzzle, with a cleared cache for each.
+pub fn run_puzzles(puzzles: &[Puzzle], config: &SuiteConfig) -> SuiteReport {
+    let mut engine = AIEngine::with_config(config.tt_size_mb, config.max_depth, config.time_limit_ms);
+    if let Err(e) = engine.set_search_params(config.params) {
+        log_warn!("Ignoring invalid search parameters: {}", e);
+    }
+    engine.set_paranoid(config.paranoid);
+    let results = puzzles
+        .iter()
+        .map(|puzzle| {
+            engine.clear_cache();
+            let start = Instant::now();
+            let result = engine.get_move_with_stats(&puzzle.board, puzzle.to_move);
+            let played = result.best_move;
+            let proof_error = result.proof.and_then(|proof| proof.verify(engine.rules(), &puzzle.board).err());
+            PuzzleResult {
+                id: puzzle.id.clone(),
+                kind: puzzle.kind,
+                played,
+                passed: played.is_some_and(|m| puzzle.is_solved_by(m)) && proof_error.is_none(),
+                proof_error,
+                time_ms: start.elapsed().as_millis() as u64,
+            }
+        })
+        .collect();
+    SuiteReport { results }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Built-in regression suite (the engine's tactical tests as puzzles)
+    const TACTICS: &str = include_str!("../testsuites/tactics.epd");
+
+    #[test]
+    fn test_position_round_trip() {
+        let mut board = Board::new();
+        board.place_stone(Pos::new(18, 0), Stone::Black);
+        board.place_stone(Pos::new(0, 18), Stone::White);
+        board.place_stone(Pos::new(9, 9), Stone::Black);
+        board.set_captures(RuleSet::NINUKI, Stone::White, 3).unwrap();
+
+        let line = format!("{} bm K11", format_position(&board, Stone::White));
+        let puzzle = parse_puzzle(&line, 1).unwrap();
+        assert_eq!(puzzle.board.bitboards(), board.bitboards());
+        assert_eq!((puzzle.board.captures(Stone::Black), puzzle.board.captures(Stone::White)), (0, 3));
+        assert_eq!(puzzle.to_move, Stone::White);
+        assert_eq!(puzzle.best, vec![Pos::new(10, 9)]);
+
+        let (parsed, to_move) = parse_position(&format_position(&board, Stone::Black)).unwrap();
+        assert_eq!(parsed.bitboards(), board.bitboards());
+        assert_eq!(to_move, Stone::Black);
+        assert!(parse_position(&line).is_err());
+    }
+
+    #[test]
+    fn test_notation_matches_display() {
+        for index in 0..crate::board::TOTAL_CELLS {
+            let pos = Pos::from_index(index);
+            assert_eq!(Pos::from_notation(&pos_to_notation(pos)), Ok(pos));
+        }
+    }
+
+    #[test]
+    fn test_rejects_malformed_lines() {
+        let empty = "19/19/19/19/19/19/19/19/19/19/19/19/19/19/19/19/19/19/19";
+        assert!(parse_puzzle(&format!("{} B 0 0 bm K10", empty), 1).is_ok());
+        assert!(parse_puzzle(&format!("{} B 0 0", empty), 1).is_err());
+        assert!(parse_puzzle(&format!("{} X 0 0 bm K10", empty), 1).is_err());
+        assert!(parse_puzzle(&format!("{} B 0 0 bm Z10", empty), 1).is_err());
+        assert!(parse_puzzle(&format!("{} B 0 0 bm K10; kind mate", empty), 1).is_err());
+        assert!(parse_puzzle("20/19 B 0 0 bm K10", 1).is_err());
+        assert!(parse_position(&format!("99999999999999999999999B{} B 0 0", &empty[2..])).unwrap_err().contains("too long"));
+    }
+
+    #[test]
+    fn test_tactics_suite() {
+        let puzzles = parse_suite(TACTICS).unwrap();
+        assert!(puzzles.len() >= 8);
+        let report = run_puzzles(&puzzles, &SuiteConfig::default());
+        assert!(report.is_pass(), "{}", report);
+    }
+}
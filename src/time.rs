@@ -0,0 +1,130 @@
+//! Time sources for searches
+//!
+//! Searches read the time through a [`Clock`] ([`Searcher::set_clock`]):
+//! - [`SystemClock`] (the default): the monotonic system clock, backed by
+//!   `performance.now()` on wasm through the `web-time` crate, since
+//!   `std::time::Instant::now()` panics on `wasm32-unknown-unknown`
+//! - [`ManualClock`]: time that only moves when told to, for tests
+//! - [`NoClock`]: no time at all
+//!
+//! Without a clock (a clock whose [`now`](Clock::now) is `None`, and the
+//! [`SystemClock`] without `std`) a search counts time in nodes instead:
+//! [`NODES_PER_MS`] nodes for every millisecond of a time limit, so time
+//! limits still end searches, reproducibly.
+//!
+//! ```
+//! use gomoku::search::Searcher;
+//! use gomoku::time::ManualClock;
+//! use gomoku::{Board, Pos, Stone};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! let clock = Arc::new(ManualClock::new());
+//! let mut searcher = Searcher::with_threads(4, 1);
+//! searcher.set_clock(clock.clone());
+//! clock.advance(Duration::from_millis(250));
+//!
+//! let mut board = Board::new();
+//! board.place_stone(Pos::new(9, 9), Stone::Black);
+//! let result = searcher.search_timed(&board, Stone::White, 4, 100);
+//! assert!(result.best_move.is_some());
+//! ```
+//!
+//! [`Searcher::set_clock`]: crate::search::Searcher::set_clock
+
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::time::Duration;
+
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub(crate) use std::time::Instant;
+
+#[cfg(all(feature = "std", target_arch = "wasm32"))]
+pub(crate) use web_time::Instant;
+
+/// Nodes a search without a clock spends per millisecond of its time limit
+pub const NODES_PER_MS: u64 = 100;
+
+/// A monotonic time source.
+///
+/// A clock either always or never tells the time: a search started with a
+/// reading counts on it to keep going.
+pub trait Clock: Send + Sync {
+    /// Time since a fixed moment of this clock, `None` if there is no clock
+    fn now(&self) -> Option<Duration>;
+}
+
+/// The system's monotonic clock; no clock without `std`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    #[cfg(feature = "std")]
+    fn now(&self) -> Option<Duration> {
+        static EPOCH: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
+        Some(EPOCH.get_or_init(Instant::now).elapsed())
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn now(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// A clock without time: searches count nodes instead
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoClock;
+
+impl Clock for NoClock {
+    fn now(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// A clock that stands still until [`advance`](ManualClock::advance)d, to
+/// test time management without waiting for it
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    /// Current time in microseconds
+    micros: AtomicU64,
+}
+
+impl ManualClock {
+    /// A clock at zero
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move the clock forward by `by`
+    pub fn advance(&self, by: Duration) {
+        self.micros.fetch_add(by.as_micros() as u64, Ordering::Relaxed);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Option<Duration> {
+        Some(Duration::from_micros(self.micros.load(Ordering::Relaxed)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clocks() {
+        let clock = ManualClock::new();
+        assert_eq!(clock.now(), Some(Duration::ZERO));
+        clock.advance(Duration::from_millis(3));
+        clock.advance(Duration::from_micros(5));
+        assert_eq!(clock.now(), Some(Duration::from_micros(3_005)));
+
+        assert_eq!(NoClock.now(), None);
+        if cfg!(feature = "std") {
+            let first = SystemClock.now().unwrap();
+            assert!(SystemClock.now().unwrap() >= first);
+        } else {
+            assert_eq!(SystemClock.now(), None);
+        }
+    }
+}
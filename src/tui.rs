@@ -0,0 +1,427 @@
+//! Terminal UI
+//!
+//! A full-screen front-end on ratatui for terminals and SSH sessions: the
+//! board with a cursor, the move list, and the engine's search stats, live
+//! while it thinks. Rules, captures and the result come from [`Game`], as
+//! in [`play`](crate::play), which this shares its [`PlayConfig`] with.
+//! The `gomoku-tui` binary runs it.
+//!
+//! Keys: arrows or `hjkl` move the cursor, Enter or Space plays there,
+//! `u` takes back the last move of each side, `n` starts over, `q` or Esc
+//! quits. A left click plays the point under the mouse.
+//!
+//! [`TuiApp`] holds the state and takes events one at a time, so it runs
+//! on any ratatui backend; [`run`] drives it on the real terminal.
+
+use std::io;
+use std::time::Duration;
+
+use ratatui::crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseButton, MouseEventKind,
+};
+use ratatui::crossterm::execute;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Frame;
+
+use crate::board::{Pos, Stone, BOARD_SIZE};
+use crate::engine::{AIEngine, MoveResult, SearchHandle};
+use crate::game::{Game, GameOutcome, WinReason};
+use crate::play::PlayConfig;
+
+/// Transposition table of the engine played against (MB)
+const TUI_HASH_MB: usize = 64;
+
+/// How long to wait for input before checking on the engine again
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Columns of the row labels left of the grid
+const LABEL_WIDTH: u16 = 3;
+
+/// Board width in the terminal: row labels, two columns per point, the
+/// right-hand labels and the border
+const BOARD_WIDTH: u16 = LABEL_WIDTH + 2 * BOARD_SIZE as u16 + 2 + 2;
+
+fn color_name(color: Stone) -> &'static str {
+    if color == Stone::Black { "Black" } else { "White" }
+}
+
+/// State of the terminal front-end
+pub struct TuiApp {
+    config: PlayConfig,
+    game: Game,
+    /// Engine between searches (`None` while it searches)
+    engine: Option<AIEngine>,
+    search: Option<SearchHandle>,
+    cursor: Pos,
+    /// The engine's last finished search
+    last_result: Option<MoveResult>,
+    /// Feedback on the last key (illegal move, engine busy)
+    message: Option<String>,
+    /// Where the grid was last drawn, for mapping clicks to points
+    grid: Rect,
+    quit: bool,
+}
+
+impl TuiApp {
+    /// A new game with the cursor on the center point
+    #[must_use]
+    pub fn new(config: &PlayConfig) -> Self {
+        let center = BOARD_SIZE as u8 / 2;
+        Self {
+            config: *config,
+            game: Game::new().with_notation(config.notation),
+            engine: Some(AIEngine::with_config(TUI_HASH_MB, config.max_depth, config.time_limit_ms)),
+            search: None,
+            cursor: Pos::new(center, center),
+            last_result: None,
+            message: None,
+            grid: Rect::default(),
+            quit: false,
+        }
+    }
+
+    /// The game being played
+    #[must_use]
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    #[must_use]
+    pub fn cursor(&self) -> Pos {
+        self.cursor
+    }
+
+    /// Whether the user asked to quit
+    #[must_use]
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Whether the engine is searching its move
+    #[must_use]
+    pub fn is_thinking(&self) -> bool {
+        self.search.is_some()
+    }
+
+    /// React to one terminal event
+    pub fn handle_event(&mut self, event: &Event) {
+        match event {
+            Event::Key(key) if key.kind != KeyEventKind::Release => match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+                KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1, 0),
+                KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1, 0),
+                KeyCode::Left | KeyCode::Char('h') => self.move_cursor(0, -1),
+                KeyCode::Right | KeyCode::Char('l') => self.move_cursor(0, 1),
+                KeyCode::Enter | KeyCode::Char(' ') => self.play_human(self.cursor),
+                KeyCode::Char('u') => self.undo(),
+                KeyCode::Char('n') => self.new_game(),
+                _ => {}
+            },
+            Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(pos) = self.point_at(mouse.column, mouse.row) {
+                    self.cursor = pos;
+                    self.play_human(pos);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Collect a finished engine search and start the engine's turn
+    pub fn tick(&mut self) {
+        if let Some(handle) = &mut self.search {
+            match handle.try_finish() {
+                None => return,
+                Some(Ok((result, engine))) => {
+                    self.search = None;
+                    self.engine = Some(engine);
+                    match result.best_move {
+                        Some(pos) => {
+                            if let Err(e) = self.game.play(pos) {
+                                self.message = Some(format!("Engine move rejected: {}", e));
+                            }
+                        }
+                        None => self.message = Some("The engine found no move".to_string()),
+                    }
+                    self.last_result = Some(result);
+                }
+                Some(Err(e)) => {
+                    self.search = None;
+                    self.message = Some(format!("Engine error: {}", e));
+                }
+            }
+        }
+
+        let color = self.game.to_move();
+        if !self.game.is_over() && color != self.config.human && self.search.is_none() {
+            let config = self.config;
+            let engine = self
+                .engine
+                .take()
+                .unwrap_or_else(|| AIEngine::with_config(TUI_HASH_MB, config.max_depth, config.time_limit_ms));
+            self.search = Some(engine.search_async(self.game.board(), color));
+        }
+    }
+
+    fn move_cursor(&mut self, d_row: i32, d_col: i32) {
+        let max = BOARD_SIZE as i32 - 1;
+        let row = (i32::from(self.cursor.row) + d_row).clamp(0, max) as u8;
+        let col = (i32::from(self.cursor.col) + d_col).clamp(0, max) as u8;
+        self.cursor = Pos::new(row, col);
+    }
+
+    fn play_human(&mut self, pos: Pos) {
+        if self.game.is_over() {
+            self.message = Some("The game is over: n starts a new one".to_string());
+        } else if self.game.to_move() != self.config.human {
+            self.message = Some("The engine is thinking".to_string());
+        } else {
+            self.message = self.game.play(pos).err();
+        }
+    }
+
+    /// Take back moves to the human's previous turn
+    fn undo(&mut self) {
+        if self.is_thinking() {
+            self.message = Some("The engine is thinking".to_string());
+            return;
+        }
+        self.game.pop_move();
+        if self.game.to_move() != self.config.human {
+            self.game.pop_move();
+        }
+        self.message = None;
+    }
+
+    /// Start over; a running search is cancelled and its engine dropped
+    fn new_game(&mut self) {
+        if let Some(handle) = self.search.take() {
+            handle.cancel();
+        }
+        self.game = Game::new().with_notation(self.config.notation);
+        self.last_result = None;
+        self.message = None;
+    }
+
+    /// Board point drawn at terminal cell (`column`, `row`)
+    fn point_at(&self, column: u16, row: u16) -> Option<Pos> {
+        let x = column.checked_sub(self.grid.x)?;
+        let y = row.checked_sub(self.grid.y)?;
+        let (col, row) = (x / 2, y);
+        (col < BOARD_SIZE as u16 && row < BOARD_SIZE as u16).then(|| Pos::new(row as u8, col as u8))
+    }
+
+    /// Draw the board, the engine stats, the move list and a status line
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let [main, status] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [board, side] =
+            Layout::horizontal([Constraint::Length(BOARD_WIDTH), Constraint::Min(20)]).areas(main);
+        let [stats, moves] = Layout::vertical([Constraint::Length(9), Constraint::Min(3)]).areas(side);
+
+        self.draw_board(frame, board);
+        frame.render_widget(
+            Paragraph::new(self.stats_lines()).block(Block::default().borders(Borders::ALL).title(" Engine ")),
+            stats,
+        );
+        let move_lines = self.move_lines();
+        let visible = moves.height.saturating_sub(2) as usize;
+        let skip = move_lines.len().saturating_sub(visible);
+        frame.render_widget(
+            Paragraph::new(move_lines.into_iter().skip(skip).collect::<Vec<_>>())
+                .block(Block::default().borders(Borders::ALL).title(" Moves ")),
+            moves,
+        );
+        frame.render_widget(Paragraph::new(self.status_line()), status);
+    }
+
+    fn draw_board(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default().borders(Borders::ALL).title(" Gomoku ");
+        let inner = block.inner(area);
+        self.grid = Rect::new(inner.x + LABEL_WIDTH, inner.y + 1, 2 * BOARD_SIZE as u16, BOARD_SIZE as u16);
+
+        let notation = self.config.notation;
+        let board = self.game.board();
+        let last = self.game.history().last().map(|record| record.pos);
+        let letters: String = (0..BOARD_SIZE as u8).map(|col| format!("{} ", notation.column_letter(col))).collect();
+        let header = Line::from(format!("{:width$}{}", "", letters, width = LABEL_WIDTH as usize));
+        let mut lines = vec![header.clone()];
+        for row in 0..BOARD_SIZE as u8 {
+            let number = notation.row_number(row);
+            let mut spans = vec![Span::raw(format!("{:>2} ", number))];
+            for col in 0..BOARD_SIZE as u8 {
+                let pos = Pos::new(row, col);
+                let (symbol, mut style) = match (board.get(pos), self.config.unicode) {
+                    (Stone::Black, true) => ("●", Style::default().fg(Color::Black).bg(Color::Yellow)),
+                    (Stone::White, true) => ("●", Style::default().fg(Color::White).bg(Color::Yellow)),
+                    (Stone::Empty, true) => ("·", Style::default().fg(Color::DarkGray).bg(Color::Yellow)),
+                    (Stone::Black, false) => ("X", Style::default()),
+                    (Stone::White, false) => ("O", Style::default()),
+                    (Stone::Empty, false) => (".", Style::default()),
+                };
+                if last == Some(pos) {
+                    style = style.fg(Color::Red).add_modifier(Modifier::BOLD);
+                }
+                if self.cursor == pos {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                spans.push(Span::styled(symbol, style));
+                spans.push(Span::styled(" ", style.remove_modifier(Modifier::REVERSED)));
+            }
+            spans.push(Span::raw(format!("{:>2}", number)));
+            lines.push(Line::from(spans));
+        }
+        lines.push(header);
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn stats_lines(&self) -> Vec<Line<'static>> {
+        let board = self.game.board();
+        let mut lines = vec![
+            Line::from(format!("You play {}", color_name(self.config.human))),
+            Line::from(format!("Captured pairs: B {}  W {}", board.captures(Stone::Black), board.captures(Stone::White))),
+        ];
+        let live = self.search.as_ref().and_then(SearchHandle::info);
+        if let Some(info) = live {
+            let pv: Vec<String> = info.pv.iter().map(|&pos| self.config.notation.format(pos)).collect();
+            lines.push(Line::from("Thinking...".to_string()));
+            lines.push(Line::from(format!("Depth {}  Score {}", info.depth, info.score)));
+            lines.push(Line::from(format!("Nodes {}  {} kN/s", info.nodes, info.nps)));
+            lines.push(Line::from(format!("Line {}", pv.join(" "))));
+        } else if self.is_thinking() {
+            lines.push(Line::from("Thinking...".to_string()));
+        } else if let Some(result) = &self.last_result {
+            let best = result.best_move.map_or("-".to_string(), |pos| self.config.notation.format(pos));
+            lines.push(Line::from(format!("Last: {} ({:?})", best, result.search_type)));
+            lines.push(Line::from(format!("Depth {}  Score {}", result.depth, result.score)));
+            lines.push(Line::from(format!("Nodes {}  {} kN/s", result.nodes, result.nps)));
+            lines.push(Line::from(format!("Time {}ms", result.time_ms)));
+        }
+        lines
+    }
+
+    /// Moves in numbered pairs, Black first
+    fn move_lines(&self) -> Vec<Line<'static>> {
+        let moves: Vec<String> = self.game.history().map(|record| self.config.notation.format(record.pos)).collect();
+        moves
+            .chunks(2)
+            .enumerate()
+            .map(|(i, pair)| Line::from(format!("{:>3}. {}", i + 1, pair.join("  "))))
+            .collect()
+    }
+
+    fn status_line(&self) -> Line<'static> {
+        let state = match self.game.outcome() {
+            Some(GameOutcome::Win { winner, reason: WinReason::FiveInRow }) => {
+                format!("{} wins with five in a row", color_name(winner))
+            }
+            Some(GameOutcome::Win { winner, reason: WinReason::Capture }) => {
+                format!("{} wins by captures", color_name(winner))
+            }
+            Some(GameOutcome::Win { winner, reason: WinReason::Resignation }) => {
+                format!("{} wins by resignation", color_name(winner))
+            }
+            Some(GameOutcome::Draw | GameOutcome::AgreedDraw) => "Draw".to_string(),
+            None => format!("{} to move", color_name(self.game.to_move())),
+        };
+        let text = match &self.message {
+            Some(message) => format!("{} | {}", state, message),
+            None => format!("{} | arrows/hjkl move, Enter plays, u undo, n new, q quit", state),
+        };
+        Line::from(text)
+    }
+}
+
+/// Run the terminal UI until the user quits, restoring the terminal after.
+///
+/// # Errors
+///
+/// Returns an error if the terminal can't be set up, drawn or read.
+pub fn run(config: &PlayConfig) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    execute!(io::stdout(), EnableMouseCapture)?;
+    let mut app = TuiApp::new(config);
+    let result = (|| -> io::Result<()> {
+        while !app.should_quit() {
+            app.tick();
+            terminal.draw(|frame| app.draw(frame))?;
+            if event::poll(POLL_INTERVAL)? {
+                app.handle_event(&event::read()?);
+            }
+        }
+        Ok(())
+    })();
+    let _ = execute!(io::stdout(), DisableMouseCapture);
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::{KeyEvent, KeyModifiers, MouseEvent};
+    use ratatui::Terminal;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn screen(terminal: &Terminal<TestBackend>) -> String {
+        let buffer = terminal.backend().buffer();
+        buffer.content().chunks(buffer.area.width as usize).map(|row| {
+            row.iter().map(|cell| cell.symbol()).collect::<String>() + "\n"
+        }).collect()
+    }
+
+    #[test]
+    fn test_keys_and_clicks_play_moves() {
+        let config = PlayConfig { human: Stone::Black, unicode: false, max_depth: 2, time_limit_ms: 50, ..PlayConfig::default() };
+        let mut app = TuiApp::new(&config);
+        let mut terminal = Terminal::new(TestBackend::new(100, 26)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+
+        app.handle_event(&key(KeyCode::Right));
+        app.handle_event(&key(KeyCode::Char('k')));
+        assert_eq!(app.cursor(), Pos::new(8, 10));
+        app.handle_event(&key(KeyCode::Enter));
+        assert_eq!(app.game().moves(), vec![Pos::new(8, 10)]);
+
+        // The engine answers in the background
+        app.handle_event(&key(KeyCode::Enter));
+        assert!(app.message.is_some(), "No move out of turn");
+        for _ in 0..400 {
+            app.tick();
+            if app.game().move_count() == 2 {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(app.game().move_count(), 2);
+        assert!(!app.is_thinking());
+
+        // A click on the grid plays the point under it
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let target = Pos::new(0, 0);
+        app.handle_event(&Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: app.grid.x,
+            row: app.grid.y,
+            modifiers: KeyModifiers::NONE,
+        }));
+        assert_eq!(app.game().moves()[2], target);
+
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let text = screen(&terminal);
+        assert!(text.contains(" Moves "));
+        let name = |pos: Pos| config.notation.format(pos);
+        assert!(text.contains(&format!("  1. {}  {}", name(Pos::new(8, 10)), name(app.game().moves()[1]))));
+        assert!(text.contains(&format!("  2. {}", name(target))));
+        assert!(text.contains("Depth"), "Engine stats are shown");
+
+        app.handle_event(&key(KeyCode::Char('q')));
+        assert!(app.should_quit());
+    }
+}
@@ -0,0 +1,331 @@
+//! Automated search parameter tuning (SPSA)
+//!
+//! [`tune`] improves [`SearchParams`] by simultaneous perturbation
+//! stochastic approximation. Each iteration shifts every [`TUNABLES`] entry
+//! up or down by its step at random, plays a short [`play_match`] between
+//! the shifted-up and shifted-down parameter sets, and moves the parameters
+//! toward whichever side scored better. Gains shrink over the iterations
+//! (`a / k^0.602`, `c / k^0.101`) so the parameters settle.
+//!
+//! Matches are self-play from the [`BENCH_POSITIONS`] (optionally also
+//! rotated and mirrored), each played with both colors, and every move is a node-limited search, so a run with the same
+//! [`TuneConfig`] is reproducible. `gomoku-cli tune [iterations] [file]` runs
+//! it and writes the tuned parameters in the [`SearchParams::parse`] format.
+//!
+//! ```
+//! use gomoku::search::SearchParams;
+//! use gomoku::tune::{tune, TuneConfig};
+//!
+//! let config = TuneConfig { iterations: 1, positions: 1, nodes_per_move: 300, max_moves: 4, ..TuneConfig::default() };
+//! let tuned = tune(SearchParams::default(), &config, |_, _, _| {});
+//! assert_eq!(tuned.validate(), Ok(()));
+//! ```
+
+use crate::bench::BENCH_POSITIONS;
+use crate::board::{Board, Stone, Symmetry};
+use crate::engine::AIEngine;
+use crate::game::Game;
+use crate::pool::EnginePool;
+use crate::search::{SearchLimits, SearchParams};
+use crate::testsuite::parse_position;
+
+/// TT size of each player's engine; small, matches are short
+const MATCH_TT_MB: usize = 4;
+
+/// One scalar search parameter SPSA adjusts
+#[derive(Debug, Clone, Copy)]
+pub struct Tunable {
+    pub name: &'static str,
+    /// Range the parameter is kept in
+    pub min: f64,
+    pub max: f64,
+    /// Perturbation size at the first iteration
+    pub step: f64,
+    get: fn(&SearchParams) -> f64,
+    set: fn(&mut SearchParams, f64),
+}
+
+/// Parameters [`tune`] adjusts. Ranges keep every combination valid; the
+/// per-depth lists (futility margins, move limits) keep their values.
+pub const TUNABLES: &[Tunable] = &[
+    Tunable {
+        name: "aspiration_window",
+        min: 10.0,
+        max: 1_000.0,
+        step: 50.0,
+        get: |p| f64::from(p.margins.aspiration_window),
+        set: |p, v| p.margins.aspiration_window = v.round() as i32,
+    },
+    Tunable {
+        name: "reverse_futility_per_depth",
+        min: 2_000.0,
+        max: 100_000.0,
+        step: 2_000.0,
+        get: |p| f64::from(p.margins.reverse_futility_per_depth),
+        set: |p, v| p.margins.reverse_futility_per_depth = v.round() as i32,
+    },
+    Tunable {
+        name: "razor_per_depth",
+        min: 2_000.0,
+        max: 100_000.0,
+        step: 2_000.0,
+        get: |p| f64::from(p.margins.razor_per_depth),
+        set: |p, v| p.margins.razor_per_depth = v.round() as i32,
+    },
+    Tunable {
+        name: "lmr_percent",
+        min: 10.0,
+        max: 200.0,
+        step: 10.0,
+        get: |p| f64::from(p.lmr_percent),
+        set: |p, v| p.lmr_percent = v.round() as u32,
+    },
+    Tunable {
+        name: "lmr_quiet_extra",
+        min: 0.0,
+        max: 3.0,
+        step: 1.0,
+        get: |p| f64::from(p.lmr_quiet_extra),
+        set: |p, v| p.lmr_quiet_extra = v.round() as i8,
+    },
+    Tunable {
+        name: "null_move_reduction",
+        min: 1.0,
+        max: 4.0,
+        step: 1.0,
+        get: |p| f64::from(p.null_move_reduction),
+        set: |p, v| p.null_move_reduction = v.round() as i8,
+    },
+    Tunable {
+        name: "null_move_min_depth",
+        min: 1.0,
+        max: 6.0,
+        step: 1.0,
+        get: |p| f64::from(p.null_move_min_depth),
+        set: |p, v| p.null_move_min_depth = v.round() as i8,
+    },
+    Tunable {
+        name: "max_qs_depth",
+        min: 8.0,
+        max: 32.0,
+        step: 2.0,
+        get: |p| f64::from(p.max_qs_depth),
+        set: |p, v| p.max_qs_depth = v.round() as i8,
+    },
+    Tunable {
+        name: "qs_fours_depth",
+        min: 0.0,
+        max: 8.0,
+        step: 1.0,
+        get: |p| f64::from(p.qs_fours_depth),
+        set: |p, v| p.qs_fours_depth = v.round() as i8,
+    },
+    Tunable {
+        name: "qs_defense_depth",
+        min: 0.0,
+        max: 8.0,
+        step: 1.0,
+        get: |p| f64::from(p.qs_defense_depth),
+        set: |p, v| p.qs_defense_depth = v.round() as i8,
+    },
+    Tunable {
+        name: "max_root_moves",
+        min: 5.0,
+        max: 60.0,
+        step: 4.0,
+        get: |p| p.max_root_moves as f64,
+        set: |p, v| p.max_root_moves = v.round() as usize,
+    },
+];
+
+/// Settings of a tuning run
+#[derive(Debug, Clone, Copy)]
+pub struct TuneConfig {
+    pub iterations: u32,
+    /// Start positions per match, from the front of [`BENCH_POSITIONS`];
+    /// each is played twice, colors swapped
+    pub positions: usize,
+    /// Orientations of each start position played, the first of
+    /// [`Symmetry::ALL`] (1 = as written, up to 8): more distinct games
+    /// from the same positions
+    pub orientations: usize,
+    /// Node budget of every move
+    pub nodes_per_move: u64,
+    /// Moves after which an unfinished game is a draw
+    pub max_moves: usize,
+    /// Step-size gain `a`: at 1.0 a clean match win moves each parameter
+    /// by half its current perturbation
+    pub learning_rate: f64,
+    /// Seed of the perturbation signs
+    pub seed: u64,
+}
+
+impl Default for TuneConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 100,
+            positions: BENCH_POSITIONS.len(),
+            orientations: 1,
+            nodes_per_move: 20_000,
+            max_moves: 80,
+            learning_rate: 1.0,
+            seed: 0x5EED_5A5A,
+        }
+    }
+}
+
+/// [`TUNABLES`] values of `params`
+#[must_use]
+pub fn to_vector(params: &SearchParams) -> Vec<f64> {
+    TUNABLES.iter().map(|t| (t.get)(params)).collect()
+}
+
+/// `base` with the [`TUNABLES`] set from `values`, clamped to their ranges
+#[must_use]
+pub fn from_vector(base: &SearchParams, values: &[f64]) -> SearchParams {
+    let mut params = *base;
+    for (t, &v) in TUNABLES.iter().zip(values) {
+        (t.set)(&mut params, v.clamp(t.min, t.max));
+    }
+    params
+}
+
+/// Play one game from `board` with `to_move` to play, by `black`'s rules;
+/// the winner, `None` for a draw. A side that returns no move or an illegal one loses.
+#[must_use]
+pub fn play_game(black: &mut AIEngine, white: &mut AIEngine, board: &Board, to_move: Stone, config: &TuneConfig) -> Option<Stone> {
+    let limits = SearchLimits::nodes(config.nodes_per_move);
+    let mut game = Game::from_position(black.rules(), board.clone(), to_move);
+    black.clear_cache();
+    white.clear_cache();
+    for _ in 0..config.max_moves {
+        if let Some(outcome) = game.outcome() {
+            return outcome.winner();
+        }
+        let color = game.to_move();
+        let engine = if color == Stone::Black { &mut *black } else { &mut *white };
+        let Some(mov) = engine.get_move_with_limits(game.board(), color, &limits).best_move else {
+            return Some(color.opponent());
+        };
+        if game.play(mov).is_err() {
+            return Some(color.opponent());
+        }
+    }
+    game.outcome().and_then(|outcome| outcome.winner())
+}
+
+/// The two engines a match needs, kept for every match of a run
+#[must_use]
+pub fn match_pool() -> EnginePool {
+    EnginePool::new(2, MATCH_TT_MB, 20, 500)
+}
+
+/// Score of `a` against `b` from 0.0 (lost every game) to 1.0, draws
+/// counting half. The engines are checked out of `pool` ([`match_pool`]).
+///
+/// # Panics
+///
+/// Panics if either parameter set fails [`SearchParams::validate`].
+#[must_use]
+pub fn play_match(pool: &EnginePool, a: SearchParams, b: SearchParams, config: &TuneConfig) -> f64 {
+    let engine = |params| {
+        let mut engine = pool.checkout();
+        engine.set_search_params(params).expect("tuned parameters are valid");
+        engine
+    };
+    let (mut a, mut b) = (engine(a), engine(b));
+    let mut points = 0.0;
+    let mut games = 0;
+    for text in BENCH_POSITIONS.iter().take(config.positions.max(1)) {
+        let (position, to_move) = parse_position(text).expect("bench positions parse");
+        for &sym in Symmetry::ALL.iter().take(config.orientations.clamp(1, Symmetry::ALL.len())) {
+            let board = position.transform(sym);
+            for a_color in [Stone::Black, Stone::White] {
+                let winner = if a_color == Stone::Black {
+                    play_game(&mut a, &mut b, &board, to_move, config)
+                } else {
+                    play_game(&mut b, &mut a, &board, to_move, config)
+                };
+                points += match winner {
+                    Some(color) if color == a_color => 1.0,
+                    Some(_) => 0.0,
+                    None => 0.5,
+                };
+                games += 1;
+            }
+        }
+    }
+    points / f64::from(games)
+}
+
+/// Tune `start` for `config.iterations` SPSA iterations and return the
+/// result. `progress` gets the iteration (from 1), the parameters after it
+/// and the shifted-up side's match score.
+pub fn tune(start: SearchParams, config: &TuneConfig, mut progress: impl FnMut(u32, &SearchParams, f64)) -> SearchParams {
+    let pool = match_pool();
+    let mut theta = to_vector(&start);
+    let mut seed = config.seed;
+    for k in 1..=config.iterations {
+        let a = config.learning_rate / f64::from(k).powf(0.602);
+        let c = 1.0 / f64::from(k).powf(0.101);
+        let delta: Vec<f64> = TUNABLES
+            .iter()
+            .map(|_| {
+                seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+                if seed >> 63 == 0 { 1.0 } else { -1.0 }
+            })
+            .collect();
+        let shifted = |sign: f64| -> Vec<f64> {
+            theta.iter().zip(TUNABLES).zip(&delta).map(|((&v, t), &d)| v + sign * c * t.step * d).collect()
+        };
+        let plus = from_vector(&start, &shifted(1.0));
+        let minus = from_vector(&start, &shifted(-1.0));
+
+        let score = play_match(&pool, plus, minus, config);
+        // Gradient estimate (score - 0.5) / (c * step * delta), scaled by step^2
+        for ((v, t), &d) in theta.iter_mut().zip(TUNABLES).zip(&delta) {
+            *v = (*v + a * c * t.step * (score - 0.5) * d).clamp(t.min, t.max);
+        }
+        progress(k, &from_vector(&start, &theta), score);
+    }
+    from_vector(&start, &theta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_round_trip_and_ranges() {
+        let params = SearchParams::default();
+        let values = to_vector(&params);
+        assert_eq!(values.len(), TUNABLES.len());
+        assert_eq!(from_vector(&params, &values), params);
+
+        // Every corner of the ranges is a valid parameter set
+        let low: Vec<f64> = TUNABLES.iter().map(|t| t.min).collect();
+        let high: Vec<f64> = TUNABLES.iter().map(|t| t.max).collect();
+        assert_eq!(from_vector(&params, &low).validate(), Ok(()));
+        assert_eq!(from_vector(&params, &high).validate(), Ok(()));
+        let beyond: Vec<f64> = TUNABLES.iter().map(|t| t.max * 10.0).collect();
+        assert_eq!(from_vector(&params, &beyond), from_vector(&params, &high));
+    }
+
+    #[test]
+    fn test_tuning_is_reproducible() {
+        let config = TuneConfig { iterations: 2, positions: 1, nodes_per_move: 500, max_moves: 6, ..TuneConfig::default() };
+        let mut scores = Vec::new();
+        let tuned = tune(SearchParams::default(), &config, |k, params, score| {
+            assert_eq!(params.validate(), Ok(()));
+            scores.push((k, score));
+        });
+        assert_eq!(scores.len(), 2);
+        assert!(scores.iter().all(|&(_, score)| (0.0..=1.0).contains(&score)));
+        assert_eq!(tune(SearchParams::default(), &config, |_, _, _| {}), tuned);
+
+        // Identical sides split a match evenly, in any orientation
+        let rotated = TuneConfig { orientations: 2, ..config };
+        let even = play_match(&match_pool(), SearchParams::default(), SearchParams::default(), &rotated);
+        assert!((even - 0.5).abs() < 1e-9, "{}", even);
+    }
+}
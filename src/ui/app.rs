@@ -1,27 +1,169 @@
 //! Main application for the Gomoku GUI
 
 use eframe::egui;
-use egui::{CentralPanel, Context, CornerRadius, Frame, RichText, ScrollArea, SidePanel, TopBottomPanel, Vec2};
-
-use crate::{Pos, Stone};
-use super::board_view::BoardView;
-use super::game_state::{GameMode, GameState, OpeningRule, WinType};
+use egui::{CentralPanel, Context, CornerRadius, Frame, Rect, RichText, ScrollArea, Sense, SidePanel, Stroke, TopBottomPanel, Vec2};
+
+use std::time::Duration;
+
+use crate::annotate::MoveQuality;
+use crate::archive::GameArchive;
+use crate::explorer::explore;
+use crate::clock::TimeControl;
+use crate::notation::{notation, set_notation, Origin};
+use crate::pool::EnginePool;
+use crate::record::GameRecord;
+use crate::report::PlayerReport;
+use crate::rules::{Forbidden, MAX_CAPTURE_WIN_PAIRS, MAX_PAIR_SIZE};
+use crate::testsuite::format_position;
+use crate::{classify_position, AIEngine, Pos, Stone};
+use super::board_view::{score_label, BoardView};
+use super::setup::SetupEditor;
+use super::replay::{Replay, MAX_REPLAY_INTERVAL, MIN_REPLAY_INTERVAL};
+use super::sound::{SoundEffect, Sounds};
+use super::game_state::{AiSettings, GameMode, GameState, OpeningRule, WinType, AI_RESIGN_SCORE, HUMAN_PLAYER};
 use super::theme::*;
 
+/// Replies listed in the opening explorer
+const EXPLORER_REPLIES: usize = 6;
+
+/// Time controls offered in the Game menu
+fn time_control_presets() -> [(&'static str, Option<TimeControl>); 6] {
+    let fischer = |min: u64, inc| Some(TimeControl::Fischer {
+        initial: Duration::from_secs(min * 60),
+        increment: Duration::from_secs(inc),
+    });
+    let byo_yomi = |min: u64, periods, secs| Some(TimeControl::ByoYomi {
+        main: Duration::from_secs(min * 60),
+        period: Duration::from_secs(secs),
+        periods,
+    });
+    [
+        ("Untimed", None),
+        ("3 min + 2s", fischer(3, 2)),
+        ("5 min + 3s", fischer(5, 3)),
+        ("10 min + 5s", fischer(10, 5)),
+        ("Byo-yomi 5 min, 5 x 30s", byo_yomi(5, 5, 30)),
+        ("Byo-yomi 10 min, 3 x 60s", byo_yomi(10, 3, 60)),
+    ]
+}
+
+/// Clock display: `m:ss`, or `s.t` under 10 seconds
+fn format_clock(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    if secs < 10 {
+        format!("{:.1}", remaining.as_secs_f32())
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Node count with a `K`/`M` suffix
+fn format_nodes(nodes: u64) -> String {
+    if nodes >= 1_000_000 {
+        format!("{:.1}M", nodes as f64 / 1_000_000.0)
+    } else if nodes >= 1_000 {
+        format!("{:.1}K", nodes as f64 / 1_000.0)
+    } else {
+        format!("{}", nodes)
+    }
+}
+
+/// Inaccuracies, mistakes and blunders of a player as `1 / 0 / 2` (`-`
+/// before the moves are analyzed)
+fn quality_counts(player: &PlayerReport) -> String {
+    if player.accuracy.is_none() {
+        return "-".to_string();
+    }
+    format!("{} / {} / {}", player.inaccuracies, player.mistakes, player.blunders)
+}
+
+/// Text color for a move of the given quality
+fn quality_color(quality: MoveQuality) -> egui::Color32 {
+    match quality {
+        MoveQuality::Best => TIMER_NORMAL,
+        MoveQuality::Good => TEXT_SECONDARY,
+        MoveQuality::Inaccuracy => TIMER_WARNING,
+        MoveQuality::Mistake | MoveQuality::Blunder => TIMER_CRITICAL,
+    }
+}
+
 /// Main Gomoku application
 pub struct GomokuApp {
+    /// Game on screen: the active tab's
     state: GameState,
+    /// Open games in tab order; `None` marks the active tab, whose game
+    /// is `state`. The others keep playing in the background.
+    tabs: Vec<Option<GameState>>,
+    active_tab: usize,
+    /// Engines for the games in the tabs, reused as tabs close and open
+    engine_pool: EnginePool,
     board_view: BoardView,
     show_debug: bool,
+    show_eval_bar: bool,
+    show_score_graph: bool,
+    show_settings: bool,
+    show_record_window: bool,
+    /// File path for saving and loading games, and the last result
+    record_path: String,
+    record_status: Option<Result<String, String>>,
+    /// Games archive, loaded from its file once archiving or the opening
+    /// explorer needs it; finished games are appended while `archiving`
+    archive: Option<GameArchive>,
+    archive_path: String,
+    archiving: bool,
+    show_explorer: bool,
+    /// The last "openings I keep losing" report
+    opening_report: Option<String>,
+    /// Result of the last game report export
+    report_status: Option<Result<String, String>>,
+    /// A loaded game being replayed in place of the live board
+    replay: Option<Replay>,
+    /// A position being set up in place of the live board
+    setup: Option<SetupEditor>,
+    sounds: Sounds,
+    /// Moves, captured pairs and game-over state at the last frame, to
+    /// detect events that play a sound
+    seen_moves: usize,
+    seen_captures: u8,
+    seen_game_over: bool,
     new_game_requested: bool,
 }
 
 impl Default for GomokuApp {
     fn default() -> Self {
+        let settings = AiSettings::default();
+        let engine_pool = EnginePool::with_threads(
+            1,
+            settings.hash_mb,
+            settings.max_depth,
+            settings.time_limit_ms,
+            settings.threads,
+        );
         Self {
-            state: GameState::new(GameMode::default()),
+            state: GameState::with_engine_pool(GameMode::default(), OpeningRule::Standard, &engine_pool),
+            tabs: vec![None],
+            active_tab: 0,
+            engine_pool,
             board_view: BoardView::default(),
             show_debug: true,
+            show_eval_bar: true,
+            show_score_graph: true,
+            show_settings: false,
+            show_record_window: false,
+            record_path: "game.sgf".to_string(),
+            record_status: None,
+            archive: None,
+            archive_path: "games.txt".to_string(),
+            archiving: false,
+            show_explorer: false,
+            opening_report: None,
+            report_status: None,
+            replay: None,
+            setup: None,
+            sounds: Sounds::default(),
+            seen_moves: 0,
+            seen_captures: 0,
+            seen_game_over: false,
             new_game_requested: false,
         }
     }
@@ -33,6 +175,112 @@ impl GomokuApp {
         Self::default()
     }
 
+    /// Replace the game, keeping the time control and AI settings
+    fn start_new_game(&mut self, mode: GameMode, rule: OpeningRule) {
+        let time_control = self.state.time_control;
+        let ai_settings = self.state.ai_settings();
+        self.state = GameState::with_engine_pool(mode, rule, &self.engine_pool);
+        self.state.set_time_control(time_control);
+        self.state.set_ai_settings(ai_settings);
+        self.report_status = None;
+    }
+
+    /// Open a tab with a new game set up like the active one (mode,
+    /// opening rule, time control, AI settings) and switch to it
+    fn open_tab(&mut self) {
+        let mut game = GameState::with_engine_pool(self.state.mode, self.state.opening_rule, &self.engine_pool);
+        game.set_time_control(self.state.time_control);
+        game.set_ai_settings(self.state.ai_settings());
+        game.set_ai_resign_threshold(self.state.ai_resign_threshold());
+        self.tabs.push(Some(game));
+        self.switch_tab(self.tabs.len() - 1);
+    }
+
+    /// Bring tab `index` on screen. The position editor, replay and
+    /// report export belong to the game that was shown and close.
+    fn switch_tab(&mut self, index: usize) {
+        let Some(next) = self.tabs.get_mut(index).and_then(Option::take) else {
+            return;
+        };
+        self.tabs[self.active_tab] = Some(std::mem::replace(&mut self.state, next));
+        self.active_tab = index;
+        self.setup = None;
+        self.replay = None;
+        self.report_status = None;
+        self.board_view.cursor = None;
+        // No sounds for what happened in the background
+        self.seen_moves = self.state.move_history.len();
+        self.seen_captures = self.state.board.captures(Stone::Black) + self.state.board.captures(Stone::White);
+        self.seen_game_over = self.state.game_over.is_some();
+    }
+
+    /// Close tab `index` unless it is the last one; its engine goes back
+    /// to the pool
+    fn close_tab(&mut self, index: usize) {
+        if self.tabs.len() < 2 || index >= self.tabs.len() {
+            return;
+        }
+        if index == self.active_tab {
+            self.switch_tab(if index + 1 < self.tabs.len() { index + 1 } else { index - 1 });
+        }
+        self.tabs.remove(index);
+        if self.active_tab > index {
+            self.active_tab -= 1;
+        }
+    }
+
+    /// Tab label: number, mode, the AI's depth and time, and a mark once over
+    fn tab_title(index: usize, state: &GameState) -> String {
+        let mode = match state.mode {
+            GameMode::PvE { human_color: Stone::White } => "PvE White",
+            GameMode::PvE { .. } => "PvE Black",
+            GameMode::PvP { .. } => "PvP",
+            GameMode::AiVsAi => "AI vs AI",
+        };
+        let settings = state.ai_settings();
+        let strength = match state.mode {
+            GameMode::PvP { .. } => String::new(),
+            _ => format!(" d{} {}ms", settings.max_depth, settings.time_limit_ms),
+        };
+        let over = if state.game_over.is_some() { " (over)" } else { "" };
+        format!("{}: {}{}{}", index + 1, mode, strength, over)
+    }
+
+    /// Render the row of game tabs (only with more than one game open)
+    fn render_tab_bar(&mut self, ctx: &Context) {
+        if self.tabs.len() < 2 {
+            return;
+        }
+        let (mut switch, mut close, mut open) = (None, None, false);
+        TopBottomPanel::top("tab_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                for index in 0..self.tabs.len() {
+                    let state = self.tabs[index].as_ref().unwrap_or(&self.state);
+                    let title = Self::tab_title(index, state);
+                    if ui.selectable_label(index == self.active_tab, title).clicked() {
+                        switch = Some(index);
+                    }
+                    if ui.small_button("x").on_hover_text("Close this game").clicked() {
+                        close = Some(index);
+                    }
+                    ui.add_space(6.0);
+                }
+                if ui.small_button("+").on_hover_text("New game in a tab, set up like this one").clicked() {
+                    open = true;
+                }
+            });
+        });
+        if let Some(index) = switch {
+            self.switch_tab(index);
+        }
+        if let Some(index) = close {
+            self.close_tab(index);
+        }
+        if open {
+            self.open_tab();
+        }
+    }
+
     /// Render the top menu bar
     fn render_menu_bar(&mut self, ctx: &Context) {
         TopBottomPanel::top("menu_bar").show(ctx, |ui| {
@@ -41,8 +289,7 @@ impl GomokuApp {
                     ui.menu_button("New Game (PvE - Black)", |ui| {
                         for (label, rule) in [("Standard", OpeningRule::Standard), ("Pro", OpeningRule::Pro), ("Swap", OpeningRule::Swap)] {
                             if ui.button(label).clicked() {
-                                self.state = GameState::with_opening_rule(
-                                    GameMode::PvE { human_color: Stone::Black }, rule);
+                                self.start_new_game(GameMode::PvE { human_color: Stone::Black }, rule);
                                 ui.close_menu();
                             }
                         }
@@ -50,8 +297,7 @@ impl GomokuApp {
                     ui.menu_button("New Game (PvE - White)", |ui| {
                         for (label, rule) in [("Standard", OpeningRule::Standard), ("Pro", OpeningRule::Pro), ("Swap", OpeningRule::Swap)] {
                             if ui.button(label).clicked() {
-                                self.state = GameState::with_opening_rule(
-                                    GameMode::PvE { human_color: Stone::White }, rule);
+                                self.start_new_game(GameMode::PvE { human_color: Stone::White }, rule);
                                 ui.close_menu();
                             }
                         }
@@ -59,8 +305,7 @@ impl GomokuApp {
                     ui.menu_button("New Game (PvP)", |ui| {
                         for (label, rule) in [("Standard", OpeningRule::Standard), ("Pro", OpeningRule::Pro), ("Swap", OpeningRule::Swap)] {
                             if ui.button(label).clicked() {
-                                self.state = GameState::with_opening_rule(
-                                    GameMode::PvP { show_suggestions: false }, rule);
+                                self.start_new_game(GameMode::PvP { show_suggestions: false }, rule);
                                 ui.close_menu();
                             }
                         }
@@ -68,21 +313,106 @@ impl GomokuApp {
                     ui.menu_button("New Game (AI vs AI)", |ui| {
                         for (label, rule) in [("Standard", OpeningRule::Standard), ("Pro", OpeningRule::Pro), ("Swap", OpeningRule::Swap)] {
                             if ui.button(label).clicked() {
-                                self.state = GameState::with_opening_rule(
-                                    GameMode::AiVsAi, rule);
+                                self.start_new_game(GameMode::AiVsAi, rule);
                                 ui.close_menu();
                             }
                         }
                     });
+                    if ui.button("New Tab").on_hover_text("Another game alongside this one, set up the same").clicked() {
+                        self.open_tab();
+                        ui.close_menu();
+                    }
+                    if ui.add_enabled(self.tabs.len() > 1, egui::Button::new("Close Tab")).clicked() {
+                        self.close_tab(self.active_tab);
+                        ui.close_menu();
+                    }
                     ui.separator();
                     if ui.button("Undo").clicked() {
                         self.state.undo();
                         ui.close_menu();
                     }
+                    ui.menu_button("Time Control", |ui| {
+                        for (label, control) in time_control_presets() {
+                            if ui.radio(self.state.time_control == control, label).clicked() {
+                                self.state.set_time_control(control);
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                    let mut auto_resign = self.state.ai_resign_threshold().is_some();
+                    if ui.checkbox(&mut auto_resign, "AI Resigns Lost Games").changed() {
+                        self.state.set_ai_resign_threshold(auto_resign.then_some(AI_RESIGN_SCORE));
+                    }
+                    if ui.button("AI Settings...").clicked() {
+                        self.show_settings = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("Save / Load Game...").clicked() {
+                        self.show_record_window = true;
+                        ui.close_menu();
+                    }
+                    if ui.add_enabled(!self.state.is_ai_thinking(), egui::Button::new("Set Up Position...")).clicked() {
+                        let rules = self.state.rule_set();
+                        self.setup = Some(SetupEditor::new(rules, &self.state.board, self.state.current_turn));
+                        self.replay = None;
+                        ui.close_menu();
+                    }
+                    if ui.button("Copy Position").on_hover_text("Copy the board as text (puzzle format)").clicked() {
+                        ui.ctx().copy_text(format_position(&self.state.board, self.state.current_turn));
+                        ui.close_menu();
+                    }
+                    let paste = ui
+                        .add_enabled(!self.state.is_ai_thinking(), egui::Button::new("Paste Position..."))
+                        .on_hover_text("Open the position editor; Ctrl+V on the board loads copied text");
+                    if paste.clicked() {
+                        let rules = self.state.rule_set();
+                        self.setup = Some(SetupEditor::new(rules, &self.state.board, self.state.current_turn));
+                        self.replay = None;
+                        ui.close_menu();
+                    }
                 });
 
                 ui.menu_button("View", |ui| {
                     ui.checkbox(&mut self.show_debug, "Debug Panel (D)");
+                    let explorer = ui
+                        .checkbox(&mut self.show_explorer, "Opening Explorer")
+                        .on_hover_text("Archived games and book moves from the shown position");
+                    if explorer.changed() && self.show_explorer {
+                        self.load_archive();
+                    }
+                    ui.checkbox(&mut self.state.show_hints, "Move Hints");
+                    ui.checkbox(&mut self.board_view.show_forbidden, "Forbidden Points");
+                    ui.checkbox(&mut self.board_view.show_heatmap, "Candidate Heatmap")
+                        .on_hover_text("Moves the engine would search, hotter = tried earlier");
+                    ui.checkbox(&mut self.board_view.animate_moves, "Move Animation");
+                    ui.checkbox(&mut self.board_view.confirm_moves, "Confirm Moves (Two Taps)")
+                        .on_hover_text("First click selects a point, a second click on it plays");
+                    ui.checkbox(&mut self.board_view.flipped, "Flip Board");
+                    ui.add_enabled(Sounds::AVAILABLE, egui::Checkbox::new(&mut self.sounds.enabled, "Sound Effects"))
+                        .on_disabled_hover_text("Built without the `sound` feature");
+                    ui.separator();
+                    ui.menu_button("Board Theme", |ui| {
+                        for palette in Palette::ALL {
+                            ui.radio_value(&mut self.board_view.theme.palette, palette, palette.name());
+                        }
+                    });
+                    ui.menu_button("Stones", |ui| {
+                        for style in StoneStyle::ALL {
+                            ui.radio_value(&mut self.board_view.theme.stone_style, style, style.name());
+                        }
+                    });
+                    ui.menu_button("Coordinates", |ui| {
+                        let mut chosen = notation();
+                        for origin in Origin::ALL {
+                            ui.radio_value(&mut chosen.origin, origin, origin.name());
+                        }
+                        ui.checkbox(&mut chosen.skip_i, "Skip I");
+                        if chosen != notation() {
+                            set_notation(chosen);
+                        }
+                    });
+                    ui.checkbox(&mut self.show_eval_bar, "Evaluation Bar");
+                    ui.checkbox(&mut self.show_score_graph, "Score Graph");
                 });
 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
@@ -145,16 +475,33 @@ impl GomokuApp {
 
                     ui.add_space(4.0);
 
+                    if self.replay.is_some() {
+                        self.render_replay_section(ui);
+                        ui.add_space(4.0);
+                    }
+
+                    if self.setup.is_some() {
+                        self.render_setup_section(ui);
+                        ui.add_space(4.0);
+                    }
+
                     // Game over (shown at top when game is over for visibility)
                     if self.state.game_over.is_some() {
                         self.render_game_over_section(ui);
                         ui.add_space(4.0);
+                        self.render_report_section(ui);
                     }
 
                     // Turn + Timer + Actions (combined)
                     self.render_turn_section(ui);
                     ui.add_space(4.0);
 
+                    // Live search progress
+                    if self.state.is_ai_thinking() {
+                        self.render_search_section(ui);
+                        ui.add_space(4.0);
+                    }
+
                     // Message (invalid move feedback)
                     if let Some(msg) = &self.state.message {
                         Frame::new()
@@ -174,6 +521,17 @@ impl GomokuApp {
                     self.render_captures_section(ui);
                     ui.add_space(4.0);
 
+                    if self.show_explorer {
+                        self.render_explorer_section(ui);
+                        ui.add_space(4.0);
+                    }
+
+                    // Score graph
+                    if self.show_score_graph {
+                        self.render_score_graph(ui);
+                        ui.add_space(4.0);
+                    }
+
                     // Debug (if enabled)
                     if self.show_debug {
                         self.render_debug_section(ui);
@@ -208,15 +566,72 @@ impl GomokuApp {
                 }
 
                 if let GameMode::PvP { .. } = self.state.mode {
-                    if ui.small_button("Hint").clicked() {
+                    if ui.add_enabled(!self.state.is_suggesting(), egui::Button::new("Hint").small()).clicked() {
                         self.state.request_suggestion();
                     }
                 }
 
+                if self.state.is_ai_thinking()
+                    && ui.small_button("Cancel").on_hover_text("Stop the search and play the best move so far").clicked()
+                {
+                    self.state.cancel_ai_thinking();
+                }
+
             });
+
+            if let Some(hint) = &self.state.suggestion {
+                ui.label(RichText::new(format!("Hint: {}", hint)).size(11.0).color(ACCENT_BLUE));
+            }
+
+            // Spoken by screen readers whenever they change
+            Self::live_label(ui, &self.state.announcement());
+            let board = self.setup.as_ref().map_or(&self.state.board, |setup| &setup.board);
+            if let Some(cursor) = self.board_view.cursor_description(board) {
+                Self::live_label(ui, &cursor);
+            }
+
+            // Resign / draw (human players only, while the game is running)
+            if self.state.game_over.is_none() && self.state.mode != GameMode::AiVsAi {
+                let player = match self.state.mode {
+                    GameMode::PvE { human_color } => human_color,
+                    _ => self.state.current_turn,
+                };
+                ui.add_space(3.0);
+                ui.horizontal(|ui| {
+                    if let Some(offerer) = self.state.draw_offer.filter(|&o| o != player) {
+                        let name = if offerer == Stone::Black { "Black" } else { "White" };
+                        ui.label(RichText::new(format!("{} offers a draw", name)).size(10.0).color(TEXT_SECONDARY));
+                        if ui.small_button("Accept").clicked() {
+                            if let Err(msg) = self.state.accept_draw() {
+                                self.state.message = Some(msg);
+                            }
+                        }
+                        if ui.small_button("Decline").clicked() {
+                            self.state.decline_draw();
+                        }
+                    } else {
+                        if ui.small_button("Resign").clicked() {
+                            if let Err(msg) = self.state.resign(player) {
+                                self.state.message = Some(msg);
+                            }
+                        }
+                        if ui.small_button("Offer Draw").clicked() {
+                            if let Err(msg) = self.state.offer_draw(player) {
+                                self.state.message = Some(msg);
+                            }
+                        }
+                    }
+                });
+            }
         });
     }
 
+    /// A small label that screen readers announce each time its text changes
+    fn live_label(ui: &mut egui::Ui, text: &str) {
+        let response = ui.label(RichText::new(text).size(10.0).color(TEXT_SECONDARY));
+        ui.ctx().accesskit_node_builder(response.id, |node| node.set_live(egui::accesskit::Live::Polite));
+    }
+
     /// Render a single turn row (Black or White)
     fn render_turn_row(ui: &mut egui::Ui, is_black: bool, is_active: bool, state: &GameState) {
         let color_name = if is_black { "BLACK" } else { "WHITE" };
@@ -247,20 +662,50 @@ impl GomokuApp {
             ui.vertical(|ui| {
                 ui.label(RichText::new(color_name).size(13.0).strong().color(name_color));
                 if is_active {
-                    let (status_text, status_color) = if state.is_ai_thinking() {
-                        ("AI thinking...", TIMER_WARNING)
-                    } else if state.game_over.is_some() {
-                        ("Game Over", WIN_HIGHLIGHT)
+                    if let Some((depth, nodes)) = state.ai_progress() {
+                        // Live search progress
+                        ui.horizontal(|ui| {
+                            ui.spacing_mut().item_spacing.x = 3.0;
+                            ui.add(egui::Spinner::new().size(9.0).color(TIMER_WARNING));
+                            ui.label(RichText::new("thinking\u{2026}").size(9.0).color(TIMER_WARNING));
+                            ui.label(RichText::new(format!("d{} \u{b7} {}", depth, format_nodes(nodes))).size(9.0).color(TEXT_SECONDARY));
+                        });
+                        // Score and expected line of the last completed depth
+                        if let Some(info) = state.ai_search_info() {
+                            let line: Vec<String> = info.pv.iter().take(4).map(|&pos| crate::engine::pos_to_notation(pos)).collect();
+                            ui.label(RichText::new(format!("{:+} {}", info.score, line.join(" "))).size(9.0).color(TEXT_MUTED));
+                        }
                     } else {
-                        ("to move", TIMER_NORMAL)
-                    };
-                    ui.label(RichText::new(status_text).size(9.0).color(status_color));
+                        let (status_text, status_color) = if state.game_over.is_some() {
+                            ("Game Over", WIN_HIGHLIGHT)
+                        } else {
+                            ("to move", TIMER_NORMAL)
+                        };
+                        ui.label(RichText::new(status_text).size(9.0).color(status_color));
+                    }
                 }
             });
 
             // Timer (right-aligned)
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                if is_active {
+                if let Some(clock) = &state.clock {
+                    let color = if is_black { Stone::Black } else { Stone::White };
+                    let remaining = clock.remaining(color);
+                    let mut text = format_clock(remaining);
+                    if clock.in_byo_yomi(color) {
+                        text = format!("{} ({})", text, clock.periods_left(color));
+                    }
+                    let text_color = if !is_active {
+                        TEXT_MUTED
+                    } else if remaining < Duration::from_secs(10) {
+                        TIMER_CRITICAL
+                    } else if remaining < Duration::from_secs(30) {
+                        TIMER_WARNING
+                    } else {
+                        TEXT_PRIMARY
+                    };
+                    ui.label(RichText::new(text).size(18.0).strong().monospace().color(text_color));
+                } else if is_active {
                     // Active side: live timer
                     if state.is_ai_thinking() {
                         if let Some(elapsed) = state.ai_thinking_elapsed() {
@@ -304,22 +749,72 @@ impl GomokuApp {
         });
     }
 
-    /// Render captures section with painted stones
+    /// Render the running AI search: depth, nodes and speed so far, with
+    /// the score and best line of the last completed depth
+    fn render_search_section(&self, ui: &mut egui::Ui) {
+        let state = &self.state;
+        let (Some((depth, nodes)), Some(nps)) = (state.ai_progress(), state.ai_nps()) else {
+            return;
+        };
+        let info = state.ai_search_info();
+        Self::render_card(ui, Some(("SEARCH", TIMER_WARNING)), |ui| {
+            egui::Grid::new("live_search_grid")
+                .num_columns(2)
+                .min_col_width(ui.available_width() / 2.0 - 8.0)
+                .spacing([8.0, 2.0])
+                .show(ui, |ui| {
+                    Self::grid_row(ui, "Depth", &format!("{}", depth), TEXT_PRIMARY);
+                    Self::grid_row(ui, "Nodes", &format_nodes(nodes), TEXT_SECONDARY);
+                    Self::grid_row(ui, "Speed", &format!("{} kN/s", nps), TEXT_SECONDARY);
+                    if let Some(info) = &info {
+                        let color = if info.score > 0 { TIMER_NORMAL } else { TEXT_SECONDARY };
+                        Self::grid_row(ui, "Score", &format!("{:+}", info.score), color);
+                    }
+                });
+            if let Some(info) = info.filter(|info| !info.pv.is_empty()) {
+                ui.add_space(2.0);
+                let line: Vec<String> = info.pv.iter().map(|&pos| crate::engine::pos_to_notation(pos)).collect();
+                ui.add(egui::Label::new(RichText::new(line.join(" ")).size(11.0).monospace().color(TEXT_PRIMARY)).wrap());
+            }
+        });
+    }
+
+    /// Render captures section with painted stones, and a status line from
+    /// [`classify_position`] (phase, capture race, winning threats)
     fn render_captures_section(&self, ui: &mut egui::Ui) {
         Self::render_card(ui, Some(("CAPTURES", TEXT_MUTED)), |ui| {
-            self.render_capture_row_painted(ui, true, self.state.board.black_captures);
+            self.render_capture_tray(ui, Stone::Black, self.state.board.captures(Stone::Black));
             ui.add_space(4.0);
-            self.render_capture_row_painted(ui, false, self.state.board.white_captures);
+            self.render_capture_tray(ui, Stone::White, self.state.board.captures(Stone::White));
+            ui.add_space(4.0);
+            let class = classify_position(self.state.rule_set(), &self.state.board);
+            let color = if class.black.threatens_win() || class.white.threatens_win() { TIMER_WARNING } else { TEXT_SECONDARY };
+            ui.label(RichText::new(class.to_string()).size(10.0).color(color));
         });
     }
 
-    /// Render capture row with painted circles
-    fn render_capture_row_painted(&self, ui: &mut egui::Ui, is_black: bool, captures: u8) {
+    /// Render a player's tray: one slot per capture, holding the captured
+    /// pair in the opponent's color. Pairs from the capture being animated
+    /// on the board drop into their slots as the stones disappear.
+    fn render_capture_tray(&self, ui: &mut egui::Ui, capturer: Stone, captures: u8) {
+        let captured_color = capturer.opponent();
+        let rules = self.state.rule_set();
+        let pair_size = usize::from(rules.pair_size());
+        let win_pairs = rules.capture_win_pairs();
+        // Slots filled by the capture currently animating (pop in with its progress)
+        let (new_pairs, progress) = match &self.state.capture_animation {
+            Some(animation) if animation.captured_color == captured_color => {
+                ((animation.positions.len() / pair_size) as u8, animation.progress())
+            }
+            _ => (0, 1.0),
+        };
+        let first_new = captures.saturating_sub(new_pairs);
+
         ui.horizontal(|ui| {
             // Fixed-width label for consistent alignment
-            let (label_rect, _) = ui.allocate_exact_size(Vec2::new(14.0, 20.0), egui::Sense::hover());
-            let label = if is_black { "B" } else { "W" };
-            let label_color = if is_black { egui::Color32::from_rgb(140, 140, 150) } else { egui::Color32::from_rgb(200, 200, 210) };
+            let (label_rect, _) = ui.allocate_exact_size(Vec2::new(14.0, 24.0), egui::Sense::hover());
+            let label = if capturer == Stone::Black { "B" } else { "W" };
+            let label_color = if capturer == Stone::Black { egui::Color32::from_rgb(140, 140, 150) } else { egui::Color32::from_rgb(200, 200, 210) };
             ui.painter().text(
                 label_rect.center(),
                 egui::Align2::CENTER_CENTER,
@@ -329,48 +824,62 @@ impl GomokuApp {
             );
             ui.add_space(2.0);
 
-            for i in 0..5u8 {
-                let (rect, _) = ui.allocate_exact_size(Vec2::new(24.0, 24.0), egui::Sense::hover());
-                let center = rect.center();
-                let filled = i < captures;
-                let near_win = captures >= 4 && filled;
-
-                if filled {
-                    let fill = if near_win {
-                        egui::Color32::from_rgb(255, 60, 60)
-                    } else if is_black {
-                        egui::Color32::from_rgb(25, 25, 30)
-                    } else {
-                        egui::Color32::from_rgb(250, 250, 252)
-                    };
-                    ui.painter().circle_filled(center + Vec2::new(0.8, 0.8), 9.0, egui::Color32::from_rgba_unmultiplied(0, 0, 0, 40));
-                    ui.painter().circle_filled(center, 9.0, fill);
-                    let ring_color = if near_win {
-                        egui::Color32::from_rgb(255, 200, 100)
-                    } else if is_black {
-                        egui::Color32::from_rgb(80, 80, 90)
-                    } else {
-                        egui::Color32::from_rgb(190, 190, 200)
-                    };
-                    ui.painter().circle_stroke(center, 9.0, egui::Stroke::new(1.5, ring_color));
-                } else {
-                    ui.painter().circle_stroke(center, 9.0, egui::Stroke::new(1.0, ACCENT_DIM));
+            let (tray, _) = ui.allocate_exact_size(Vec2::new(5.0 * 30.0, 24.0), egui::Sense::hover());
+            let near_win = captures + 1 >= win_pairs;
+            ui.painter().rect(
+                tray,
+                CornerRadius::same(4),
+                PANEL_CARD_ACCENT,
+                egui::Stroke::new(1.0, if near_win { TIMER_CRITICAL } else { ACCENT_DIM }),
+                egui::StrokeKind::Inside,
+            );
+
+            // Slots share the tray; stones shrink to fit more slots or larger pairs
+            let slot_width = tray.width() / f32::from(win_pairs);
+            let radius = 6.5f32.min(slot_width * 0.65 / pair_size as f32);
+            let spacing = radius * 18.0 / 13.0;
+            for slot in 0..win_pairs {
+                let center = egui::pos2(tray.left() + slot_width * (f32::from(slot) + 0.5), tray.center().y);
+                if slot >= captures {
+                    ui.painter().circle_stroke(center, 3.0, egui::Stroke::new(1.0, ACCENT_DIM));
+                    continue;
+                }
+                // Pop in: grow from nothing during the first half of the board animation
+                let scale = if slot >= first_new { (progress * 2.0).min(1.0) } else { 1.0 };
+                if scale <= 0.0 {
+                    continue;
+                }
+                for i in 0..pair_size {
+                    let dx = (i as f32 - (pair_size - 1) as f32 / 2.0) * spacing;
+                    Self::paint_tray_stone(ui.painter(), center + Vec2::new(dx, 0.0), radius * scale, captured_color);
                 }
             }
 
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                let color = if captures >= 5 {
+                let color = if captures >= win_pairs {
                     WIN_HIGHLIGHT
-                } else if captures >= 4 {
+                } else if near_win {
                     TIMER_CRITICAL
                 } else {
                     TEXT_SECONDARY
                 };
-                ui.label(RichText::new(format!("{}/5", captures)).size(13.0).strong().color(color));
+                ui.label(RichText::new(format!("{}/{}", captures, win_pairs)).size(13.0).strong().color(color));
             });
         });
     }
 
+    /// Paint one small stone in a capture tray
+    fn paint_tray_stone(painter: &egui::Painter, center: egui::Pos2, radius: f32, color: Stone) {
+        let (fill, ring) = if color == Stone::Black {
+            (BLACK_STONE, BLACK_STONE_HIGHLIGHT)
+        } else {
+            (WHITE_STONE, WHITE_STONE_SHADOW)
+        };
+        painter.circle_filled(center + Vec2::new(0.6, 0.6), radius, egui::Color32::from_rgba_unmultiplied(0, 0, 0, 40));
+        painter.circle_filled(center, radius, fill);
+        painter.circle_stroke(center, radius, egui::Stroke::new(1.0, ring));
+    }
+
     /// Helper: render a key-value row in a grid
     fn grid_row(ui: &mut egui::Ui, label: &str, value: &str, value_color: egui::Color32) {
         ui.label(RichText::new(label).size(11.0).color(TEXT_MUTED));
@@ -400,6 +909,7 @@ impl GomokuApp {
                         crate::engine::SearchType::VCF => ("VCF", WIN_HIGHLIGHT),
                         crate::engine::SearchType::Defense => ("Defense", TIMER_CRITICAL),
                         crate::engine::SearchType::AlphaBeta => ("Alpha-Beta", TIMER_NORMAL),
+                        crate::engine::SearchType::Swindle => ("Swindle", TIMER_CRITICAL),
                     };
 
                     ui.horizontal(|ui| {
@@ -421,18 +931,13 @@ impl GomokuApp {
 
                     ui.add_space(2.0);
 
-                    let (score_text, score_color) = if result.score >= 999_900 {
-                        ("+WIN".to_string(), WIN_HIGHLIGHT)
-                    } else if result.score <= -999_900 {
-                        ("-LOSE".to_string(), TIMER_CRITICAL)
-                    } else if result.score > 50_000 {
-                        (format!("+{}", result.score), WIN_HIGHLIGHT)
-                    } else if result.score < -50_000 {
-                        (format!("{}", result.score), TIMER_CRITICAL)
-                    } else if result.score > 0 {
-                        (format!("+{}", result.score), TIMER_NORMAL)
-                    } else {
-                        (format!("{}", result.score), TEXT_SECONDARY)
+                    let (score_text, score_color) = match result.mate_in {
+                        Some(n) if n > 0 => (format!("Win in {}", n), WIN_HIGHLIGHT),
+                        Some(n) => (format!("Loss in {}", -n), TIMER_CRITICAL),
+                        None if result.score > 50_000 => (format!("+{}", result.score), WIN_HIGHLIGHT),
+                        None if result.score < -50_000 => (format!("{}", result.score), TIMER_CRITICAL),
+                        None if result.score > 0 => (format!("+{}", result.score), TIMER_NORMAL),
+                        None => (format!("{}", result.score), TEXT_SECONDARY),
                     };
 
                     let grid_id = format!("last_move_grid_{}", idx);
@@ -467,18 +972,12 @@ impl GomokuApp {
                                 };
                                 Self::grid_row(ui, "Depth", &format!("{}", result.depth), depth_color);
 
-                                let nodes_str = if result.nodes >= 1_000_000 {
-                                    format!("{:.1}M", result.nodes as f64 / 1_000_000.0)
-                                } else if result.nodes >= 1_000 {
-                                    format!("{:.1}K", result.nodes as f64 / 1_000.0)
-                                } else {
-                                    format!("{}", result.nodes)
-                                };
-                                Self::grid_row(ui, "Nodes", &nodes_str, TEXT_SECONDARY);
+                                Self::grid_row(ui, "Nodes", &format_nodes(result.nodes), TEXT_SECONDARY);
 
                                 if result.nps > 0 {
                                     Self::grid_row(ui, "Speed", &format!("{} kN/s", result.nps), TEXT_SECONDARY);
                                 }
+                                Self::grid_row(ui, "Threads", &format!("{}", result.threads), TEXT_SECONDARY);
                                 if result.tt_usage > 0 {
                                     Self::grid_row(ui, "TT Hit", &format!("{}%", result.tt_usage), TEXT_SECONDARY);
                                 }
@@ -536,14 +1035,7 @@ impl GomokuApp {
                             let max_depth_color = if stats.max_depth >= 10 { TIMER_NORMAL } else { TEXT_SECONDARY };
                             Self::grid_row(ui, "Max Depth", &format!("{}", stats.max_depth), max_depth_color);
 
-                            let total_str = if stats.total_nodes >= 1_000_000 {
-                                format!("{:.1}M", stats.total_nodes as f64 / 1_000_000.0)
-                            } else if stats.total_nodes >= 1_000 {
-                                format!("{:.1}K", stats.total_nodes as f64 / 1_000.0)
-                            } else {
-                                format!("{}", stats.total_nodes)
-                            };
-                            Self::grid_row(ui, "Total Nodes", &total_str, TEXT_SECONDARY);
+                            Self::grid_row(ui, "Total Nodes", &format_nodes(stats.total_nodes), TEXT_SECONDARY);
 
                             if stats.avg_nps() > 0 {
                                 Self::grid_row(ui, "Avg Speed", &format!("{} kN/s", stats.avg_nps()), TEXT_SECONDARY);
@@ -558,14 +1050,24 @@ impl GomokuApp {
 
     /// Render game over section
     fn render_game_over_section(&mut self, ui: &mut egui::Ui) {
-        let Some(result) = self.state.game_over.clone() else {
+        let Some(result) = self.state.game_over else {
             return;
         };
         let is_black = result.winner == Stone::Black;
         let winner = if is_black { "BLACK" } else { "WHITE" };
+        let rules = self.state.rule_set();
+        let capture_win = format!("{} captures", u16::from(rules.pair_size()) * u16::from(rules.capture_win_pairs()));
         let win_type = match result.win_type {
             WinType::FiveInRow => "5-in-a-row",
-            WinType::Capture => "10 captures",
+            WinType::Capture => capture_win.as_str(),
+            WinType::Resignation => "resignation",
+            WinType::Timeout => "timeout",
+            WinType::DrawAgreement => "agreement",
+        };
+        let headline = if result.is_draw() {
+            "DRAW".to_string()
+        } else {
+            format!("{} WINS!", winner)
         };
 
         Frame::new()
@@ -578,18 +1080,20 @@ impl GomokuApp {
 
                 // Winner info + New Game button (separate rows to avoid overlap)
                 ui.horizontal(|ui| {
-                    let (rect, _) = ui.allocate_exact_size(Vec2::new(22.0, 22.0), egui::Sense::hover());
-                    let center = rect.center();
-                    let stone_color = if is_black {
-                        egui::Color32::from_rgb(30, 30, 35)
-                    } else {
-                        egui::Color32::from_rgb(245, 245, 248)
-                    };
-                    ui.painter().circle_filled(center, 9.0, stone_color);
-                    ui.painter().circle_stroke(center, 9.0, egui::Stroke::new(1.5, WIN_HIGHLIGHT));
+                    if !result.is_draw() {
+                        let (rect, _) = ui.allocate_exact_size(Vec2::new(22.0, 22.0), egui::Sense::hover());
+                        let center = rect.center();
+                        let stone_color = if is_black {
+                            egui::Color32::from_rgb(30, 30, 35)
+                        } else {
+                            egui::Color32::from_rgb(245, 245, 248)
+                        };
+                        ui.painter().circle_filled(center, 9.0, stone_color);
+                        ui.painter().circle_stroke(center, 9.0, egui::Stroke::new(1.5, WIN_HIGHLIGHT));
 
-                    ui.add_space(4.0);
-                    ui.label(RichText::new(format!("{} WINS!", winner)).size(14.0).strong().color(TEXT_PRIMARY));
+                        ui.add_space(4.0);
+                    }
+                    ui.label(RichText::new(headline).size(14.0).strong().color(TEXT_PRIMARY));
 
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         if ui.small_button("New Game").clicked() {
@@ -599,7 +1103,9 @@ impl GomokuApp {
                 });
                 // Win details on separate line
                 let move_count = self.state.move_history.len();
-                let last_info = if let Some(pos) = self.state.last_move {
+                let last_info = if matches!(result.win_type, WinType::Resignation | WinType::Timeout | WinType::DrawAgreement) {
+                    format!("by {} (move #{})", win_type, move_count)
+                } else if let Some(pos) = self.state.last_move {
                     let notation = crate::engine::pos_to_notation(pos);
                     format!("by {} at {} (move #{})", win_type, notation, move_count)
                 } else {
@@ -642,14 +1148,581 @@ impl GomokuApp {
                         }
                     });
                 });
+
+                // Move-quality annotation of the reviewed move
+                ui.add_space(4.0);
+                if self.state.is_annotating() {
+                    ui.horizontal(|ui| {
+                        ui.add(egui::Spinner::new().size(10.0));
+                        ui.label(RichText::new("Analyzing moves\u{2026}").size(10.0).color(TEXT_SECONDARY));
+                    });
+                } else if let Some(annotation) = &self.state.annotation {
+                    if let Some(m) = self.state.reviewed_annotation() {
+                        let text = match (m.quality, m.best_move) {
+                            (MoveQuality::Best, _) => format!("{}: best move", crate::engine::pos_to_notation(m.mov)),
+                            (quality, Some(best)) if quality > MoveQuality::Good => format!(
+                                "{} {} {}, best {}",
+                                crate::engine::pos_to_notation(m.mov),
+                                quality.symbol(),
+                                quality.name(),
+                                crate::engine::pos_to_notation(best)
+                            ),
+                            _ => format!("{}: good move", crate::engine::pos_to_notation(m.mov)),
+                        };
+                        ui.label(RichText::new(text).size(10.0).color(quality_color(m.quality)));
+                    }
+                    for (color, name) in [(Stone::Black, "Black"), (Stone::White, "White")] {
+                        let counts = format!(
+                            "{}: {} ?!  {} ?  {} ??",
+                            name,
+                            annotation.count(color, MoveQuality::Inaccuracy),
+                            annotation.count(color, MoveQuality::Mistake),
+                            annotation.count(color, MoveQuality::Blunder)
+                        );
+                        ui.label(RichText::new(counts).size(9.0).color(TEXT_MUTED));
+                    }
+                } else if ui.small_button("Analyze Moves").on_hover_text("Mark inaccuracies, mistakes and blunders").clicked() {
+                    self.state.request_annotation();
+                }
+            });
+    }
+
+    /// Render the post-game report: both players side by side, the biggest
+    /// swing, and export to Markdown or JSON next to the game file
+    fn render_report_section(&mut self, ui: &mut egui::Ui) {
+        let Some(report) = self.state.report() else {
+            return;
+        };
+        let mut export = None;
+        Self::render_card(ui, Some(("GAME REPORT", ACCENT_BLUE)), |ui| {
+            let cell = |value: Option<f64>, unit: &str| value.map_or_else(|| "-".to_string(), |v| format!("{:.1}{}", v, unit));
+            let [black, white] = &report.players;
+            egui::Grid::new("game_report_grid")
+                .num_columns(3)
+                .min_col_width(ui.available_width() / 3.0 - 8.0)
+                .spacing([8.0, 2.0])
+                .show(ui, |ui| {
+                    ui.label("");
+                    ui.label(RichText::new("Black").size(11.0).strong().color(TEXT_PRIMARY));
+                    ui.label(RichText::new("White").size(11.0).strong().color(TEXT_PRIMARY));
+                    ui.end_row();
+                    let mut row = |label: &str, b: String, w: String| {
+                        ui.label(RichText::new(label).size(11.0).color(TEXT_MUTED));
+                        ui.label(RichText::new(b).size(11.0).color(TEXT_SECONDARY));
+                        ui.label(RichText::new(w).size(11.0).color(TEXT_SECONDARY));
+                        ui.end_row();
+                    };
+                    row("Accuracy", cell(black.accuracy, "%"), cell(white.accuracy, "%"));
+                    row("?! / ? / ??", quality_counts(black), quality_counts(white));
+                    let captures = |p: &PlayerReport| {
+                        format!("{} ({})", p.pairs_captured, cell(p.capture_efficiency().map(|e| e * 100.0), "%"))
+                    };
+                    row("Captures", captures(black), captures(white));
+                    row("Avg Depth", cell(black.avg_depth, ""), cell(white.avg_depth, ""));
+                    row("Avg Time", cell(black.avg_time_ms, "ms"), cell(white.avg_time_ms, "ms"));
+                });
+            if let Some(swing) = report.biggest_swing.filter(|swing| swing.index < report.moves.len()) {
+                let text = format!(
+                    "Biggest swing: #{} {} ({:.0}% \u{2192} {:.0}%)",
+                    swing.index + 1,
+                    crate::engine::pos_to_notation(report.moves[swing.index]),
+                    swing.before * 100.0,
+                    swing.after * 100.0
+                );
+                ui.label(RichText::new(text).size(10.0).color(TEXT_SECONDARY));
+            }
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.small_button("Export Markdown").clicked() {
+                    export = Some(("md", report.to_markdown()));
+                }
+                if ui.small_button("Export JSON").clicked() {
+                    export = Some(("json", report.to_json() + "\n"));
+                }
+            });
+            match &self.report_status {
+                Some(Ok(status)) => {
+                    ui.label(RichText::new(status).size(10.0).color(TEXT_SECONDARY));
+                }
+                Some(Err(error)) => {
+                    ui.label(RichText::new(error).size(10.0).color(TIMER_CRITICAL));
+                }
+                None => {}
+            }
+        });
+        if let Some((extension, text)) = export {
+            let path = std::path::Path::new(&self.record_path).with_extension(format!("report.{}", extension));
+            self.report_status =
+                Some(std::fs::write(&path, text).map(|()| format!("Saved {}", path.display())).map_err(|e| e.to_string()));
+        }
+        ui.add_space(4.0);
+    }
+
+    /// Render the position editor controls: brush, side to move, captures,
+    /// position text, and starting play from the position
+    fn render_setup_section(&mut self, ui: &mut egui::Ui) {
+        let Some(setup) = &mut self.setup else {
+            return;
+        };
+        let mut start = None;
+        let mut close = false;
+
+        Self::render_card(ui, Some(("SET UP POSITION", ACCENT_BLUE)), |ui| {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Place").size(11.0).color(TEXT_SECONDARY));
+                for (label, brush) in [("Black", Stone::Black), ("White", Stone::White), ("Erase", Stone::Empty)] {
+                    ui.radio_value(&mut setup.brush, brush, label);
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("To move").size(11.0).color(TEXT_SECONDARY));
+                for (label, color) in [("Black", Stone::Black), ("White", Stone::White)] {
+                    ui.radio_value(&mut setup.to_move, color, label);
+                }
+            });
+            let max_captures = setup.rules.capture_win_pairs() - 1;
+            ui.horizontal(|ui| {
+                for (label, color) in [("Captures B", Stone::Black), ("W", Stone::White)] {
+                    ui.label(RichText::new(label).size(11.0).color(TEXT_SECONDARY));
+                    let mut pairs = setup.board.captures(color);
+                    if ui.add(egui::DragValue::new(&mut pairs).range(0..=max_captures)).changed() {
+                        let _ = setup.board.set_captures(setup.rules, color, pairs);
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Position").size(11.0).color(TEXT_SECONDARY));
+                let edit = ui.add(egui::TextEdit::singleline(&mut setup.text).hint_text("paste text").desired_width(120.0));
+                let entered = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if ui.button("Load").clicked() || entered {
+                    let text = std::mem::take(&mut setup.text);
+                    if setup.load_text(&text).is_err() {
+                        setup.text = text;
+                    }
+                }
+                if ui.button("Copy").on_hover_text("Copy this position as text").clicked() {
+                    ui.ctx().copy_text(setup.to_text());
+                }
+            });
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.button("Play").on_hover_text("Start a game from this position").clicked() {
+                    match setup.to_game() {
+                        Ok(game) => start = Some(game),
+                        Err(e) => setup.error = Some(e),
+                    }
+                }
+                if ui.button("Clear").clicked() {
+                    setup.clear();
+                }
+                if ui.button("Cancel").clicked() {
+                    close = true;
+                }
+            });
+            if let Some(error) = &setup.error {
+                ui.label(RichText::new(error).size(11.0).color(TIMER_CRITICAL));
+            }
+            for warning in setup.warnings() {
+                ui.label(RichText::new(format!("Not from a game: {}", warning)).size(10.0).color(TIMER_WARNING));
+            }
+        });
+
+        if let Some(game) = start {
+            self.state.start_from_setup(&game);
+            close = true;
+        }
+        if close {
+            self.setup = None;
+        }
+    }
+
+    /// Open the position editor on pasted position text. Text that doesn't
+    /// parse leaves the editor on the current board with the error shown.
+    fn paste_position(&mut self, text: &str) {
+        let rules = self.state.rule_set();
+        let setup =
+            self.setup.get_or_insert_with(|| SetupEditor::new(rules, &self.state.board, self.state.current_turn));
+        if setup.load_text(text).is_err() {
+            setup.text = text.trim().to_string();
+        }
+        self.replay = None;
+    }
+
+    /// Render the replay controls: stepping, autoplay speed and the engine's
+    /// evaluation of the shown move
+    fn render_replay_section(&mut self, ui: &mut egui::Ui) {
+        let Some(replay) = &mut self.replay else {
+            return;
+        };
+        let mut close = false;
+
+        Self::render_card(ui, Some(("REPLAY", ACCENT_BLUE)), |ui| {
+            ui.horizontal(|ui| {
+                ui.spacing_mut().item_spacing.x = 2.0;
+                let s = Vec2::new(24.0, 18.0);
+                let button = |text: &str| egui::Button::new(RichText::new(text).size(10.0).color(TEXT_SECONDARY));
+
+                if ui.add_sized(s, button("<<")).clicked() {
+                    replay.seek(0);
+                }
+                if ui.add_sized(s, button("<")).clicked() {
+                    replay.step(-1);
+                }
+                let play_label = if replay.autoplay { "||" } else { "|>" };
+                if ui.add_sized(s, button(play_label)).clicked() {
+                    if !replay.autoplay && replay.index() == replay.len() {
+                        replay.seek(0);
+                    }
+                    replay.autoplay = !replay.autoplay;
+                }
+                if ui.add_sized(s, button(">")).clicked() {
+                    replay.step(1);
+                }
+                if ui.add_sized(s, button(">>")).clicked() {
+                    replay.seek(replay.len());
+                }
+                ui.label(RichText::new(format!(" {}/{} ", replay.index(), replay.len())).size(10.0).color(TEXT_SECONDARY));
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    close = ui.small_button("Close").clicked();
+                });
+            });
+
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Speed").size(10.0).color(TEXT_MUTED));
+                ui.add(egui::Slider::new(&mut replay.interval, MIN_REPLAY_INTERVAL..=MAX_REPLAY_INTERVAL)
+                    .logarithmic(true)
+                    .suffix(" s/move"));
+            });
+
+            ui.add_space(4.0);
+            let last = replay.last_move().map_or_else(
+                || "Start".to_string(),
+                |pos| {
+                    let color = if replay.side_to_move() == Stone::White { "Black" } else { "White" };
+                    format!("#{} {} {}", replay.index(), color, crate::engine::pos_to_notation(pos))
+                },
+            );
+            let eval = match (replay.eval(), replay.previous_eval()) {
+                (Some(score), Some(before)) => {
+                    format!("{} ({:+.1})", score_label(score), f64::from(score - before) / 1000.0)
+                }
+                (Some(score), None) => score_label(score),
+                (None, _) => "analyzing...".to_string(),
+            };
+            Self::grid_row(ui, &last, &eval, TEXT_PRIMARY);
+        });
+
+        if close {
+            self.replay = None;
+        }
+    }
+
+    /// Render the save/load window. Saving writes SGF for a `.sgf` path and
+    /// a move list otherwise; loading accepts either and starts a replay.
+    /// With the `render` feature, Export GIF draws the game beside the file.
+    fn render_record_window(&mut self, ctx: &Context) {
+        let mut open = self.show_record_window;
+
+        egui::Window::new("Save / Load Game")
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("File");
+                    ui.text_edit_singleline(&mut self.record_path);
+                });
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    let saveable = !self.state.move_history.is_empty() && self.state.start.is_none();
+                    let save = ui
+                        .add_enabled(saveable, egui::Button::new("Save Game"))
+                        .on_disabled_hover_text("Games from a set-up position can't be saved");
+                    if save.clicked() {
+                        let record = self.state.record();
+                        let text = if self.record_path.to_ascii_lowercase().ends_with(".sgf") {
+                            record.to_sgf()
+                        } else {
+                            record.to_move_list()
+                        };
+                        self.record_status = Some(std::fs::write(&self.record_path, text + "\n")
+                            .map(|()| format!("Saved {} moves", record.moves.len()))
+                            .map_err(|e| e.to_string()));
+                    }
+                    if ui.button("Load & Replay").clicked() {
+                        let replay = std::fs::read_to_string(&self.record_path)
+                            .map_err(|e| e.to_string())
+                            .and_then(|text| GameRecord::parse(&text))
+                            .and_then(|record| Replay::new(self.state.rule_set(), record));
+                        self.record_status = Some(replay.map(|replay| {
+                            let status = format!("Loaded {} moves", replay.len());
+                            self.replay = Some(replay);
+                            status
+                        }));
+                    }
+                    #[cfg(feature = "render")]
+                    if ui.add_enabled(saveable, egui::Button::new("Export GIF")).clicked() {
+                        let path = std::path::Path::new(&self.record_path).with_extension("gif");
+                        let moves = self.state.record().moves;
+                        let style = crate::render::RenderStyle::default();
+                        self.record_status = Some(
+                            crate::render::export_game(self.state.rule_set(), &moves, &path, &style)
+                                .map(|count| format!("Drew {} moves to {}", count, path.display())),
+                        );
+                    }
+                });
+                match &self.record_status {
+                    Some(Ok(status)) => {
+                        ui.label(RichText::new(status).size(10.0).color(TEXT_SECONDARY));
+                    }
+                    Some(Err(error)) => {
+                        ui.label(RichText::new(error).size(10.0).color(TIMER_CRITICAL));
+                    }
+                    None => {}
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("Archive");
+                    ui.text_edit_singleline(&mut self.archive_path);
+                });
+                let toggle = ui
+                    .checkbox(&mut self.archiving, "Add Finished Games")
+                    .on_hover_text("Append every game that ends, in any tab, to the archive file");
+                if toggle.changed() && self.archiving {
+                    self.archiving = self.load_archive();
+                }
+                let losing = ui
+                    .add_enabled(self.archive.is_some(), egui::Button::new("Openings I Keep Losing"))
+                    .on_hover_text("Your openings (first 4 moves) scoring below 50% over 2+ games");
+                if losing.clicked() {
+                    self.opening_report =
+                        self.archive.as_ref().map(|archive| archive.openings(HUMAN_PLAYER, 4, 2).to_markdown());
+                }
+                if let Some(report) = &self.opening_report {
+                    ScrollArea::vertical().max_height(160.0).show(ui, |ui| {
+                        ui.label(RichText::new(report).size(10.0).monospace().color(TEXT_SECONDARY));
+                    });
+                }
+            });
+
+        self.show_record_window = open;
+    }
+
+    /// Append the games that ended since the last frame, in any tab, to
+    /// the archive when archiving is on
+    fn archive_finished_games(&mut self) {
+        let Some(archive) = self.archive.as_mut().filter(|_| self.archiving) else {
+            return;
+        };
+        for state in std::iter::once(&mut self.state).chain(self.tabs.iter_mut().flatten()) {
+            if let Some(game) = state.take_archive_entry() {
+                if let Err(e) = archive.append(game) {
+                    self.record_status = Some(Err(e));
+                }
+            }
+        }
+    }
+
+    /// Load the archive at `archive_path` unless it is already loaded;
+    /// failures go to the record window's status
+    fn load_archive(&mut self) -> bool {
+        let path = std::path::Path::new(&self.archive_path);
+        if self.archive.as_ref().is_some_and(|archive| archive.path() == Some(path)) {
+            return true;
+        }
+        match GameArchive::open(path) {
+            Ok(archive) => {
+                self.record_status = Some(Ok(format!("Archive has {} games", archive.games().len())));
+                self.archive = Some(archive);
+                true
+            }
+            Err(e) => {
+                self.record_status = Some(Err(e));
+                self.archive = None;
+                false
+            }
+        }
+    }
+
+    /// Play sounds for what changed since the last frame: a placed stone,
+    /// a capture, a win
+    fn play_event_sounds(&mut self) {
+        let moves = self.state.move_history.len();
+        let captures = self.state.board.captures(Stone::Black) + self.state.board.captures(Stone::White);
+        let game_over = self.state.game_over.is_some();
+
+        if moves > self.seen_moves {
+            let effect = if captures > self.seen_captures { SoundEffect::Capture } else { SoundEffect::Place };
+            self.sounds.play(effect);
+        }
+        if game_over && !self.seen_game_over && self.state.game_over.is_some_and(|r| !r.is_draw()) {
+            self.sounds.play(SoundEffect::Win);
+        }
+
+        self.seen_moves = moves;
+        self.seen_captures = captures;
+        self.seen_game_over = game_over;
+    }
+
+    /// Render the opening explorer for the shown position (the reviewed
+    /// one during review): archived games through it and the replies
+    /// played, with the book move starred
+    fn render_explorer_section(&self, ui: &mut egui::Ui) {
+        Self::render_card(ui, Some(("OPENING EXPLORER", ACCENT_BLUE)), |ui| {
+            let Some(archive) = &self.archive else {
+                ui.label(RichText::new("No archive: check the path in Save / Load").size(10.0).color(TEXT_MUTED));
+                return;
+            };
+            if self.state.start.is_some() {
+                ui.label(RichText::new("Set-up positions aren't in the archive").size(10.0).color(TEXT_MUTED));
+                return;
+            }
+            let (board, to_move) = match self.state.review_index {
+                Some(index) if index < self.state.move_history.len() => {
+                    (self.state.build_review_board(index).0, self.state.move_history[index].1)
+                }
+                _ => (self.state.board.clone(), self.state.current_turn),
+            };
+            let explored = explore(archive, &board, to_move);
+            ui.label(
+                RichText::new(format!(
+                    "{} games: Black {} / White {} / draws {}",
+                    explored.games, explored.black_wins, explored.white_wins, explored.draws
+                ))
+                .size(10.0)
+                .color(TEXT_SECONDARY),
+            );
+            for reply in explored.replies.iter().take(EXPLORER_REPLIES) {
+                let score = reply.score().map_or_else(|| "-".to_string(), |score| format!("{:.0}%", score * 100.0));
+                let book = if reply.book { " *" } else { "" };
+                ui.label(
+                    RichText::new(format!("{:<4}{:<2} {:>4} games  {:>4}", notation().format(reply.pos), book, reply.games, score))
+                        .size(10.0)
+                        .monospace()
+                        .color(if reply.book { ACCENT_BLUE } else { TEXT_PRIMARY }),
+                );
+            }
+        });
+    }
+
+    /// Render the per-move score graph: Black's win probability after each
+    /// scored move, with the biggest swing marked
+    fn render_score_graph(&self, ui: &mut egui::Ui) {
+        Self::render_card(ui, Some(("SCORE GRAPH", ACCENT_BLUE)), |ui| {
+            let (rect, response) = ui.allocate_exact_size(Vec2::new(ui.available_width(), 70.0), Sense::hover());
+            let painter = ui.painter_at(rect);
+            painter.rect_filled(rect, CornerRadius::same(3), PANEL_CARD_ACCENT);
+            painter.hline(rect.x_range(), rect.center().y, Stroke::new(1.0, TEXT_MUTED));
+
+            let total = self.state.move_scores.len();
+            let points: Vec<(usize, egui::Pos2)> = self
+                .state
+                .move_scores
+                .iter()
+                .enumerate()
+                .filter_map(|(i, score)| {
+                    let prob = AIEngine::win_probability((*score)?) as f32;
+                    let x = rect.left() + rect.width() * (i + 1) as f32 / total.max(1) as f32;
+                    Some((i, egui::pos2(x, rect.bottom() - rect.height() * prob)))
+                })
+                .collect();
+
+            if points.is_empty() {
+                painter.text(rect.center(), egui::Align2::CENTER_CENTER, "No scored moves yet",
+                    egui::FontId::proportional(10.0), TEXT_MUTED);
+                return;
+            }
+
+            let line: Vec<egui::Pos2> = points.iter().map(|&(_, p)| p).collect();
+            painter.add(egui::Shape::line(line, Stroke::new(1.5, ACCENT_BLUE)));
+            for &(i, p) in &points {
+                let color = if self.state.move_history[i].1 == Stone::Black { BLACK_STONE } else { WHITE_STONE };
+                painter.circle_filled(p, 2.5, color);
+            }
+            // Annotated mistakes and blunders
+            if let Some(annotation) = &self.state.annotation {
+                for &(i, p) in &points {
+                    let quality = annotation.moves.get(i).map_or(MoveQuality::Good, |m| m.quality);
+                    if quality >= MoveQuality::Mistake {
+                        painter.circle_stroke(p, 4.0, Stroke::new(1.5, quality_color(quality)));
+                    }
+                }
+            }
+            if let Some(&(_, p)) = self.state.turning_point().and_then(|t| points.iter().find(|&&(i, _)| i == t)) {
+                painter.circle_stroke(p, 5.0, Stroke::new(1.5, TIMER_CRITICAL));
+            }
+
+            // Hover: the nearest scored move
+            if let Some(hover) = response.hover_pos() {
+                if let Some(&(i, p)) = points.iter().min_by(|a, b| (a.1.x - hover.x).abs().total_cmp(&(b.1.x - hover.x).abs())) {
+                    painter.vline(p.x, rect.y_range(), Stroke::new(1.0, TEXT_SECONDARY));
+                    let (pos, _) = self.state.move_history[i];
+                    let score = self.state.move_scores[i].unwrap_or(0);
+                    response.on_hover_text(format!("#{} {}: {}", i + 1, crate::engine::pos_to_notation(pos), score_label(score)));
+                }
+            }
+        });
+    }
+
+    /// Render the evaluation bar left of the board: Black's win probability
+    /// fills from the bottom
+    fn render_eval_bar(&self, ctx: &Context) {
+        SidePanel::left("eval_bar")
+            .resizable(false)
+            .exact_width(22.0)
+            .frame(Frame::new()
+                .fill(self.board_view.theme.colors().backdrop)
+                .inner_margin(egui::Margin::symmetric(4, BOARD_MARGIN as i8)))
+            .show(ctx, |ui| {
+                let rect = ui.available_rect_before_wrap();
+                let painter = ui.painter();
+                let score = match &self.replay {
+                    Some(replay) => replay.eval(),
+                    None => self.state.current_score(),
+                };
+                let black = score.map_or(0.5, AIEngine::win_probability) as f32;
+                let split = rect.bottom() - rect.height() * black;
+
+                painter.rect_filled(Rect::from_x_y_ranges(rect.x_range(), rect.top()..=split), CornerRadius::ZERO, WHITE_STONE);
+                painter.rect_filled(Rect::from_x_y_ranges(rect.x_range(), split..=rect.bottom()), CornerRadius::ZERO, BLACK_STONE);
+                painter.hline(rect.x_range(), rect.center().y, Stroke::new(1.0, TEXT_MUTED));
+
+                if let Some(score) = score {
+                    // Label at the leading side's end of the bar
+                    let (anchor, align, color) = if score >= 0 {
+                        (rect.center_bottom(), egui::Align2::CENTER_BOTTOM, WHITE_STONE)
+                    } else {
+                        (rect.center_top(), egui::Align2::CENTER_TOP, BLACK_STONE)
+                    };
+                    painter.text(anchor, align, score_label(score.abs()), egui::FontId::proportional(8.0), color);
+                }
             });
     }
 
     /// Render the main board
     fn render_board(&mut self, ctx: &Context) {
-        CentralPanel::default().show(ctx, |ui| {
-            // Set board area background
-            ui.style_mut().visuals.panel_fill = egui::Color32::from_rgb(40, 42, 46);
+        // Board area background follows the board theme
+        let backdrop = Frame::central_panel(&ctx.style()).fill(self.board_view.theme.colors().backdrop);
+        CentralPanel::default().frame(backdrop).show(ctx, |ui| {
+            if let Some(replay) = &self.replay {
+                let board = replay.board().clone();
+                let available = ui.available_size();
+                ui.add_space((available.y - available.x.min(available.y)).max(0.0) / 2.0);
+                let (rules, to_move, last_move) = (replay.rules(), replay.side_to_move(), replay.last_move());
+                self.board_view.show(ui, rules, &board, to_move, last_move, None, &[], None, true, None, None);
+                return;
+            }
+            if let Some(setup) = &mut self.setup {
+                let available = ui.available_size();
+                ui.add_space((available.y - available.x.min(available.y)).max(0.0) / 2.0);
+                if let Some(pos) = self.board_view.show_setup(ui, &setup.board, setup.brush) {
+                    setup.click(pos);
+                }
+                return;
+            }
 
             // In review mode, show a temporary board at the review index
             let (board_ref, last_move, winning_line) = if let Some(idx) = self.state.review_index {
@@ -690,12 +1763,19 @@ impl GomokuApp {
                 None
             };
 
+            let hints = match &self.state.hints {
+                Some(hints) if self.state.show_hints && !self.state.is_reviewing() => hints.as_slice(),
+                _ => &[],
+            };
+
             let clicked = self.board_view.show(
                 ui,
+                self.state.rule_set(),
                 &board_ref,
                 self.state.current_turn,
                 last_move,
-                self.state.suggested_move,
+                self.state.suggestion.as_ref().map(|hint| hint.mov),
+                hints,
                 winning_line,
                 self.state.game_over.is_some() && !self.state.is_reviewing(),
                 self.state.capture_animation.as_ref(),
@@ -713,6 +1793,101 @@ impl GomokuApp {
         });
     }
 
+    /// Render the AI settings window. Engine changes apply from the next AI
+    /// move; the opening rule can only change before the first move.
+    fn render_settings_window(&mut self, ctx: &Context) {
+        let mut open = self.show_settings;
+        let mut settings = self.state.ai_settings();
+        let max_threads = std::thread::available_parallelism().map_or(8, |n| n.get());
+
+        egui::Window::new("AI Settings")
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("ai_settings_grid").num_columns(2).spacing([12.0, 6.0]).show(ui, |ui| {
+                    ui.label("Max depth");
+                    ui.add(egui::Slider::new(&mut settings.max_depth, 1..=crate::search::MAX_SEARCH_DEPTH));
+                    ui.end_row();
+
+                    ui.label("Time per move");
+                    ui.add(egui::Slider::new(&mut settings.time_limit_ms, 50..=10_000).logarithmic(true).suffix(" ms"));
+                    ui.end_row();
+
+                    ui.label("Threads");
+                    ui.add(egui::Slider::new(&mut settings.threads, 1..=max_threads));
+                    ui.end_row();
+
+                    ui.label("Hash table");
+                    egui::ComboBox::from_id_salt("hash_mb")
+                        .selected_text(format!("{} MB", settings.hash_mb))
+                        .show_ui(ui, |ui| {
+                            for mb in [16, 32, 64, 128, 256, 512] {
+                                ui.selectable_value(&mut settings.hash_mb, mb, format!("{} MB", mb));
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Opening rule");
+                    ui.add_enabled_ui(self.state.move_history.is_empty(), |ui| {
+                        ui.horizontal(|ui| {
+                            for (label, rule) in [("Standard", OpeningRule::Standard), ("Pro", OpeningRule::Pro), ("Swap", OpeningRule::Swap)] {
+                                ui.radio_value(&mut self.state.opening_rule, rule, label);
+                            }
+                        });
+                    });
+                    ui.end_row();
+
+                    ui.label("Rules");
+                    ui.add_enabled_ui(self.state.move_history.is_empty(), |ui| {
+                        let current = self.state.rule_set();
+                        let (mut forbidden, mut caro, mut overline_wins) =
+                            (current.forbidden(), current.caro(), current.overline_wins());
+                        let (mut pair_size, mut capture_win_pairs) = (current.pair_size(), current.capture_win_pairs());
+                        ui.vertical(|ui| {
+                            for choice in Forbidden::ALL {
+                                ui.radio_value(&mut forbidden, choice, choice.name());
+                            }
+                            ui.checkbox(&mut caro, "Caro: a five blocked at both ends doesn't win");
+                            ui.checkbox(&mut overline_wins, "Six or more in a row wins");
+                            ui.horizontal(|ui| {
+                                ui.label("Capture");
+                                ui.add(egui::Slider::new(&mut pair_size, 2..=MAX_PAIR_SIZE as u8).suffix(" stones"));
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Win at");
+                                ui.add(egui::Slider::new(&mut capture_win_pairs, 1..=MAX_CAPTURE_WIN_PAIRS).suffix(" pairs"));
+                            });
+                        });
+                        let rules = current
+                            .with_forbidden(forbidden)
+                            .with_caro(caro)
+                            .with_overline_wins(overline_wins)
+                            .with_captures(pair_size, capture_win_pairs);
+                        match rules {
+                            Ok(rules) if rules != current => self.state.set_rule_set(rules),
+                            Ok(_) => {}
+                            Err(e) => self.state.message = Some(e),
+                        }
+                    });
+                    ui.end_row();
+                });
+
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Defaults").clicked() {
+                        settings = AiSettings::default();
+                    }
+                    ui.label(RichText::new("Applied from the next AI move").size(10.0).color(TEXT_MUTED));
+                });
+            });
+
+        if settings != self.state.ai_settings() {
+            self.state.set_ai_settings(settings);
+        }
+        self.show_settings = open;
+    }
+
     /// Render swap rule dialog overlay
     fn render_swap_dialog(&mut self, ctx: &Context) {
         egui::Area::new(egui::Id::new("swap_dialog"))
@@ -745,12 +1920,37 @@ impl GomokuApp {
 
     /// Handle keyboard shortcuts
     fn handle_input(&mut self, ctx: &Context) {
+        let typing = ctx.wants_keyboard_input();
+        let mut enter = None;
         ctx.input(|i| {
             // D - Toggle debug panel
             if i.key_pressed(egui::Key::D) {
                 self.show_debug = !self.show_debug;
             }
 
+            // Arrows - Move the board cursor, Enter - Play (or edit) there,
+            // while a game is running or a position is being set up
+            if !typing && self.replay.is_none() && (self.setup.is_some() || self.state.game_over.is_none()) {
+                for (key, d_row, d_col) in [
+                    (egui::Key::ArrowUp, -1, 0),
+                    (egui::Key::ArrowDown, 1, 0),
+                    (egui::Key::ArrowLeft, 0, -1),
+                    (egui::Key::ArrowRight, 0, 1),
+                ] {
+                    if i.key_pressed(key) {
+                        self.board_view.move_cursor(d_row, d_col);
+                    }
+                }
+                if i.key_pressed(egui::Key::Enter) {
+                    enter = self.board_view.cursor;
+                }
+            }
+
+            // The position editor takes clicks only
+            if self.setup.is_some() {
+                return;
+            }
+
             // H - Get hint (PvP mode)
             if i.key_pressed(egui::Key::H) {
                 if let GameMode::PvP { .. } = self.state.mode {
@@ -768,6 +1968,20 @@ impl GomokuApp {
                 self.state.redo();
             }
 
+            // Replay: arrows step, Space toggles autoplay
+            if let Some(replay) = &mut self.replay {
+                if i.key_pressed(egui::Key::ArrowLeft) {
+                    replay.step(-1);
+                }
+                if i.key_pressed(egui::Key::ArrowRight) {
+                    replay.step(1);
+                }
+                if i.key_pressed(egui::Key::Space) {
+                    replay.autoplay = !replay.autoplay;
+                }
+                return;
+            }
+
             // Left/Right arrows - Review mode (after game over)
             if i.key_pressed(egui::Key::ArrowLeft) {
                 self.state.review_prev();
@@ -781,6 +1995,16 @@ impl GomokuApp {
                 self.state.reset();
             }
         });
+
+        if let Some(pos) = enter {
+            if let Some(setup) = &mut self.setup {
+                setup.click(pos);
+            } else if !self.state.is_reviewing() && !self.state.swap_pending {
+                if let Err(msg) = self.state.try_place_stone(pos) {
+                    self.state.message = Some(msg);
+                }
+            }
+        }
     }
 }
 
@@ -800,51 +2024,56 @@ impl eframe::App for GomokuApp {
             ctx.send_viewport_cmd(egui::ViewportCommand::Close);
         }
 
-        // Check AI result
-        self.state.check_ai_result();
-
-        // Clean up completed capture animations
-        if let Some(animation) = &self.state.capture_animation {
-            if animation.is_complete() {
-                self.state.capture_animation = None;
+        // Ctrl+V outside a text field - Load a copied position into the editor
+        if !ctx.wants_keyboard_input() && !self.state.is_ai_thinking() {
+            let pasted = ctx.input(|i| {
+                i.events.iter().find_map(|event| match event {
+                    egui::Event::Paste(text) => Some(text.clone()),
+                    _ => None,
+                })
+            });
+            if let Some(text) = pasted {
+                self.paste_position(&text);
             }
         }
 
-        // Start AI thinking if needed (not during swap decision)
-        if self.state.is_ai_turn() && !self.state.is_ai_thinking() && self.state.game_over.is_none() && !self.state.swap_pending {
-            self.state.start_ai_thinking();
+        // AI results, flag falls and AI turns, for every tab; the active
+        // game holds still while a position is set up over it
+        self.state.update(self.setup.is_some());
+        for state in self.tabs.iter_mut().flatten() {
+            state.update(false);
         }
-
-        // Auto-decide swap for AI in PvE/AiVsAi mode
-        if self.state.swap_pending {
-            match self.state.mode {
-                GameMode::PvE { human_color } => {
-                    if self.state.current_turn != human_color {
-                        // AI decides: always swap (takes initiative)
-                        self.state.execute_swap();
-                    }
-                }
-                GameMode::AiVsAi => {
-                    // AI auto-decides: always decline swap
-                    self.state.decline_swap();
-                }
-                _ => {}
-            }
+        self.archive_finished_games();
+        self.play_event_sounds();
+        if self.replay.as_mut().is_some_and(Replay::update) {
+            ctx.request_repaint();
         }
 
         // Render UI
         self.render_menu_bar(ctx);
+        self.render_tab_bar(ctx);
         self.render_side_panel(ctx);
+        if self.show_eval_bar {
+            self.render_eval_bar(ctx);
+        }
         self.render_board(ctx);
 
+        if self.show_settings {
+            self.render_settings_window(ctx);
+        }
+        if self.show_record_window {
+            self.render_record_window(ctx);
+        }
+
         // Swap dialog overlay (only for human decision)
         if self.state.swap_pending {
             self.render_swap_dialog(ctx);
         }
 
         // Always repaint while game is in progress (live timer), plus animations/messages
-        let game_in_progress = self.state.game_over.is_none();
-        if game_in_progress || self.state.capture_animation.is_some() || self.state.message.is_some() {
+        let game_in_progress = self.state.game_over.is_none()
+            || self.tabs.iter().flatten().any(|state| state.game_over.is_none());
+        if game_in_progress || self.state.capture_animation.is_some() || self.state.message.is_some() || self.state.is_annotating() {
             ctx.request_repaint();
         }
     }
@@ -1,17 +1,70 @@
 //! Board rendering for the Gomoku GUI
 
+use std::time::Instant;
+
+use crate::board::{Bitboard, Symmetry};
+use crate::notation::notation;
+use crate::rules::RuleSet;
+use crate::search::{mate_distance, RootLine, Searcher};
 use crate::{Pos, Stone, BOARD_SIZE};
 use egui::{Color32, CornerRadius, Painter, Pos2, Rect, Sense, Stroke, Vec2};
 
 use super::game_state::CaptureAnimation;
 use super::theme::*;
 
+/// Length of the stone placement animation
+const PLACE_ANIMATION_SECS: f32 = 0.18;
+
+/// The last stone placed, dropping onto the board
+struct PlaceAnimation {
+    pos: Pos,
+    start: Instant,
+}
+
+impl PlaceAnimation {
+    /// Height above the board: 1.0 when placed, 0.0 once it has landed
+    fn lift(&self) -> f32 {
+        let t = (self.start.elapsed().as_secs_f32() / PLACE_ANIMATION_SECS).min(1.0);
+        (1.0 - t) * (1.0 - t)
+    }
+}
+
+/// A position as drawn: stones, side to move and the rules it is played by
+type PositionKey = ((Bitboard, Bitboard), Stone, RuleSet);
+
 /// Board view handles rendering and input for the game board
 pub struct BoardView {
     /// Cached cell size for coordinate calculations
     cell_size: f32,
     /// Board drawing area
     board_rect: Rect,
+    /// Board palette and stone style
+    pub theme: Theme,
+    /// Mark double-three points forbidden for the side to move
+    pub show_forbidden: bool,
+    /// Shade the engine's root candidates for the side to move
+    pub show_heatmap: bool,
+    /// Animate newly placed stones
+    pub animate_moves: bool,
+    /// Draw the board turned half around, as seen from the other side
+    pub flipped: bool,
+    /// Keyboard cursor: moved by the arrow keys, follows clicks
+    pub cursor: Option<Pos>,
+    /// Two-tap moves: the first click selects a point, a second click on
+    /// it plays (for touch screens and against misclicks)
+    pub confirm_moves: bool,
+    /// Point selected by the first tap, waiting for the second
+    pending: Option<Pos>,
+    /// Last move seen by `show`, to detect new moves
+    seen_last_move: Option<Pos>,
+    placing: Option<PlaceAnimation>,
+    /// Forbidden points of the last position drawn, keyed by its stones,
+    /// side to move and rules (recomputed only when the position changes)
+    forbidden_cache: Option<(PositionKey, Vec<Pos>)>,
+    /// Root candidates of the last position drawn, keyed the same way
+    heatmap_cache: Option<(PositionKey, Vec<(Pos, i32)>)>,
+    /// Searcher ordering the heatmap candidates; created on first use
+    heatmap_searcher: Option<Searcher>,
 }
 
 impl Default for BoardView {
@@ -19,20 +72,37 @@ impl Default for BoardView {
         Self {
             cell_size: 30.0,
             board_rect: Rect::NOTHING,
+            theme: Theme::default(),
+            show_forbidden: true,
+            show_heatmap: false,
+            animate_moves: true,
+            flipped: false,
+            cursor: None,
+            confirm_moves: false,
+            pending: None,
+            seen_last_move: None,
+            placing: None,
+            forbidden_cache: None,
+            heatmap_cache: None,
+            heatmap_searcher: None,
         }
     }
 }
 
 impl BoardView {
-    /// Render the board and return click position if any.
+    /// Render the board, played by `rules`, and return click position if any.
+    /// `hints` are the engine's best moves for `current_turn`, best first.
     /// `extra_invalid` optionally rejects positions beyond normal rules (e.g. Pro opening).
+    #[allow(clippy::too_many_arguments)]
     pub fn show(
         &mut self,
         ui: &mut egui::Ui,
+        rules: RuleSet,
         board: &crate::Board,
         current_turn: Stone,
         last_move: Option<Pos>,
         suggested_move: Option<Pos>,
+        hints: &[RootLine],
         winning_line: Option<[Pos; 5]>,
         game_over: bool,
         capture_animation: Option<&CaptureAnimation>,
@@ -58,7 +128,7 @@ impl BoardView {
         );
 
         // Draw board background
-        painter.rect_filled(self.board_rect, CornerRadius::same(4), BOARD_BG);
+        painter.rect_filled(self.board_rect, CornerRadius::same(4), self.theme.colors().board);
 
         // Draw grid lines
         self.draw_grid(&painter);
@@ -69,14 +139,47 @@ impl BoardView {
         // Draw coordinate labels
         self.draw_coordinates(&painter);
 
+        // Start a placement animation for a new last move; a selection
+        // made for the old position no longer applies
+        if last_move != self.seen_last_move {
+            self.seen_last_move = last_move;
+            self.pending = None;
+            self.placing = last_move
+                .filter(|_| self.animate_moves)
+                .map(|pos| PlaceAnimation { pos, start: Instant::now() });
+        }
+        if self.placing.as_ref().is_some_and(|p| p.lift() <= 0.0) {
+            self.placing = None;
+        }
+
+        // Draw candidate heatmap
+        if self.show_heatmap && !game_over {
+            self.update_heatmap(rules, board, current_turn);
+            self.draw_heatmap(&painter);
+        }
+
         // Draw placed stones
         self.draw_stones(&painter, board);
+        if let Some(placing) = &self.placing {
+            self.draw_stone(&painter, placing.pos, board.get(placing.pos), placing.lift());
+            ui.ctx().request_repaint();
+        }
 
         // Draw last move marker
         if let Some(pos) = last_move {
             self.draw_last_move_marker(&painter, pos);
         }
 
+        // Draw forbidden points
+        if self.show_forbidden && !game_over {
+            self.update_forbidden(rules, board, current_turn);
+            if let Some((_, points)) = &self.forbidden_cache {
+                for &pos in points {
+                    self.draw_forbidden_marker(&painter, pos);
+                }
+            }
+        }
+
         // Draw winning line highlight
         if let Some(line) = winning_line {
             self.draw_winning_line(&painter, &line);
@@ -92,38 +195,125 @@ impl BoardView {
             self.draw_suggestion(&painter, pos, current_turn);
         }
 
-        // Handle hover preview and click
+        // Draw engine hints
+        if !game_over && !hints.is_empty() {
+            self.draw_hints(&painter, hints, last_move);
+        }
+
+        // Handle hover preview and click; the keyboard cursor previews
+        // while the pointer is off the board
         let mut clicked_pos = None;
 
         if !game_over {
-            if let Some(pointer_pos) = response.hover_pos() {
-                if let Some(board_pos) = self.screen_to_board(pointer_pos) {
-                    let is_valid = board.get(board_pos) == Stone::Empty
-                        && crate::rules::is_valid_move(board, board_pos, current_turn)
-                        && !extra_invalid.is_some_and(|f| f(board_pos));
-
-                    // Draw hover preview
-                    let hover_color = if is_valid {
-                        super::theme::hover_valid()
-                    } else {
-                        super::theme::hover_invalid()
-                    };
-                    self.draw_hover_preview(&painter, board_pos, current_turn, is_valid, hover_color);
+            let pointed = response.hover_pos().and_then(|p| self.screen_to_board(p));
+            if let Some(board_pos) = pointed.or(self.cursor) {
+                let is_valid = board.get(board_pos) == Stone::Empty
+                    && crate::rules::is_valid_move(rules, board, board_pos, current_turn)
+                    && !extra_invalid.is_some_and(|f| f(board_pos));
+
+                // Draw hover preview
+                let hover_color = if is_valid {
+                    super::theme::hover_valid()
+                } else {
+                    super::theme::hover_invalid()
+                };
+                self.draw_hover_preview(&painter, board_pos, current_turn, is_valid, hover_color);
 
-                    // Check for click
-                    if response.clicked() && is_valid {
+                // Check for click; in confirm mode the first one only selects
+                if pointed.is_some() && response.clicked() {
+                    if !is_valid {
+                        self.pending = None;
+                    } else if !self.confirm_moves || self.pending == Some(board_pos) {
                         clicked_pos = Some(board_pos);
+                        self.pending = None;
+                    } else {
+                        self.pending = Some(board_pos);
+                    }
+                    if is_valid {
+                        self.cursor = Some(board_pos);
                     }
                 }
             }
+            if let Some(pos) = self.pending.filter(|_| self.confirm_moves) {
+                self.draw_pending(&painter, pos, current_turn);
+            }
+            if let Some(pos) = self.cursor {
+                self.draw_cursor(&painter, pos);
+            }
+        } else {
+            self.pending = None;
         }
 
         clicked_pos
     }
 
+    /// Move the keyboard cursor by rows and columns as drawn (so arrows
+    /// follow the screen on a flipped board), stopping at the edge. The
+    /// first move puts it on the center point.
+    pub fn move_cursor(&mut self, d_row: i32, d_col: i32) {
+        let Some(pos) = self.cursor else {
+            self.cursor = Some(Pos::new(BOARD_SIZE as u8 / 2, BOARD_SIZE as u8 / 2));
+            return;
+        };
+        let shown = pos.transform(self.orientation());
+        let max = BOARD_SIZE as i32 - 1;
+        let row = (i32::from(shown.row) + d_row).clamp(0, max) as u8;
+        let col = (i32::from(shown.col) + d_col).clamp(0, max) as u8;
+        self.cursor = Some(Pos::new(row, col).transform(self.orientation()));
+    }
+
+    /// The cursor point and what is on it, for screen readers
+    pub fn cursor_description(&self, board: &crate::Board) -> Option<String> {
+        let pos = self.cursor?;
+        let what = match board.get(pos) {
+            Stone::Black => "black stone",
+            Stone::White => "white stone",
+            Stone::Empty => "empty",
+        };
+        Some(format!("Cursor {}: {}", notation().format(pos), what))
+    }
+
+    /// Show `board` for the position editor and return the point clicked,
+    /// empty or not. `brush` is previewed under the pointer.
+    pub fn show_setup(&mut self, ui: &mut egui::Ui, board: &crate::Board, brush: Stone) -> Option<Pos> {
+        // Drawn as a finished game: no move is checked against the rules
+        self.show(ui, RuleSet::NINUKI, board, brush, None, None, &[], None, true, None, None);
+        let response = ui.interact(self.board_rect, ui.id().with("setup"), Sense::click());
+        if let Some(pos) = self.cursor {
+            self.draw_cursor(ui.painter(), pos);
+        }
+        let pos = self.screen_to_board(response.hover_pos()?)?;
+        self.draw_hover_preview(ui.painter(), pos, brush, true, super::theme::hover_valid());
+        let clicked = response.clicked().then_some(pos);
+        if clicked.is_some() {
+            self.cursor = clicked;
+        }
+        clicked
+    }
+
+    /// Draw the stone selected by a first tap: half see-through, ringed
+    fn draw_pending(&self, painter: &Painter, pos: Pos, turn: Stone) {
+        let center = self.board_to_screen(pos);
+        let radius = self.cell_size * STONE_RADIUS_RATIO;
+        let fill = match turn {
+            Stone::Black => Color32::from_rgba_unmultiplied(20, 20, 20, 160),
+            Stone::White => Color32::from_rgba_unmultiplied(240, 240, 240, 160),
+            Stone::Empty => return,
+        };
+        painter.circle_filled(center, radius, fill);
+        painter.circle_stroke(center, radius, Stroke::new(2.0, ACCENT_BLUE));
+    }
+
+    /// Outline the keyboard cursor's point
+    fn draw_cursor(&self, painter: &Painter, pos: Pos) {
+        let center = self.board_to_screen(pos);
+        let rect = Rect::from_center_size(center, Vec2::splat(self.cell_size * 0.9));
+        painter.rect_stroke(rect, CornerRadius::same(2), Stroke::new(2.0, ACCENT_BLUE), egui::StrokeKind::Middle);
+    }
+
     /// Draw the 19x19 grid lines
     fn draw_grid(&self, painter: &Painter) {
-        let stroke = Stroke::new(GRID_LINE_WIDTH, GRID_LINE);
+        let stroke = Stroke::new(GRID_LINE_WIDTH, self.theme.colors().grid);
 
         for i in 0..BOARD_SIZE {
             let offset = BOARD_MARGIN + i as f32 * self.cell_size;
@@ -142,110 +332,131 @@ impl BoardView {
 
     /// Draw star points (hoshi)
     fn draw_star_points(&self, painter: &Painter) {
+        let color = self.theme.colors().star_point;
         for (row, col) in STAR_POINTS {
             let center = self.board_to_screen(Pos::new(row, col));
-            painter.circle_filled(center, STAR_POINT_RADIUS, STAR_POINT);
+            painter.circle_filled(center, STAR_POINT_RADIUS, color);
         }
     }
 
+    /// How board points are turned for display
+    fn orientation(&self) -> Symmetry {
+        if self.flipped { Symmetry::Rotate180 } else { Symmetry::Identity }
+    }
+
     /// Draw coordinate labels (A-T skipping I, 1-19)
     fn draw_coordinates(&self, painter: &Painter) {
         let font = egui::FontId::proportional(12.0);
+        let color = self.theme.colors().grid;
+        // Labels follow the configured notation so they match the move list
+        let notation = notation();
 
-        // Column labels (A-T, skipping I to match standard notation)
+        // Column labels
         for col in 0..BOARD_SIZE {
-            let col_byte = col as u8;
-            let letter = if col_byte < 8 {
-                (b'A' + col_byte) as char
-            } else {
-                (b'A' + col_byte + 1) as char // skip 'I'
-            };
+            let label_col = Pos::new(0, col as u8).transform(self.orientation()).col;
+            let letter = notation.column_letter(label_col);
             let x = self.board_rect.min.x + BOARD_MARGIN + col as f32 * self.cell_size;
 
             // Top
             let pos = Pos2::new(x - 4.0, self.board_rect.min.y + 8.0);
-            painter.text(pos, egui::Align2::CENTER_CENTER, letter, font.clone(), GRID_LINE);
+            painter.text(pos, egui::Align2::CENTER_CENTER, letter, font.clone(), color);
 
             // Bottom
             let pos = Pos2::new(x - 4.0, self.board_rect.max.y - 12.0);
-            painter.text(pos, egui::Align2::CENTER_CENTER, letter, font.clone(), GRID_LINE);
+            painter.text(pos, egui::Align2::CENTER_CENTER, letter, font.clone(), color);
         }
 
-        // Row labels (19-1, displayed top to bottom)
+        // Row labels
         for row in 0..BOARD_SIZE {
-            let num = BOARD_SIZE - row;
+            let label_row = Pos::new(row as u8, 0).transform(self.orientation()).row;
+            let num = notation.row_number(label_row);
             let y = self.board_rect.min.y + BOARD_MARGIN + row as f32 * self.cell_size;
 
             // Left
             let pos = Pos2::new(self.board_rect.min.x + 12.0, y);
-            painter.text(pos, egui::Align2::CENTER_CENTER, format!("{}", num), font.clone(), GRID_LINE);
+            painter.text(pos, egui::Align2::CENTER_CENTER, format!("{}", num), font.clone(), color);
 
             // Right
             let pos = Pos2::new(self.board_rect.max.x - 12.0, y);
-            painter.text(pos, egui::Align2::CENTER_CENTER, format!("{}", num), font.clone(), GRID_LINE);
+            painter.text(pos, egui::Align2::CENTER_CENTER, format!("{}", num), font.clone(), color);
         }
     }
 
-    /// Draw all placed stones
+    /// Draw all placed stones except the one being animated
     fn draw_stones(&self, painter: &Painter, board: &crate::Board) {
+        let placing = self.placing.as_ref().map(|p| p.pos);
         for row in 0..BOARD_SIZE {
             for col in 0..BOARD_SIZE {
                 let pos = Pos::new(row as u8, col as u8);
                 let stone = board.get(pos);
 
-                if stone != Stone::Empty {
-                    self.draw_stone(painter, pos, stone);
+                if stone != Stone::Empty && Some(pos) != placing {
+                    self.draw_stone(painter, pos, stone, 0.0);
                 }
             }
         }
     }
 
-    /// Draw a single stone with visual polish
-    fn draw_stone(&self, painter: &Painter, pos: Pos, stone: Stone) {
-        let center = self.board_to_screen(pos);
-        let radius = self.cell_size * STONE_RADIUS_RATIO;
+    /// Draw a single stone in the theme's colors and style, `lift` (0-1)
+    /// above the board: raised, enlarged, with a longer shadow
+    fn draw_stone(&self, painter: &Painter, pos: Pos, stone: Stone, lift: f32) {
+        let radius = self.cell_size * STONE_RADIUS_RATIO * (1.0 + 0.2 * lift);
+        let center = self.board_to_screen(pos) - Vec2::new(0.0, radius * 0.3 * lift);
+        let colors = self.theme.colors();
+        let style = self.theme.stone_style;
+
+        let (fill, accent) = match stone {
+            Stone::Black => (colors.black_stone, colors.black_highlight),
+            Stone::White => (colors.white_stone, colors.white_shadow),
+            Stone::Empty => return,
+        };
 
-        match stone {
-            Stone::Black => {
-                // Shadow
-                let shadow_offset = Vec2::new(2.0, 2.0);
-                painter.circle_filled(
-                    center + shadow_offset,
-                    radius,
-                    Color32::from_rgba_unmultiplied(0, 0, 0, 60),
-                );
+        // Shadow
+        if style != StoneStyle::Flat {
+            let alpha = if stone == Stone::Black { 60 } else { 40 };
+            let offset = 2.0 + 6.0 * lift;
+            painter.circle_filled(center + Vec2::new(offset, offset), radius, Color32::from_rgba_unmultiplied(0, 0, 0, alpha));
+        }
 
-                // Main stone
-                painter.circle_filled(center, radius, BLACK_STONE);
+        // Main stone
+        painter.circle_filled(center, radius, fill);
 
+        match (style, stone) {
+            (StoneStyle::Glossy, Stone::Black) => {
                 // Highlight
                 let highlight_offset = Vec2::new(-radius * 0.3, -radius * 0.3);
-                painter.circle_filled(
-                    center + highlight_offset,
-                    radius * 0.2,
-                    BLACK_STONE_HIGHLIGHT,
-                );
+                painter.circle_filled(center + highlight_offset, radius * 0.2, accent);
             }
-            Stone::White => {
-                // Shadow
-                let shadow_offset = Vec2::new(2.0, 2.0);
-                painter.circle_filled(
-                    center + shadow_offset,
-                    radius,
-                    Color32::from_rgba_unmultiplied(0, 0, 0, 40),
-                );
-
-                // Main stone
-                painter.circle_filled(center, radius, WHITE_STONE);
-
+            (StoneStyle::Glossy, _) => {
                 // Inner shadow for depth
-                painter.circle_stroke(
-                    center,
-                    radius * 0.85,
-                    Stroke::new(radius * 0.1, WHITE_STONE_SHADOW),
-                );
+                painter.circle_stroke(center, radius * 0.85, Stroke::new(radius * 0.1, accent));
+            }
+            (StoneStyle::Flat, _) => {
+                let outline = if stone == Stone::Black { accent } else { colors.grid };
+                painter.circle_stroke(center, radius, Stroke::new(1.0, outline));
+            }
+            (StoneStyle::Shell, Stone::Black) => {
+                // Matte slate: soft rim light instead of a specular spot
+                painter.circle_stroke(center, radius * 0.9, Stroke::new(radius * 0.12, accent.gamma_multiply(0.5)));
+            }
+            (StoneStyle::Shell, _) => {
+                // Clamshell stripes: slightly curved chords across the stone
+                let stroke = Stroke::new(1.0, accent.gamma_multiply(0.6));
+                for i in -3i8..=3 {
+                    let y = f32::from(i) * radius * 0.22;
+                    let half = (radius * radius - y * y).sqrt() * 0.9;
+                    let sag = radius * 0.08;
+                    painter.add(egui::Shape::line(
+                        vec![
+                            center + Vec2::new(-half, y),
+                            center + Vec2::new(0.0, y + sag),
+                            center + Vec2::new(half, y),
+                        ],
+                        stroke,
+                    ));
+                }
+                painter.circle_stroke(center, radius, Stroke::new(1.0, accent));
             }
-            Stone::Empty => {}
         }
     }
 
@@ -255,6 +466,58 @@ impl BoardView {
         painter.circle_filled(center, LAST_MOVE_MARKER_RADIUS, LAST_MOVE_MARKER);
     }
 
+    /// Recompute the forbidden points for `color` under `rules` if the
+    /// position changed. Every foul involves the new stone in a line with
+    /// existing stones, so only empty cells near existing stones can be
+    /// forbidden.
+    fn update_forbidden(&mut self, rules: RuleSet, board: &crate::Board, color: Stone) {
+        let key = (board.bitboards(), color, rules);
+        if self.forbidden_cache.as_ref().is_some_and(|(cached, _)| *cached == key) {
+            return;
+        }
+        let points = board
+            .candidate_moves()
+            .iter_ones()
+            .filter(|&pos| crate::rules::foul(rules, board, pos, color).is_some())
+            .collect();
+        self.forbidden_cache = Some((key, points));
+    }
+
+    /// Recompute the heatmap candidates when the position or rules change
+    fn update_heatmap(&mut self, rules: RuleSet, board: &crate::Board, color: Stone) {
+        let key = (board.bitboards(), color, rules);
+        if self.heatmap_cache.as_ref().is_some_and(|(cached, _)| *cached == key) {
+            return;
+        }
+        let searcher = self.heatmap_searcher.get_or_insert_with(|| Searcher::with_threads(1, 1));
+        searcher.set_rules(rules);
+        let moves = searcher.root_move_scores(board, color);
+        self.heatmap_cache = Some((key, moves));
+    }
+
+    /// Shade each candidate by its rank in the root move order, from hot
+    /// (tried first) to cold (tried last)
+    fn draw_heatmap(&self, painter: &Painter) {
+        let Some((_, moves)) = &self.heatmap_cache else { return };
+        let half = self.cell_size * 0.42;
+        let last = moves.len().saturating_sub(1).max(1) as f32;
+        for (i, &(pos, _)) in moves.iter().enumerate() {
+            let heat = 1.0 - i as f32 / last;
+            let color = HEAT_COLD.lerp_to_gamma(HEAT_HOT, heat).gamma_multiply(0.25 + 0.35 * heat);
+            let rect = Rect::from_center_size(self.board_to_screen(pos), Vec2::splat(half * 2.0));
+            painter.rect_filled(rect, CornerRadius::same(3), color);
+        }
+    }
+
+    /// Draw a small red cross on a forbidden point
+    fn draw_forbidden_marker(&self, painter: &Painter, pos: Pos) {
+        let center = self.board_to_screen(pos);
+        let half = self.cell_size * 0.18;
+        let stroke = Stroke::new(2.0, FORBIDDEN_MARKER);
+        painter.line_segment([center + Vec2::new(-half, -half), center + Vec2::new(half, half)], stroke);
+        painter.line_segment([center + Vec2::new(-half, half), center + Vec2::new(half, -half)], stroke);
+    }
+
     /// Draw winning line highlight
     fn draw_winning_line(&self, painter: &Painter, line: &[Pos; 5]) {
         let stroke = Stroke::new(4.0, WIN_HIGHLIGHT);
@@ -292,10 +555,38 @@ impl BoardView {
             egui::Align2::CENTER_CENTER,
             "?",
             egui::FontId::proportional(14.0),
-            if turn == Stone::Black { WHITE_STONE } else { BLACK_STONE },
+            if turn == Stone::Black { self.theme.colors().white_stone } else { self.theme.colors().black_stone },
         );
     }
 
+    /// Draw engine hints: a ring and score per candidate in rank color,
+    /// and an arrow from the last move to the best one
+    fn draw_hints(&self, painter: &Painter, hints: &[RootLine], last_move: Option<Pos>) {
+        let radius = self.cell_size * STONE_RADIUS_RATIO;
+
+        if let Some(from) = last_move.filter(|&p| p != hints[0].mov) {
+            let start = self.board_to_screen(from);
+            let end = self.board_to_screen(hints[0].mov);
+            let dir = (end - start).normalized();
+            let origin = start + dir * radius;
+            let shaft = end - dir * radius - origin;
+            painter.arrow(origin, shaft, Stroke::new(3.0, HINT_COLORS[0].gamma_multiply(0.7)));
+        }
+
+        for (hint, &color) in hints.iter().zip(HINT_COLORS.iter()).rev() {
+            let center = self.board_to_screen(hint.mov);
+            painter.circle_filled(center, radius, color.gamma_multiply(0.35));
+            painter.circle_stroke(center, radius, Stroke::new(2.5, color));
+            painter.text(
+                center,
+                egui::Align2::CENTER_CENTER,
+                score_label(hint.score),
+                egui::FontId::proportional((self.cell_size * 0.32).max(9.0)),
+                BLACK_STONE,
+            );
+        }
+    }
+
     /// Draw hover preview
     fn draw_hover_preview(&self, painter: &Painter, pos: Pos, turn: Stone, is_valid: bool, hover_color: Color32) {
         let center = self.board_to_screen(pos);
@@ -324,7 +615,7 @@ impl BoardView {
         let row = y.floor() as i32;
 
         if col >= 0 && col < BOARD_SIZE as i32 && row >= 0 && row < BOARD_SIZE as i32 {
-            Some(Pos::new(row as u8, col as u8))
+            Some(Pos::new(row as u8, col as u8).transform(self.orientation()))
         } else {
             None
         }
@@ -332,6 +623,7 @@ impl BoardView {
 
     /// Convert board position to screen coordinates
     pub fn board_to_screen(&self, pos: Pos) -> Pos2 {
+        let pos = pos.transform(self.orientation());
         let x = self.board_rect.min.x + BOARD_MARGIN + pos.col as f32 * self.cell_size;
         let y = self.board_rect.min.y + BOARD_MARGIN + pos.row as f32 * self.cell_size;
         Pos2::new(x, y)
@@ -341,6 +633,7 @@ impl BoardView {
     fn draw_capture_animation(&self, painter: &Painter, animation: &CaptureAnimation) {
         let progress = animation.progress();
 
+        let colors = self.theme.colors();
         for pos in &animation.positions {
             let center = self.board_to_screen(*pos);
             let base_radius = self.cell_size * STONE_RADIUS_RATIO;
@@ -362,8 +655,8 @@ impl BoardView {
 
                 // Stone still visible
                 let stone_color = match animation.captured_color {
-                    Stone::Black => BLACK_STONE,
-                    Stone::White => WHITE_STONE,
+                    Stone::Black => colors.black_stone,
+                    Stone::White => colors.white_stone,
                     Stone::Empty => return,
                 };
                 painter.circle_filled(center, radius, stone_color);
@@ -384,10 +677,10 @@ impl BoardView {
                 );
 
                 // Shrinking stone
-                let stone_alpha = ((1.0 - phase_progress * 0.5) * 255.0) as u8;
+                let stone_alpha = 1.0 - phase_progress * 0.5;
                 let stone_color = match animation.captured_color {
-                    Stone::Black => Color32::from_rgba_unmultiplied(25, 25, 30, stone_alpha),
-                    Stone::White => Color32::from_rgba_unmultiplied(250, 250, 252, stone_alpha),
+                    Stone::Black => colors.black_stone.gamma_multiply(stone_alpha),
+                    Stone::White => colors.white_stone.gamma_multiply(stone_alpha),
                     Stone::Empty => return,
                 };
                 painter.circle_filled(center, radius, stone_color);
@@ -416,3 +709,13 @@ impl BoardView {
         }
     }
 }
+
+/// Short score label: `W3`/`L3` for a forced win/loss in 3 moves,
+/// otherwise the score in thousands
+pub(super) fn score_label(score: i32) -> String {
+    match mate_distance(score) {
+        Some(n) if n > 0 => format!("W{}", n),
+        Some(n) => format!("L{}", -n),
+        None => format!("{:+.1}", score as f32 / 1000.0),
+    }
+}
@@ -1,14 +1,29 @@
 //! Game state management for the Gomoku GUI
 
-use crate::{AIEngine, Board, MoveResult, Pos, Stone, ai_log, pos_to_notation, rules};
-use std::sync::mpsc::{channel, Receiver};
+use crate::annotate::{annotate_game, GameAnnotation, MoveAnnotation};
+use crate::archive::{ArchivedGame, GameScore};
+use crate::clock::{GameClock, TimeControl, TimeManager};
+use crate::game::{Game, GameOutcome, WinReason};
+use crate::logging::log_info;
+use crate::pool::EnginePool;
+use crate::record::GameRecord;
+use crate::report::{GameReport, Swing};
+use crate::search::{RootLine, SearchInfo, Searcher};
+use crate::{AIEngine, Board, Hint, MoveResult, Pos, SearchHandle, Stone, pos_to_notation, rules};
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
 use std::thread;
 use std::time::{Duration, Instant};
 
+/// Player names of archived games
+pub const HUMAN_PLAYER: &str = "Human";
+pub const ENGINE_PLAYER: &str = "Engine";
+
 /// Opening rule variants for game start
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OpeningRule {
     /// No restrictions
+    #[default]
     Standard,
     /// Move 1: center, Move 3: ≥3 intersections from center
     Pro,
@@ -16,14 +31,9 @@ pub enum OpeningRule {
     Swap,
 }
 
-impl Default for OpeningRule {
-    fn default() -> Self {
-        OpeningRule::Standard
-    }
-}
-
 /// Game mode selection
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameMode {
     /// Player vs AI
     PvE {
@@ -44,16 +54,20 @@ impl Default for GameMode {
 }
 
 /// AI computation state
+#[derive(Default)]
 pub enum AiState {
+    #[default]
     Idle,
     Thinking {
-        receiver: Receiver<(MoveResult, AIEngine)>,
-        start_time: Instant,
+        /// The running search; cancels it and reports its progress
+        handle: SearchHandle,
+        /// Play a fallback move if the search runs longer than this
+        timeout: Duration,
     },
     /// Timed out but still waiting for the thread to finish so we can reclaim the engine.
     /// This prevents losing the 64MB TT cache on timeout.
     Reclaiming {
-        receiver: Receiver<(MoveResult, AIEngine)>,
+        handle: SearchHandle,
     },
 }
 
@@ -87,6 +101,7 @@ impl CaptureAnimation {
 
 /// Cumulative AI statistics across a game
 #[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AiStats {
     /// Number of AI moves made
     pub move_count: u32,
@@ -104,6 +119,9 @@ pub struct AiStats {
     pub move_times: Vec<u64>,
     /// History of per-move depths
     pub move_depths: Vec<i8>,
+    /// Every move's full result, for the post-game report
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub results: Vec<MoveResult>,
 }
 
 impl AiStats {
@@ -127,6 +145,7 @@ impl AiStats {
         }
         self.move_times.push(result.time_ms);
         self.move_depths.push(result.depth);
+        self.results.push(result.clone());
     }
 
     /// Average time excluding non-search moves (depth=0 from VCF/Defense/Opening).
@@ -163,23 +182,85 @@ impl AiStats {
     }
 
     pub fn avg_nps(&self) -> u64 {
-        if self.total_time_ms == 0 { 0 } else { self.total_nodes * 1000 / self.total_time_ms / 1000 }
+        (self.total_nodes * 1000).checked_div(self.total_time_ms).unwrap_or(0) / 1000
+    }
+}
+
+/// Engine settings adjustable from the GUI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AiSettings {
+    /// Maximum alpha-beta depth
+    pub max_depth: i8,
+    /// Time per move (ms) when no time control is set
+    pub time_limit_ms: u64,
+    /// Lazy SMP search threads
+    pub threads: usize,
+    /// Transposition table size (MB)
+    pub hash_mb: usize,
+}
+
+impl Default for AiSettings {
+    /// 64MB TT, depth 20, 500ms, one thread per core (up to 8)
+    fn default() -> Self {
+        Self {
+            max_depth: 20,
+            time_limit_ms: 500,
+            threads: std::thread::available_parallelism().map_or(4, |n| n.get().min(8)),
+            hash_mb: 64,
+        }
     }
 }
 
 /// Main game state
+///
+/// With the `serde` feature, the game record (board, history, mode, AI results
+/// and settings) is serialized; in-flight AI work, timers and animations are
+/// runtime-only and come back in their idle state, with a fresh AI engine.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameState {
     pub board: Board,
     pub mode: GameMode,
     pub current_turn: Stone,
     pub game_over: Option<GameResult>,
     pub last_move: Option<Pos>,
+    /// Set-up position the game started from and its side to move (`None`:
+    /// the empty board, Black to move). `move_history` starts here.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub start: Option<(Board, Stone)>,
     pub move_history: Vec<(Pos, Stone)>,
+    /// Engine score of each move in `move_history`, from Black's side:
+    /// the AI's search score for its moves, the hint analysis of the
+    /// position for human moves (`None` when nothing was analyzed)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub move_scores: Vec<Option<i32>>,
     pub last_ai_result: [Option<MoveResult>; 2],
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub ai_state: AiState,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub move_timer: MoveTimer,
-    pub suggested_move: Option<Pos>,
+    /// Suggested move and its explanation (PvP hint)
+    pub suggestion: Option<Hint>,
+    /// Background search producing `suggestion`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    suggestion_analysis: Option<Receiver<Option<Hint>>>,
+    /// Show the engine's best moves for the side to move on human turns
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub show_hints: bool,
+    /// Best moves for the current position, best first (`None` until analyzed)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub hints: Option<Vec<RootLine>>,
+    /// Background analysis producing `hints`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    hint_analysis: Option<Receiver<Vec<RootLine>>>,
+    /// Move-quality annotation of the finished game, for the review
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub annotation: Option<GameAnnotation>,
+    /// Background analysis producing `annotation`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    annotation_analysis: Option<Receiver<Result<GameAnnotation, String>>>,
     pub message: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub capture_animation: Option<CaptureAnimation>,
     pub ai_stats: [AiStats; 2],
     /// Review mode: when Some(index), shows board at move #index
@@ -188,31 +269,69 @@ pub struct GameState {
     pub redo_groups: Vec<Vec<(Pos, Stone)>>,
     /// Opening rule for this game
     pub opening_rule: OpeningRule,
+    /// Rules this game is played by; other games keep their own
+    #[cfg_attr(feature = "serde", serde(default))]
+    rules: rules::RuleSet,
     /// Swap rule: waiting for swap decision after 3rd move
     pub swap_pending: bool,
+    /// Pending draw offer: the color that offered
+    pub draw_offer: Option<Stone>,
     /// Per-color last move duration [Black, White]
     pub last_move_time: [Option<std::time::Duration>; 2],
+    /// Time control for both players (`None` = untimed)
+    pub time_control: Option<TimeControl>,
+    /// Running clocks when a time control is set
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub clock: Option<GameClock>,
 
     // Persistent AI engine (reuses TT across moves)
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_ai_engine"))]
     ai_engine: Option<AIEngine>,
+    /// Pool `ai_engine` came from and goes back to when the game is dropped
+    #[cfg_attr(feature = "serde", serde(skip))]
+    engine_pool: Option<EnginePool>,
 
     // AI engine configuration
-    ai_depth: i8,
-    ai_time_limit_ms: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    ai_settings: AiSettings,
+    /// Settings `ai_engine` was last configured with
+    #[cfg_attr(feature = "serde", serde(skip))]
+    engine_settings: AiSettings,
+    ai_resign_threshold: Option<i32>,
+    /// The finished game went to the archive ([`Self::take_archive_entry`])
+    #[cfg_attr(feature = "serde", serde(skip))]
+    archived: bool,
 }
 
 /// Game result
+///
+/// `winner` is `Stone::Empty` for a draw.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameResult {
     pub winner: Stone,
     pub win_type: WinType,
     pub winning_line: Option<[Pos; 5]>,
 }
 
+impl GameResult {
+    /// Check if the game ended without a winner
+    pub fn is_draw(&self) -> bool {
+        self.win_type == WinType::DrawAgreement
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WinType {
     FiveInRow,
     Capture,
+    /// The loser resigned
+    Resignation,
+    /// The loser ran out of time
+    Timeout,
+    /// Both players agreed to a draw (no winner)
+    DrawAgreement,
 }
 
 /// Move timer for tracking thinking time
@@ -253,34 +372,110 @@ impl MoveTimer {
     }
 }
 
+/// Engine used by new games, with [`AiSettings::default`].
+#[cfg(feature = "serde")]
+fn default_ai_engine() -> Option<AIEngine> {
+    Some(new_ai_engine(&AiSettings::default()))
+}
+
+fn new_ai_engine(settings: &AiSettings) -> AIEngine {
+    let mut engine = AIEngine::with_config(settings.hash_mb, settings.max_depth, settings.time_limit_ms);
+    engine.set_threads(settings.threads);
+    engine
+}
+
+/// AI auto-resign threshold used by the GUI: resign once a forced loss is found
+pub const AI_RESIGN_SCORE: i32 = -900_000;
+
+/// Move hints: lines shown, and depth/node budget per line
+const HINT_LINES: usize = 3;
+const HINT_DEPTH: i8 = 10;
+const HINT_NODES: u64 = 100_000;
+
+/// Fallback move if an untimed AI search runs this long
+const AI_MOVE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Clocks for a new game, with Black's running
+fn start_clock(time_control: Option<TimeControl>) -> Option<GameClock> {
+    time_control.map(|control| {
+        let mut clock = GameClock::new(control);
+        clock.start(Stone::Black);
+        clock
+    })
+}
+
 impl GameState {
     pub fn new(mode: GameMode) -> Self {
         Self::with_opening_rule(mode, OpeningRule::Standard)
     }
 
     pub fn with_opening_rule(mode: GameMode, opening_rule: OpeningRule) -> Self {
+        Self::with_engine(mode, opening_rule, new_ai_engine(&AiSettings::default()), None)
+    }
+
+    /// A game whose engine comes from `pool` and returns to it when the
+    /// game is dropped, so games opened and closed side by side (the GUI's
+    /// tabs) reuse engines instead of allocating a table each
+    pub fn with_engine_pool(mode: GameMode, opening_rule: OpeningRule, pool: &EnginePool) -> Self {
+        Self::with_engine(mode, opening_rule, pool.take(), Some(pool.clone()))
+    }
+
+    fn with_engine(mode: GameMode, opening_rule: OpeningRule, engine: AIEngine, engine_pool: Option<EnginePool>) -> Self {
+        // Only the table size and threads are applied conditionally
+        let engine_settings = AiSettings { hash_mb: engine.hash_size(), threads: engine.threads(), ..AiSettings::default() };
         Self {
             board: Board::new(),
             mode,
             current_turn: Stone::Black,
             game_over: None,
             last_move: None,
+            start: None,
             move_history: Vec::new(),
+            move_scores: Vec::new(),
             last_ai_result: [None, None],
             ai_state: AiState::Idle,
             move_timer: MoveTimer::default(),
-            suggested_move: None,
+            suggestion: None,
+            suggestion_analysis: None,
+            show_hints: false,
+            hints: None,
+            hint_analysis: None,
+            annotation: None,
+            annotation_analysis: None,
             message: None,
             capture_animation: None,
             ai_stats: [AiStats::default(), AiStats::default()],
             review_index: None,
             redo_groups: Vec::new(),
             opening_rule,
+            rules: rules::RuleSet::NINUKI,
             swap_pending: false,
+            draw_offer: None,
             last_move_time: [None, None],
-            ai_engine: Some(AIEngine::with_config(64, 20, 500)),
-            ai_depth: 20,
-            ai_time_limit_ms: 500,
+            time_control: None,
+            clock: None,
+            ai_engine: Some(engine),
+            engine_pool,
+            ai_settings: AiSettings::default(),
+            engine_settings,
+            ai_resign_threshold: None,
+            archived: false,
+        }
+    }
+
+    /// A new engine with the current settings, from the pool if the game has one
+    fn fresh_engine(&mut self) -> AIEngine {
+        match &self.engine_pool {
+            Some(pool) => {
+                let engine = pool.take();
+                self.engine_settings =
+                    AiSettings { hash_mb: engine.hash_size(), threads: engine.threads(), ..self.ai_settings };
+                engine
+            }
+            None => {
+                self.engine_settings = self.ai_settings;
+                new_ai_engine(&self.ai_settings)
+            }
         }
     }
 
@@ -289,23 +484,53 @@ impl GameState {
         self.current_turn = Stone::Black;
         self.game_over = None;
         self.last_move = None;
+        self.start = None;
         self.move_history.clear();
+        self.move_scores.clear();
         self.last_ai_result = [None, None];
         self.ai_state = AiState::Idle;
         self.move_timer = MoveTimer::default();
-        self.suggested_move = None;
+        self.clear_suggestion();
+        self.clear_hints();
+        self.annotation = None;
+        self.annotation_analysis = None;
         self.message = None;
         self.capture_animation = None;
         self.ai_stats = [AiStats::default(), AiStats::default()];
         self.review_index = None;
         self.redo_groups.clear();
         self.swap_pending = false;
+        self.draw_offer = None;
         self.last_move_time = [None, None];
+        self.archived = false;
+        self.clock = start_clock(self.time_control);
         if let Some(ref mut engine) = self.ai_engine {
             engine.clear_cache();
         }
     }
 
+    /// Start over from a set-up position (see [`Game::from_setup`]) and
+    /// its rules, keeping the mode. Opening rules don't apply to it.
+    ///
+    /// [`Game::from_setup`]: crate::game::Game::from_setup
+    pub fn start_from_setup(&mut self, game: &Game) {
+        self.reset();
+        self.opening_rule = OpeningRule::Standard;
+        self.rules = game.rules();
+        self.board = game.board().clone();
+        self.current_turn = game.to_move();
+        self.start = Some((self.board.clone(), self.current_turn));
+        self.move_timer.start();
+        if let Some(clock) = &mut self.clock {
+            clock.start(self.current_turn);
+        }
+    }
+
+    /// Position `move_history` starts from and its side to move
+    fn start_position(&self) -> (Board, Stone) {
+        self.start.clone().unwrap_or_else(|| (Board::new(), Stone::Black))
+    }
+
     /// Execute color swap (Swap rule)
     pub fn execute_swap(&mut self) {
         self.swap_pending = false;
@@ -326,6 +551,196 @@ impl GameState {
         self.message = Some("Swap declined, game continues.".to_string());
     }
 
+    /// Play this game by `rules`, dropping engine results searched under
+    /// the old rules; other games keep theirs
+    pub fn set_rule_set(&mut self, rules: rules::RuleSet) {
+        self.rules = rules;
+        if let Some(ref mut engine) = self.ai_engine {
+            engine.set_rules(rules);
+        }
+    }
+
+    /// Rules this game is played by
+    pub fn rule_set(&self) -> rules::RuleSet {
+        self.rules
+    }
+
+    /// Set the AI auto-resign threshold (`None` = play every game out)
+    pub fn set_ai_resign_threshold(&mut self, threshold: Option<i32>) {
+        // Applied to the engine when the next search starts
+        self.ai_resign_threshold = threshold;
+    }
+
+    /// Get the AI auto-resign threshold
+    pub fn ai_resign_threshold(&self) -> Option<i32> {
+        self.ai_resign_threshold
+    }
+
+    /// Current engine settings
+    pub fn ai_settings(&self) -> AiSettings {
+        self.ai_settings
+    }
+
+    /// Change the engine settings; applied to the engine when the next
+    /// search starts, keeping its TT unless the size changed
+    pub fn set_ai_settings(&mut self, settings: AiSettings) {
+        self.ai_settings = settings;
+    }
+
+    /// Bring `engine` up to date with `ai_settings`
+    fn configure_engine(&mut self, engine: &mut AIEngine) {
+        let (old, new) = (self.engine_settings, self.ai_settings);
+        if old.hash_mb != new.hash_mb {
+            engine.set_hash_size(new.hash_mb);
+        }
+        if old.threads != new.threads {
+            engine.set_threads(new.threads);
+        }
+        engine.set_max_depth(new.max_depth);
+        engine.set_time_limit(new.time_limit_ms);
+        self.engine_settings = new;
+        // A pooled engine may come from a game with other rules
+        engine.set_rules(self.rules);
+    }
+
+    /// Resign the game for `color`
+    pub fn resign(&mut self, color: Stone) -> Result<(), String> {
+        if self.game_over.is_some() {
+            return Err("Game is over".to_string());
+        }
+        if color == Stone::Empty {
+            return Err("Only a player can resign".to_string());
+        }
+
+        let result = GameResult {
+            winner: color.opponent(),
+            win_type: WinType::Resignation,
+            winning_line: None,
+        };
+        self.finish_game(result);
+        Ok(())
+    }
+
+    /// Offer a draw on behalf of `color`
+    ///
+    /// Against the AI the engine answers immediately (see
+    /// `AIEngine::should_accept_draw`); in PvP the offer stays pending until
+    /// the opponent accepts, declines or makes a move.
+    pub fn offer_draw(&mut self, color: Stone) -> Result<(), String> {
+        if self.game_over.is_some() {
+            return Err("Game is over".to_string());
+        }
+        if color == Stone::Empty {
+            return Err("Only a player can offer a draw".to_string());
+        }
+        if self.draw_offer.is_some() {
+            return Err("A draw offer is already pending".to_string());
+        }
+
+        match self.mode {
+            GameMode::PvE { .. } => {
+                if self.is_ai_thinking() {
+                    return Err("AI is thinking".to_string());
+                }
+                let accepted = self
+                    .ai_engine
+                    .as_ref()
+                    .is_some_and(|engine| engine.should_accept_draw(&self.board, color.opponent()));
+                if accepted {
+                    self.finish_game(Self::draw_result());
+                } else {
+                    self.message = Some("AI declined the draw".to_string());
+                }
+            }
+            GameMode::PvP { .. } => {
+                self.draw_offer = Some(color);
+                let name = if color == Stone::Black { "Black" } else { "White" };
+                self.message = Some(format!("{} offers a draw", name));
+            }
+            GameMode::AiVsAi => return Err("No player to offer a draw".to_string()),
+        }
+        Ok(())
+    }
+
+    /// Accept the pending draw offer
+    pub fn accept_draw(&mut self) -> Result<(), String> {
+        if self.game_over.is_some() {
+            return Err("Game is over".to_string());
+        }
+        if self.draw_offer.take().is_none() {
+            return Err("No draw offer to accept".to_string());
+        }
+        self.finish_game(Self::draw_result());
+        Ok(())
+    }
+
+    /// Decline the pending draw offer
+    pub fn decline_draw(&mut self) {
+        if self.draw_offer.take().is_some() {
+            self.message = Some("Draw declined".to_string());
+        }
+    }
+
+    /// Set the time control; takes effect immediately with full clocks
+    pub fn set_time_control(&mut self, time_control: Option<TimeControl>) {
+        self.time_control = time_control;
+        self.clock = time_control.map(GameClock::new);
+        if self.game_over.is_none() {
+            if let Some(clock) = &mut self.clock {
+                clock.start(self.current_turn);
+            }
+        }
+    }
+
+    /// End the game if the side to move has run out of time
+    pub fn check_clock(&mut self) {
+        if self.game_over.is_some() {
+            return;
+        }
+        if let Some(flagged) = self.clock.as_ref().and_then(GameClock::flagged) {
+            self.finish_game(Self::timeout_result(flagged));
+            self.message = Some("Time out".to_string());
+        }
+    }
+
+    fn timeout_result(loser: Stone) -> GameResult {
+        GameResult {
+            winner: loser.opponent(),
+            win_type: WinType::Timeout,
+            winning_line: None,
+        }
+    }
+
+    fn draw_result() -> GameResult {
+        GameResult {
+            winner: Stone::Empty,
+            win_type: WinType::DrawAgreement,
+            winning_line: None,
+        }
+    }
+
+    /// End the game without a move (resignation or agreed draw)
+    fn finish_game(&mut self, result: GameResult) {
+        let move_num = self.move_history.len();
+        if result.is_draw() {
+            log_info!("\n*** GAME OVER: DRAW by agreement (move #{}) ***", move_num);
+        } else {
+            let winner_str = if result.winner == Stone::Black { "BLACK" } else { "WHITE" };
+            let how = if result.win_type == WinType::Timeout { "on time" } else { "by resignation" };
+            log_info!("\n*** GAME OVER: {} WINS {} (move #{}) ***",
+                winner_str, how, move_num);
+        }
+        self.move_timer.stop();
+        if let Some(clock) = &mut self.clock {
+            clock.pause();
+        }
+        self.draw_offer = None;
+        self.swap_pending = false;
+        self.clear_suggestion();
+        self.clear_hints();
+        self.game_over = Some(result);
+    }
+
     /// Check if it's the human's turn
     pub fn is_human_turn(&self) -> bool {
         match self.mode {
@@ -383,30 +798,32 @@ impl GameState {
         if !self.board.is_empty(pos) {
             return Err("Position is occupied".to_string());
         }
-        if rules::is_double_three(&self.board, pos, self.current_turn) {
-            return Err("Forbidden: Double-three".to_string());
+        if let Some(foul) = rules::foul(self.rules, &self.board, pos, self.current_turn) {
+            return Err(format!("Forbidden: {}", foul));
         }
-        if !rules::is_valid_move(&self.board, pos, self.current_turn) {
+        if !rules::is_valid_move(self.rules, &self.board, pos, self.current_turn) {
             return Err("Invalid move".to_string());
         }
 
         // New move invalidates redo history
         self.redo_groups.clear();
 
-        // Place the stone
-        self.execute_move(pos);
+        // Place the stone, scored by the hint analysis if one finished
+        let score = self.hints.as_ref().and_then(|hints| hints.first()).map(|line| line.score);
+        self.execute_move(pos, score);
         Ok(())
     }
 
-    /// Execute a move (for both human and AI)
-    fn execute_move(&mut self, pos: Pos) {
+    /// Execute a move (for both human and AI); `score` is the engine's
+    /// score for the mover, if known
+    fn execute_move(&mut self, pos: Pos, score: Option<i32>) {
         let color = self.current_turn;
         let is_human = !self.is_ai_turn();
         let move_num = self.move_history.len() + 1;
 
         // Place stone and handle captures
         self.board.place_stone(pos, color);
-        let captured_positions = rules::execute_captures(&mut self.board, pos, color);
+        let captured_positions = rules::execute_captures(self.rules, &mut self.board, pos, color);
         let capture_count = captured_positions.len() / 2; // Each capture is a pair
 
         // Log moves for game reconstruction
@@ -418,11 +835,11 @@ impl GameState {
             String::new()
         };
         if is_human {
-            ai_log(&format!("  >> Human #{}: {} plays {}{}",
-                move_num, color_str, pos_to_notation(pos), cap_str));
+            log_info!("  >> Human #{}: {} plays {}{}",
+                move_num, color_str, pos_to_notation(pos), cap_str);
         } else {
-            ai_log(&format!("  >> AI #{}: {} plays {}{}",
-                move_num, color_str, pos_to_notation(pos), cap_str));
+            log_info!("  >> AI #{}: {} plays {}{}",
+                move_num, color_str, pos_to_notation(pos), cap_str);
         }
 
         // Start capture animation if any captures occurred
@@ -435,23 +852,42 @@ impl GameState {
 
         // Record move
         self.move_history.push((pos, color));
+        self.move_scores.push(score.map(|s| if color == Stone::Black { s } else { -s }));
         self.last_move = Some(pos);
-        self.suggested_move = None;
+        self.clear_suggestion();
+        self.clear_hints();
+
+        // Moving instead of answering declines the opponent's draw offer
+        if self.draw_offer.is_some_and(|offerer| offerer != color) {
+            self.draw_offer = None;
+        }
 
         // Stop timer and record per-color duration
         let duration = self.move_timer.stop();
         let idx = if color == Stone::Black { 0 } else { 1 };
         self.last_move_time[idx] = Some(duration);
 
+        // Flag fall: the move came too late
+        if self.clock.as_mut().is_some_and(|clock| !clock.press(color)) {
+            self.finish_game(Self::timeout_result(color));
+            return;
+        }
+
         // Check for win
         if let Some(result) = self.check_win(pos, color) {
             let winner_str = if result.winner == Stone::Black { "BLACK" } else { "WHITE" };
             let win_type_str = match result.win_type {
                 WinType::FiveInRow => "5-in-a-row",
                 WinType::Capture => "capture",
+                WinType::Resignation => "resignation",
+                WinType::Timeout => "timeout",
+                WinType::DrawAgreement => "agreement",
             };
-            ai_log(&format!("\n*** GAME OVER: {} WINS by {} (move #{}) ***",
-                winner_str, win_type_str, move_num));
+            log_info!("\n*** GAME OVER: {} WINS by {} (move #{}) ***",
+                winner_str, win_type_str, move_num);
+            if let Some(clock) = &mut self.clock {
+                clock.pause();
+            }
             self.game_over = Some(result);
             return;
         }
@@ -459,6 +895,9 @@ impl GameState {
         // Switch turn
         self.current_turn = color.opponent();
         self.move_timer.start();
+        if let Some(clock) = &mut self.clock {
+            clock.start(self.current_turn);
+        }
 
         // Swap rule: after 3rd move, trigger swap decision
         if self.opening_rule == OpeningRule::Swap && self.move_history.len() == 3 {
@@ -472,13 +911,7 @@ impl GameState {
     /// Check for win condition
     fn check_win(&self, pos: Pos, color: Stone) -> Option<GameResult> {
         // Check capture win
-        let total_captures = if color == Stone::Black {
-            self.board.black_captures
-        } else {
-            self.board.white_captures
-        };
-
-        if total_captures >= 5 {
+        if self.board.captures_to_win(self.rules, color) == 0 {
             return Some(GameResult {
                 winner: color,
                 win_type: WinType::Capture,
@@ -490,7 +923,7 @@ impl GameState {
         // In Ninuki-renju, a breakable five gives the opponent one chance to
         // capture and break it. If they fail (don't break it), the five-holder wins.
         let opponent = color.opponent();
-        if let Some(opp_five) = rules::find_five_positions(&self.board, opponent) {
+        if let Some(opp_five) = rules::find_five_positions(self.rules, &self.board, opponent) {
             let winning_line = if opp_five.len() >= 5 {
                 Some([opp_five[0], opp_five[1], opp_five[2], opp_five[3], opp_five[4]])
             } else {
@@ -506,7 +939,7 @@ impl GameState {
         // Check five-in-a-row by the current player
         if let Some(line) = self.find_winning_line(pos, color) {
             let line_vec: Vec<Pos> = line.to_vec();
-            if !rules::can_break_five_by_capture(&self.board, &line_vec, color) {
+            if !rules::can_break_five_by_capture(self.rules, &self.board, &line_vec, color) {
                 return Some(GameResult {
                     winner: color,
                     win_type: WinType::FiveInRow,
@@ -529,7 +962,7 @@ impl GameState {
             // Count in negative direction
             let mut r = pos.row as i8;
             let mut c = pos.col as i8;
-            while r >= 0 && r < 19 && c >= 0 && c < 19 {
+            while (0..19).contains(&r) && (0..19).contains(&c) {
                 let p = Pos::new(r as u8, c as u8);
                 if self.board.get(p) == color {
                     line.insert(0, p);
@@ -543,7 +976,7 @@ impl GameState {
             // Count in positive direction (skip center)
             r = pos.row as i8 + dr;
             c = pos.col as i8 + dc;
-            while r >= 0 && r < 19 && c >= 0 && c < 19 {
+            while (0..19).contains(&r) && (0..19).contains(&c) {
                 let p = Pos::new(r as u8, c as u8);
                 if self.board.get(p) == color {
                     line.push(p);
@@ -583,20 +1016,59 @@ impl GameState {
         // Take engine out (will be returned after search)
         let mut engine = match self.ai_engine.take() {
             Some(e) => e,
-            None => AIEngine::with_config(64, self.ai_depth, self.ai_time_limit_ms),
+            None => self.fresh_engine(),
         };
+        self.configure_engine(&mut engine);
+        engine.set_resign_threshold(self.ai_resign_threshold);
 
-        let (tx, rx) = channel();
+        // Under a time control the AI budgets from its clock
+        let allowance = self.clock.as_ref().map(|clock| clock.allowance(color));
+        let timeout = self.clock.as_ref().map_or(AI_MOVE_TIMEOUT, |clock| {
+            AI_MOVE_TIMEOUT.max(clock.budget(&TimeManager::default(), self.rules, &board, color) * 2)
+        });
 
-        thread::spawn(move || {
-            let result = engine.get_move_with_stats(&board, color);
-            let _ = tx.send((result, engine));
+        let handle = engine.search_async_with(move |engine| match allowance {
+            Some((remaining, increment)) => engine.get_move_with_clock(&board, color, remaining, increment),
+            None => engine.get_move_with_stats(&board, color),
         });
 
-        self.ai_state = AiState::Thinking {
-            receiver: rx,
-            start_time: Instant::now(),
-        };
+        self.ai_state = AiState::Thinking { handle, timeout };
+    }
+
+    /// Per-frame work: collect AI and analysis results, check the clock,
+    /// and start the AI's turn or its swap decision. `paused` (a position
+    /// being set up over the game) holds the AI back.
+    pub fn update(&mut self, paused: bool) {
+        self.check_ai_result();
+        self.check_clock();
+        self.update_hints();
+        self.update_suggestion();
+        self.update_annotation();
+
+        // Clean up completed capture animations
+        if self.capture_animation.as_ref().is_some_and(CaptureAnimation::is_complete) {
+            self.capture_animation = None;
+        }
+
+        // Start AI thinking if needed (not during swap decision)
+        if self.is_ai_turn() && !self.is_ai_thinking() && self.game_over.is_none() && !self.swap_pending && !paused {
+            self.start_ai_thinking();
+        }
+
+        // Auto-decide swap for AI in PvE/AiVsAi mode
+        if self.swap_pending {
+            match self.mode {
+                GameMode::PvE { human_color } if self.current_turn != human_color => {
+                    // AI decides: always swap (takes initiative)
+                    self.execute_swap();
+                }
+                GameMode::AiVsAi => {
+                    // AI auto-decides: always decline swap
+                    self.decline_swap();
+                }
+                _ => {}
+            }
+        }
     }
 
     /// Check if AI has finished thinking
@@ -605,36 +1077,37 @@ impl GameState {
         // This runs every frame and recovers the engine + TT cache once the thread finishes.
         self.try_reclaim_engine();
 
-        // Check if AI has timed out (5 seconds)
+        // Check if AI has timed out (5 seconds, or twice its clock budget)
         let should_force_move = match &self.ai_state {
-            AiState::Thinking { start_time, .. } => {
-                start_time.elapsed() > Duration::from_secs(5)
-            }
+            AiState::Thinking { handle, timeout } => handle.elapsed() > *timeout,
             _ => false,
         };
 
         // If timed out, transition to Reclaiming (keep receiver!) and play fallback
         if should_force_move {
-            // Take the current state and extract the receiver for background reclamation
+            // Take the current state and keep the handle for background reclamation
             let old_state = std::mem::replace(&mut self.ai_state, AiState::Idle);
-            if let AiState::Thinking { receiver, .. } = old_state {
-                self.ai_state = AiState::Reclaiming { receiver };
+            if let AiState::Thinking { handle, .. } = old_state {
+                // Stop the search so the engine comes back sooner
+                handle.cancel();
+                self.ai_state = AiState::Reclaiming { handle };
             }
             self.message = Some("AI timeout - quick move".to_string());
 
             if let Some(fallback) = self.find_fallback_move() {
                 let fallback = self.validate_pro_rule_ai_move(fallback);
-                self.execute_move(fallback);
+                self.execute_move(fallback, None);
             }
             return;
         }
 
-        let result = match &self.ai_state {
-            AiState::Thinking { receiver, start_time } => {
-                match receiver.try_recv() {
-                    Ok((result, engine)) => Some((result, engine, start_time.elapsed())),
-                    Err(std::sync::mpsc::TryRecvError::Empty) => None,
-                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+        let result = match &mut self.ai_state {
+            AiState::Thinking { handle, .. } => {
+                let (elapsed, cancelled) = (handle.elapsed(), handle.is_cancelled());
+                match handle.try_finish() {
+                    Some(Ok((result, engine))) => Some((result, engine, elapsed, cancelled)),
+                    None => None,
+                    Some(Err(_)) => {
                         self.ai_state = AiState::Idle;
                         self.message = Some("AI error".to_string());
                         return;
@@ -644,18 +1117,33 @@ impl GameState {
             _ => None,
         };
 
-        if let Some((move_result, engine, elapsed)) = result {
+        if let Some((move_result, engine, elapsed, cancelled)) = result {
             self.ai_state = AiState::Idle;
             self.ai_engine = Some(engine); // Return engine for reuse
+            if self.game_over.is_some() {
+                // Game ended while the AI was thinking (resignation, flag fall)
+                return;
+            }
             let idx = if self.current_turn == Stone::Black { 0 } else { 1 };
             self.ai_stats[idx].record(&move_result);
             self.last_ai_result[idx] = Some(move_result.clone());
             self.move_timer.set_ai_time(elapsed);
 
-            if let Some(pos) = move_result.best_move {
+            let resigns = self
+                .ai_engine
+                .as_ref()
+                .is_some_and(|engine| engine.should_resign(&move_result));
+            if resigns {
+                let _ = self.resign(self.current_turn);
+                self.message = Some("AI resigned".to_string());
+            } else if let Some(pos) = move_result.best_move {
                 // Validate AI move against Pro rule
                 let pos = self.validate_pro_rule_ai_move(pos);
-                self.execute_move(pos);
+                self.execute_move(pos, Some(move_result.score));
+            } else if let Some(fallback) = self.find_fallback_move().filter(|_| cancelled) {
+                // Cancelled before the first iteration finished
+                let fallback = self.validate_pro_rule_ai_move(fallback);
+                self.execute_move(fallback, None);
             } else {
                 self.message = Some("AI could not find a move".to_string());
             }
@@ -713,21 +1201,19 @@ impl GameState {
     /// Called every frame — once the thread finishes, we get the engine back
     /// with its full TT cache intact, avoiding expensive re-creation.
     fn try_reclaim_engine(&mut self) {
-        if let AiState::Reclaiming { receiver } = &self.ai_state {
-            match receiver.try_recv() {
-                Ok((_result, engine)) => {
+        if let AiState::Reclaiming { handle } = &mut self.ai_state {
+            match handle.try_finish() {
+                Some(Ok((_result, engine))) => {
                     self.ai_engine = Some(engine);
                     self.ai_state = AiState::Idle;
                 }
-                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                None => {
                     // Thread still running — will try again next frame
                 }
-                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
-                    // Thread panicked or dropped sender — give up gracefully
+                Some(Err(_)) => {
+                    // Thread panicked — give up gracefully
                     if self.ai_engine.is_none() {
-                        self.ai_engine = Some(AIEngine::with_config(
-                            64, self.ai_depth, self.ai_time_limit_ms,
-                        ));
+                        self.ai_engine = Some(self.fresh_engine());
                     }
                     self.ai_state = AiState::Idle;
                 }
@@ -740,35 +1226,25 @@ impl GameState {
         let color = self.current_turn;
 
         // 1. Try to find a winning move
-        for r in 0..19u8 {
-            for c in 0..19u8 {
-                let pos = Pos::new(r, c);
-                if rules::is_valid_move(&self.board, pos, color) {
-                    let mut test = self.board.clone();
-                    test.place_stone(pos, color);
-                    rules::execute_captures(&mut test, pos, color);
-                    if rules::check_winner(&test) == Some(color) {
-                        return Some(pos);
-                    }
-                }
+        for pos in rules::legal_moves_iter(self.rules, &self.board, color) {
+            let mut test = self.board.clone();
+            test.place_stone(pos, color);
+            rules::execute_captures(self.rules, &mut test, pos, color);
+            if rules::check_winner(self.rules, &test) == Some(color) {
+                return Some(pos);
             }
         }
 
         // 2. Try to block opponent's winning move
         let opponent = color.opponent();
-        for r in 0..19u8 {
-            for c in 0..19u8 {
-                let pos = Pos::new(r, c);
-                if rules::is_valid_move(&self.board, pos, opponent) {
-                    let mut test = self.board.clone();
-                    test.place_stone(pos, opponent);
-                    rules::execute_captures(&mut test, pos, opponent);
-                    if rules::check_winner(&test) == Some(opponent) {
-                        // Opponent would win here, so block it
-                        if rules::is_valid_move(&self.board, pos, color) {
-                            return Some(pos);
-                        }
-                    }
+        for pos in rules::legal_moves_iter(self.rules, &self.board, opponent) {
+            let mut test = self.board.clone();
+            test.place_stone(pos, opponent);
+            rules::execute_captures(self.rules, &mut test, pos, opponent);
+            if rules::check_winner(self.rules, &test) == Some(opponent) {
+                // Opponent would win here, so block it
+                if rules::is_valid_move(self.rules, &self.board, pos, color) {
+                    return Some(pos);
                 }
             }
         }
@@ -779,9 +1255,9 @@ impl GameState {
                 for dc in -2i8..=2 {
                     let r = last.row as i8 + dr;
                     let c = last.col as i8 + dc;
-                    if r >= 0 && r < 19 && c >= 0 && c < 19 {
+                    if (0..19).contains(&r) && (0..19).contains(&c) {
                         let pos = Pos::new(r as u8, c as u8);
-                        if rules::is_valid_move(&self.board, pos, color) {
+                        if rules::is_valid_move(self.rules, &self.board, pos, color) {
                             return Some(pos);
                         }
                     }
@@ -793,7 +1269,7 @@ impl GameState {
         for r in 7..12u8 {
             for c in 7..12u8 {
                 let pos = Pos::new(r, c);
-                if rules::is_valid_move(&self.board, pos, color) {
+                if rules::is_valid_move(self.rules, &self.board, pos, color) {
                     return Some(pos);
                 }
             }
@@ -805,27 +1281,141 @@ impl GameState {
     /// Get AI thinking elapsed time
     pub fn ai_thinking_elapsed(&self) -> Option<Duration> {
         match &self.ai_state {
-            AiState::Thinking { start_time, .. } => Some(start_time.elapsed()),
+            AiState::Thinking { handle, .. } => Some(handle.elapsed()),
+            AiState::Idle | AiState::Reclaiming { .. } => None,
+        }
+    }
+
+    /// Depth completed and nodes searched so far by the running AI search
+    pub fn ai_progress(&self) -> Option<(i8, u64)> {
+        match &self.ai_state {
+            AiState::Thinking { handle, .. } => Some((handle.depth(), handle.nodes())),
+            AiState::Idle | AiState::Reclaiming { .. } => None,
+        }
+    }
+
+    /// Search speed of the running AI search so far, in kN/s
+    pub fn ai_nps(&self) -> Option<u64> {
+        match &self.ai_state {
+            AiState::Thinking { handle, .. } => {
+                let ms = handle.elapsed().as_millis() as u64;
+                Some((handle.nodes() * 1000).checked_div(ms).unwrap_or(0) / 1000)
+            }
             AiState::Idle | AiState::Reclaiming { .. } => None,
         }
     }
 
-    /// Request move suggestion for PvP mode
+    /// Score and expected line of the running AI search's last completed
+    /// depth
+    pub fn ai_search_info(&self) -> Option<SearchInfo> {
+        match &self.ai_state {
+            AiState::Thinking { handle, .. } => handle.info(),
+            AiState::Idle | AiState::Reclaiming { .. } => None,
+        }
+    }
+
+    /// Stop the running AI search; the AI plays the best move found so far
+    pub fn cancel_ai_thinking(&mut self) {
+        if let AiState::Thinking { handle, .. } = &self.ai_state {
+            handle.cancel();
+        }
+    }
+
+    /// Request move suggestion for PvP mode. The search runs in the
+    /// background; [`GameState::update`] picks up the result.
     pub fn request_suggestion(&mut self) {
-        if self.game_over.is_some() || self.is_ai_thinking() {
+        if self.game_over.is_some() || self.is_ai_thinking() || self.suggestion_analysis.is_some() {
             return;
         }
 
         let board = self.board.clone();
-        let color = self.current_turn;
+        let (color, rules) = (self.current_turn, self.rules);
+        let (tx, rx) = channel();
+        thread::spawn(move || {
+            // Run quick suggestion (lower depth)
+            let mut engine = AIEngine::with_config(16, 4, 200);
+            engine.set_rules(rules);
+            let _ = tx.send(engine.get_hint(&board, color));
+        });
+        self.suggestion_analysis = Some(rx);
+    }
 
-        // Run quick suggestion (lower depth)
-        let mut engine = AIEngine::with_config(16, 4, 200);
-        let result = engine.get_move_with_stats(&board, color);
+    /// Collect a finished suggestion search.
+    pub fn update_suggestion(&mut self) {
+        let Some(receiver) = &self.suggestion_analysis else {
+            return;
+        };
+        let hint = match receiver.try_recv() {
+            Ok(hint) => hint,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => None,
+        };
+        self.suggestion_analysis = None;
+        let Some(hint) = hint else {
+            return;
+        };
 
-        self.suggested_move = result.best_move;
-        let idx = if color == Stone::Black { 0 } else { 1 };
-        self.last_ai_result[idx] = Some(result);
+        log_info!("Hint: {}", hint);
+        let idx = if self.current_turn == Stone::Black { 0 } else { 1 };
+        self.last_ai_result[idx] = Some(hint.result.clone());
+        self.suggestion = Some(hint);
+    }
+
+    /// Whether a suggestion search is running
+    pub fn is_suggesting(&self) -> bool {
+        self.suggestion_analysis.is_some()
+    }
+
+    /// Drop the suggestion for a position that is no longer on the board;
+    /// a running search finishes in the background and is discarded.
+    fn clear_suggestion(&mut self) {
+        self.suggestion = None;
+        self.suggestion_analysis = None;
+    }
+
+    /// Keep move hints current: collect a finished analysis, or start one
+    /// for the current position when hints are on and a human is to move.
+    pub fn update_hints(&mut self) {
+        if let Some(receiver) = &self.hint_analysis {
+            match receiver.try_recv() {
+                Ok(lines) => {
+                    self.hints = Some(lines);
+                    self.hint_analysis = None;
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => {
+                    self.hints = Some(Vec::new());
+                    self.hint_analysis = None;
+                }
+            }
+            return;
+        }
+
+        if !self.show_hints
+            || self.hints.is_some()
+            || !self.is_human_turn()
+            || self.game_over.is_some()
+            || self.swap_pending
+        {
+            return;
+        }
+
+        let board = self.board.clone();
+        let (color, rules) = (self.current_turn, self.rules);
+        let (tx, rx) = channel();
+        thread::spawn(move || {
+            let mut searcher = Searcher::with_threads(16, 1);
+            searcher.set_rules(rules);
+            let _ = tx.send(searcher.search_multipv(&board, color, HINT_DEPTH, HINT_NODES, HINT_LINES));
+        });
+        self.hint_analysis = Some(rx);
+    }
+
+    /// Drop hints for a position that is no longer on the board; a running
+    /// analysis finishes in the background and is discarded.
+    fn clear_hints(&mut self) {
+        self.hints = None;
+        self.hint_analysis = None;
     }
 
     /// Undo last move
@@ -851,23 +1441,28 @@ impl GameState {
         // Truncate and replay
         let moves: Vec<_> = self.move_history[..keep].to_vec();
 
-        self.board = Board::new();
-        self.current_turn = Stone::Black;
+        (self.board, self.current_turn) = self.start_position();
         self.game_over = None;
+        self.draw_offer = None;
         self.last_move = None;
-        self.suggested_move = None;
+        self.clear_suggestion();
+        self.clear_hints();
         self.capture_animation = None;
         self.move_history.clear();
+        self.move_scores.truncate(keep);
 
         for (pos, color) in moves {
             self.board.place_stone(pos, color);
-            rules::execute_captures(&mut self.board, pos, color);
+            rules::execute_captures(self.rules, &mut self.board, pos, color);
             self.move_history.push((pos, color));
             self.last_move = Some(pos);
             self.current_turn = color.opponent();
         }
 
         self.move_timer.start();
+        if let Some(clock) = &mut self.clock {
+            clock.start(self.current_turn);
+        }
     }
 
     /// Redo last undone move(s)
@@ -884,18 +1479,165 @@ impl GameState {
                 if self.game_over.is_some() {
                     break;
                 }
-                self.execute_move(pos);
+                self.execute_move(pos, None);
+            }
+        }
+    }
+
+    /// Latest engine score from Black's side: the running hint analysis
+    /// if it has finished, otherwise the last scored move.
+    pub fn current_score(&self) -> Option<i32> {
+        let hint = self.hints.as_ref().and_then(|hints| hints.first()).map(|line| {
+            if self.current_turn == Stone::Black { line.score } else { -line.score }
+        });
+        hint.or_else(|| self.move_scores.iter().rev().find_map(|&s| s))
+    }
+
+    /// Index of the move that swung Black's win probability the most
+    /// between consecutive scored moves.
+    pub fn turning_point(&self) -> Option<usize> {
+        Swing::find(&self.move_scores).map(|swing| swing.index)
+    }
+
+    /// Summary of the finished game: captures, the biggest swing, the AI's
+    /// search depth and time, and accuracy once the moves are analyzed.
+    /// `None` while the game goes on and for games from a set-up position.
+    pub fn report(&self) -> Option<GameReport> {
+        if self.game_over.is_none() || self.start.is_some() {
+            return None;
+        }
+        let mut report = GameReport::new(self.rules, &self.record().moves).ok()?.with_scores(&self.move_scores);
+        if let Some(annotation) = &self.annotation {
+            report = report.with_annotation(annotation);
+        }
+        for (stats, color) in self.ai_stats.iter().zip([Stone::Black, Stone::White]) {
+            if !stats.results.is_empty() {
+                report = report.with_engine_moves(color, &stats.results);
+            }
+        }
+        Some(report)
+    }
+
+    /// The latest event in one sentence, for screen readers: the last move,
+    /// captured pairs, and who plays next or how the game ended
+    pub fn announcement(&self) -> String {
+        let name = |color| if color == Stone::Black { "Black" } else { "White" };
+        let mut text = match self.move_history.last() {
+            Some(&(pos, color)) => format!("{} {}.", name(color), pos_to_notation(pos)),
+            None => "New game.".to_string(),
+        };
+        if self.board.captures(Stone::Black) + self.board.captures(Stone::White) > 0 {
+            text += &format!(
+                " Captured pairs: Black {}, White {}.",
+                self.board.captures(Stone::Black), self.board.captures(Stone::White)
+            );
+        }
+        match self.game_over {
+            Some(result) if result.is_draw() => text += " Draw.",
+            Some(result) => {
+                let how = match result.win_type {
+                    WinType::FiveInRow => "five in a row",
+                    WinType::Capture => "captures",
+                    WinType::Resignation => "resignation",
+                    WinType::Timeout => "timeout",
+                    WinType::DrawAgreement => "agreement",
+                };
+                text += &format!(" {} wins by {}.", name(result.winner), how);
+            }
+            None if self.swap_pending => text += " Swap decision pending.",
+            None => text += &format!(" {} to move.", name(self.current_turn)),
+        }
+        text
+    }
+
+    /// Annotate the finished game's moves in the background
+    pub fn request_annotation(&mut self) {
+        if self.game_over.is_none() || self.annotation.is_some() || self.annotation_analysis.is_some() {
+            return;
+        }
+        if self.start.is_some() {
+            self.message = Some("Move analysis needs a game played from the empty board".to_string());
+            return;
+        }
+        let (rules, moves) = (self.rules, self.record().moves);
+        let (tx, rx) = channel();
+        thread::spawn(move || {
+            let _ = tx.send(annotate_game(rules, &moves));
+        });
+        self.annotation_analysis = Some(rx);
+    }
+
+    /// Whether the game is being annotated
+    pub fn is_annotating(&self) -> bool {
+        self.annotation_analysis.is_some()
+    }
+
+    /// Collect a finished annotation; drop one that no longer matches the
+    /// moves (undo)
+    pub fn update_annotation(&mut self) {
+        if let Some(receiver) = &self.annotation_analysis {
+            match receiver.try_recv() {
+                Ok(Ok(annotation)) => {
+                    self.annotation = Some(annotation);
+                    self.annotation_analysis = None;
+                }
+                Ok(Err(e)) => {
+                    self.message = Some(format!("Analysis failed: {}", e));
+                    self.annotation_analysis = None;
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => self.annotation_analysis = None,
             }
         }
+        if self.annotation.as_ref().is_some_and(|a| a.moves.len() != self.move_history.len()) {
+            self.annotation = None;
+        }
+    }
+
+    /// Annotation of the move that led to the reviewed position
+    pub fn reviewed_annotation(&self) -> Option<&MoveAnnotation> {
+        let index = self.review_index.unwrap_or(self.move_history.len());
+        self.annotation.as_ref()?.moves.get(index.checked_sub(1)?)
+    }
+
+    /// The moves played so far, for saving (without the set-up position),
+    /// and a resignation or agreed draw that ended the game
+    pub fn record(&self) -> GameRecord {
+        let result = self.game_over.as_ref().and_then(|result| match result.win_type {
+            WinType::Resignation => Some(GameOutcome::Win { winner: result.winner, reason: WinReason::Resignation }),
+            WinType::DrawAgreement => Some(GameOutcome::AgreedDraw),
+            _ => None,
+        });
+        GameRecord { moves: self.move_history.iter().map(|&(pos, _)| pos).collect(), result }
+    }
+
+    /// The finished game for the games archive, once per game: players
+    /// are [`HUMAN_PLAYER`] and [`ENGINE_PLAYER`] by mode. Games from a
+    /// set-up position or by other rules than the archive's Ninuki-renju
+    /// aren't archived.
+    pub fn take_archive_entry(&mut self) -> Option<ArchivedGame> {
+        let result = self.game_over?;
+        if self.archived || self.start.is_some() || self.rules != rules::RuleSet::NINUKI {
+            return None;
+        }
+        self.archived = true;
+        let player = |color: Stone| match self.mode {
+            GameMode::PvE { human_color } if human_color != color => ENGINE_PLAYER,
+            GameMode::PvE { .. } | GameMode::PvP { .. } => HUMAN_PLAYER,
+            GameMode::AiVsAi => ENGINE_PLAYER,
+        };
+        let time_control = self.time_control.map_or_else(|| "-".to_string(), |control| control.label());
+        let score = if result.is_draw() { GameScore::Draw } else { GameScore::from_winner(result.winner) };
+        Some(ArchivedGame::new(player(Stone::Black), player(Stone::White), score, &time_control, self.record().moves))
     }
 
     /// Build a board from a subset of moves (for review mode)
     pub fn build_review_board(&self, up_to: usize) -> (Board, Option<Pos>) {
-        let mut board = Board::new();
+        let (mut board, _) = self.start_position();
         let mut last = None;
         for &(pos, color) in self.move_history.iter().take(up_to) {
             board.place_stone(pos, color);
-            rules::execute_captures(&mut board, pos, color);
+            rules::execute_captures(self.rules, &mut board, pos, color);
             last = Some(pos);
         }
         (board, last)
@@ -930,9 +1672,18 @@ impl GameState {
     }
 }
 
+impl Drop for GameState {
+    fn drop(&mut self) {
+        if let (Some(pool), Some(engine)) = (&self.engine_pool, self.ai_engine.take()) {
+            pool.check_in(engine);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rules::RuleSet;
 
     /// Reproduce the exact game position where the UI incorrectly declared a win.
     /// F13-G12-H11-J10-K9 diagonal five is breakable because White can capture
@@ -1056,11 +1807,11 @@ mod tests {
         // Pattern: K10(W) - J10(B) - H10(B) - G10(W) = X-O-O-X
         let k10 = Pos::new(9, 9);
         state.board.place_stone(k10, Stone::White);
-        rules::execute_captures(&mut state.board, k10, Stone::White);
+        rules::execute_captures(RuleSet::NINUKI, &mut state.board, k10, Stone::White);
 
         // Black's five should be broken (J10 removed from the diagonal)
         assert!(
-            rules::find_five_positions(&state.board, Stone::Black).is_none(),
+            rules::find_five_positions(RuleSet::NINUKI, &state.board, Stone::Black).is_none(),
             "Black's five should be broken after capture"
         );
 
@@ -1068,4 +1819,359 @@ mod tests {
         let result = state.check_win(k10, Stone::White);
         assert!(result.is_none(), "Game should continue after five is broken by capture");
     }
+
+    #[test]
+    fn test_time_control_increment_and_flag_fall() {
+        let mut state = GameState::new(GameMode::PvP { show_suggestions: false });
+        state.set_time_control(Some(TimeControl::Fischer {
+            initial: Duration::from_secs(60),
+            increment: Duration::from_secs(2),
+        }));
+        state.try_place_stone(Pos::new(9, 9)).unwrap();
+        let clock = state.clock.as_ref().unwrap();
+        assert!(clock.remaining(Stone::Black) > Duration::from_secs(61), "increment should be added");
+
+        state.set_time_control(Some(TimeControl::Fischer {
+            initial: Duration::from_millis(1),
+            increment: Duration::ZERO,
+        }));
+        thread::sleep(Duration::from_millis(5));
+        state.check_clock();
+        let result = state.game_over.expect("White should lose on time");
+        assert_eq!(result.win_type, WinType::Timeout);
+        assert_eq!(result.winner, Stone::Black);
+
+        // Time control survives a new game
+        state.reset();
+        assert!(state.clock.is_some());
+        assert!(state.game_over.is_none());
+    }
+
+    #[test]
+    fn test_resign_ends_game() {
+        let mut state = GameState::new(GameMode::PvP { show_suggestions: false });
+        state.try_place_stone(Pos::new(9, 9)).unwrap();
+
+        state.resign(Stone::White).unwrap();
+        let result = state.game_over.expect("Resignation should end the game");
+        assert_eq!(result.winner, Stone::Black);
+        assert_eq!(result.win_type, WinType::Resignation);
+        assert!(state.try_place_stone(Pos::new(9, 10)).is_err());
+        assert!(state.resign(Stone::Black).is_err());
+
+        // Undo takes back the move and the resignation with it
+        state.undo();
+        assert!(state.game_over.is_none());
+    }
+
+    #[test]
+    fn test_finished_games_are_archived_once() {
+        let mut state = GameState::new(GameMode::PvE { human_color: Stone::Black });
+        let control = TimeControl::Fischer { initial: Duration::from_secs(300), increment: Duration::from_secs(3) };
+        state.set_time_control(Some(control));
+        state.try_place_stone(Pos::new(9, 9)).unwrap();
+        assert!(state.take_archive_entry().is_none(), "The game isn't over");
+
+        state.resign(Stone::White).unwrap();
+        let entry = state.take_archive_entry().expect("Finished games are archived");
+        assert_eq!((entry.black.as_str(), entry.white.as_str()), (HUMAN_PLAYER, ENGINE_PLAYER));
+        assert_eq!(entry.result, GameScore::BlackWins);
+        assert_eq!(entry.time_control, "5+3");
+        assert_eq!(entry.moves, vec![Pos::new(9, 9)]);
+        assert!(state.take_archive_entry().is_none(), "Only once");
+
+        state.reset();
+        state.resign(Stone::Black).unwrap();
+        assert_eq!(state.take_archive_entry().map(|entry| entry.result), Some(GameScore::WhiteWins));
+    }
+
+    #[test]
+    fn test_pvp_draw_offer_lifecycle() {
+        let mut state = GameState::new(GameMode::PvP { show_suggestions: false });
+        state.offer_draw(Stone::Black).unwrap();
+        assert_eq!(state.draw_offer, Some(Stone::Black));
+        assert!(state.offer_draw(Stone::Black).is_err());
+
+        // Black's own move keeps the offer open; White moving declines it
+        state.try_place_stone(Pos::new(9, 9)).unwrap();
+        assert_eq!(state.draw_offer, Some(Stone::Black));
+        state.try_place_stone(Pos::new(9, 10)).unwrap();
+        assert_eq!(state.draw_offer, None);
+        assert!(state.accept_draw().is_err());
+
+        state.offer_draw(Stone::Black).unwrap();
+        state.accept_draw().unwrap();
+        let result = state.game_over.expect("Accepted draw should end the game");
+        assert!(result.is_draw());
+        assert_eq!(result.winner, Stone::Empty);
+    }
+
+    #[test]
+    fn test_ai_answers_draw_offer() {
+        // Level position: the AI takes the draw
+        let mut state = GameState::new(GameMode::PvE { human_color: Stone::Black });
+        state.offer_draw(Stone::Black).unwrap();
+        assert!(state.game_over.is_some_and(|r| r.is_draw()));
+
+        // The AI (White) has a four and plays on
+        let mut state = GameState::new(GameMode::PvE { human_color: Stone::Black });
+        for c in 5..9 {
+            state.board.place_stone(Pos::new(9, c), Stone::White);
+        }
+        state.offer_draw(Stone::Black).unwrap();
+        assert!(state.game_over.is_none(), "Winning AI should decline the draw");
+        assert_eq!(state.draw_offer, None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_game_state_serde_round_trip() {
+        let mut state = GameState::new(GameMode::PvP { show_suggestions: false });
+        state.try_place_stone(Pos::new(9, 9)).unwrap();
+        state.try_place_stone(Pos::new(9, 10)).unwrap();
+
+        let json = serde_json::to_string(&state).unwrap();
+        let restored: GameState = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.move_history, state.move_history);
+        assert_eq!(restored.current_turn, state.current_turn);
+        assert_eq!(restored.mode, state.mode);
+        assert_eq!(restored.board.get(Pos::new(9, 10)), Stone::White);
+        assert!(matches!(restored.ai_state, AiState::Idle));
+        assert!(restored.ai_engine.is_some(), "Deserialized game needs a usable engine");
+    }
+
+    #[test]
+    fn test_move_scores_track_history() {
+        let mut state = GameState::new(GameMode::PvP { show_suggestions: false });
+        state.execute_move(Pos::new(9, 9), Some(100));
+        state.execute_move(Pos::new(9, 10), Some(-200));
+        state.execute_move(Pos::new(10, 10), None);
+        state.execute_move(Pos::new(8, 8), Some(-40_000));
+
+        // Stored from Black's side
+        assert_eq!(state.move_scores, vec![Some(100), Some(200), None, Some(40_000)]);
+        assert_eq!(state.current_score(), Some(40_000));
+        assert_eq!(state.turning_point(), Some(3));
+
+        state.undo();
+        assert_eq!(state.move_scores.len(), state.move_history.len());
+        assert_eq!(state.current_score(), Some(200));
+    }
+
+    #[test]
+    fn test_setup_position_survives_undo() {
+        let stones = [(Pos::new(9, 9), Stone::Black), (Pos::new(3, 3), Stone::Black)];
+        let game = Game::from_setup(rules::RuleSet::NINUKI, &stones, (1, 0), Stone::White).unwrap();
+        let mut state = GameState::new(GameMode::PvP { show_suggestions: false });
+        state.start_from_setup(&game);
+        assert_eq!(state.current_turn, Stone::White);
+
+        state.execute_move(Pos::new(10, 10), None);
+        assert_eq!(state.board.get(Pos::new(10, 10)), Stone::White);
+        assert_eq!(state.build_review_board(0).0.stone_count(), 2);
+
+        state.undo();
+        assert_eq!(state.current_turn, Stone::White);
+        assert_eq!(state.board.stone_count(), 2);
+        assert_eq!(state.board.captures(Stone::Black), 1);
+
+        state.reset();
+        assert!(state.start.is_none() && state.board.stone_count() == 0);
+    }
+
+    #[test]
+    fn test_record_replays_to_the_same_board() {
+        let mut state = GameState::new(GameMode::PvP { show_suggestions: false });
+        for pos in [Pos::new(9, 8), Pos::new(9, 9), Pos::new(0, 0), Pos::new(9, 10), Pos::new(9, 11)] {
+            state.execute_move(pos, None);
+        }
+        let record = GameRecord::parse(&state.record().to_sgf()).unwrap();
+        let last = record.boards(RuleSet::NINUKI).unwrap().pop().unwrap();
+        assert_eq!(last.captures(Stone::Black), 1);
+        assert_eq!(last.bitboards(), state.board.bitboards());
+
+        state.resign(Stone::White).unwrap();
+        let resigned = Some(GameOutcome::Win { winner: Stone::Black, reason: WinReason::Resignation });
+        assert_eq!(GameRecord::parse(&state.record().to_sgf()).unwrap().result, resigned);
+    }
+
+    #[test]
+    fn test_suggestion_explains_the_move() {
+        let mut state = GameState::new(GameMode::PvP { show_suggestions: false });
+        // Black F10-J10 against White E10: White must block K10
+        for pos in [Pos::new(9, 5), Pos::new(9, 4), Pos::new(9, 6), Pos::new(0, 0), Pos::new(9, 7), Pos::new(0, 2), Pos::new(9, 8)] {
+            state.try_place_stone(pos).unwrap();
+        }
+        state.request_suggestion();
+        assert!(state.suggestion.is_none() && state.is_suggesting(), "The search runs in the background");
+        while state.is_suggesting() {
+            state.update_suggestion();
+            thread::sleep(Duration::from_millis(5));
+        }
+        let hint = state.suggestion.clone().unwrap();
+        assert_eq!(hint.mov, Pos::new(9, 9));
+        assert_eq!(hint.reason, crate::HintReason::BlocksFive);
+        assert!(state.last_ai_result[1].is_some());
+
+        state.try_place_stone(hint.mov).unwrap();
+        assert!(state.suggestion.is_none());
+    }
+
+    #[test]
+    fn test_cancelled_ai_search_still_moves() {
+        let mut state = GameState::new(GameMode::AiVsAi);
+        state.set_ai_settings(AiSettings { max_depth: 30, time_limit_ms: 10_000, threads: 2, hash_mb: 16 });
+        state.execute_move(Pos::new(9, 9), None);
+        state.execute_move(Pos::new(10, 10), None);
+
+        state.start_ai_thinking();
+        assert!(state.ai_progress().is_some());
+        let start = Instant::now();
+        while state.ai_search_info().is_none() {
+            assert!(start.elapsed() < Duration::from_secs(5), "no search info reported");
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert!(state.ai_nps().is_some());
+        state.cancel_ai_thinking();
+        let start = Instant::now();
+        while state.is_ai_thinking() {
+            assert!(start.elapsed() < Duration::from_secs(5), "cancelled search did not stop");
+            thread::sleep(Duration::from_millis(10));
+            state.check_ai_result();
+        }
+        assert_eq!(state.move_history.len(), 3);
+        assert!(state.ai_progress().is_none());
+        assert!(state.ai_search_info().is_none());
+        assert!(state.ai_nps().is_none());
+    }
+
+    #[test]
+    fn test_report_of_finished_game() {
+        let mut state = GameState::new(GameMode::PvP { show_suggestions: false });
+        state.execute_move(Pos::new(9, 9), Some(0));
+        assert!(state.report().is_none(), "No report before the game ends");
+        state.execute_move(Pos::new(9, 10), Some(-300));
+        state.resign(Stone::White).unwrap();
+
+        let report = state.report().unwrap();
+        assert_eq!(report.moves.len(), 2);
+        assert_eq!(report.biggest_swing.map(|swing| swing.index), state.turning_point());
+        assert_eq!(report.player(Stone::Black).accuracy, None);
+    }
+
+    #[test]
+    fn test_pooled_games_play_side_by_side() {
+        let pool = EnginePool::with_threads(1, 1, 4, 100, 1);
+        let mut games: Vec<GameState> = [2, 4]
+            .into_iter()
+            .map(|max_depth| {
+                let mut game = GameState::with_engine_pool(GameMode::AiVsAi, OpeningRule::Standard, &pool);
+                game.set_ai_settings(AiSettings { max_depth, time_limit_ms: 100, threads: 1, hash_mb: 1 });
+                game
+            })
+            .collect();
+        assert_eq!(pool.idle(), 0);
+
+        let start = Instant::now();
+        while games.iter().any(|game| game.move_history.len() < 3) {
+            assert!(start.elapsed() < Duration::from_secs(20), "Both games keep moving");
+            for game in &mut games {
+                game.update(false);
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        // Stop both and let their searches hand the engines back
+        for game in &mut games {
+            game.resign(game.current_turn).unwrap();
+        }
+        while games.iter().any(GameState::is_ai_thinking) {
+            assert!(start.elapsed() < Duration::from_secs(20));
+            for game in &mut games {
+                game.update(false);
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        drop(games);
+        assert_eq!(pool.idle(), 1, "Closed games return their engines (up to the pool size)");
+    }
+
+    #[test]
+    fn test_announcement_follows_the_game() {
+        let mut state = GameState::new(GameMode::PvP { show_suggestions: false });
+        assert_eq!(state.announcement(), "New game. Black to move.");
+        let center = Pos::new(9, 9);
+        state.execute_move(center, None);
+        assert_eq!(state.announcement(), format!("Black {}. White to move.", pos_to_notation(center)));
+
+        state.board.set_captures(RuleSet::NINUKI, Stone::White, 1).unwrap();
+        state.resign(Stone::Black).unwrap();
+        assert_eq!(
+            state.announcement(),
+            format!("Black {}. Captured pairs: Black 0, White 1. White wins by resignation.", pos_to_notation(center))
+        );
+    }
+
+    #[test]
+    fn test_annotation_of_finished_game() {
+        let mut state = GameState::new(GameMode::PvP { show_suggestions: false });
+        state.execute_move(Pos::new(9, 9), None);
+        state.request_annotation();
+        assert!(!state.is_annotating(), "Only finished games are annotated");
+
+        state.resign(Stone::White).unwrap();
+        state.request_annotation();
+        let start = Instant::now();
+        while state.is_annotating() {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            thread::sleep(Duration::from_millis(10));
+            state.update_annotation();
+        }
+        assert_eq!(state.annotation.as_ref().map(|a| a.moves.len()), Some(1));
+        assert_eq!(state.reviewed_annotation().map(|a| a.mov), Some(Pos::new(9, 9)));
+        state.review_index = Some(0);
+        assert!(state.reviewed_annotation().is_none());
+
+        state.reset();
+        assert!(state.annotation.is_none());
+    }
+
+    #[test]
+    fn test_ai_settings_apply_to_engine() {
+        let mut state = GameState::new(GameMode::AiVsAi);
+        let settings = AiSettings { max_depth: 6, time_limit_ms: 100, threads: 2, hash_mb: 16 };
+        state.set_ai_settings(settings);
+
+        let mut engine = state.ai_engine.take().unwrap();
+        state.configure_engine(&mut engine);
+        assert_eq!(engine.max_depth(), 6);
+        assert_eq!(engine.threads(), 2);
+        assert_eq!(engine.tt_stats().size, AIEngine::with_config(16, 6, 100).tt_stats().size);
+        assert_eq!(state.engine_settings, settings);
+    }
+
+    #[test]
+    fn test_hints_follow_the_position() {
+        let mut state = GameState::new(GameMode::PvP { show_suggestions: false });
+        for col in 5..9 {
+            state.try_place_stone(Pos::new(9, col)).unwrap();
+            state.try_place_stone(Pos::new(3, col * 2)).unwrap();
+        }
+
+        state.update_hints();
+        assert!(state.hint_analysis.is_none(), "Hints are off by default");
+
+        state.show_hints = true;
+        while state.hints.is_none() {
+            state.update_hints();
+            thread::sleep(Duration::from_millis(5));
+        }
+        let hints = state.hints.clone().unwrap();
+        assert_eq!(hints.len(), HINT_LINES);
+        assert!(hints[0].mov == Pos::new(9, 4) || hints[0].mov == Pos::new(9, 9));
+
+        state.try_place_stone(hints[0].mov).unwrap();
+        assert!(state.hints.is_none() && state.hint_analysis.is_none());
+    }
 }
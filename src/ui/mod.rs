@@ -5,7 +5,10 @@
 mod app;
 mod board_view;
 mod game_state;
+mod replay;
+mod setup;
+mod sound;
 mod theme;
 
 pub use app::GomokuApp;
-pub use game_state::{GameMode, GameState, OpeningRule};
+pub use game_state::{AiSettings, GameMode, GameState, OpeningRule};
@@ -0,0 +1,168 @@
+//! Replay of a loaded game record
+//!
+//! A [`Replay`] steps through a [`GameRecord`] by hand or on a timer. A
+//! background thread evaluates every position of the record once, so each
+//! move's evaluation shows up as the replay reaches it.
+
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::eval::PatternScore;
+use crate::notation::notation;
+use crate::record::GameRecord;
+use crate::rules::{check_winner, RuleSet};
+use crate::search::Searcher;
+use crate::{Board, Pos, Stone};
+
+/// Search budget per replayed position
+const REPLAY_EVAL_DEPTH: i8 = 10;
+const REPLAY_EVAL_NODES: u64 = 50_000;
+
+/// Autoplay speed range (seconds per move)
+pub const MIN_REPLAY_INTERVAL: f32 = 0.2;
+pub const MAX_REPLAY_INTERVAL: f32 = 5.0;
+
+/// A game record being replayed
+pub struct Replay {
+    pub record: GameRecord,
+    /// Rules the record is replayed by
+    rules: RuleSet,
+    /// Position before the first move, then after each move
+    boards: Vec<Board>,
+    /// Moves shown on the board (0 = empty board)
+    index: usize,
+    /// Stepping forward on a timer
+    pub autoplay: bool,
+    /// Seconds per move while autoplaying
+    pub interval: f32,
+    last_step: Instant,
+    /// Evaluation of each position from Black's side, once analyzed
+    evals: Vec<Option<i32>>,
+    analysis: Option<Receiver<(usize, i32)>>,
+}
+
+impl Replay {
+    /// Start replaying `record`, played by `rules`, from the empty board,
+    /// with autoplay on.
+    ///
+    /// Fails if `rules` is invalid or the record contains an illegal move.
+    pub fn new(rules: RuleSet, record: GameRecord) -> Result<Self, String> {
+        let mut searcher = Searcher::with_threads(16, 1);
+        searcher.set_rules(rules);
+        let boards = record.boards_with_notation(rules, notation())?;
+        let evals = vec![None; boards.len()];
+
+        let positions = boards.clone();
+        let (tx, rx) = channel();
+        thread::spawn(move || {
+            for (i, board) in positions.iter().enumerate() {
+                // Stops once the replay is closed and the receiver dropped
+                if tx.send((i, evaluate_position(&mut searcher, board, GameRecord::color_of(i)))).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            record,
+            rules,
+            boards,
+            index: 0,
+            autoplay: true,
+            interval: 1.0,
+            last_step: Instant::now(),
+            evals,
+            analysis: Some(rx),
+        })
+    }
+
+    /// Rules the record is replayed by
+    pub fn rules(&self) -> RuleSet {
+        self.rules
+    }
+
+    /// Number of moves in the record
+    pub fn len(&self) -> usize {
+        self.record.moves.len()
+    }
+
+    /// Moves shown on the board
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The position after [`Self::index`] moves
+    pub fn board(&self) -> &Board {
+        &self.boards[self.index]
+    }
+
+    /// The move that led to the shown position
+    pub fn last_move(&self) -> Option<Pos> {
+        self.index.checked_sub(1).map(|i| self.record.moves[i])
+    }
+
+    /// Side to move in the shown position
+    pub fn side_to_move(&self) -> Stone {
+        GameRecord::color_of(self.index)
+    }
+
+    /// Evaluation of the shown position from Black's side, once analyzed
+    pub fn eval(&self) -> Option<i32> {
+        self.evals[self.index]
+    }
+
+    /// Evaluation before the last shown move, to judge that move
+    pub fn previous_eval(&self) -> Option<i32> {
+        self.index.checked_sub(1).and_then(|i| self.evals[i])
+    }
+
+    /// Show the position after `index` moves (clamped to the record)
+    pub fn seek(&mut self, index: usize) {
+        self.index = index.min(self.len());
+        self.last_step = Instant::now();
+    }
+
+    /// Step `delta` moves forward or back
+    pub fn step(&mut self, delta: isize) {
+        self.seek(self.index.saturating_add_signed(delta));
+    }
+
+    /// Collect finished evaluations and advance autoplay. Returns whether
+    /// the replay still needs repainting (autoplay or analysis running).
+    pub fn update(&mut self) -> bool {
+        if let Some(receiver) = &self.analysis {
+            loop {
+                match receiver.try_recv() {
+                    Ok((i, score)) => self.evals[i] = Some(score),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        self.analysis = None;
+                        break;
+                    }
+                }
+            }
+        }
+
+        if self.autoplay {
+            if self.index >= self.len() {
+                self.autoplay = false;
+            } else if self.last_step.elapsed() >= Duration::from_secs_f32(self.interval) {
+                self.step(1);
+            }
+        }
+
+        self.autoplay || self.analysis.is_some()
+    }
+}
+
+/// Score of `board` with `to_move` to play, from Black's side, by the
+/// searcher's rules
+fn evaluate_position(searcher: &mut Searcher, board: &Board, to_move: Stone) -> i32 {
+    let score = match check_winner(searcher.rules(), board) {
+        Some(winner) if winner == to_move => PatternScore::FIVE,
+        Some(_) => -PatternScore::FIVE,
+        None => searcher.search_nodes(board, to_move, REPLAY_EVAL_DEPTH, REPLAY_EVAL_NODES).score,
+    };
+    if to_move == Stone::Black { score } else { -score }
+}
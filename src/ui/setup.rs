@@ -0,0 +1,157 @@
+//! Position editor
+//!
+//! A [`SetupEditor`] holds a position being built by hand: stones are
+//! placed and removed freely, without turns or rules, and the side to move
+//! and capture counts are set directly. [`SetupEditor::to_game`] checks it
+//! with [`Game::from_setup`], by the rules of the game it was opened from,
+//! before play starts from it.
+//! [`SetupEditor::warnings`] lists what no game could produce; handicap
+//! positions trip those checks by design, so they don't block play.
+//! Positions travel as text in the puzzle format
+//! ([`format_position`]), for the clipboard, the CLI and bug reports.
+
+use crate::game::Game;
+use crate::rules::{validate_position, PositionError, RuleSet};
+use crate::testsuite::{format_position, parse_position};
+use crate::{Board, Pos, Stone};
+
+/// A position being edited
+pub struct SetupEditor {
+    /// Rules the position is checked and played by
+    pub rules: RuleSet,
+    pub board: Board,
+    /// Stone placed by a click; `Stone::Empty` erases
+    pub brush: Stone,
+    pub to_move: Stone,
+    /// Position text typed or pasted in, loaded by [`SetupEditor::load_text`]
+    pub text: String,
+    /// Why the last attempt to start from the position failed
+    pub error: Option<String>,
+}
+
+impl SetupEditor {
+    /// Edit a copy of `board` (captures included) with `to_move` to play,
+    /// under `rules`
+    pub fn new(rules: RuleSet, board: &Board, to_move: Stone) -> Self {
+        Self { rules, board: board.clone(), brush: Stone::Black, to_move, text: String::new(), error: None }
+    }
+
+    /// The edited position in the puzzle format
+    pub fn to_text(&self) -> String {
+        format_position(&self.board, self.to_move)
+    }
+
+    /// Replace the edited position with one in the puzzle format. A
+    /// malformed one leaves the position alone and is reported in `error`.
+    pub fn load_text(&mut self, text: &str) -> Result<(), String> {
+        match parse_position(text.trim()) {
+            Ok((board, to_move)) => {
+                self.board = board;
+                self.to_move = to_move;
+                self.error = None;
+                Ok(())
+            }
+            Err(e) => {
+                self.error = Some(format!("Bad position: {}", e));
+                Err(e)
+            }
+        }
+    }
+
+    /// Apply the brush at `pos`. Clicking a stone of the brush color
+    /// removes it, so one brush both places and takes back.
+    pub fn click(&mut self, pos: Pos) {
+        let current = self.board.get(pos);
+        if current != Stone::Empty {
+            self.board.remove_stone(pos);
+        }
+        if self.brush != Stone::Empty && current != self.brush {
+            self.board.place_stone(pos, self.brush);
+        }
+        self.error = None;
+    }
+
+    /// Remove every stone and capture
+    pub fn clear(&mut self) {
+        self.board = Board::new();
+        self.error = None;
+    }
+
+    /// Reasons no game reaches the edited position ([`validate_position`])
+    pub fn warnings(&self) -> Vec<PositionError> {
+        validate_position(self.rules, &self.board).err().unwrap_or_default()
+    }
+
+    /// The edited position as a game, if it is a valid start
+    pub fn to_game(&self) -> Result<Game, String> {
+        let stones: Vec<(Pos, Stone)> = (0..crate::board::TOTAL_CELLS)
+            .map(Pos::from_index)
+            .map(|pos| (pos, self.board.get(pos)))
+            .filter(|&(_, stone)| stone != Stone::Empty)
+            .collect();
+        let captures = (self.board.captures(Stone::Black), self.board.captures(Stone::White));
+        Game::from_setup(self.rules, &stones, captures, self.to_move)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_brush_places_replaces_and_removes() {
+        let mut editor = SetupEditor::new(RuleSet::NINUKI, &Board::new(), Stone::Black);
+        let pos = Pos::new(9, 9);
+        editor.click(pos);
+        assert_eq!(editor.board.get(pos), Stone::Black);
+        editor.brush = Stone::White;
+        editor.click(pos);
+        assert_eq!(editor.board.get(pos), Stone::White);
+        editor.click(pos);
+        assert_eq!(editor.board.get(pos), Stone::Empty);
+
+        editor.click(pos);
+        editor.brush = Stone::Empty;
+        editor.click(pos);
+        assert_eq!(editor.board.stone_count(), 0);
+    }
+
+    #[test]
+    fn test_to_game_keeps_stones_captures_and_turn() {
+        let mut editor = SetupEditor::new(RuleSet::NINUKI, &Board::new(), Stone::White);
+        editor.click(Pos::new(9, 9));
+        editor.click(Pos::new(3, 15));
+        editor.board.set_captures(RuleSet::NINUKI, Stone::Black, 2).unwrap();
+        let game = editor.to_game().unwrap();
+        assert_eq!(game.board().stone_count(), 2);
+        assert_eq!(game.board().captures(Stone::Black), 2);
+        assert_eq!(game.to_move(), Stone::White);
+
+        assert_eq!(editor.warnings(), vec![PositionError::MoveCounts { black: 2, white: 4 }]);
+
+        editor.board.add_captures(Stone::White, 9);
+        assert!(editor.to_game().is_err());
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let mut editor = SetupEditor::new(RuleSet::NINUKI, &Board::new(), Stone::White);
+        editor.click(Pos::new(9, 9));
+        editor.brush = Stone::White;
+        editor.click(Pos::new(0, 18));
+        editor.board.set_captures(RuleSet::NINUKI, Stone::White, 3).unwrap();
+        let text = editor.to_text();
+
+        let mut copy = SetupEditor::new(RuleSet::NINUKI, &Board::new(), Stone::Black);
+        copy.load_text(&format!("  {}\n", text)).unwrap();
+        assert_eq!(copy.board.get(Pos::new(9, 9)), Stone::Black);
+        assert_eq!(copy.board.get(Pos::new(0, 18)), Stone::White);
+        assert_eq!(copy.board.captures(Stone::White), 3);
+        assert_eq!(copy.to_move, Stone::White);
+        assert_eq!(copy.to_text(), text);
+
+        assert!(copy.load_text("19/19 B 0 0").is_err());
+        assert!(copy.error.is_some());
+        assert_eq!(copy.to_text(), text);
+    }
+}
@@ -0,0 +1,119 @@
+//! Sound effects for the Gomoku GUI
+//!
+//! Effects are synthesized at startup (no audio files) and played through
+//! rodio with the `sound` feature. Without it, or when no output device can
+//! be opened, [`Sounds::play`] does nothing.
+
+#[cfg(feature = "sound")]
+use crate::logging::log_warn;
+
+/// Game events with a sound
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundEffect {
+    /// A stone was placed
+    Place,
+    /// A move captured one or more pairs
+    Capture,
+    /// The game was won
+    Win,
+}
+
+#[cfg(feature = "sound")]
+const SAMPLE_RATE: u32 = 44_100;
+
+/// Sound effect player; off until enabled
+#[derive(Default)]
+pub struct Sounds {
+    pub enabled: bool,
+    /// Output stream (must stay alive while sounds play) and its handle;
+    /// opened on first use
+    #[cfg(feature = "sound")]
+    output: Option<(rodio::OutputStream, rodio::OutputStreamHandle)>,
+    /// Set once opening the output failed, so it is not retried every move
+    #[cfg(feature = "sound")]
+    unavailable: bool,
+}
+
+impl Sounds {
+    /// Whether this build can play sounds at all
+    pub const AVAILABLE: bool = cfg!(feature = "sound");
+
+    /// Play `effect` without blocking (no-op when disabled or unavailable)
+    pub fn play(&mut self, effect: SoundEffect) {
+        if !self.enabled {
+            return;
+        }
+        #[cfg(feature = "sound")]
+        self.play_now(effect);
+        #[cfg(not(feature = "sound"))]
+        let _ = effect;
+    }
+
+    #[cfg(feature = "sound")]
+    fn play_now(&mut self, effect: SoundEffect) {
+        if self.output.is_none() && !self.unavailable {
+            match rodio::OutputStream::try_default() {
+                Ok(output) => self.output = Some(output),
+                Err(e) => {
+                    log_warn!("Sound disabled: {}", e);
+                    self.unavailable = true;
+                }
+            }
+        }
+        if let Some((_, handle)) = &self.output {
+            let buffer = rodio::buffer::SamplesBuffer::new(1, SAMPLE_RATE, synthesize(effect));
+            if let Err(e) = handle.play_raw(buffer) {
+                log_warn!("Sound playback failed: {}", e);
+            }
+        }
+    }
+}
+
+/// Mono samples for `effect`
+#[cfg(feature = "sound")]
+fn synthesize(effect: SoundEffect) -> Vec<f32> {
+    match effect {
+        // Short woody knock
+        SoundEffect::Place => tone(320.0, 0.06, 60.0, 0.5),
+        // Two quick knocks, then a falling note
+        SoundEffect::Capture => [
+            tone(420.0, 0.05, 70.0, 0.5),
+            tone(420.0, 0.05, 70.0, 0.5),
+            sweep(660.0, 330.0, 0.18, 0.35),
+        ]
+        .concat(),
+        // Rising major arpeggio
+        SoundEffect::Win => [
+            tone(523.25, 0.12, 12.0, 0.35),
+            tone(659.25, 0.12, 12.0, 0.35),
+            tone(783.99, 0.30, 6.0, 0.35),
+        ]
+        .concat(),
+    }
+}
+
+/// Sine at `freq` Hz for `secs`, decaying exponentially at `decay` per second
+#[cfg(feature = "sound")]
+fn tone(freq: f32, secs: f32, decay: f32, volume: f32) -> Vec<f32> {
+    let n = (secs * SAMPLE_RATE as f32) as usize;
+    (0..n)
+        .map(|i| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            (t * freq * std::f32::consts::TAU).sin() * (-t * decay).exp() * volume
+        })
+        .collect()
+}
+
+/// Sine gliding linearly from `from` to `to` Hz, fading out
+#[cfg(feature = "sound")]
+fn sweep(from: f32, to: f32, secs: f32, volume: f32) -> Vec<f32> {
+    let n = (secs * SAMPLE_RATE as f32) as usize;
+    let mut phase = 0.0f32;
+    (0..n)
+        .map(|i| {
+            let frac = i as f32 / n as f32;
+            phase += (from + (to - from) * frac) * std::f32::consts::TAU / SAMPLE_RATE as f32;
+            phase.sin() * (1.0 - frac) * volume
+        })
+        .collect()
+}
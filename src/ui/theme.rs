@@ -1,13 +1,17 @@
 //! Theme constants for the Gomoku GUI
+//!
+//! Panel colors are fixed; the board is drawn with a [`Theme`]: one of the
+//! built-in [`Palette`]s plus a [`StoneStyle`], switchable at runtime.
 
 use egui::Color32;
 
-// Board colors - warm wood tones
+// Board colors - warm wood tones (the Classic palette)
 pub const BOARD_BG: Color32 = Color32::from_rgb(222, 184, 135); // Burlywood
 #[allow(dead_code)]
 pub const BOARD_BORDER: Color32 = Color32::from_rgb(139, 90, 43);  // Saddle brown
 pub const GRID_LINE: Color32 = Color32::from_rgb(60, 40, 20);
 pub const STAR_POINT: Color32 = Color32::from_rgb(50, 35, 20);
+pub const BOARD_BACKDROP: Color32 = Color32::from_rgb(40, 42, 46);
 
 // Stone colors with better contrast
 pub const BLACK_STONE: Color32 = Color32::from_rgb(25, 25, 30);
@@ -17,7 +21,17 @@ pub const WHITE_STONE_SHADOW: Color32 = Color32::from_rgb(190, 190, 195);
 
 // Markers
 pub const LAST_MOVE_MARKER: Color32 = Color32::from_rgb(230, 60, 60);
+pub const FORBIDDEN_MARKER: Color32 = Color32::from_rgb(210, 40, 40);
 pub const WIN_HIGHLIGHT: Color32 = Color32::from_rgb(50, 220, 50);
+/// Engine hints by rank: best, second, third
+pub const HINT_COLORS: [Color32; 3] = [
+    Color32::from_rgb(40, 200, 90),
+    Color32::from_rgb(240, 170, 40),
+    Color32::from_rgb(70, 140, 220),
+];
+/// Candidate heatmap: last and first root move
+pub const HEAT_COLD: Color32 = Color32::from_rgb(60, 120, 230);
+pub const HEAT_HOT: Color32 = Color32::from_rgb(240, 50, 40);
 
 // Capture effect colors (used in board_view animation)
 #[allow(dead_code)]
@@ -86,3 +100,128 @@ pub const STAR_POINTS: [(u8, u8); 9] = [
     (9, 3), (9, 9), (9, 15),
     (15, 3), (15, 9), (15, 15),
 ];
+
+/// Colors used to draw the board and stones
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoardColors {
+    /// Board surface
+    pub board: Color32,
+    /// Area around the board
+    pub backdrop: Color32,
+    /// Grid lines and coordinates
+    pub grid: Color32,
+    pub star_point: Color32,
+    pub black_stone: Color32,
+    pub black_highlight: Color32,
+    pub white_stone: Color32,
+    pub white_shadow: Color32,
+}
+
+/// Built-in board palettes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Palette {
+    /// Light wood (the original look)
+    #[default]
+    Classic,
+    /// Dark walnut with high-contrast grid
+    Walnut,
+    /// Flat paper-white board for printing and screenshots
+    Paper,
+    /// Dark mode: dim slate board, low-glare stones
+    Night,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 4] = [Palette::Classic, Palette::Walnut, Palette::Paper, Palette::Night];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Palette::Classic => "Classic",
+            Palette::Walnut => "Walnut",
+            Palette::Paper => "Paper",
+            Palette::Night => "Night",
+        }
+    }
+
+    pub fn colors(self) -> BoardColors {
+        match self {
+            Palette::Classic => BoardColors {
+                board: BOARD_BG,
+                backdrop: BOARD_BACKDROP,
+                grid: GRID_LINE,
+                star_point: STAR_POINT,
+                black_stone: BLACK_STONE,
+                black_highlight: BLACK_STONE_HIGHLIGHT,
+                white_stone: WHITE_STONE,
+                white_shadow: WHITE_STONE_SHADOW,
+            },
+            Palette::Walnut => BoardColors {
+                board: Color32::from_rgb(150, 100, 60),
+                backdrop: Color32::from_rgb(36, 30, 26),
+                grid: Color32::from_rgb(35, 22, 12),
+                star_point: Color32::from_rgb(30, 18, 10),
+                black_stone: Color32::from_rgb(15, 15, 18),
+                black_highlight: Color32::from_rgb(75, 75, 85),
+                white_stone: Color32::from_rgb(246, 242, 232),
+                white_shadow: Color32::from_rgb(200, 192, 178),
+            },
+            Palette::Paper => BoardColors {
+                board: Color32::from_rgb(248, 246, 240),
+                backdrop: Color32::from_rgb(210, 210, 214),
+                grid: Color32::from_rgb(90, 90, 95),
+                star_point: Color32::from_rgb(70, 70, 75),
+                black_stone: Color32::from_rgb(20, 20, 22),
+                black_highlight: Color32::from_rgb(20, 20, 22),
+                white_stone: Color32::from_rgb(255, 255, 255),
+                white_shadow: Color32::from_rgb(40, 40, 45),
+            },
+            Palette::Night => BoardColors {
+                board: Color32::from_rgb(52, 58, 68),
+                backdrop: Color32::from_rgb(22, 24, 28),
+                grid: Color32::from_rgb(120, 130, 145),
+                star_point: Color32::from_rgb(140, 150, 165),
+                black_stone: Color32::from_rgb(12, 13, 16),
+                black_highlight: Color32::from_rgb(60, 66, 78),
+                white_stone: Color32::from_rgb(200, 204, 212),
+                white_shadow: Color32::from_rgb(150, 155, 165),
+            },
+        }
+    }
+}
+
+/// How stones are rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StoneStyle {
+    /// Shaded stones with a highlight (the original look)
+    #[default]
+    Glossy,
+    /// Solid disks with an outline
+    Flat,
+    /// Slate-and-shell texture: matte black, striped white
+    Shell,
+}
+
+impl StoneStyle {
+    pub const ALL: [StoneStyle; 3] = [StoneStyle::Glossy, StoneStyle::Flat, StoneStyle::Shell];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            StoneStyle::Glossy => "Glossy",
+            StoneStyle::Flat => "Flat",
+            StoneStyle::Shell => "Shell",
+        }
+    }
+}
+
+/// Board appearance selected in the View menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Theme {
+    pub palette: Palette,
+    pub stone_style: StoneStyle,
+}
+
+impl Theme {
+    pub fn colors(&self) -> BoardColors {
+        self.palette.colors()
+    }
+}
@@ -0,0 +1,259 @@
+//! WebAssembly bindings for browser front-ends (feature `wasm`)
+//!
+//! Exposes a single `GomokuEngine` class to JavaScript that owns a board and
+//! an [`AIEngine`]. Cells are addressed by 0-based `(row, col)`; colors are
+//! passed as numbers: `0` = empty, `1` = Black, `2` = White.
+//!
+//! Build with:
+//!
+//! ```text
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/gomoku.wasm
+//! ```
+//!
+//! ```text
+//! import init, { GomokuEngine } from "./pkg/gomoku.js";
+//! await init();
+//! const engine = new GomokuEngine(10, 500);
+//! engine.play(9, 9, 1);
+//! const reply = engine.getMove(2); // { row, col, score, depth, ... } or undefined
+//! ```
+//!
+//! Search runs on the calling thread (browsers have no OS threads), so call
+//! `getMove` from a Web Worker to keep the page responsive.
+
+use wasm_bindgen::prelude::*;
+
+use crate::board::{Board, Pos, Stone};
+use crate::engine::{AIEngine, SearchType};
+use crate::rules;
+
+/// Transposition table size for the browser build (MB).
+const WASM_TT_SIZE_MB: usize = 16;
+
+/// Best move returned by [`GomokuEngine::get_move`].
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct EngineMove {
+    pub row: u8,
+    pub col: u8,
+    /// Evaluation from the mover's perspective
+    pub score: i32,
+    /// Alpha-beta depth reached (0 for book/VCF/defense moves)
+    pub depth: i8,
+    /// Nodes searched
+    pub nodes: u32,
+    /// Thinking time in milliseconds
+    #[wasm_bindgen(js_name = timeMs)]
+    pub time_ms: u32,
+    /// `true` when the move is a proven win (immediate or VCF)
+    pub forced: bool,
+    /// `true` when the engine would rather resign (see `setResignThreshold`)
+    pub resign: bool,
+}
+
+/// Board plus AI engine, driven from JavaScript.
+#[wasm_bindgen]
+pub struct GomokuEngine {
+    engine: AIEngine,
+    board: Board,
+}
+
+#[wasm_bindgen]
+impl GomokuEngine {
+    /// Create an engine with the given search depth and time limit.
+    #[wasm_bindgen(constructor)]
+    pub fn new(max_depth: i8, time_limit_ms: u32) -> GomokuEngine {
+        GomokuEngine {
+            engine: AIEngine::with_config(WASM_TT_SIZE_MB, max_depth, u64::from(time_limit_ms)),
+            board: Board::new(),
+        }
+    }
+
+    /// Clear the board, captures and search caches.
+    pub fn reset(&mut self) {
+        self.board = Board::new();
+        self.engine.clear_cache();
+    }
+
+    /// Color at `(row, col)`: 0 = empty, 1 = Black, 2 = White.
+    #[wasm_bindgen(js_name = getStone)]
+    pub fn get_stone(&self, row: u8, col: u8) -> Result<u8, JsError> {
+        Ok(stone_to_u8(self.board.get(parse_pos(row, col)?)))
+    }
+
+    /// Put or remove a stone without applying any rules (position setup).
+    #[wasm_bindgen(js_name = setStone)]
+    pub fn set_stone(&mut self, row: u8, col: u8, color: u8) -> Result<(), JsError> {
+        let pos = parse_pos(row, col)?;
+        match parse_stone(color)? {
+            Stone::Empty => self.board.remove_stone(pos),
+            stone => {
+                self.board.remove_stone(pos);
+                self.board.place_stone(pos, stone);
+            }
+        }
+        Ok(())
+    }
+
+    /// Set captured pair counts (position setup); at most the capture win.
+    #[wasm_bindgen(js_name = setCaptures)]
+    pub fn set_captures(&mut self, black: u8, white: u8) -> Result<(), JsError> {
+        let mut board = self.board.clone();
+        board.set_captures(self.engine.rules(), Stone::Black, black)?;
+        board.set_captures(self.engine.rules(), Stone::White, white)?;
+        self.board = board;
+        Ok(())
+    }
+
+    /// Pairs captured by `color`.
+    pub fn captures(&self, color: u8) -> Result<u8, JsError> {
+        Ok(self.board.captures(parse_player(color)?))
+    }
+
+    /// Whether `color` may legally play at `(row, col)` (empty, no double-three).
+    #[wasm_bindgen(js_name = isValidMove)]
+    pub fn is_valid_move(&self, row: u8, col: u8, color: u8) -> Result<bool, JsError> {
+        Ok(rules::is_valid_move(self.engine.rules(), &self.board, parse_pos(row, col)?, parse_player(color)?))
+    }
+
+    /// All legal moves for `color` as a flat `[row, col, row, col, ...]`
+    /// array, in row-major order.
+    #[wasm_bindgen(js_name = legalMoves)]
+    pub fn legal_moves(&self, color: u8) -> Result<Vec<u8>, JsError> {
+        let moves = rules::legal_moves(self.engine.rules(), &self.board, parse_player(color)?);
+        Ok(moves.iter().flat_map(|p| [p.row, p.col]).collect())
+    }
+
+    /// Play a move with full rules. Returns the captured cells as a flat
+    /// `[row, col, row, col, ...]` array.
+    pub fn play(&mut self, row: u8, col: u8, color: u8) -> Result<Vec<u8>, JsError> {
+        let pos = parse_pos(row, col)?;
+        let stone = parse_player(color)?;
+        if !rules::is_valid_move(self.engine.rules(), &self.board, pos, stone) {
+            return Err(JsError::new("illegal move"));
+        }
+        self.board.place_stone(pos, stone);
+        let captured = rules::execute_captures(self.engine.rules(), &mut self.board, pos, stone);
+        Ok(captured.iter().flat_map(|p| [p.row, p.col]).collect())
+    }
+
+    /// Winner of the current position: 0 = none, 1 = Black, 2 = White.
+    pub fn winner(&self) -> u8 {
+        rules::check_winner(self.engine.rules(), &self.board).map_or(0, stone_to_u8)
+    }
+
+    /// Search the best move for `color`. Returns `undefined` if there is none.
+    #[wasm_bindgen(js_name = getMove)]
+    pub fn get_move(&mut self, color: u8) -> Result<Option<EngineMove>, JsError> {
+        let stone = parse_player(color)?;
+        let result = self.engine.get_move_with_stats(&self.board, stone);
+        Ok(result.best_move.map(|pos| EngineMove {
+            row: pos.row,
+            col: pos.col,
+            score: result.score,
+            depth: result.depth,
+            nodes: u32::try_from(result.nodes).unwrap_or(u32::MAX),
+            time_ms: u32::try_from(result.time_ms).unwrap_or(u32::MAX),
+            forced: matches!(result.search_type, SearchType::ImmediateWin | SearchType::VCF),
+            resign: self.engine.should_resign(&result),
+        }))
+    }
+
+    /// Static evaluation from `color`'s perspective (see [`AIEngine::evaluate_position`]).
+    pub fn evaluate(&self, color: u8) -> Result<i32, JsError> {
+        Ok(self.engine.evaluate_position(&self.board, parse_player(color)?))
+    }
+
+    /// Whether `color` accepts a draw offer in the current position.
+    #[wasm_bindgen(js_name = shouldAcceptDraw)]
+    pub fn should_accept_draw(&self, color: u8) -> Result<bool, JsError> {
+        Ok(self.engine.should_accept_draw(&self.board, parse_player(color)?))
+    }
+
+    /// Score at or below which `getMove` flags `resign`; `undefined` disables it.
+    #[wasm_bindgen(js_name = setResignThreshold)]
+    pub fn set_resign_threshold(&mut self, threshold: Option<i32>) {
+        self.engine.set_resign_threshold(threshold);
+    }
+
+    /// Set the search time limit in milliseconds.
+    #[wasm_bindgen(js_name = setTimeLimit)]
+    pub fn set_time_limit(&mut self, time_limit_ms: u32) {
+        self.engine.set_time_limit(u64::from(time_limit_ms));
+    }
+
+    /// Set the maximum search depth.
+    #[wasm_bindgen(js_name = setMaxDepth)]
+    pub fn set_max_depth(&mut self, depth: i8) {
+        self.engine.set_max_depth(depth);
+    }
+}
+
+fn parse_pos(row: u8, col: u8) -> Result<Pos, JsError> {
+    Ok(Pos::try_new(row, col)?)
+}
+
+fn parse_stone(color: u8) -> Result<Stone, JsError> {
+    match color {
+        0 => Ok(Stone::Empty),
+        1 => Ok(Stone::Black),
+        2 => Ok(Stone::White),
+        _ => Err(JsError::new("color must be 0, 1 or 2")),
+    }
+}
+
+fn parse_player(color: u8) -> Result<Stone, JsError> {
+    match parse_stone(color)? {
+        Stone::Empty => Err(JsError::new("color must be 1 (Black) or 2 (White)")),
+        stone => Ok(stone),
+    }
+}
+
+fn stone_to_u8(stone: Stone) -> u8 {
+    match stone {
+        Stone::Empty => 0,
+        Stone::Black => 1,
+        Stone::White => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_play_applies_rules_and_reports_captures() {
+        let mut engine = GomokuEngine::new(4, 200);
+        engine.play(9, 9, 1).unwrap();
+        engine.play(9, 10, 2).unwrap();
+        engine.play(5, 5, 1).unwrap();
+        engine.play(9, 11, 2).unwrap();
+
+        // Black closes X-O-O-X and captures the white pair
+        let captured = engine.play(9, 12, 1).unwrap();
+        let mut cells: Vec<_> = captured.chunks(2).map(|c| (c[0], c[1])).collect();
+        cells.sort_unstable();
+        assert_eq!(cells, vec![(9, 10), (9, 11)]);
+        assert_eq!(engine.captures(1).unwrap(), 1);
+        assert_eq!(engine.get_stone(9, 10).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_get_move_finds_immediate_win() {
+        let mut engine = GomokuEngine::new(4, 200);
+        for col in 5..9 {
+            engine.set_stone(9, col, 1).unwrap();
+        }
+        engine.set_stone(9, 4, 2).unwrap();
+
+        let mv = engine.get_move(1).unwrap().expect("engine should move");
+        assert_eq!((mv.row, mv.col), (9, 9));
+        assert!(mv.forced);
+
+        engine.play(mv.row, mv.col, 1).unwrap();
+        assert_eq!(engine.winner(), 1);
+        assert!(!engine.should_accept_draw(1).unwrap());
+        assert!(engine.should_accept_draw(2).unwrap());
+    }
+}